@@ -0,0 +1,93 @@
+//! Timings for the operations most commands are built from, over made-up
+//! trackers of different sizes. Run with `cargo bench`; compare against a
+//! saved baseline with `cargo bench -- --save-baseline main` and
+//! `cargo bench -- --baseline main`.
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use fintrack::utils::storage::{load_tracker, save_tracker};
+use fintrack::utils::synthetic;
+use fintrack::{GlobalContext, TrackerData, commands};
+use std::fs;
+use tempfile::TempDir;
+
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// A context whose tracker file holds `data`
+fn context_with(data: &TrackerData) -> (TempDir, GlobalContext) {
+    let dir = TempDir::new().unwrap();
+    let mut gctx = GlobalContext::new(dir.path().to_path_buf());
+    fs::create_dir_all(gctx.base_path()).unwrap();
+    // Measure writing the tracker, not the backup taken before each write
+    commands::exec_line(&mut gctx, "config set backup.enabled false").unwrap();
+    fs::write(gctx.tracker_path(), "").unwrap();
+    save_tracker(&mut gctx, data).unwrap();
+    (dir, gctx)
+}
+
+fn bench_load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("load");
+    for size in SIZES {
+        let (dir, _) = context_with(&synthetic::tracker(size));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            // A fresh context each time, so the parsed-tracker cache never helps
+            b.iter_batched(
+                || GlobalContext::new(dir.path().to_path_buf()),
+                |gctx| load_tracker(&gctx).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_filter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("filter");
+    for size in SIZES {
+        let (_dir, mut gctx) = context_with(&synthetic::tracker(size));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.iter(|| {
+                commands::exec_line(&mut gctx, "list -S 01-03-2022 -E 30-09-2023 -c expenses -s groceries")
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_totals(c: &mut Criterion) {
+    let mut group = c.benchmark_group("totals");
+    for size in SIZES {
+        let data = synthetic::tracker(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+            b.iter(|| data.net_balance())
+        });
+    }
+    group.finish();
+}
+
+fn bench_describe(c: &mut Criterion) {
+    let mut group = c.benchmark_group("describe");
+    for size in SIZES {
+        let (_dir, mut gctx) = context_with(&synthetic::tracker(size));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.iter(|| commands::exec_line(&mut gctx, "describe").unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_write(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write");
+    group.sample_size(20);
+    for size in SIZES {
+        let data = synthetic::tracker(size);
+        let (_dir, mut gctx) = context_with(&data);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+            b.iter(|| save_tracker(&mut gctx, data).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_load, bench_filter, bench_totals, bench_describe, bench_write);
+criterion_main!(benches);
@@ -0,0 +1,582 @@
+# English messages for fintrack. This is the reference file: every message
+# here should also exist in the other locales.
+
+## Markers and labels
+
+success = Success
+suggestion-label = Suggestion:
+validation-error-label = ValidationError:
+
+## Errors
+
+error-file-not-found = File not found: { $path }
+suggest-init = Run 'fintrack init' to initialize the tracker
+error-not-initialized = No tracker yet — looks like this is your first time using fintrack
+suggest-quick-start = Create one to get started:
+      fintrack init --currency USD --opening 1000
+      fintrack add income 4000 -s Wages
+      fintrack list
+error-read-only = Read-only mode is on, so this command can't change your tracker
+suggest-read-only = Run without --read-only, or turn it off with 'fintrack config unset general.read_only'
+error-checksum-mismatch = Your records don't match the checksum saved with them; tracker.json was changed outside fintrack or is incomplete
+suggest-checksum-mismatch = If you edited the file yourself, delete its "checksum" line to accept the changes. Otherwise copy the newest file from ~/.fintrack/backups over tracker.json
+error-invalid-field = tracker.json has a value that doesn't fit at { $path }: { $message }
+suggest-fix-field = Change { $path } in tracker.json to { $expected } and delete the "checksum" line, or copy the newest file from ~/.fintrack/backups over tracker.json
+suggest-check-field = Fix { $path } in tracker.json and delete the "checksum" line, or copy the newest file from ~/.fintrack/backups over tracker.json
+error-invalid-json = Invalid JSON: { $message }
+suggest-restore-backup = Your tracker data may be corrupted. Try restoring from backup
+error-permission-denied = Permission denied: { $path }
+suggest-check-permissions = Check file permissions or run with appropriate access
+error-corrupted-restored = Data was corrupted but restored from backup ({ $timestamp })
+suggest-verify-changes = Please verify your recent changes
+error-corrupted-unrecoverable = Data corruption detected and backup restoration failed
+suggest-inspect-or-reset = Run 'fintrack dump' to inspect remaining data, or 'fintrack clear' to reset
+error-already-initialized = Tracker already initialized
+suggest-clear = Use 'fintrack clear' to start over
+error-amount-too-small = Amount must be greater than 0, got: { $amount }
+suggest-positive-amount = Re-run the command with a positive amount (e.g., --amount 500)
+error-invalid-date = Invalid date format: '{ $provided }'
+suggest-date-format = Expected format: { $format }
+error-impossible-date = '{ $provided }' is not a real date
+suggest-nearest-date = Check the day and month; the nearest real date is { $nearest }
+error-subcategory-not-found = Subcategory '{ $name }' not found
+suggest-subcategory-list = Use 'fintrack subcategory list' to see available subcategories
+suggest-subcategory-nearest = Did you mean '{ $nearest }'?
+
+error-subcategory-exists = Subcategory '{ $name }' already exists
+suggest-different-name = Use a different name or check existing subcategories
+error-record-not-found = Record with ID { $id } not found
+suggest-list = Use 'fintrack list' to see available records
+error-subcategory-has-records = Cannot delete '{ $name }' — it has { $count ->
+        [one] { $count } record
+       *[other] { $count } records
+    }
+suggest-delete-by-subcat = Delete those records first using 'fintrack delete --by-subcat { $name }', or manually delete individual records
+error-cannot-delete-misc = Cannot delete 'Miscellaneous' — it is a system subcategory
+error-category-immutable = Categories are fixed, so '{ $category }' can't be added, deleted or renamed: there are only Income and Expenses
+suggest-category-immutable = Subcategories can be added, renamed and deleted instead; see 'fintrack subcategory --help'
+error-invalid-category-name = Invalid category name '{ $name }': { $reason }
+error-invalid-name = Invalid name '{ $name }': { $reason }
+error-invalid-amount = Invalid amount: { $reason }
+error-unknown-subcommand = Unknown subcommand: '{ $subcommand }'
+suggest-help = Use 'fintrack --help' to see available commands
+error-unknown-config-key = Unknown setting: '{ $key }'
+suggest-config-list = Use 'fintrack config list' to see available settings
+error-invalid-config-value = Invalid value '{ $value }' for { $key }: { $reason }
+error-strict-check-failed = Strict mode rejected this record: { $reason }
+suggest-strict = Fix the record, or run without --strict (and with 'validation.strict' off) to accept it
+error-duplicate-record = Record { $id } already has the same date, amount, subcategory and description
+suggest-duplicate-record = Change the description if this really is a second transaction, or turn off 'validation.reject_exact_duplicates'
+error-nothing-owed = Nothing is outstanding with '{ $person }'
+suggest-owed-list = Use 'fintrack owed list' to see open loans
+error-bill-not-found = No bill named '{ $name }'
+suggest-bills = Use 'fintrack bills' to see your bills
+error-project-not-found = No records belong to a project named '{ $name }'
+suggest-project-list = Use 'fintrack project list' to see your projects
+error-schedule-not-found = No schedule with ID { $id }
+suggest-schedule-list = Use 'fintrack schedule list' to see schedule IDs
+error-budget-not-found = '{ $subcategory }' has no budget
+suggest-budget-list = Use 'fintrack budget list' to see your budgets
+error-version-not-found = Record { $id } has no version { $version }
+suggest-history = Use 'fintrack history { $id }' to see its versions
+error-subscription-not-found = No subscription named '{ $name }'
+suggest-subscriptions = Use 'fintrack subscriptions list' to see your subscriptions
+error-token-not-found = No token named '{ $name }'
+suggest-token-list = Use 'fintrack serve token list' to see your tokens
+error-tag-not-found = No tag named '{ $name }'
+suggest-tag-list = Use 'fintrack tag list' to see your tags
+error-tag-in-use = '{ $name }' is still on { $count ->
+        [one] { $count } record
+       *[other] { $count } records
+    }
+suggest-tag-from-records = Add --from-records to take it off them too, or merge it into another tag with 'fintrack tag rename'
+error-period-closed = { $month } is closed, so its records can't be added, changed or deleted
+suggest-reopen = Reopen it first with 'fintrack close --month { $month } --reopen'
+
+## Records
+
+record-created = Record created:
+record-updated = Record updated:
+no-records = No records found.
+search-no-matches = No records match "{ $query }".
+no-description = (no description)
+unknown = Unknown
+category-fallback = Category { $id }
+subcategory-fallback = Subcategory { $id }
+new-balance = Balance:
+records-deleted = { $count ->
+        [one] Deleted 1 record
+       *[other] Deleted { $count } records
+    }
+
+column-id = ID
+column-category = Category
+column-subcategory = Subcategory
+column-tag = Tag
+column-amount = Amount
+column-date = Date
+column-description = Description
+column-income = Income
+column-expenses = Expenses
+column-net = Net
+column-budget = Budget
+column-spent = Spent
+column-remaining = Remaining
+column-carried = Carried
+column-version = Version
+column-since = Since
+
+## Totals and listings
+
+summary-title = Financial Summary:
+summary-period = Period:
+summary-as-of = As of:
+summary-opening = Opening Balance:
+summary-income = Total Income:
+summary-expenses = Total Expenses:
+summary-investments = Investments:
+summary-net = Net Balance:
+summary-budget-remaining = Budget remaining this month:
+summary-round-up = Round-up savings:
+categories-title = Categories:
+subcategories-title = Subcategories:
+tags-title = Tags:
+tags-none = No tags yet; tag a record with 'fintrack add --tag <name>'
+tag-records = { $count ->
+        [one] { $count } record
+       *[other] { $count } records
+    }
+
+## Describe
+
+describe-title = Financial Overview:
+describe-total-records = Total Records:
+describe-date-range = Date Range:
+describe-range = { $start } to { $end }
+describe-by-category = By Category:
+describe-category-chart = Category Spending Chart:
+describe-by-subcategory = By Subcategory (Top 5):
+describe-subcategory-chart = Top Subcategories Chart:
+describe-group = { $name }: { $count ->
+        [one] { $count_text } record
+       *[other] { $count_text } records
+    } | { $total } { $currency }
+describe-by-user = By Person:
+describe-by-tag = By Tag:
+describe-by-weekday = Expenses by Day of Week:
+describe-busiest = ◀ most spending
+describe-weekdays = Weekdays:
+describe-weekend = Weekend:
+describe-share = { $amount } ({ $percent }%)
+weekday-monday = Monday
+weekday-tuesday = Tuesday
+weekday-wednesday = Wednesday
+weekday-thursday = Thursday
+weekday-friday = Friday
+weekday-saturday = Saturday
+weekday-sunday = Sunday
+describe-average = Average Transaction:
+currency-title = By Currency:
+currency-row = { $currency }: { $count ->
+        [one] { $count_text } record
+       *[other] { $count_text } records
+    }, income { $income }, expenses { $expenses }
+currency-converted = = income { $income }, expenses { $expenses } in { $base }
+currency-rate = 1 { $currency } = { $rate } { $base }, rate of { $date } ({ $count ->
+        [one] 1 record
+       *[other] { $count } records
+    })
+currency-rate-range = 1 { $currency } = { $rate } { $base }, rates of { $from } to { $to } ({ $count ->
+        [one] 1 record
+       *[other] { $count } records
+    })
+
+
+## Report
+
+report-title = Monthly Report: { $month }
+report-title-year = Annual Report: { $month }
+report-title-week = Weekly Report: { $month }
+report-records = Records:
+report-opening = Opening Balance:
+report-income = Income:
+report-expenses = Expenses:
+report-net = Net for the Period:
+report-closing = Closing Balance:
+report-closed = This month is closed and matches its saved balance.
+report-snapshot-mismatch = Warning: this month was closed at { $snapshot }; the records no longer add up to it.
+report-income-by-subcategory = Income by Subcategory:
+report-expenses-by-subcategory = Expenses by Subcategory:
+report-empty = No records in this period.
+deductions-title = Tax Deductions: { $year }
+deductions-empty = No deductible expenses this year.
+deductions-total = Total Deductible:
+digest-title = Week of { $start } to { $end }
+digest-spent = Spent { $amount }
+digest-up = up { $percent }% on last week ({ $amount })
+digest-down = down { $percent }% on last week ({ $amount })
+digest-same = the same as last week
+digest-first = nothing spent last week
+digest-top = Top: { $subcategories }
+digest-unusual = Unusual: #{ $id } { $description } { $amount } in { $subcategory } (usually { $typical })
+reconcile-title = Reconciliation for { $date }
+reconcile-recorded = Recorded balance:
+reconcile-statement = Statement balance:
+reconcile-drift = Drift:
+reconcile-balanced = The records match the statement.
+reconcile-adjusted = Added adjustment record { $id }.
+reconcile-hint = Look for missing or mistyped records, or run again with --adjust to record the difference.
+dry-run-title = Dry run: nothing was saved. These changes would be made:
+dry-run-unchanged = Dry run: nothing would change.
+diff-other-changes = Budgets, bills or other tracker data differ too.
+diff-income = Income:
+diff-expenses = Expenses:
+diff-balance = Balance:
+diff-title = Changes since { $source }:
+diff-restore-title = Restoring { $source } would make these changes:
+diff-unchanged = The tracker is the same as { $source }.
+diff-subcategory = subcategory { $name }
+diff-subcategory-renamed = subcategory { $old } → { $new }
+
+
+projects-title = Projects
+projects-none = No projects yet. Tag an expense with 'fintrack add expenses 300 --project "Lagos trip"'.
+project-title = Project: { $name }
+project-dates = Dates:
+project-spent = Total Spent:
+owed-title = Money Owed
+owed-none = Nobody owes anything.
+owed-owes-you = owes you
+owed-you-owe = you owe
+owed-due = due { $date }
+owed-overdue = OVERDUE
+owed-net = Net owed to you:
+bills-title = Bills
+bills-none = No bills yet. Add one with 'fintrack bill add'.
+bills-due-in = { $days ->
+        [one] in 1 day
+       *[other] in { $days } days
+    }
+bills-due-today = DUE TODAY
+bills-overdue = { $days ->
+        [one] OVERDUE by 1 day
+       *[other] OVERDUE by { $days } days
+    }
+bills-total = Total per cycle:
+subscriptions-title = Subscriptions
+subscriptions-none = No subscriptions yet. Add one with 'fintrack subscriptions add'.
+subscriptions-every = { $every ->
+        [day] a day
+        [week] a week
+        [year] a year
+       *[other] a month
+    }
+subscriptions-renews = renews { $date }
+subscriptions-renews-today = RENEWS TODAY
+subscriptions-payments = { $count ->
+        [0] no payments linked
+        [one] 1 payment linked
+       *[other] { $count } payments linked
+    }
+subscriptions-cancel-by = cancel by { $date }
+subscriptions-cancel-passed = cancel-by date { $date } has passed
+subscriptions-total = Total per month:
+tokens-title = Tokens for 'fintrack serve'
+tokens-none = No tokens, so 'fintrack serve' is open to anyone who can reach it. Create one with 'fintrack serve token create'.
+tokens-created = created { $date }
+merge-title = Merged { $source }
+merge-title-dry-run = Merging { $source } would make these changes:
+merge-records = Records added: { $count }
+merge-duplicates = Duplicates skipped: { $count }
+merge-subcategories = New subcategories:
+merge-budgets-added = New budgets:
+merge-budgets-replaced = Budgets replaced:
+merge-dry-run = Nothing was changed (dry run).
+budgets-title = Budgets for { $month }
+budgets-none = No budgets yet. Set one with 'fintrack budget set'.
+budgets-spent = { $spent } of { $budget } spent
+budgets-carried = ({ $amount } carried over)
+budgets-left = { $amount } left
+budgets-over = { $amount } over
+history-title = Versions of record { $id }
+history-current = (current)
+history-reverted = (back to { $version })
+status-title = Status for { $date }
+status-balance = Balance:
+status-spent-today = Spent today:
+status-limit-daily = Daily limit:
+status-limit-weekly = Weekly limit:
+status-over-budget = ({ $count ->
+        [one] 1 budget over
+       *[other] { $count } budgets over
+    })
+status-short-today = today { $amount }
+status-short-budget = budget { $amount } left
+status-short-daily-over = over daily limit
+status-short-weekly-over = over weekly limit
+status-short-over = { $count ->
+        [one] 1 budget over
+       *[other] { $count } budgets over
+    }
+status-payday = Payday:
+status-payday-in = { $days ->
+        [0] today
+        [one] tomorrow, { $date }
+       *[other] in { $days } days, { $date }
+    }
+status-per-day = Per day until then:
+status-short-payday = { $days ->
+        [0] payday today
+       *[other] payday in { $days }d
+    }
+schedules-title = Scheduled Records
+schedules-none = Nothing scheduled. Add something with 'fintrack schedule add'.
+schedules-next = next { $date }
+frequency-day = every day
+frequency-week = every week
+frequency-month = every month
+frequency-year = every year
+holdings-title = Investments ({ $currency })
+holdings-none = No investments yet. Add one with 'fintrack invest add'.
+holdings-symbol = Symbol
+holdings-quantity = Quantity
+holdings-cost = Cost
+holdings-price = Price
+holdings-value = Value
+holdings-gain = Gain
+holdings-total-value = Total Value:
+holdings-total-gain = Unrealized Gain:
+
+## Config
+
+settings-title = Settings:
+config-not-set = (not set)
+config-set = { $key } set to '{ $value }'
+config-was-not-set = { $key } was not set
+config-unset = { $key } reset to its default
+config-exported = Settings exported to: { $path }
+config-imported = Settings imported from: { $path }
+config-dry-run-set = Dry run: nothing was written. { $key } would be set to '{ $value }'
+config-dry-run-unset = Dry run: nothing was written. { $key } would be reset to its default
+config-dry-run-import = Dry run: nothing was written. Settings would be imported from { $path }
+
+## Command results
+
+edit-resumed = Carried on with the edit that wasn't saved last time; use --fresh to start over from the tracker
+edit-no-changes = No changes made; the tracker was left as it was
+edit-saved = Tracker replaced with your edit ({ $count } record(s))
+batch-ran = Ran { $count } command(s) from { $file }
+batch-stopped = Stopped at line { $line } ({ $command }); no changes were saved
+watch-header = fintrack watch · { $view } · Ctrl-C to stop
+dump-written = Tracker data written to: { $path }
+schema-written = Schema written to: { $path }
+export-done = Data exported to: { $path }
+digest-sent = Digest sent to { $channels }
+notify-alerts-sent = { $count ->
+        [0] Nothing to alert about
+       *[other] Sent { $count } alert(s) to { $channels }
+    }
+notify-summary-sent = Summary sent to { $channels }
+report-sent = Report for { $month } sent to { $recipients }
+owed-balance-owes-you = { $person } owes you { $amount } { $currency }
+owed-balance-you-owe = You owe { $person } { $amount } { $currency }
+owed-balance-settled = You and { $person } are settled up
+init-dry-run-exists = Dry run: { $path } already exists, so init would fail
+init-dry-run = Dry run: nothing was written. A { $currency } tracker would be created at { $path }
+bill-added = Added { $name }, next due { $due }
+bill-paid = Paid { $name } ({ $amount } { $currency }), next due { $due }
+bill-deleted = Deleted bill { $name }
+history-current-version = Record { $id } is already at version { $version }
+subcategory-added = Subcategory '{ $name }' added (ID: { $id })
+subcategory-renamed = Subcategory renamed: '{ $old }' → '{ $new }'
+subcategory-deleted = Subcategory '{ $name }' deleted
+token-created = Created token '{ $name }'. Copy it now, it won't be shown again:
+
+    {"  "}{ $token }
+
+    Send it as 'Authorization: Bearer { $token }'.
+token-revoked = Revoked token '{ $name }'
+token-dry-run-create = Dry run: nothing was written. A token named '{ $name }' would be created
+token-dry-run-revoke = Dry run: nothing was written. Token '{ $name }' would be revoked
+serve-listening = Serving metrics at { $url } (Ctrl-C to stop)
+plan-exported = Plan exported to: { $path }
+plan-imported = Plan imported from { $path } (new subcategories: { $subcategories }, budgets set: { $budgets }, schedules added: { $schedules })
+budget-set = Budget for { $name } set to { $amount } { $currency } a month{ $rollover ->
+        [true] , rolling over
+       *[other] {""}
+    }
+budget-deleted = Removed the budget for { $name }
+invest-bought = Bought { $quantity } { $symbol } at { $price } { $currency } each
+close-not-closed = { $month } is not closed
+close-reopened = Reopened { $month }
+close-already-closed = { $month } is already closed
+close-closed = Closed { $month } with a balance of { $balance } { $currency }
+schedule-added = Scheduled #{ $id }, first due { $due }
+schedule-deleted = Deleted schedule #{ $id }
+schedule-ran = { $count ->
+        [0] No scheduled records are due
+        [one] Added 1 scheduled record
+       *[other] Added { $count } scheduled records
+    }
+tag-renamed = Tag renamed: '{ $old }' → '{ $new }' on { $count } record(s)
+tag-merged = Tag '{ $old }' merged into '{ $new }' on { $count } record(s)
+tag-deleted = { $count ->
+        [0] Tag '{ $name }' deleted
+       *[other] Tag '{ $name }' deleted and taken off { $count } record(s)
+    }
+bench-generated = Generated { $count } record(s) in { $path }
+subscription-added = Added { $name }, renews { $renews }{ $linked ->
+        [0] {""}
+        [one] , linked 1 earlier payment
+       *[other] , linked { $linked } earlier payments
+    }
+subscription-deleted = Deleted subscription { $name }
+import-done = Imported { $count } record(s) from { $path } (new subcategories: { $subcategories })
+import-skipped = Skipped { $count } row(s) that couldn't be imported, starting with { $first }
+chart-balance-written = Wrote the balance from { $from } to { $to } to { $path }
+clear-cancelled = Clear cancelled.
+clear-archived = A copy was saved to { $path }.
+clear-records-removed = Removed { $count } record(s); subcategories were kept.
+clear-dry-run = Dry run: nothing was removed. { $path } would be removed, with { $count } record(s)
+clear-file-removed = Removed { $path }.
+clear-done = All data cleared.
+clear-start-over = Run 'fintrack init' to start over.
+tui-done = Done
+tui-record-created = Record { $id } created
+tui-record-updated = Record { $id } updated
+tui-deleted = Deleted { $count } record(s)
+
+## Warnings
+
+add-suggest-subcategory = This looks like { $name }; file it there with 'fintrack update { $id } -s "{ $name }"'
+plan-currency-mismatch = The plan's amounts are in { $plan } but this tracker is in { $tracker }; they were imported as they are
+limit-daily-over = Spent { $spent } { $currency } on { $date }, over your daily limit of { $limit } { $currency }
+limit-weekly-over = Spent { $spent } { $currency } in the week of { $week }, over your weekly limit of { $limit } { $currency }
+budget-threshold-reached = The { $name } budget for { $month } is { $percent }% spent ({ $spent } of { $available } { $currency })
+budget-over = Over the { $name } budget for { $month }: { $spent } of { $available } { $currency } spent
+serve-unprotected = Warning: anyone who can reach { $address } can read your metrics. Create a token with 'fintrack serve token create'.
+
+## Prompts
+
+add-suggest-prompt = Looks like { $name }. File it there? (Y/n):
+edit-problems = The edited tracker has problems:
+edit-again-prompt = Edit it again? (Y/n):
+
+## Command errors
+
+error-argument-missing = Required argument '{ $name }' not provided
+error-io = IO error: { $reason }
+error-not-interactive = '{ $command }' is not available in interactive mode
+error-empty-name = Invalid name
+error-file-exists = { $path } already exists. Use --force to overwrite it
+error-read-file = Could not read { $path }: { $reason }
+error-path-missing = Path does not exist: { $path }
+error-path-not-directory = Path is not a directory: { $path }
+error-tracker-currency = Invalid currency in tracker data: { $reason }
+error-schema-compile = The tracker schema couldn't be compiled: { $reason }
+error-offline = { $what } needs the network, which --offline rules out
+error-service-status = { $service } responded with status { $status }
+error-service-unreachable = Could not reach { $service }: { $reason }
+error-server-status = server responded with status { $status }
+error-config-invalid = Invalid config file { $path }: { $reason }
+error-config-write = Failed to write config: { $reason }
+error-no-editor = No editor given
+error-editor-start = Couldn't start { $editor }: { $reason }
+error-editor-failed = { $editor } exited with { $status }; the tracker was left as it was
+error-edit-not-saved = The edited tracker wasn't saved: { $problems }. Your edit is kept in { $path }
+edit-field-invalid = { $path } doesn't fit ({ $message })
+edit-json-invalid = it isn't valid JSON ({ $message })
+edit-unreadable = it can't be read ({ $kind })
+error-merge-currency = { $path } is in { $other } but this tracker is in { $currency }, so their records can't be added together
+error-close-not-ended = { $month } hasn't ended yet; only past months can be closed
+error-pdf-export = PDF export not yet implemented
+error-no-export-dir = No export directory given. Pass one, or set a default with 'fintrack config set export.dir <dir>'
+error-no-backups = There are no backups yet
+error-diff-not-found = There's no file or backup called '{ $file }'; 'latest' picks the newest backup
+error-delete-unfiltered = Say which records to delete with --ids or at least one filter, such as --subcategory
+error-bench-exists = { $path } already exists; pass another file name
+error-watch-unsupported = '{ $command }' can't be watched. Use one of: { $views }
+error-watch-failed = Failed to watch the tracker: { $reason }
+error-import-line = { $path } line { $line }: { $reason }; nothing was imported
+import-row = line { $line }: { $reason }
+import-no-column = the header has no '{ $column }' column
+import-not-amount = '{ $amount }' is not an amount
+import-category-empty = the category is empty
+import-too-many-decimals = { $amount } has too many decimal places
+import-amount-not-positive = the amount must be more than 0
+import-currency-mismatch = the amount is in { $currency } but the tracker is in { $tracker }
+import-not-date = '{ $date }' is not a date
+import-closed-month = { $date } is in a closed month
+import-bad-subcategory = '{ $name }' can't be used as a subcategory name
+import-no-subcategory-nearest = there's no subcategory '{ $name }' (did you mean '{ $nearest }'?)
+import-no-subcategory = there's no subcategory '{ $name }'; add --create to create it
+error-chart-format = Can't tell what format to write { $path } in. End it in .svg or .png
+error-chart-empty = Nothing to chart yet: there are no records in that range
+error-chart-png = Could not draw the chart as PNG: { $reason }
+error-serve-listen = Could not listen on { $address }: { $reason }
+error-budget-thresholds = The warning threshold ({ $warn }%) must be below the error threshold ({ $error }%)
+error-plan-invalid = Invalid plan file { $path }: { $reason }
+error-plan-version = { $path } is a version { $version } plan; this fintrack reads up to version { $supported }
+error-plan-write = Failed to write plan: { $reason }
+error-no-notify-channel = No notification channel set up. Set 'notify.ntfy_topic', 'notify.desktop', or 'notify.telegram_token' and 'notify.telegram_chat_id', with 'fintrack config set'
+error-desktop-notification = Failed to show a desktop notification: { $reason }
+error-notify-send = Failed to send to { $channel }: { $reason }
+error-email-not-set-up = Email is not set up. Set 'email.smtp_host', 'email.from' and 'email.to' with 'fintrack config set'
+error-email-build = Failed to build email: { $reason }
+error-email-send = Failed to send email: { $reason }
+error-email-address = Invalid email address '{ $address }': { $reason }
+error-smtp-host = Invalid SMTP host '{ $host }': { $reason }
+error-gsheet-no-spreadsheet = No spreadsheet set. Set one with 'fintrack config set gsheet.spreadsheet_id <id>'
+error-gsheet-no-token = No Google access token. Set { $variable } (e.g. from 'gcloud auth print-access-token') or 'gsheet.access_token'
+error-gsheet-status = Google Sheets responded with status { $status }: { $detail }
+error-gsheet-unreachable = Could not reach Google Sheets: { $reason }
+error-hook-failed = The { $hook } hook failed ({ $status })
+error-hook-not-run = The { $hook } hook could not be run: { $reason }
+error-token-generate = Could not generate a token: { $reason }
+error-no-price-service = No price service set up; set 'invest.price_url' or give prices with --price
+error-price-status = Price service responded with status { $status } for { $symbol }
+error-price-unreachable = Could not reach the price service: { $reason }
+error-price-missing = No price at '{ $field }' in the response for { $symbol }
+error-no-rate = No rate for { $currency }; give one with --rate, add it to 'rates.manual' or set 'rates.provider'
+error-rates-access-key = No access key for exchangerate.host; set 'rates.access_key'
+error-rates-refused = the request was refused
+error-rates-provider = { $provider }: { $reason }
+error-rates-no-result = { $provider } sent no { $from } to { $to } rate
+error-ecb-no-rates = The ECB has no rates for { $date }
+error-ecb-no-currency = The ECB doesn't publish a rate for { $currency }
+error-amount-range = --min-amount { $min } is more than --max-amount { $max }
+error-where = --where "{ $expression }": { $reason }
+where-unsupported-op = '{ $op }' can't be used with '{ $field }'
+where-already-set = { $field } is already '{ $value }'
+where-category-set = the category is already set
+where-subcategory-set = the subcategory is already set
+where-deductible = only 'deductible = true' is supported
+where-use-contains = use '{ $field } ~ TEXT' to match part of the description
+where-unknown-field = there's no '{ $field }'; conditions can be on category, subcategory, user, project, tag, deductible, description, amount or date
+reason-split-not-expense = only expenses can be split
+reason-split-over-total = the shares add up to { $shares }, more than the { $amount } { $currency } spent
+reason-rate-not-positive = the rate for { $currency } must be more than 0
+reason-name-empty = the name is empty
+reason-bill-exists = a bill with this name already exists
+reason-token-exists = a token with this name already exists
+reason-subscription-exists = a subscription with this name already exists
+
+## Alerts
+
+alert-budget-title = Over budget: { $name }
+alert-budget-body = Spent { $spent } of { $budget } this month, { $over } over
+alert-bill-overdue-title = Bill overdue: { $name }
+alert-bill-due-title = Bill due: { $name }
+alert-bill-overdue = { $amount } was due { $due }, { $days ->
+        [one] 1 day
+       *[other] { $days } days
+    } ago
+alert-bill-due = { $amount } { $days ->
+        [0] is due today
+        [one] is due { $due }, in 1 day
+       *[other] is due { $due }, in { $days } days
+    }
+alert-daily-limit-title = Over your daily limit
+alert-daily-limit-body = Spent { $spent } today, against a limit of { $limit }
+alert-weekly-limit-title = Over your weekly limit
+alert-weekly-limit-body = Spent { $spent } this week, against a limit of { $limit }
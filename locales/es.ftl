@@ -0,0 +1,581 @@
+# Mensajes en español para fintrack
+
+## Markers and labels
+
+success = Hecho
+suggestion-label = Sugerencia:
+validation-error-label = Error de validación:
+
+## Errors
+
+error-file-not-found = Archivo no encontrado: { $path }
+suggest-init = Ejecuta 'fintrack init' para inicializar el registro
+error-not-initialized = Aún no hay ningún registro — parece que es la primera vez que usas fintrack
+suggest-quick-start = Crea uno para empezar:
+      fintrack init --currency EUR --opening 1000
+      fintrack add income 4000 -s Salario
+      fintrack list
+error-read-only = El modo de solo lectura está activado, así que este comando no puede cambiar tus datos
+suggest-read-only = Ejecuta sin --read-only, o desactívalo con 'fintrack config unset general.read_only'
+error-checksum-mismatch = Tus registros no coinciden con la suma de comprobación guardada; tracker.json se modificó fuera de fintrack o está incompleto
+suggest-checksum-mismatch = Si editaste el archivo tú mismo, borra su línea "checksum" para aceptar los cambios. Si no, copia el archivo más reciente de ~/.fintrack/backups sobre tracker.json
+error-invalid-field = tracker.json tiene un valor no válido en { $path }: { $message }
+suggest-fix-field = Cambia { $path } en tracker.json a { $expected } y borra la línea "checksum", o copia el archivo más reciente de ~/.fintrack/backups sobre tracker.json
+suggest-check-field = Corrige { $path } en tracker.json y borra la línea "checksum", o copia el archivo más reciente de ~/.fintrack/backups sobre tracker.json
+error-invalid-json = JSON no válido: { $message }
+suggest-restore-backup = Es posible que tus datos estén dañados. Prueba a restaurar una copia de seguridad
+error-permission-denied = Permiso denegado: { $path }
+suggest-check-permissions = Revisa los permisos del archivo o ejecuta con los permisos adecuados
+error-corrupted-restored = Los datos estaban dañados pero se restauraron desde una copia de seguridad ({ $timestamp })
+suggest-verify-changes = Revisa tus cambios recientes
+error-corrupted-unrecoverable = Se detectaron datos dañados y no se pudo restaurar la copia de seguridad
+suggest-inspect-or-reset = Ejecuta 'fintrack dump' para revisar los datos restantes, o 'fintrack clear' para empezar de cero
+error-already-initialized = El registro ya está inicializado
+suggest-clear = Usa 'fintrack clear' para empezar de nuevo
+error-amount-too-small = El importe debe ser mayor que 0, se recibió: { $amount }
+suggest-positive-amount = Vuelve a ejecutar el comando con un importe positivo (p. ej., --amount 500)
+error-invalid-date = Formato de fecha no válido: '{ $provided }'
+suggest-date-format = Formato esperado: { $format }
+error-impossible-date = '{ $provided }' no es una fecha real
+suggest-nearest-date = Revisa el día y el mes; la fecha real más cercana es { $nearest }
+error-subcategory-not-found = No se encontró la subcategoría '{ $name }'
+suggest-subcategory-list = Usa 'fintrack subcategory list' para ver las subcategorías disponibles
+suggest-subcategory-nearest = ¿Quisiste decir '{ $nearest }'?
+
+error-subcategory-exists = La subcategoría '{ $name }' ya existe
+suggest-different-name = Usa otro nombre o revisa las subcategorías existentes
+error-record-not-found = No se encontró el registro con ID { $id }
+suggest-list = Usa 'fintrack list' para ver los registros disponibles
+error-subcategory-has-records = No se puede eliminar '{ $name }' — tiene { $count ->
+        [one] { $count } registro
+       *[other] { $count } registros
+    }
+suggest-delete-by-subcat = Elimina primero esos registros con 'fintrack delete --by-subcat { $name }', o uno por uno
+error-cannot-delete-misc = No se puede eliminar 'Miscellaneous' — es una subcategoría del sistema
+error-category-immutable = Las categorías son fijas, así que '{ $category }' no se puede añadir, eliminar ni renombrar: solo existen Income y Expenses
+suggest-category-immutable = Las subcategorías sí se pueden añadir, renombrar y eliminar; consulta 'fintrack subcategory --help'
+error-invalid-category-name = Nombre de categoría no válido '{ $name }': { $reason }
+error-invalid-name = Nombre no válido '{ $name }': { $reason }
+error-invalid-amount = Importe no válido: { $reason }
+error-unknown-subcommand = Subcomando desconocido: '{ $subcommand }'
+suggest-help = Usa 'fintrack --help' para ver los comandos disponibles
+error-unknown-config-key = Ajuste desconocido: '{ $key }'
+suggest-config-list = Usa 'fintrack config list' para ver los ajustes disponibles
+error-invalid-config-value = Valor '{ $value }' no válido para { $key }: { $reason }
+error-strict-check-failed = El modo estricto rechazó este registro: { $reason }
+suggest-strict = Corrige el registro, o ejecuta sin --strict (y con 'validation.strict' desactivado) para aceptarlo
+error-duplicate-record = El registro { $id } ya tiene la misma fecha, importe, subcategoría y descripción
+suggest-duplicate-record = Cambia la descripción si de verdad es una segunda transacción, o desactiva 'validation.reject_exact_duplicates'
+error-nothing-owed = No hay nada pendiente con '{ $person }'
+suggest-owed-list = Usa 'fintrack owed list' para ver los préstamos abiertos
+error-bill-not-found = No hay ninguna factura llamada '{ $name }'
+suggest-bills = Usa 'fintrack bills' para ver tus facturas
+error-project-not-found = Ningún registro pertenece a un proyecto llamado '{ $name }'
+suggest-project-list = Usa 'fintrack project list' para ver tus proyectos
+error-schedule-not-found = No hay ninguna programación con ID { $id }
+suggest-schedule-list = Usa 'fintrack schedule list' para ver los ID de las programaciones
+error-budget-not-found = '{ $subcategory }' no tiene presupuesto
+suggest-budget-list = Usa 'fintrack budget list' para ver tus presupuestos
+error-version-not-found = El registro { $id } no tiene la versión { $version }
+suggest-history = Usa 'fintrack history { $id }' para ver sus versiones
+error-subscription-not-found = No hay ninguna suscripción llamada '{ $name }'
+suggest-subscriptions = Usa 'fintrack subscriptions list' para ver tus suscripciones
+error-token-not-found = No hay ningún token llamado '{ $name }'
+suggest-token-list = Usa 'fintrack serve token list' para ver tus tokens
+error-tag-not-found = No hay ninguna etiqueta llamada '{ $name }'
+suggest-tag-list = Usa 'fintrack tag list' para ver tus etiquetas
+error-tag-in-use = '{ $name }' sigue en { $count ->
+        [one] { $count } registro
+       *[other] { $count } registros
+    }
+suggest-tag-from-records = Añade --from-records para quitarla también de ellos, o únela a otra etiqueta con 'fintrack tag rename'
+error-period-closed = { $month } está cerrado, así que sus registros no se pueden añadir, cambiar ni eliminar
+suggest-reopen = Reábrelo primero con 'fintrack close --month { $month } --reopen'
+
+## Records
+
+record-created = Registro creado:
+record-updated = Registro actualizado:
+no-records = No se encontraron registros.
+search-no-matches = Ningún registro coincide con "{ $query }".
+no-description = (sin descripción)
+unknown = Desconocido
+category-fallback = Categoría { $id }
+subcategory-fallback = Subcategoría { $id }
+new-balance = Saldo:
+records-deleted = { $count ->
+        [one] Se eliminó 1 registro
+       *[other] Se eliminaron { $count } registros
+    }
+
+column-id = ID
+column-category = Categoría
+column-subcategory = Subcategoría
+column-tag = Etiqueta
+column-amount = Importe
+column-date = Fecha
+column-description = Descripción
+column-income = Ingresos
+column-expenses = Gastos
+column-net = Neto
+column-budget = Presupuesto
+column-spent = Gastado
+column-remaining = Restante
+column-carried = Arrastrado
+column-version = Versión
+column-since = Desde
+
+## Totals and listings
+
+summary-title = Resumen financiero:
+summary-period = Periodo:
+summary-as-of = A fecha de:
+summary-opening = Saldo inicial:
+summary-income = Ingresos totales:
+summary-expenses = Gastos totales:
+summary-investments = Inversiones:
+summary-net = Saldo neto:
+summary-budget-remaining = Presupuesto restante este mes:
+summary-round-up = Ahorro por redondeo:
+categories-title = Categorías:
+subcategories-title = Subcategorías:
+tags-title = Etiquetas:
+tags-none = Aún no hay etiquetas; etiqueta un registro con 'fintrack add --tag <nombre>'
+tag-records = { $count ->
+        [one] { $count } registro
+       *[other] { $count } registros
+    }
+
+## Describe
+
+describe-title = Resumen general:
+describe-total-records = Total de registros:
+describe-date-range = Periodo:
+describe-range = del { $start } al { $end }
+describe-by-category = Por categoría:
+describe-category-chart = Gastos por categoría:
+describe-by-subcategory = Por subcategoría (top 5):
+describe-subcategory-chart = Principales subcategorías:
+describe-group = { $name }: { $count ->
+        [one] { $count_text } registro
+       *[other] { $count_text } registros
+    } | { $total } { $currency }
+describe-by-user = Por persona:
+describe-by-tag = Por etiqueta:
+describe-by-weekday = Gastos por día de la semana:
+describe-busiest = ◀ más gasto
+describe-weekdays = Entre semana:
+describe-weekend = Fin de semana:
+describe-share = { $amount } ({ $percent } %)
+weekday-monday = Lunes
+weekday-tuesday = Martes
+weekday-wednesday = Miércoles
+weekday-thursday = Jueves
+weekday-friday = Viernes
+weekday-saturday = Sábado
+weekday-sunday = Domingo
+describe-average = Transacción media:
+currency-title = Por moneda:
+currency-row = { $currency }: { $count ->
+        [one] { $count_text } registro
+       *[other] { $count_text } registros
+    }, ingresos { $income }, gastos { $expenses }
+currency-converted = = ingresos { $income }, gastos { $expenses } en { $base }
+currency-rate = 1 { $currency } = { $rate } { $base }, tipo del { $date } ({ $count ->
+        [one] 1 registro
+       *[other] { $count } registros
+    })
+currency-rate-range = 1 { $currency } = { $rate } { $base }, tipos del { $from } al { $to } ({ $count ->
+        [one] 1 registro
+       *[other] { $count } registros
+    })
+
+
+## Report
+
+report-title = Informe mensual: { $month }
+report-title-year = Informe anual: { $month }
+report-title-week = Informe semanal: { $month }
+report-records = Registros:
+report-opening = Saldo inicial:
+report-income = Ingresos:
+report-expenses = Gastos:
+report-net = Neto del periodo:
+report-closing = Saldo final:
+report-closed = Este mes está cerrado y coincide con su saldo guardado.
+report-snapshot-mismatch = Aviso: este mes se cerró con { $snapshot }; los registros ya no suman esa cantidad.
+report-income-by-subcategory = Ingresos por subcategoría:
+report-expenses-by-subcategory = Gastos por subcategoría:
+report-empty = No hay registros en este periodo.
+deductions-title = Deducciones fiscales: { $year }
+deductions-empty = No hay gastos deducibles este año.
+deductions-total = Total deducible:
+digest-title = Semana del { $start } al { $end }
+digest-spent = Gastado { $amount }
+digest-up = { $percent }% más que la semana pasada ({ $amount })
+digest-down = { $percent }% menos que la semana pasada ({ $amount })
+digest-same = igual que la semana pasada
+digest-first = nada gastado la semana pasada
+digest-top = Principales: { $subcategories }
+digest-unusual = Inusual: #{ $id } { $description } { $amount } en { $subcategory } (normalmente { $typical })
+reconcile-title = Conciliación al { $date }
+reconcile-recorded = Saldo registrado:
+reconcile-statement = Saldo del extracto:
+reconcile-drift = Diferencia:
+reconcile-balanced = Los registros coinciden con el extracto.
+reconcile-adjusted = Se añadió el registro de ajuste { $id }.
+reconcile-hint = Busque registros que falten o estén mal escritos, o vuelva a ejecutar con --adjust para registrar la diferencia.
+dry-run-title = Simulación: no se guardó nada. Se harían estos cambios:
+dry-run-unchanged = Simulación: nada cambiaría.
+diff-other-changes = También difieren presupuestos, facturas u otros datos del registro.
+diff-income = Ingresos:
+diff-expenses = Gastos:
+diff-balance = Saldo:
+diff-title = Cambios desde { $source }:
+diff-restore-title = Restaurar { $source } haría estos cambios:
+diff-unchanged = El registro es igual que { $source }.
+diff-subcategory = subcategoría { $name }
+diff-subcategory-renamed = subcategoría { $old } → { $new }
+
+
+projects-title = Proyectos
+projects-none = Aún no hay proyectos. Asigna un gasto con 'fintrack add expenses 300 --project "Viaje a Lagos"'.
+project-title = Proyecto: { $name }
+project-dates = Fechas:
+project-spent = Total gastado:
+owed-title = Dinero pendiente
+owed-none = Nadie debe nada.
+owed-owes-you = te debe
+owed-you-owe = le debes
+owed-due = vence el { $date }
+owed-overdue = VENCIDO
+owed-net = Neto que te deben:
+bills-title = Facturas
+bills-none = Aún no hay facturas. Añade una con 'fintrack bill add'.
+bills-due-in = { $days ->
+        [one] en 1 día
+       *[other] en { $days } días
+    }
+bills-due-today = VENCE HOY
+bills-overdue = { $days ->
+        [one] VENCIDA hace 1 día
+       *[other] VENCIDA hace { $days } días
+    }
+bills-total = Total por ciclo:
+subscriptions-title = Suscripciones
+subscriptions-none = Aún no hay suscripciones. Añade una con 'fintrack subscriptions add'.
+subscriptions-every = { $every ->
+        [day] al día
+        [week] a la semana
+        [year] al año
+       *[other] al mes
+    }
+subscriptions-renews = se renueva el { $date }
+subscriptions-renews-today = SE RENUEVA HOY
+subscriptions-payments = { $count ->
+        [0] ningún pago vinculado
+        [one] 1 pago vinculado
+       *[other] { $count } pagos vinculados
+    }
+subscriptions-cancel-by = cancelar antes del { $date }
+subscriptions-cancel-passed = la fecha para cancelar ({ $date }) ya pasó
+subscriptions-total = Total al mes:
+tokens-title = Tokens para 'fintrack serve'
+tokens-none = No hay tokens, así que 'fintrack serve' está abierto a cualquiera que pueda acceder. Crea uno con 'fintrack serve token create'.
+tokens-created = creado el { $date }
+merge-title = Se fusionó { $source }
+merge-title-dry-run = Fusionar { $source } haría estos cambios:
+merge-records = Registros añadidos: { $count }
+merge-duplicates = Duplicados omitidos: { $count }
+merge-subcategories = Nuevas subcategorías:
+merge-budgets-added = Nuevos presupuestos:
+merge-budgets-replaced = Presupuestos reemplazados:
+merge-dry-run = No se cambió nada (simulación).
+budgets-title = Presupuestos de { $month }
+budgets-none = Aún no hay presupuestos. Crea uno con 'fintrack budget set'.
+budgets-spent = { $spent } de { $budget } gastado
+budgets-carried = ({ $amount } del mes anterior)
+budgets-left = quedan { $amount }
+budgets-over = { $amount } por encima
+history-title = Versiones del registro { $id }
+history-current = (actual)
+history-reverted = (vuelta a la { $version })
+status-title = Estado al { $date }
+status-balance = Saldo:
+status-spent-today = Gastado hoy:
+status-limit-daily = Límite diario:
+status-limit-weekly = Límite semanal:
+status-over-budget = ({ $count ->
+        [one] 1 presupuesto superado
+       *[other] { $count } presupuestos superados
+    })
+status-short-today = hoy { $amount }
+status-short-budget = quedan { $amount } de presupuesto
+status-short-daily-over = límite diario superado
+status-short-weekly-over = límite semanal superado
+status-short-over = { $count ->
+        [one] 1 presupuesto superado
+       *[other] { $count } presupuestos superados
+    }
+status-payday = Día de pago:
+status-payday-in = { $days ->
+        [0] hoy
+        [one] mañana, { $date }
+       *[other] en { $days } días, { $date }
+    }
+status-per-day = Por día hasta entonces:
+status-short-payday = { $days ->
+        [0] cobro hoy
+       *[other] cobro en { $days }d
+    }
+schedules-title = Registros programados
+schedules-none = No hay nada programado. Añade algo con 'fintrack schedule add'.
+schedules-next = próximo { $date }
+frequency-day = cada día
+frequency-week = cada semana
+frequency-month = cada mes
+frequency-year = cada año
+holdings-title = Inversiones ({ $currency })
+holdings-none = Aún no hay inversiones. Añade una con 'fintrack invest add'.
+holdings-symbol = Símbolo
+holdings-quantity = Cantidad
+holdings-cost = Coste
+holdings-price = Precio
+holdings-value = Valor
+holdings-gain = Ganancia
+holdings-total-value = Valor total:
+holdings-total-gain = Ganancia no realizada:
+
+## Config
+
+settings-title = Ajustes:
+config-not-set = (sin definir)
+config-set = { $key } se estableció en '{ $value }'
+config-was-not-set = { $key } no estaba establecido
+config-unset = { $key } volvió a su valor predeterminado
+config-exported = Ajustes exportados a: { $path }
+config-imported = Ajustes importados desde: { $path }
+config-dry-run-set = Simulación: no se escribió nada. { $key } pasaría a ser '{ $value }'
+config-dry-run-unset = Simulación: no se escribió nada. { $key } volvería a su valor predeterminado
+config-dry-run-import = Simulación: no se escribió nada. Los ajustes se importarían desde { $path }
+
+## Command results
+
+edit-resumed = Se continuó con la edición que no se guardó la última vez; usa --fresh para empezar de nuevo desde el registro
+edit-no-changes = No hubo cambios; el registro se dejó como estaba
+edit-saved = Registro reemplazado por tu edición ({ $count } registro(s))
+batch-ran = Se ejecutaron { $count } comando(s) de { $file }
+batch-stopped = Detenido en la línea { $line } ({ $command }); no se guardó ningún cambio
+watch-header = fintrack watch · { $view } · Ctrl-C para detener
+dump-written = Datos del registro escritos en: { $path }
+schema-written = Esquema escrito en: { $path }
+export-done = Datos exportados a: { $path }
+digest-sent = Resumen enviado a { $channels }
+notify-alerts-sent = { $count ->
+        [0] No hay nada que avisar
+       *[other] Se enviaron { $count } aviso(s) a { $channels }
+    }
+notify-summary-sent = Resumen enviado a { $channels }
+report-sent = Informe de { $month } enviado a { $recipients }
+owed-balance-owes-you = { $person } te debe { $amount } { $currency }
+owed-balance-you-owe = Le debes a { $person } { $amount } { $currency }
+owed-balance-settled = { $person } y tú están a mano
+init-dry-run-exists = Simulación: { $path } ya existe, así que init fallaría
+init-dry-run = Simulación: no se escribió nada. Se crearía un registro en { $currency } en { $path }
+bill-added = { $name } añadida, próximo vencimiento el { $due }
+bill-paid = { $name } pagada ({ $amount } { $currency }), próximo vencimiento el { $due }
+bill-deleted = Factura { $name } eliminada
+history-current-version = El registro { $id } ya está en la versión { $version }
+subcategory-added = Subcategoría '{ $name }' añadida (ID: { $id })
+subcategory-renamed = Subcategoría renombrada: '{ $old }' → '{ $new }'
+subcategory-deleted = Subcategoría '{ $name }' eliminada
+token-created = Token '{ $name }' creado. Cópialo ahora, no se volverá a mostrar:
+
+    {"  "}{ $token }
+
+    Envíalo como 'Authorization: Bearer { $token }'.
+token-revoked = Token '{ $name }' revocado
+token-dry-run-create = Simulación: no se escribió nada. Se crearía un token llamado '{ $name }'
+token-dry-run-revoke = Simulación: no se escribió nada. Se revocaría el token '{ $name }'
+serve-listening = Sirviendo métricas en { $url } (Ctrl-C para detener)
+plan-exported = Plan exportado a: { $path }
+plan-imported = Plan importado desde { $path } (subcategorías nuevas: { $subcategories }, presupuestos fijados: { $budgets }, programaciones añadidas: { $schedules })
+budget-set = Presupuesto de { $name } fijado en { $amount } { $currency } al mes{ $rollover ->
+        [true] , con arrastre
+       *[other] {""}
+    }
+budget-deleted = Se eliminó el presupuesto de { $name }
+invest-bought = Compradas { $quantity } { $symbol } a { $price } { $currency } cada una
+close-not-closed = { $month } no está cerrado
+close-reopened = { $month } reabierto
+close-already-closed = { $month } ya está cerrado
+close-closed = { $month } cerrado con un saldo de { $balance } { $currency }
+schedule-added = Programación n.º { $id } añadida, primer vencimiento el { $due }
+schedule-deleted = Programación n.º { $id } eliminada
+schedule-ran = { $count ->
+        [0] No hay registros programados pendientes
+        [one] Se añadió 1 registro programado
+       *[other] Se añadieron { $count } registros programados
+    }
+tag-renamed = Etiqueta renombrada: '{ $old }' → '{ $new }' en { $count } registro(s)
+tag-merged = Etiqueta '{ $old }' fusionada con '{ $new }' en { $count } registro(s)
+tag-deleted = { $count ->
+        [0] Etiqueta '{ $name }' eliminada
+       *[other] Etiqueta '{ $name }' eliminada y quitada de { $count } registro(s)
+    }
+bench-generated = Se generaron { $count } registro(s) en { $path }
+subscription-added = { $name } añadida, se renueva el { $renews }{ $linked ->
+        [0] {""}
+        [one] , se vinculó 1 pago anterior
+       *[other] , se vincularon { $linked } pagos anteriores
+    }
+subscription-deleted = Suscripción { $name } eliminada
+import-done = Se importaron { $count } registro(s) desde { $path } (subcategorías nuevas: { $subcategories })
+import-skipped = Se omitieron { $count } fila(s) que no se pudieron importar, empezando por { $first }
+chart-balance-written = Saldo del { $from } al { $to } escrito en { $path }
+clear-cancelled = Borrado cancelado.
+clear-archived = Se guardó una copia en { $path }.
+clear-records-removed = Se eliminaron { $count } registro(s); las subcategorías se conservaron.
+clear-dry-run = Simulación: no se eliminó nada. Se eliminaría { $path }, con { $count } registro(s)
+clear-file-removed = Se eliminó { $path }.
+clear-done = Se borraron todos los datos.
+clear-start-over = Ejecuta 'fintrack init' para empezar de nuevo.
+tui-done = Hecho
+tui-record-created = Registro { $id } creado
+tui-record-updated = Registro { $id } actualizado
+tui-deleted = Se eliminaron { $count } registro(s)
+
+## Warnings
+
+add-suggest-subcategory = Parece { $name }; archívalo ahí con 'fintrack update { $id } -s "{ $name }"'
+plan-currency-mismatch = Los importes del plan están en { $plan } pero este registro está en { $tracker }; se importaron tal cual
+limit-daily-over = Gastaste { $spent } { $currency } el { $date }, por encima de tu límite diario de { $limit } { $currency }
+limit-weekly-over = Gastaste { $spent } { $currency } en la semana del { $week }, por encima de tu límite semanal de { $limit } { $currency }
+budget-threshold-reached = El presupuesto de { $name } para { $month } está gastado al { $percent } % ({ $spent } de { $available } { $currency })
+budget-over = Presupuesto de { $name } para { $month } superado: { $spent } de { $available } { $currency } gastados
+serve-unprotected = Atención: cualquiera que pueda acceder a { $address } puede leer tus métricas. Crea un token con 'fintrack serve token create'.
+
+## Prompts
+
+add-suggest-prompt = Parece { $name }. ¿Archivarlo ahí? (Y/n):
+edit-problems = El registro editado tiene problemas:
+edit-again-prompt = ¿Editarlo de nuevo? (Y/n):
+
+## Command errors
+
+error-argument-missing = No se indicó el argumento obligatorio '{ $name }'
+error-io = Error de E/S: { $reason }
+error-not-interactive = '{ $command }' no está disponible en modo interactivo
+error-empty-name = Nombre no válido
+error-file-exists = { $path } ya existe. Usa --force para sobrescribirlo
+error-read-file = No se pudo leer { $path }: { $reason }
+error-path-missing = La ruta no existe: { $path }
+error-path-not-directory = La ruta no es una carpeta: { $path }
+error-tracker-currency = Moneda no válida en los datos del registro: { $reason }
+error-schema-compile = No se pudo compilar el esquema del registro: { $reason }
+error-offline = { $what } necesita la red, y --offline lo impide
+error-service-status = { $service } respondió con el estado { $status }
+error-service-unreachable = No se pudo conectar con { $service }: { $reason }
+error-server-status = el servidor respondió con el estado { $status }
+error-config-invalid = Archivo de configuración no válido { $path }: { $reason }
+error-config-write = No se pudo escribir la configuración: { $reason }
+error-no-editor = No se indicó ningún editor
+error-editor-start = No se pudo iniciar { $editor }: { $reason }
+error-editor-failed = { $editor } terminó con { $status }; el registro se dejó como estaba
+error-edit-not-saved = El registro editado no se guardó: { $problems }. Tu edición se conserva en { $path }
+edit-field-invalid = { $path } no encaja ({ $message })
+edit-json-invalid = no es JSON válido ({ $message })
+edit-unreadable = no se puede leer ({ $kind })
+error-merge-currency = { $path } está en { $other } pero este registro está en { $currency }, así que sus registros no se pueden sumar
+error-close-not-ended = { $month } aún no ha terminado; solo se pueden cerrar meses pasados
+error-pdf-export = La exportación a PDF aún no está disponible
+error-no-export-dir = No se indicó ninguna carpeta de exportación. Indica una, o fija una predeterminada con 'fintrack config set export.dir <carpeta>'
+error-no-backups = Todavía no hay copias de seguridad
+error-diff-not-found = No hay ningún archivo ni copia de seguridad llamado '{ $file }'; 'latest' elige la copia más reciente
+error-delete-unfiltered = Indica qué registros eliminar con --ids o al menos un filtro, como --subcategory
+error-bench-exists = { $path } ya existe; indica otro nombre de archivo
+error-watch-unsupported = '{ $command }' no se puede vigilar. Usa uno de: { $views }
+error-watch-failed = No se pudo vigilar el registro: { $reason }
+error-import-line = { $path } línea { $line }: { $reason }; no se importó nada
+import-row = línea { $line }: { $reason }
+import-no-column = el encabezado no tiene la columna '{ $column }'
+import-not-amount = '{ $amount }' no es un importe
+import-category-empty = la categoría está vacía
+import-too-many-decimals = { $amount } tiene demasiados decimales
+import-amount-not-positive = el importe debe ser mayor que 0
+import-currency-mismatch = el importe está en { $currency } pero el registro está en { $tracker }
+import-not-date = '{ $date }' no es una fecha
+import-closed-month = { $date } está en un mes cerrado
+import-bad-subcategory = '{ $name }' no se puede usar como nombre de subcategoría
+import-no-subcategory-nearest = no hay ninguna subcategoría '{ $name }' (¿quisiste decir '{ $nearest }'?)
+import-no-subcategory = no hay ninguna subcategoría '{ $name }'; añade --create para crearla
+error-chart-format = No se sabe en qué formato escribir { $path }. Termínalo en .svg o .png
+error-chart-empty = Aún no hay nada que graficar: no hay registros en ese periodo
+error-chart-png = No se pudo dibujar el gráfico como PNG: { $reason }
+error-serve-listen = No se pudo escuchar en { $address }: { $reason }
+error-budget-thresholds = El umbral de aviso ({ $warn } %) debe ser menor que el umbral de error ({ $error } %)
+error-plan-invalid = Archivo de plan no válido { $path }: { $reason }
+error-plan-version = { $path } es un plan de la versión { $version }; este fintrack lee hasta la versión { $supported }
+error-plan-write = No se pudo escribir el plan: { $reason }
+error-no-notify-channel = No hay ningún canal de notificación configurado. Establece 'notify.ntfy_topic', 'notify.desktop', o 'notify.telegram_token' y 'notify.telegram_chat_id', con 'fintrack config set'
+error-desktop-notification = No se pudo mostrar una notificación de escritorio: { $reason }
+error-notify-send = No se pudo enviar a { $channel }: { $reason }
+error-email-not-set-up = El correo no está configurado. Establece 'email.smtp_host', 'email.from' y 'email.to' con 'fintrack config set'
+error-email-build = No se pudo preparar el correo: { $reason }
+error-email-send = No se pudo enviar el correo: { $reason }
+error-email-address = Dirección de correo no válida '{ $address }': { $reason }
+error-smtp-host = Servidor SMTP no válido '{ $host }': { $reason }
+error-gsheet-no-spreadsheet = No hay ninguna hoja de cálculo. Indica una con 'fintrack config set gsheet.spreadsheet_id <id>'
+error-gsheet-no-token = No hay token de acceso de Google. Establece { $variable } (p. ej. con 'gcloud auth print-access-token') o 'gsheet.access_token'
+error-gsheet-status = Google Sheets respondió con el estado { $status }: { $detail }
+error-gsheet-unreachable = No se pudo conectar con Google Sheets: { $reason }
+error-hook-failed = El hook { $hook } falló ({ $status })
+error-hook-not-run = No se pudo ejecutar el hook { $hook }: { $reason }
+error-token-generate = No se pudo generar un token: { $reason }
+error-no-price-service = No hay ningún servicio de cotizaciones configurado; establece 'invest.price_url' o indica los precios con --price
+error-price-status = El servicio de cotizaciones respondió con el estado { $status } para { $symbol }
+error-price-unreachable = No se pudo conectar con el servicio de cotizaciones: { $reason }
+error-price-missing = No hay precio en '{ $field }' en la respuesta para { $symbol }
+error-no-rate = No hay tipo de cambio para { $currency }; indica uno con --rate, añádelo a 'rates.manual' o establece 'rates.provider'
+error-rates-access-key = No hay clave de acceso para exchangerate.host; establece 'rates.access_key'
+error-rates-refused = se rechazó la solicitud
+error-rates-provider = { $provider }: { $reason }
+error-rates-no-result = { $provider } no envió ningún tipo de { $from } a { $to }
+error-ecb-no-rates = El BCE no tiene tipos para el { $date }
+error-ecb-no-currency = El BCE no publica ningún tipo para { $currency }
+error-amount-range = --min-amount { $min } es mayor que --max-amount { $max }
+error-where = --where "{ $expression }": { $reason }
+where-unsupported-op = '{ $op }' no se puede usar con '{ $field }'
+where-already-set = { $field } ya es '{ $value }'
+where-category-set = la categoría ya está fijada
+where-subcategory-set = la subcategoría ya está fijada
+where-deductible = solo se admite 'deductible = true'
+where-use-contains = usa '{ $field } ~ TEXTO' para buscar parte de la descripción
+where-unknown-field = no existe '{ $field }'; las condiciones pueden ser sobre category, subcategory, user, project, tag, deductible, description, amount o date
+reason-split-not-expense = solo se pueden dividir los gastos
+reason-split-over-total = las partes suman { $shares }, más que los { $amount } { $currency } gastados
+reason-rate-not-positive = el tipo para { $currency } debe ser mayor que 0
+reason-name-empty = el nombre está vacío
+reason-bill-exists = ya hay una factura con este nombre
+reason-token-exists = ya hay un token con este nombre
+reason-subscription-exists = ya hay una suscripción con este nombre
+
+## Alerts
+
+alert-budget-title = Presupuesto superado: { $name }
+alert-budget-body = Gastaste { $spent } de { $budget } este mes, { $over } de más
+alert-bill-overdue-title = Factura vencida: { $name }
+alert-bill-due-title = Factura por pagar: { $name }
+alert-bill-overdue = { $amount } vencía el { $due }, hace { $days ->
+        [one] 1 día
+       *[other] { $days } días
+    }
+alert-bill-due = { $amount } { $days ->
+        [0] vence hoy
+        [one] vence el { $due }, en 1 día
+       *[other] vence el { $due }, en { $days } días
+    }
+alert-daily-limit-title = Límite diario superado
+alert-daily-limit-body = Gastaste { $spent } hoy, con un límite de { $limit }
+alert-weekly-limit-title = Límite semanal superado
+alert-weekly-limit-body = Gastaste { $spent } esta semana, con un límite de { $limit }
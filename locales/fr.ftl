@@ -0,0 +1,581 @@
+# Messages en français pour fintrack
+
+## Markers and labels
+
+success = Succès
+suggestion-label = Conseil :
+validation-error-label = Erreur de validation :
+
+## Errors
+
+error-file-not-found = Fichier introuvable : { $path }
+suggest-init = Lancez 'fintrack init' pour initialiser le suivi
+error-not-initialized = Aucun suivi pour l'instant — il semble que ce soit votre première utilisation de fintrack
+suggest-quick-start = Créez-en un pour commencer :
+      fintrack init --currency EUR --opening 1000
+      fintrack add income 4000 -s Salaire
+      fintrack list
+error-read-only = Le mode lecture seule est activé : cette commande ne peut pas modifier votre suivi
+suggest-read-only = Relancez sans --read-only, ou désactivez-le avec 'fintrack config unset general.read_only'
+error-checksum-mismatch = Vos opérations ne correspondent plus à la somme de contrôle enregistrée ; tracker.json a été modifié hors de fintrack ou est incomplet
+suggest-checksum-mismatch = Si vous avez modifié le fichier vous-même, supprimez sa ligne "checksum" pour accepter les changements. Sinon, copiez le fichier le plus récent de ~/.fintrack/backups à la place de tracker.json
+error-invalid-field = tracker.json contient une valeur invalide à { $path } : { $message }
+suggest-fix-field = Remplacez { $path } dans tracker.json par { $expected } et supprimez la ligne "checksum", ou copiez le fichier le plus récent de ~/.fintrack/backups à la place de tracker.json
+suggest-check-field = Corrigez { $path } dans tracker.json et supprimez la ligne "checksum", ou copiez le fichier le plus récent de ~/.fintrack/backups à la place de tracker.json
+error-invalid-json = JSON invalide : { $message }
+suggest-restore-backup = Vos données sont peut-être corrompues. Essayez de restaurer une sauvegarde
+error-permission-denied = Permission refusée : { $path }
+suggest-check-permissions = Vérifiez les permissions du fichier ou relancez avec les droits nécessaires
+error-corrupted-restored = Les données étaient corrompues mais ont été restaurées depuis une sauvegarde ({ $timestamp })
+suggest-verify-changes = Vérifiez vos modifications récentes
+error-corrupted-unrecoverable = Données corrompues et la restauration de la sauvegarde a échoué
+suggest-inspect-or-reset = Lancez 'fintrack dump' pour inspecter les données restantes, ou 'fintrack clear' pour tout réinitialiser
+error-already-initialized = Le suivi est déjà initialisé
+suggest-clear = Utilisez 'fintrack clear' pour recommencer
+error-amount-too-small = Le montant doit être supérieur à 0, reçu : { $amount }
+suggest-positive-amount = Relancez la commande avec un montant positif (par ex. --amount 500)
+error-invalid-date = Format de date invalide : '{ $provided }'
+suggest-date-format = Format attendu : { $format }
+error-impossible-date = '{ $provided }' n'est pas une vraie date
+suggest-nearest-date = Vérifiez le jour et le mois ; la vraie date la plus proche est le { $nearest }
+error-subcategory-not-found = Sous-catégorie '{ $name }' introuvable
+suggest-subcategory-list = Utilisez 'fintrack subcategory list' pour voir les sous-catégories disponibles
+suggest-subcategory-nearest = Vouliez-vous dire '{ $nearest }' ?
+
+error-subcategory-exists = La sous-catégorie '{ $name }' existe déjà
+suggest-different-name = Choisissez un autre nom ou consultez les sous-catégories existantes
+error-record-not-found = Enregistrement avec l'ID { $id } introuvable
+suggest-list = Utilisez 'fintrack list' pour voir les enregistrements disponibles
+error-subcategory-has-records = Impossible de supprimer '{ $name }' — { $count ->
+        [one] { $count } enregistrement l'utilise
+       *[other] { $count } enregistrements l'utilisent
+    }
+suggest-delete-by-subcat = Supprimez d'abord ces enregistrements avec 'fintrack delete --by-subcat { $name }', ou un par un
+error-cannot-delete-misc = Impossible de supprimer 'Miscellaneous' — c'est une sous-catégorie système
+error-category-immutable = Les catégories sont fixes : '{ $category }' ne peut être ni ajoutée, ni supprimée, ni renommée. Il n'y a que Income et Expenses
+suggest-category-immutable = Les sous-catégories, elles, peuvent être ajoutées, renommées et supprimées ; voir 'fintrack subcategory --help'
+error-invalid-category-name = Nom de catégorie invalide '{ $name }' : { $reason }
+error-invalid-name = Nom invalide '{ $name }' : { $reason }
+error-invalid-amount = Montant invalide : { $reason }
+error-unknown-subcommand = Sous-commande inconnue : '{ $subcommand }'
+suggest-help = Utilisez 'fintrack --help' pour voir les commandes disponibles
+error-unknown-config-key = Paramètre inconnu : '{ $key }'
+suggest-config-list = Utilisez 'fintrack config list' pour voir les paramètres disponibles
+error-invalid-config-value = Valeur '{ $value }' invalide pour { $key } : { $reason }
+error-strict-check-failed = Le mode strict a refusé cet enregistrement : { $reason }
+suggest-strict = Corrigez l'enregistrement, ou lancez sans --strict (et avec 'validation.strict' désactivé) pour l'accepter
+error-duplicate-record = L'enregistrement { $id } a déjà la même date, le même montant, la même sous-catégorie et la même description
+suggest-duplicate-record = Modifiez la description s'il s'agit vraiment d'une deuxième transaction, ou désactivez 'validation.reject_exact_duplicates'
+error-nothing-owed = Rien n'est en cours avec '{ $person }'
+suggest-owed-list = Utilisez 'fintrack owed list' pour voir les prêts en cours
+error-bill-not-found = Aucune facture nommée '{ $name }'
+suggest-bills = Utilisez 'fintrack bills' pour voir vos factures
+error-project-not-found = Aucun enregistrement n'appartient à un projet nommé '{ $name }'
+suggest-project-list = Utilisez 'fintrack project list' pour voir vos projets
+error-schedule-not-found = Aucune planification avec l'ID { $id }
+suggest-schedule-list = Utilisez 'fintrack schedule list' pour voir les ID des planifications
+error-budget-not-found = '{ $subcategory }' n'a pas de budget
+suggest-budget-list = Utilisez 'fintrack budget list' pour voir vos budgets
+error-version-not-found = L'enregistrement { $id } n'a pas de version { $version }
+suggest-history = Utilisez 'fintrack history { $id }' pour voir ses versions
+error-subscription-not-found = Aucun abonnement nommé '{ $name }'
+suggest-subscriptions = Utilisez 'fintrack subscriptions list' pour voir vos abonnements
+error-token-not-found = Aucun jeton nommé '{ $name }'
+suggest-token-list = Utilisez 'fintrack serve token list' pour voir vos jetons
+error-tag-not-found = Aucune étiquette nommée '{ $name }'
+suggest-tag-list = Utilisez 'fintrack tag list' pour voir vos étiquettes
+error-tag-in-use = '{ $name }' est encore sur { $count ->
+        [one] { $count } enregistrement
+       *[other] { $count } enregistrements
+    }
+suggest-tag-from-records = Ajoutez --from-records pour la retirer aussi de ceux-ci, ou fusionnez-la avec une autre étiquette via 'fintrack tag rename'
+error-period-closed = { $month } est clôturé : ses enregistrements ne peuvent être ni ajoutés, ni modifiés, ni supprimés
+suggest-reopen = Rouvrez-le d'abord avec 'fintrack close --month { $month } --reopen'
+
+## Records
+
+record-created = Enregistrement créé :
+record-updated = Enregistrement modifié :
+no-records = Aucun enregistrement trouvé.
+search-no-matches = Aucun enregistrement ne correspond à « { $query } ».
+no-description = (sans description)
+unknown = Inconnu
+category-fallback = Catégorie { $id }
+subcategory-fallback = Sous-catégorie { $id }
+new-balance = Solde :
+records-deleted = { $count ->
+        [one] { $count } enregistrement supprimé
+       *[other] { $count } enregistrements supprimés
+    }
+
+column-id = ID
+column-category = Catégorie
+column-subcategory = Sous-catégorie
+column-tag = Étiquette
+column-amount = Montant
+column-date = Date
+column-description = Description
+column-income = Revenus
+column-expenses = Dépenses
+column-net = Net
+column-budget = Budget
+column-spent = Dépensé
+column-remaining = Restant
+column-carried = Report
+column-version = Version
+column-since = Depuis
+
+## Totals and listings
+
+summary-title = Résumé financier :
+summary-period = Période :
+summary-as-of = Au :
+summary-opening = Solde d'ouverture :
+summary-income = Total des revenus :
+summary-expenses = Total des dépenses :
+summary-investments = Investissements :
+summary-net = Solde net :
+summary-budget-remaining = Budget restant ce mois-ci :
+summary-round-up = Épargne par arrondi :
+categories-title = Catégories :
+subcategories-title = Sous-catégories :
+tags-title = Étiquettes :
+tags-none = Pas encore d'étiquettes ; étiquetez un enregistrement avec 'fintrack add --tag <nom>'
+tag-records = { $count ->
+        [one] { $count } enregistrement
+       *[other] { $count } enregistrements
+    }
+
+## Describe
+
+describe-title = Vue d'ensemble :
+describe-total-records = Nombre d'enregistrements :
+describe-date-range = Période :
+describe-range = du { $start } au { $end }
+describe-by-category = Par catégorie :
+describe-category-chart = Dépenses par catégorie :
+describe-by-subcategory = Par sous-catégorie (top 5) :
+describe-subcategory-chart = Principales sous-catégories :
+describe-group = { $name } : { $count ->
+        [one] { $count_text } enregistrement
+       *[other] { $count_text } enregistrements
+    } | { $total } { $currency }
+describe-by-user = Par personne :
+describe-by-tag = Par étiquette :
+describe-by-weekday = Dépenses par jour de la semaine :
+describe-busiest = ◀ jour le plus dépensier
+describe-weekdays = En semaine :
+describe-weekend = Week-end :
+describe-share = { $amount } ({ $percent } %)
+weekday-monday = Lundi
+weekday-tuesday = Mardi
+weekday-wednesday = Mercredi
+weekday-thursday = Jeudi
+weekday-friday = Vendredi
+weekday-saturday = Samedi
+weekday-sunday = Dimanche
+describe-average = Transaction moyenne :
+currency-title = Par devise :
+currency-row = { $currency } : { $count ->
+        [one] { $count_text } enregistrement
+       *[other] { $count_text } enregistrements
+    }, revenus { $income }, dépenses { $expenses }
+currency-converted = = revenus { $income }, dépenses { $expenses } en { $base }
+currency-rate = 1 { $currency } = { $rate } { $base }, taux du { $date } ({ $count ->
+        [one] 1 enregistrement
+       *[other] { $count } enregistrements
+    })
+currency-rate-range = 1 { $currency } = { $rate } { $base }, taux du { $from } au { $to } ({ $count ->
+        [one] 1 enregistrement
+       *[other] { $count } enregistrements
+    })
+
+
+## Report
+
+report-title = Rapport mensuel : { $month }
+report-title-year = Rapport annuel : { $month }
+report-title-week = Rapport hebdomadaire : { $month }
+report-records = Enregistrements :
+report-opening = Solde d'ouverture :
+report-income = Revenus :
+report-expenses = Dépenses :
+report-net = Net de la période :
+report-closing = Solde de clôture :
+report-closed = Ce mois est clôturé et correspond à son solde enregistré.
+report-snapshot-mismatch = Attention : ce mois a été clôturé à { $snapshot } ; les enregistrements ne correspondent plus à ce solde.
+report-income-by-subcategory = Revenus par sous-catégorie :
+report-expenses-by-subcategory = Dépenses par sous-catégorie :
+report-empty = Aucun enregistrement sur cette période.
+deductions-title = Déductions fiscales : { $year }
+deductions-empty = Aucune dépense déductible cette année.
+deductions-total = Total déductible :
+digest-title = Semaine du { $start } au { $end }
+digest-spent = Dépensé { $amount }
+digest-up = { $percent } % de plus que la semaine dernière ({ $amount })
+digest-down = { $percent } % de moins que la semaine dernière ({ $amount })
+digest-same = autant que la semaine dernière
+digest-first = rien de dépensé la semaine dernière
+digest-top = En tête : { $subcategories }
+digest-unusual = Inhabituel : #{ $id } { $description } { $amount } dans { $subcategory } (d'habitude { $typical })
+reconcile-title = Rapprochement au { $date }
+reconcile-recorded = Solde enregistré :
+reconcile-statement = Solde du relevé :
+reconcile-drift = Écart :
+reconcile-balanced = Les enregistrements correspondent au relevé.
+reconcile-adjusted = Enregistrement d'ajustement { $id } ajouté.
+reconcile-hint = Cherchez des enregistrements manquants ou erronés, ou relancez avec --adjust pour enregistrer l'écart.
+dry-run-title = Simulation : rien n'a été enregistré. Ces changements seraient faits :
+dry-run-unchanged = Simulation : rien ne changerait.
+diff-other-changes = Des budgets, factures ou autres données du suivi diffèrent aussi.
+diff-income = Revenus :
+diff-expenses = Dépenses :
+diff-balance = Solde :
+diff-title = Changements depuis { $source } :
+diff-restore-title = Restaurer { $source } ferait ces changements :
+diff-unchanged = Le suivi est identique à { $source }.
+diff-subcategory = sous-catégorie { $name }
+diff-subcategory-renamed = sous-catégorie { $old } → { $new }
+
+
+projects-title = Projets
+projects-none = Aucun projet pour l'instant. Rattachez une dépense avec 'fintrack add expenses 300 --project "Voyage à Lagos"'.
+project-title = Projet : { $name }
+project-dates = Dates :
+project-spent = Total dépensé :
+owed-title = Argent dû
+owed-none = Personne ne doit rien.
+owed-owes-you = vous doit
+owed-you-owe = vous devez
+owed-due = échéance le { $date }
+owed-overdue = EN RETARD
+owed-net = Net qui vous est dû :
+bills-title = Factures
+bills-none = Aucune facture pour l'instant. Ajoutez-en une avec 'fintrack bill add'.
+bills-due-in = { $days ->
+        [one] dans 1 jour
+       *[other] dans { $days } jours
+    }
+bills-due-today = À PAYER AUJOURD'HUI
+bills-overdue = { $days ->
+        [one] EN RETARD de 1 jour
+       *[other] EN RETARD de { $days } jours
+    }
+bills-total = Total par cycle :
+subscriptions-title = Abonnements
+subscriptions-none = Aucun abonnement pour l'instant. Ajoutez-en un avec 'fintrack subscriptions add'.
+subscriptions-every = { $every ->
+        [day] par jour
+        [week] par semaine
+        [year] par an
+       *[other] par mois
+    }
+subscriptions-renews = renouvelé le { $date }
+subscriptions-renews-today = RENOUVELÉ AUJOURD'HUI
+subscriptions-payments = { $count ->
+        [0] aucun paiement lié
+        [one] 1 paiement lié
+       *[other] { $count } paiements liés
+    }
+subscriptions-cancel-by = à résilier avant le { $date }
+subscriptions-cancel-passed = la date de résiliation ({ $date }) est passée
+subscriptions-total = Total par mois :
+tokens-title = Jetons pour 'fintrack serve'
+tokens-none = Aucun jeton : 'fintrack serve' est ouvert à quiconque peut l'atteindre. Créez-en un avec 'fintrack serve token create'.
+tokens-created = créé le { $date }
+merge-title = { $source } fusionné
+merge-title-dry-run = Fusionner { $source } ferait ces changements :
+merge-records = Enregistrements ajoutés : { $count }
+merge-duplicates = Doublons ignorés : { $count }
+merge-subcategories = Nouvelles sous-catégories :
+merge-budgets-added = Nouveaux budgets :
+merge-budgets-replaced = Budgets remplacés :
+merge-dry-run = Rien n'a été modifié (simulation).
+budgets-title = Budgets de { $month }
+budgets-none = Aucun budget pour l'instant. Créez-en un avec 'fintrack budget set'.
+budgets-spent = { $spent } dépensés sur { $budget }
+budgets-carried = ({ $amount } reporté)
+budgets-left = reste { $amount }
+budgets-over = { $amount } de dépassement
+history-title = Versions de l'enregistrement { $id }
+history-current = (actuelle)
+history-reverted = (retour à la { $version })
+status-title = État au { $date }
+status-balance = Solde :
+status-spent-today = Dépensé aujourd'hui :
+status-limit-daily = Limite quotidienne :
+status-limit-weekly = Limite hebdomadaire :
+status-over-budget = ({ $count ->
+        [one] 1 budget dépassé
+       *[other] { $count } budgets dépassés
+    })
+status-short-today = aujourd'hui { $amount }
+status-short-budget = budget : { $amount } restant
+status-short-daily-over = limite quotidienne dépassée
+status-short-weekly-over = limite hebdomadaire dépassée
+status-short-over = { $count ->
+        [one] 1 budget dépassé
+       *[other] { $count } budgets dépassés
+    }
+status-payday = Jour de paie :
+status-payday-in = { $days ->
+        [0] aujourd'hui
+        [one] demain, { $date }
+       *[other] dans { $days } jours, { $date }
+    }
+status-per-day = Par jour d'ici là :
+status-short-payday = { $days ->
+        [0] paie aujourd'hui
+       *[other] paie dans { $days } j
+    }
+schedules-title = Enregistrements planifiés
+schedules-none = Rien de planifié. Ajoutez-en avec 'fintrack schedule add'.
+schedules-next = prochain le { $date }
+frequency-day = chaque jour
+frequency-week = chaque semaine
+frequency-month = chaque mois
+frequency-year = chaque année
+holdings-title = Investissements ({ $currency })
+holdings-none = Aucun investissement pour l'instant. Ajoutez-en un avec 'fintrack invest add'.
+holdings-symbol = Symbole
+holdings-quantity = Quantité
+holdings-cost = Coût
+holdings-price = Prix
+holdings-value = Valeur
+holdings-gain = Gain
+holdings-total-value = Valeur totale :
+holdings-total-gain = Plus-value latente :
+
+## Config
+
+settings-title = Paramètres :
+config-not-set = (non défini)
+config-set = { $key } défini sur '{ $value }'
+config-was-not-set = { $key } n'était pas défini
+config-unset = { $key } remis à sa valeur par défaut
+config-exported = Paramètres exportés vers : { $path }
+config-imported = Paramètres importés depuis : { $path }
+config-dry-run-set = Simulation : rien n'a été écrit. { $key } serait réglé sur '{ $value }'
+config-dry-run-unset = Simulation : rien n'a été écrit. { $key } reprendrait sa valeur par défaut
+config-dry-run-import = Simulation : rien n'a été écrit. Les paramètres seraient importés depuis { $path }
+
+## Command results
+
+edit-resumed = Reprise de la modification non enregistrée la dernière fois ; utilisez --fresh pour repartir du suivi
+edit-no-changes = Aucune modification ; le suivi n'a pas été changé
+edit-saved = Suivi remplacé par votre modification ({ $count } enregistrement(s))
+batch-ran = { $count } commande(s) exécutée(s) depuis { $file }
+batch-stopped = Arrêt à la ligne { $line } ({ $command }) ; aucune modification n'a été enregistrée
+watch-header = fintrack watch · { $view } · Ctrl-C pour arrêter
+dump-written = Données du suivi écrites dans : { $path }
+schema-written = Schéma écrit dans : { $path }
+export-done = Données exportées vers : { $path }
+digest-sent = Résumé envoyé à { $channels }
+notify-alerts-sent = { $count ->
+        [0] Aucune alerte à envoyer
+       *[other] { $count } alerte(s) envoyée(s) à { $channels }
+    }
+notify-summary-sent = Résumé envoyé à { $channels }
+report-sent = Rapport de { $month } envoyé à { $recipients }
+owed-balance-owes-you = { $person } vous doit { $amount } { $currency }
+owed-balance-you-owe = Vous devez { $amount } { $currency } à { $person }
+owed-balance-settled = Vous êtes quittes avec { $person }
+init-dry-run-exists = Simulation : { $path } existe déjà, init échouerait donc
+init-dry-run = Simulation : rien n'a été écrit. Un suivi en { $currency } serait créé dans { $path }
+bill-added = { $name } ajoutée, prochaine échéance le { $due }
+bill-paid = { $name } payée ({ $amount } { $currency }), prochaine échéance le { $due }
+bill-deleted = Facture { $name } supprimée
+history-current-version = L'enregistrement { $id } est déjà à la version { $version }
+subcategory-added = Sous-catégorie '{ $name }' ajoutée (ID : { $id })
+subcategory-renamed = Sous-catégorie renommée : '{ $old }' → '{ $new }'
+subcategory-deleted = Sous-catégorie '{ $name }' supprimée
+token-created = Jeton '{ $name }' créé. Copiez-le maintenant, il ne sera plus affiché :
+
+    {"  "}{ $token }
+
+    Envoyez-le sous la forme 'Authorization: Bearer { $token }'.
+token-revoked = Jeton '{ $name }' révoqué
+token-dry-run-create = Simulation : rien n'a été écrit. Un jeton nommé '{ $name }' serait créé
+token-dry-run-revoke = Simulation : rien n'a été écrit. Le jeton '{ $name }' serait révoqué
+serve-listening = Métriques servies sur { $url } (Ctrl-C pour arrêter)
+plan-exported = Plan exporté vers : { $path }
+plan-imported = Plan importé depuis { $path } (nouvelles sous-catégories : { $subcategories }, budgets définis : { $budgets }, planifications ajoutées : { $schedules })
+budget-set = Budget de { $name } fixé à { $amount } { $currency } par mois{ $rollover ->
+        [true] , avec report
+       *[other] {""}
+    }
+budget-deleted = Budget de { $name } supprimé
+invest-bought = { $quantity } { $symbol } acheté(s) à { $price } { $currency } l'unité
+close-not-closed = { $month } n'est pas clôturé
+close-reopened = { $month } rouvert
+close-already-closed = { $month } est déjà clôturé
+close-closed = { $month } clôturé avec un solde de { $balance } { $currency }
+schedule-added = Planification n°{ $id } ajoutée, première échéance le { $due }
+schedule-deleted = Planification n°{ $id } supprimée
+schedule-ran = { $count ->
+        [0] Aucun enregistrement planifié n'est dû
+        [one] 1 enregistrement planifié ajouté
+       *[other] { $count } enregistrements planifiés ajoutés
+    }
+tag-renamed = Étiquette renommée : '{ $old }' → '{ $new }' sur { $count } enregistrement(s)
+tag-merged = Étiquette '{ $old }' fusionnée dans '{ $new }' sur { $count } enregistrement(s)
+tag-deleted = { $count ->
+        [0] Étiquette '{ $name }' supprimée
+       *[other] Étiquette '{ $name }' supprimée et retirée de { $count } enregistrement(s)
+    }
+bench-generated = { $count } enregistrement(s) générés dans { $path }
+subscription-added = { $name } ajouté, renouvellement le { $renews }{ $linked ->
+        [0] {""}
+        [one] , 1 paiement antérieur associé
+       *[other] , { $linked } paiements antérieurs associés
+    }
+subscription-deleted = Abonnement { $name } supprimé
+import-done = { $count } enregistrement(s) importé(s) depuis { $path } (nouvelles sous-catégories : { $subcategories })
+import-skipped = { $count } ligne(s) impossible(s) à importer ignorée(s), à commencer par { $first }
+chart-balance-written = Solde du { $from } au { $to } écrit dans { $path }
+clear-cancelled = Effacement annulé.
+clear-archived = Une copie a été enregistrée dans { $path }.
+clear-records-removed = { $count } enregistrement(s) supprimé(s) ; les sous-catégories ont été conservées.
+clear-dry-run = Simulation : rien n'a été supprimé. { $path } serait supprimé, avec { $count } enregistrement(s)
+clear-file-removed = { $path } supprimé.
+clear-done = Toutes les données ont été effacées.
+clear-start-over = Lancez 'fintrack init' pour recommencer.
+tui-done = Terminé
+tui-record-created = Enregistrement { $id } créé
+tui-record-updated = Enregistrement { $id } mis à jour
+tui-deleted = { $count } enregistrement(s) supprimé(s)
+
+## Warnings
+
+add-suggest-subcategory = Cela ressemble à { $name } ; classez-le là avec 'fintrack update { $id } -s "{ $name }"'
+plan-currency-mismatch = Les montants du plan sont en { $plan } mais ce suivi est en { $tracker } ; ils ont été importés tels quels
+limit-daily-over = { $spent } { $currency } dépensés le { $date }, au-delà de votre limite quotidienne de { $limit } { $currency }
+limit-weekly-over = { $spent } { $currency } dépensés la semaine du { $week }, au-delà de votre limite hebdomadaire de { $limit } { $currency }
+budget-threshold-reached = Le budget { $name } de { $month } est dépensé à { $percent } % ({ $spent } sur { $available } { $currency })
+budget-over = Budget { $name } de { $month } dépassé : { $spent } sur { $available } { $currency } dépensés
+serve-unprotected = Attention : quiconque peut joindre { $address } peut lire vos métriques. Créez un jeton avec 'fintrack serve token create'.
+
+## Prompts
+
+add-suggest-prompt = Cela ressemble à { $name }. Le classer là ? (Y/n) :
+edit-problems = Le suivi modifié a des problèmes :
+edit-again-prompt = Le modifier à nouveau ? (Y/n) :
+
+## Command errors
+
+error-argument-missing = Argument requis '{ $name }' non fourni
+error-io = Erreur d'E/S : { $reason }
+error-not-interactive = '{ $command }' n'est pas disponible en mode interactif
+error-empty-name = Nom invalide
+error-file-exists = { $path } existe déjà. Utilisez --force pour l'écraser
+error-read-file = Impossible de lire { $path } : { $reason }
+error-path-missing = Le chemin n'existe pas : { $path }
+error-path-not-directory = Le chemin n'est pas un dossier : { $path }
+error-tracker-currency = Devise invalide dans les données du suivi : { $reason }
+error-schema-compile = Le schéma du suivi n'a pas pu être compilé : { $reason }
+error-offline = { $what } a besoin du réseau, ce que --offline interdit
+error-service-status = { $service } a répondu avec le statut { $status }
+error-service-unreachable = Impossible de joindre { $service } : { $reason }
+error-server-status = le serveur a répondu avec le statut { $status }
+error-config-invalid = Fichier de configuration invalide { $path } : { $reason }
+error-config-write = Échec de l'écriture de la configuration : { $reason }
+error-no-editor = Aucun éditeur indiqué
+error-editor-start = Impossible de lancer { $editor } : { $reason }
+error-editor-failed = { $editor } s'est terminé avec { $status } ; le suivi n'a pas été changé
+error-edit-not-saved = Le suivi modifié n'a pas été enregistré : { $problems }. Votre modification est conservée dans { $path }
+edit-field-invalid = { $path } ne convient pas ({ $message })
+edit-json-invalid = ce n'est pas du JSON valide ({ $message })
+edit-unreadable = il est illisible ({ $kind })
+error-merge-currency = { $path } est en { $other } mais ce suivi est en { $currency }, leurs enregistrements ne peuvent donc pas être additionnés
+error-close-not-ended = { $month } n'est pas encore terminé ; seuls les mois passés peuvent être clôturés
+error-pdf-export = L'export PDF n'est pas encore disponible
+error-no-export-dir = Aucun dossier d'export indiqué. Passez-en un, ou définissez-en un par défaut avec 'fintrack config set export.dir <dossier>'
+error-no-backups = Il n'y a pas encore de sauvegarde
+error-diff-not-found = Aucun fichier ni sauvegarde nommé '{ $file }' ; 'latest' choisit la sauvegarde la plus récente
+error-delete-unfiltered = Indiquez les enregistrements à supprimer avec --ids ou au moins un filtre, comme --subcategory
+error-bench-exists = { $path } existe déjà ; indiquez un autre nom de fichier
+error-watch-unsupported = '{ $command }' ne peut pas être surveillé. Utilisez l'une de ces commandes : { $views }
+error-watch-failed = Échec de la surveillance du suivi : { $reason }
+error-import-line = { $path } ligne { $line } : { $reason } ; rien n'a été importé
+import-row = ligne { $line } : { $reason }
+import-no-column = l'en-tête n'a pas de colonne '{ $column }'
+import-not-amount = '{ $amount }' n'est pas un montant
+import-category-empty = la catégorie est vide
+import-too-many-decimals = { $amount } a trop de décimales
+import-amount-not-positive = le montant doit être supérieur à 0
+import-currency-mismatch = le montant est en { $currency } mais le suivi est en { $tracker }
+import-not-date = '{ $date }' n'est pas une date
+import-closed-month = { $date } est dans un mois clôturé
+import-bad-subcategory = '{ $name }' ne peut pas servir de nom de sous-catégorie
+import-no-subcategory-nearest = il n'y a pas de sous-catégorie '{ $name }' (vouliez-vous dire '{ $nearest }' ?)
+import-no-subcategory = il n'y a pas de sous-catégorie '{ $name }' ; ajoutez --create pour la créer
+error-chart-format = Impossible de savoir dans quel format écrire { $path }. Terminez-le par .svg ou .png
+error-chart-empty = Rien à tracer pour l'instant : aucun enregistrement sur cette période
+error-chart-png = Impossible de dessiner le graphique en PNG : { $reason }
+error-serve-listen = Impossible d'écouter sur { $address } : { $reason }
+error-budget-thresholds = Le seuil d'avertissement ({ $warn } %) doit être inférieur au seuil d'erreur ({ $error } %)
+error-plan-invalid = Fichier de plan invalide { $path } : { $reason }
+error-plan-version = { $path } est un plan en version { $version } ; ce fintrack lit jusqu'à la version { $supported }
+error-plan-write = Échec de l'écriture du plan : { $reason }
+error-no-notify-channel = Aucun canal de notification configuré. Définissez 'notify.ntfy_topic', 'notify.desktop', ou 'notify.telegram_token' et 'notify.telegram_chat_id', avec 'fintrack config set'
+error-desktop-notification = Impossible d'afficher une notification de bureau : { $reason }
+error-notify-send = Échec de l'envoi vers { $channel } : { $reason }
+error-email-not-set-up = L'e-mail n'est pas configuré. Définissez 'email.smtp_host', 'email.from' et 'email.to' avec 'fintrack config set'
+error-email-build = Impossible de préparer l'e-mail : { $reason }
+error-email-send = Échec de l'envoi de l'e-mail : { $reason }
+error-email-address = Adresse e-mail invalide '{ $address }' : { $reason }
+error-smtp-host = Hôte SMTP invalide '{ $host }' : { $reason }
+error-gsheet-no-spreadsheet = Aucune feuille de calcul définie. Définissez-en une avec 'fintrack config set gsheet.spreadsheet_id <id>'
+error-gsheet-no-token = Aucun jeton d'accès Google. Définissez { $variable } (par exemple avec 'gcloud auth print-access-token') ou 'gsheet.access_token'
+error-gsheet-status = Google Sheets a répondu avec le statut { $status } : { $detail }
+error-gsheet-unreachable = Impossible de joindre Google Sheets : { $reason }
+error-hook-failed = Le hook { $hook } a échoué ({ $status })
+error-hook-not-run = Le hook { $hook } n'a pas pu être lancé : { $reason }
+error-token-generate = Impossible de générer un jeton : { $reason }
+error-no-price-service = Aucun service de cours configuré ; définissez 'invest.price_url' ou indiquez les prix avec --price
+error-price-status = Le service de cours a répondu avec le statut { $status } pour { $symbol }
+error-price-unreachable = Impossible de joindre le service de cours : { $reason }
+error-price-missing = Aucun prix à '{ $field }' dans la réponse pour { $symbol }
+error-no-rate = Aucun taux pour { $currency } ; indiquez-en un avec --rate, ajoutez-le à 'rates.manual' ou définissez 'rates.provider'
+error-rates-access-key = Aucune clé d'accès pour exchangerate.host ; définissez 'rates.access_key'
+error-rates-refused = la requête a été refusée
+error-rates-provider = { $provider } : { $reason }
+error-rates-no-result = { $provider } n'a envoyé aucun taux de { $from } vers { $to }
+error-ecb-no-rates = La BCE n'a aucun taux pour le { $date }
+error-ecb-no-currency = La BCE ne publie pas de taux pour { $currency }
+error-amount-range = --min-amount { $min } est supérieur à --max-amount { $max }
+error-where = --where "{ $expression }" : { $reason }
+where-unsupported-op = '{ $op }' ne peut pas être utilisé avec '{ $field }'
+where-already-set = { $field } vaut déjà '{ $value }'
+where-category-set = la catégorie est déjà définie
+where-subcategory-set = la sous-catégorie est déjà définie
+where-deductible = seul 'deductible = true' est pris en charge
+where-use-contains = utilisez '{ $field } ~ TEXTE' pour chercher dans la description
+where-unknown-field = '{ $field }' n'existe pas ; les conditions peuvent porter sur category, subcategory, user, project, tag, deductible, description, amount ou date
+reason-split-not-expense = seules les dépenses peuvent être partagées
+reason-split-over-total = les parts font { $shares } au total, plus que les { $amount } { $currency } dépensés
+reason-rate-not-positive = le taux pour { $currency } doit être supérieur à 0
+reason-name-empty = le nom est vide
+reason-bill-exists = une facture porte déjà ce nom
+reason-token-exists = un jeton porte déjà ce nom
+reason-subscription-exists = un abonnement porte déjà ce nom
+
+## Alerts
+
+alert-budget-title = Budget dépassé : { $name }
+alert-budget-body = { $spent } dépensés sur { $budget } ce mois-ci, { $over } de trop
+alert-bill-overdue-title = Facture en retard : { $name }
+alert-bill-due-title = Facture à payer : { $name }
+alert-bill-overdue = { $amount } à payer le { $due }, il y a { $days ->
+        [one] 1 jour
+       *[other] { $days } jours
+    }
+alert-bill-due = { $amount } { $days ->
+        [0] à payer aujourd'hui
+        [one] à payer le { $due }, dans 1 jour
+       *[other] à payer le { $due }, dans { $days } jours
+    }
+alert-daily-limit-title = Limite quotidienne dépassée
+alert-daily-limit-body = { $spent } dépensés aujourd'hui, pour une limite de { $limit }
+alert-weekly-limit-title = Limite hebdomadaire dépassée
+alert-weekly-limit-body = { $spent } dépensés cette semaine, pour une limite de { $limit }
@@ -1,4 +1,7 @@
-use crate::{CliResult, command_prelude::*};
+use crate::{
+  CliError, CliResult, command_prelude::*, parsers::split_command_line, t,
+  utils::expand::{expand_aliases, insert_default_args},
+};
 use clap::{ArgMatches, Command};
 
 pub type Exec = fn(&mut GlobalContext, &ArgMatches) -> CliResult;
@@ -6,47 +9,186 @@ pub type Exec = fn(&mut GlobalContext, &ArgMatches) -> CliResult;
 pub fn cli() -> Vec<Command> {
   vec![
     add::cli(),
+    balances::cli(),
+    batch::cli(),
+    bench::cli(),
+    bill::cli(),
+    bills::cli(),
+    budget::cli(),
     category::cli(),
+    chart::cli(),
     clear::cli(),
+    close::cli(),
+    config::cli(),
     delete::cli(),
     describe::cli(),
+    diff::cli(),
+    digest::cli(),
     dump::cli(),
+    edit_file::cli(),
     export::cli(),
+    history::cli(),
+    import::cli(),
     init::cli(),
+    invest::cli(),
     list::cli(),
+    merge::cli(),
+    notify::cli(),
+    owed::cli(),
+    plan::cli(),
+    project::cli(),
+    quick::cli(),
+    reconcile::cli(),
+    report::cli(),
+    schedule::cli(),
+    schema::cli(),
+    search::cli(),
+    serve::cli(),
     subcategory::cli(),
+    subscriptions::cli(),
+    shell::cli(),
+    status::cli(),
+    tag::cli(),
     total::cli(),
+    tui::cli(),
     update::cli(),
+    watch::cli(),
   ]
 }
 
 pub fn build_exec(cmd: &str) -> Option<Exec> {
   match cmd {
     "add" => Some(add::exec),
+    "balances" => Some(balances::exec),
+    "batch" => Some(batch::exec),
+    "bench" => Some(bench::exec),
+    "bill" => Some(bill::exec),
+    "bills" => Some(bills::exec),
+    "budget" => Some(budget::exec),
     "category" => Some(category::exec),
+    "chart" => Some(chart::exec),
     "clear" => Some(clear::exec),
+    "close" => Some(close::exec),
+    "config" => Some(config::exec),
     "delete" => Some(delete::exec),
     "describe" => Some(describe::exec),
+    "diff" => Some(diff::exec),
+    "digest" => Some(digest::exec),
     "dump" => Some(dump::exec),
+    "edit-file" => Some(edit_file::exec),
     "export" => Some(export::exec),
+    "history" => Some(history::exec),
+    "import" => Some(import::exec),
     "init" => Some(init::exec),
+    "invest" => Some(invest::exec),
     "list" => Some(list::exec),
+    "merge" => Some(merge::exec),
+    "notify" => Some(notify::exec),
+    "owed" => Some(owed::exec),
+    "plan" => Some(plan::exec),
+    "project" => Some(project::exec),
+    "quick" => Some(quick::exec),
+    "reconcile" => Some(reconcile::exec),
+    "report" => Some(report::exec),
+    "schedule" => Some(schedule::exec),
+    "schema" => Some(schema::exec),
+    "search" => Some(search::exec),
+    "serve" => Some(serve::exec),
     "subcategory" => Some(subcategory::exec),
+    "subscriptions" => Some(subscriptions::exec),
+    "shell" => Some(shell::exec),
+    "status" => Some(status::exec),
+    "tag" => Some(tag::exec),
     "total" => Some(total::exec),
+    "tui" => Some(tui::exec),
     "update" => Some(update::exec),
+    "watch" => Some(watch::exec),
     _ => None,
   }
 }
 
+/// Commands that take over the terminal or prompt on stdin, and so cannot be
+/// run from inside the interactive front-ends.
+pub(crate) const NON_INTERACTIVE: [&str; 7] =
+  ["batch", "clear", "edit-file", "serve", "shell", "tui", "watch"];
+
+/// The command tree used to parse lines typed into an interactive front-end,
+/// i.e. every subcommand without the leading `fintrack`.
+pub fn line_command() -> Command {
+  Command::new("fintrack")
+    .no_binary_name(true)
+    .subcommand_required(true)
+    .subcommands(cli())
+}
+
+/// Parse and run one command line (without the leading `fintrack`), as typed
+/// into an interactive front-end. Errors from argument parsing are reported as
+/// `CliError::Other` with clap's message.
+pub fn exec_line(gctx: &mut GlobalContext, line: &str) -> CliResult {
+  let root = line_command();
+  let argv = split_command_line(line).map_err(CliError::Other)?;
+  let argv = expand_aliases(argv, &gctx.config().aliases, &root).map_err(CliError::Other)?;
+  let (root, argv) = insert_default_args(root, argv, gctx.config()).map_err(CliError::Other)?;
+
+  let matches = root
+    .try_get_matches_from(argv)
+    .map_err(|e| CliError::Other(e.render().to_string().trim().to_string()))?;
+
+  let (cmd, args) = matches
+    .subcommand()
+    .expect("subcommand required but not found");
+
+  if NON_INTERACTIVE.contains(&cmd) {
+    return Err(CliError::Other(t!("error-not-interactive", command = cmd)));
+  }
+
+  let exec_fn = build_exec(cmd).ok_or_else(|| crate::invalid_subcommand_error(cmd))?;
+  let result = exec_fn(gctx, args);
+  crate::utils::storage::finish_dry_run(gctx, result)
+}
+
 pub mod add;
+pub mod balances;
+pub mod batch;
+pub mod bench;
+pub mod bill;
+pub mod bills;
+pub mod budget;
 pub mod category;
+pub mod chart;
 pub mod clear;
+pub mod close;
+pub mod config;
 pub mod delete;
 pub mod describe;
+pub mod diff;
+pub mod digest;
 pub mod dump;
+pub mod edit_file;
 pub mod export;
+pub mod history;
+pub mod import;
 pub mod init;
+pub mod invest;
 pub mod list;
+pub mod merge;
+pub mod notify;
+pub mod owed;
+pub mod plan;
+pub mod project;
+pub mod quick;
+pub mod reconcile;
+pub mod report;
+pub mod schedule;
+pub mod schema;
+pub mod search;
+pub mod serve;
+pub mod shell;
+pub mod status;
 pub mod subcategory;
+pub mod subscriptions;
+pub mod tag;
 pub mod total;
+pub mod tui;
 pub mod update;
+pub mod watch;
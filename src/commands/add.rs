@@ -1,11 +1,23 @@
-use chrono::Local;
-use clap::{Arg, ArgMatches, Command};
+use std::io::{self, Write};
+
+use chrono::NaiveDate;
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, ArgMatches, Command};
 
 use crate::command_prelude::ArgMatchesExt;
-use crate::utils::file::{FilePath, write_json_to_file};
-use crate::utils::parsers::{parse_category, parse_date};
+use crate::commands::subcategory;
+use crate::utils::storage::{AfterSave, after_save, ensure_writable, load_tracker, save_tracker};
+use crate::utils::validation::{check_duplicate, check_open, check_strict, decimals, round_amount};
+use crate::output::{format_amount, record_json};
+use crate::utils::hooks::{self, Hook};
+use crate::utils::classify;
+use crate::utils::limits;
+use crate::utils::rates;
+use crate::utils::parsers::{parse_amount, parse_category, parse_split, parse_tag};
+use crate::utils::webhook::{self, Event};
 use crate::{
-  CliError, CliResponse, CliResult, GlobalContext, Record, ResponseContent, TrackerData,
+  Category, CliError, CliResponse, CliResult, Currency, ForeignAmount, GlobalContext, OwedEntry,
+  Record, ResponseContent, RoundingMode, Share, Split, TrackerData, ValidationErrorKind, t,
 };
 
 pub fn cli() -> Command {
@@ -24,7 +36,7 @@ pub fn cli() -> Command {
       Arg::new("amount")
         .index(2)
         .required(true)
-        .value_parser(clap::value_parser!(f64))
+        .value_parser(parse_amount)
         .help("Transaction amount (must be greater than 0)")
         .long_help("The amount of money for this transaction. Must be a positive number greater than 0. Examples: 100, 150.50, 2000.75"),
     )
@@ -35,7 +47,13 @@ pub fn cli() -> Command {
         .value_parser(clap::value_parser!(String))
         .default_value("miscellaneous")
         .help("Subcategory name for this transaction")
-        .long_help("A more specific category for this transaction (e.g., 'Groceries', 'Salary', 'Rent'). Must already exist - use 'fintrack subcategory list' to see available subcategories. Defaults to 'miscellaneous' if not specified."),
+        .long_help("A more specific category for this transaction (e.g., 'Groceries', 'Salary', 'Rent'). Must already exist unless --create is given - use 'fintrack subcategory list' to see available subcategories. Defaults to 'miscellaneous' if not specified."),
+    )
+    .arg(
+      Arg::new("create")
+        .long("create")
+        .action(ArgAction::SetTrue)
+        .help("Create the subcategory if it doesn't exist yet"),
     )
     .arg(
       Arg::new("description")
@@ -49,18 +67,73 @@ pub fn cli() -> Command {
       Arg::new("date")
         .short('D')
         .long("date")
-        .value_parser(parse_date)
+        .value_parser(clap::value_parser!(String))
         .help("Transaction date in DD-MM-YYYY format")
-        .long_help("The date when this transaction occurred. Format: DD-MM-YYYY (e.g., 30-12-2025). Defaults to today's date if not specified."),
+        .long_help("The date when this transaction occurred. Format: DD-MM-YYYY (e.g., 30-12-2025), or any format listed in the 'input.date_formats' setting. Defaults to today's date if not specified, in the 'general.timezone' time zone when that is set."),
+    )
+    .arg(
+      Arg::new("by")
+        .short('u')
+        .long("by")
+        .value_parser(clap::value_parser!(String))
+        .help("Who entered this transaction")
+        .long_help("The person this transaction is attributed to, for trackers shared by several people. Defaults to the 'general.user' setting, or to $USER when that isn't set."),
+    )
+    .arg(
+      Arg::new("deductible")
+        .long("deductible")
+        .action(ArgAction::SetTrue)
+        .help("Mark the expense as tax-deductible")
+        .long_help("Marks the record as tax-deductible, so it's counted by 'fintrack report --deductions' and shown by 'fintrack list --deductible'."),
+    )
+    .arg(
+      Arg::new("project")
+        .long("project")
+        .value_parser(clap::value_parser!(String))
+        .help("Trip or event this transaction belongs to, e.g. \"Lagos trip\"")
+        .long_help("Groups the record with others for the same trip or event, across subcategories. See what a project cost with 'fintrack project status <name>'. Names are matched case-insensitively."),
+    )
+    .arg(
+      Arg::new("tag")
+        .long("tag")
+        .value_name("TAG")
+        .action(ArgAction::Append)
+        .value_delimiter(',')
+        .value_parser(parse_tag)
+        .help("Tag the record, e.g. vacation; repeat or separate with commas for several")
+        .long_help("Labels the record so it can be found and totalled with others that share the tag, whatever their subcategory. Tags are matched case-insensitively and keep the spelling they were first used with; see them with 'fintrack tag list'."),
+    )
+    .arg(
+      Arg::new("currency")
+        .long("currency")
+        .value_parser(clap::value_parser!(Currency))
+        .ignore_case(true)
+        .help("Currency the amount was paid or received in, if not the tracker's")
+        .long_help("The currency AMOUNT is in, for money spent or received abroad. The record is kept in the tracker's currency, converted at --rate or, without it, at the rate for the record's date from 'rates.provider'. It remembers the original amount so 'total' and 'describe' can break totals down by currency."),
+    )
+    .arg(
+      Arg::new("rate")
+        .long("rate")
+        .value_parser(parse_amount)
+        .requires("currency")
+        .help("How much one unit of --currency is worth in the tracker's currency"),
+    )
+    .arg(
+      Arg::new("split-with")
+        .long("split-with")
+        .value_parser(parse_split)
+        .action(ArgAction::Append)
+        .help("Share an expense, e.g. alice:50%, bob:20 or carol (repeatable)")
+        .long_help("Someone who owes you part of this expense: a percentage (alice:50%), an amount (bob:20), or just a name for an even split of what's left with you. Their parts go on the ledger shown by 'fintrack balances'; your balance still shows the full amount you paid."),
     )
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
-  let mut file = gctx.tracker_path().open_read_write()?;
-  let mut tracker_data: TrackerData = serde_json::from_reader(&file)?;
+  let mut tracker_data = load_tracker(gctx)?;
 
   let category = args.get_category("category")?;
-  let amount = args.get_f64_or_default("amount");
+  let date = args.get_date_opt("date")?.unwrap_or_else(|| gctx.today());
+  let (amount, foreign) = converted_amount(gctx, &tracker_data, args, date)?;
 
   if amount <= 0.0 {
     return Err(CliError::ValidationError(
@@ -74,18 +147,41 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
   let category_str = category.to_string();
   let category_id = tracker_data.category_id(&category_str);
 
-  let subcategory_id = tracker_data
-    .subcategory_id(&subcategory_name)
-    .ok_or_else(|| {
-      CliError::ValidationError(crate::ValidationErrorKind::SubcategoryNotFound {
-        name: subcategory_name,
-      })
-    })?;
+  let subcategory_id = match tracker_data.subcategory_id(&subcategory_name) {
+    Some(id) => id,
+    None if args.get_flag("create") => {
+      gctx.verbose(format!("Creating subcategory '{}'", subcategory_name));
+      subcategory::add::create(&mut tracker_data, &subcategory_name)?
+    }
+    None => return Err(tracker_data.subcategory_not_found(subcategory_name)),
+  };
+
+  // Without -s, offer the subcategory records like this one usually go in
+  let mut hints = Vec::new();
+  let suggested = (args.value_source("subcategory") == Some(ValueSource::DefaultValue))
+    .then(|| classify::suggest(&tracker_data, category_id, &description))
+    .flatten();
+  let subcategory_id = match suggested {
+    Some(suggested) => {
+      let name = tracker_data.subcategory_name(suggested).cloned().unwrap_or_default();
+      gctx.verbose(format!("'{}' looks like {}", description, name));
+      if gctx.is_interactive() {
+        if ask_subcategory(&name)? { suggested } else { subcategory_id }
+      } else {
+        hints.push(t!("add-suggest-subcategory", name = name.as_str(), id = tracker_data.next_record_id));
+        subcategory_id
+      }
+    }
+    None => subcategory_id,
+  };
 
-  let date = args
-    .get_date_opt("date")
-    .map(|d| d.format("%d-%m-%Y").to_string())
-    .unwrap_or_else(|| Local::now().format("%d-%m-%Y").to_string());
+  let mut tags: Vec<String> = Vec::new();
+  for tag in args.get_vec::<String>("tag") {
+    let tag = tracker_data.tag_name(&tag);
+    if !tags.contains(&tag) {
+      tags.push(tag);
+    }
+  }
 
   let record_id = tracker_data.next_record_id;
   let record = Record {
@@ -94,19 +190,193 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     amount,
     subcategory: subcategory_id,
     description,
-    date,
+    date: date.format("%d-%m-%Y").to_string(),
+    entered_by: entered_by(gctx, args),
+    deductible: args.get_flag("deductible"),
+    project: args
+      .get_string_opt("project")
+      .map(|project| project.trim().to_string())
+      .filter(|project| !project.is_empty())
+      .map(|project| tracker_data.project_name(&project)),
+    foreign,
+    tags,
+    parsed_date: Default::default(),
+  };
+
+  let splits: Vec<Split> = args.get_vec("split-with");
+  if !splits.is_empty() {
+    if *category != Category::Expenses {
+      return Err(CliError::ValidationError(ValidationErrorKind::InvalidAmount {
+        reason: t!("reason-split-not-expense"),
+      }));
+    }
+    let shares = split_shares(gctx, &tracker_data, &record, &splits)?;
+    tracker_data.owed.extend(shares);
+  }
+
+  let budget_warnings = insert_record(gctx, &mut tracker_data, record.clone())?;
+  let mut warnings = limits::warnings(gctx, &tracker_data, &record);
+  warnings.extend(budget_warnings);
+  warnings.extend(hints);
+
+  Ok(
+    CliResponse::new(ResponseContent::Record {
+      record,
+      tracker_data,
+      is_update: false,
+    })
+    .with_warnings(warnings),
+  )
+}
+
+/// "Looks like Groceries. File it there? (Y/n)"; anything but no is yes
+fn ask_subcategory(name: &str) -> Result<bool, CliError> {
+  print!("{} ", t!("add-suggest-prompt", name = name));
+  io::stdout().flush()?;
+
+  let mut input = String::new();
+  io::stdin().read_line(&mut input)?;
+  Ok(!matches!(input.trim().to_lowercase().as_str(), "n" | "no"))
+}
+
+/// The amount to record in the tracker's currency, and the original when
+/// `--currency` names another one. `--rate` converts it, or else the
+/// configured rate provider; rates are the tracker's currency per unit of the
+/// other.
+fn converted_amount(
+  gctx: &GlobalContext,
+  tracker_data: &TrackerData,
+  args: &ArgMatches,
+  date: NaiveDate,
+) -> Result<(f64, Option<ForeignAmount>), CliError> {
+  let typed = args.get_f64_or_default("amount");
+  let currency = args
+    .get_one::<Currency>("currency")
+    .map(Currency::to_string)
+    .filter(|currency| !currency.eq_ignore_ascii_case(&tracker_data.currency));
+  let Some(currency) = currency else {
+    return Ok((round_amount(gctx, &tracker_data.currency, typed)?, None));
+  };
+
+  let rate = match args.get_one::<f64>("rate") {
+    Some(rate) => *rate,
+    None => rates::lookup(gctx, &tracker_data.currency, &currency, &tracker_data.currency, date)?,
+  };
+  if rate <= 0.0 {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidAmount {
+      reason: t!("reason-rate-not-positive", currency = currency.as_str()),
+    }));
+  }
+  let original = round_amount(gctx, &currency, typed)?;
+  // The product rarely fits the currency's precision, whatever the rounding setting
+  let converted = original * rate;
+  let amount = RoundingMode::HalfUp
+    .round(converted, decimals(gctx, &tracker_data.currency))
+    .unwrap_or(converted);
+  gctx.verbose(format!(
+    "Converted {} {} at {} to {} {}",
+    format_amount(original),
+    currency,
+    rate,
+    format_amount(amount),
+    tracker_data.currency
+  ));
+
+  let foreign = ForeignAmount {
+    currency,
+    amount: original,
+    rate,
+    rate_date: date.format("%d-%m-%Y").to_string(),
   };
+  Ok((amount, Some(foreign)))
+}
+
+/// Check, save and announce a new record the way `add` does: validation
+/// settings, hooks and the webhook all apply. Also used by commands that
+/// create records for the user, such as `bill pay`. Gives the budget warnings
+/// the record set off.
+pub(crate) fn insert_record(
+  gctx: &mut GlobalContext,
+  tracker_data: &mut TrackerData,
+  record: Record,
+) -> Result<Vec<String>, CliError> {
+  check_open(tracker_data, record.naive_date())?;
+  check_strict(gctx, &record, tracker_data)?;
+  check_duplicate(gctx, &record, tracker_data)?;
+
+  // Check before the pre-add hook, which would otherwise run for nothing
+  ensure_writable(gctx)?;
+  hooks::run(gctx, Hook::PreAdd, &record_json(&record, tracker_data))?;
 
   tracker_data.next_record_id += 1;
   tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  tracker_data.link_subscriptions(&record);
   tracker_data.push_record(record.clone());
+  tracing::info!(id = record.id, amount = record.amount, "record added");
+
+  save_tracker(gctx, tracker_data)?;
+  after_save(gctx, AfterSave::Hook(Hook::PostAdd, record_json(&record, tracker_data)));
+  webhook::notify(gctx, Event::Add, std::slice::from_ref(&record), tracker_data);
+
+  Ok(limits::budget_warnings(tracker_data, &record))
+}
 
-  let tracker_json = serde_json::json!(tracker_data);
-  write_json_to_file(&tracker_json, &mut file)?;
+/// Ledger entries for what each person in `--split-with` owes of `record`
+fn split_shares(
+  gctx: &GlobalContext,
+  tracker_data: &TrackerData,
+  record: &Record,
+  splits: &[Split],
+) -> Result<Vec<OwedEntry>, CliError> {
+  let currency = &tracker_data.currency;
+  // `None` for an even split, which depends on everyone else's part
+  let fixed = |split: &Split| match split.share {
+    Share::Percent(percent) => Some(record.amount * percent / 100.0),
+    Share::Amount(amount) => Some(amount),
+    Share::Equal => None,
+  };
+  let explicit: f64 = splits.iter().filter_map(fixed).sum();
+  if explicit > record.amount + 0.005 {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidAmount {
+      reason: t!(
+        "reason-split-over-total",
+        shares = format_amount(explicit),
+        amount = format_amount(record.amount),
+        currency = currency.as_str()
+      ),
+    }));
+  }
+
+  // Everyone splitting evenly, you included, gets the same part of the rest
+  let equal = splits.iter().filter(|split| split.share == Share::Equal).count();
+  let even_share = (record.amount - explicit) / (equal + 1) as f64;
+
+  let first_id = tracker_data.owed.iter().map(|entry| entry.id).max().unwrap_or(0) + 1;
+  splits
+    .iter()
+    .enumerate()
+    .map(|(index, split)| {
+      Ok(OwedEntry {
+        id: first_id + index,
+        person: tracker_data.owed_name(&split.person),
+        amount: round_amount(gctx, currency, fixed(split).unwrap_or(even_share))?,
+        date: record.date.clone(),
+        due: None,
+        description: record.description.clone(),
+        settlement: false,
+        record: Some(record.id),
+      })
+    })
+    .collect()
+}
 
-  Ok(CliResponse::new(ResponseContent::Record {
-    record,
-    tracker_data,
-    is_update: false,
-  }))
+/// `--by`, then the `general.user` setting, then `$USER`
+pub(crate) fn entered_by(gctx: &GlobalContext, args: &ArgMatches) -> Option<String> {
+  args
+    .get_one::<String>("by")
+    .cloned()
+    .or_else(|| gctx.config().general.user.clone())
+    .or_else(|| std::env::var("USER").ok())
+    .map(|name| name.trim().to_string())
+    .filter(|name| !name.is_empty())
 }
@@ -0,0 +1,18 @@
+use clap::{ArgMatches, Command};
+
+use crate::commands::owed::{list, settle};
+use crate::{CliResult, GlobalContext};
+
+pub fn cli() -> Command {
+  Command::new("balances")
+    .about("Show who owes whom from shared expenses and loans")
+    .long_about("Lists what each person owes you, or you owe them, from expenses split with 'fintrack add --split-with' and loans from 'fintrack owed'. Zero a balance out with 'fintrack balances settle <person>'.")
+    .subcommand(settle::cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some(("settle", sub_args)) => settle::exec(gctx, sub_args),
+    _ => list::exec(gctx, args),
+  }
+}
@@ -0,0 +1,66 @@
+use std::fs;
+use std::io::{self, Read};
+
+use clap::{Arg, ArgMatches, Command};
+use colored::Colorize;
+
+use crate::utils::storage::{discard_session, end_session, ensure_writable, start_session};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, commands, t};
+
+pub fn cli() -> Command {
+  Command::new("batch")
+    .about("Run many commands from a file as one change")
+    .long_about("Runs the commands in a script file, one per line and written as you would after 'fintrack' (e.g. 'add expenses 12 -s Food'). Blank lines and lines starting with '#' are skipped. The tracker is loaded once and written once at the end: if any command fails, nothing is saved. Post-add and post-delete hooks and the webhook run once the tracker is written, so a failed batch doesn't report changes that never happened.")
+    .arg(
+      Arg::new("file")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Script file to run, or '-' to read from stdin"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let file = args.get_one::<String>("file").expect("file is required");
+  let script = read_script(file)?;
+
+  ensure_writable(gctx)?;
+  start_session(gctx)?;
+
+  let mut count = 0;
+  for (index, line) in script.lines().enumerate() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+
+    gctx.verbose(format!("Line {}: {}", index + 1, line));
+    if let Err(err) = commands::exec_line(gctx, line) {
+      discard_session(gctx);
+      eprintln!("{}", t!("batch-stopped", line = index + 1, command = line).dimmed());
+      return Err(err);
+    }
+    count += 1;
+  }
+
+  end_session(gctx)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "batch-ran",
+    count = count,
+    file = if file == "-" { "stdin" } else { file }
+  ))))
+}
+
+fn read_script(file: &str) -> Result<String, CliError> {
+  if file == "-" {
+    let mut script = String::new();
+    io::stdin().read_to_string(&mut script)?;
+    return Ok(script);
+  }
+
+  fs::read_to_string(file).map_err(|err| match err.kind() {
+    io::ErrorKind::NotFound => CliError::FileNotFound(file.to_string()),
+    _ => err.into(),
+  })
+}
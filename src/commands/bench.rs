@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use clap::{Arg, ArgMatches, Command};
+
+use crate::utils::file::{FilePath, write_json_to_file};
+use crate::utils::synthetic;
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, t};
+
+pub fn cli() -> Command {
+  Command::new("bench")
+    .about("Generate a large made-up tracker for performance testing")
+    .long_about("Writes a tracker file full of made-up records, to see how FinTrack performs with a lot of data or to reproduce a slowdown. Your own tracker is not touched. The same record count always produces the same data.")
+    .arg(
+      Arg::new("generate")
+        .long("generate")
+        .required(true)
+        .value_parser(clap::value_parser!(usize))
+        .help("Number of records to generate, e.g. 100000"),
+    )
+    .arg(
+      Arg::new("file")
+        .index(1)
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("File to write (default: fintrack_bench_<count>.json)")
+        .long_help("Where to write the generated tracker. Defaults to fintrack_bench_<count>.json in the current directory. An existing file is not overwritten."),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let count = *args.get_one::<usize>("generate").expect("generate is required");
+  let path = args
+    .get_one::<PathBuf>("file")
+    .cloned()
+    .unwrap_or_else(|| PathBuf::from(format!("fintrack_bench_{}.json", count)));
+
+  if path.exists() {
+    return Err(CliError::Other(t!(
+      "error-bench-exists",
+      path = path.display().to_string()
+    )));
+  }
+
+  let data = synthetic::tracker(count);
+  gctx.verbose(format!("Writing {} record(s) to {}", count, path.display()));
+  let mut file = path.create_file_if_not_exists()?;
+  write_json_to_file(&data, &mut file, gctx.config().storage.compact)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "bench-generated",
+    count = count,
+    path = path.display().to_string()
+  ))))
+}
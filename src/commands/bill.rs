@@ -0,0 +1,52 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("bill")
+    .about("Manage recurring bills")
+    .long_about("Keeps track of bills that come due on a schedule, like rent or electricity: add them with 'bill add', record a payment with 'bill pay', and see what's coming up with 'fintrack bills'.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![add::cli(), delete::cli(), pay::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "add" => Some(add::exec),
+    "delete" => Some(delete::exec),
+    "pay" => Some(pay::exec),
+    _ => None,
+  }
+}
+
+/// Position of the bill called `name`, ignoring case
+pub(crate) fn find_bill(tracker_data: &crate::TrackerData, name: &str) -> Result<usize, crate::CliError> {
+  tracker_data
+    .bills
+    .iter()
+    .position(|bill| bill.name.eq_ignore_ascii_case(name.trim()))
+    .ok_or_else(|| {
+      crate::CliError::ValidationError(crate::ValidationErrorKind::BillNotFound {
+        name: name.trim().to_string(),
+      })
+    })
+}
+
+pub mod add;
+pub mod delete;
+pub mod pay;
@@ -0,0 +1,102 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::parsers::{parse_amount, parse_due};
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::utils::validation::round_amount;
+use crate::{
+  Bill, Frequency, CliError, CliResponse, CliResult, GlobalContext, ResponseContent,
+  ValidationErrorKind, t,
+};
+
+pub fn cli() -> Command {
+  Command::new("add")
+    .about("Add a recurring bill")
+    .long_about("Adds a bill that comes due on a schedule. Nothing is recorded until you pay it with 'fintrack bill pay'.")
+    .arg(
+      Arg::new("name")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Name of the bill, e.g. Electricity"),
+    )
+    .arg(
+      Arg::new("amount")
+        .index(2)
+        .required(true)
+        .value_parser(parse_amount)
+        .help("Usual amount of the bill (must be greater than 0)"),
+    )
+    .arg(
+      Arg::new("due")
+        .long("due")
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Day of the month it's due (1-31), or the next due date")
+        .long_help("When the bill is next due: a day of the month such as 10, meaning the next 10th, or a date in DD-MM-YYYY or any format listed in the 'input.date_formats' setting. Days past the end of a month fall on its last day."),
+    )
+    .arg(
+      Arg::new("every")
+        .long("every")
+        .value_parser(clap::value_parser!(Frequency))
+        .default_value("month")
+        .help("How often the bill comes due"),
+    )
+    .arg(
+      Arg::new("subcategory")
+        .short('s')
+        .long("subcategory")
+        .value_parser(clap::value_parser!(String))
+        .default_value("miscellaneous")
+        .help("Subcategory for the expense recorded when it's paid"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let name = args.get_string_or_default("name").trim().to_string();
+  if name.is_empty() {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidName {
+      name,
+      reason: t!("reason-name-empty"),
+    }));
+  }
+  if tracker_data.bills.iter().any(|bill| bill.name.eq_ignore_ascii_case(&name)) {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidName {
+      name,
+      reason: t!("reason-bill-exists"),
+    }));
+  }
+
+  let amount = round_amount(gctx, &tracker_data.currency, args.get_f64_or_default("amount"))?;
+  if amount <= 0.0 {
+    return Err(CliError::ValidationError(ValidationErrorKind::AmountTooSmall { amount }));
+  }
+
+  let subcategory_name = args.get_subcategory_or_default("subcategory");
+  let subcategory = tracker_data
+    .subcategory_id(&subcategory_name)
+    .and_then(|id| tracker_data.subcategories_by_id.get(&id).cloned())
+    .ok_or_else(|| tracker_data.subcategory_not_found(subcategory_name))?;
+
+  let (due, day) = parse_due(&args.get_string_or_default("due"), gctx.today())
+    .map_err(CliError::ValidationError)?;
+  let bill = Bill {
+    name: name.clone(),
+    amount,
+    subcategory,
+    every: args.get_one::<Frequency>("every").copied().unwrap_or_default(),
+    due: due.format("%d-%m-%Y").to_string(),
+    day,
+  };
+  tracker_data.bills.push(bill);
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "bill-added",
+    name = name.as_str(),
+    due = due.format("%d-%m-%Y").to_string()
+  ))))
+}
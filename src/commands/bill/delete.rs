@@ -0,0 +1,30 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::commands::bill::find_bill;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent, t};
+
+pub fn cli() -> Command {
+  Command::new("delete")
+    .about("Stop tracking a bill")
+    .long_about("Removes a bill. Expenses already recorded by 'fintrack bill pay' are kept.")
+    .arg(
+      Arg::new("name")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Name of the bill (case-insensitive)"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+  let index = find_bill(&tracker_data, &args.get_string_or_default("name"))?;
+
+  let bill = tracker_data.bills.remove(index);
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!("bill-deleted", name = bill.name.as_str()))))
+}
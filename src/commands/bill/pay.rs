@@ -0,0 +1,101 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::commands::add::{entered_by, insert_record};
+use crate::commands::bill::find_bill;
+use crate::output::format_amount;
+use crate::parsers::parse_amount;
+use crate::utils::storage::load_tracker;
+use crate::utils::validation::round_amount;
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, Record, ResponseContent, ValidationErrorKind, t,
+};
+
+pub fn cli() -> Command {
+  Command::new("pay")
+    .about("Record a bill payment and move it to its next due date")
+    .long_about("Adds an expense for the bill, in the bill's subcategory with its name as the description, then moves the bill on to its next due date. Validation settings, hooks and the webhook apply as they do for 'fintrack add'.")
+    .arg(
+      Arg::new("name")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Name of the bill (case-insensitive)"),
+    )
+    .arg(
+      Arg::new("amount")
+        .short('a')
+        .long("amount")
+        .value_parser(parse_amount)
+        .help("Amount paid, when it differs from the bill's usual amount"),
+    )
+    .arg(
+      Arg::new("date")
+        .short('D')
+        .long("date")
+        .value_parser(clap::value_parser!(String))
+        .help("Payment date in DD-MM-YYYY format; defaults to today"),
+    )
+    .arg(
+      Arg::new("by")
+        .short('u')
+        .long("by")
+        .value_parser(clap::value_parser!(String))
+        .help("Who paid the bill"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+  let index = find_bill(&tracker_data, &args.get_string_or_default("name"))?;
+
+  let amount = match args.get_f64_opt("amount") {
+    Some(amount) => round_amount(gctx, &tracker_data.currency, amount)?,
+    None => tracker_data.bills[index].amount,
+  };
+  if amount <= 0.0 {
+    return Err(CliError::ValidationError(ValidationErrorKind::AmountTooSmall { amount }));
+  }
+
+  let bill = &tracker_data.bills[index];
+  let subcategory = tracker_data
+    .subcategory_id(&bill.subcategory)
+    .ok_or_else(|| {
+      CliError::ValidationError(ValidationErrorKind::SubcategoryNotFound {
+        name: bill.subcategory.clone(),
+        nearest: None,
+      })
+    })?;
+  let date = args.get_date_opt("date")?.unwrap_or_else(|| gctx.today());
+
+  let record = Record {
+    id: tracker_data.next_record_id,
+    category: tracker_data.category_id("expenses"),
+    amount,
+    subcategory,
+    description: bill.name.clone(),
+    date: date.format("%d-%m-%Y").to_string(),
+    entered_by: entered_by(gctx, args),
+    deductible: false,
+    project: None,
+    foreign: None,
+    tags: Vec::new(),
+    parsed_date: Default::default(),
+  };
+
+  // Saved along with the record, so a rejected payment leaves the bill alone
+  tracker_data.bills[index].advance();
+  let warnings = insert_record(gctx, &mut tracker_data, record)?;
+
+  let bill = &tracker_data.bills[index];
+  Ok(
+    CliResponse::new(ResponseContent::Message(t!(
+      "bill-paid",
+      name = bill.name.as_str(),
+      amount = format_amount(amount),
+      currency = tracker_data.currency.as_str(),
+      due = bill.due.as_str()
+    )))
+    .with_warnings(warnings),
+  )
+}
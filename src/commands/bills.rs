@@ -0,0 +1,19 @@
+use clap::{ArgMatches, Command};
+
+use crate::utils::storage::load_tracker;
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("bills")
+    .about("Show upcoming and overdue bills")
+    .long_about("Lists every bill added with 'fintrack bill add', soonest first, with how many days are left until each is due or how long it has been overdue.")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+
+  Ok(CliResponse::new(ResponseContent::Bills {
+    bills: tracker_data.bill_statuses(gctx.today()),
+    currency: tracker_data.currency,
+  }))
+}
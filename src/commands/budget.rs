@@ -0,0 +1,39 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("budget")
+    .about("Manage monthly budgets")
+    .long_about("Sets a monthly spending limit per subcategory with 'budget set', and shows how this month's expenses compare with 'budget list'. 'fintrack total' also shows what's left.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![delete::cli(), list::cli(), set::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "delete" => Some(delete::exec),
+    "list" => Some(list::exec),
+    "set" => Some(set::exec),
+    _ => None,
+  }
+}
+
+pub mod delete;
+pub mod list;
+pub mod set;
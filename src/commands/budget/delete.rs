@@ -0,0 +1,43 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind, t};
+
+pub fn cli() -> Command {
+  Command::new("delete")
+    .about("Remove a subcategory's budget")
+    .arg(
+      Arg::new("subcategory")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Subcategory whose budget to remove (case-insensitive)"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let name = args.get_subcategory_or_default("subcategory");
+  let not_found = || {
+    CliError::ValidationError(ValidationErrorKind::BudgetNotFound {
+      subcategory: name.clone(),
+    })
+  };
+  let subcategory = tracker_data.subcategory_id(&name).ok_or_else(not_found)?;
+  let index = tracker_data
+    .budgets
+    .iter()
+    .position(|b| b.subcategory == subcategory)
+    .ok_or_else(not_found)?;
+
+  tracker_data.budgets.remove(index);
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "budget-deleted",
+    name = tracker_data.subcategory_name(subcategory).cloned().unwrap_or(name)
+  ))))
+}
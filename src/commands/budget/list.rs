@@ -0,0 +1,23 @@
+use chrono::{Datelike, Days, Months};
+use clap::{ArgMatches, Command};
+
+use crate::utils::storage::load_tracker;
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("list")
+    .about("Show this month's spending against each budget")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+
+  let start = gctx.today().with_day(1).expect("day 1 exists");
+  let end = start + Months::new(1) - Days::new(1);
+
+  Ok(CliResponse::new(ResponseContent::Budgets {
+    month: start.format("%m-%Y").to_string(),
+    budgets: tracker_data.budget_statuses(start, end),
+    currency: tracker_data.currency,
+  }))
+}
@@ -0,0 +1,123 @@
+use chrono::Datelike;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::output::format_amount;
+use crate::parsers::{STORAGE_DATE_FORMAT, parse_amount, parse_percent};
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::utils::validation::round_amount;
+use crate::{
+  Budget, CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind, t,
+};
+
+pub fn cli() -> Command {
+  Command::new("set")
+    .about("Set the monthly budget for a subcategory")
+    .long_about("Sets how much you mean to spend on a subcategory each month, replacing any budget it already has. With --rollover, what's left at the end of a month is added to the next month's budget and overspending is taken off it, starting from this month; --no-rollover turns that off again. Leaving both out keeps the budget's current setting.\n\n--warn-at and --error-at set how much of the month's budget can go before 'fintrack add' warns and 'budget list' shows it in yellow, and then in red. They default to no warning and 100%, and are kept when a budget is set again without them.")
+    .arg(
+      Arg::new("subcategory")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Subcategory to budget for (case-insensitive)"),
+    )
+    .arg(
+      Arg::new("amount")
+        .index(2)
+        .required(true)
+        .value_parser(parse_amount)
+        .help("Monthly limit (must be greater than 0)"),
+    )
+    .arg(
+      Arg::new("rollover")
+        .long("rollover")
+        .action(ArgAction::SetTrue)
+        .conflicts_with("no-rollover")
+        .help("Carry what's unspent, or overspent, into the next month"),
+    )
+    .arg(
+      Arg::new("no-rollover")
+        .long("no-rollover")
+        .action(ArgAction::SetTrue)
+        .help("Start each month with just the budget again"),
+    )
+    .arg(
+      Arg::new("warn-at")
+        .long("warn-at")
+        .value_name("PERCENT")
+        .value_parser(parse_percent)
+        .help("Warn once this much of the budget is spent, e.g. 80%"),
+    )
+    .arg(
+      Arg::new("error-at")
+        .long("error-at")
+        .value_name("PERCENT")
+        .value_parser(parse_percent)
+        .help("Count the budget as overspent from this much, e.g. 100% (the default)"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let name = args.get_subcategory_or_default("subcategory");
+  let subcategory = tracker_data
+    .subcategory_id(&name)
+    .ok_or_else(|| tracker_data.subcategory_not_found(name))?;
+
+  let amount = round_amount(gctx, &tracker_data.currency, args.get_f64_or_default("amount"))?;
+  if amount <= 0.0 {
+    return Err(CliError::ValidationError(ValidationErrorKind::AmountTooSmall { amount }));
+  }
+
+  let index = match tracker_data.budgets.iter().position(|b| b.subcategory == subcategory) {
+    Some(index) => index,
+    None => {
+      tracker_data.budgets.push(Budget {
+        subcategory,
+        amount,
+        rollover: false,
+        since: None,
+        warn_at: None,
+        error_at: None,
+      });
+      tracker_data.budgets.len() - 1
+    }
+  };
+  let budget = &mut tracker_data.budgets[index];
+  budget.amount = amount;
+  if args.get_flag("rollover") && !budget.rollover {
+    let month_start = gctx.today().with_day(1).expect("day 1 exists");
+    budget.rollover = true;
+    budget.since = Some(month_start.format(STORAGE_DATE_FORMAT).to_string());
+  } else if args.get_flag("no-rollover") {
+    budget.rollover = false;
+    budget.since = None;
+  }
+  if let Some(&warn_at) = args.get_one::<f64>("warn-at") {
+    budget.warn_at = Some(warn_at);
+  }
+  if let Some(&error_at) = args.get_one::<f64>("error-at") {
+    budget.error_at = Some(error_at);
+  }
+  if let Some(warn_at) = budget.warn_at
+    && warn_at >= budget.error_at.unwrap_or(100.0)
+  {
+    return Err(CliError::Other(t!(
+      "error-budget-thresholds",
+      warn = warn_at,
+      error = budget.error_at.unwrap_or(100.0)
+    )));
+  }
+  let rollover = budget.rollover;
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "budget-set",
+    name = tracker_data.subcategory_name(subcategory).cloned().unwrap_or_default(),
+    amount = format_amount(amount),
+    currency = tracker_data.currency.as_str(),
+    rollover = rollover.to_string()
+  ))))
+}
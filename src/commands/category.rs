@@ -1,13 +1,15 @@
 use clap::{ArgMatches, Command};
 
-use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+use crate::{
+  CliError, CliResult, GlobalContext, ValidationErrorKind, commands::Exec, invalid_subcommand_error,
+};
 
 pub fn cli() -> Command {
   Command::new("category")
     .about("View available categories")
-    .long_about("Categories are fixed and cannot be modified. There are only two categories: Income and Expenses. Use this command to view them.")
+    .long_about("Categories are fixed and cannot be modified. There are only two categories: Income and Expenses. Use this command to view them. 'add', 'delete' and 'rename' only explain this; subcategories are the way to organize records further.")
     .subcommand_required(true)
-    .subcommands([list::cli()])
+    .subcommands([list::cli(), add::cli(), delete::cli(), rename::cli()])
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
@@ -23,9 +25,21 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
 
 pub fn build_exec(cmd: &str) -> Option<Exec> {
   match cmd {
+    "add" => Some(add::exec),
+    "delete" => Some(delete::exec),
     "list" => Some(list::exec),
+    "rename" => Some(rename::exec),
     _ => None,
   }
 }
 
+/// The error for any attempt to change the category named by the `arg` argument
+fn immutable(args: &ArgMatches, arg: &str) -> CliError {
+  let category = args.get_one::<String>(arg).cloned().unwrap_or_default();
+  CliError::ValidationError(ValidationErrorKind::CategoryImmutable { category })
+}
+
+pub mod add;
+pub mod delete;
 pub mod list;
+pub mod rename;
@@ -0,0 +1,20 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::category::immutable};
+
+pub fn cli() -> Command {
+  Command::new("add")
+    .about("Not supported: categories are fixed")
+    .long_about("There are only two categories, Income and Expenses, and no others can be added. To group records further, add a subcategory with 'fintrack subcategory add <name>'; subcategories work with either category.")
+    .arg(
+      Arg::new("name")
+        .help("Name of the category")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String)),
+    )
+}
+
+pub fn exec(_gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  Err(immutable(args, "name"))
+}
@@ -0,0 +1,20 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::category::immutable};
+
+pub fn cli() -> Command {
+  Command::new("delete")
+    .about("Not supported: categories are fixed")
+    .long_about("Income and Expenses can't be deleted; every record belongs to one of them. To remove a subcategory, use 'fintrack subcategory delete <name>'.")
+    .arg(
+      Arg::new("name")
+        .help("Name of the category")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String)),
+    )
+}
+
+pub fn exec(_gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  Err(immutable(args, "name"))
+}
@@ -0,0 +1,27 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::category::immutable};
+
+pub fn cli() -> Command {
+  Command::new("rename")
+    .about("Not supported: categories are fixed")
+    .long_about("Income and Expenses keep their names, which other commands and exported files rely on. Subcategories can be renamed with 'fintrack subcategory rename <old> <new>'.")
+    .arg(
+      Arg::new("old")
+        .help("Current name of the category")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String)),
+    )
+    .arg(
+      Arg::new("new")
+        .help("New name for the category")
+        .index(2)
+        .required(true)
+        .value_parser(clap::value_parser!(String)),
+    )
+}
+
+pub fn exec(_gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  Err(immutable(args, "old"))
+}
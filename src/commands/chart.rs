@@ -0,0 +1,35 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("chart")
+    .about("Draw charts as SVG or PNG files")
+    .long_about("Draws a chart of your data to an image file, e.g. 'chart balance --out balance.svg' for your balance over time. The file's extension picks the format: .svg or .png.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![balance::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "balance" => Some(balance::exec),
+    _ => None,
+  }
+}
+
+pub mod balance;
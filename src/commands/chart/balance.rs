@@ -0,0 +1,71 @@
+use std::{fs, path::PathBuf};
+
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::parsers::STORAGE_DATE_FORMAT;
+use crate::utils::chart;
+use crate::utils::storage::load_tracker;
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, t};
+
+pub fn cli() -> Command {
+  Command::new("balance")
+    .about("Chart your running balance over time")
+    .long_about("Draws your balance at the end of each day that has records, from the first record on, or from --from at the balance going into that day. Writes SVG or PNG depending on the extension of --out; text in PNGs needs a common font such as Arial or DejaVu Sans to be installed.")
+    .arg(
+      Arg::new("from")
+        .long("from")
+        .value_parser(clap::value_parser!(String))
+        .help("Start the chart on this date (DD-MM-YYYY), at the balance going into it"),
+    )
+    .arg(
+      Arg::new("to")
+        .long("to")
+        .value_parser(clap::value_parser!(String))
+        .help("End the chart on this date (DD-MM-YYYY)"),
+    )
+    .arg(
+      Arg::new("out")
+        .short('o')
+        .long("out")
+        .required(true)
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("File to write, ending in .svg or .png"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let out = args.get_one::<PathBuf>("out").cloned().unwrap_or_default();
+  let extension = out
+    .extension()
+    .and_then(|ext| ext.to_str())
+    .map(str::to_ascii_lowercase)
+    .unwrap_or_default();
+  if !matches!(extension.as_str(), "svg" | "png") {
+    return Err(CliError::Other(t!(
+      "error-chart-format",
+      path = out.display().to_string()
+    )));
+  }
+
+  let tracker_data = load_tracker(gctx)?;
+  let from = args.get_date_opt("from")?;
+  let to = args.get_date_opt("to")?;
+  let points = tracker_data.running_balance(from, to);
+  let (Some(first), Some(last)) = (points.first(), points.last()) else {
+    return Err(CliError::Other(t!("error-chart-empty")));
+  };
+
+  let svg = chart::balance_svg(&points, &tracker_data.currency);
+  match extension.as_str() {
+    "png" => fs::write(&out, chart::svg_to_png(&svg)?)?,
+    _ => fs::write(&out, svg)?,
+  }
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "chart-balance-written",
+    from = first.0.format(STORAGE_DATE_FORMAT).to_string(),
+    to = last.0.format(STORAGE_DATE_FORMAT).to_string(),
+    path = out.display().to_string()
+  ))))
+}
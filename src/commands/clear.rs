@@ -1,31 +1,131 @@
+use std::fs;
 use std::io::{self, Write};
+use std::path::PathBuf;
 
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 
-use crate::{utils::file::FilePath, CliResponse, CliResult, GlobalContext, ResponseContent};
+use crate::{
+  t,
+  utils::{
+    backup::archive_tracker,
+    file::FilePath,
+    storage::{ensure_writable, load_tracker, save_tracker},
+  },
+  CliError, CliResponse, CliResult, GlobalContext, ResponseContent,
+};
 
 pub fn cli() -> Command {
   Command::new("clear")
     .about("Delete all data and reset tracker")
-    .long_about("Permanently deletes all your financial data including all records, subcategories, and the tracker file itself. This action cannot be undone. You will be prompted to confirm before deletion. After clearing, you can run 'fintrack init' to start fresh.")
+    .long_about("Permanently deletes all your financial data including all records, subcategories, and the tracker file itself. A copy of the tracker is saved in the backups directory first (or wherever --archive-to says), and the backups themselves are kept. You will be prompted to confirm before deletion. After clearing, you can run 'fintrack init' to start fresh.")
+    .arg(
+      Arg::new("archive-to")
+        .long("archive-to")
+        .value_name("PATH")
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("Save the final copy of the tracker here instead of in the backups directory")
+        .long_help("File to save the final copy of the tracker to, or an existing directory to save it in as archive_<time>.json. Defaults to ~/.fintrack/backups."),
+    )
+    .arg(
+      Arg::new("keep-subcategories")
+        .long("keep-subcategories")
+        .action(ArgAction::SetTrue)
+        .help("Only remove the records, keeping subcategories and settings")
+        .long_help("Removes the records, and the loans and closed months that refer to them, but keeps the tracker itself: its currency, opening balance, subcategories, bills, subscriptions, schedules and investments stay as they are. Record IDs start again from 1."),
+    )
 }
 
-pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
-  print!("Delete ALL data? This cannot be undone. (yes/no): ");
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  ensure_writable(gctx)?;
+  let keep_subcategories = args.get_flag("keep-subcategories");
+  if gctx.config().general.confirm && !gctx.is_dry_run() && !confirm(keep_subcategories)? {
+    return Ok(CliResponse::new(ResponseContent::Message(t!("clear-cancelled"))));
+  }
+
+  let archive = match gctx.is_dry_run() {
+    true => None,
+    false => archive_tracker(gctx, args.get_one::<PathBuf>("archive-to").map(PathBuf::as_path))?,
+  };
+  let saved = archive
+    .map(|path| format!(" {}", t!("clear-archived", path = path.display().to_string())))
+    .unwrap_or_default();
+
+  if keep_subcategories {
+    let mut tracker_data = load_tracker(gctx)?;
+    let removed = tracker_data.records.len();
+    tracker_data.records.clear();
+    tracker_data.next_record_id = 1;
+    tracker_data.owed.clear();
+    tracker_data.closed_periods.clear();
+    tracker_data.history.clear();
+    for subscription in &mut tracker_data.subscriptions {
+      subscription.records.clear();
+    }
+    tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+    save_tracker(gctx, &tracker_data)?;
+
+    return Ok(CliResponse::new(ResponseContent::Message(format!(
+      "{}{}",
+      t!("clear-records-removed", count = removed),
+      saved
+    ))));
+  }
+
+  // The files go, so there's no tracker left to compare with
+  if gctx.is_dry_run() {
+    let records = load_tracker(gctx).map(|tracker| tracker.records.len()).unwrap_or(0);
+    let target = match gctx.has_custom_tracker() {
+      true => gctx.tracker_path(),
+      false => gctx.base_path(),
+    };
+    return Ok(CliResponse::new(ResponseContent::Message(t!(
+      "clear-dry-run",
+      path = target.display().to_string(),
+      count = records
+    ))));
+  }
+
+  // A tracker given with --tracker-file is all there is to clear
+  if gctx.has_custom_tracker() {
+    gctx.verbose(format!("Removing {}", gctx.tracker_path().display()));
+    gctx.tracker_path().delete_if_exists()?;
+    return Ok(CliResponse::new(ResponseContent::Message(format!(
+      "{}{}",
+      t!("clear-file-removed", path = gctx.tracker_path().display().to_string()),
+      saved
+    ))));
+  }
+
+  // Everything goes except the backups, which now hold the archive too
+  gctx.verbose(format!("Removing {}", gctx.base_path().display()));
+  if gctx.base_path().exists() {
+    for entry in fs::read_dir(gctx.base_path())? {
+      let path = entry?.path();
+      if path != *gctx.backups_path() {
+        path.delete_if_exists()?;
+      }
+    }
+  }
+
+  Ok(CliResponse::new(ResponseContent::Message(format!(
+    "{}{} {}",
+    t!("clear-done"),
+    saved,
+    t!("clear-start-over")
+  ))))
+}
+
+fn confirm(keep_subcategories: bool) -> Result<bool, CliError> {
+  if keep_subcategories {
+    print!("Delete ALL records? This cannot be undone. (yes/no): ");
+  } else {
+    print!("Delete ALL data? This cannot be undone. (yes/no): ");
+  }
   io::stdout().flush()?;
 
   let mut input = String::new();
   io::stdin().read_line(&mut input)?;
   let input = input.trim().to_lowercase();
 
-  if input == "yes" || input == "y" {
-    gctx.base_path().delete_if_exists()?;
-    Ok(CliResponse::new(ResponseContent::Message(
-      "All data cleared. Run 'fintrack init' to start over.".to_string(),
-    )))
-  } else {
-    Ok(CliResponse::new(ResponseContent::Message(
-      "Clear cancelled.".to_string(),
-    )))
-  }
+  Ok(input == "yes" || input == "y")
 }
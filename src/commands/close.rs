@@ -0,0 +1,86 @@
+use chrono::{Datelike, Months, NaiveDate};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::output::format_amount;
+use crate::parsers::parse_month;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::{ClosedPeriod, CliError, CliResponse, CliResult, GlobalContext, ResponseContent, t};
+
+pub fn cli() -> Command {
+  Command::new("close")
+    .about("Lock a finished month against changes")
+    .long_about("Closes a month once its records are final: records dated in it can no longer be added, updated or deleted, and the balance it ended on is saved. Reports for later months start from that balance. Defaults to last month. Use --reopen to unlock a month that needs correcting.")
+    .arg(
+      Arg::new("month")
+        .short('m')
+        .long("month")
+        .value_parser(parse_month)
+        .help("Month to close, in MM-YYYY format (default: last month)"),
+    )
+    .arg(
+      Arg::new("reopen")
+        .long("reopen")
+        .action(ArgAction::SetTrue)
+        .help("Unlock the month again and drop its saved balance"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let this_month = gctx.today().with_day(1).expect("day 1 exists");
+  let start = args
+    .get_one::<NaiveDate>("month")
+    .copied()
+    .unwrap_or(this_month - Months::new(1));
+  let month = start.format("%m-%Y").to_string();
+  let closed = tracker_data.closed_periods.iter().position(|c| c.month == month);
+
+  if args.get_flag("reopen") {
+    let Some(index) = closed else {
+      return Ok(CliResponse::new(ResponseContent::Message(t!(
+        "close-not-closed",
+        month = month.to_string()
+      ))));
+    };
+    tracker_data.closed_periods.remove(index);
+    tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+    save_tracker(gctx, &tracker_data)?;
+    return Ok(CliResponse::new(ResponseContent::Message(t!(
+      "close-reopened",
+      month = month.to_string()
+    ))));
+  }
+
+  if closed.is_some() {
+    return Ok(CliResponse::new(ResponseContent::Message(t!(
+      "close-already-closed",
+      month = month.to_string()
+    ))));
+  }
+  if start >= this_month {
+    return Err(CliError::Other(t!("error-close-not-ended", month = month.to_string())));
+  }
+
+  let end = start + Months::new(1) - chrono::Days::new(1);
+  let closing_balance = tracker_data.balance_through(end);
+  gctx.verbose(format!("Closing {} at {}", month, closing_balance));
+
+  tracker_data.closed_periods.push(ClosedPeriod {
+    month: month.clone(),
+    closing_balance,
+    closed_at: chrono::Utc::now().to_rfc3339(),
+  });
+  tracker_data
+    .closed_periods
+    .sort_by_key(|closed| closed.start());
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "close-closed",
+    month = month.to_string(),
+    balance = format_amount(closing_balance),
+    currency = tracker_data.currency.as_str()
+  ))))
+}
@@ -0,0 +1,48 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("config")
+    .about("View and change settings")
+    .long_about("Reads and writes ~/.fintrack/config.toml, which holds your preferences: date format, table style, language, confirmation prompts and command aliases. Settings are named 'section.key', e.g. 'display.date_format'. Use 'fintrack config list' to see them all.")
+    .subcommand_required(true)
+    .subcommands([
+      export::cli(),
+      get::cli(),
+      import::cli(),
+      list::cli(),
+      set::cli(),
+      unset::cli(),
+    ])
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+pub fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "export" => Some(export::exec),
+    "get" => Some(get::exec),
+    "import" => Some(import::exec),
+    "list" => Some(list::exec),
+    "set" => Some(set::exec),
+    "unset" => Some(unset::exec),
+    _ => None,
+  }
+}
+
+pub mod export;
+pub mod get;
+pub mod import;
+pub mod list;
+pub mod set;
+pub mod unset;
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, ResponseContent, t,
+  utils::config::{read_table, write_table},
+};
+
+pub fn cli() -> Command {
+  Command::new("export")
+    .about("Save your settings to a file")
+    .long_about("Writes every setting you have changed (display preferences, default flags and aliases) to a TOML file, so you can move them to another machine with 'fintrack config import'. Your financial data is not included; use 'fintrack export' for that.")
+    .arg(
+      Arg::new("file")
+        .help("File to write, e.g. settings.toml")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(PathBuf)),
+    )
+    .arg(
+      Arg::new("force")
+        .long("force")
+        .action(ArgAction::SetTrue)
+        .help("Overwrite the file if it already exists"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let file = args
+    .get_one::<PathBuf>("file")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "file")))?;
+
+  if file.exists() && !args.get_flag("force") {
+    return Err(CliError::Other(t!(
+      "error-file-exists",
+      path = file.display().to_string()
+    )));
+  }
+
+  let table = read_table(gctx.config_path())?;
+  gctx.verbose(format!("Writing {}", file.display()));
+  write_table(file, &table)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "config-exported",
+    path = file.display().to_string()
+  ))))
+}
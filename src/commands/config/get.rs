@@ -0,0 +1,28 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, t};
+
+pub fn cli() -> Command {
+  Command::new("get")
+    .about("Print the value of a setting")
+    .long_about("Prints the value a setting currently has, including built-in defaults for settings you haven't changed. Use 'fintrack config list' to see the available settings.")
+    .arg(
+      Arg::new("key")
+        .help("Setting name, e.g. display.date_format")
+        .index(1)
+        .required(true),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let key = args
+    .get_one::<String>("key")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "key")))?;
+
+  let value = gctx.config().get(key).map_err(CliError::ValidationError)?;
+
+  Ok(CliResponse::new(ResponseContent::ConfigValue {
+    key: key.clone(),
+    value,
+  }))
+}
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use toml::Value;
+
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, ResponseContent, t,
+  utils::config::{Config, read_table, write_table},
+};
+
+pub fn cli() -> Command {
+  Command::new("import")
+    .about("Load settings from a file")
+    .long_about("Replaces your settings with the ones in a file written by 'fintrack config export'. The file is checked before anything is changed. Use --merge to keep your current settings and only add or overwrite the ones in the file.")
+    .arg(
+      Arg::new("file")
+        .help("Settings file to read, e.g. settings.toml")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(PathBuf)),
+    )
+    .arg(
+      Arg::new("merge")
+        .long("merge")
+        .action(ArgAction::SetTrue)
+        .help("Keep existing settings that the file doesn't mention"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let file = args
+    .get_one::<PathBuf>("file")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "file")))?;
+
+  if !file.exists() {
+    return Err(CliError::FileNotFound(file.display().to_string()));
+  }
+  // Reject files that wouldn't load, before touching the current settings
+  Config::load(file)?;
+  let imported = read_table(file)?;
+
+  let table = if args.get_flag("merge") {
+    let mut table = read_table(gctx.config_path())?;
+    for (section, value) in imported {
+      match (table.get_mut(&section), value) {
+        (Some(Value::Table(existing)), Value::Table(entries)) => existing.extend(entries),
+        (_, value) => {
+          table.insert(section, value);
+        }
+      }
+    }
+    table
+  } else {
+    imported
+  };
+  if gctx.is_dry_run() {
+    return Ok(CliResponse::new(ResponseContent::Message(t!(
+      "config-dry-run-import",
+      path = file.display().to_string()
+    ))));
+  }
+
+  gctx.verbose(format!("Writing {}", gctx.config_path().display()));
+  write_table(gctx.config_path(), &table)?;
+  gctx.load_config()?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "config-imported",
+    path = file.display().to_string()
+  ))))
+}
@@ -0,0 +1,13 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("list")
+    .about("List settings and their values")
+    .long_about("Lists every setting with the value it currently has (defaults included), followed by your aliases. Run 'fintrack config set --help' to see what each setting does.")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  Ok(CliResponse::new(ResponseContent::Settings(gctx.config().entries())))
+}
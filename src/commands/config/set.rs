@@ -0,0 +1,76 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind, commands, t,
+  utils::config::{SETTINGS, read_table, set_value, write_table},
+};
+
+pub fn cli() -> Command {
+  Command::new("set")
+    .about("Change a setting")
+    .long_about("Saves a setting to ~/.fintrack/config.toml. The value is checked before it is written, so a typo can't leave you with a broken config. Examples: 'fintrack config set display.date_format iso', 'fintrack config set aliases.spent \"add expenses\"'.")
+    .arg(
+      Arg::new("key")
+        .help("Setting name, e.g. display.date_format")
+        .long_help(settings_help())
+        .index(1)
+        .required(true),
+    )
+    .arg(
+      Arg::new("value")
+        .help("New value")
+        .index(2)
+        .allow_hyphen_values(true)
+        .required(true),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let key = args
+    .get_one::<String>("key")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "key")))?;
+  let value = args
+    .get_one::<String>("value")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "value")))?;
+
+  if let Some(name) = key.strip_prefix("aliases.")
+    && commands::cli().iter().any(|cmd| cmd.get_name() == name)
+  {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidConfigValue {
+      key: key.clone(),
+      value: value.clone(),
+      reason: format!("'{}' is a built-in command and can't be aliased", name),
+    }));
+  }
+
+  let path = gctx.config_path().clone();
+  let mut table = read_table(&path)?;
+  set_value(&mut table, key, value).map_err(CliError::ValidationError)?;
+  if gctx.is_dry_run() {
+    return Ok(CliResponse::new(ResponseContent::Message(t!(
+      "config-dry-run-set",
+      key = key.as_str(),
+      value = value.as_str()
+    ))));
+  }
+
+  gctx.verbose(format!("Writing {}", path.display()));
+  write_table(&path, &table)?;
+  gctx.load_config()?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "config-set",
+    key = key.as_str(),
+    value = value.as_str()
+  ))))
+}
+
+fn settings_help() -> String {
+  let mut help = String::from("Setting name. Available settings:\n");
+  for (key, description) in SETTINGS {
+    help.push_str(&format!("  {:<22}{}\n", key, description));
+  }
+  help.push_str(&format!("  {:<22}{}\n", "<command>.default", "Flags always added to a command, e.g. '--last 20'"));
+  help.push_str(&format!("  {:<22}{}", "aliases.<name>", "A command alias, e.g. 'add expenses'"));
+  help
+}
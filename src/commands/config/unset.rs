@@ -0,0 +1,39 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, ResponseContent, t,
+  utils::config::{read_table, unset_value, write_table},
+};
+
+pub fn cli() -> Command {
+  Command::new("unset")
+    .about("Reset a setting to its default")
+    .long_about("Removes a setting from ~/.fintrack/config.toml so its built-in default applies again. Removing an 'aliases.<name>' entry deletes that alias.")
+    .arg(
+      Arg::new("key")
+        .help("Setting name, e.g. display.date_format")
+        .index(1)
+        .required(true),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let key = args
+    .get_one::<String>("key")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "key")))?;
+
+  let path = gctx.config_path().clone();
+  let mut table = read_table(&path)?;
+  if !unset_value(&mut table, key).map_err(CliError::ValidationError)? {
+    return Ok(CliResponse::new(ResponseContent::Message(t!("config-was-not-set", key = key.as_str()))));
+  }
+  if gctx.is_dry_run() {
+    return Ok(CliResponse::new(ResponseContent::Message(t!("config-dry-run-unset", key = key.as_str()))));
+  }
+
+  gctx.verbose(format!("Writing {}", path.display()));
+  write_table(&path, &table)?;
+  gctx.load_config()?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!("config-unset", key = key.as_str()))))
+}
@@ -1,18 +1,23 @@
 use std::collections::HashSet;
 
-use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde_json::Value;
 
 use crate::{
-  CliResponse, CliResult, GlobalContext, TrackerData,
+  CliError, CliResponse, CliResult, GlobalContext, Record, ResponseContent,
   command_prelude::ArgMatchesExt,
-  utils::file::{FilePath, write_json_to_file},
-  utils::parsers::parse_category,
+  output::record_json, t,
+  utils::hooks::Hook,
+  utils::filters::{FILTER_ARGS, RecordFilter, filter_args},
+  utils::storage::{AfterSave, after_save, load_tracker, save_tracker},
+  utils::validation::check_open,
+  utils::webhook::{self, Event},
 };
 
 pub fn cli() -> Command {
-  Command::new("delete")
+  let cmd = Command::new("delete")
     .about("Delete transaction records")
-    .long_about("Removes one or more records from your tracker. You can delete by record ID(s), by category (all income or all expenses), or by subcategory (all records in a specific subcategory).")
+    .long_about("Removes one or more records from your tracker. You can delete by record ID(s), or every record that matches the same filters 'fintrack list' takes: a date range, month or the last N days, category, subcategory, who entered it, project, amount, description text or --where conditions. Filters combine, so '-s Groceries --max-amount 1' only deletes groceries of 1 or less. Run 'fintrack list' with the same filters first to see what will go.")
     .arg(
       Arg::new("ids")
         .help("Delete specific records by their IDs")
@@ -22,68 +27,75 @@ pub fn cli() -> Command {
         .value_parser(clap::value_parser!(usize))
         .action(ArgAction::Append)
         .value_delimiter(','),
-    )
-    .arg(
-      Arg::new("by-cat")
+    );
+  filter_args(cmd)
+    .mut_arg("category", |arg| {
+      arg
+        .visible_alias("by-cat")
         .help("Delete all records in a category")
         .long_help("Deletes all records in the specified category (either 'income' or 'expenses'). Use with caution as this will remove all transactions of that type. Case-insensitive.")
-        .short('c')
-        .long("by-cat")
-        .value_parser(parse_category),
-    )
-    .arg(
-      Arg::new("by-subcat")
+    })
+    .mut_arg("subcategory", |arg| {
+      arg
+        .visible_alias("by-subcat")
         .help("Delete all records in a subcategory")
         .long_help("Deletes all records that belong to the specified subcategory. The subcategory name is case-insensitive. Use 'fintrack subcategory list' to see available subcategories.")
-        .short('s')
-        .long("by-subcat")
-        .value_parser(clap::value_parser!(String)),
-    )
-    .group(
-      ArgGroup::new("delete_by")
-        .args(["ids", "by-cat", "by-subcat"])
-        .multiple(false)
-        .required(true),
-    )
+    })
+    .mut_arg("ids", |arg| arg.conflicts_with_all(FILTER_ARGS))
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
-  let mut file = gctx.tracker_path().open_read_write()?;
-  let mut tracker_data: TrackerData = serde_json::from_reader(&file)?;
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let records_before = tracker_data.records.clone();
+  let filter_span = tracing::info_span!("filter").entered();
 
   if args.contains_id("ids") {
     let ids: Vec<usize> = args.get_vec::<usize>("ids");
+    gctx.verbose(format!("Deleting records with IDs {:?}", ids));
     let ids_set: HashSet<usize> = ids.into_iter().collect();
 
     tracker_data.records.retain(|r| !ids_set.contains(&r.id));
-  } else if args.contains_id("by-cat") {
-    let category = args.get_category("by-cat")?;
-    let category_str = category.to_string();
-    let category_id = tracker_data.category_id(&category_str);
+  } else {
+    let filter = RecordFilter::from_args(gctx, &tracker_data, args)?;
+    if filter.is_empty() {
+      return Err(CliError::Other(t!("error-delete-unfiltered")));
+    }
+    gctx.verbose(format!("Deleting records matching {:?}", filter));
 
-    tracker_data.records.retain(|r| r.category != category_id);
-  } else if args.contains_id("by-subcat") {
-    let subcategory_name = args
-      .get_subcategory_opt("by-subcat")
-      .ok_or_else(|| crate::CliError::Other("Subcategory not provided".to_string()))?;
-
-    let subcategory_id = tracker_data
-      .subcategory_id(subcategory_name.as_str())
-      .ok_or_else(|| {
-        crate::CliError::ValidationError(crate::ValidationErrorKind::SubcategoryNotFound {
-          name: subcategory_name.clone(),
-        })
-      })?;
+    tracker_data.records.retain(|r| !filter.matches(r));
+  }
 
-    tracker_data
-      .records
-      .retain(|r| r.subcategory != subcategory_id);
+  drop(filter_span);
+  let remaining: HashSet<usize> = tracker_data.records.iter().map(|r| r.id).collect();
+  let deleted: Vec<Record> = records_before
+    .into_iter()
+    .filter(|r| !remaining.contains(&r.id))
+    .collect();
+  for record in &deleted {
+    check_open(&tracker_data, record.naive_date())?;
   }
+  let removed = deleted.len();
+  tracing::info!(removed, "records deleted");
+
+  // Shares of a deleted expense go with it; repayments already made stay
+  tracker_data
+    .owed
+    .retain(|entry| entry.record.is_none_or(|id| remaining.contains(&id)));
+  tracker_data
+    .history
+    .retain(|version| remaining.contains(&version.record.id));
+  gctx.verbose(format!("Removed {} record(s)", removed));
 
   tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
 
-  let tracker_json = serde_json::json!(tracker_data);
-  write_json_to_file(&tracker_json, &mut file)?;
+  save_tracker(gctx, &tracker_data)?;
+  let payload = deleted.iter().map(|r| record_json(r, &tracker_data)).collect();
+  after_save(gctx, AfterSave::Hook(Hook::PostDelete, Value::Array(payload)));
+  webhook::notify(gctx, Event::Delete, &deleted, &tracker_data);
 
-  Ok(CliResponse::success())
+  Ok(CliResponse::new(ResponseContent::Deleted {
+    count: removed,
+    tracker_data,
+  }))
 }
@@ -1,87 +1,108 @@
-use chrono::NaiveDate;
-use clap::{ArgMatches, Command};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use chrono::Datelike;
+use clap::{Arg, ArgMatches, Command};
+use rayon::prelude::*;
 
 use crate::{
-  CliError, CliResponse, CliResult, Currency, DescribeData, GlobalContext, TrackerData,
-  utils::file::FilePath,
+  CliError, CliResponse, CliResult, Currency, DayOfWeek, DescribeData, GlobalContext, Record, t,
+  utils::{
+    filters::{DateRange, RecordFilter, filter_args},
+    period::{Period, fiscal_year_of},
+    storage::load_tracker,
+  },
 };
 
+/// Records tallied per task
+const CHUNK_RECORDS: usize = 4096;
+
 pub fn cli() -> Command {
-  Command::new("describe")
+  let cmd = Command::new("describe")
     .about("Show financial insights and statistics")
-    .long_about("Provides an overview of your financial data including total records, date range, spending breakdown by category and subcategory, and average transaction amount.")
+    .long_about("Provides an overview of your financial data including total records, date range, spending breakdown by category and subcategory, and average transaction amount. Takes the same filters as 'fintrack list' to describe only some of the records.")
+    .arg(
+      Arg::new("year")
+        .long("year")
+        .value_parser(clap::value_parser!(i32))
+        .num_args(0..=1)
+        .conflicts_with_all(["start", "end", "month", "days"])
+        .help("Only describe one year, the current one if no year is given")
+        .long_help("Only describe records from one year. When 'period.fiscal_year_start' is set the year is a fiscal year, named after the calendar year it starts in."),
+    )
+    .arg(
+      Arg::new("by")
+        .long("by")
+        .value_parser(["tag"])
+        .help("Also break the records down by tag")
+        .long_help("Adds a 'By Tag' section. A record counts under each of its tags, so the section can add up to more than the records described; untagged records aren't in it."),
+    );
+  filter_args(cmd)
 }
 
-pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
-  let file = gctx.tracker_path().open_read()?;
-  let tracker_data: TrackerData = serde_json::from_reader(&file)?;
-
-  let total_records = tracker_data.records.len();
-
-  // Calculate date range
-  let date_range = if !tracker_data.records.is_empty() {
-    let mut dates: Vec<NaiveDate> = tracker_data
-      .records
-      .iter()
-      .filter_map(|r| NaiveDate::parse_from_str(&r.date, "%d-%m-%Y").ok())
-      .collect();
-
-    if !dates.is_empty() {
-      dates.sort();
-      let earliest_str = dates.first().unwrap().format("%d-%m-%Y").to_string();
-      let latest_str = dates.last().unwrap().format("%d-%m-%Y").to_string();
-      Some((earliest_str, latest_str))
-    } else {
-      None
-    }
-  } else {
-    None
-  };
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
 
-  // Calculate by category
-  let mut category_stats: std::collections::HashMap<usize, (usize, f64)> =
-    std::collections::HashMap::new();
-  for record in &tracker_data.records {
-    let entry = category_stats.entry(record.category).or_insert((0, 0.0));
-    entry.0 += 1;
-    entry.1 += record.amount;
+  let mut filter = RecordFilter::from_args(gctx, &tracker_data, args)?;
+  if args.contains_id("year") {
+    let periods = &gctx.config().period;
+    let year = args
+      .get_one::<i32>("year")
+      .copied()
+      .unwrap_or_else(|| fiscal_year_of(periods, gctx.today()));
+    let period = Period::fiscal_year(periods, year);
+    filter.dates = filter.dates.within(DateRange {
+      start: Some(period.start),
+      end: Some(period.end),
+    });
   }
+  gctx.verbose(format!("Describing records matching {:?}", filter));
+  let selected = filter.select(&tracker_data);
+  let records = &selected[..];
+  let total_records = records.len();
+
+  // Calculate date range; records are kept in date order, undated ones first
+  let dated = &records[records.partition_point(|r| r.naive_date().is_none())..];
+  let date_range = match (dated.first(), dated.last()) {
+    (Some(earliest), Some(latest)) => Some((earliest.date.clone(), latest.date.clone())),
+    _ => None,
+  };
 
-  let mut by_category: Vec<(String, usize, f64)> = category_stats
-    .iter()
-    .filter_map(|(&id, &(count, total))| {
-      tracker_data
-        .category_name(id)
-        .map(|name| (name.clone(), count, total))
+  // Count and total fixed-size chunks in parallel, then combine them in
+  // order so the floating-point sums are the same on every run
+  let expenses_id = tracker_data.category_id("expenses");
+  let stats = records
+    .par_chunks(CHUNK_RECORDS)
+    .map(|records| {
+      let mut stats = Stats::default();
+      records.iter().for_each(|record| stats.add(record, expenses_id));
+      stats
     })
-    .collect();
-  by_category.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
-
-  // Calculate by subcategory
-  let mut subcategory_stats: std::collections::HashMap<usize, (usize, f64)> =
-    std::collections::HashMap::new();
-
-  for record in &tracker_data.records {
-    let entry = subcategory_stats
-      .entry(record.subcategory)
-      .or_insert((0, 0.0));
-    entry.0 += 1;
-    entry.1 += record.amount;
-  }
+    .collect::<Vec<_>>()
+    .into_iter()
+    .fold(Stats::default(), Stats::merge);
 
-  let mut by_subcategory: Vec<(String, usize, f64)> = subcategory_stats
-    .iter()
-    .filter_map(|(&id, &(count, total))| {
-      tracker_data
-        .subcategory_name(id)
-        .map(|name| (name.clone(), count, total))
+  let by_category = groups(stats.by_category, |id| tracker_data.category_name(id).cloned());
+  let by_subcategory = groups(stats.by_subcategory, |id| {
+    tracker_data.subcategory_name(id).cloned()
+  });
+  let by_user = groups(stats.by_user, |name| Some(name.to_string()));
+  let by_tag = match args.get_one::<String>("by").map(String::as_str) {
+    Some("tag") => tag_groups(records),
+    _ => Vec::new(),
+  };
+  let week_start = gctx.config().period.week_start.weekday();
+  let by_weekday = std::iter::successors(Some(week_start), |day| Some(day.succ()))
+    .take(7)
+    .map(|day| {
+      let (count, total) = stats.by_weekday[day.num_days_from_monday() as usize];
+      (DayOfWeek::from_weekday(day), count, total)
     })
     .collect();
-  by_subcategory.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
 
   // Calculate average transaction
   let average_transaction = if total_records > 0 {
-    tracker_data.records.iter().map(|r| r.amount).sum::<f64>() / total_records as f64
+    stats.sum / total_records as f64
   } else {
     0.0
   };
@@ -89,7 +110,7 @@ pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
   let currency = tracker_data
     .currency
     .parse::<Currency>()
-    .map_err(|e| CliError::Other(format!("Invalid currency in tracker data: {}", e)))?;
+    .map_err(|e| CliError::Other(t!("error-tracker-currency", reason = e.to_string())))?;
 
   Ok(CliResponse::new(crate::ResponseContent::Describe(
     DescribeData {
@@ -97,8 +118,99 @@ pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
       date_range,
       by_category,
       by_subcategory,
+      by_user,
+      by_tag,
+      by_weekday,
+      by_currency: tracker_data.currency_totals(records),
       average_transaction,
       currency,
     },
   )))
 }
+
+/// Record counts and totals per group, for one slice of the records
+#[derive(Default)]
+struct Stats<'a> {
+  by_category: HashMap<usize, (usize, f64)>,
+  by_subcategory: HashMap<usize, (usize, f64)>,
+  /// Only records with `entered_by` set are counted here
+  by_user: HashMap<&'a str, (usize, f64)>,
+  /// Expenses only, Monday first
+  by_weekday: [(usize, f64); 7],
+  sum: f64,
+}
+
+impl<'a> Stats<'a> {
+  fn add(&mut self, record: &'a Record, expenses_id: usize) {
+    tally(&mut self.by_category, record.category, 1, record.amount);
+    tally(&mut self.by_subcategory, record.subcategory, 1, record.amount);
+    if let Some(name) = &record.entered_by {
+      tally(&mut self.by_user, name.as_str(), 1, record.amount);
+    }
+    if record.category == expenses_id
+      && let Some(date) = record.naive_date()
+    {
+      let day = &mut self.by_weekday[date.weekday().num_days_from_monday() as usize];
+      day.0 += 1;
+      day.1 += record.amount;
+    }
+    self.sum += record.amount;
+  }
+
+  fn merge(mut self, other: Self) -> Self {
+    for (id, (count, total)) in other.by_category {
+      tally(&mut self.by_category, id, count, total);
+    }
+    for (id, (count, total)) in other.by_subcategory {
+      tally(&mut self.by_subcategory, id, count, total);
+    }
+    for (name, (count, total)) in other.by_user {
+      tally(&mut self.by_user, name, count, total);
+    }
+    for (day, (count, total)) in self.by_weekday.iter_mut().zip(other.by_weekday) {
+      day.0 += count;
+      day.1 += total;
+    }
+    self.sum += other.sum;
+    self
+  }
+}
+
+fn tally<K: Eq + Hash>(map: &mut HashMap<K, (usize, f64)>, key: K, count: usize, total: f64) {
+  let entry = map.entry(key).or_insert((0, 0.0));
+  entry.0 += count;
+  entry.1 += total;
+}
+
+/// `(name, count, total)` per tag, ignoring case, under the first spelling met
+fn tag_groups(records: &[Record]) -> Vec<(String, usize, f64)> {
+  let mut spellings: HashMap<String, &str> = HashMap::new();
+  let mut stats: HashMap<String, (usize, f64)> = HashMap::new();
+  for record in records {
+    for tag in &record.tags {
+      let key = tag.to_lowercase();
+      spellings.entry(key.clone()).or_insert(tag);
+      tally(&mut stats, key, 1, record.amount);
+    }
+  }
+  groups(stats, |key| spellings.get(&key).map(|name| name.to_string()))
+}
+
+/// Named `(name, count, total)` groups, largest total first. Ties are broken by
+/// name so the order doesn't depend on how the work was split.
+fn groups<K>(
+  stats: HashMap<K, (usize, f64)>,
+  name: impl Fn(K) -> Option<String>,
+) -> Vec<(String, usize, f64)> {
+  let mut groups: Vec<(String, usize, f64)> = stats
+    .into_iter()
+    .filter_map(|(key, (count, total))| name(key).map(|name| (name, count, total)))
+    .collect();
+  groups.sort_by(|a, b| {
+    b.2
+      .partial_cmp(&a.2)
+      .unwrap_or(std::cmp::Ordering::Equal)
+      .then_with(|| a.0.cmp(&b.0))
+  });
+  groups
+}
@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::utils::backup::list_backups;
+use crate::utils::storage::{load_tracker, read_tracker_file};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, TrackerDiff, t};
+
+pub fn cli() -> Command {
+  Command::new("diff")
+    .about("Compare the tracker with a backup or another tracker file")
+    .long_about("Shows how the tracker differs from another copy of it: the records added, changed and removed since then, subcategories added, renamed or removed, and the totals on each side. Give a file path, the name of a file in ~/.fintrack/backups, or 'latest' for the newest backup. With --reverse the changes go the other way, showing what restoring the file would do to the tracker.")
+    .arg(
+      Arg::new("file")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("A tracker file, a backup's name, or 'latest'"),
+    )
+    .arg(
+      Arg::new("reverse")
+        .long("reverse")
+        .action(ArgAction::SetTrue)
+        .help("Show what replacing the tracker with the file would change"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let file = args.get_one::<String>("file").expect("file is required");
+  let path = resolve(gctx, file)?;
+  gctx.verbose(format!("Comparing with {}", path.display()));
+
+  let other = read_tracker_file(&path)?;
+  let current = load_tracker(gctx)?;
+  let reverse = args.get_flag("reverse");
+  let diff = match reverse {
+    true => TrackerDiff::between(current, other),
+    false => TrackerDiff::between(other, current),
+  };
+
+  Ok(CliResponse::new(ResponseContent::Diff {
+    source: path.display().to_string(),
+    reverse,
+    diff: Box::new(diff),
+  }))
+}
+
+/// A file by its path, or a backup by its name
+fn resolve(gctx: &GlobalContext, file: &str) -> Result<PathBuf, CliError> {
+  if file == "latest" {
+    return list_backups(gctx)?
+      .pop()
+      .ok_or_else(|| CliError::Other(t!("error-no-backups")));
+  }
+
+  let path = PathBuf::from(file);
+  if path.exists() {
+    return Ok(path);
+  }
+  let backup = gctx.backups_path().join(file);
+  match backup.exists() {
+    true => Ok(backup),
+    false => Err(CliError::Other(t!("error-diff-not-found", file = file))),
+  }
+}
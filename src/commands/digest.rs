@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use chrono::{Days, NaiveDate};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, ResponseContent, TrackerData, UnusualExpense,
+  WeeklyDigest,
+  output::digest_lines,
+  parsers::STORAGE_DATE_FORMAT, t,
+  utils::{notifier::Channel, period::Period, storage::load_tracker},
+};
+
+/// Number of expense subcategories listed
+const TOP_SUBCATEGORIES: usize = 3;
+/// Number of unusual expenses listed
+const MAX_UNUSUAL: usize = 3;
+/// How far back an expense is compared to its subcategory's history
+const HISTORY_DAYS: u64 = 90;
+/// Expenses the subcategory needs in that time before anything counts as unusual
+const MIN_HISTORY: usize = 3;
+/// How many times the typical expense an unusual one costs
+const UNUSUAL_FACTOR: f64 = 3.0;
+
+pub fn cli() -> Command {
+  Command::new("digest")
+    .about("Summarize the week's spending in a few lines")
+    .long_about("Prints a short summary of the week so far: what was spent, how that compares with the same days last week, the top 3 subcategories and any expense far above what its subcategory usually costs. It's kept short enough to read in a notification; --send sends it to the channels in the [notify] section of your config. The week starts on 'period.week_start'.")
+    .arg(
+      Arg::new("week")
+        .long("week")
+        .action(ArgAction::SetTrue)
+        .help("Summarize the current week (the default)"),
+    )
+    .arg(
+      Arg::new("send")
+        .long("send")
+        .action(ArgAction::SetTrue)
+        .help("Send the digest to the configured notification channels instead of printing it"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+  let week = Period::week(&gctx.config().period, gctx.today());
+  let digest = digest(&tracker_data, week.start, gctx.today());
+
+  if !args.get_flag("send") {
+    return Ok(CliResponse::new(ResponseContent::Digest(digest)));
+  }
+
+  let channels = Channel::configured(&gctx.config().notify);
+  if channels.is_empty() {
+    return Err(CliError::Other(t!("error-no-notify-channel")));
+  }
+  if channels.iter().any(Channel::needs_network) {
+    gctx.ensure_online("Sending the digest")?;
+  }
+  let mut lines = digest_lines(&digest);
+  let title = lines.remove(0);
+  let body = lines.join("\n");
+  for channel in &channels {
+    gctx.verbose(format!("Sending the digest to {}", channel.name()));
+    channel.send(&title, &body)?;
+  }
+
+  let names: Vec<&str> = channels.iter().map(|c| c.name()).collect();
+  Ok(CliResponse::new(ResponseContent::Message(t!("digest-sent", channels = names.join(", ")))))
+}
+
+/// The week from `start` up to `end`, against the same days a week earlier
+fn digest(tracker_data: &TrackerData, start: NaiveDate, end: NaiveDate) -> WeeklyDigest {
+  let expenses_id = tracker_data.category_id("expenses");
+  let week_ago = |date: NaiveDate| date - Days::new(7);
+  let history_start = start - Days::new(HISTORY_DAYS);
+
+  let mut spent = 0.0;
+  let mut last_week = 0.0;
+  let mut by_subcategory: HashMap<usize, f64> = HashMap::new();
+  let mut history: HashMap<usize, Vec<f64>> = HashMap::new();
+  let mut this_week = Vec::new();
+  for record in tracker_data.records_between(Some(history_start), Some(end)) {
+    let Some(date) = record.naive_date() else {
+      continue;
+    };
+    if record.category != expenses_id {
+      continue;
+    }
+    if date >= start {
+      spent += record.amount;
+      *by_subcategory.entry(record.subcategory).or_default() += record.amount;
+      this_week.push(record);
+    } else {
+      history.entry(record.subcategory).or_default().push(record.amount);
+      if date >= week_ago(start) && date <= week_ago(end) {
+        last_week += record.amount;
+      }
+    }
+  }
+
+  let name = |subcategory: usize| {
+    tracker_data
+      .subcategory_name(subcategory)
+      .cloned()
+      .unwrap_or_else(|| "Unknown".to_string())
+  };
+
+  let mut top: Vec<(String, f64)> = by_subcategory
+    .into_iter()
+    .map(|(subcategory, total)| (name(subcategory), total))
+    .collect();
+  top.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+  top.truncate(TOP_SUBCATEGORIES);
+
+  let mut unusual: Vec<UnusualExpense> = this_week
+    .into_iter()
+    .filter_map(|record| {
+      let typical = median(history.get_mut(&record.subcategory)?)?;
+      (record.amount >= typical * UNUSUAL_FACTOR).then(|| UnusualExpense {
+        id: record.id,
+        description: record.description.clone(),
+        subcategory: name(record.subcategory),
+        amount: record.amount,
+        typical,
+      })
+    })
+    .collect();
+  unusual.sort_by(|a, b| (b.amount / b.typical).total_cmp(&(a.amount / a.typical)));
+  unusual.truncate(MAX_UNUSUAL);
+
+  WeeklyDigest {
+    start: start.format(STORAGE_DATE_FORMAT).to_string(),
+    end: end.format(STORAGE_DATE_FORMAT).to_string(),
+    currency: tracker_data.currency.clone(),
+    spent,
+    last_week,
+    top,
+    unusual,
+  }
+}
+
+/// The middle amount, when there are enough to go on
+fn median(amounts: &mut [f64]) -> Option<f64> {
+  if amounts.len() < MIN_HISTORY {
+    return None;
+  }
+  amounts.sort_by(f64::total_cmp);
+  let middle = amounts.len() / 2;
+  Some(if amounts.len().is_multiple_of(2) {
+    (amounts[middle - 1] + amounts[middle]) / 2.0
+  } else {
+    amounts[middle]
+  })
+}
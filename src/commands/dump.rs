@@ -1,18 +1,85 @@
-use clap::{ArgMatches, Command};
+use std::fs::File;
+use std::path::PathBuf;
 
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::output::write_tracker_data;
 use crate::{
-  CliResponse, CliResult, GlobalContext, ResponseContent, TrackerData, utils::file::FilePath,
+  CliResponse, CliResult, DumpPart, GlobalContext, ResponseContent, t, utils::storage::load_tracker,
 };
 
 pub fn cli() -> Command {
   Command::new("dump")
     .about("Display raw JSON data")
     .long_about("Prints the complete tracker data in JSON format to the terminal. Useful for inspection, debugging, or manual backup. The output is formatted with proper indentation for readability.")
+    .arg(
+      Arg::new("records-only")
+        .long("records-only")
+        .action(ArgAction::SetTrue)
+        .help("Print only the records array")
+        .long_help("Prints just the array of records, without the currency, categories and other tracker fields. Handy for piping into jq."),
+    )
+    .arg(
+      Arg::new("meta-only")
+        .long("meta-only")
+        .action(ArgAction::SetTrue)
+        .conflicts_with_all(["records-only", "since"])
+        .help("Print everything but the records")
+        .long_help("Prints the currency, categories, subcategories, budgets and the rest of the tracker, leaving out the records and their edit history, which are most of a big tracker."),
+    )
+    .arg(
+      Arg::new("since")
+        .long("since")
+        .value_name("DATE")
+        .value_parser(clap::value_parser!(String))
+        .help("Only include records on or after this date (DD-MM-YYYY)"),
+    )
+    .arg(
+      Arg::new("compact")
+        .long("compact")
+        .action(ArgAction::SetTrue)
+        .help("Print the JSON on a single line")
+        .long_help("Leaves out indentation and line breaks, which makes the output much smaller for big trackers."),
+    )
+    .arg(
+      Arg::new("out")
+        .short('o')
+        .long("out")
+        .value_name("FILE")
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("Write the JSON to this file instead of the terminal"),
+    )
 }
 
-pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
-  let file = gctx.tracker_path().open_read()?;
-  let tracker_data: TrackerData = serde_json::from_reader(&file)?;
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  if let Some(since) = args.get_date_opt("since")? {
+    tracker_data.records = tracker_data.records_between(Some(since), None).to_vec();
+  }
+
+  let part = if args.get_flag("records-only") {
+    DumpPart::Records
+  } else if args.get_flag("meta-only") {
+    DumpPart::Meta
+  } else {
+    DumpPart::All
+  };
+  let compact = args.get_flag("compact");
+
+  if let Some(out) = args.get_one::<PathBuf>("out") {
+    gctx.verbose(format!("Writing {}", out.display()));
+    write_tracker_data(&tracker_data, part, compact, &mut File::create(out)?)?;
+    return Ok(CliResponse::new(ResponseContent::Message(t!(
+      "dump-written",
+      path = out.display().to_string()
+    ))));
+  }
 
-  Ok(CliResponse::new(ResponseContent::TrackerData(tracker_data)))
+  Ok(CliResponse::new(ResponseContent::TrackerData {
+    tracker_data,
+    part,
+    compact,
+  }))
 }
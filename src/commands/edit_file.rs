@@ -0,0 +1,151 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::utils::parsers::split_command_line;
+use crate::utils::storage::{ensure_writable, load_tracker, parse_tracker, replace_tracker};
+use crate::utils::validation::integrity_problems;
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, TrackerData, t};
+
+pub fn cli() -> Command {
+  Command::new("edit-file")
+    .about("Edit the raw tracker JSON in your editor, safely")
+    .long_about("Copies the tracker to a temporary JSON file and opens it in $VISUAL or $EDITOR. When the editor closes, the file is checked: it must still be a valid tracker, and every record must point at a category and subcategory that exist, with unique ids, real dates and amounts above 0. Only then does it replace the tracker, in one step and after a backup. If the check fails you can edit it again, or the tracker is left alone and your edit is kept so nothing is lost; the next edit-file picks it up again.")
+    .arg(
+      Arg::new("editor")
+        .long("editor")
+        .value_name("COMMAND")
+        .value_parser(clap::value_parser!(String))
+        .help("Editor to use instead of $VISUAL or $EDITOR, e.g. \"code --wait\""),
+    )
+    .arg(
+      Arg::new("fresh")
+        .long("fresh")
+        .action(ArgAction::SetTrue)
+        .help("Throw away an earlier edit that wasn't saved and start from the tracker"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  ensure_writable(gctx)?;
+  let tracker_data = load_tracker(gctx)?;
+
+  let path = gctx.base_path().join("tracker.edit.json");
+  let original = serde_json::to_vec_pretty(&tracker_data)?;
+  let mut warnings = Vec::new();
+  // An edit left over from an earlier run is the user's work, not a copy of the tracker
+  let resumed = path.is_file() && !args.get_flag("fresh");
+  if resumed {
+    warnings.push(t!("edit-resumed"));
+  } else {
+    fs::write(&path, &original)?;
+  }
+  let editor = editor(args)?;
+
+  loop {
+    gctx.verbose(format!("Opening {} with {}", path.display(), editor.join(" ")));
+    if let Err(err) = run_editor(&editor, &path) {
+      if !resumed {
+        fs::remove_file(&path)?;
+      }
+      return Err(err);
+    }
+
+    let edited = fs::read(&path)?;
+    if edited == original {
+      fs::remove_file(&path)?;
+      return Ok(
+        CliResponse::new(ResponseContent::Message(t!("edit-no-changes")))
+        .with_warnings(warnings),
+      );
+    }
+
+    match check(&edited) {
+      Ok(mut edited) => {
+        edited.last_modified = chrono::Utc::now().to_rfc3339();
+        replace_tracker(gctx, &edited)?;
+        fs::remove_file(&path)?;
+        return Ok(
+          CliResponse::new(ResponseContent::Message(t!("edit-saved", count = edited.records.len())))
+          .with_warnings(warnings),
+        );
+      }
+      Err(problems) if gctx.is_interactive() && edit_again(&problems)? => continue,
+      Err(problems) => {
+        return Err(CliError::Other(t!(
+          "error-edit-not-saved",
+          problems = problems.join("; "),
+          path = path.display().to_string()
+        )));
+      }
+    }
+  }
+}
+
+/// `--editor`, then `$VISUAL`, then `$EDITOR`, split into a program and its
+/// arguments
+fn editor(args: &ArgMatches) -> Result<Vec<String>, CliError> {
+  let command = args
+    .get_one::<String>("editor")
+    .cloned()
+    .or_else(|| std::env::var("VISUAL").ok())
+    .or_else(|| std::env::var("EDITOR").ok())
+    .filter(|command| !command.trim().is_empty())
+    .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+  let words = split_command_line(&command).map_err(CliError::Other)?;
+  if words.is_empty() {
+    return Err(CliError::Other(t!("error-no-editor")));
+  }
+  Ok(words)
+}
+
+fn run_editor(editor: &[String], path: &Path) -> Result<(), CliError> {
+  let status = process::Command::new(&editor[0])
+    .args(&editor[1..])
+    .arg(path)
+    .status()
+    .map_err(|err| {
+      CliError::Other(t!("error-editor-start", editor = editor[0].as_str(), reason = err.to_string()))
+    })?;
+  if !status.success() {
+    return Err(CliError::Other(t!(
+      "error-editor-failed",
+      editor = editor[0].as_str(),
+      status = status.to_string()
+    )));
+  }
+  Ok(())
+}
+
+/// The edited tracker, or everything wrong with it
+fn check(edited: &[u8]) -> Result<TrackerData, Vec<String>> {
+  let tracker_data = parse_tracker(edited).map_err(|err| {
+    vec![match err {
+      CliError::InvalidField { path, message, .. } => t!("edit-field-invalid", path = path, message = message),
+      CliError::InvalidJson(message) => t!("edit-json-invalid", message = message),
+      other => t!("edit-unreadable", kind = other.kind()),
+    }]
+  })?;
+  let problems = integrity_problems(&tracker_data);
+  if problems.is_empty() {
+    Ok(tracker_data)
+  } else {
+    Err(problems)
+  }
+}
+
+fn edit_again(problems: &[String]) -> Result<bool, CliError> {
+  println!("{}", t!("edit-problems"));
+  for problem in problems {
+    println!("  - {}", problem);
+  }
+  print!("{} ", t!("edit-again-prompt"));
+  io::stdout().flush()?;
+
+  let mut input = String::new();
+  io::stdin().read_line(&mut input)?;
+  Ok(!matches!(input.trim().to_lowercase().as_str(), "n" | "no"))
+}
@@ -1,61 +1,82 @@
+use std::fmt::Write as _;
 use std::fs::File;
-use std::io::Write;
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 
 use chrono::Utc;
 use clap::{Arg, ArgMatches, Command};
+use rayon::prelude::*;
 
 use crate::{
-  CliError, CliResponse, CliResult, ExportFileType, GlobalContext, TrackerData,
-  utils::file::FilePath,
+  CliError, CliResponse, CliResult, ExportFileType, GlobalContext, TrackerData, t,
+  utils::{
+    filters::{RecordFilter, filter_args},
+    gsheet,
+    hooks::{self, Hook},
+    progress::{self, Unit},
+    storage::load_tracker,
+  },
 };
 
+/// Size of each write when reporting progress on a JSON export
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Records formatted per task on a CSV export
+const CSV_CHUNK_RECORDS: usize = 4096;
+
 pub fn cli() -> Command {
-  Command::new("export")
+  let cmd = Command::new("export")
     .about("Export your tracker data to a file")
-    .long_about("Saves your complete financial data to a file in CSV or JSON format. The file will be created with a timestamp in the filename (e.g., fintrack_export_2025-12-30T14-45-30Z.csv). Useful for backups or importing into other tools.")
+    .long_about("Saves your complete financial data to a file in CSV or JSON format. The file will be created with a timestamp in the filename (e.g., fintrack_export_2025-12-30T14-45-30Z.csv). Useful for backups or importing into other tools. The filters 'fintrack list' takes export just the records that match. With '-t gsheet' the records and a summary are written to a Google Sheet instead (see the gsheet.* settings in 'fintrack config set --help').")
     .arg(
       Arg::new("path")
         .help("Directory where the exported file will be saved")
-        .long_help("The folder path where you want to save the exported file. The directory must exist. The file will be created in this directory with an auto-generated timestamped filename.")
+        .long_help("The folder path where you want to save the exported file. The directory must exist. The file will be created in this directory with an auto-generated timestamped filename. If omitted, the 'export.dir' setting is used (see 'fintrack config').")
         .index(1)
-        .required(true)
         .value_parser(clap::value_parser!(PathBuf)),
     )
     .arg(
       Arg::new("type")
-        .help("File format: 'csv' or 'json'")
-        .long_help("The format for the exported file. Use 'csv' for spreadsheet compatibility (Excel, Google Sheets) or 'json' for programmatic access. Defaults to 'json' if not specified.")
+        .help("File format: 'csv', 'json' or 'gsheet'")
+        .long_help("The format for the exported file. Use 'csv' for spreadsheet compatibility (Excel, Google Sheets) or 'json' for programmatic access. Use 'gsheet' to replace the 'Records' and 'Summary' tabs of the Google Sheet in 'gsheet.spreadsheet_id'; no path is needed. Defaults to 'json' if not specified.")
         .short('t')
         .long("type")
         .value_parser(clap::value_parser!(ExportFileType))
         .default_value("json"),
-    )
+    );
+  filter_args(cmd)
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
-  let file = gctx.tracker_path().open_read()?;
-  let tracker_data: TrackerData = serde_json::from_reader(&file)?;
-
-  let export_path = args
-    .get_one::<PathBuf>("path")
-    .ok_or_else(|| CliError::Other("Export path not provided".to_string()))?;
+  let mut tracker_data = load_tracker(gctx)?;
+  let filter = RecordFilter::from_args(gctx, &tracker_data, args)?;
+  if !filter.is_empty() {
+    gctx.verbose(format!("Exporting records matching {:?}", filter));
+    tracker_data.records = filter.select(&tracker_data).into_owned();
+  }
 
   let file_type = args
     .get_one::<ExportFileType>("type")
-    .ok_or_else(|| CliError::Other("File type not provided".to_string()))?;
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "type")))?;
+  if let ExportFileType::GSheet = file_type {
+    return export_to_gsheet(gctx, &tracker_data);
+  }
+
+  let export_path = match args.get_one::<PathBuf>("path") {
+    Some(path) => path.clone(),
+    None => default_export_dir(gctx)?,
+  };
 
   // Validate path exists and is a directory
   if !export_path.exists() {
-    return Err(CliError::Other(format!(
-      "Path does not exist: {}",
-      export_path.display()
+    return Err(CliError::Other(t!(
+      "error-path-missing",
+      path = export_path.display().to_string()
     )));
   }
   if !export_path.is_dir() {
-    return Err(CliError::Other(format!(
-      "Path is not a directory: {}",
-      export_path.display()
+    return Err(CliError::Other(t!(
+      "error-path-not-directory",
+      path = export_path.display().to_string()
     )));
   }
 
@@ -65,33 +86,111 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     ExportFileType::CSV => "csv",
     ExportFileType::JSON => "json",
     ExportFileType::PDF => "pdf",
+    ExportFileType::GSheet => unreachable!("Google Sheets exports are handled above"),
   };
   let filename = format!("fintrack_export_{}.{}", timestamp_str, extension);
   let file_path = export_path.join(&filename);
+  gctx.verbose(format!("Writing export to {}", file_path.display()));
 
   // Export based on file type
   match file_type {
-    ExportFileType::CSV => export_to_csv(&tracker_data, &file_path)?,
-    ExportFileType::JSON => export_to_json(&tracker_data, &file_path)?,
+    ExportFileType::CSV => export_to_csv(gctx, &tracker_data, &file_path)?,
+    ExportFileType::JSON => export_to_json(gctx, &tracker_data, &file_path)?,
     ExportFileType::PDF => {
-      return Err(CliError::Other("PDF export not yet implemented".to_string()))
+      return Err(CliError::Other(t!("error-pdf-export")))
     }
+    ExportFileType::GSheet => unreachable!("Google Sheets exports are handled above"),
   }
 
-  Ok(CliResponse::new(crate::ResponseContent::Message(format!(
-    "Data exported to: {}",
-    file_path.display()
+  let payload = serde_json::json!({
+    "file": file_path,
+    "type": extension,
+    "records": tracker_data.records.len(),
+  });
+  hooks::run(gctx, Hook::PostExport, &payload)?;
+
+  Ok(CliResponse::new(crate::ResponseContent::Message(t!(
+    "export-done",
+    path = file_path.display().to_string()
   ))))
 }
 
-fn export_to_csv(tracker_data: &TrackerData, file_path: &PathBuf) -> Result<(), CliError> {
-  let mut file = File::create(file_path)?;
+fn export_to_gsheet(gctx: &GlobalContext, tracker_data: &TrackerData) -> CliResult {
+  gctx.ensure_online("Exporting to Google Sheets")?;
+  gctx.verbose(format!(
+    "Writing {} record(s) to Google Sheets",
+    tracker_data.records.len()
+  ));
+  let url = gsheet::push(&gctx.config().gsheet, tracker_data)?;
+
+  let payload = serde_json::json!({
+    "file": url,
+    "type": "gsheet",
+    "records": tracker_data.records.len(),
+  });
+  hooks::run(gctx, Hook::PostExport, &payload)?;
+
+  Ok(CliResponse::new(crate::ResponseContent::Message(t!(
+    "export-done",
+    path = url.as_str()
+  ))))
+}
+
+/// The configured `export.dir`, created if it doesn't exist yet
+fn default_export_dir(gctx: &GlobalContext) -> Result<PathBuf, CliError> {
+  let dir = gctx.config().export.dir.as_deref().ok_or_else(|| {
+    CliError::Other(t!("error-no-export-dir"))
+  })?;
+
+  let path = match dir.strip_prefix("~") {
+    Some("") => gctx.home_path().clone(),
+    Some(rest) if rest.starts_with(std::path::is_separator) => gctx.home_path().join(&rest[1..]),
+    _ => PathBuf::from(dir),
+  };
+  if !path.exists() {
+    gctx.verbose(format!("Creating export directory {}", path.display()));
+    std::fs::create_dir_all(&path)?;
+  }
+
+  Ok(path)
+}
+
+fn export_to_csv(
+  gctx: &GlobalContext,
+  tracker_data: &TrackerData,
+  file_path: &PathBuf,
+) -> Result<(), CliError> {
+  let mut file = BufWriter::new(File::create(file_path)?);
+  let bar = progress::start(
+    gctx,
+    Unit::Records,
+    tracker_data.records.len() as u64,
+    "Exporting",
+  );
 
   // Write CSV header
   writeln!(file, "ID,Category,Subcategory,Amount,Currency,Date,Description")?;
 
-  // Write records
-  for record in &tracker_data.records {
+  // Format chunks of records in parallel; collecting keeps them in order
+  let chunks: Vec<(usize, String)> = tracker_data
+    .records
+    .par_chunks(CSV_CHUNK_RECORDS)
+    .map(|records| (records.len(), csv_rows(tracker_data, records)))
+    .collect();
+
+  for (count, rows) in chunks {
+    file.write_all(rows.as_bytes())?;
+    bar.inc(count as u64);
+  }
+
+  file.flush()?;
+  bar.finish_and_clear();
+  Ok(())
+}
+
+fn csv_rows(tracker_data: &TrackerData, records: &[crate::Record]) -> String {
+  let mut rows = String::new();
+  for record in records {
     let category_name = tracker_data
       .category_name(record.category)
       .map(|s| s.as_str())
@@ -105,11 +204,10 @@ fn export_to_csv(tracker_data: &TrackerData, file_path: &PathBuf) -> Result<(),
     let description = record
       .description
       .replace('"', "\"\"")
-      .replace('\n', " ")
-      .replace('\r', " ");
+      .replace(['\n', '\r'], " ");
 
-    writeln!(
-      file,
+    let _ = writeln!(
+      rows,
       "{},{},{},{},{},{},\"{}\"",
       record.id,
       category_name,
@@ -118,15 +216,25 @@ fn export_to_csv(tracker_data: &TrackerData, file_path: &PathBuf) -> Result<(),
       tracker_data.currency,
       record.date,
       description
-    )?;
+    );
   }
-
-  Ok(())
+  rows
 }
 
-fn export_to_json(tracker_data: &TrackerData, file_path: &PathBuf) -> Result<(), CliError> {
+fn export_to_json(
+  gctx: &GlobalContext,
+  tracker_data: &TrackerData,
+  file_path: &PathBuf,
+) -> Result<(), CliError> {
   let json_string = serde_json::to_string_pretty(tracker_data)?;
   let mut file = File::create(file_path)?;
-  file.write_all(json_string.as_bytes())?;
+
+  let bar = progress::start(gctx, Unit::Bytes, json_string.len() as u64, "Exporting");
+  for chunk in json_string.as_bytes().chunks(CHUNK_SIZE) {
+    file.write_all(chunk)?;
+    bar.inc(chunk.len() as u64);
+  }
+
+  bar.finish_and_clear();
   Ok(())
 }
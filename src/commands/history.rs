@@ -0,0 +1,94 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::utils::validation::check_open;
+use crate::utils::webhook::{self, Event};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind, t};
+
+pub fn cli() -> Command {
+  Command::new("history")
+    .about("Show every version of a record, or put an earlier one back")
+    .long_about("Lists the versions a record has been through, oldest first: each 'fintrack update' keeps what the record was before the change. With --revert N the record goes back to version N. The revert is itself a change, so it shows up as a new version and can be undone the same way.")
+    .arg(
+      Arg::new("record_id")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(usize))
+        .help("The ID of the record"),
+    )
+    .arg(
+      Arg::new("revert")
+        .long("revert")
+        .value_name("VERSION")
+        .value_parser(clap::value_parser!(usize))
+        .help("Restore the record to this version"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let record_id = args
+    .get_usize("record_id")
+    .map_err(|_| CliError::ValidationError(ValidationErrorKind::RecordNotFound { id: 0 }))?;
+  let mut versions = tracker_data.record_versions(record_id);
+  if versions.is_empty() {
+    return Err(CliError::ValidationError(ValidationErrorKind::RecordNotFound { id: record_id }));
+  }
+
+  let Some(&version) = args.get_one::<usize>("revert") else {
+    return Ok(CliResponse::new(ResponseContent::History {
+      id: record_id,
+      versions,
+      tracker_data,
+    }));
+  };
+
+  let current = versions.len();
+  if version == 0 || version > current {
+    return Err(CliError::ValidationError(ValidationErrorKind::VersionNotFound {
+      id: record_id,
+      version,
+    }));
+  }
+  if version == current {
+    return Ok(CliResponse::new(ResponseContent::Message(t!(
+      "history-current-version",
+      id = record_id,
+      version = version
+    ))));
+  }
+
+  let restored = versions.swap_remove(version - 1).record;
+  if tracker_data.subcategory_name(restored.subcategory).is_none() {
+    return Err(CliError::ValidationError(ValidationErrorKind::SubcategoryNotFound {
+      name: format!("#{}", restored.subcategory),
+      nearest: None,
+    }));
+  }
+
+  let index = tracker_data
+    .records
+    .iter()
+    .position(|r| r.id == record_id)
+    .expect("the record has versions, so it exists");
+  check_open(&tracker_data, tracker_data.records[index].naive_date())?;
+  check_open(&tracker_data, restored.naive_date())?;
+
+  let previous = std::mem::replace(&mut tracker_data.records[index], restored.clone());
+  tracker_data.keep_version(previous, Some(version));
+  tracker_data.sort_records();
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+
+  tracing::info!(id = record_id, version, "record reverted");
+
+  save_tracker(gctx, &tracker_data)?;
+  webhook::notify(gctx, Event::Update, std::slice::from_ref(&restored), &tracker_data);
+
+  Ok(CliResponse::new(ResponseContent::Record {
+    record: restored,
+    tracker_data,
+    is_update: true,
+  }))
+}
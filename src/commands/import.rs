@@ -0,0 +1,226 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::commands::subcategory;
+use crate::parsers::{parse_category, parse_date};
+use crate::utils::fuzzy;
+use crate::utils::progress::{self, Unit};
+use crate::utils::storage::{ensure_writable, load_tracker, save_tracker};
+use crate::utils::validation::{check_open, round_amount};
+use crate::{
+  Category, CliError, CliResponse, CliResult, GlobalContext, Record, ResponseContent, TrackerData, t,
+};
+
+/// Rows checked before they're added to the tracker together
+const BATCH_ROWS: usize = 10_000;
+
+pub fn cli() -> Command {
+  Command::new("import")
+    .about("Add records from a CSV file, such as a bank statement")
+    .long_about("Reads records from a CSV file with a header row. 'Amount' and 'Date' columns are required; 'Category', 'Subcategory', 'Description' and 'Currency' are used when present, so a file from 'fintrack export -t csv' reads back in. Without a Category column, negative amounts are expenses and positive ones income, as on most bank statements. Dates can be in any of the 'input.date_formats'. Rows are read and checked one at a time, so statements with hundreds of thousands of rows don't need much memory. The first bad row stops the import and nothing is saved, unless --skip-invalid is given. Hooks and the webhook don't run for imported records.")
+    .arg(
+      Arg::new("file")
+        .help("CSV file to read")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(PathBuf)),
+    )
+    .arg(
+      Arg::new("create")
+        .long("create")
+        .action(ArgAction::SetTrue)
+        .help("Create subcategories the file uses that don't exist yet"),
+    )
+    .arg(
+      Arg::new("skip-invalid")
+        .long("skip-invalid")
+        .action(ArgAction::SetTrue)
+        .help("Leave out rows that can't be imported instead of stopping"),
+    )
+}
+
+/// Where each known column is in the file
+struct Columns {
+  category: Option<usize>,
+  subcategory: Option<usize>,
+  amount: usize,
+  date: usize,
+  description: Option<usize>,
+  currency: Option<usize>,
+}
+
+impl Columns {
+  fn from_header(header: &csv::StringRecord) -> Result<Self, String> {
+    let find = |name: &str| header.iter().position(|column| column.trim().eq_ignore_ascii_case(name));
+    let required = |name: &str| find(name).ok_or_else(|| t!("import-no-column", column = name));
+    Ok(Columns {
+      category: find("category"),
+      subcategory: find("subcategory"),
+      amount: required("amount")?,
+      date: required("date")?,
+      description: find("description"),
+      currency: find("currency"),
+    })
+  }
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let path = args
+    .get_one::<PathBuf>("file")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "file")))?;
+  let create = args.get_flag("create");
+  let skip_invalid = args.get_flag("skip-invalid");
+
+  ensure_writable(gctx)?;
+  let mut tracker_data = load_tracker(gctx)?;
+  let subcategories_before = tracker_data.subcategories_by_id.len();
+
+  let file = File::open(path).map_err(|err| match err.kind() {
+    std::io::ErrorKind::NotFound => CliError::FileNotFound(path.display().to_string()),
+    _ => err.into(),
+  })?;
+  let bar = progress::start(gctx, Unit::Bytes, file.metadata()?.len(), "Importing");
+  let mut reader = csv::ReaderBuilder::new()
+    .flexible(true)
+    .from_reader(BufReader::new(bar.wrap_read(file)));
+  let invalid = |line: u64, reason: String| {
+    CliError::Other(t!("error-import-line", path = path.display().to_string(), line = line, reason = reason))
+  };
+  let columns = Columns::from_header(reader.headers().map_err(|err| invalid(1, err.to_string()))?)
+    .map_err(|reason| invalid(1, reason))?;
+
+  let mut row = csv::StringRecord::new();
+  let mut batch = Vec::with_capacity(BATCH_ROWS);
+  let mut imported = 0;
+  let mut skipped = Vec::new();
+  loop {
+    let line = reader.position().line();
+    match reader.read_record(&mut row) {
+      Ok(true) => {}
+      Ok(false) => break,
+      Err(err) => return Err(invalid(line, err.to_string())),
+    }
+    if row.iter().all(|field| field.trim().is_empty()) {
+      continue;
+    }
+
+    match read_row(gctx, &mut tracker_data, &columns, &row, create) {
+      Ok(record) => batch.push(record),
+      Err(reason) if skip_invalid => skipped.push(t!("import-row", line = line, reason = reason)),
+      Err(reason) => {
+        bar.finish_and_clear();
+        return Err(invalid(line, reason));
+      }
+    }
+    if batch.len() == BATCH_ROWS {
+      imported += add_batch(&mut tracker_data, &mut batch);
+    }
+  }
+  imported += add_batch(&mut tracker_data, &mut batch);
+  bar.finish_and_clear();
+
+  // Records were appended a batch at a time; one sort puts them in place
+  tracker_data.sort_records();
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  let mut warnings = Vec::new();
+  if !skipped.is_empty() {
+    gctx.verbose(format!("Skipped rows:\n  {}", skipped.join("\n  ")));
+    warnings.push(t!("import-skipped", count = skipped.len(), first = skipped[0].as_str()));
+  }
+  Ok(
+    CliResponse::new(ResponseContent::Message(t!(
+      "import-done",
+      count = imported,
+      path = path.display().to_string(),
+      subcategories = tracker_data.subcategories_by_id.len() - subcategories_before
+    )))
+    .with_warnings(warnings),
+  )
+}
+
+/// Give the batch's records their ids and move them into the tracker
+fn add_batch(tracker_data: &mut TrackerData, batch: &mut Vec<Record>) -> usize {
+  let count = batch.len();
+  for record in batch.iter_mut() {
+    record.id = tracker_data.next_record_id;
+    tracker_data.next_record_id += 1;
+  }
+  tracker_data.records.append(batch);
+  count
+}
+
+/// The record on one row, or why it can't be imported
+fn read_row(
+  gctx: &GlobalContext,
+  tracker_data: &mut TrackerData,
+  columns: &Columns,
+  row: &csv::StringRecord,
+  create: bool,
+) -> Result<Record, String> {
+  let field = |index: Option<usize>| index.and_then(|index| row.get(index)).map(str::trim).unwrap_or("");
+
+  let amount_text = field(Some(columns.amount));
+  let amount: f64 = amount_text
+    .parse()
+    .ok()
+    .filter(|amount: &f64| amount.is_finite())
+    .ok_or_else(|| t!("import-not-amount", amount = amount_text))?;
+  let category = match field(columns.category) {
+    "" if columns.category.is_some() => return Err(t!("import-category-empty")),
+    "" if amount < 0.0 => Category::Expenses,
+    "" => Category::Income,
+    name => parse_category(name)?,
+  };
+  let amount = round_amount(gctx, &tracker_data.currency, amount.abs())
+    .map_err(|_| t!("import-too-many-decimals", amount = amount_text))?;
+  if amount <= 0.0 {
+    return Err(t!("import-amount-not-positive"));
+  }
+
+  let currency = field(columns.currency);
+  if !currency.is_empty() && !currency.eq_ignore_ascii_case(&tracker_data.currency) {
+    return Err(t!("import-currency-mismatch", currency = currency, tracker = tracker_data.currency.as_str()));
+  }
+
+  let date_text = field(Some(columns.date));
+  let date = parse_date(date_text).map_err(|_| t!("import-not-date", date = date_text))?;
+  check_open(tracker_data, Some(date))
+    .map_err(|_| t!("import-closed-month", date = date.format("%d-%m-%Y").to_string()))?;
+
+  let name = match field(columns.subcategory) {
+    "" => "miscellaneous",
+    name => name,
+  };
+  let subcategory = match tracker_data.subcategory_id(name) {
+    Some(id) => id,
+    None if create => subcategory::add::create(tracker_data, name)
+      .map_err(|_| t!("import-bad-subcategory", name = name))?,
+    None => {
+      let names = tracker_data.subcategories_by_id.values().map(String::as_str);
+      return Err(match fuzzy::closest(name, names) {
+        Some(nearest) => t!("import-no-subcategory-nearest", name = name, nearest = nearest),
+        None => t!("import-no-subcategory", name = name),
+      });
+    }
+  };
+
+  Ok(Record {
+    id: 0,
+    category: tracker_data.category_id(&category.to_string()),
+    subcategory,
+    description: field(columns.description).to_string(),
+    amount,
+    date: date.format("%d-%m-%Y").to_string(),
+    entered_by: None,
+    deductible: false,
+    project: None,
+    foreign: None,
+    tags: Vec::new(),
+    parsed_date: Default::default(),
+  })
+}
@@ -1,8 +1,16 @@
+use std::io::{self, BufRead, Write};
+
 use clap::{Arg, ArgMatches, Command};
+use colored::Colorize;
 
 use crate::command_prelude::ArgMatchesExt;
+use crate::parsers::parse_amount;
 use crate::utils::file::{FilePath, write_json_to_file};
-use crate::{CliResponse, CliResult, Currency, GlobalContext, default_tracker_json};
+use crate::utils::storage::ensure_writable;
+use crate::utils::validation::round_amount;
+use crate::{
+  CliError, CliResponse, CliResult, Currency, GlobalContext, ResponseContent, default_tracker_json, t,
+};
 
 pub fn cli() -> Command {
   Command::new("init")
@@ -21,7 +29,7 @@ pub fn cli() -> Command {
       Arg::new("opening")
         .short('o')
         .long("opening")
-        .value_parser(clap::value_parser!(f64))
+        .value_parser(parse_amount)
         .help("Your opening balance amount")
         .long_help("Sets your starting balance. This is the amount you have before adding any income or expenses. Defaults to 0.0 if not specified."),
     )
@@ -31,12 +39,91 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
   let currency = args.get_currency_or_default("currency");
   let opening_balance = args.get_f64_or_default("opening");
 
-  // std::fs::create_dir_all(gctx.backups_path())?;
+  // There's no tracker yet to show changes to
+  if gctx.is_dry_run() {
+    ensure_writable(gctx)?;
+    return Ok(CliResponse::new(ResponseContent::Message(
+      match gctx.tracker_path().exists() {
+        true => t!("init-dry-run-exists", path = gctx.tracker_path().display().to_string()),
+        false => t!(
+          "init-dry-run",
+          currency = currency.to_string(),
+          path = gctx.tracker_path().display().to_string()
+        ),
+      },
+    )));
+  }
+
+  create_tracker(gctx, currency, opening_balance)?;
+
+  Ok(CliResponse::success())
+}
 
+fn create_tracker(gctx: &GlobalContext, currency: &Currency, opening_balance: f64) -> Result<(), CliError> {
+  ensure_writable(gctx)?;
+  gctx.verbose(format!("Creating {}", gctx.tracker_path().display()));
   let mut file = gctx.tracker_path().create_file_if_not_exists()?;
 
+  let opening_balance = round_amount(gctx, &currency.to_string(), opening_balance)?;
   let default_json = default_tracker_json(currency, opening_balance);
-  write_json_to_file(&default_json, &mut file)?;
+  write_json_to_file(&default_json, &mut file, gctx.config().storage.compact)?;
 
-  Ok(CliResponse::success())
+  Ok(())
+}
+
+/// Offer to create the tracker when a command is run before `init`, asking
+/// for the currency and opening balance. Returns whether a tracker was created.
+pub fn guided_setup(gctx: &GlobalContext) -> Result<bool, CliError> {
+  let stdin = io::stdin();
+  let mut input = stdin.lock();
+
+  println!("{}", "Welcome to fintrack! You don't have a tracker yet.".bright_white().bold());
+  let answer = prompt(&mut input, "Set one up now? (Y/n): ")?;
+  if matches!(answer.to_lowercase().as_str(), "n" | "no") {
+    return Ok(false);
+  }
+
+  let currency = loop {
+    let answer = prompt(&mut input, "Currency (NGN, USD, GBP, EUR, CAD, AUD, JPY) [NGN]: ")?;
+    if answer.is_empty() {
+      break Currency::NGN;
+    }
+    match answer.parse::<Currency>() {
+      Ok(currency) => break currency,
+      Err(_) => println!("{} '{}' is not a supported currency", "✗".red().bold(), answer),
+    }
+  };
+
+  let opening_balance = loop {
+    let answer = prompt(&mut input, "Opening balance [0]: ")?;
+    if answer.is_empty() {
+      break 0.0;
+    }
+    match parse_amount(&answer) {
+      Ok(amount) => break amount,
+      Err(err) => println!("{} {}", "✗".red().bold(), err),
+    }
+  };
+
+  create_tracker(gctx, &currency, opening_balance)?;
+  println!(
+    "{} {}\n",
+    "✓".green().bold(),
+    format!("Tracker created at {}", gctx.tracker_path().display()).bright_green()
+  );
+
+  Ok(true)
+}
+
+/// Print `question` and read one trimmed line. End of input counts as "no"
+/// to the setup as a whole.
+fn prompt(input: &mut impl BufRead, question: &str) -> Result<String, CliError> {
+  print!("{}", question);
+  io::stdout().flush()?;
+
+  let mut line = String::new();
+  if input.read_line(&mut line)? == 0 {
+    return Err(CliError::NotInitialized);
+  }
+  Ok(line.trim().to_string())
 }
@@ -0,0 +1,37 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("invest")
+    .about("Track investment holdings")
+    .long_about("Keeps a record of shares, funds or coins you've bought, with what they cost, so you can see their value and unrealized gains. Holdings are separate from your cash records; add them to your net worth with 'fintrack total --with-investments'.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![add::cli(), value::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "add" => Some(add::exec),
+    "value" => Some(value::exec),
+    _ => None,
+  }
+}
+
+pub mod add;
+pub mod value;
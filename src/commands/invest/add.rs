@@ -0,0 +1,84 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::output::format_amount;
+use crate::parsers::parse_amount;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, InvestmentLot, ResponseContent, ValidationErrorKind, t};
+
+pub fn cli() -> Command {
+  Command::new("add")
+    .about("Record a purchase")
+    .long_about("Adds a purchase of some units of a symbol at a price per unit. Buying the same symbol again adds to the holding; its cost basis is the total paid.")
+    .arg(
+      Arg::new("symbol")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Ticker or other name for what you bought, e.g. AAPL"),
+    )
+    .arg(
+      Arg::new("quantity")
+        .index(2)
+        .required(true)
+        .value_parser(parse_amount)
+        .help("How many units (fractions are fine)"),
+    )
+    .arg(
+      Arg::new("price")
+        .short('p')
+        .long("price")
+        .required(true)
+        .value_parser(parse_amount)
+        .help("Price paid per unit, in the tracker's currency"),
+    )
+    .arg(
+      Arg::new("date")
+        .short('D')
+        .long("date")
+        .value_parser(clap::value_parser!(String))
+        .help("Purchase date in DD-MM-YYYY format; defaults to today"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let symbol = args.get_string_or_default("symbol").trim().to_uppercase();
+  if symbol.is_empty() || symbol.chars().any(char::is_whitespace) {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidName {
+      name: symbol,
+      reason: "symbols can't be empty or contain spaces".to_string(),
+    }));
+  }
+
+  let quantity = args.get_f64_or_default("quantity");
+  let price = args.get_f64_or_default("price");
+  for amount in [quantity, price] {
+    if amount <= 0.0 {
+      return Err(CliError::ValidationError(ValidationErrorKind::AmountTooSmall { amount }));
+    }
+  }
+  let date = args.get_date_opt("date")?.unwrap_or_else(|| gctx.today());
+
+  let investments = &mut tracker_data.investments;
+  investments.lots.push(InvestmentLot {
+    id: investments.lots.iter().map(|lot| lot.id).max().unwrap_or(0) + 1,
+    symbol: symbol.clone(),
+    quantity,
+    price,
+    date: date.format("%d-%m-%Y").to_string(),
+  });
+  // What was just paid is the latest price we know of
+  investments.prices.insert(symbol.clone(), price);
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "invest-bought",
+    quantity = quantity.to_string(),
+    symbol = symbol.as_str(),
+    price = format_amount(price),
+    currency = tracker_data.currency.as_str()
+  ))))
+}
@@ -0,0 +1,77 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::utils::prices;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind};
+
+pub fn cli() -> Command {
+  Command::new("value")
+    .about("Show holdings with their value and unrealized gains")
+    .long_about("Lists each symbol held with its quantity, cost basis, current price, value and unrealized gain. Prices given with --price or fetched with --fetch are remembered for next time; until a symbol has a newer price, its last purchase price is used.")
+    .arg(
+      Arg::new("price")
+        .short('p')
+        .long("price")
+        .value_parser(parse_price)
+        .action(ArgAction::Append)
+        .help("Current price of a symbol, e.g. AAPL=195 (repeatable)"),
+    )
+    .arg(
+      Arg::new("fetch")
+        .long("fetch")
+        .action(ArgAction::SetTrue)
+        .help("Get current prices from the 'invest.price_url' service"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+  let holdings = tracker_data.investments.holdings();
+
+  let mut updates: Vec<(String, f64)> = args.get_vec("price");
+  if args.get_flag("fetch") {
+    gctx.ensure_online("Fetching prices")?;
+    for holding in &holdings {
+      // Typed prices win over fetched ones
+      if !updates.iter().any(|(symbol, _)| *symbol == holding.symbol) {
+        let price = prices::fetch(&gctx.config().invest, &holding.symbol)?;
+        updates.push((holding.symbol.clone(), price));
+      }
+    }
+  }
+
+  if !updates.is_empty() {
+    for (symbol, price) in updates {
+      if !holdings.iter().any(|h| h.symbol == symbol) {
+        return Err(CliError::ValidationError(ValidationErrorKind::InvalidName {
+          name: symbol,
+          reason: "no holding has this symbol".to_string(),
+        }));
+      }
+      tracker_data.investments.prices.insert(symbol, price);
+    }
+    tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+    save_tracker(gctx, &tracker_data)?;
+  }
+
+  Ok(CliResponse::new(ResponseContent::Holdings {
+    holdings: tracker_data.investments.holdings(),
+    currency: tracker_data.currency,
+  }))
+}
+
+/// `SYMBOL=PRICE`, with the symbol upper-cased
+fn parse_price(s: &str) -> Result<(String, f64), String> {
+  let (symbol, price) = s
+    .split_once('=')
+    .ok_or_else(|| format!("'{}' should look like SYMBOL=PRICE, e.g. AAPL=195", s))?;
+  let price: f64 = price
+    .trim()
+    .parse()
+    .map_err(|_| format!("'{}' is not a price", price))?;
+  if price <= 0.0 || !price.is_finite() {
+    return Err(format!("the price of {} must be greater than 0", symbol));
+  }
+  Ok((symbol.trim().to_uppercase(), price))
+}
@@ -1,109 +1,44 @@
-use chrono::NaiveDate;
-use clap::{Arg, ArgGroup, ArgMatches, Command};
+use clap::{Arg, ArgMatches, Command};
 
 use crate::command_prelude::ArgMatchesExt;
-use crate::utils::file::FilePath;
-use crate::utils::parsers::{parse_category, parse_date};
-use crate::{CliResponse, CliResult, GlobalContext, Record, ResponseContent, TrackerData};
+use crate::utils::config::ListOrder;
+use crate::utils::filters::{RecordFilter, filter_args};
+use crate::utils::storage::load_tracker;
+use crate::{CliResponse, CliResult, GlobalContext, Record, ResponseContent};
 
 pub fn cli() -> Command {
-  Command::new("list")
+  let cmd = Command::new("list")
     .about("View and filter your transaction records")
-    .long_about("Displays all your records in a table format. You can filter by date range, category, subcategory, or limit to first/last N records. Records are sorted by date (oldest first).")
+    .long_about("Displays all your records in a table format. You can filter by date range (or a month, or the last N days), category, subcategory, who entered them, project, amount or description text, or limit to first/last N records. The same filters work with delete, export, total and describe. Records are sorted by date (oldest first).")
     .arg(
       Arg::new("first")
         .short('f')
         .long("first")
         .value_parser(clap::value_parser!(usize))
+        .overrides_with("last")
         .help("Show only the first N records (oldest)")
-        .long_help("Limits the output to the first N records when sorted by date. Shows the oldest records. Example: -f 5 shows the first 5 records."),
+        .long_help("Limits the output to the first N records when sorted by date. Shows the oldest records. Example: -f 5 shows the first 5 records. If both --first and --last are given, the one typed last is used. Without either, the 'list.default_limit' setting applies."),
     )
     .arg(
       Arg::new("last")
         .short('l')
         .long("last")
         .value_parser(clap::value_parser!(usize))
+        .overrides_with("first")
         .help("Show only the last N records (newest)")
-        .long_help("Limits the output to the last N records when sorted by date. Shows the most recent records. Example: -l 10 shows the last 10 records."),
-    )
-    .group(
-      ArgGroup::new("first_or_last")
-        .args(["first", "last"])
-        .multiple(false),
-    )
-    .arg(
-      Arg::new("start")
-        .short('S')
-        .long("start")
-        .value_parser(parse_date)
-        .help("Filter records from this date onwards (DD-MM-YYYY)")
-        .long_help("Shows only records on or after this date. Format: DD-MM-YYYY (e.g., 01-12-2025). Use with --end to specify a date range."),
-    )
-    .arg(
-      Arg::new("end")
-        .short('E')
-        .long("end")
-        .value_parser(parse_date)
-        .help("Filter records up to this date (DD-MM-YYYY)")
-        .long_help("Shows only records on or before this date. Format: DD-MM-YYYY (e.g., 31-12-2025). Use with --start to specify a date range."),
-    )
-    .arg(
-      Arg::new("category")
-        .short('c')
-        .long("category")
-        .value_parser(parse_category)
-        .help("Filter by category: 'income' or 'expenses'")
-        .long_help("Shows only records in the specified category. Use 'income' to see all income transactions or 'expenses' to see all expense transactions. Case-insensitive."),
-    )
-    .arg(
-      Arg::new("subcategory")
-        .short('s')
-        .long("subcategory")
-        .value_parser(clap::value_parser!(String))
-        .help("Filter by subcategory name")
-        .long_help("Shows only records in the specified subcategory. The subcategory name is case-insensitive. Use 'fintrack subcategory list' to see available subcategories."),
-    )
+        .long_help("Limits the output to the last N records when sorted by date. Shows the most recent records. Example: -l 10 shows the last 10 records. If both --first and --last are given, the one typed last is used. -l 0 shows every record, whatever 'list.default_limit' says."),
+    );
+  filter_args(cmd)
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
-  let file = gctx.tracker_path().open_read()?;
-  let tracker_data: TrackerData = serde_json::from_reader(&file)?;
-
-  let start_date = args.get_date_opt("start");
-  let end_date = args.get_date_opt("end");
-
-  let category_filter = args
-    .get_category_opt("category")
-    .map(|cat| tracker_data.category_id(&cat.to_string()));
+  let tracker_data = load_tracker(gctx)?;
 
-  let subcategory_filter = args
-    .get_subcategory_opt("subcategory")
-    .and_then(|name| tracker_data.subcategory_id(&name));
+  let filter = RecordFilter::from_args(gctx, &tracker_data, args)?;
+  gctx.verbose(format!("Filters: {:?}", filter));
 
-  let mut filtered_data: Vec<Record> = tracker_data
-    .records
-    .iter()
-    .filter(|r| {
-      // Category filter: if filter is set, record must match
-      category_filter.map_or(true, |expected_id| r.category == expected_id)
-        // Subcategory filter: if filter is set, record must match
-        && subcategory_filter.map_or(true, |expected_id| r.subcategory == expected_id)
-        // Date range filter: parse date and check bounds
-        && NaiveDate::parse_from_str(&r.date, "%d-%m-%Y")
-          .map(|record_date| {
-            start_date.map_or(true, |start| record_date >= start)
-              && end_date.map_or(true, |end| record_date <= end)
-          })
-          .unwrap_or(false)
-    })
-    .cloned()
-    .collect();
-
-  filtered_data.sort_by(|a, b| {
-    let date_a = NaiveDate::parse_from_str(&a.date, "%d-%m-%Y").unwrap_or(NaiveDate::MIN);
-    let date_b = NaiveDate::parse_from_str(&b.date, "%d-%m-%Y").unwrap_or(NaiveDate::MIN);
-    date_a.cmp(&date_b)
-  });
+  let filter_span = tracing::info_span!("filter").entered();
+  let mut filtered_data: Vec<Record> = filter.select(&tracker_data).into_owned();
 
   if args.contains_id("first") {
     let first = args.get_usize_or_default("first");
@@ -116,8 +51,26 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
       let start_idx = filtered_data.len() - last;
       filtered_data = filtered_data.into_iter().skip(start_idx).collect();
     }
+  } else if let Some(limit) = gctx.config().list.default_limit
+    && limit > 0
+    && filtered_data.len() > limit
+  {
+    gctx.verbose(format!(
+      "Showing {} of {} records (list.default_limit)",
+      limit,
+      filtered_data.len()
+    ));
+    match gctx.config().list.default_order {
+      ListOrder::Newest => {
+        filtered_data.drain(..filtered_data.len() - limit);
+      }
+      ListOrder::Oldest => filtered_data.truncate(limit),
+    }
   }
 
+  tracing::debug!(matched = filtered_data.len(), "records filtered");
+  drop(filter_span);
+
   Ok(CliResponse::new(ResponseContent::List {
     records: filtered_data,
     tracker_data,
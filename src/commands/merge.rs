@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::utils::storage::{load_tracker, read_tracker_file, save_tracker};
+use crate::utils::validation::{check_open, find_duplicate};
+use crate::utils::webhook::{self, Event};
+use crate::{
+  Budget, BudgetRule, CliError, CliResponse, CliResult, DuplicateRule, GlobalContext, MergeSummary,
+  Record, ResponseContent, t,
+};
+
+pub fn cli() -> Command {
+  Command::new("merge")
+    .about("Bring the records of another tracker into this one")
+    .long_about("Adds the records of another tracker file to this one, e.g. to bring separate personal and work trackers together. Records get new IDs here, subcategories are matched by name and created when missing, and records that match one already here (same day, amount, subcategory and description) are skipped unless --duplicates keep is given. Budgets come along only with --budgets. Loans, bills, schedules and history stay behind. Both trackers must use the same currency.")
+    .arg(
+      Arg::new("tracker")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("The tracker file to merge in, e.g. work/tracker.json"),
+    )
+    .arg(
+      Arg::new("duplicates")
+        .long("duplicates")
+        .value_parser(clap::value_parser!(DuplicateRule))
+        .default_value("skip")
+        .help("What to do with records that match one already here"),
+    )
+    .arg(
+      Arg::new("budgets")
+        .long("budgets")
+        .value_name("WINNER")
+        .num_args(0..=1)
+        .default_missing_value("ours")
+        .value_parser(clap::value_parser!(BudgetRule))
+        .help("Also merge budgets; when both have one for a subcategory, keep ours (the default) or theirs"),
+    )
+    .arg(
+      Arg::new("dry-run")
+        .long("dry-run")
+        .action(ArgAction::SetTrue)
+        .help("Show what would be merged without changing anything"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+  let path = args.get_one::<PathBuf>("tracker").cloned().unwrap_or_default();
+  let other = read_tracker_file(&path)?;
+  if !other.currency.eq_ignore_ascii_case(&tracker_data.currency) {
+    return Err(CliError::Other(t!(
+      "error-merge-currency",
+      path = path.display().to_string(),
+      other = other.currency.as_str(),
+      currency = tracker_data.currency.as_str()
+    )));
+  }
+
+  let duplicates = args.get_one::<DuplicateRule>("duplicates").copied().unwrap_or_default();
+  let dry_run = args.get_flag("dry-run");
+  let mut summary = MergeSummary {
+    source: path.display().to_string(),
+    dry_run,
+    ..Default::default()
+  };
+
+  // Their subcategory IDs to ours, creating the ones this tracker lacks
+  let mut subcategories: HashMap<usize, usize> = HashMap::new();
+  let mut theirs: Vec<(&usize, &String)> = other.subcategories_by_id.iter().collect();
+  theirs.sort();
+  for (&id, name) in theirs {
+    let ours = match tracker_data.subcategory_id(name) {
+      Some(ours) => ours,
+      None => {
+        summary.subcategories_added.push(name.clone());
+        tracker_data.add_subcategory(name)
+      }
+    };
+    subcategories.insert(id, ours);
+  }
+
+  // Checked against this tracker as it was, so their records never count as
+  // duplicates of each other
+  let mut added: Vec<Record> = Vec::new();
+  for record in &other.records {
+    // A subcategory missing from their tracker falls back to Miscellaneous
+    let Some(subcategory) = subcategories
+      .get(&record.subcategory)
+      .copied()
+      .or(tracker_data.miscellaneous_subcategory_id())
+    else {
+      continue;
+    };
+    let category = other.category_name(record.category).map_or("expenses", String::as_str);
+    let merged = Record {
+      id: tracker_data.next_record_id + added.len(),
+      category: tracker_data.category_id(category),
+      subcategory,
+      ..record.clone()
+    };
+    if duplicates == DuplicateRule::Skip && find_duplicate(&merged, &tracker_data).is_some() {
+      summary.duplicates_skipped += 1;
+      continue;
+    }
+    check_open(&tracker_data, merged.naive_date())?;
+    added.push(merged);
+  }
+  summary.records_added = added.len();
+
+  if let Some(&rule) = args.get_one::<BudgetRule>("budgets") {
+    for budget in &other.budgets {
+      let Some(&subcategory) = subcategories.get(&budget.subcategory) else {
+        continue;
+      };
+      let name = tracker_data.subcategory_name(subcategory).cloned().unwrap_or_default();
+      match tracker_data.budgets.iter_mut().find(|b| b.subcategory == subcategory) {
+        Some(ours)
+          if rule == BudgetRule::Theirs
+            && (ours.amount != budget.amount
+              || ours.rollover != budget.rollover
+              || ours.warn_at != budget.warn_at
+              || ours.error_at != budget.error_at) =>
+        {
+          *ours = Budget {
+            subcategory,
+            ..budget.clone()
+          };
+          summary.budgets_replaced.push(name);
+        }
+        Some(_) => {}
+        None => {
+          tracker_data.budgets.push(Budget {
+            subcategory,
+            ..budget.clone()
+          });
+          summary.budgets_added.push(name);
+        }
+      }
+    }
+  }
+
+  let changed = !added.is_empty()
+    || !summary.subcategories_added.is_empty()
+    || !summary.budgets_added.is_empty()
+    || !summary.budgets_replaced.is_empty();
+  if dry_run || !changed {
+    return Ok(CliResponse::new(ResponseContent::Merge(summary)));
+  }
+
+  tracker_data.next_record_id += added.len();
+  for record in &added {
+    tracker_data.link_subscriptions(record);
+    tracker_data.push_record(record.clone());
+  }
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  tracing::info!(
+    source = %path.display(),
+    records = added.len(),
+    skipped = summary.duplicates_skipped,
+    "tracker merged"
+  );
+
+  save_tracker(gctx, &tracker_data)?;
+  if !added.is_empty() {
+    webhook::notify(gctx, Event::Add, &added, &tracker_data);
+  }
+
+  Ok(CliResponse::new(ResponseContent::Merge(summary)))
+}
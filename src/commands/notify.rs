@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, ResponseContent, TrackerData,
+  parsers::STORAGE_DATE_FORMAT, t,
+  utils::{alerts, notifier::Channel, storage::load_tracker},
+};
+
+/// Number of expense subcategories listed in the summary
+const TOP_SUBCATEGORIES: usize = 3;
+
+pub fn cli() -> Command {
+  Command::new("notify")
+    .about("Send a spending summary or alerts to Telegram, ntfy or the desktop")
+    .long_about("Sends a summary of the last few days (income, expenses, where the money went and the current balance) to every channel set up in the [notify] section of your config. With --alerts it sends one message per overspent budget, bill due in the next 3 days and spending limit gone over instead, and nothing when all is well. Meant to be run from cron, e.g. every Monday morning.")
+    .arg(
+      Arg::new("days")
+        .help("How many days the summary covers")
+        .long_help("How many days back from today the summary covers, today included. Defaults to 7 for a weekly summary.")
+        .long("days")
+        .value_parser(clap::value_parser!(u32).range(1..))
+        .default_value("7"),
+    )
+    .arg(
+      Arg::new("alerts")
+        .long("alerts")
+        .action(ArgAction::SetTrue)
+        .conflicts_with("days")
+        .help("Send budget overruns, upcoming bills and spending limits gone over instead of a summary"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let channels = Channel::configured(&gctx.config().notify);
+  if channels.is_empty() {
+    return Err(CliError::Other(t!("error-no-notify-channel")));
+  }
+
+  if channels.iter().any(Channel::needs_network) {
+    gctx.ensure_online("Sending notifications")?;
+  }
+
+  let tracker_data = load_tracker(gctx)?;
+  let names: Vec<&str> = channels.iter().map(|c| c.name()).collect();
+
+  if args.get_flag("alerts") {
+    let alerts = alerts::current(gctx, &tracker_data);
+    for channel in &channels {
+      gctx.verbose(format!("Sending {} alert(s) to {}", alerts.len(), channel.name()));
+      for alert in &alerts {
+        channel.send(&alert.title, &alert.body)?;
+      }
+    }
+    return Ok(CliResponse::new(ResponseContent::Message(t!(
+      "notify-alerts-sent",
+      count = alerts.len(),
+      channels = names.join(", ")
+    ))));
+  }
+
+  let days = args.get_one::<u32>("days").copied().unwrap_or(7);
+  let (title, body) = summary(&tracker_data, days, gctx.today());
+
+  for channel in &channels {
+    gctx.verbose(format!("Sending summary to {}", channel.name()));
+    channel.send(&title, &body)?;
+  }
+
+  Ok(CliResponse::new(ResponseContent::Message(t!("notify-summary-sent", channels = names.join(", ")))))
+}
+
+/// Title and body of the summary for the `days` days up to `end`
+fn summary(tracker_data: &TrackerData, days: u32, end: NaiveDate) -> (String, String) {
+  let start = end - Duration::days(i64::from(days) - 1);
+  let income_id = tracker_data.category_id("income");
+
+  let mut income = 0.0;
+  let mut expenses = 0.0;
+  let mut by_subcategory: BTreeMap<&str, f64> = BTreeMap::new();
+  for record in &tracker_data.records {
+    let Some(date) = record.naive_date() else {
+      continue;
+    };
+    if date < start || date > end {
+      continue;
+    }
+
+    if record.category == income_id {
+      income += record.amount;
+    } else {
+      expenses += record.amount;
+      let name = tracker_data
+        .subcategory_name(record.subcategory)
+        .map(|s| s.as_str())
+        .unwrap_or("Unknown");
+      *by_subcategory.entry(name).or_default() += record.amount;
+    }
+  }
+
+  let currency = &tracker_data.currency;
+  let title = format!(
+    "fintrack: {} to {}",
+    start.format(STORAGE_DATE_FORMAT),
+    end.format(STORAGE_DATE_FORMAT)
+  );
+  let mut lines = vec![
+    format!("Income: {:.2} {}", income, currency),
+    format!("Expenses: {:.2} {}", expenses, currency),
+    format!("Net: {:.2} {}", income - expenses, currency),
+  ];
+
+  let mut top: Vec<_> = by_subcategory.into_iter().collect();
+  top.sort_by(|a, b| b.1.total_cmp(&a.1));
+  if !top.is_empty() {
+    lines.push("Top spending:".to_string());
+    for (name, amount) in top.into_iter().take(TOP_SUBCATEGORIES) {
+      lines.push(format!("  {}: {:.2} {}", name, amount, currency));
+    }
+  }
+  lines.push(format!("Balance: {:.2} {}", tracker_data.net_balance(), currency));
+
+  (title, lines.join("\n"))
+}
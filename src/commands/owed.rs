@@ -0,0 +1,39 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("owed")
+    .about("Track money lent to or borrowed from people")
+    .long_about("Keeps a ledger of personal loans, separate from your income and expenses: record what you lend or borrow with 'owed add', repayments with 'owed settle', and see who owes what with 'owed list'. None of it changes your balance.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![add::cli(), list::cli(), settle::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "add" => Some(add::exec),
+    "list" => Some(list::exec),
+    "settle" => Some(settle::exec),
+    _ => None,
+  }
+}
+
+pub mod add;
+pub mod list;
+pub mod settle;
@@ -0,0 +1,120 @@
+use chrono::NaiveDate;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::output::format_amount;
+use crate::parsers::parse_amount;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::utils::validation::round_amount;
+use crate::{CliError, CliResponse, CliResult, GlobalContext, OwedEntry, ResponseContent, t};
+
+pub fn cli() -> Command {
+  Command::new("add")
+    .about("Record money you lent to or borrowed from someone")
+    .long_about("Adds a loan to the ledger. By default the person owes you the amount; use --borrowed when you owe them. Loans never change your balance.")
+    .arg(
+      Arg::new("person")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Who the loan is with")
+        .long_help("The other person. Names are matched case-insensitively, so 'ada' and 'Ada' are the same person."),
+    )
+    .arg(
+      Arg::new("amount")
+        .index(2)
+        .required(true)
+        .value_parser(parse_amount)
+        .help("Amount lent or borrowed (must be greater than 0)"),
+    )
+    .arg(
+      Arg::new("borrowed")
+        .long("borrowed")
+        .action(ArgAction::SetTrue)
+        .help("You borrowed the money, so you owe them"),
+    )
+    .arg(
+      Arg::new("due")
+        .long("due")
+        .value_parser(clap::value_parser!(String))
+        .help("When it should be paid back, in DD-MM-YYYY format")
+        .long_help("When the loan should be paid back, in DD-MM-YYYY or any format listed in the 'input.date_formats' setting. 'owed list' marks it once this date has passed."),
+    )
+    .arg(
+      Arg::new("date")
+        .short('D')
+        .long("date")
+        .value_parser(clap::value_parser!(String))
+        .help("When the money changed hands, in DD-MM-YYYY format; defaults to today"),
+    )
+    .arg(
+      Arg::new("description")
+        .short('d')
+        .long("description")
+        .value_parser(clap::value_parser!(String))
+        .help("Optional note, e.g. what the money was for"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let person = args.get_string_or_default("person").trim().to_string();
+  if person.is_empty() {
+    return Err(CliError::ValidationError(crate::ValidationErrorKind::InvalidName {
+      name: person,
+      reason: t!("reason-name-empty"),
+    }));
+  }
+
+  let amount = round_amount(gctx, &tracker_data.currency, args.get_f64_or_default("amount"))?;
+  if amount <= 0.0 {
+    return Err(CliError::ValidationError(
+      crate::ValidationErrorKind::AmountTooSmall { amount },
+    ));
+  }
+
+  let date = args.get_date_opt("date")?.unwrap_or_else(|| gctx.today());
+  let due = args.get_date_opt("due")?;
+
+  // Keep the spelling used the first time this person was entered
+  let person = tracker_data.owed_name(&person);
+
+  let borrowed = args.get_flag("borrowed");
+  tracker_data.owed.push(OwedEntry {
+    id: tracker_data.owed.iter().map(|entry| entry.id).max().unwrap_or(0) + 1,
+    person: person.clone(),
+    amount: if borrowed { -amount } else { amount },
+    date: date.format("%d-%m-%Y").to_string(),
+    due: due.map(|due| due.format("%d-%m-%Y").to_string()),
+    description: args.get_string_or_default("description"),
+    settlement: false,
+    record: None,
+  });
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(balance_message(
+    &tracker_data,
+    &person,
+    gctx.today(),
+  ))))
+}
+
+/// Where things stand with `person`, e.g. "Ada owes you 200.00 NGN"
+pub(crate) fn balance_message(tracker_data: &crate::TrackerData, person: &str, today: NaiveDate) -> String {
+  let balance = tracker_data
+    .owed_balances(today)
+    .into_iter()
+    .find(|b| b.person.eq_ignore_ascii_case(person))
+    .map_or(0.0, |b| b.balance);
+  let currency = &tracker_data.currency;
+
+  if balance > 0.0 {
+    t!("owed-balance-owes-you", person = person, amount = format_amount(balance), currency = currency.as_str())
+  } else if balance < 0.0 {
+    t!("owed-balance-you-owe", person = person, amount = format_amount(-balance), currency = currency.as_str())
+  } else {
+    t!("owed-balance-settled", person = person)
+  }
+}
@@ -0,0 +1,19 @@
+use clap::{ArgMatches, Command};
+
+use crate::utils::storage::load_tracker;
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("list")
+    .about("Show outstanding balances per person")
+    .long_about("Lists everyone you have an open loan with: how much they owe you (or you owe them), when it's due, and whether it's overdue. People you're settled up with are left out.")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+
+  Ok(CliResponse::new(ResponseContent::Owed {
+    balances: tracker_data.owed_balances(gctx.today()),
+    currency: tracker_data.currency,
+  }))
+}
@@ -0,0 +1,87 @@
+use clap::{Arg, ArgMatches, Command};
+
+use super::add::balance_message;
+use crate::command_prelude::ArgMatchesExt;
+use crate::output::format_amount;
+use crate::parsers::parse_amount;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::utils::validation::round_amount;
+use crate::{CliError, CliResponse, CliResult, GlobalContext, OwedEntry, ResponseContent, ValidationErrorKind};
+
+pub fn cli() -> Command {
+  Command::new("settle")
+    .about("Record a repayment")
+    .long_about("Records that a loan was paid back, in either direction. Without an amount the whole outstanding balance with that person is settled.")
+    .arg(
+      Arg::new("person")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Who paid or was paid"),
+    )
+    .arg(
+      Arg::new("amount")
+        .index(2)
+        .value_parser(parse_amount)
+        .help("Amount repaid; defaults to everything outstanding"),
+    )
+    .arg(
+      Arg::new("date")
+        .short('D')
+        .long("date")
+        .value_parser(clap::value_parser!(String))
+        .help("When the repayment happened, in DD-MM-YYYY format; defaults to today"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let person = args.get_string_or_default("person").trim().to_string();
+  let today = gctx.today();
+  let outstanding = tracker_data
+    .owed_balances(today)
+    .into_iter()
+    .find(|b| b.person.eq_ignore_ascii_case(&person))
+    .ok_or_else(|| CliError::ValidationError(ValidationErrorKind::NothingOwed { person: person.clone() }))?;
+
+  let amount = match args.get_f64_opt("amount") {
+    Some(amount) => round_amount(gctx, &tracker_data.currency, amount)?,
+    None => outstanding.balance.abs(),
+  };
+  if amount <= 0.0 {
+    return Err(CliError::ValidationError(ValidationErrorKind::AmountTooSmall { amount }));
+  }
+  if amount > outstanding.balance.abs() + 0.005 {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidAmount {
+      reason: format!(
+        "{} is more than the {} {} outstanding with {}",
+        format_amount(amount),
+        format_amount(outstanding.balance.abs()),
+        tracker_data.currency,
+        outstanding.person
+      ),
+    }));
+  }
+
+  let date = args.get_date_opt("date")?.unwrap_or(today);
+  tracker_data.owed.push(OwedEntry {
+    id: tracker_data.owed.iter().map(|entry| entry.id).max().unwrap_or(0) + 1,
+    person: outstanding.person.clone(),
+    // Moves the balance towards zero, whichever way it stands
+    amount: -amount * outstanding.balance.signum(),
+    date: date.format("%d-%m-%Y").to_string(),
+    due: None,
+    description: String::new(),
+    settlement: true,
+    record: None,
+  });
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(balance_message(
+    &tracker_data,
+    &outstanding.person,
+    gctx.today(),
+  ))))
+}
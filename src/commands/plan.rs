@@ -0,0 +1,37 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("plan")
+    .about("Share a budget setup without your records")
+    .long_about("Writes your subcategories, budgets, schedules and spending limits to a plan file with 'plan export', and adopts someone else's with 'plan import'. Plans never hold records, so they're safe to publish as a starter budget.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![export::cli(), import::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "export" => Some(export::exec),
+    "import" => Some(import::exec),
+    _ => None,
+  }
+}
+
+pub mod export;
+pub mod import;
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::utils::plan::Plan;
+use crate::utils::storage::load_tracker;
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, t};
+
+pub fn cli() -> Command {
+  Command::new("export")
+    .about("Save your budget setup to a plan file")
+    .long_about("Writes your subcategories, monthly budgets, schedules and the [limits] settings to a TOML file that 'fintrack plan import' can read. No records, balances or history are included.")
+    .arg(
+      Arg::new("file")
+        .help("File to write, e.g. starter-plan.toml")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(PathBuf)),
+    )
+    .arg(
+      Arg::new("force")
+        .long("force")
+        .action(ArgAction::SetTrue)
+        .help("Overwrite the file if it already exists"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let file = args
+    .get_one::<PathBuf>("file")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "file")))?;
+
+  if file.exists() && !args.get_flag("force") {
+    return Err(CliError::Other(t!(
+      "error-file-exists",
+      path = file.display().to_string()
+    )));
+  }
+
+  let tracker_data = load_tracker(gctx)?;
+  let plan = Plan::from_tracker(&tracker_data, &gctx.config().limits);
+  gctx.verbose(format!("Writing {}", file.display()));
+  plan.write(file)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "plan-exported",
+    path = file.display().to_string()
+  ))))
+}
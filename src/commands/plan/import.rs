@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+
+use chrono::Datelike;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::parsers::{STORAGE_DATE_FORMAT, parse_category, parse_due};
+use crate::utils::config::{read_table, set_value, write_table};
+use crate::utils::plan::Plan;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::{Budget, CliError, CliResponse, CliResult, Frequency, GlobalContext, ResponseContent, Schedule, t};
+
+pub fn cli() -> Command {
+  Command::new("import")
+    .about("Adopt the budget setup in a plan file")
+    .long_about("Reads a file written by 'fintrack plan export'. Subcategories you don't have yet are created, its budgets replace yours for the same subcategories, and its schedules are added unless you already have the same one, first due on their next day of the month from today. Spending limits in the plan overwrite your [limits] settings. Your records are never touched. The whole file is checked before anything changes.")
+    .arg(
+      Arg::new("file")
+        .help("Plan file to read, e.g. starter-plan.toml")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(PathBuf)),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let file = args
+    .get_one::<PathBuf>("file")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "file")))?;
+
+  let mut tracker_data = load_tracker(gctx)?;
+  let mut plan = Plan::read(file)?;
+  check(&mut plan).map_err(|reason| {
+    CliError::Other(t!("error-plan-invalid", path = file.display().to_string(), reason = reason))
+  })?;
+
+  let mut warnings = Vec::new();
+  if !plan.currency.eq_ignore_ascii_case(&tracker_data.currency) {
+    warnings.push(t!(
+      "plan-currency-mismatch",
+      plan = plan.currency.as_str(),
+      tracker = tracker_data.currency.as_str()
+    ));
+  }
+
+  // Every name the plan mentions, so its budgets and schedules always have a
+  // subcategory to point at
+  let names = plan
+    .subcategories
+    .iter()
+    .chain(plan.budgets.iter().map(|b| &b.subcategory))
+    .chain(plan.schedules.iter().map(|s| &s.subcategory));
+  let mut subcategories_added = 0;
+  for name in names {
+    if tracker_data.subcategory_id(name).is_none() {
+      tracker_data.add_subcategory(name);
+      subcategories_added += 1;
+    }
+  }
+
+  let today = gctx.today();
+  // Rollover in a plan starts from this month, not from when it was written
+  let month_start = today.with_day(1).expect("day 1 exists");
+  for budget in &plan.budgets {
+    let subcategory = tracker_data.subcategory_id(&budget.subcategory).expect("created above");
+    let since = budget.rollover.then(|| month_start.format(STORAGE_DATE_FORMAT).to_string());
+    match tracker_data.budgets.iter_mut().find(|b| b.subcategory == subcategory) {
+      Some(ours) => {
+        ours.amount = budget.amount;
+        ours.warn_at = budget.warn_at;
+        ours.error_at = budget.error_at;
+        if ours.rollover != budget.rollover {
+          ours.rollover = budget.rollover;
+          ours.since = since;
+        }
+      }
+      None => tracker_data.budgets.push(Budget {
+        subcategory,
+        amount: budget.amount,
+        rollover: budget.rollover,
+        since,
+        warn_at: budget.warn_at,
+        error_at: budget.error_at,
+      }),
+    }
+  }
+
+  let mut schedules_added = 0;
+  for planned in &plan.schedules {
+    let subcategory = tracker_data
+      .subcategory_id(&planned.subcategory)
+      .and_then(|id| tracker_data.subcategory_name(id).cloned())
+      .expect("created above");
+    let exists = tracker_data.schedules.iter().any(|s| {
+      s.category == planned.category
+        && s.subcategory == subcategory
+        && s.amount == planned.amount
+        && s.description == planned.description
+        && s.every == planned.every
+        && s.day == planned.day
+    });
+    if exists {
+      continue;
+    }
+
+    let next = match planned.every {
+      Frequency::Day => today,
+      _ => parse_due(&planned.day.to_string(), today).map_err(CliError::ValidationError)?.0,
+    };
+    let schedule = Schedule {
+      id: tracker_data.schedules.iter().map(|s| s.id).max().unwrap_or(0) + 1,
+      category: planned.category.clone(),
+      subcategory,
+      amount: planned.amount,
+      description: planned.description.clone(),
+      every: planned.every,
+      next: next.format("%d-%m-%Y").to_string(),
+      day: planned.day,
+    };
+    tracker_data.schedules.push(schedule);
+    schedules_added += 1;
+  }
+
+  let limits = [("limits.daily", plan.limits.daily), ("limits.weekly", plan.limits.weekly)];
+  // The dry run's summary covers the tracker; the config is left alone
+  if limits.iter().any(|(_, limit)| limit.is_some()) && !gctx.is_dry_run() {
+    let path = gctx.config_path().clone();
+    let mut table = read_table(&path)?;
+    for (key, limit) in limits {
+      if let Some(limit) = limit {
+        set_value(&mut table, key, &limit.to_string()).map_err(CliError::ValidationError)?;
+      }
+    }
+    gctx.verbose(format!("Writing {}", path.display()));
+    write_table(&path, &table)?;
+    gctx.load_config()?;
+  }
+
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(
+    CliResponse::new(ResponseContent::Message(t!(
+      "plan-imported",
+      path = file.display().to_string(),
+      subcategories = subcategories_added,
+      budgets = plan.budgets.len(),
+      schedules = schedules_added
+    )))
+    .with_warnings(warnings),
+  )
+}
+
+/// Why the plan can't be imported, if it can't. Categories are written the
+/// way schedules store them.
+fn check(plan: &mut Plan) -> Result<(), String> {
+  let names = plan
+    .subcategories
+    .iter()
+    .chain(plan.budgets.iter().map(|b| &b.subcategory))
+    .chain(plan.schedules.iter().map(|s| &s.subcategory));
+  for name in names {
+    if name.trim().is_empty() {
+      return Err("a subcategory has no name".to_string());
+    }
+  }
+  for budget in &plan.budgets {
+    if budget.amount <= 0.0 {
+      return Err(format!("the budget for {} must be more than 0", budget.subcategory));
+    }
+    if budget.warn_at.into_iter().chain(budget.error_at).any(|percent| percent <= 0.0) {
+      return Err(format!("the budget thresholds for {} must be more than 0%", budget.subcategory));
+    }
+  }
+  for schedule in &mut plan.schedules {
+    schedule.category = parse_category(&schedule.category)?.to_string();
+    if schedule.amount <= 0.0 {
+      return Err(format!("a schedule for {} must be more than 0", schedule.subcategory));
+    }
+    if !(1..=31).contains(&schedule.day) {
+      return Err(format!("{} is not a day of the month", schedule.day));
+    }
+  }
+  for limit in [plan.limits.daily, plan.limits.weekly].into_iter().flatten() {
+    if limit <= 0.0 {
+      return Err("spending limits must be more than 0".to_string());
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,37 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("project")
+    .about("See what trips and events cost")
+    .long_about("Totals records tagged with 'fintrack add --project <name>' across subcategories, for budgeting a trip, a wedding or any other event: 'project list' shows every project, 'project status' breaks one down.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![list::cli(), status::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "list" => Some(list::exec),
+    "status" => Some(status::exec),
+    _ => None,
+  }
+}
+
+pub mod list;
+pub mod status;
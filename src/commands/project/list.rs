@@ -0,0 +1,19 @@
+use clap::{ArgMatches, Command};
+
+use crate::utils::storage::load_tracker;
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("list")
+    .about("Show every project with its total cost")
+    .long_about("Lists each project with what was spent on it and the dates it covers, in the order they started.")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+
+  Ok(CliResponse::new(ResponseContent::Projects {
+    projects: tracker_data.project_summaries(),
+    currency: tracker_data.currency,
+  }))
+}
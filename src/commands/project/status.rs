@@ -0,0 +1,34 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::utils::storage::load_tracker;
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind};
+
+pub fn cli() -> Command {
+  Command::new("status")
+    .about("Break down what one project cost")
+    .long_about("Shows a project's total spending by subcategory, any income tagged with it, and the dates it covers.")
+    .arg(
+      Arg::new("name")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Name of the project (case-insensitive)"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+  let name = args.get_string_or_default("name").trim().to_string();
+
+  let project = tracker_data
+    .project_summaries()
+    .into_iter()
+    .find(|project| project.name.eq_ignore_ascii_case(&name))
+    .ok_or(CliError::ValidationError(ValidationErrorKind::ProjectNotFound { name }))?;
+
+  Ok(CliResponse::new(ResponseContent::Project {
+    project,
+    currency: tracker_data.currency,
+  }))
+}
@@ -0,0 +1,92 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::commands::add::{entered_by, insert_record};
+use crate::utils::limits;
+use crate::utils::parsers::parse_quick;
+use crate::utils::storage::load_tracker;
+use crate::utils::validation::round_amount;
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, Record, ResponseContent, ValidationErrorKind,
+};
+
+pub fn cli() -> Command {
+  Command::new("quick")
+    .about("Add a record from one line of text, e.g. \"coffee 4.50 yesterday #work\"")
+    .long_about("Adds a record from a line of plain text, so a purchase can be logged without any flags. The last number is the amount, and records are expenses unless it starts with '+'. 'today', 'yesterday', a weekday name (the latest one, counting today) or a date sets the date, which is otherwise today. Every '#name' word tags the record. Every other word becomes the description.")
+    .arg(
+      Arg::new("text")
+        .required(true)
+        .num_args(1..)
+        .action(ArgAction::Append)
+        .value_parser(clap::value_parser!(String))
+        .help("What was bought or received, with the amount; quotes are optional"),
+    )
+    .arg(
+      Arg::new("subcategory")
+        .short('s')
+        .long("subcategory")
+        .value_parser(clap::value_parser!(String))
+        .default_value("miscellaneous")
+        .help("Subcategory to file it under, which must already exist"),
+    )
+    .arg(
+      Arg::new("by")
+        .short('u')
+        .long("by")
+        .value_parser(clap::value_parser!(String))
+        .help("Who entered this transaction"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let text = args
+    .get_many::<String>("text")
+    .unwrap_or_default()
+    .map(String::as_str)
+    .collect::<Vec<_>>()
+    .join(" ");
+  let entry = parse_quick(&text, gctx.today()).map_err(CliError::ValidationError)?;
+  gctx.verbose(format!("Read '{}' as {:?}", text, entry));
+
+  let amount = round_amount(gctx, &tracker_data.currency, entry.amount)?;
+  if amount <= 0.0 {
+    return Err(CliError::ValidationError(ValidationErrorKind::AmountTooSmall { amount }));
+  }
+
+  let subcategory_name = args.get_subcategory_or_default("subcategory");
+  let subcategory = tracker_data
+    .subcategory_id(&subcategory_name)
+    .ok_or_else(|| tracker_data.subcategory_not_found(subcategory_name))?;
+  let tags = entry.tags.iter().map(|tag| tracker_data.tag_name(tag)).collect();
+
+  let record = Record {
+    id: tracker_data.next_record_id,
+    category: tracker_data.category_id(&entry.category.to_string()),
+    subcategory,
+    description: entry.description,
+    amount,
+    date: entry.date.format("%d-%m-%Y").to_string(),
+    entered_by: entered_by(gctx, args),
+    deductible: false,
+    project: None,
+    foreign: None,
+    tags,
+    parsed_date: Default::default(),
+  };
+
+  let budget_warnings = insert_record(gctx, &mut tracker_data, record.clone())?;
+  let mut warnings = limits::warnings(gctx, &tracker_data, &record);
+  warnings.extend(budget_warnings);
+
+  Ok(
+    CliResponse::new(ResponseContent::Record {
+      record,
+      tracker_data,
+      is_update: false,
+    })
+    .with_warnings(warnings),
+  )
+}
@@ -0,0 +1,128 @@
+use std::io::{self, Write};
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::commands::add::{entered_by, insert_record};
+use crate::output::format_amount;
+use crate::parsers::parse_amount;
+use crate::utils::storage::load_tracker;
+use crate::utils::validation::decimals;
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, Reconciliation, Record, ResponseContent,
+  RoundingMode,
+};
+
+pub fn cli() -> Command {
+  Command::new("reconcile")
+    .about("Check the balance against a bank statement")
+    .long_about("Compares the balance your records add up to on the statement date with the closing balance on your bank statement. Any difference (drift) usually means a missed, doubled or mistyped record; 'fintrack list' around the statement date helps find it. If you'd rather just close the gap, reconcile offers to add an adjustment record for the difference, or adds it straight away with --adjust.")
+    .arg(
+      Arg::new("statement-balance")
+        .long("statement-balance")
+        .value_name("AMOUNT")
+        .required(true)
+        .allow_hyphen_values(true)
+        .value_parser(parse_amount)
+        .help("The closing balance on the statement"),
+    )
+    .arg(
+      Arg::new("date")
+        .short('D')
+        .long("date")
+        .value_parser(clap::value_parser!(String))
+        .help("The statement's closing date (DD-MM-YYYY); defaults to today"),
+    )
+    .arg(
+      Arg::new("adjust")
+        .long("adjust")
+        .action(ArgAction::SetTrue)
+        .help("Add an adjustment record for any drift without asking"),
+    )
+    .arg(
+      Arg::new("subcategory")
+        .short('s')
+        .long("subcategory")
+        .default_value("miscellaneous")
+        .value_parser(clap::value_parser!(String))
+        .help("Subcategory for the adjustment record"),
+    )
+    .arg(
+      Arg::new("by")
+        .short('u')
+        .long("by")
+        .value_parser(clap::value_parser!(String))
+        .help("Who entered the adjustment"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+  let date = args.get_date_opt("date")?.unwrap_or_else(|| gctx.today());
+  let places = decimals(gctx, &tracker_data.currency);
+  let round = |amount: f64| RoundingMode::HalfUp.round(amount, places).unwrap_or(amount);
+
+  let statement = round(args.get_f64_or_default("statement-balance"));
+  let recorded = round(tracker_data.balance_through(date));
+  let drift = round(statement - recorded);
+  gctx.verbose(format!("Records add up to {} on {}", recorded, date.format("%d-%m-%Y")));
+
+  let mut reconciliation = Reconciliation {
+    date: date.format("%d-%m-%Y").to_string(),
+    currency: tracker_data.currency.clone(),
+    recorded,
+    statement,
+    drift,
+    adjustment: None,
+  };
+  if drift == 0.0 {
+    return Ok(CliResponse::new(ResponseContent::Reconciliation(reconciliation)));
+  }
+
+  let adjust = args.get_flag("adjust")
+    || (gctx.is_interactive() && ask_adjustment(drift, &tracker_data.currency)?);
+  if !adjust {
+    return Ok(CliResponse::new(ResponseContent::Reconciliation(reconciliation)));
+  }
+
+  let subcategory_name = args
+    .get_one::<String>("subcategory")
+    .cloned()
+    .unwrap_or_else(|| "miscellaneous".to_string());
+  let subcategory = tracker_data
+    .subcategory_id(&subcategory_name)
+    .ok_or_else(|| tracker_data.subcategory_not_found(subcategory_name))?;
+  let category = if drift > 0.0 { "income" } else { "expenses" };
+  let record = Record {
+    id: tracker_data.next_record_id,
+    category: tracker_data.category_id(category),
+    subcategory,
+    description: format!("Reconciliation adjustment (statement balance {})", format_amount(statement)),
+    amount: drift.abs(),
+    date: reconciliation.date.clone(),
+    entered_by: entered_by(gctx, args),
+    deductible: false,
+    project: None,
+    foreign: None,
+    tags: Vec::new(),
+    parsed_date: Default::default(),
+  };
+  reconciliation.adjustment = Some(record.id);
+  let warnings = insert_record(gctx, &mut tracker_data, record)?;
+
+  Ok(CliResponse::new(ResponseContent::Reconciliation(reconciliation)).with_warnings(warnings))
+}
+
+fn ask_adjustment(drift: f64, currency: &str) -> Result<bool, CliError> {
+  print!(
+    "The records are {} {} {} the statement. Add an adjustment record? (Y/n): ",
+    format_amount(drift.abs()),
+    currency,
+    if drift > 0.0 { "short of" } else { "over" }
+  );
+  io::stdout().flush()?;
+
+  let mut input = String::new();
+  io::stdin().read_line(&mut input)?;
+  Ok(!matches!(input.trim().to_lowercase().as_str(), "n" | "no"))
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Months, NaiveDate};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::{
+  CliError, CliResponse, CliResult, Currency, DeductionReport, GlobalContext, MonthlyReport,
+  ReportPeriod, ResponseContent, TrackerData,
+  output::{report_html, report_title},
+  parsers::parse_month, t,
+  utils::{
+    email,
+    period::{Period, fiscal_year_label, fiscal_year_of},
+    storage::load_tracker,
+  },
+};
+
+pub fn cli() -> Command {
+  Command::new("report")
+    .about("Summarize a month's income and expenses")
+    .long_about("Shows one month of activity: the balance going in, income and expenses by subcategory, and the balance coming out. Defaults to the current month; --year and --week report on a whole (fiscal) year or week instead. With --email the report is sent as HTML using the SMTP settings in the [email] section of your config, which makes it easy to mail yourself a summary from cron at the start of each month.")
+    .arg(
+      Arg::new("month")
+        .help("Month to report on (MM-YYYY)")
+        .long_help("Month to report on, in the format MM-YYYY, e.g. 03-2025. Defaults to the current month.")
+        .short('m')
+        .long("month")
+        .value_parser(parse_month)
+        .conflicts_with("last-month"),
+    )
+    .arg(
+      Arg::new("last-month")
+        .long("last-month")
+        .action(ArgAction::SetTrue)
+        .help("Report on the previous month")
+        .conflicts_with("month"),
+    )
+    .arg(
+      Arg::new("year")
+        .long("year")
+        .value_parser(clap::value_parser!(i32))
+        .num_args(0..=1)
+        .conflicts_with_all(["month", "last-month"])
+        .help("Report on a whole year, the current one if no year is given")
+        .long_help("Report on a year instead of a month. When 'period.fiscal_year_start' is set the year is a fiscal year, named after the calendar year it starts in: with April, --year 2024 covers 01-04-2024 to 31-03-2025. Also picks the year for --deductions."),
+    )
+    .arg(
+      Arg::new("week")
+        .long("week")
+        .action(ArgAction::SetTrue)
+        .conflicts_with_all(["month", "last-month", "year"])
+        .help("Report on the current week, starting on 'period.week_start'"),
+    )
+    .arg(
+      Arg::new("email")
+        .long("email")
+        .action(ArgAction::SetTrue)
+        .help("Email the report instead of printing it")
+        .long_help("Send the report as an HTML email to the addresses in 'email.to'. Set 'email.smtp_host', 'email.from' and 'email.to' first with 'fintrack config set'; see 'fintrack config set --help' for the other email settings."),
+    )
+    .arg(
+      Arg::new("deductions")
+        .long("deductions")
+        .action(ArgAction::SetTrue)
+        .conflicts_with_all(["month", "last-month", "week", "email"])
+        .help("Summarize tax-deductible expenses for a year instead")
+        .long_help("Totals the expenses marked with --deductible over a year, by subcategory, for tax filing. Defaults to the current year; pick another with --year. Follows 'period.fiscal_year_start' like --year does."),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+  let periods = &gctx.config().period;
+  let today = gctx.today();
+
+  let year = (args.contains_id("year") || args.get_flag("deductions")).then(|| {
+    args
+      .get_one::<i32>("year")
+      .copied()
+      .unwrap_or_else(|| fiscal_year_of(periods, today))
+  });
+
+  if args.get_flag("deductions") {
+    let year = year.expect("set for --deductions");
+    return Ok(CliResponse::new(ResponseContent::Deductions(deduction_report(
+      &tracker_data,
+      Period::fiscal_year(periods, year),
+      fiscal_year_label(periods, year),
+    ))));
+  }
+
+  let (period, kind, label) = if let Some(year) = year {
+    let period = Period::fiscal_year(periods, year);
+    (period, ReportPeriod::Year, fiscal_year_label(periods, year))
+  } else if args.get_flag("week") {
+    let period = Period::week(periods, today);
+    (period, ReportPeriod::Week, period.label())
+  } else {
+    let this_month = today.with_day(1).expect("day 1 exists");
+    let month = match args.get_one::<NaiveDate>("month") {
+      Some(month) => *month,
+      None if args.get_flag("last-month") => this_month - Months::new(1),
+      None => this_month,
+    };
+    (Period::month(month), ReportPeriod::Month, month.format("%m-%Y").to_string())
+  };
+
+  let report = period_report(&tracker_data, period, kind, label)?;
+
+  if !args.get_flag("email") {
+    return Ok(CliResponse::new(ResponseContent::Report(report)));
+  }
+
+  gctx.ensure_online("Emailing the report")?;
+  gctx.verbose(format!("Emailing the report for {}", report.month));
+  let subject = report_title(&report);
+  let recipients = email::send_html(&gctx.config().email, &subject, report_html(&report))?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "report-sent",
+    month = report.month.as_str(),
+    recipients = recipients.join(", ")
+  ))))
+}
+
+fn period_report(
+  tracker_data: &TrackerData,
+  period: Period,
+  kind: ReportPeriod,
+  label: String,
+) -> Result<MonthlyReport, CliError> {
+  let currency = tracker_data
+    .currency
+    .parse::<Currency>()
+    .map_err(|e| CliError::Other(t!("error-tracker-currency", reason = e.to_string())))?;
+  let income_id = tracker_data.category_id("income");
+  let signed = |amount: f64, category: usize| if category == income_id { amount } else { -amount };
+
+  // Start from the balance saved when an earlier month was closed, so the
+  // report agrees with it even if the opening balance has changed since
+  let snapshot = tracker_data.snapshot_before(period.start);
+  let mut opening_balance = snapshot.map_or(tracker_data.opening_balance, |(_, balance)| balance);
+  let mut records = 0;
+  let mut income = 0.0;
+  let mut expenses = 0.0;
+  let mut by_subcategory: HashMap<(usize, usize), f64> = HashMap::new();
+
+  for record in &tracker_data.records {
+    let Some(date) = record.naive_date() else {
+      continue;
+    };
+    if snapshot.is_some_and(|(end, _)| date <= end) {
+      continue;
+    }
+    if date < period.start {
+      opening_balance += signed(record.amount, record.category);
+    } else if period.contains(date) {
+      records += 1;
+      if record.category == income_id {
+        income += record.amount;
+      } else {
+        expenses += record.amount;
+      }
+      *by_subcategory
+        .entry((record.category, record.subcategory))
+        .or_default() += record.amount;
+    }
+  }
+
+  let totals_for = |category: usize| {
+    let mut totals: Vec<(String, f64)> = by_subcategory
+      .iter()
+      .filter(|((c, _), _)| *c == category)
+      .map(|((_, subcategory), total)| {
+        let name = tracker_data
+          .subcategory_name(*subcategory)
+          .cloned()
+          .unwrap_or_else(|| "Unknown".to_string());
+        (name, *total)
+      })
+      .collect();
+    totals.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    totals
+  };
+
+  let closed = match kind {
+    ReportPeriod::Month => tracker_data.closed_period(period.start),
+    _ => None,
+  };
+
+  Ok(MonthlyReport {
+    month: label,
+    period: kind,
+    snapshot: closed.map(|closed| closed.closing_balance),
+    currency,
+    records,
+    opening_balance,
+    income,
+    expenses,
+    closing_balance: opening_balance + income - expenses,
+    income_by_subcategory: totals_for(income_id),
+    expenses_by_subcategory: totals_for(tracker_data.category_id("expenses")),
+  })
+}
+
+fn deduction_report(tracker_data: &TrackerData, period: Period, year: String) -> DeductionReport {
+  let expenses_id = tracker_data.category_id("expenses");
+  let mut records = 0;
+  let mut by_subcategory: HashMap<usize, f64> = HashMap::new();
+
+  for record in tracker_data.records_between(Some(period.start), Some(period.end)) {
+    if record.deductible && record.category == expenses_id {
+      records += 1;
+      *by_subcategory.entry(record.subcategory).or_default() += record.amount;
+    }
+  }
+
+  let mut by_subcategory: Vec<(String, f64)> = by_subcategory
+    .into_iter()
+    .map(|(subcategory, total)| {
+      let name = tracker_data
+        .subcategory_name(subcategory)
+        .cloned()
+        .unwrap_or_else(|| "Unknown".to_string());
+      (name, total)
+    })
+    .collect();
+  by_subcategory.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+  DeductionReport {
+    year,
+    currency: tracker_data.currency.clone(),
+    records,
+    total: by_subcategory.iter().map(|(_, total)| total).sum(),
+    by_subcategory,
+  }
+}
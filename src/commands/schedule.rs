@@ -0,0 +1,41 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("schedule")
+    .about("Add records automatically when they come due")
+    .long_about("Schedules income or expenses that repeat, like a salary or a subscription. 'schedule run' turns every occurrence that has come due into a record, exactly once, catching up on any it missed. Run it from cron, or set 'schedule.run_on_start' to run it before every command.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![add::cli(), delete::cli(), list::cli(), run::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "add" => Some(add::exec),
+    "delete" => Some(delete::exec),
+    "list" => Some(list::exec),
+    "run" => Some(run::exec),
+    _ => None,
+  }
+}
+
+pub mod add;
+pub mod delete;
+pub mod list;
+pub mod run;
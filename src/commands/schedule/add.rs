@@ -0,0 +1,103 @@
+use chrono::Datelike;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::parsers::{parse_amount, parse_category, parse_due};
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::utils::validation::round_amount;
+use crate::{
+  CliError, CliResponse, CliResult, Frequency, GlobalContext, ResponseContent, Schedule,
+  ValidationErrorKind, t,
+};
+
+pub fn cli() -> Command {
+  Command::new("add")
+    .about("Schedule a repeating income or expense")
+    .long_about("Adds a schedule. Nothing is recorded until 'fintrack schedule run' finds it due; from then on a record is added for every occurrence up to today.")
+    .arg(
+      Arg::new("category")
+        .index(1)
+        .required(true)
+        .value_parser(parse_category)
+        .help("Transaction category: 'income' or 'expenses' (case-insensitive)"),
+    )
+    .arg(
+      Arg::new("amount")
+        .index(2)
+        .required(true)
+        .value_parser(parse_amount)
+        .help("Amount of each record (must be greater than 0)"),
+    )
+    .arg(
+      Arg::new("every")
+        .long("every")
+        .value_parser(clap::value_parser!(Frequency))
+        .default_value("month")
+        .help("How often a record is added"),
+    )
+    .arg(
+      Arg::new("start")
+        .long("start")
+        .value_parser(clap::value_parser!(String))
+        .help("First date a record is due, or a day of the month (1-31); defaults to today")
+        .long_help("When the first record is due: a date in DD-MM-YYYY or any format listed in the 'input.date_formats' setting, or a day of the month such as 25, meaning the next 25th. A date in the past is caught up on by the next 'schedule run'. Defaults to today."),
+    )
+    .arg(
+      Arg::new("subcategory")
+        .short('s')
+        .long("subcategory")
+        .value_parser(clap::value_parser!(String))
+        .default_value("miscellaneous")
+        .help("Subcategory of the records"),
+    )
+    .arg(
+      Arg::new("description")
+        .short('d')
+        .long("description")
+        .value_parser(clap::value_parser!(String))
+        .help("Description of the records"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let category = args.get_category("category")?;
+  let amount = round_amount(gctx, &tracker_data.currency, args.get_f64_or_default("amount"))?;
+  if amount <= 0.0 {
+    return Err(CliError::ValidationError(ValidationErrorKind::AmountTooSmall { amount }));
+  }
+
+  let subcategory_name = args.get_subcategory_or_default("subcategory");
+  let subcategory = tracker_data
+    .subcategory_id(&subcategory_name)
+    .and_then(|id| tracker_data.subcategories_by_id.get(&id).cloned())
+    .ok_or_else(|| tracker_data.subcategory_not_found(subcategory_name))?;
+
+  let today = gctx.today();
+  let (next, day) = match args.get_string_opt("start") {
+    Some(start) => parse_due(&start, today).map_err(CliError::ValidationError)?,
+    None => (today, today.day()),
+  };
+
+  let schedule = Schedule {
+    id: tracker_data.schedules.iter().map(|s| s.id).max().unwrap_or(0) + 1,
+    category: category.to_string(),
+    subcategory,
+    amount,
+    description: args.get_string_or_default("description"),
+    every: args.get_one::<Frequency>("every").copied().unwrap_or_default(),
+    next: next.format("%d-%m-%Y").to_string(),
+    day,
+  };
+  let id = schedule.id;
+  tracker_data.schedules.push(schedule);
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "schedule-added",
+    id = id,
+    due = next.format("%d-%m-%Y").to_string()
+  ))))
+}
@@ -0,0 +1,34 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind, t};
+
+pub fn cli() -> Command {
+  Command::new("delete")
+    .about("Stop a schedule")
+    .long_about("Removes a schedule. Records it already added are kept.")
+    .arg(
+      Arg::new("id")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(usize))
+        .help("ID of the schedule, from 'fintrack schedule list'"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+  let id = args.get_usize_or_default("id");
+
+  let index = tracker_data
+    .schedules
+    .iter()
+    .position(|schedule| schedule.id == id)
+    .ok_or(CliError::ValidationError(ValidationErrorKind::ScheduleNotFound { id }))?;
+  tracker_data.schedules.remove(index);
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!("schedule-deleted", id = id))))
+}
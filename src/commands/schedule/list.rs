@@ -0,0 +1,18 @@
+use clap::{ArgMatches, Command};
+
+use crate::utils::storage::load_tracker;
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("list")
+    .about("Show scheduled records and when each is next due")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+
+  Ok(CliResponse::new(ResponseContent::Schedules {
+    schedules: tracker_data.schedules,
+    currency: tracker_data.currency,
+  }))
+}
@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+use clap::{ArgMatches, Command};
+
+use crate::commands::add::insert_record;
+use crate::utils::storage::load_tracker;
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, Record, ResponseContent, ValidationErrorKind, t,
+};
+
+pub fn cli() -> Command {
+  Command::new("run")
+    .about("Add the records that have come due")
+    .long_about("Adds a record for every scheduled occurrence up to today, dated when it was due, including any missed while fintrack wasn't run. Each occurrence is added once: running it again the same day adds nothing. Safe to run from cron.")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  let (added, warnings) = apply_due(gctx, gctx.today())?;
+
+  Ok(
+    CliResponse::new(ResponseContent::Message(t!("schedule-ran", count = added)))
+      .with_warnings(warnings),
+  )
+}
+
+/// Add a record for every scheduled occurrence on or before `today`, giving
+/// how many were added and the budget warnings they set off
+pub fn apply_due(
+  gctx: &mut GlobalContext,
+  today: NaiveDate,
+) -> Result<(usize, Vec<String>), CliError> {
+  let mut tracker_data = load_tracker(gctx)?;
+  let mut added = 0;
+  let mut warnings = Vec::new();
+
+  for index in 0..tracker_data.schedules.len() {
+    // Oldest first, so missed occurrences are caught up on in order
+    while let Some(date) = tracker_data.schedules[index].next_date().filter(|date| *date <= today) {
+      let schedule = &tracker_data.schedules[index];
+      let subcategory = tracker_data.subcategory_id(&schedule.subcategory).ok_or_else(|| {
+        CliError::ValidationError(ValidationErrorKind::SubcategoryNotFound {
+          name: schedule.subcategory.clone(),
+          nearest: None,
+        })
+      })?;
+      let record = Record {
+        id: tracker_data.next_record_id,
+        category: tracker_data.category_id(&schedule.category),
+        amount: schedule.amount,
+        subcategory,
+        description: schedule.description.clone(),
+        date: date.format("%d-%m-%Y").to_string(),
+        entered_by: None,
+        deductible: false,
+        project: None,
+        foreign: None,
+        tags: Vec::new(),
+        parsed_date: Default::default(),
+      };
+
+      // Saved together with the record, so no occurrence is added twice
+      tracker_data.schedules[index].advance();
+      warnings.extend(insert_record(gctx, &mut tracker_data, record)?);
+      added += 1;
+    }
+  }
+
+  tracing::info!(added, "scheduled records applied");
+  Ok((added, warnings))
+}
@@ -0,0 +1,36 @@
+use std::fs;
+use std::path::PathBuf;
+
+use clap::{Arg, ArgMatches, Command};
+
+use crate::utils::storage::tracker_schema;
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent, t};
+
+pub fn cli() -> Command {
+  Command::new("schema")
+    .about("Print the JSON Schema of the tracker file")
+    .long_about("Prints the JSON Schema (draft 2020-12) that tracker.json follows, so tools that write fintrack data can check their output before handing it over. Files read by 'fintrack merge', 'fintrack diff' and 'fintrack edit-file' are checked against the same schema, and the first value that doesn't fit is reported with its path. No tracker is needed.")
+    .arg(
+      Arg::new("out")
+        .short('o')
+        .long("out")
+        .value_name("FILE")
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("Write the schema to this file instead of the terminal"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let schema = tracker_schema();
+
+  if let Some(out) = args.get_one::<PathBuf>("out") {
+    gctx.verbose(format!("Writing {}", out.display()));
+    fs::write(out, serde_json::to_vec_pretty(&schema)?)?;
+    return Ok(CliResponse::new(ResponseContent::Message(t!(
+      "schema-written",
+      path = out.display().to_string()
+    ))));
+  }
+
+  Ok(CliResponse::new(ResponseContent::Schema(schema)))
+}
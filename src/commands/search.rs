@@ -0,0 +1,61 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::utils::fuzzy::fuzzy_match;
+use crate::utils::storage::load_tracker;
+use crate::{CliResponse, CliResult, GlobalContext, Record, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("search")
+    .about("Find records by description, subcategory, project or tag")
+    .long_about("Fuzzily matches the text against every record's description, subcategory name, project and tags, ignoring case. Letters only have to appear in order, so 'grcrs' finds 'Groceries'. The best matches are shown first, with the matching letters highlighted.")
+    .arg(
+      Arg::new("text")
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("What to look for"),
+    )
+    .arg(
+      Arg::new("limit")
+        .short('n')
+        .long("limit")
+        .value_parser(clap::value_parser!(usize))
+        .default_value("20")
+        .help("Show at most N matches (0 shows all)"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+  let query = args.get_one::<String>("text").cloned().unwrap_or_default();
+  let limit = args.get_one::<usize>("limit").copied().unwrap_or_default();
+
+  let mut hits: Vec<(i64, &Record)> = tracker_data
+    .records
+    .iter()
+    .filter_map(|r| {
+      let subcategory = tracker_data.subcategory_name(r.subcategory);
+      [Some(&r.description), subcategory, r.project.as_ref()]
+        .into_iter()
+        .flatten()
+        .chain(&r.tags)
+        .filter_map(|field| fuzzy_match(&query, field))
+        .map(|m| m.score)
+        .max()
+        .map(|score| (score, r))
+    })
+    .collect();
+
+  // Best first; among equal matches the newest record first
+  hits.sort_by_key(|(score, r)| (std::cmp::Reverse(*score), std::cmp::Reverse(r.naive_date())));
+  if limit > 0 {
+    hits.truncate(limit);
+  }
+  gctx.verbose(format!("{} record(s) match '{}'", hits.len(), query));
+
+  let records = hits.into_iter().map(|(_, r)| r.clone()).collect();
+  Ok(CliResponse::new(ResponseContent::Search {
+    query,
+    records,
+    tracker_data,
+  }))
+}
@@ -0,0 +1,244 @@
+use std::{collections::BTreeMap, fmt::Write, fs, net::SocketAddr, path::PathBuf};
+
+use chrono::{Datelike, NaiveDate};
+use clap::{Arg, ArgMatches, Command};
+use tiny_http::{Header, Method, Response, Server, SslConfig};
+
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, TrackerData, t,
+  utils::storage::load_tracker,
+  utils::tokens,
+};
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:9184";
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+pub fn cli() -> Command {
+  Command::new("serve")
+    .about("Serve tracker metrics over HTTP")
+    .long_about("Starts a small HTTP server with Prometheus metrics at /metrics: balance, totals, record count, this month's spending per subcategory and how much of each budget is used. Point Prometheus at it to graph your finances in Grafana. The tracker is read on every request, so changes show up on the next scrape. Once a token has been made with 'serve token create', every request needs one. Press Ctrl-C to stop.")
+    .args_conflicts_with_subcommands(true)
+    .subcommand(token::cli())
+    .arg(
+      Arg::new("bind")
+        .help("Address to listen on")
+        .long_help("Address and port to listen on. The default only accepts connections from this machine; use 0.0.0.0:9184 to let other machines on your network scrape it, ideally with a token and TLS.")
+        .long("bind")
+        .default_value(DEFAULT_ADDRESS),
+    )
+    .arg(
+      Arg::new("tls-cert")
+        .long("tls-cert")
+        .value_name("FILE")
+        .requires("tls-key")
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("Serve HTTPS with this PEM certificate (chain)"),
+    )
+    .arg(
+      Arg::new("tls-key")
+        .long("tls-key")
+        .value_name("FILE")
+        .requires("tls-cert")
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("Private key for --tls-cert, in PEM format"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  if let Some(("token", sub_args)) = args.subcommand() {
+    return token::exec(gctx, sub_args);
+  }
+
+  // Fail now rather than on the first scrape
+  load_tracker(gctx)?;
+  let protected = !tokens::load(gctx)?.is_empty();
+
+  let address = args
+    .get_one::<String>("bind")
+    .map(String::as_str)
+    .unwrap_or(DEFAULT_ADDRESS);
+  let tls = match (args.get_one::<PathBuf>("tls-cert"), args.get_one::<PathBuf>("tls-key")) {
+    (Some(cert), Some(key)) => Some(SslConfig {
+      certificate: read_pem(cert)?,
+      private_key: read_pem(key)?,
+    }),
+    _ => None,
+  };
+  let scheme = if tls.is_some() { "https" } else { "http" };
+  let server = match tls {
+    Some(tls) => Server::https(address, tls),
+    None => Server::http(address),
+  }
+  .map_err(|err| CliError::Other(t!("error-serve-listen", address = address, reason = err.to_string())))?;
+
+  let local = address.parse::<SocketAddr>().is_ok_and(|a| a.ip().is_loopback());
+  if !protected && !local {
+    tracing::warn!(address, "serving without a token");
+    eprintln!("{}", t!("serve-unprotected", address = address));
+  }
+  if !gctx.is_quiet() {
+    println!("{}", t!("serve-listening", url = format!("{}://{}/metrics", scheme, address)));
+  }
+
+  for request in server.incoming_requests() {
+    let _span = tracing::info_span!("request", method = %request.method(), url = request.url()).entered();
+    gctx.verbose(format!("{} {}", request.method(), request.url()));
+
+    // Read every time, so a revoked token stops working straight away.
+    // Revoking the last one keeps the server closed until it's restarted.
+    let allowed = match tokens::load(gctx) {
+      Ok(tokens) if tokens.is_empty() && !protected => true,
+      Ok(tokens) => {
+        let authorization = request
+          .headers()
+          .iter()
+          .find(|h| h.field.equiv("Authorization"))
+          .map(|h| h.value.as_str());
+        tokens::authorized(&tokens, authorization)
+      }
+      Err(err) => {
+        tracing::warn!(code = err.code(), "could not load tokens");
+        false
+      }
+    };
+
+    let response = match (request.method(), request.url()) {
+      _ if !allowed => {
+        tracing::info!("request without a valid token");
+        Response::from_string("Unauthorized\n")
+          .with_status_code(401)
+          .with_header(header("WWW-Authenticate", "Bearer"))
+      }
+      (Method::Get, "/metrics") => match load_tracker(gctx) {
+        Ok(tracker_data) => Response::from_string(metrics(&tracker_data, gctx.today()))
+          .with_header(header("Content-Type", METRICS_CONTENT_TYPE)),
+        Err(err) => {
+          tracing::warn!(code = err.code(), "could not load tracker");
+          let body = format!("Could not load tracker: {} ({})\n", err.kind(), err.code());
+          Response::from_string(body).with_status_code(500)
+        }
+      },
+      _ => Response::from_string("Not found\n").with_status_code(404),
+    };
+
+    if let Err(err) = request.respond(response) {
+      gctx.verbose(format!("Failed to respond: {}", err));
+    }
+  }
+
+  Ok(CliResponse::success())
+}
+
+/// The tracker's metrics in the Prometheus text format. Monthly figures are
+/// for the month containing `today`.
+pub fn metrics(tracker_data: &TrackerData, today: NaiveDate) -> String {
+  let currency = escape(&tracker_data.currency);
+  let (income, expenses) = tracker_data.totals();
+  let month_start = today.with_day(1).expect("day 1 exists");
+  let income_id = tracker_data.category_id("income");
+
+  let mut monthly: BTreeMap<&str, (f64, f64)> = tracker_data
+    .subcategories_by_id
+    .values()
+    .map(|name| (name.as_str(), (0.0, 0.0)))
+    .collect();
+  for record in &tracker_data.records {
+    let in_month = record.naive_date().is_some_and(|d| d >= month_start && d <= today);
+    let name = tracker_data.subcategory_name(record.subcategory);
+    if let (true, Some(totals)) = (in_month, name.and_then(|n| monthly.get_mut(n.as_str()))) {
+      if record.category == income_id {
+        totals.0 += record.amount;
+      } else {
+        totals.1 += record.amount;
+      }
+    }
+  }
+
+  let mut out = String::new();
+  let mut gauge = |name: &str, help: &str, samples: Vec<(String, f64)>| {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    for (labels, value) in samples {
+      let _ = match labels.is_empty() {
+        true => writeln!(out, "{} {}", name, value),
+        false => writeln!(out, "{}{{{}}} {}", name, labels, value),
+      };
+    }
+  };
+  let with_currency = |value: f64| vec![(format!("currency=\"{}\"", currency), value)];
+  let per_subcategory = |pick: fn(&(f64, f64)) -> f64| {
+    monthly
+      .iter()
+      .map(|(name, totals)| {
+        (
+          format!("currency=\"{}\",subcategory=\"{}\"", currency, escape(name)),
+          pick(totals),
+        )
+      })
+      .collect()
+  };
+
+  gauge(
+    "fintrack_balance",
+    "Opening balance plus all income minus all expenses.",
+    with_currency(tracker_data.net_balance()),
+  );
+  gauge(
+    "fintrack_opening_balance",
+    "Balance the tracker started with.",
+    with_currency(tracker_data.opening_balance),
+  );
+  gauge("fintrack_income_total", "Sum of all income records.", with_currency(income));
+  gauge("fintrack_expenses_total", "Sum of all expense records.", with_currency(expenses));
+  gauge(
+    "fintrack_month_income",
+    "Income this calendar month, by subcategory.",
+    per_subcategory(|t| t.0),
+  );
+  gauge(
+    "fintrack_month_spend",
+    "Expenses this calendar month, by subcategory.",
+    per_subcategory(|t| t.1),
+  );
+  gauge(
+    "fintrack_budget_utilization",
+    "Share of this month's budget spent, by subcategory; 1 is the whole budget.",
+    tracker_data
+      .budget_statuses(month_start, today)
+      .into_iter()
+      .filter(|status| status.budget + status.carried > 0.0)
+      .map(|status| {
+        (
+          format!("subcategory=\"{}\"", escape(&status.subcategory)),
+          status.spent / (status.budget + status.carried),
+        )
+      })
+      .collect(),
+  );
+  gauge(
+    "fintrack_records",
+    "Number of records in the tracker.",
+    vec![(String::new(), tracker_data.records.len() as f64)],
+  );
+  out
+}
+
+/// Escape a label value for the Prometheus text format
+fn escape(value: &str) -> String {
+  value
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n")
+}
+
+fn read_pem(path: &PathBuf) -> Result<Vec<u8>, CliError> {
+  fs::read(path).map_err(|err| {
+    CliError::Other(t!("error-read-file", path = path.display().to_string(), reason = err.to_string()))
+  })
+}
+
+fn header(name: &str, value: &str) -> Header {
+  Header::from_bytes(name, value).expect("static header is valid")
+}
+
+pub mod token;
@@ -0,0 +1,39 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("token")
+    .about("Manage the tokens 'fintrack serve' accepts")
+    .long_about("Once there's at least one token, 'fintrack serve' only answers requests that send one as 'Authorization: Bearer <token>'. Create one per client, e.g. Prometheus, so each can be revoked on its own.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![create::cli(), list::cli(), revoke::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "create" => Some(create::exec),
+    "list" => Some(list::exec),
+    "revoke" => Some(revoke::exec),
+    _ => None,
+  }
+}
+
+pub mod create;
+pub mod list;
+pub mod revoke;
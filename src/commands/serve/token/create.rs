@@ -0,0 +1,56 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::utils::storage::ensure_writable;
+use crate::utils::tokens::{self, ApiToken};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind, t};
+
+pub fn cli() -> Command {
+  Command::new("create")
+    .about("Create a token for 'fintrack serve'")
+    .long_about("Creates a token and prints it. Only a hash of it is kept, so copy it now: it can't be shown again.")
+    .arg(
+      Arg::new("name")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("What the token is for, e.g. prometheus"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  ensure_writable(gctx)?;
+  let mut existing = tokens::load(gctx)?;
+
+  let name = args.get_string_or_default("name").trim().to_string();
+  if name.is_empty() {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidName {
+      name,
+      reason: t!("reason-name-empty"),
+    }));
+  }
+  if existing.iter().any(|token| token.name.eq_ignore_ascii_case(&name)) {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidName {
+      name,
+      reason: t!("reason-token-exists"),
+    }));
+  }
+  if gctx.is_dry_run() {
+    return Ok(CliResponse::new(ResponseContent::Message(t!("token-dry-run-create", name = name.as_str()))));
+  }
+
+  let token = tokens::generate()?;
+  existing.push(ApiToken {
+    name: name.clone(),
+    hash: tokens::hash(&token),
+    created_at: chrono::Utc::now().to_rfc3339(),
+  });
+  tokens::save(gctx, &existing)?;
+  tracing::info!(name = %name, "serve token created");
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "token-created",
+    name = name.as_str(),
+    token = token.as_str()
+  ))))
+}
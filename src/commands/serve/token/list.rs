@@ -0,0 +1,16 @@
+use clap::{ArgMatches, Command};
+
+use crate::utils::tokens;
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("list")
+    .about("List the tokens 'fintrack serve' accepts")
+    .long_about("Lists each token's name and when it was created. The tokens themselves aren't kept, so they can't be shown.")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  Ok(CliResponse::new(ResponseContent::Tokens {
+    tokens: tokens::load(gctx)?,
+  }))
+}
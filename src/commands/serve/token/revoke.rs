@@ -0,0 +1,45 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::utils::storage::ensure_writable;
+use crate::utils::tokens;
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind, t};
+
+pub fn cli() -> Command {
+  Command::new("revoke")
+    .about("Stop 'fintrack serve' accepting a token")
+    .long_about("Removes a token. A server that's already running turns it away from the next request on.")
+    .arg(
+      Arg::new("name")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Name of the token (case-insensitive)"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  ensure_writable(gctx)?;
+  let mut existing = tokens::load(gctx)?;
+
+  let name = args.get_string_or_default("name").trim().to_string();
+  let index = existing
+    .iter()
+    .position(|token| token.name.eq_ignore_ascii_case(&name))
+    .ok_or(CliError::ValidationError(ValidationErrorKind::TokenNotFound { name }))?;
+  if gctx.is_dry_run() {
+    return Ok(CliResponse::new(ResponseContent::Message(t!(
+      "token-dry-run-revoke",
+      name = existing[index].name.as_str()
+    ))));
+  }
+
+  let token = existing.remove(index);
+  tokens::save(gctx, &existing)?;
+  tracing::info!(name = %token.name, "serve token revoked");
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "token-revoked",
+    name = token.name.as_str()
+  ))))
+}
@@ -0,0 +1,142 @@
+use std::io::{self, Write};
+
+use clap::{ArgMatches, Command};
+use colored::Colorize;
+use rustyline::{Editor, error::ReadlineError, history::DefaultHistory};
+
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, commands, output,
+  utils::storage::{end_session, flush_session, start_session},
+};
+
+use completion::ShellHelper;
+
+const HISTORY_FILE: &str = "shell_history";
+
+pub fn cli() -> Command {
+  Command::new("shell")
+    .about("Start an interactive session")
+    .long_about("Starts a prompt that accepts the same commands as fintrack itself (without the leading 'fintrack'), with history and tab completion. The tracker is loaded once and kept in memory; changes are written when you type 'save' or leave the shell with 'exit' or Ctrl-D, and post-add and post-delete hooks and the webhook run then.")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  start_session(gctx)?;
+
+  let result = run(gctx);
+  // Write pending changes even if the prompt itself failed
+  end_session(gctx)?;
+  result?;
+
+  Ok(CliResponse::success())
+}
+
+fn run(gctx: &mut GlobalContext) -> Result<(), CliError> {
+  let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new().map_err(readline_error)?;
+  editor.set_helper(Some(ShellHelper::new(gctx)));
+
+  let history_path = gctx.base_path().join(HISTORY_FILE);
+  // A missing history file just means this is the first session
+  let _ = editor.load_history(&history_path);
+
+  println!(
+    "{}",
+    "fintrack shell · 'help' lists commands · 'save' writes changes · 'exit' saves and quits".dimmed()
+  );
+
+  loop {
+    let prompt = match gctx.session() {
+      Some(session) if session.is_dirty() => "fintrack*> ",
+      _ => "fintrack> ",
+    };
+
+    let line = match editor.readline(prompt) {
+      Ok(line) => line,
+      Err(ReadlineError::Interrupted) => continue,
+      Err(ReadlineError::Eof) => break,
+      Err(err) => return Err(readline_error(err)),
+    };
+
+    let line = line.trim();
+    if line.is_empty() {
+      continue;
+    }
+    editor.add_history_entry(line).map_err(readline_error)?;
+
+    match line {
+      "exit" | "quit" => break,
+      "save" => save(gctx),
+      "help" => print_help(),
+      _ if line.starts_with("help ") => print_command_help(line["help ".len()..].trim()),
+      _ => {
+        let result = commands::exec_line(gctx, line);
+        report(gctx, result);
+      }
+    }
+
+    if let Some(helper) = editor.helper_mut() {
+      helper.refresh(gctx);
+    }
+  }
+
+  if let Err(err) = editor.save_history(&history_path) {
+    gctx.verbose(format!("Could not save shell history: {}", err));
+  }
+  Ok(())
+}
+
+fn save(gctx: &mut GlobalContext) {
+  match flush_session(gctx) {
+    Ok(true) => println!("{} {}", "✓".green().bold(), "Changes saved".bright_green()),
+    Ok(false) => println!("{}", "No unsaved changes".dimmed()),
+    Err(err) => report(gctx, Err(err)),
+  }
+}
+
+/// Print a command's result the same way the one-shot CLI does
+pub(crate) fn report(gctx: &GlobalContext, result: CliResult) {
+  let written = match result {
+    Ok(res) if gctx.is_quiet() && res.is_confirmation() => Ok(()),
+    Ok(res) => output::write_response(&res, gctx.display(), &mut io::stdout().lock()),
+    Err(err) => output::write_error(&err, &mut io::stderr().lock()),
+  };
+
+  if let Err(err) = written.and_then(|_| io::stdout().flush()) {
+    eprintln!("Failed to write output: {}", err);
+  }
+}
+
+fn print_help() {
+  println!("Commands:");
+  for cmd in commands::cli() {
+    if commands::NON_INTERACTIVE.contains(&cmd.get_name()) {
+      continue;
+    }
+    println!(
+      "  {:<16}{}",
+      cmd.get_name(),
+      cmd.get_about().map(|a| a.to_string()).unwrap_or_default()
+    );
+  }
+  println!();
+  println!("Shell:");
+  println!("  {:<16}Show help for a command", "help <command>");
+  println!("  {:<16}Write changes to the tracker file", "save");
+  println!("  {:<16}Save and leave the shell (also Ctrl-D)", "exit");
+}
+
+fn print_command_help(name: &str) {
+  let mut root = commands::line_command();
+  match root.find_subcommand_mut(name) {
+    Some(cmd) => println!("{}", cmd.render_long_help()),
+    None => eprintln!("{} Unknown command: {}", "✗".red().bold(), name.bright_red()),
+  }
+}
+
+fn readline_error(err: ReadlineError) -> CliError {
+  match err {
+    ReadlineError::Io(err) => err.into(),
+    err => CliError::Other(err.to_string()),
+  }
+}
+
+pub mod completion;
@@ -0,0 +1,205 @@
+use clap::Command;
+use rustyline::{
+  Context, Helper,
+  completion::{Completer, Pair},
+  highlight::Highlighter,
+  hint::Hinter,
+  validate::Validator,
+};
+
+use crate::{GlobalContext, commands, utils::storage::load_tracker};
+
+/// Words handled by the shell itself rather than passed to a command
+const BUILTINS: [&str; 4] = ["exit", "help", "quit", "save"];
+
+/// Tab completion for command names, aliases, subcommands, flags and
+/// subcategory names
+pub struct ShellHelper {
+  root: Command,
+  aliases: Vec<String>,
+  subcategories: Vec<String>,
+}
+
+impl ShellHelper {
+  pub fn new(gctx: &GlobalContext) -> Self {
+    let mut helper = Self {
+      root: commands::line_command(),
+      aliases: gctx.config().aliases.keys().cloned().collect(),
+      subcategories: Vec::new(),
+    };
+    helper.refresh(gctx);
+    helper
+  }
+
+  /// Pick up subcategories added or renamed by the last command
+  pub fn refresh(&mut self, gctx: &GlobalContext) {
+    if let Ok(tracker_data) = load_tracker(gctx) {
+      let mut names: Vec<String> = tracker_data.subcategories_by_id.into_values().collect();
+      names.sort();
+      self.subcategories = names;
+    }
+  }
+
+  /// Completions for the word ending at the end of `line`, along with the
+  /// byte offset that word starts at
+  pub fn candidates(&self, line: &str) -> (usize, Vec<String>) {
+    let word_start = line
+      .rfind(char::is_whitespace)
+      .map(|i| i + 1)
+      .unwrap_or(0);
+    let word = &line[word_start..];
+    let previous: Vec<&str> = line[..word_start].split_whitespace().collect();
+
+    let mut cmd = &self.root;
+    for w in &previous {
+      if let Some(sub) = cmd.find_subcommand(w) {
+        cmd = sub;
+      }
+    }
+
+    let options: Vec<String> = if previous.last().is_some_and(|p| takes_subcategory(cmd, p)) {
+      self.subcategories.clone()
+    } else if word.starts_with('-') {
+      cmd
+        .get_arguments()
+        .filter_map(|arg| arg.get_long())
+        .chain(["help"])
+        .map(|long| format!("--{}", long))
+        .collect()
+    } else if previous.is_empty() {
+      self
+        .root
+        .get_subcommands()
+        .map(|sub| sub.get_name())
+        .filter(|name| !commands::NON_INTERACTIVE.contains(name))
+        .chain(BUILTINS)
+        .map(String::from)
+        .chain(self.aliases.iter().cloned())
+        .collect()
+    } else if previous == ["help"] {
+      self
+        .root
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect()
+    } else {
+      cmd
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect()
+    };
+
+    let needle = word.to_lowercase();
+    let mut matches: Vec<String> = options
+      .into_iter()
+      .filter(|option| option.to_lowercase().starts_with(&needle))
+      .collect();
+    matches.sort();
+    matches.dedup();
+
+    (word_start, matches)
+  }
+}
+
+/// Whether `flag` is an option of `cmd` whose value is a subcategory name
+fn takes_subcategory(cmd: &Command, flag: &str) -> bool {
+  cmd.get_arguments().any(|arg| {
+    let matches_flag = flag
+      .strip_prefix("--")
+      .map(|long| arg.get_long() == Some(long))
+      .unwrap_or_else(|| {
+        flag
+          .strip_prefix('-')
+          .is_some_and(|short| arg.get_short().map(String::from).as_deref() == Some(short))
+      });
+    matches_flag && arg.get_id().as_str().contains("subcategory")
+  })
+}
+
+impl Completer for ShellHelper {
+  type Candidate = Pair;
+
+  fn complete(
+    &self,
+    line: &str,
+    pos: usize,
+    _ctx: &Context<'_>,
+  ) -> rustyline::Result<(usize, Vec<Pair>)> {
+    let (start, candidates) = self.candidates(&line[..pos]);
+    let pairs = candidates
+      .into_iter()
+      .map(|candidate| Pair {
+        display: candidate.clone(),
+        replacement: candidate,
+      })
+      .collect();
+
+    Ok((start, pairs))
+  }
+}
+
+impl Hinter for ShellHelper {
+  type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_helper() -> ShellHelper {
+        ShellHelper {
+            root: commands::line_command(),
+            aliases: vec!["spent".to_string()],
+            subcategories: vec!["Groceries".to_string(), "Miscellaneous".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_completes_top_level_commands_and_builtins() {
+        let helper = create_helper();
+
+        let (start, candidates) = helper.candidates("su");
+        assert_eq!(start, 0);
+        assert_eq!(candidates, vec!["subcategory", "subscriptions"]);
+
+        let (_, candidates) = helper.candidates("");
+        assert!(candidates.contains(&"add".to_string()));
+        assert!(candidates.contains(&"save".to_string()));
+        assert!(candidates.contains(&"spent".to_string()));
+        assert!(!candidates.contains(&"tui".to_string()));
+        assert!(!candidates.contains(&"shell".to_string()));
+    }
+
+    #[test]
+    fn test_completes_nested_subcommands() {
+        let helper = create_helper();
+        let (start, candidates) = helper.candidates("subcategory re");
+        assert_eq!(start, 12);
+        assert_eq!(candidates, vec!["rename"]);
+    }
+
+    #[test]
+    fn test_completes_long_flags() {
+        let helper = create_helper();
+        let (_, candidates) = helper.candidates("add expenses 5 --sub");
+        assert_eq!(candidates, vec!["--subcategory"]);
+    }
+
+    #[test]
+    fn test_completes_subcategory_names_after_flag() {
+        let helper = create_helper();
+
+        let (start, candidates) = helper.candidates("add expenses 5 -s gro");
+        assert_eq!(start, 18);
+        assert_eq!(candidates, vec!["Groceries"]);
+
+        let (_, candidates) = helper.candidates("add expenses 5 --subcategory ");
+        assert_eq!(candidates.len(), 2);
+    }
+}
@@ -0,0 +1,42 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::utils::storage::read_tracker_file;
+use crate::utils::summary;
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("status")
+    .about("Show your balance, today's spending and budgets at a glance")
+    .long_about("Shows your net balance, what you've spent today and how this month's budgets stand. The summary is kept up to date in status.json whenever the tracker changes, so it's quick enough to run from a shell prompt or a tmux status bar with --short.")
+    .arg(
+      Arg::new("short")
+        .long("short")
+        .action(ArgAction::SetTrue)
+        .help("Print everything on one line, for shell prompts and status bars"),
+    )
+    .arg(
+      Arg::new("check")
+        .long("check")
+        .action(ArgAction::SetTrue)
+        .conflicts_with("short")
+        .help("Only check the tracker exists and can be read, for scripts")
+        .long_help("Prints nothing and exits with 0 if the tracker is set up and reads cleanly. Otherwise the error goes to stderr and the exit code says what's wrong: 3 when there's no tracker yet, 7 when it's damaged (bad JSON, a bad field or a checksum mismatch). The tracker is only read, never repaired from a backup."),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  if args.get_flag("check") {
+    if !gctx.tracker_path().exists() {
+      return Err(CliError::NotInitialized);
+    }
+    read_tracker_file(gctx.tracker_path())?;
+    return Ok(CliResponse::success());
+  }
+
+  let summary = summary::current(gctx)?;
+
+  Ok(CliResponse::new(ResponseContent::Status {
+    summary,
+    short: args.get_flag("short"),
+  }))
+}
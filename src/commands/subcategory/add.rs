@@ -1,9 +1,9 @@
 use clap::{Arg, ArgMatches, Command};
 
 use crate::{
-  CliError, CliResponse, CliResult, GlobalContext, TrackerData,
-  utils::file::{FilePath, write_json_to_file},
+  CliError, CliResponse, CliResult, GlobalContext, TrackerData, t,
   utils::parsers::parse_label,
+  utils::storage::{load_tracker, save_tracker},
 };
 
 pub fn cli() -> Command {
@@ -21,19 +21,41 @@ pub fn cli() -> Command {
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
-  let mut file = gctx.tracker_path().open_read_write()?;
-  let mut tracker_data: TrackerData = serde_json::from_reader(&file)?;
+  let mut tracker_data = load_tracker(gctx)?;
 
   let name = args
     .get_one::<String>("name")
-    .ok_or_else(|| CliError::Other("Subcategory name not provided".to_string()))?;
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "name")))?;
+
+  let subcategory_id = create(&mut tracker_data, name)?;
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(crate::ResponseContent::Message(t!(
+    "subcategory-added",
+    name = tracker_data.subcategory_name(subcategory_id).cloned().unwrap_or_default(),
+    id = subcategory_id
+  ))))
+}
+
+/// Add a subcategory to `tracker_data`, also for `add --create` and
+/// `update --create`, returning its ID. Names are checked like the `name`
+/// argument is, and stored in Title Case.
+pub fn create(tracker_data: &mut TrackerData, name: &str) -> Result<usize, CliError> {
+  parse_label(name).map_err(|reason| {
+    CliError::ValidationError(crate::ValidationErrorKind::InvalidName {
+      name: name.to_string(),
+      reason,
+    })
+  })?;
 
   // Normalize to lowercase for lookup, but store in Title Case
   let name_lower = name.to_lowercase();
   let name_title = {
     let mut chars = name_lower.chars();
     match chars.next() {
-      None => return Err(CliError::Other("Invalid name".to_string())),
+      None => return Err(CliError::Other(t!("error-empty-name"))),
       Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
     }
   };
@@ -53,17 +75,5 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     ));
   }
 
-  let subcategory_id = tracker_data.next_subcategory_id as usize;
-  tracker_data.subcategories_by_id.insert(subcategory_id, name_title.clone());
-  tracker_data.subcategories_by_name.insert(name_lower, subcategory_id);
-  tracker_data.next_subcategory_id += 1;
-  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
-
-  let tracker_json = serde_json::json!(tracker_data);
-  write_json_to_file(&tracker_json, &mut file)?;
-
-  Ok(CliResponse::new(crate::ResponseContent::Message(format!(
-    "Subcategory '{}' added (ID: {})",
-    name_title, subcategory_id
-  ))))
+  Ok(tracker_data.add_subcategory(&name_title))
 }
@@ -1,9 +1,9 @@
 use clap::{Arg, ArgMatches, Command};
 
 use crate::{
-  CliError, CliResponse, CliResult, GlobalContext, TrackerData,
-  utils::file::{FilePath, write_json_to_file},
+  CliError, CliResponse, CliResult, GlobalContext, t,
   utils::parsers::parse_label,
+  utils::storage::{load_tracker, save_tracker},
 };
 
 pub fn cli() -> Command {
@@ -21,12 +21,11 @@ pub fn cli() -> Command {
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
-  let mut file = gctx.tracker_path().open_read_write()?;
-  let mut tracker_data: TrackerData = serde_json::from_reader(&file)?;
+  let mut tracker_data = load_tracker(gctx)?;
 
   let name = args
     .get_one::<String>("name")
-    .ok_or_else(|| CliError::Other("Subcategory name not provided".to_string()))?;
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "name")))?;
 
   let name_lower = name.to_lowercase();
 
@@ -38,11 +37,7 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
 
   let subcategory_id = tracker_data
     .subcategory_id(&name_lower)
-    .ok_or_else(|| {
-      CliError::ValidationError(crate::ValidationErrorKind::SubcategoryNotFound {
-        name: name.to_string(),
-      })
-    })?;
+    .ok_or_else(|| tracker_data.subcategory_not_found(name))?;
 
   let record_count = tracker_data
     .records
@@ -61,13 +56,13 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
 
   tracker_data.subcategories_by_id.remove(&subcategory_id);
   tracker_data.subcategories_by_name.remove(&name_lower);
+  tracker_data.budgets.retain(|budget| budget.subcategory != subcategory_id);
   tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
 
-  let tracker_json = serde_json::json!(tracker_data);
-  write_json_to_file(&tracker_json, &mut file)?;
+  save_tracker(gctx, &tracker_data)?;
 
-  Ok(CliResponse::new(crate::ResponseContent::Message(format!(
-    "Subcategory '{}' deleted",
-    name
+  Ok(CliResponse::new(crate::ResponseContent::Message(t!(
+    "subcategory-deleted",
+    name = name.as_str()
   ))))
 }
@@ -1,6 +1,8 @@
 use clap::{ArgMatches, Command};
 
-use crate::{CliResponse, CliResult, GlobalContext, ResponseContent, TrackerData, utils::file::FilePath};
+use crate::{
+  CliResponse, CliResult, GlobalContext, ResponseContent, utils::storage::load_tracker,
+};
 
 pub fn cli() -> Command {
   Command::new("list")
@@ -9,8 +11,7 @@ pub fn cli() -> Command {
 }
 
 pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
-  let file = gctx.tracker_path().open_read()?;
-  let tracker_data: TrackerData = serde_json::from_reader(&file)?;
+  let tracker_data = load_tracker(gctx)?;
 
   let mut subcategories: Vec<(usize, String)> = tracker_data
     .subcategories_by_id
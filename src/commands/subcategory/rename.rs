@@ -1,9 +1,9 @@
 use clap::{Arg, ArgMatches, Command};
 
 use crate::{
-  CliError, CliResponse, CliResult, GlobalContext, TrackerData,
-  utils::file::{FilePath, write_json_to_file},
+  CliError, CliResponse, CliResult, GlobalContext, t,
   utils::parsers::parse_label,
+  utils::storage::{load_tracker, save_tracker},
 };
 
 pub fn cli() -> Command {
@@ -29,22 +29,21 @@ pub fn cli() -> Command {
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
-  let mut file = gctx.tracker_path().open_read_write()?;
-  let mut tracker_data: TrackerData = serde_json::from_reader(&file)?;
+  let mut tracker_data = load_tracker(gctx)?;
 
   let old_name = args
     .get_one::<String>("old")
-    .ok_or_else(|| CliError::Other("Old subcategory name not provided".to_string()))?;
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "old")))?;
   let new_name = args
     .get_one::<String>("new")
-    .ok_or_else(|| CliError::Other("New subcategory name not provided".to_string()))?;
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "new")))?;
 
   let old_name_lower = old_name.to_lowercase();
   let new_name_lower = new_name.to_lowercase();
   let new_name_title = {
     let mut chars = new_name_lower.chars();
     match chars.next() {
-      None => return Err(CliError::Other("Invalid new name".to_string())),
+      None => return Err(CliError::Other(t!("error-empty-name"))),
       Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
     }
   };
@@ -52,11 +51,7 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
   // Check if old subcategory exists
   let subcategory_id = tracker_data
     .subcategory_id(&old_name_lower)
-    .ok_or_else(|| {
-      CliError::ValidationError(crate::ValidationErrorKind::SubcategoryNotFound {
-        name: old_name.to_string(),
-      })
-    })?;
+    .ok_or_else(|| tracker_data.subcategory_not_found(old_name))?;
 
   // Check if new name already exists
   if tracker_data.subcategories_by_name.contains_key(&new_name_lower) {
@@ -77,11 +72,11 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     .insert(new_name_lower, subcategory_id);
   tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
 
-  let tracker_json = serde_json::json!(tracker_data);
-  write_json_to_file(&tracker_json, &mut file)?;
+  save_tracker(gctx, &tracker_data)?;
 
-  Ok(CliResponse::new(crate::ResponseContent::Message(format!(
-    "Subcategory renamed: '{}' → '{}'",
-    old_name, new_name_title
+  Ok(CliResponse::new(crate::ResponseContent::Message(t!(
+    "subcategory-renamed",
+    old = old_name.as_str(),
+    new = new_name_title
   ))))
 }
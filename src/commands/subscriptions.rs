@@ -0,0 +1,55 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("subscriptions")
+    .about("Manage subscriptions to services")
+    .long_about("Keeps track of services you pay for on repeat, like streaming or software: add them with 'subscriptions add', and see what they cost a month and when each renews with 'subscriptions list'. Expenses whose description contains a subscription's name are linked to it as they're added.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![add::cli(), delete::cli(), list::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "add" => Some(add::exec),
+    "delete" => Some(delete::exec),
+    "list" => Some(list::exec),
+    _ => None,
+  }
+}
+
+/// Position of the subscription called `name`, ignoring case
+pub(crate) fn find_subscription(
+  tracker_data: &crate::TrackerData,
+  name: &str,
+) -> Result<usize, crate::CliError> {
+  tracker_data
+    .subscriptions
+    .iter()
+    .position(|subscription| subscription.name.eq_ignore_ascii_case(name.trim()))
+    .ok_or_else(|| {
+      crate::CliError::ValidationError(crate::ValidationErrorKind::SubscriptionNotFound {
+        name: name.trim().to_string(),
+      })
+    })
+}
+
+pub mod add;
+pub mod delete;
+pub mod list;
@@ -0,0 +1,112 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::parsers::{parse_amount, parse_due};
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::utils::validation::round_amount;
+use crate::{
+  CliError, CliResponse, CliResult, Frequency, GlobalContext, ResponseContent, Subscription,
+  ValidationErrorKind, t,
+};
+
+pub fn cli() -> Command {
+  Command::new("add")
+    .about("Add a subscription")
+    .long_about("Adds a service you pay for on repeat. Expenses already recorded with its name in their description are linked to it straight away, and later ones as they're added.")
+    .arg(
+      Arg::new("name")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Name of the service, e.g. Netflix"),
+    )
+    .arg(
+      Arg::new("amount")
+        .index(2)
+        .required(true)
+        .value_parser(parse_amount)
+        .help("What it costs each time it renews (must be greater than 0)"),
+    )
+    .arg(
+      Arg::new("renews")
+        .long("renews")
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Day of the month it renews (1-31), or the next renewal date")
+        .long_help("When the subscription next renews: a day of the month such as 10, meaning the next 10th, or a date in DD-MM-YYYY or any format listed in the 'input.date_formats' setting. Days past the end of a month fall on its last day."),
+    )
+    .arg(
+      Arg::new("every")
+        .long("every")
+        .value_parser(clap::value_parser!(Frequency))
+        .default_value("month")
+        .help("How often it renews"),
+    )
+    .arg(
+      Arg::new("cancel-by")
+        .long("cancel-by")
+        .value_parser(clap::value_parser!(String))
+        .help("Last day to cancel it, e.g. before a free trial ends, in DD-MM-YYYY format"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+
+  let name = args.get_string_or_default("name").trim().to_string();
+  if name.is_empty() {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidName {
+      name,
+      reason: t!("reason-name-empty"),
+    }));
+  }
+  if tracker_data
+    .subscriptions
+    .iter()
+    .any(|subscription| subscription.name.eq_ignore_ascii_case(&name))
+  {
+    return Err(CliError::ValidationError(ValidationErrorKind::InvalidName {
+      name,
+      reason: t!("reason-subscription-exists"),
+    }));
+  }
+
+  let amount = round_amount(gctx, &tracker_data.currency, args.get_f64_or_default("amount"))?;
+  if amount <= 0.0 {
+    return Err(CliError::ValidationError(ValidationErrorKind::AmountTooSmall { amount }));
+  }
+
+  let (renews, day) = parse_due(&args.get_string_or_default("renews"), gctx.today())
+    .map_err(CliError::ValidationError)?;
+  let cancel_by = args.get_date_opt("cancel-by")?;
+
+  let mut subscription = Subscription {
+    name: name.clone(),
+    amount,
+    every: args.get_one::<Frequency>("every").copied().unwrap_or_default(),
+    renews: renews.format("%d-%m-%Y").to_string(),
+    day,
+    cancel_by: cancel_by.map(|date| date.format("%d-%m-%Y").to_string()),
+    records: Vec::new(),
+  };
+  let expenses_id = tracker_data.category_id("expenses");
+  subscription.records = tracker_data
+    .records
+    .iter()
+    .filter(|record| subscription.matches(record, expenses_id))
+    .map(|record| record.id)
+    .collect();
+  let linked = subscription.records.len();
+  let next = subscription.next_renewal(gctx.today()).unwrap_or(renews);
+
+  tracker_data.subscriptions.push(subscription);
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "subscription-added",
+    name = name.as_str(),
+    renews = next.format("%d-%m-%Y").to_string(),
+    linked = linked
+  ))))
+}
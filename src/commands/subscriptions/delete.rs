@@ -0,0 +1,33 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::command_prelude::ArgMatchesExt;
+use crate::commands::subscriptions::find_subscription;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent, t};
+
+pub fn cli() -> Command {
+  Command::new("delete")
+    .about("Stop tracking a subscription")
+    .long_about("Removes a subscription, e.g. once it's cancelled. The expenses linked to it are kept.")
+    .arg(
+      Arg::new("name")
+        .index(1)
+        .required(true)
+        .value_parser(clap::value_parser!(String))
+        .help("Name of the subscription (case-insensitive)"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+  let index = find_subscription(&tracker_data, &args.get_string_or_default("name"))?;
+
+  let subscription = tracker_data.subscriptions.remove(index);
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "subscription-deleted",
+    name = subscription.name.as_str()
+  ))))
+}
@@ -0,0 +1,19 @@
+use clap::{ArgMatches, Command};
+
+use crate::utils::storage::load_tracker;
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("list")
+    .about("Show subscriptions, their monthly cost and upcoming renewals")
+    .long_about("Lists every subscription, soonest renewal first, with what it costs, how many payments are linked to it and any cancel-by date, then what they all add up to in an average month.")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+
+  Ok(CliResponse::new(ResponseContent::Subscriptions {
+    subscriptions: tracker_data.subscription_statuses(gctx.today()),
+    currency: tracker_data.currency,
+  }))
+}
@@ -0,0 +1,39 @@
+use clap::{ArgMatches, Command};
+
+use crate::{CliResult, GlobalContext, commands::Exec, invalid_subcommand_error};
+
+pub fn cli() -> Command {
+  Command::new("tag")
+    .about("List, rename and delete tags")
+    .long_about("Keeps the tags added with 'fintrack add --tag' tidy: 'tag list' shows each one with how many records carry it, 'tag rename' fixes a spelling or merges two tags, and 'tag delete' removes one.")
+    .subcommand_required(true)
+    .subcommands(build_cli())
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  match args.subcommand() {
+    Some((cmd, sub_args)) => {
+      let exec_fn = build_exec(cmd).ok_or_else(|| invalid_subcommand_error(cmd))?;
+
+      exec_fn(gctx, sub_args)
+    }
+    None => Err(invalid_subcommand_error("")), // Shouldn't happen due to subcommand_required
+  }
+}
+
+fn build_cli() -> Vec<Command> {
+  vec![delete::cli(), list::cli(), rename::cli()]
+}
+
+fn build_exec(cmd: &str) -> Option<Exec> {
+  match cmd {
+    "delete" => Some(delete::exec),
+    "list" => Some(list::exec),
+    "rename" => Some(rename::exec),
+    _ => None,
+  }
+}
+
+pub mod delete;
+pub mod list;
+pub mod rename;
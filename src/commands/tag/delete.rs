@@ -0,0 +1,54 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::utils::parsers::parse_tag;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind, t};
+
+pub fn cli() -> Command {
+  Command::new("delete")
+    .about("Delete a tag")
+    .long_about("Removes a tag from the tracker. A tag records still carry is only deleted with --from-records, which takes it off those records; the records themselves are kept.")
+    .arg(
+      Arg::new("name")
+        .index(1)
+        .required(true)
+        .value_parser(parse_tag)
+        .help("Name of the tag (case-insensitive)"),
+    )
+    .arg(
+      Arg::new("from-records")
+        .long("from-records")
+        .action(ArgAction::SetTrue)
+        .help("Also take the tag off every record that carries it"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+  let name = args
+    .get_one::<String>("name")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "name")))?;
+
+  let (name, count) = tracker_data
+    .tag_counts()
+    .into_iter()
+    .find(|(tag, _)| tag.eq_ignore_ascii_case(name))
+    .ok_or_else(|| CliError::ValidationError(ValidationErrorKind::TagNotFound { name: name.clone() }))?;
+  if count > 0 && !args.get_flag("from-records") {
+    return Err(CliError::ValidationError(ValidationErrorKind::TagInUse { name, count }));
+  }
+
+  tracker_data.tags.retain(|tag| !tag.eq_ignore_ascii_case(&name));
+  for record in &mut tracker_data.records {
+    record.tags.retain(|tag| !tag.eq_ignore_ascii_case(&name));
+  }
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+
+  save_tracker(gctx, &tracker_data)?;
+
+  Ok(CliResponse::new(ResponseContent::Message(t!(
+    "tag-deleted",
+    name = name.as_str(),
+    count = count
+  ))))
+}
@@ -0,0 +1,16 @@
+use clap::{ArgMatches, Command};
+
+use crate::utils::storage::load_tracker;
+use crate::{CliResponse, CliResult, GlobalContext, ResponseContent};
+
+pub fn cli() -> Command {
+  Command::new("list")
+    .about("Show every tag with how many records carry it")
+    .long_about("Lists each tag with the number of records tagged with it, most used first. Tags no record carries any more are listed with 0 and can be removed with 'fintrack tag delete'.")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+
+  Ok(CliResponse::new(ResponseContent::Tags(tracker_data.tag_counts())))
+}
@@ -0,0 +1,70 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::utils::parsers::parse_tag;
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, ValidationErrorKind, t};
+
+pub fn cli() -> Command {
+  Command::new("rename")
+    .about("Rename a tag on every record that carries it")
+    .long_about("Changes a tag's name on every record. If the new name is already a tag, the two are merged, so 'fintrack tag rename holiday vacation' leaves only 'vacation'. Renaming to a different capitalisation of the same name just changes how it's spelled.")
+    .arg(
+      Arg::new("old")
+        .index(1)
+        .required(true)
+        .value_parser(parse_tag)
+        .help("Current name of the tag (case-insensitive)"),
+    )
+    .arg(
+      Arg::new("new")
+        .index(2)
+        .required(true)
+        .value_parser(parse_tag)
+        .help("New name, or an existing tag to merge into"),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let mut tracker_data = load_tracker(gctx)?;
+  let old = args
+    .get_one::<String>("old")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "old")))?;
+  let new = args
+    .get_one::<String>("new")
+    .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = "new")))?;
+
+  let (old, count) = tracker_data
+    .tag_counts()
+    .into_iter()
+    .find(|(name, _)| name.eq_ignore_ascii_case(old))
+    .ok_or_else(|| CliError::ValidationError(ValidationErrorKind::TagNotFound { name: old.clone() }))?;
+
+  // Merging keeps the spelling of the tag merged into
+  let merge_into = tracker_data
+    .tags
+    .iter()
+    .find(|name| name.eq_ignore_ascii_case(new) && !name.eq_ignore_ascii_case(&old))
+    .cloned();
+  let new = merge_into.clone().unwrap_or_else(|| new.clone());
+
+  tracker_data.tags.retain(|name| !name.eq_ignore_ascii_case(&old));
+  if merge_into.is_none() {
+    tracker_data.tags.push(new.clone());
+  }
+  for record in &mut tracker_data.records {
+    if !record.tags.iter().any(|tag| tag.eq_ignore_ascii_case(&old)) {
+      continue;
+    }
+    record.tags.retain(|tag| !tag.eq_ignore_ascii_case(&old) && *tag != new);
+    record.tags.push(new.clone());
+  }
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+
+  save_tracker(gctx, &tracker_data)?;
+
+  let message = match merge_into {
+    Some(_) => t!("tag-merged", old = old.as_str(), new = new.as_str(), count = count),
+    None => t!("tag-renamed", old = old.as_str(), new = new.as_str(), count = count),
+  };
+  Ok(CliResponse::new(ResponseContent::Message(message)))
+}
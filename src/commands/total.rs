@@ -1,33 +1,201 @@
-use clap::{ArgMatches, Command};
+use std::collections::HashMap;
+
+use chrono::{Datelike, Days, Months};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 
 use crate::{
-  CliError, CliResponse, CliResult, Currency, GlobalContext, Total, TrackerData,
-  utils::file::FilePath,
+  BudgetStatus, CliError, CliResponse, CliResult, Currency, CurrencyTotal, GlobalContext, GroupBy, GroupTotal, Record,
+  RoundingMode, Total, TrackerData,
+  command_prelude::ArgMatchesExt,
+  parsers::STORAGE_DATE_FORMAT, t,
+  utils::{
+    filters::{DateRange, RecordFilter, filter_args},
+    storage::load_tracker,
+    validation::{self, decimals},
+  },
 };
 
 pub fn cli() -> Command {
-  Command::new("total")
+  let cmd = Command::new("total")
     .about("Display financial summary with totals")
-    .long_about("Shows a summary of your finances including opening balance, total income, total expenses, and net balance (opening + income - expenses).")
+    .long_about("Shows a summary of your finances including opening balance, total income, total expenses, and net balance (opening + income - expenses). With --month, --days or --start/--end only that period's income and expenses are counted, and the opening balance is the balance the period started with. The other filters 'fintrack list' takes narrow down which records are counted too.")
+    .arg(
+      Arg::new("with-investments")
+        .long("with-investments")
+        .action(ArgAction::SetTrue)
+        .help("Add the value of your investments to the net balance")
+        .long_help("Adds the current value of holdings from 'fintrack invest' to the net balance, giving your net worth. Uses the latest prices from 'fintrack invest value'."),
+    )
+    .arg(
+      Arg::new("as-of")
+        .long("as-of")
+        .value_name("DATE")
+        .value_parser(clap::value_parser!(String))
+        .conflicts_with_all(["start", "end", "month", "days"])
+        .help("Show the balance as it stood at the end of this date (DD-MM-YYYY)")
+        .long_help("Counts the opening balance and every record up to and including this date, e.g. to check against an old bank statement or see what you had at the end of a year. Same as --end without --start."),
+    )
+    .arg(
+      Arg::new("by")
+        .long("by")
+        .value_parser(clap::value_parser!(GroupBy))
+        .help("Also show income, expenses and net per category, subcategory or tag")
+        .long_help("Adds a table of income, expenses and net per group. With 'tag', a record counts under each of its tags, so the groups can add up to more than the totals, and untagged records aren't in any group."),
+    );
+  filter_args(cmd)
 }
 
-pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
-  let file = gctx.tracker_path().open_read()?;
-  let tracker_data: TrackerData = serde_json::from_reader(&file)?;
-
-  let opening_balance = tracker_data.opening_balance;
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+  let mut filter = RecordFilter::from_args(gctx, &tracker_data, args)?;
+  if let Some(date) = args.get_date_opt("as-of")? {
+    filter.dates = filter.dates.within(DateRange {
+      start: None,
+      end: Some(date),
+    });
+  }
+  let range = filter.dates;
+  gctx.verbose(format!("Counting records matching {:?}", filter));
 
   let currency = tracker_data
     .currency
     .parse::<Currency>()
-    .map_err(|e| CliError::Other(format!("Invalid currency in tracker data: {}", e)))?;
+    .map_err(|e| CliError::Other(t!("error-tracker-currency", reason = e.to_string())))?;
+
+  let records = &filter.select(&tracker_data)[..];
+  let opening_balance = match range.start.and_then(|start| start.pred_opt()) {
+    Some(before) => tracker_data.balance_through(before),
+    None => tracker_data.opening_balance,
+  };
+  let income_id = tracker_data.category_id("income");
+  let (income_total, expenses_total) = sums(records, income_id);
 
-  let (income_total, expenses_total) = tracker_data.totals();
+  // Sums of binary floats drift (0.1 + 0.2); report them as the bank would
+  let decimals = decimals(gctx, &tracker_data.currency);
+  let round = |amount: f64| RoundingMode::HalfUp.round(amount, decimals).unwrap_or(amount);
+
+  let savings = &gctx.config().savings;
+  let by = args.get_one::<GroupBy>("by").copied();
+  let groups = by
+    .map(|by| group_totals(&tracker_data, records, by))
+    .unwrap_or_default()
+    .into_iter()
+    .map(|group| GroupTotal {
+      income: round(group.income),
+      expenses: round(group.expenses),
+      net: round(group.net),
+      ..group
+    })
+    .collect();
+  let by_currency = tracker_data
+    .currency_totals(records)
+    .into_iter()
+    .map(|total| {
+      let places = validation::decimals(gctx, &total.currency);
+      let round_in = |amount: f64| RoundingMode::HalfUp.round(amount, places).unwrap_or(amount);
+      CurrencyTotal {
+        income: round_in(total.income),
+        expenses: round_in(total.expenses),
+        converted_income: round(total.converted_income),
+        converted_expenses: round(total.converted_expenses),
+        ..total
+      }
+    })
+    .collect();
+
+  let month_start = gctx.today().with_day(1).expect("day 1 exists");
+  let budgets = tracker_data
+    .budget_statuses(month_start, month_start + Months::new(1) - Days::new(1))
+    .into_iter()
+    .map(|status| BudgetStatus {
+      carried: round(status.carried),
+      spent: round(status.spent),
+      remaining: round(status.remaining),
+      ..status
+    })
+    .collect();
 
   Ok(CliResponse::new(crate::ResponseContent::Total(Total {
     currency,
-    opening_balance,
-    income_total,
-    expenses_total,
+    start: range.start.map(|date| date.format(STORAGE_DATE_FORMAT).to_string()),
+    end: range.end.map(|date| date.format(STORAGE_DATE_FORMAT).to_string()),
+    opening_balance: round(opening_balance),
+    income_total: round(income_total),
+    expenses_total: round(expenses_total),
+    investments: args
+      .get_flag("with-investments")
+      .then(|| tracker_data.investments.market_value()),
+    round_up_savings: savings.round_up.then(|| {
+      let expenses_id = tracker_data.category_id("expenses");
+      round_up_savings(records, expenses_id, savings.round_up_to, decimals)
+    }),
+    budgets,
+    by,
+    groups,
+    by_currency,
   })))
 }
+
+/// What rounding every expense up to a multiple of `unit` puts aside.
+/// Worked out in the currency's smallest unit, so 0.1 + 0.2 needs no rounding.
+fn round_up_savings(records: &[Record], expenses_id: usize, unit: f64, decimals: u32) -> f64 {
+  let factor = 10f64.powi(decimals as i32);
+  let unit = if unit > 0.0 { unit } else { 1.0 };
+  let unit = ((unit * factor).round() as i64).max(1);
+  let saved: i64 = records
+    .iter()
+    .filter(|r| r.category == expenses_id)
+    .map(|r| (unit - (r.amount * factor).round() as i64 % unit) % unit)
+    .sum();
+  saved as f64 / factor
+}
+
+/// Income and expense totals of `records`
+fn sums<'a>(records: impl IntoIterator<Item = &'a Record>, income_id: usize) -> (f64, f64) {
+  records.into_iter().fold((0.0, 0.0), |(income, expenses), r| {
+    if r.category == income_id {
+      (income + r.amount, expenses)
+    } else {
+      (income, expenses + r.amount)
+    }
+  })
+}
+
+/// Totals per category, subcategory or tag, largest net amount first
+fn group_totals(tracker_data: &TrackerData, records: &[Record], by: GroupBy) -> Vec<GroupTotal> {
+  let income_id = tracker_data.category_id("income");
+  let unknown = || "Unknown".to_string();
+  // Tags are grouped ignoring case, under the first spelling met
+  let mut groups: HashMap<String, (String, Vec<&Record>)> = HashMap::new();
+  for record in records {
+    let names = match by {
+      GroupBy::Category => vec![tracker_data.category_name(record.category).cloned().unwrap_or_else(unknown)],
+      GroupBy::Subcategory => {
+        vec![tracker_data.subcategory_name(record.subcategory).cloned().unwrap_or_else(unknown)]
+      }
+      GroupBy::Tag => record.tags.clone(),
+    };
+    for name in names {
+      groups
+        .entry(name.to_lowercase())
+        .or_insert_with(|| (name, Vec::new()))
+        .1
+        .push(record);
+    }
+  }
+
+  let mut totals: Vec<GroupTotal> = groups
+    .into_values()
+    .map(|(name, records)| {
+      let (income, expenses) = sums(records, income_id);
+      GroupTotal {
+        name,
+        income,
+        expenses,
+        net: income - expenses,
+      }
+    })
+    .collect();
+  totals.sort_by(|a, b| b.net.total_cmp(&a.net).then_with(|| a.name.cmp(&b.name)));
+  totals
+}
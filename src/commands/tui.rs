@@ -0,0 +1,191 @@
+use clap::{ArgMatches, Command};
+use ratatui::{
+  DefaultTerminal,
+  crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+};
+
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, ResponseContent, Verbosity, commands,
+  output::{self, DisplayOptions}, t,
+  utils::storage::load_tracker,
+};
+
+use app::{App, Mode};
+
+pub fn cli() -> Command {
+  Command::new("tui")
+    .about("Open an interactive dashboard")
+    .long_about("Opens a full-screen dashboard with a sortable, filterable records table and a live summary of your totals. Records can be added, edited and deleted with single keystrokes, and any other fintrack command can be typed after ':'. Press 'q' to quit.")
+}
+
+pub fn exec(gctx: &mut GlobalContext, _args: &ArgMatches) -> CliResult {
+  let tracker_data = load_tracker(gctx)?;
+  let mut app = App::new(tracker_data);
+  app.date_format = gctx.display().date_format;
+
+  // Verbose diagnostics and progress bars go to stderr and would garble the
+  // full-screen UI; command results are shown in the status bar regardless
+  let verbosity = gctx.verbosity();
+  gctx.set_verbosity(Verbosity::Quiet);
+  // Command results are shown in the status bar, which can't render ANSI codes
+  colored::control::set_override(false);
+
+  let mut terminal = ratatui::init();
+  let result = run(&mut terminal, gctx, &mut app);
+  ratatui::restore();
+
+  gctx.set_verbosity(verbosity);
+  result?;
+
+  Ok(CliResponse::success())
+}
+
+fn run(terminal: &mut DefaultTerminal, gctx: &mut GlobalContext, app: &mut App) -> Result<(), CliError> {
+  while !app.should_quit {
+    terminal.draw(|frame| ui::draw(frame, app))?;
+
+    if let Event::Key(key) = event::read()?
+      && key.kind == KeyEventKind::Press
+    {
+      handle_key(gctx, app, key)?;
+    }
+  }
+
+  Ok(())
+}
+
+fn handle_key(gctx: &mut GlobalContext, app: &mut App, key: KeyEvent) -> Result<(), CliError> {
+  if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+    app.should_quit = true;
+    return Ok(());
+  }
+
+  match app.mode.clone() {
+    Mode::Normal => match key.code {
+      KeyCode::Char('q') | KeyCode::Esc => app.should_quit = true,
+      KeyCode::Char('j') | KeyCode::Down => app.select_next(),
+      KeyCode::Char('k') | KeyCode::Up => app.select_previous(),
+      KeyCode::Char('s') => app.cycle_sort(),
+      KeyCode::Char('r') => app.toggle_sort_direction(),
+      KeyCode::Char('/') => {
+        app.mode = Mode::Filter;
+        app.input = app.filter.clone();
+      }
+      KeyCode::Char('a') => app.start_command("add "),
+      KeyCode::Char('e') => {
+        if let Some(id) = app.selected_record().map(|r| r.id) {
+          app.start_command(&format!("update {} ", id));
+        }
+      }
+      KeyCode::Char('d') => {
+        if let Some(id) = app.selected_record().map(|r| r.id) {
+          app.mode = Mode::ConfirmDelete(id);
+        }
+      }
+      KeyCode::Char(':') => app.start_command(""),
+      _ => {}
+    },
+    Mode::Filter => match key.code {
+      KeyCode::Enter => app.mode = Mode::Normal,
+      KeyCode::Esc => {
+        app.mode = Mode::Normal;
+        app.input.clear();
+        app.filter.clear();
+        app.refresh();
+      }
+      KeyCode::Backspace => {
+        app.input.pop();
+        app.filter = app.input.clone();
+        app.refresh();
+      }
+      KeyCode::Char(c) => {
+        app.input.push(c);
+        app.filter = app.input.clone();
+        app.refresh();
+      }
+      _ => {}
+    },
+    Mode::Command => match key.code {
+      KeyCode::Esc => {
+        app.mode = Mode::Normal;
+        app.input.clear();
+      }
+      KeyCode::Enter => {
+        app.mode = Mode::Normal;
+        let line = std::mem::take(&mut app.input);
+        run_command(gctx, app, &line)?;
+      }
+      KeyCode::Backspace => {
+        app.input.pop();
+      }
+      KeyCode::Char(c) => app.input.push(c),
+      _ => {}
+    },
+    Mode::ConfirmDelete(id) => {
+      app.mode = Mode::Normal;
+      if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+        run_command(gctx, app, &format!("delete -i {}", id))?;
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Run a command line through the regular command implementations, then reload
+/// the tracker so the table and totals reflect any change
+fn run_command(gctx: &mut GlobalContext, app: &mut App, line: &str) -> Result<(), CliError> {
+  if line.trim().is_empty() {
+    return Ok(());
+  }
+
+  match commands::exec_line(gctx, line) {
+    Ok(response) => app.set_status(response_text(&response)?, false),
+    Err(err) => app.set_status(error_text(&err)?, true),
+  }
+
+  app.reload(load_tracker(gctx)?);
+  Ok(())
+}
+
+/// Condense a command response into a single status-bar line
+fn response_text(response: &CliResponse) -> Result<String, CliError> {
+  let text = match response.content() {
+    None => t!("tui-done"),
+    Some(ResponseContent::Message(msg)) => msg.clone(),
+    Some(ResponseContent::Record {
+      record, is_update, ..
+    }) => {
+      match is_update {
+        true => t!("tui-record-updated", id = record.id),
+        false => t!("tui-record-created", id = record.id),
+      }
+    }
+    Some(ResponseContent::Deleted { count, .. }) => t!("tui-deleted", count = *count),
+    Some(_) => {
+      let mut buffer = Vec::new();
+      output::write_response(response, &DisplayOptions::default(), &mut buffer)?;
+      collapse_lines(&buffer)
+    }
+  };
+
+  Ok(text)
+}
+
+fn error_text(err: &CliError) -> Result<String, CliError> {
+  let mut buffer = Vec::new();
+  output::write_error(err, &mut buffer)?;
+  Ok(collapse_lines(&buffer))
+}
+
+fn collapse_lines(buffer: &[u8]) -> String {
+  String::from_utf8_lossy(buffer)
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty())
+    .collect::<Vec<_>>()
+    .join(" · ")
+}
+
+pub mod app;
+pub mod ui;
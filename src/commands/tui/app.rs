@@ -0,0 +1,325 @@
+use std::cmp::Ordering;
+
+use crate::{DateFormat, Record, TrackerData};
+
+/// Column the records table is sorted by
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortKey {
+  Date,
+  Amount,
+  Id,
+  Subcategory,
+}
+
+impl SortKey {
+  /// The next key in the cycle used by the `s` keybinding
+  pub fn next(self) -> Self {
+    match self {
+      SortKey::Date => SortKey::Amount,
+      SortKey::Amount => SortKey::Id,
+      SortKey::Id => SortKey::Subcategory,
+      SortKey::Subcategory => SortKey::Date,
+    }
+  }
+
+  pub fn label(self) -> &'static str {
+    match self {
+      SortKey::Date => "date",
+      SortKey::Amount => "amount",
+      SortKey::Id => "id",
+      SortKey::Subcategory => "subcategory",
+    }
+  }
+}
+
+/// What keystrokes are currently interpreted as
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mode {
+  Normal,
+  /// Typing a text filter after `/`
+  Filter,
+  /// Typing a fintrack command line after `:`, `a` or `e`
+  Command,
+  /// Waiting for `y` to delete the record with this ID
+  ConfirmDelete(usize),
+}
+
+/// A message shown in the status bar after an action
+#[derive(Clone, Debug)]
+pub struct Status {
+  pub text: String,
+  pub is_error: bool,
+}
+
+/// Totals shown in the summary pane
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Summary {
+  pub opening_balance: f64,
+  pub income: f64,
+  pub expenses: f64,
+  pub shown_count: usize,
+  pub shown_net: f64,
+}
+
+impl Summary {
+  pub fn net(&self) -> f64 {
+    self.opening_balance + self.income - self.expenses
+  }
+}
+
+/// State of the TUI dashboard, independent of the terminal so it can be tested
+pub struct App {
+  pub tracker: TrackerData,
+  /// Indices into `tracker.records` of the rows currently shown, in display order
+  pub rows: Vec<usize>,
+  pub selected: usize,
+  pub sort: SortKey,
+  pub descending: bool,
+  pub filter: String,
+  pub date_format: DateFormat,
+  pub mode: Mode,
+  pub input: String,
+  pub status: Option<Status>,
+  pub should_quit: bool,
+}
+
+impl App {
+  pub fn new(tracker: TrackerData) -> Self {
+    let mut app = Self {
+      tracker,
+      rows: Vec::new(),
+      selected: 0,
+      sort: SortKey::Date,
+      descending: true,
+      filter: String::new(),
+      date_format: DateFormat::default(),
+      mode: Mode::Normal,
+      input: String::new(),
+      status: None,
+      should_quit: false,
+    };
+    app.refresh();
+    app
+  }
+
+  /// Swap in freshly loaded data (after a command changed the tracker)
+  pub fn reload(&mut self, tracker: TrackerData) {
+    self.tracker = tracker;
+    self.refresh();
+  }
+
+  /// Recompute which rows are shown and in what order
+  pub fn refresh(&mut self) {
+    let needle = self.filter.to_lowercase();
+    let mut rows: Vec<usize> = self
+      .tracker
+      .records
+      .iter()
+      .enumerate()
+      .filter(|(_, r)| needle.is_empty() || self.matches_filter(r, &needle))
+      .map(|(i, _)| i)
+      .collect();
+
+    let records = &self.tracker.records;
+    rows.sort_by(|&a, &b| {
+      let ordering = self.compare(&records[a], &records[b]);
+      if self.descending {
+        ordering.reverse()
+      } else {
+        ordering
+      }
+    });
+
+    self.rows = rows;
+    self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+  }
+
+  fn matches_filter(&self, record: &Record, needle: &str) -> bool {
+    let subcategory = self
+      .tracker
+      .subcategory_name(record.subcategory)
+      .map(|s| s.to_lowercase())
+      .unwrap_or_default();
+    let category = self
+      .tracker
+      .category_name(record.category)
+      .map(|s| s.to_lowercase())
+      .unwrap_or_default();
+
+    record.description.to_lowercase().contains(needle)
+      || subcategory.contains(needle)
+      || category.contains(needle)
+      || record.date.contains(needle)
+  }
+
+  fn compare(&self, a: &Record, b: &Record) -> Ordering {
+    match self.sort {
+      SortKey::Date => a.sort_key().cmp(&b.sort_key()),
+      SortKey::Amount => a
+        .amount
+        .partial_cmp(&b.amount)
+        .unwrap_or(Ordering::Equal),
+      SortKey::Id => a.id.cmp(&b.id),
+      SortKey::Subcategory => self
+        .tracker
+        .subcategory_name(a.subcategory)
+        .cmp(&self.tracker.subcategory_name(b.subcategory)),
+    }
+  }
+
+  pub fn selected_record(&self) -> Option<&Record> {
+    self
+      .rows
+      .get(self.selected)
+      .map(|&index| &self.tracker.records[index])
+  }
+
+  pub fn select_next(&mut self) {
+    if self.selected + 1 < self.rows.len() {
+      self.selected += 1;
+    }
+  }
+
+  pub fn select_previous(&mut self) {
+    self.selected = self.selected.saturating_sub(1);
+  }
+
+  pub fn cycle_sort(&mut self) {
+    self.sort = self.sort.next();
+    self.refresh();
+  }
+
+  pub fn toggle_sort_direction(&mut self) {
+    self.descending = !self.descending;
+    self.refresh();
+  }
+
+  /// Enter command mode with the input pre-filled (e.g. "add ")
+  pub fn start_command(&mut self, prefill: &str) {
+    self.mode = Mode::Command;
+    self.input = prefill.to_string();
+  }
+
+  pub fn set_status(&mut self, text: impl Into<String>, is_error: bool) {
+    self.status = Some(Status {
+      text: text.into(),
+      is_error,
+    });
+  }
+
+  /// Totals over the whole tracker, plus the net of the rows currently shown
+  pub fn summary(&self) -> Summary {
+    let (income, expenses) = self.tracker.totals();
+    let income_id = self.tracker.category_id("income");
+
+    let shown_net = self
+      .rows
+      .iter()
+      .map(|&i| &self.tracker.records[i])
+      .map(|r| if r.category == income_id { r.amount } else { -r.amount })
+      .sum();
+
+    Summary {
+      opening_balance: self.tracker.opening_balance,
+      income,
+      expenses,
+      shown_count: self.rows.len(),
+      shown_net,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Currency, default_tracker_json};
+
+    fn create_test_app() -> App {
+        let mut tracker: TrackerData =
+            serde_json::from_value(default_tracker_json(&Currency::USD, 100.0)).unwrap();
+        tracker.subcategories_by_id.insert(2, "Groceries".to_string());
+        tracker.subcategories_by_name.insert("groceries".to_string(), 2);
+
+        let records = [
+            (1, 1, 1, "Salary", 500.0, "01-01-2025"),
+            (2, 2, 2, "Weekly shop", 80.0, "03-01-2025"),
+            (3, 2, 1, "Coffee", 4.5, "02-01-2025"),
+        ];
+        for (id, category, subcategory, description, amount, date) in records {
+            tracker.records.push(Record {
+                id,
+                category,
+                subcategory,
+                description: description.to_string(),
+                amount,
+                date: date.to_string(),
+                entered_by: None,
+                deductible: false,
+                project: None,
+                foreign: None,
+                tags: Vec::new(),
+                parsed_date: Default::default(),
+            });
+        }
+
+        App::new(tracker)
+    }
+
+    fn shown_ids(app: &App) -> Vec<usize> {
+        app.rows.iter().map(|&i| app.tracker.records[i].id).collect()
+    }
+
+    #[test]
+    fn test_default_sort_is_newest_first() {
+        let app = create_test_app();
+        assert_eq!(shown_ids(&app), vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn test_sort_by_amount_ascending() {
+        let mut app = create_test_app();
+        app.cycle_sort();
+        app.toggle_sort_direction();
+
+        assert_eq!(app.sort, SortKey::Amount);
+        assert_eq!(shown_ids(&app), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_filter_matches_description_and_subcategory() {
+        let mut app = create_test_app();
+
+        app.filter = "coffee".to_string();
+        app.refresh();
+        assert_eq!(shown_ids(&app), vec![3]);
+
+        app.filter = "GROCER".to_string();
+        app.refresh();
+        assert_eq!(shown_ids(&app), vec![2]);
+    }
+
+    #[test]
+    fn test_summary_reflects_filter() {
+        let mut app = create_test_app();
+        app.filter = "expenses".to_string();
+        app.refresh();
+
+        let summary = app.summary();
+        assert_eq!(summary.net(), 100.0 + 500.0 - 84.5);
+        assert_eq!(summary.shown_count, 2);
+        assert_eq!(summary.shown_net, -84.5);
+    }
+
+    #[test]
+    fn test_selection_is_clamped_after_refresh() {
+        let mut app = create_test_app();
+        app.select_next();
+        app.select_next();
+        assert_eq!(app.selected, 2);
+
+        app.filter = "coffee".to_string();
+        app.refresh();
+        assert_eq!(app.selected, 0);
+        assert_eq!(app.selected_record().unwrap().id, 3);
+    }
+}
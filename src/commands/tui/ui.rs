@@ -0,0 +1,142 @@
+use ratatui::{
+  Frame,
+  layout::{Constraint, Layout, Rect},
+  style::{Color, Modifier, Style},
+  text::{Line, Span},
+  widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+
+use super::app::{App, Mode};
+use crate::output::format_amount;
+
+const HELP: &str = "j/k move · s sort · r reverse · / filter · a add · e edit · d delete · : command · q quit";
+
+/// Draw the whole dashboard for the current app state
+pub fn draw(frame: &mut Frame, app: &App) {
+  let [header, body, footer] = Layout::vertical([
+    Constraint::Length(1),
+    Constraint::Min(5),
+    Constraint::Length(3),
+  ])
+  .areas(frame.area());
+
+  let [records, summary] =
+    Layout::horizontal([Constraint::Min(40), Constraint::Length(32)]).areas(body);
+
+  frame.render_widget(
+    Paragraph::new(HELP).style(Style::default().fg(Color::DarkGray)),
+    header,
+  );
+  draw_records(frame, app, records);
+  draw_summary(frame, app, summary);
+  draw_footer(frame, app, footer);
+}
+
+fn draw_records(frame: &mut Frame, app: &App, area: Rect) {
+  let income_id = app.tracker.category_id("income");
+
+  let rows = app.rows.iter().map(|&index| {
+    let record = &app.tracker.records[index];
+    let is_income = record.category == income_id;
+    let (sign, color) = if is_income {
+      ("+", Color::Green)
+    } else {
+      ("-", Color::Red)
+    };
+
+    Row::new(vec![
+      Cell::from(record.id.to_string()),
+      Cell::from(app.date_format.format(&record.date)),
+      Cell::from(
+        app
+          .tracker
+          .subcategory_name(record.subcategory)
+          .cloned()
+          .unwrap_or_default(),
+      ),
+      Cell::from(format!("{}{}", sign, format_amount(record.amount)))
+        .style(Style::default().fg(color)),
+      Cell::from(record.description.clone()),
+    ])
+  });
+
+  let direction = if app.descending { "↓" } else { "↑" };
+  let mut title = format!(" Records (sorted by {} {}) ", app.sort.label(), direction);
+  if !app.filter.is_empty() {
+    title.push_str(&format!("[filter: {}] ", app.filter));
+  }
+
+  let table = Table::new(
+    rows,
+    [
+      Constraint::Length(5),
+      Constraint::Length(11),
+      Constraint::Length(16),
+      Constraint::Length(14),
+      Constraint::Min(10),
+    ],
+  )
+  .header(
+    Row::new(["ID", "Date", "Subcategory", "Amount", "Description"])
+      .style(Style::default().add_modifier(Modifier::BOLD)),
+  )
+  .block(Block::default().borders(Borders::ALL).title(title))
+  .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+  let mut state = TableState::default().with_selected(Some(app.selected));
+  frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_summary(frame: &mut Frame, app: &App, area: Rect) {
+  let summary = app.summary();
+  let currency = &app.tracker.currency;
+
+  let line = |label: &str, amount: f64, color: Color| {
+    Line::from(vec![
+      Span::raw(format!("{:<10}", label)),
+      Span::styled(
+        format!("{} {}", format_amount(amount), currency),
+        Style::default().fg(color),
+      ),
+    ])
+  };
+
+  let lines = vec![
+    line("Opening", summary.opening_balance, Color::White),
+    line("Income", summary.income, Color::Green),
+    line("Expenses", summary.expenses, Color::Red),
+    line("Net", summary.net(), Color::Cyan),
+    Line::raw(""),
+    Line::raw(format!("Showing {} record(s)", summary.shown_count)),
+    line("Shown net", summary.shown_net, Color::Cyan),
+  ];
+
+  frame.render_widget(
+    Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Summary ")),
+    area,
+  );
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+  let (title, content, style) = match &app.mode {
+    Mode::Filter => (" Filter ", format!("/{}", app.input), Style::default()),
+    Mode::Command => (" Command ", format!(":{}", app.input), Style::default()),
+    Mode::ConfirmDelete(id) => (
+      " Confirm ",
+      format!("Delete record {}? (y/n)", id),
+      Style::default().fg(Color::Yellow),
+    ),
+    Mode::Normal => match &app.status {
+      Some(status) if status.is_error => (" Status ", status.text.clone(), Style::default().fg(Color::Red)),
+      Some(status) => (" Status ", status.text.clone(), Style::default().fg(Color::Green)),
+      None => (" Status ", String::new(), Style::default()),
+    },
+  };
+
+  frame.render_widget(
+    Paragraph::new(content)
+      .style(style)
+      .block(Block::default().borders(Borders::ALL).title(title)),
+    area,
+  );
+}
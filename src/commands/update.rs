@@ -1,9 +1,12 @@
-use clap::{Arg, ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 
 use crate::command_prelude::ArgMatchesExt;
-use crate::utils::file::{FilePath, write_json_to_file};
-use crate::utils::parsers::{parse_category, parse_date};
-use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent, TrackerData};
+use crate::commands::subcategory;
+use crate::utils::parsers::{parse_amount, parse_category, parse_tag};
+use crate::utils::storage::{load_tracker, save_tracker};
+use crate::utils::validation::{check_open, round_amount};
+use crate::utils::webhook::{self, Event};
+use crate::{CliError, CliResponse, CliResult, GlobalContext, ResponseContent};
 
 pub fn cli() -> Command {
   Command::new("update")
@@ -29,7 +32,7 @@ pub fn cli() -> Command {
       Arg::new("amount")
         .short('a')
         .long("amount")
-        .value_parser(clap::value_parser!(f64))
+        .value_parser(parse_amount)
         .help("Change the transaction amount (must be greater than 0)")
         .long_help("Updates the transaction amount. Must be a positive number greater than 0. Examples: 100, 150.50, 2000.75"),
     )
@@ -39,7 +42,14 @@ pub fn cli() -> Command {
         .long("subcategory")
         .value_parser(clap::value_parser!(String))
         .help("Change the subcategory name")
-        .long_help("Updates the subcategory. The subcategory must already exist - use 'fintrack subcategory list' to see available subcategories."),
+        .long_help("Updates the subcategory. The subcategory must already exist unless --create is given - use 'fintrack subcategory list' to see available subcategories."),
+    )
+    .arg(
+      Arg::new("create")
+        .long("create")
+        .requires("subcategory")
+        .action(ArgAction::SetTrue)
+        .help("Create the subcategory if it doesn't exist yet"),
     )
     .arg(
       Arg::new("description")
@@ -53,15 +63,47 @@ pub fn cli() -> Command {
       Arg::new("date")
         .short('D')
         .long("date")
-        .value_parser(parse_date)
+        .value_parser(clap::value_parser!(String))
         .help("Change the transaction date (DD-MM-YYYY format)")
-        .long_help("Updates the transaction date. Format: DD-MM-YYYY (e.g., 30-12-2025)."),
+        .long_help("Updates the transaction date. Format: DD-MM-YYYY (e.g., 30-12-2025), or any format listed in the 'input.date_formats' setting."),
+    )
+    .arg(
+      Arg::new("deductible")
+        .long("deductible")
+        .value_parser(clap::value_parser!(bool))
+        .num_args(0..=1)
+        .default_missing_value("true")
+        .action(ArgAction::Set)
+        .help("Mark as tax-deductible, or not with --deductible false"),
+    )
+    .arg(
+      Arg::new("project")
+        .long("project")
+        .value_parser(clap::value_parser!(String))
+        .help("Move the record to a project; an empty name removes it from its project"),
+    )
+    .arg(
+      Arg::new("tag")
+        .long("tag")
+        .value_name("TAG")
+        .action(ArgAction::Append)
+        .value_delimiter(',')
+        .value_parser(parse_tag)
+        .help("Add a tag to the record; repeat or separate with commas for several"),
+    )
+    .arg(
+      Arg::new("untag")
+        .long("untag")
+        .value_name("TAG")
+        .action(ArgAction::Append)
+        .value_delimiter(',')
+        .value_parser(parse_tag)
+        .help("Take a tag off the record"),
     )
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
-  let mut file = gctx.tracker_path().open_read_write()?;
-  let mut tracker_data: TrackerData = serde_json::from_reader(&file)?;
+  let mut tracker_data = load_tracker(gctx)?;
 
   let record_id = args
     .get_usize("record_id")
@@ -72,28 +114,47 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     tracker_data.category_id(&category_str)
   });
 
-  let subcategory_id = args
-    .get_subcategory_opt("subcategory")
-    .map(|name| {
-      tracker_data.subcategory_id(&name).ok_or_else(|| {
-        CliError::ValidationError(crate::ValidationErrorKind::SubcategoryNotFound { name })
-      })
-    })
-    .transpose()?;
+  let subcategory_id = match args.get_subcategory_opt("subcategory") {
+    Some(name) => match tracker_data.subcategory_id(&name) {
+      Some(id) => Some(id),
+      None if args.get_flag("create") => {
+        gctx.verbose(format!("Creating subcategory '{}'", name));
+        Some(subcategory::add::create(&mut tracker_data, &name)?)
+      }
+      None => return Err(tracker_data.subcategory_not_found(name)),
+    },
+    None => None,
+  };
+
+  let project = args.get_string_opt("project").map(|project| {
+    let project = project.trim();
+    (!project.is_empty()).then(|| tracker_data.project_name(project))
+  });
+
+  let added_tags: Vec<String> = args
+    .get_vec::<String>("tag")
+    .iter()
+    .map(|tag| tracker_data.tag_name(tag))
+    .collect();
+  let removed_tags: Vec<String> = args.get_vec("untag");
 
-  let record = tracker_data
+  let index = tracker_data
     .records
-    .iter_mut()
-    .find(|r| r.id == record_id)
-    .ok_or_else(|| {
-      CliError::ValidationError(crate::ValidationErrorKind::RecordNotFound { id: record_id })
-    })?;
+    .iter()
+    .position(|r| r.id == record_id)
+    .ok_or(CliError::ValidationError(
+      crate::ValidationErrorKind::RecordNotFound { id: record_id },
+    ))?;
+  check_open(&tracker_data, tracker_data.records[index].naive_date())?;
+  let previous = tracker_data.records[index].clone();
+  let record = &mut tracker_data.records[index];
 
   if let Some(cat_id) = category_id {
     record.category = cat_id;
   }
 
   if let Some(amount) = args.get_f64_opt("amount") {
+    let amount = round_amount(gctx, &tracker_data.currency, amount)?;
     if amount <= 0.0 {
       return Err(CliError::ValidationError(
         crate::ValidationErrorKind::AmountTooSmall { amount },
@@ -110,16 +171,39 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     record.description = description;
   }
 
-  if let Some(date) = args.get_date_opt("date") {
-    record.date = date.format("%d-%m-%Y").to_string();
+  if let Some(date) = args.get_date_opt("date")? {
+    record.set_date(date);
   }
 
-  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+  if let Some(deductible) = args.get_one::<bool>("deductible") {
+    record.deductible = *deductible;
+  }
+
+  if let Some(project) = project {
+    record.project = project;
+  }
+
+  record.tags.retain(|tag| !removed_tags.iter().any(|removed| removed.eq_ignore_ascii_case(tag)));
+  for tag in added_tags {
+    if !record.tags.contains(&tag) {
+      record.tags.push(tag);
+    }
+  }
 
   let updated_record = record.clone();
+  // Nor can a record be moved into a closed month
+  check_open(&tracker_data, updated_record.naive_date())?;
+  // Keep what it was for `fintrack history`, unless nothing changed
+  if serde_json::to_value(&previous).ok() != serde_json::to_value(&updated_record).ok() {
+    tracker_data.keep_version(previous, None);
+  }
+  tracker_data.sort_records();
+  tracker_data.last_modified = chrono::Utc::now().to_rfc3339();
+
+  tracing::info!(id = record_id, "record updated");
 
-  let tracker_json = serde_json::json!(tracker_data);
-  write_json_to_file(&tracker_json, &mut file)?;
+  save_tracker(gctx, &tracker_data)?;
+  webhook::notify(gctx, Event::Update, std::slice::from_ref(&updated_record), &tracker_data);
 
   Ok(CliResponse::new(ResponseContent::Record {
     record: updated_record,
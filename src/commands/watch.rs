@@ -0,0 +1,150 @@
+use std::{
+  collections::HashSet,
+  io::{self, IsTerminal},
+  sync::mpsc,
+  time::Duration,
+};
+
+use clap::{Arg, ArgMatches, Command};
+use colored::Colorize;
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::utils::alerts::{self, Alert};
+use crate::utils::notifier::Channel;
+use crate::utils::storage::load_tracker;
+use crate::{CliError, CliResponse, CliResult, GlobalContext, commands, t};
+
+/// Commands that only read the tracker, and so make sense to keep on screen
+pub const VIEWS: [&str; 4] = ["list", "total", "describe", "report"];
+
+/// How long the tracker must stay untouched before the view is redrawn, so a
+/// burst of writes causes one refresh
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+pub fn cli() -> Command {
+  Command::new("watch")
+    .about("Keep a view on screen and refresh it when the tracker changes")
+    .long_about("Shows the output of a command and redraws it whenever tracker.json changes, whether from another terminal, a script or a sync from another device. Handy on a second monitor. The view is the 'watch.view' setting (default 'list') unless you give one. With 'notify.desktop' on, budget overruns, bills coming due and spending limits gone over also pop up as desktop notifications, each once. Press Ctrl-C to stop.")
+    .arg(
+      Arg::new("view")
+        .help("Command to show, e.g. 'list --last 10' (list, total, describe or report)")
+        .num_args(1..)
+        .trailing_var_arg(true)
+        .allow_hyphen_values(true),
+    )
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+  let view = match args.get_many::<String>("view") {
+    Some(words) => words.map(|word| quote(word)).collect::<Vec<_>>().join(" "),
+    None => gctx.config().watch.view.clone(),
+  };
+  check_view(&view)?;
+  if !gctx.tracker_path().exists() {
+    return Err(CliError::NotInitialized);
+  }
+
+  let (tx, rx) = mpsc::channel();
+  let mut watcher = notify::recommended_watcher(tx).map_err(watch_error)?;
+  // Watch the directory: editors and sync tools often replace the file
+  // rather than write to it, which would end a watch on the file itself
+  let dir = gctx
+    .tracker_path()
+    .parent()
+    .filter(|dir| !dir.as_os_str().is_empty())
+    .unwrap_or(gctx.base_path());
+  watcher
+    .watch(dir, RecursiveMode::NonRecursive)
+    .map_err(watch_error)?;
+  gctx.verbose(format!("Watching {}", gctx.tracker_path().display()));
+
+  // Alerts already shown, so each pops up once rather than on every change
+  let mut shown = HashSet::new();
+  render(gctx, &view);
+  show_alerts(gctx, &mut shown);
+  for event in &rx {
+    let event = event.map_err(watch_error)?;
+    let tracker_changed = !matches!(event.kind, EventKind::Access(_))
+      && event
+        .paths
+        .iter()
+        .any(|path| path.file_name() == gctx.tracker_path().file_name());
+    if !tracker_changed {
+      continue;
+    }
+
+    while rx.recv_timeout(SETTLE_TIME).is_ok() {}
+    render(gctx, &view);
+    show_alerts(gctx, &mut shown);
+  }
+
+  Ok(CliResponse::success())
+}
+
+/// Check that `view` runs a command from [`VIEWS`]
+pub fn check_view(view: &str) -> Result<(), CliError> {
+  let command = view.split_whitespace().next().unwrap_or_default();
+  if VIEWS.contains(&command) {
+    return Ok(());
+  }
+
+  Err(CliError::Other(t!(
+    "error-watch-unsupported",
+    command = command,
+    views = VIEWS.join(", ")
+  )))
+}
+
+fn render(gctx: &mut GlobalContext, view: &str) {
+  if io::stdout().is_terminal() {
+    // Clear the screen and move the cursor to the top left
+    print!("\x1b[2J\x1b[H");
+  }
+  println!("{}", t!("watch-header", view = view).dimmed());
+  println!();
+
+  let result = commands::exec_line(gctx, view);
+  commands::shell::report(gctx, result);
+}
+
+/// Show alerts that weren't in `shown` as desktop notifications, if
+/// `notify.desktop` is on. Failures are logged rather than ending the watch.
+fn show_alerts(gctx: &GlobalContext, shown: &mut HashSet<Alert>) {
+  if !gctx.config().notify.desktop {
+    return;
+  }
+  let Ok(tracker_data) = load_tracker(gctx) else {
+    return;
+  };
+  for alert in alerts::current(gctx, &tracker_data) {
+    if shown.contains(&alert) {
+      continue;
+    }
+    if let Err(err) = Channel::Desktop.send(&alert.title, &alert.body) {
+      tracing::warn!(code = err.code(), "could not show an alert");
+    }
+    shown.insert(alert);
+  }
+}
+
+/// Escape a word so [`commands::exec_line`] splits it back out unchanged
+fn quote(word: &str) -> String {
+  if word.is_empty() {
+    return "''".to_string();
+  }
+  let mut quoted = String::new();
+  for c in word.chars() {
+    if c.is_whitespace() || matches!(c, '\'' | '"' | '\\') {
+      quoted.push('\\');
+    }
+    quoted.push(c);
+  }
+  quoted
+}
+
+fn watch_error(err: notify::Error) -> CliError {
+  match err.kind {
+    notify::ErrorKind::Io(err) => err.into(),
+    _ => CliError::Other(t!("error-watch-failed", reason = err.to_string())),
+  }
+}
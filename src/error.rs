@@ -1,8 +1,11 @@
 use std::io;
 
-use crate::output;
+use serde::Serialize;
 
-#[derive(Debug)]
+use crate::{output, t};
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
 pub enum ValidationErrorKind {
   AmountTooSmall {
     amount: f64,
@@ -10,9 +13,14 @@ pub enum ValidationErrorKind {
   InvalidDate {
     provided: String,
     expected_format: String,
+    /// Set when the input has the right shape but isn't a real date
+    nearest: Option<String>,
   },
   SubcategoryNotFound {
     name: String,
+    /// The existing subcategory it was most likely a typo of
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nearest: Option<String>,
   },
   SubcategoryAlreadyExists {
     name: String,
@@ -25,8 +33,9 @@ pub enum ValidationErrorKind {
     count: usize,
   },
   CannotDeleteMiscellaneous,
+  /// Categories can't be added, deleted or renamed
   CategoryImmutable {
-    category: usize,
+    category: String,
   },
   InvalidCategoryName {
     name: String,
@@ -43,6 +52,68 @@ pub enum ValidationErrorKind {
   InvalidSubcommand {
     subcommand: String,
   },
+  UnknownConfigKey {
+    key: String,
+  },
+  InvalidConfigValue {
+    key: String,
+    value: String,
+    reason: String,
+  },
+  /// `validation.reject_exact_duplicates` is on and record `id` matches
+  DuplicateRecord {
+    id: usize,
+  },
+  /// `owed settle` for someone with no open loans
+  NothingOwed {
+    person: String,
+  },
+  /// `bill pay` for a bill that was never added
+  BillNotFound {
+    name: String,
+  },
+  /// `project status` for a project no record belongs to
+  ProjectNotFound {
+    name: String,
+  },
+  ScheduleNotFound {
+    id: usize,
+  },
+  /// `budget delete` for a subcategory without a budget
+  BudgetNotFound {
+    subcategory: String,
+  },
+  /// `history --revert` to a version the record never had
+  VersionNotFound {
+    id: usize,
+    version: usize,
+  },
+  /// `subscriptions delete` for a subscription that was never added
+  SubscriptionNotFound {
+    name: String,
+  },
+  /// `serve token revoke` for a token that doesn't exist
+  TokenNotFound {
+    name: String,
+  },
+  /// `tag rename` or `tag delete` for a tag the tracker doesn't have
+  TagNotFound {
+    name: String,
+  },
+  /// `tag delete` without `--from-records` for a tag records still carry
+  TagInUse {
+    name: String,
+    count: usize,
+  },
+  /// A change to a record in a month `fintrack close` has locked
+  PeriodClosed {
+    /// MM-YYYY
+    month: String,
+  },
+  /// A record strict mode refuses
+  StrictCheckFailed {
+    reason: String,
+  },
 }
 
 #[derive(Debug)]
@@ -56,6 +127,22 @@ pub enum CliError {
     timestamp: String,
   },
   FileAlreadyExists,
+  /// No tracker has been created yet, i.e. `fintrack init` hasn't been run
+  NotInitialized,
+  /// A command tried to change the tracker while `--read-only` or
+  /// `general.read_only` is on
+  ReadOnly,
+  /// The records no longer match the checksum saved with them, e.g. after the
+  /// file was edited by hand or cut short
+  ChecksumMismatch,
+  /// The tracker is valid JSON but a value doesn't fit, e.g. an amount that
+  /// is a string. `path` locates it (`records[2].amount`) and `expected`
+  /// says what would fit, when serde knows.
+  InvalidField {
+    path: String,
+    message: String,
+    expected: Option<String>,
+  },
   Other(String),
 }
 
@@ -64,11 +151,189 @@ pub enum CliError {
 //   error: ProcessError,
 // }
 
+/// Process exit codes, grouped by the kind of failure so scripts can branch on
+/// them without parsing the error message
+pub mod exit_code {
+  pub const SUCCESS: i32 = 0;
+  /// Anything not covered below
+  pub const GENERAL: i32 = 1;
+  /// Unknown command or bad arguments (clap uses the same code)
+  pub const USAGE: i32 = 2;
+  /// The tracker or another required file doesn't exist
+  pub const FILE_NOT_FOUND: i32 = 3;
+  /// An amount, date or name was rejected
+  pub const INVALID_INPUT: i32 = 4;
+  /// The record or subcategory referred to doesn't exist
+  pub const NOT_FOUND: i32 = 5;
+  /// The change conflicts with existing data (duplicates, records still in use, fixed categories, closed months)
+  pub const CONFLICT: i32 = 6;
+  /// The tracker file is unreadable or corrupted
+  pub const DATA_ERROR: i32 = 7;
+  pub const PERMISSION_DENIED: i32 = 8;
+}
+
+impl ValidationErrorKind {
+  /// Stable error code shown in text and JSON output. Codes are never reused.
+  pub fn code(&self) -> &'static str {
+    match self {
+      ValidationErrorKind::AmountTooSmall { .. } => "E010",
+      ValidationErrorKind::InvalidDate { .. } => "E011",
+      ValidationErrorKind::SubcategoryNotFound { .. } => "E012",
+      ValidationErrorKind::SubcategoryAlreadyExists { .. } => "E013",
+      ValidationErrorKind::RecordNotFound { .. } => "E014",
+      ValidationErrorKind::SubcategoryHasRecords { .. } => "E015",
+      ValidationErrorKind::CannotDeleteMiscellaneous => "E016",
+      ValidationErrorKind::CategoryImmutable { .. } => "E017",
+      ValidationErrorKind::InvalidCategoryName { .. } => "E018",
+      ValidationErrorKind::InvalidName { .. } => "E019",
+      ValidationErrorKind::InvalidAmount { .. } => "E020",
+      ValidationErrorKind::TrackerAlreadyInitialized => "E021",
+      ValidationErrorKind::InvalidSubcommand { .. } => "E022",
+      ValidationErrorKind::UnknownConfigKey { .. } => "E023",
+      ValidationErrorKind::InvalidConfigValue { .. } => "E024",
+      ValidationErrorKind::StrictCheckFailed { .. } => "E025",
+      ValidationErrorKind::DuplicateRecord { .. } => "E026",
+      ValidationErrorKind::NothingOwed { .. } => "E027",
+      ValidationErrorKind::BillNotFound { .. } => "E028",
+      ValidationErrorKind::ProjectNotFound { .. } => "E029",
+      ValidationErrorKind::ScheduleNotFound { .. } => "E030",
+      ValidationErrorKind::PeriodClosed { .. } => "E031",
+      ValidationErrorKind::BudgetNotFound { .. } => "E032",
+      ValidationErrorKind::VersionNotFound { .. } => "E033",
+      ValidationErrorKind::SubscriptionNotFound { .. } => "E034",
+      ValidationErrorKind::TokenNotFound { .. } => "E035",
+      ValidationErrorKind::TagNotFound { .. } => "E036",
+      ValidationErrorKind::TagInUse { .. } => "E037",
+    }
+  }
+
+  /// Machine-readable name for this kind of validation failure
+  pub fn kind(&self) -> &'static str {
+    match self {
+      ValidationErrorKind::AmountTooSmall { .. } => "amount_too_small",
+      ValidationErrorKind::InvalidDate { .. } => "invalid_date",
+      ValidationErrorKind::SubcategoryNotFound { .. } => "subcategory_not_found",
+      ValidationErrorKind::SubcategoryAlreadyExists { .. } => "subcategory_already_exists",
+      ValidationErrorKind::RecordNotFound { .. } => "record_not_found",
+      ValidationErrorKind::SubcategoryHasRecords { .. } => "subcategory_has_records",
+      ValidationErrorKind::CannotDeleteMiscellaneous => "cannot_delete_miscellaneous",
+      ValidationErrorKind::CategoryImmutable { .. } => "category_immutable",
+      ValidationErrorKind::InvalidCategoryName { .. } => "invalid_category_name",
+      ValidationErrorKind::InvalidName { .. } => "invalid_name",
+      ValidationErrorKind::InvalidAmount { .. } => "invalid_amount",
+      ValidationErrorKind::TrackerAlreadyInitialized => "tracker_already_initialized",
+      ValidationErrorKind::InvalidSubcommand { .. } => "invalid_subcommand",
+      ValidationErrorKind::UnknownConfigKey { .. } => "unknown_config_key",
+      ValidationErrorKind::InvalidConfigValue { .. } => "invalid_config_value",
+      ValidationErrorKind::StrictCheckFailed { .. } => "strict_check_failed",
+      ValidationErrorKind::DuplicateRecord { .. } => "duplicate_record",
+      ValidationErrorKind::NothingOwed { .. } => "nothing_owed",
+      ValidationErrorKind::BillNotFound { .. } => "bill_not_found",
+      ValidationErrorKind::ProjectNotFound { .. } => "project_not_found",
+      ValidationErrorKind::ScheduleNotFound { .. } => "schedule_not_found",
+      ValidationErrorKind::PeriodClosed { .. } => "period_closed",
+      ValidationErrorKind::BudgetNotFound { .. } => "budget_not_found",
+      ValidationErrorKind::VersionNotFound { .. } => "version_not_found",
+      ValidationErrorKind::SubscriptionNotFound { .. } => "subscription_not_found",
+      ValidationErrorKind::TokenNotFound { .. } => "token_not_found",
+      ValidationErrorKind::TagNotFound { .. } => "tag_not_found",
+      ValidationErrorKind::TagInUse { .. } => "tag_in_use",
+    }
+  }
+
+  pub fn exit_code(&self) -> i32 {
+    match self {
+      ValidationErrorKind::AmountTooSmall { .. }
+      | ValidationErrorKind::InvalidDate { .. }
+      | ValidationErrorKind::InvalidCategoryName { .. }
+      | ValidationErrorKind::InvalidName { .. }
+      | ValidationErrorKind::InvalidAmount { .. }
+      | ValidationErrorKind::UnknownConfigKey { .. }
+      | ValidationErrorKind::InvalidConfigValue { .. }
+      | ValidationErrorKind::StrictCheckFailed { .. } => exit_code::INVALID_INPUT,
+      ValidationErrorKind::SubcategoryNotFound { .. }
+      | ValidationErrorKind::RecordNotFound { .. }
+      | ValidationErrorKind::NothingOwed { .. }
+      | ValidationErrorKind::BillNotFound { .. }
+      | ValidationErrorKind::ProjectNotFound { .. }
+      | ValidationErrorKind::ScheduleNotFound { .. }
+      | ValidationErrorKind::BudgetNotFound { .. }
+      | ValidationErrorKind::VersionNotFound { .. }
+      | ValidationErrorKind::SubscriptionNotFound { .. }
+      | ValidationErrorKind::TokenNotFound { .. }
+      | ValidationErrorKind::TagNotFound { .. } => exit_code::NOT_FOUND,
+      ValidationErrorKind::SubcategoryAlreadyExists { .. }
+      | ValidationErrorKind::SubcategoryHasRecords { .. }
+      | ValidationErrorKind::CannotDeleteMiscellaneous
+      | ValidationErrorKind::CategoryImmutable { .. }
+      | ValidationErrorKind::TrackerAlreadyInitialized
+      | ValidationErrorKind::DuplicateRecord { .. }
+      | ValidationErrorKind::PeriodClosed { .. }
+      | ValidationErrorKind::TagInUse { .. } => exit_code::CONFLICT,
+      ValidationErrorKind::InvalidSubcommand { .. } => exit_code::USAGE,
+    }
+  }
+}
+
 impl CliError {
   /// Write this error to the given writer
   pub fn write_to(&self, writer: &mut impl std::io::Write) -> io::Result<()> {
     output::write_error(self, writer)
   }
+
+  /// Write this error to the given writer as a JSON document
+  pub fn write_json_to(&self, writer: &mut impl std::io::Write) -> io::Result<()> {
+    output::write_error_json(self, writer)
+  }
+
+  /// Stable error code (e.g. `E012`) shown in text and JSON output
+  pub fn code(&self) -> &'static str {
+    match self {
+      CliError::Other(_) => "E000",
+      CliError::FileNotFound(_) => "E001",
+      CliError::InvalidJson(_) => "E002",
+      CliError::PermissionDenied(_) => "E003",
+      CliError::CorruptedData { .. } => "E004",
+      CliError::FileAlreadyExists => "E005",
+      CliError::NotInitialized => "E006",
+      CliError::ReadOnly => "E007",
+      CliError::ChecksumMismatch => "E008",
+      CliError::InvalidField { .. } => "E009",
+      CliError::ValidationError(kind) => kind.code(),
+    }
+  }
+
+  /// Machine-readable name for this error, used by the JSON output
+  pub fn kind(&self) -> &'static str {
+    match self {
+      CliError::FileNotFound(_) => "file_not_found",
+      CliError::InvalidJson(_) => "invalid_json",
+      CliError::ValidationError(kind) => kind.kind(),
+      CliError::PermissionDenied(_) => "permission_denied",
+      CliError::CorruptedData { .. } => "corrupted_data",
+      CliError::FileAlreadyExists => "file_already_exists",
+      CliError::NotInitialized => "not_initialized",
+      CliError::ReadOnly => "read_only",
+      CliError::ChecksumMismatch => "checksum_mismatch",
+      CliError::InvalidField { .. } => "invalid_field",
+      CliError::Other(_) => "other",
+    }
+  }
+
+  /// The process exit code for a command that failed with this error
+  pub fn exit_code(&self) -> i32 {
+    match self {
+      CliError::FileNotFound(_) | CliError::NotInitialized => exit_code::FILE_NOT_FOUND,
+      CliError::InvalidJson(_)
+      | CliError::CorruptedData { .. }
+      | CliError::ChecksumMismatch
+      | CliError::InvalidField { .. } => exit_code::DATA_ERROR,
+      CliError::ValidationError(kind) => kind.exit_code(),
+      CliError::PermissionDenied(_) | CliError::ReadOnly => exit_code::PERMISSION_DENIED,
+      CliError::FileAlreadyExists => exit_code::CONFLICT,
+      CliError::Other(_) => exit_code::GENERAL,
+    }
+  }
 }
 
 impl From<std::io::Error> for CliError {
@@ -77,7 +342,7 @@ impl From<std::io::Error> for CliError {
       std::io::ErrorKind::NotFound => CliError::FileNotFound(err.to_string()),
       std::io::ErrorKind::PermissionDenied => CliError::PermissionDenied(err.to_string()),
       std::io::ErrorKind::AlreadyExists => CliError::FileAlreadyExists,
-      _ => CliError::Other(format!("IO error: {}", err)),
+      _ => CliError::Other(t!("error-io", reason = err.to_string())),
     }
   }
 }
@@ -93,3 +358,92 @@ pub fn invalid_subcommand_error(cmd: &str) -> CliError {
     subcommand: cmd.to_string(),
   })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_errors() -> Vec<CliError> {
+        let name = || "Food".to_string();
+        let kinds = vec![
+            ValidationErrorKind::AmountTooSmall { amount: 0.0 },
+            ValidationErrorKind::InvalidDate {
+                provided: "2025".to_string(),
+                expected_format: "DD-MM-YYYY".to_string(),
+                nearest: None,
+            },
+            ValidationErrorKind::SubcategoryNotFound { name: name(), nearest: None },
+            ValidationErrorKind::SubcategoryAlreadyExists { name: name() },
+            ValidationErrorKind::RecordNotFound { id: 1 },
+            ValidationErrorKind::SubcategoryHasRecords { name: name(), count: 2 },
+            ValidationErrorKind::CannotDeleteMiscellaneous,
+            ValidationErrorKind::CategoryImmutable { category: name() },
+            ValidationErrorKind::InvalidCategoryName { name: name(), reason: String::new() },
+            ValidationErrorKind::InvalidName { name: name(), reason: String::new() },
+            ValidationErrorKind::InvalidAmount { reason: String::new() },
+            ValidationErrorKind::TrackerAlreadyInitialized,
+            ValidationErrorKind::InvalidSubcommand { subcommand: "x".to_string() },
+            ValidationErrorKind::UnknownConfigKey { key: "x".to_string() },
+            ValidationErrorKind::InvalidConfigValue {
+                key: "x".to_string(),
+                value: "y".to_string(),
+                reason: String::new(),
+            },
+            ValidationErrorKind::StrictCheckFailed { reason: String::new() },
+            ValidationErrorKind::DuplicateRecord { id: 1 },
+            ValidationErrorKind::NothingOwed { person: name() },
+            ValidationErrorKind::BillNotFound { name: name() },
+            ValidationErrorKind::ProjectNotFound { name: name() },
+            ValidationErrorKind::ScheduleNotFound { id: 1 },
+            ValidationErrorKind::PeriodClosed { month: name() },
+            ValidationErrorKind::BudgetNotFound { subcategory: name() },
+            ValidationErrorKind::VersionNotFound { id: 1, version: 2 },
+            ValidationErrorKind::SubscriptionNotFound { name: name() },
+            ValidationErrorKind::TokenNotFound { name: name() },
+            ValidationErrorKind::TagNotFound { name: name() },
+            ValidationErrorKind::TagInUse { name: name(), count: 2 },
+        ];
+
+        let mut errors = vec![
+            CliError::FileNotFound(String::new()),
+            CliError::InvalidJson(String::new()),
+            CliError::PermissionDenied(String::new()),
+            CliError::CorruptedData { backup_restored: false, timestamp: String::new() },
+            CliError::FileAlreadyExists,
+            CliError::NotInitialized,
+            CliError::ReadOnly,
+            CliError::ChecksumMismatch,
+            CliError::InvalidField { path: String::new(), message: String::new(), expected: None },
+            CliError::Other(String::new()),
+        ];
+        errors.extend(kinds.into_iter().map(CliError::ValidationError));
+        errors
+    }
+
+    #[test]
+    fn test_error_codes_are_unique() {
+        let errors = all_errors();
+        let mut codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+        assert!(codes.iter().all(|c| c.len() == 4 && c.starts_with('E')));
+    }
+
+    #[test]
+    fn test_exit_codes_are_never_success() {
+        for err in all_errors() {
+            assert_ne!(err.exit_code(), exit_code::SUCCESS, "{}", err.kind());
+        }
+    }
+
+    #[test]
+    fn test_exit_codes_group_failures() {
+        let not_found = CliError::ValidationError(ValidationErrorKind::RecordNotFound { id: 3 });
+        let bad_amount = CliError::ValidationError(ValidationErrorKind::AmountTooSmall { amount: -1.0 });
+
+        assert_eq!(not_found.exit_code(), exit_code::NOT_FOUND);
+        assert_eq!(bad_amount.exit_code(), exit_code::INVALID_INPUT);
+        assert_eq!(CliError::FileNotFound(String::new()).exit_code(), exit_code::FILE_NOT_FOUND);
+    }
+}
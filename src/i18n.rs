@@ -0,0 +1,250 @@
+use std::sync::{OnceLock, RwLock};
+
+use fluent_bundle::{FluentResource, concurrent::FluentBundle};
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentArgs;
+
+/// Languages the CLI's messages are translated into
+#[derive(
+  clap::ValueEnum,
+  Clone,
+  Copy,
+  Debug,
+  Default,
+  PartialEq,
+  serde::Serialize,
+  serde::Deserialize,
+  strum::Display,
+  strum::EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum Language {
+  #[default]
+  En,
+  Fr,
+  Es,
+}
+
+impl Language {
+  const ALL: [Language; 3] = [Language::En, Language::Fr, Language::Es];
+
+  /// Pick a language from a POSIX locale name such as `fr_FR.UTF-8`
+  pub fn from_locale(locale: &str) -> Option<Self> {
+    let code = locale.split(['_', '-', '.', '@']).next()?;
+    code.parse().ok()
+  }
+
+  fn source(self) -> &'static str {
+    match self {
+      Language::En => include_str!("../locales/en.ftl"),
+      Language::Fr => include_str!("../locales/fr.ftl"),
+      Language::Es => include_str!("../locales/es.ftl"),
+    }
+  }
+
+  fn bundle(self) -> &'static FluentBundle<FluentResource> {
+    static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+
+    let bundles = BUNDLES.get_or_init(|| Language::ALL.iter().map(|l| l.build_bundle()).collect());
+    &bundles[self as usize]
+  }
+
+  fn build_bundle(self) -> FluentBundle<FluentResource> {
+    let langid: LanguageIdentifier = self.to_string().parse().expect("valid language identifier");
+    let resource = FluentResource::try_new(self.source().to_string())
+      .unwrap_or_else(|(_, errors)| panic!("invalid {} translations: {:?}", self, errors));
+
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // Directional isolation marks would end up in terminal output and JSON
+    bundle.set_use_isolating(false);
+    bundle
+      .add_resource(resource)
+      .unwrap_or_else(|errors| panic!("duplicate {} translations: {:?}", self, errors));
+    bundle
+  }
+}
+
+static CURRENT: RwLock<Language> = RwLock::new(Language::En);
+
+/// The language messages are currently rendered in
+pub fn language() -> Language {
+  *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn set_language(language: Language) {
+  *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = language;
+}
+
+/// Choose a language from locale settings in priority order (`LC_ALL`,
+/// `LC_MESSAGES`, `LANG`). As with other POSIX tools the first non-empty value
+/// wins; English is used if that locale isn't translated.
+pub fn detect_language<'a>(locales: impl IntoIterator<Item = Option<&'a str>>) -> Language {
+  locales
+    .into_iter()
+    .flatten()
+    .find(|locale| !locale.is_empty())
+    .and_then(Language::from_locale)
+    .unwrap_or_default()
+}
+
+/// Detect the language from the process environment
+pub fn language_from_env() -> Language {
+  let vars = ["LC_ALL", "LC_MESSAGES", "LANG"].map(|name| std::env::var(name).ok());
+  detect_language(vars.iter().map(|v| v.as_deref()))
+}
+
+/// Look up a message in the current language
+pub fn tr(id: &str, args: Option<&FluentArgs>) -> String {
+  tr_in(language(), id, args)
+}
+
+/// Look up a message in the given language, falling back to English for
+/// messages that haven't been translated yet
+pub fn tr_in(language: Language, id: &str, args: Option<&FluentArgs>) -> String {
+  [language, Language::En]
+    .iter()
+    .find_map(|l| {
+      let bundle = l.bundle();
+      let pattern = bundle.get_message(id)?.value()?;
+      let mut errors = Vec::new();
+      Some(bundle.format_pattern(pattern, args, &mut errors).into_owned())
+    })
+    .unwrap_or_else(|| id.to_string())
+}
+
+/// Translate a message ID, with optional `name = value` arguments:
+///
+/// ```text
+/// t!("error-record-not-found", id = 42)
+/// ```
+#[macro_export]
+macro_rules! t {
+  ($id:expr) => {
+    $crate::i18n::tr($id, None)
+  };
+  ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+    let mut args = $crate::i18n::FluentArgs::new();
+    $(args.set(stringify!($name), $value);)+
+    $crate::i18n::tr($id, Some(&args))
+  }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_from_locale() {
+        assert_eq!(Language::from_locale("fr_FR.UTF-8"), Some(Language::Fr));
+        assert_eq!(Language::from_locale("es"), Some(Language::Es));
+        assert_eq!(Language::from_locale("en_GB"), Some(Language::En));
+        assert_eq!(Language::from_locale("C"), None);
+        assert_eq!(Language::from_locale("de_DE.UTF-8"), None);
+    }
+
+    #[test]
+    fn test_detect_language_uses_first_non_empty_locale() {
+        assert_eq!(detect_language([None, Some(""), Some("fr_FR.UTF-8")]), Language::Fr);
+        assert_eq!(detect_language([Some("es_ES"), None, Some("fr_FR")]), Language::Es);
+        // An untranslated locale still takes priority and falls back to English
+        assert_eq!(detect_language([Some("de_DE"), None, Some("fr_FR")]), Language::En);
+        assert_eq!(detect_language([None, None, None]), Language::En);
+    }
+
+    #[test]
+    fn test_translations_with_arguments() {
+        let mut args = FluentArgs::new();
+        args.set("id", 7);
+
+        assert_eq!(
+            tr_in(Language::En, "error-record-not-found", Some(&args)),
+            "Record with ID 7 not found"
+        );
+        assert_eq!(
+            tr_in(Language::Fr, "error-record-not-found", Some(&args)),
+            "Enregistrement avec l'ID 7 introuvable"
+        );
+        assert_eq!(
+            tr_in(Language::Es, "error-record-not-found", Some(&args)),
+            "No se encontró el registro con ID 7"
+        );
+    }
+
+    #[test]
+    fn test_plural_selection() {
+        let mut args = FluentArgs::new();
+        args.set("name", "Food");
+        args.set("count", 1);
+        assert_eq!(
+            tr_in(Language::En, "error-subcategory-has-records", Some(&args)),
+            "Cannot delete 'Food' — it has 1 record"
+        );
+
+        args.set("count", 3);
+        assert_eq!(
+            tr_in(Language::En, "error-subcategory-has-records", Some(&args)),
+            "Cannot delete 'Food' — it has 3 records"
+        );
+    }
+
+    #[test]
+    fn test_every_message_is_translated() {
+        let ids: Vec<&str> = Language::En
+            .source()
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|line| line.split_once(" = ").map(|(id, _)| id))
+            .collect();
+        assert!(ids.len() > 50);
+
+        for language in [Language::Fr, Language::Es] {
+            for id in &ids {
+                assert!(
+                    language.bundle().has_message(id),
+                    "'{}' is missing from the {} translations",
+                    id,
+                    language
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_command_messages_are_translated() {
+        let mut args = FluentArgs::new();
+        args.set("name", "Food");
+        args.set("amount", "100.00");
+        args.set("currency", "USD");
+        args.set("rollover", "true");
+        assert_eq!(
+            tr_in(Language::En, "budget-set", Some(&args)),
+            "Budget for Food set to 100.00 USD a month, rolling over"
+        );
+        assert_eq!(
+            tr_in(Language::Fr, "budget-set", Some(&args)),
+            "Budget de Food fixé à 100.00 USD par mois, avec report"
+        );
+
+        args.set("rollover", "false");
+        assert_eq!(
+            tr_in(Language::Es, "budget-set", Some(&args)),
+            "Presupuesto de Food fijado en 100.00 USD al mes"
+        );
+
+        let mut args = FluentArgs::new();
+        args.set("count", 0);
+        assert_eq!(tr_in(Language::En, "schedule-ran", Some(&args)), "No scheduled records are due");
+        args.set("count", 2);
+        assert_eq!(
+            tr_in(Language::Fr, "schedule-ran", Some(&args)),
+            "2 enregistrements planifiés ajoutés"
+        );
+    }
+
+    #[test]
+    fn test_unknown_message_falls_back_to_id() {
+        assert_eq!(tr_in(Language::Fr, "no-such-message", None), "no-such-message");
+    }
+}
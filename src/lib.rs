@@ -1,5 +1,6 @@
 pub mod commands;
 pub mod error;
+pub mod i18n;
 pub mod models;
 pub mod output;
 pub mod utils;
@@ -7,7 +8,7 @@ pub mod utils;
 // Re-export commonly used items at crate root for convenience
 // utils
 pub use utils::command_prelude;
-pub use utils::context::GlobalContext;
+pub use utils::context::{GlobalContext, Verbosity};
 pub use utils::parsers;
 
 // model
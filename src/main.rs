@@ -1,32 +1,238 @@
-use std::io;
+use std::io::{self, IsTerminal};
+use std::time::Instant;
+use std::path::PathBuf;
 
-use clap::Command;
-use fintrack::{GlobalContext, commands};
+use clap::{Arg, ArgAction, Command};
+use terminal_size::Width;
+use fintrack::utils::expand::{expand_aliases, insert_default_args};
+use fintrack::utils::logging::{LogLevel, init_logging};
+use fintrack::utils::storage;
+use fintrack::{
+  CliError, GlobalContext, OutputFormat, TableStyle, Verbosity, commands, exit_code, i18n, output,
+};
 
 fn main() {
-  let exit_code = match run() {
-    Ok(_) => 0,
+  let code = match run() {
+    Ok(code) => code,
     Err(e) => {
       eprintln!("Error: {}", e);
-      1
+      exit_code::GENERAL
     }
   };
-  std::process::exit(exit_code);
+  std::process::exit(code);
 }
 
-fn run() -> Result<(), String> {
+/// Run the CLI and return the process exit code: 0 on success, otherwise the
+/// code for the kind of error the command failed with (see [`exit_code`])
+fn run() -> Result<i32, String> {
   let home_dir =
     dirs::home_dir().ok_or_else(|| "Failed to determine home directory".to_string())?;
 
   let mut gctx = GlobalContext::new(home_dir);
+  // Aliases are needed before parsing; a broken config file is reported once
+  // the output format is known
+  i18n::set_language(i18n::language_from_env());
+  let config_result = gctx.load_config();
 
-  let matches = Command::new("fintrack")
+  let cli = Command::new("fintrack")
     .bin_name("fintrack")
     .about("A local-first CLI financial tracker for managing income and expenses")
     .version(env!("CARGO_PKG_VERSION"))
     .subcommand_required(true)
-    .subcommands(commands::cli())
-    .get_matches();
+    .arg(
+      Arg::new("output")
+        .long("output")
+        .global(true)
+        .value_parser(clap::value_parser!(OutputFormat))
+        .default_value("text")
+        .help("Output format: 'text' or 'json'")
+        .long_help("How results are printed. Use 'json' to get a machine-readable document (including structured error codes) for scripts and other tools. Defaults to 'text'."),
+    )
+    .arg(
+      Arg::new("no-color")
+        .long("no-color")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Disable colored output")
+        .long_help("Prints plain text without ANSI color codes. Colors are also disabled when the NO_COLOR environment variable is set or when output is not a terminal (e.g. redirected to a file)."),
+    )
+    .arg(
+      Arg::new("quiet")
+        .short('q')
+        .long("quiet")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .conflicts_with("verbose")
+        .help("Suppress success messages")
+        .long_help("Hides confirmations such as 'Record created' or '✓ Success'. Errors and requested data (lists, totals) are still printed, and the exit code tells whether the command succeeded."),
+    )
+    .arg(
+      Arg::new("verbose")
+        .short('v')
+        .long("verbose")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Show files touched, parsed filters and timing")
+        .long_help("Prints extra diagnostics to stderr: which files are read or written, the filters a command parsed from its arguments, and how long the command took."),
+    )
+    .arg(
+      Arg::new("read-only")
+        .long("read-only")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Refuse to change the tracker")
+        .long_help("Makes every command that would modify the tracker (add, update, delete, subcategory changes, init, clear) fail instead. Useful when exploring a shared or backed-up tracker you must not touch. Set 'general.read_only' to make it the default."),
+    )
+    .arg(
+      Arg::new("tracker-file")
+        .long("tracker-file")
+        .global(true)
+        .value_name("FILE")
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("Use this tracker file instead of ~/.fintrack/tracker.json")
+        .long_help("Runs the command against another tracker file, such as a backup, an export from 'fintrack dump' or a test fixture, leaving your own tracker alone. Settings still come from ~/.fintrack/config.toml, but no backups are made of the file and it isn't restored from one if it's damaged. Add --read-only to be sure the file isn't changed either."),
+    )
+    .arg(
+      Arg::new("dry-run")
+        .long("dry-run")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Show what a command would change without saving it")
+        .long_help("Runs the command in full but doesn't write the tracker. Instead it prints the records that would be added, changed or removed and the totals before and after. Hooks that follow a change and the webhook don't run. Works with every command that changes the tracker, including batch, where it shows the whole script's changes. Commands that change settings or serve tokens say what they would change instead."),
+    )
+    .arg(
+      Arg::new("offline")
+        .long("offline")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Never use the network")
+        .long_help("Makes commands that would go online fail instead: fetching exchange rates or prices, notifications other than desktop ones, emailed reports and Google Sheets exports. Exchange rates fetched before are still used, however old. The webhook is skipped."),
+    )
+    .arg(
+      Arg::new("strict")
+        .long("strict")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Reject suspicious new records")
+        .long_help("Makes 'add' refuse records dated in the future, with an empty description, above the 'validation.max_amount' ceiling, or in a tracker whose currency fintrack doesn't know. Set 'validation.strict' to make it the default."),
+    )
+    .arg(
+      Arg::new("log-level")
+        .long("log-level")
+        .global(true)
+        .value_parser(clap::value_parser!(LogLevel))
+        .help("Diagnostic log level: off, error, warn, info, debug or trace")
+        .long_help("Emits structured diagnostic logs for the load, parse, filter and write phases of a command, including how long each took. Logs go to stderr unless --log-file is given. Defaults to 'off' (or 'info' when --log-file is used)."),
+    )
+    .arg(
+      Arg::new("log-file")
+        .long("log-file")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Append diagnostic logs to ~/.fintrack/fintrack.log")
+        .long_help("Writes diagnostic logs to ~/.fintrack/fintrack.log instead of stderr, so you can attach them to a bug report or review what changed your data."),
+    )
+    .arg(
+      Arg::new("table-style")
+        .long("table-style")
+        .global(true)
+        .value_parser(clap::value_parser!(TableStyle))
+        .help("Table border style: modern, ascii, markdown or compact")
+        .long_help("Controls how record tables are drawn. Use 'ascii' if box-drawing characters render badly in your terminal, 'markdown' to paste tables into docs, or 'compact' for minimal separators. Defaults to 'modern'."),
+    )
+    .arg(
+      Arg::new("hide-currency")
+        .long("hide-currency")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Leave the currency code out of amounts in tables")
+        .long_help("Drops the ' USD' after each amount in record, history, budget and --by tables, which narrows them on small screens. The 'display.hide_currency' setting does the same for every command."),
+    )
+    .arg(
+      Arg::new("compact-amounts")
+        .long("compact-amounts")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Show large amounts in tables as 12.3k, 4.5M and so on")
+        .long_help("Shortens amounts of a thousand or more in record, history, budget and --by tables to one decimal place and a k, M, B or T suffix. Amounts outside tables, and JSON output, stay exact. The 'display.compact_amounts' setting does the same for every command."),
+    )
+    .arg(
+      Arg::new("full")
+        .long("full")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Show full descriptions instead of fitting tables to the terminal")
+        .long_help("By default long descriptions are truncated with '…' so record tables fit the terminal width. Use --full to show every description in full. Output that is piped or redirected is never truncated."),
+    )
+    .subcommands(commands::cli());
+
+  let mut argv: Vec<String> = std::env::args_os()
+    .map(|arg| arg.to_string_lossy().into_owned())
+    .collect();
+  let args = expand_aliases(argv.split_off(1), &gctx.config().aliases, &cli)?;
+  let (cli, args) = insert_default_args(cli, args, gctx.config())?;
+  let matches = cli.get_matches_from(argv.into_iter().chain(args));
+
+  let output_format = matches
+    .get_one::<OutputFormat>("output")
+    .copied()
+    .unwrap_or_default();
+
+  let colors = output::colors_enabled(
+    matches.get_flag("no-color"),
+    std::env::var_os("NO_COLOR").as_deref(),
+    output_format,
+    io::stdout().is_terminal(),
+  );
+  colored::control::set_override(colors);
+
+  let log_to_file = matches.get_flag("log-file");
+  let log_level = matches
+    .get_one::<LogLevel>("log-level")
+    .copied()
+    .unwrap_or(if log_to_file { LogLevel::Info } else { LogLevel::Off });
+  init_logging(
+    log_level,
+    log_to_file.then(|| gctx.log_path().as_path()),
+    colors && io::stderr().is_terminal(),
+  )
+    .map_err(|e| format!("Failed to open log file: {}", e))?;
+
+  gctx.set_read_only(matches.get_flag("read-only"));
+  if let Some(path) = matches.get_one::<PathBuf>("tracker-file") {
+    gctx.set_tracker_file(std::path::absolute(path).map_err(|e| format!("Bad --tracker-file: {}", e))?);
+  }
+  gctx.set_offline(matches.get_flag("offline"));
+  gctx.set_dry_run(matches.get_flag("dry-run"));
+  gctx.set_strict(matches.get_flag("strict"));
+  gctx.set_interactive(
+    output_format == OutputFormat::Text && io::stdin().is_terminal() && io::stdout().is_terminal(),
+  );
+  if matches.get_flag("quiet") {
+    gctx.set_verbosity(Verbosity::Quiet);
+  } else if matches.get_flag("verbose") {
+    gctx.set_verbosity(Verbosity::Verbose);
+  }
+
+  if let Err(err) = config_result {
+    let code = err.exit_code();
+    process_result(&Err(err), output_format, &gctx)
+      .map_err(|e| format!("Failed to report config error: {}", e))?;
+    return Ok(code);
+  }
+  if let Some(style) = matches.get_one::<TableStyle>("table-style") {
+    gctx.display_mut().table_style = *style;
+  }
+  if matches.get_flag("hide-currency") {
+    gctx.display_mut().hide_currency = true;
+  }
+  if matches.get_flag("compact-amounts") {
+    gctx.display_mut().compact_amounts = true;
+  }
+  if !matches.get_flag("full") && io::stdout().is_terminal() {
+    gctx.display_mut().max_width =
+      terminal_size::terminal_size().map(|(Width(width), _)| width as usize);
+  }
 
   let (cmd, args) = matches
     .subcommand()
@@ -34,16 +240,84 @@ fn run() -> Result<(), String> {
 
   let exec_fn = commands::build_exec(cmd).ok_or_else(|| format!("Unknown command: {}", cmd))?;
 
-  let exec_result = exec_fn(&mut gctx, args);
+  // Only the exit code matters: nothing on success, and no offer to set up a
+  // missing tracker
+  if cmd == "status" && args.get_flag("check") {
+    gctx.set_verbosity(Verbosity::Quiet);
+  }
+
+  if gctx.config().schedule.run_on_start && !gctx.is_read_only() && !matches!(cmd, "init" | "schedule") {
+    run_schedules(&mut gctx, output_format);
+  }
+
+  let started = Instant::now();
+  let mut exec_result = {
+    let _span = tracing::info_span!("command", name = cmd).entered();
+    exec_fn(&mut gctx, args)
+  };
+
+  // First run: offer to create the tracker, then carry on with the command
+  if matches!(exec_result, Err(CliError::NotInitialized))
+    && !gctx.is_read_only()
+    && output_format == OutputFormat::Text
+    && !gctx.is_quiet()
+    && io::stdin().is_terminal()
+    && io::stdout().is_terminal()
+  {
+    match commands::init::guided_setup(&gctx) {
+      Ok(true) => exec_result = exec_fn(&mut gctx, args),
+      Ok(false) => {}
+      Err(err) => exec_result = Err(err),
+    }
+  }
+  let exec_result = storage::finish_dry_run(&gctx, exec_result);
+  if let Err(err) = &exec_result {
+    tracing::warn!(code = err.code(), kind = err.kind(), "command failed");
+  }
+  gctx.verbose(format!("'{}' finished in {:.2?}", cmd, started.elapsed()));
+
   // the error expected here is not CliError, it is an io error that occured as CliResponse or CliError is being written to stdout
-  process_result(&exec_result).expect("An error occured displaying response");
+  process_result(&exec_result, output_format, &gctx)
+    .expect("An error occured displaying response");
+
+  Ok(match &exec_result {
+    Ok(_) => exit_code::SUCCESS,
+    Err(err) => err.exit_code(),
+  })
+}
 
-  Ok(())
+/// `schedule.run_on_start`: add scheduled records that have come due. A failure
+/// is reported but doesn't stop the command that was asked for.
+fn run_schedules(gctx: &mut GlobalContext, output_format: OutputFormat) {
+  match commands::schedule::run::apply_due(gctx, gctx.today()) {
+    Ok((added, warnings)) => {
+      gctx.verbose(format!("Added {} scheduled record(s)", added));
+      if output_format == OutputFormat::Text {
+        let _ = output::write_warnings(&warnings, &mut io::stderr());
+      }
+    }
+    // The command itself will say so, or offer to set the tracker up
+    Err(CliError::NotInitialized) => {}
+    Err(err) => {
+      tracing::warn!(code = err.code(), kind = err.kind(), "scheduled records not added");
+      if output_format == OutputFormat::Text {
+        let _ = err.write_to(&mut io::stderr());
+      }
+    }
+  }
 }
 
-fn process_result(result: &fintrack::CliResult) -> io::Result<()> {
-  match result {
-    Ok(res) => res.write_to(&mut std::io::stdout()),
-    Err(err) => err.write_to(&mut std::io::stderr()),
+fn process_result(
+  result: &fintrack::CliResult,
+  format: OutputFormat,
+  gctx: &GlobalContext,
+) -> io::Result<()> {
+  match (result, format) {
+    (Ok(res), _) if gctx.is_quiet() && res.is_confirmation() => Ok(()),
+    (Ok(res), OutputFormat::Text) => res.write_to(gctx.display(), &mut std::io::stdout()),
+    (Err(err), OutputFormat::Text) => err.write_to(&mut std::io::stderr()),
+    // JSON documents always go to stdout so scripts only need to read one stream
+    (Ok(res), OutputFormat::Json) => res.write_json_to(&mut std::io::stdout()),
+    (Err(err), OutputFormat::Json) => err.write_json_to(&mut std::io::stdout()),
   }
 }
@@ -1,59 +1,405 @@
-use crate::{CliError, output};
+use crate::{
+  CliError, ValidationErrorKind,
+  i18n::{self, Language},
+  output, parsers,
+  utils::fuzzy,
+};
+use chrono::{Datelike, Months, NaiveDate};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, io};
+use sha2::{Digest, Sha256};
+use std::{
+  collections::{BTreeMap, HashMap},
+  io,
+  sync::OnceLock,
+};
 
 #[derive(Debug)]
 pub struct CliResponse {
   content: Option<ResponseContent>,
+  /// Shown after the content, e.g. a spending limit that was just passed
+  warnings: Vec<String>,
 }
 
 impl CliResponse {
   pub fn new(content: ResponseContent) -> Self {
     Self {
       content: Some(content),
+      warnings: Vec::new(),
     }
   }
 
   pub fn success() -> Self {
-    Self { content: None }
+    Self {
+      content: None,
+      warnings: Vec::new(),
+    }
+  }
+
+  pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+    self.warnings.extend(warnings);
+    self
   }
 
   pub fn content(&self) -> Option<&ResponseContent> {
     self.content.as_ref()
   }
+
+  pub fn warnings(&self) -> &[String] {
+    &self.warnings
+  }
+
+  /// Whether this response only confirms that something happened (as opposed
+  /// to carrying data the user asked for). Confirmations are hidden by `--quiet`.
+  pub fn is_confirmation(&self) -> bool {
+    matches!(
+      self.content,
+      None
+        | Some(ResponseContent::Message(_))
+        | Some(ResponseContent::Record { .. })
+        | Some(ResponseContent::Deleted { .. })
+    )
+  }
 }
 
 impl CliResponse {
   /// Write this response to the given writer
-  pub fn write_to(&self, writer: &mut impl std::io::Write) -> io::Result<()> {
-    output::write_response(self, writer)
+  pub fn write_to(
+    &self,
+    options: &output::DisplayOptions,
+    writer: &mut impl std::io::Write,
+  ) -> io::Result<()> {
+    output::write_response(self, options, writer)
+  }
+
+  /// Write this response to the given writer as a JSON document
+  pub fn write_json_to(&self, writer: &mut impl std::io::Write) -> io::Result<()> {
+    output::write_response_json(self, writer)
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Total {
   pub currency: Currency,
+  /// First and last day counted (DD-MM-YYYY) when `total` was given a date
+  /// range; `None` leaves that end open
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub start: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub end: Option<String>,
+  /// The opening balance, or the balance just before `start`
   pub opening_balance: f64,
   pub income_total: f64,
   pub expenses_total: f64,
+  /// Market value of investments, with `total --with-investments`
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub investments: Option<f64>,
+  /// What rounding each expense up would have saved, when
+  /// `savings.round_up` is on. Not part of the balance.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub round_up_savings: Option<f64>,
+  /// This month's budgets, whatever period the totals cover
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub budgets: Vec<BudgetStatus>,
+  /// What `groups` is broken down by, with `total --by`
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub by: Option<GroupBy>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub groups: Vec<GroupTotal>,
+  /// Totals per currency records were entered in; empty when they're all in
+  /// the tracker's currency
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub by_currency: Vec<CurrencyTotal>,
+}
+
+/// Records entered in one currency, from `total` and `describe` once any
+/// record was entered in a currency other than the tracker's
+#[derive(Debug, PartialEq, Serialize)]
+pub struct CurrencyTotal {
+  pub currency: String,
+  pub records: usize,
+  /// In `currency`
+  pub income: f64,
+  pub expenses: f64,
+  /// In the tracker's currency, as counted in the totals
+  pub converted_income: f64,
+  pub converted_expenses: f64,
+  /// The rates these records were converted at; empty for the tracker's own
+  /// currency
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub rates: Vec<RateUsed>,
+}
+
+/// One exchange rate and the days it was used for
+#[derive(Debug, PartialEq, Serialize)]
+pub struct RateUsed {
+  pub rate: f64,
+  /// Earliest and latest rate date, DD-MM-YYYY
+  pub from: String,
+  pub to: String,
+  pub records: usize,
+}
+
+/// What `total --by` breaks the totals down by
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+  Category,
+  Subcategory,
+  /// A record counts under each of its tags, so the groups can add up to
+  /// more than the totals; untagged records are left out
+  Tag,
+}
+
+/// Income and expenses of one category, subcategory or tag, from `total --by`
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GroupTotal {
+  pub name: String,
+  pub income: f64,
+  pub expenses: f64,
+  /// Income minus expenses
+  pub net: f64,
 }
 
 impl Total {
   pub fn total(&self) -> f64 {
-    self.opening_balance + self.income_total - self.expenses_total
+    self.opening_balance + self.income_total - self.expenses_total + self.investments.unwrap_or(0.0)
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct DescribeData {
   pub total_records: usize,
   pub date_range: Option<(String, String)>,
   pub by_category: Vec<(String, usize, f64)>, // (name, count, total)
   pub by_subcategory: Vec<(String, usize, f64)>, // (name, count, total)
+  /// Only records with `entered_by` set are counted here
+  pub by_user: Vec<(String, usize, f64)>, // (name, count, total)
+  /// With `describe --by tag`; a record counts under each of its tags
+  pub by_tag: Vec<(String, usize, f64)>, // (name, count, total)
+  /// Expenses on each day of the week, all seven starting on `period.week_start`
+  pub by_weekday: Vec<(DayOfWeek, usize, f64)>, // (day, count, total)
+  /// See [`Total::by_currency`]
+  pub by_currency: Vec<CurrencyTotal>,
   pub average_transaction: f64,
   pub currency: Currency,
 }
 
+/// What a `fintrack report` covers
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportPeriod {
+  Month,
+  /// A calendar or fiscal year, from `--year`
+  Year,
+  Week,
+}
+
+/// One month of activity, from `fintrack report`, or a year or week with
+/// `--year` and `--week`
+#[derive(Debug, Serialize)]
+pub struct MonthlyReport {
+  /// MM-YYYY for a month, the year (`2024`, or `2024-25` for a fiscal year)
+  /// or the dates a week covers
+  pub month: String,
+  pub period: ReportPeriod,
+  pub currency: Currency,
+  pub records: usize,
+  /// Balance at the start of the month
+  pub opening_balance: f64,
+  pub income: f64,
+  pub expenses: f64,
+  pub closing_balance: f64,
+  /// The closing balance saved by `fintrack close`, when the month is closed
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub snapshot: Option<f64>,
+  pub income_by_subcategory: Vec<(String, f64)>,
+  pub expenses_by_subcategory: Vec<(String, f64)>,
+}
+
+/// Deductible expenses over a year, from `fintrack report --deductions`
+#[derive(Debug, Serialize)]
+pub struct DeductionReport {
+  /// `2024`, or `2024-25` for a fiscal year
+  pub year: String,
+  pub currency: String,
+  pub records: usize,
+  pub total: f64,
+  /// Largest first
+  pub by_subcategory: Vec<(String, f64)>,
+}
+
+/// A week's spending in a few lines, from `fintrack digest`
+#[derive(Debug, Serialize)]
+pub struct WeeklyDigest {
+  /// DD-MM-YYYY; the first day of the week and the last day covered, today
+  /// while the week is still going
+  pub start: String,
+  pub end: String,
+  pub currency: String,
+  pub spent: f64,
+  /// Spent over the same days of the week before
+  pub last_week: f64,
+  /// Expense subcategories, largest first
+  pub top: Vec<(String, f64)>,
+  pub unusual: Vec<UnusualExpense>,
+}
+
+impl WeeklyDigest {
+  /// Change from last week in percent, `None` when nothing was spent then
+  pub fn change(&self) -> Option<f64> {
+    (self.last_week > 0.0).then(|| (self.spent - self.last_week) / self.last_week * 100.0)
+  }
+}
+
+/// An expense well above what its subcategory usually costs
+#[derive(Debug, Serialize)]
+pub struct UnusualExpense {
+  pub id: usize,
+  pub description: String,
+  pub subcategory: String,
+  pub amount: f64,
+  /// The median expense in the subcategory over the months before
+  pub typical: f64,
+}
+
+/// A statement balance checked against the records, from `fintrack reconcile`
+#[derive(Debug, Serialize)]
+pub struct Reconciliation {
+  /// DD-MM-YYYY; the statement's closing date
+  pub date: String,
+  pub currency: String,
+  /// What the records add up to on `date`
+  pub recorded: f64,
+  pub statement: f64,
+  /// The statement less the records; positive when income is missing
+  pub drift: f64,
+  /// The record added to close the gap, if one was
+  pub adjustment: Option<usize>,
+}
+
+/// How one tracker differs from another: what a command run with `--dry-run`
+/// would have changed, or `fintrack diff` against a backup
+#[derive(Debug)]
+pub struct TrackerDiff {
+  pub added: Vec<Record>,
+  /// Each changed record as it was and as it is
+  pub changed: Vec<(Record, Record)>,
+  pub removed: Vec<Record>,
+  pub subcategories_added: Vec<String>,
+  pub subcategories_removed: Vec<String>,
+  /// Old and new names of subcategories that kept their ID
+  pub subcategories_renamed: Vec<(String, String)>,
+  /// Whether anything besides the records and subcategories changed too,
+  /// such as budgets or bills
+  pub other_changes: bool,
+  pub before: TrackerData,
+  pub after: TrackerData,
+}
+
+impl TrackerDiff {
+  pub fn between(before: TrackerData, after: TrackerData) -> Self {
+    let as_json = |record: &Record| serde_json::to_value(record).ok();
+    let old: HashMap<usize, &Record> = before.records.iter().map(|r| (r.id, r)).collect();
+    let new: HashMap<usize, &Record> = after.records.iter().map(|r| (r.id, r)).collect();
+
+    let added = after.records.iter().filter(|r| !old.contains_key(&r.id)).cloned().collect();
+    let removed = before.records.iter().filter(|r| !new.contains_key(&r.id)).cloned().collect();
+    let changed = after
+      .records
+      .iter()
+      .filter_map(|r| {
+        let was = old.get(&r.id)?;
+        (as_json(was) != as_json(r)).then(|| ((*was).clone(), r.clone()))
+      })
+      .collect();
+
+    let mut subcategories_added = Vec::new();
+    let mut subcategories_renamed = Vec::new();
+    for (id, name) in &after.subcategories_by_id {
+      match before.subcategories_by_id.get(id) {
+        None => subcategories_added.push(name.clone()),
+        Some(old) if old != name => subcategories_renamed.push((old.clone(), name.clone())),
+        Some(_) => {}
+      }
+    }
+    let mut subcategories_removed: Vec<String> = before
+      .subcategories_by_id
+      .iter()
+      .filter(|(id, _)| !after.subcategories_by_id.contains_key(id))
+      .map(|(_, name)| name.clone())
+      .collect();
+    subcategories_added.sort();
+    subcategories_removed.sort();
+    subcategories_renamed.sort();
+
+    // Everything but the records, subcategories and the bookkeeping that
+    // follows them. Tags come and go with the records that carry them.
+    let rest = |tracker_data: &TrackerData| {
+      let mut value = serde_json::to_value(tracker_data).ok()?;
+      let fields = value.as_object_mut()?;
+      for field in [
+        "records",
+        "history",
+        "last_modified",
+        "next_record_id",
+        "subcategories_by_id",
+        "subcategories_by_name",
+        "next_subcategory_id",
+        "tags",
+      ] {
+        fields.remove(field);
+      }
+      Some(value)
+    };
+    let other_changes = rest(&before) != rest(&after);
+
+    TrackerDiff {
+      added,
+      changed,
+      removed,
+      subcategories_added,
+      subcategories_removed,
+      subcategories_renamed,
+      other_changes,
+      before,
+      after,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.added.is_empty()
+      && self.changed.is_empty()
+      && self.removed.is_empty()
+      && self.subcategories_added.is_empty()
+      && self.subcategories_removed.is_empty()
+      && self.subcategories_renamed.is_empty()
+      && !self.other_changes
+  }
+}
+
+/// What a project cost, from `fintrack project list` and `project status`
+#[derive(Debug, Serialize)]
+pub struct ProjectSummary {
+  pub name: String,
+  pub records: usize,
+  /// Expenses tagged with the project
+  pub spent: f64,
+  /// Income tagged with the project, e.g. refunds or contributions
+  pub income: f64,
+  /// Dates of the first and last records, DD-MM-YYYY
+  pub start: Option<String>,
+  pub end: Option<String>,
+  /// Expenses by subcategory, largest first
+  pub by_subcategory: Vec<(String, f64)>,
+}
+
+impl MonthlyReport {
+  pub fn net(&self) -> f64 {
+    self.income - self.expenses
+  }
+}
+
 #[derive(Debug)]
 pub enum ResponseContent {
   Message(String),
@@ -63,16 +409,118 @@ pub enum ResponseContent {
     is_update: bool,
   },
   List { records: Vec<Record>, tracker_data: TrackerData },
-  TrackerData(TrackerData),
+  /// Records matching `fintrack search`, best match first
+  Search {
+    query: String,
+    records: Vec<Record>,
+    tracker_data: TrackerData,
+  },
+  /// Records were removed; carries the updated tracker for the new balance
+  Deleted {
+    count: usize,
+    tracker_data: TrackerData,
+  },
+  /// The raw tracker from `fintrack dump`
+  TrackerData {
+    tracker_data: TrackerData,
+    part: DumpPart,
+    /// One line instead of indented JSON
+    compact: bool,
+  },
+  /// Tags with how many records carry each, from `fintrack tag list`
+  Tags(Vec<(String, usize)>),
+  /// The tracker's JSON Schema, from `fintrack schema`
+  Schema(serde_json::Value),
   Total(Total),
   Categories(Vec<(usize, String)>),
   Subcategories(Vec<(usize, String)>),
   Describe(DescribeData),
+  Report(MonthlyReport),
+  Deductions(DeductionReport),
+  /// A short summary of the week, from `fintrack digest`
+  Digest(WeeklyDigest),
+  /// A statement balance against the records, from `fintrack reconcile`
+  Reconciliation(Reconciliation),
+  /// What a command would have changed, from `--dry-run`
+  DryRun(Box<TrackerDiff>),
+  /// How the tracker differs from another file, from `fintrack diff`
+  Diff {
+    source: String,
+    /// Whether `diff` goes from the tracker to `source` rather than the other way
+    reverse: bool,
+    diff: Box<TrackerDiff>,
+  },
+  /// Scheduled records, from `fintrack schedule list`
+  Schedules {
+    schedules: Vec<Schedule>,
+    currency: String,
+  },
+  /// Every project, from `fintrack project list`
+  Projects {
+    projects: Vec<ProjectSummary>,
+    currency: String,
+  },
+  /// One project in detail, from `fintrack project status`
+  Project {
+    project: ProjectSummary,
+    currency: String,
+  },
+  /// Investment holdings, from `fintrack invest value`
+  Holdings {
+    holdings: Vec<Holding>,
+    currency: String,
+  },
+  /// Upcoming and overdue bills, from `fintrack bills`
+  Bills {
+    bills: Vec<BillStatus>,
+    currency: String,
+  },
+  /// Subscriptions by next renewal, from `fintrack subscriptions list`
+  Subscriptions {
+    subscriptions: Vec<SubscriptionStatus>,
+    currency: String,
+  },
+  /// The outcome of `fintrack merge`
+  Merge(MergeSummary),
+  /// Tokens `fintrack serve` accepts, from `fintrack serve token list`
+  Tokens {
+    tokens: Vec<crate::utils::tokens::ApiToken>,
+  },
+  /// Every version of one record, from `fintrack history`
+  History {
+    id: usize,
+    versions: Vec<HistoryEntry>,
+    tracker_data: TrackerData,
+  },
+  /// Today's summary, from `fintrack status`
+  Status {
+    summary: StatusSummary,
+    /// One line for a shell prompt
+    short: bool,
+  },
+  /// This month's budgets, from `fintrack budget list`
+  Budgets {
+    /// MM-YYYY
+    month: String,
+    budgets: Vec<BudgetStatus>,
+    currency: String,
+  },
+  /// Outstanding loans per person, from `fintrack owed list`
+  Owed {
+    balances: Vec<OwedBalance>,
+    currency: String,
+  },
+  /// One setting from `fintrack config get`; `None` if it has no value
+  ConfigValue { key: String, value: Option<String> },
+  /// `(key, value)` pairs from `fintrack config list`
+  Settings(Vec<(String, String)>),
 }
 
 pub type CliResult = Result<CliResponse, CliError>;
 
-#[derive(clap::ValueEnum, Clone, Debug, PartialEq, strum::Display, strum::EnumString)]
+#[derive(
+  clap::ValueEnum, Clone, Debug, PartialEq, Serialize, strum::Display, strum::EnumString,
+)]
 #[strum(serialize_all = "UPPERCASE", ascii_case_insensitive)]
 pub enum Currency {
   NGN,
@@ -84,6 +532,16 @@ pub enum Currency {
   JPY,
 }
 
+impl Currency {
+  /// Decimal places amounts in this currency are kept to, as banks report them
+  pub fn decimals(&self) -> u32 {
+    match self {
+      Currency::JPY => 0,
+      _ => 2,
+    }
+  }
+}
+
 #[derive(clap::ValueEnum, Clone, Debug, PartialEq, strum::Display, strum::EnumString)]
 #[strum(serialize_all = "lowercase", ascii_case_insensitive)]
 pub enum Category {
@@ -97,9 +555,329 @@ pub enum ExportFileType {
   JSON,
   PDF,
   CSV,
+  /// Google Sheets, set up in the `[gsheet]` config section
+  #[value(name = "gsheet")]
+  GSheet,
+}
+
+/// Border style used when rendering record tables
+#[derive(
+  clap::ValueEnum,
+  Clone,
+  Copy,
+  Debug,
+  Default,
+  PartialEq,
+  Serialize,
+  Deserialize,
+  strum::Display,
+  strum::EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum TableStyle {
+  /// Box-drawing borders
+  #[default]
+  Modern,
+  /// Plain ASCII borders for terminals that can't render box-drawing characters
+  Ascii,
+  /// GitHub-flavored markdown, for pasting into docs
+  Markdown,
+  /// Minimal column separators without an outer border
+  Compact,
+}
+
+/// A month, for `period.fiscal_year_start`
+#[derive(
+  Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, strum::Display, strum::EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum MonthOfYear {
+  #[default]
+  January,
+  February,
+  March,
+  April,
+  May,
+  June,
+  July,
+  August,
+  September,
+  October,
+  November,
+  December,
+}
+
+impl MonthOfYear {
+  /// 1 for January
+  pub fn number(self) -> u32 {
+    self as u32 + 1
+  }
+}
+
+/// A day of the week, for `period.week_start` and the weekday breakdown in `describe`
+#[derive(
+  Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, strum::Display, strum::EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum DayOfWeek {
+  #[default]
+  Monday,
+  Tuesday,
+  Wednesday,
+  Thursday,
+  Friday,
+  Saturday,
+  Sunday,
+}
+
+impl DayOfWeek {
+  pub fn from_weekday(weekday: chrono::Weekday) -> Self {
+    match weekday {
+      chrono::Weekday::Mon => DayOfWeek::Monday,
+      chrono::Weekday::Tue => DayOfWeek::Tuesday,
+      chrono::Weekday::Wed => DayOfWeek::Wednesday,
+      chrono::Weekday::Thu => DayOfWeek::Thursday,
+      chrono::Weekday::Fri => DayOfWeek::Friday,
+      chrono::Weekday::Sat => DayOfWeek::Saturday,
+      chrono::Weekday::Sun => DayOfWeek::Sunday,
+    }
+  }
+
+  pub fn is_weekend(self) -> bool {
+    matches!(self, DayOfWeek::Saturday | DayOfWeek::Sunday)
+  }
+
+  pub fn weekday(self) -> chrono::Weekday {
+    match self {
+      DayOfWeek::Monday => chrono::Weekday::Mon,
+      DayOfWeek::Tuesday => chrono::Weekday::Tue,
+      DayOfWeek::Wednesday => chrono::Weekday::Wed,
+      DayOfWeek::Thursday => chrono::Weekday::Thu,
+      DayOfWeek::Friday => chrono::Weekday::Fri,
+      DayOfWeek::Saturday => chrono::Weekday::Sat,
+      DayOfWeek::Sunday => chrono::Weekday::Sun,
+    }
+  }
+}
+
+/// How dates are rendered in tables and reports. Records are always stored as
+/// DD-MM-YYYY; this only changes what is displayed.
+#[derive(
+  Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, strum::Display, strum::EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum DateFormat {
+  /// 15-01-2025, the storage format
+  #[default]
+  #[serde(rename = "dd-mm-yyyy")]
+  #[strum(serialize = "dd-mm-yyyy")]
+  DdMmYyyy,
+  /// 2025-01-15 (ISO 8601)
+  Iso,
+  /// Jan 15 2025
+  Short,
+  /// The usual numeric format for the current language, e.g. 01/15/2025 in
+  /// English and 15/01/2025 in French or Spanish
+  Locale,
+}
+
+impl DateFormat {
+  /// Render a stored DD-MM-YYYY date. Dates that don't parse are shown as-is.
+  pub fn format(self, date: &str) -> String {
+    let pattern = match self {
+      DateFormat::DdMmYyyy => return date.to_string(),
+      DateFormat::Iso => "%Y-%m-%d",
+      DateFormat::Short => "%b %d %Y",
+      DateFormat::Locale => match i18n::language() {
+        Language::En => "%m/%d/%Y",
+        Language::Fr | Language::Es => "%d/%m/%Y",
+      },
+    };
+
+    match parsers::parse_stored_date(date) {
+      Some(parsed) => parsed.format(pattern).to_string(),
+      None => date.to_string(),
+    }
+  }
+}
+
+/// What happens to an amount typed with more decimals than the currency has
+#[derive(
+  Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, strum::Display, strum::EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum RoundingMode {
+  /// Halves go away from zero: 0.125 becomes 0.13
+  #[default]
+  HalfUp,
+  /// Halves go to the even neighbour (banker's rounding): 0.125 becomes 0.12
+  HalfEven,
+  /// Drop the extra decimals
+  Down,
+  /// Round away from zero whenever there are extra decimals
+  Up,
+  /// Refuse the amount
+  Reject,
+}
+
+impl RoundingMode {
+  /// `amount` rounded to `decimals` places, or `None` for [`RoundingMode::Reject`]
+  /// when it has more
+  pub fn round(self, amount: f64, decimals: u32) -> Option<f64> {
+    let factor = 10f64.powi(decimals as i32);
+    let scaled = amount * factor;
+    // Binary floats turn 0.285 * 100 into 28.499999999999996; treat anything
+    // this close as exact so halves are recognised
+    let epsilon = 1e-9 * scaled.abs().max(1.0);
+    if (scaled - scaled.round()).abs() < epsilon {
+      return Some(scaled.round() / factor);
+    }
+
+    let is_half = ((scaled - scaled.trunc()).abs() - 0.5).abs() < epsilon;
+    let rounded = match self {
+      RoundingMode::HalfUp if is_half => scaled.trunc() + scaled.signum(),
+      RoundingMode::HalfEven if is_half => {
+        let toward_zero = scaled.trunc();
+        match toward_zero % 2.0 == 0.0 {
+          true => toward_zero,
+          false => toward_zero + scaled.signum(),
+        }
+      }
+      RoundingMode::HalfUp | RoundingMode::HalfEven => scaled.round(),
+      RoundingMode::Down => scaled.trunc(),
+      RoundingMode::Up => scaled.trunc() + scaled.signum(),
+      RoundingMode::Reject => return None,
+    };
+    Some(rounded / factor)
+  }
+}
+
+/// How typed amounts group thousands and mark decimals, from `input.locale`
+#[derive(
+  Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, strum::Display, strum::EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum NumberLocale {
+  /// 1,234.56
+  #[default]
+  En,
+  /// 1.234,56
+  De,
+  /// 1 234,56, with a plain or non-breaking space
+  Fr,
+}
+
+impl NumberLocale {
+  /// An example amount written this way, for error messages
+  pub fn example(self) -> &'static str {
+    match self {
+      NumberLocale::En => "1,234.56",
+      NumberLocale::De => "1.234,56",
+      NumberLocale::Fr => "1 234,56",
+    }
+  }
+
+  /// Read `s` as an amount written this way. Thousands separators are
+  /// optional but must split the whole number into groups of three, so
+  /// "1.50" isn't taken as 150 in German style.
+  pub fn parse(self, s: &str) -> Option<f64> {
+    let (separators, decimal): (&[char], char) = match self {
+      NumberLocale::En => (&[','], '.'),
+      NumberLocale::De => (&['.'], ','),
+      // Banking apps copy the thin and non-breaking spaces French uses
+      NumberLocale::Fr => (&[' ', '\u{a0}', '\u{202f}'], ','),
+    };
+
+    let s = s.trim();
+    let (sign, unsigned) = match s.strip_prefix('-') {
+      Some(rest) => ("-", rest),
+      None => ("", s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (whole, fraction) = match unsigned.split_once(decimal) {
+      Some((whole, fraction)) => (whole, Some(fraction)),
+      None => (unsigned, None),
+    };
+
+    let digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+    let groups: Vec<&str> = whole.split(separators).collect();
+    let grouped = match groups.split_first() {
+      Some((first, [])) => first.is_empty() || digits(first),
+      Some((first, rest)) => {
+        digits(first) && first.len() <= 3 && rest.iter().all(|group| digits(group) && group.len() == 3)
+      }
+      None => false,
+    };
+    if !grouped || fraction.is_some_and(|fraction| !digits(fraction)) || (whole.is_empty() && fraction.is_none()) {
+      return None;
+    }
+
+    format!("{}{}.{}", sign, groups.concat(), fraction.unwrap_or("0"))
+      .parse()
+      .ok()
+  }
+}
+
+/// Which part of the tracker `fintrack dump` prints
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DumpPart {
+  #[default]
+  All,
+  /// Only the `records` array
+  Records,
+  /// Everything but the records and their history
+  Meta,
+}
+
+/// What `fintrack merge` does with a record that matches one already here
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum DuplicateRule {
+  /// Leave it out
+  #[default]
+  Skip,
+  /// Add it anyway
+  Keep,
+}
+
+/// Whose budget `fintrack merge --budgets` keeps when both trackers have one
+/// for a subcategory
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum BudgetRule {
+  /// This tracker's
+  #[default]
+  Ours,
+  /// The other tracker's
+  Theirs,
+}
+
+/// What `fintrack merge` did, or would do with `--dry-run`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MergeSummary {
+  /// The tracker file merged in
+  pub source: String,
+  pub dry_run: bool,
+  pub records_added: usize,
+  pub duplicates_skipped: usize,
+  /// Subcategories created for records that needed them
+  pub subcategories_added: Vec<String>,
+  pub budgets_added: Vec<String>,
+  pub budgets_replaced: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+/// How responses and errors are rendered to the terminal
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+  #[default]
+  Text,
+  Json,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 pub struct Record {
   pub id: usize,
   pub category: usize,    // ID from categories map
@@ -107,9 +885,544 @@ pub struct Record {
   pub description: String,
   pub amount: f64,  // Always positive; sign determined by category
   pub date: String, // Format: DD-MM-YYYY
+  /// Who entered the record, for trackers shared between several people
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub entered_by: Option<String>,
+  /// Counts towards `report --deductions`
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub deductible: bool,
+  /// A trip or event the record belongs to, for `fintrack project`
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub project: Option<String>,
+  /// What was paid or received, when it wasn't in the tracker's currency.
+  /// `amount` is then the converted figure.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub foreign: Option<ForeignAmount>,
+  /// Labels from `add --tag`, for grouping records across subcategories.
+  /// Each is one of the tracker's `tags`.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub tags: Vec<String>,
+  /// `date`, parsed the first time it's needed
+  #[serde(skip)]
+  pub(crate) parsed_date: OnceLock<Option<NaiveDate>>,
+}
+
+/// An amount in another currency, from `add --currency`, with the rate it was
+/// converted at
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ForeignAmount {
+  pub currency: String,
+  pub amount: f64,
+  /// Units of the tracker's currency per unit of `currency`
+  pub rate: f64,
+  /// The day the rate is for, DD-MM-YYYY
+  pub rate_date: String,
+}
+
+impl Record {
+  /// The record's date, or `None` if the stored string isn't a valid date
+  pub fn naive_date(&self) -> Option<NaiveDate> {
+    *self
+      .parsed_date
+      .get_or_init(|| parsers::parse_stored_date(&self.date))
+  }
+
+  pub fn set_date(&mut self, date: NaiveDate) {
+    self.date = date.format(parsers::STORAGE_DATE_FORMAT).to_string();
+    self.parsed_date = OnceLock::from(Some(date));
+  }
+
+  /// Records are kept in this order: by date, then by ID. Records whose date
+  /// doesn't parse come first.
+  pub fn sort_key(&self) -> (Option<NaiveDate>, usize) {
+    (self.naive_date(), self.id)
+  }
+}
+
+/// A loan to or from someone, or a repayment of one
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct OwedEntry {
+  pub id: usize,
+  /// As first typed; later entries match it case-insensitively
+  pub person: String,
+  /// Positive when the person owes you more afterwards, negative when less
+  /// (or when you borrowed from them)
+  pub amount: f64,
+  pub date: String, // Format: DD-MM-YYYY
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub due: Option<String>, // Format: DD-MM-YYYY
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub description: String,
+  /// Entered by `owed settle` rather than `owed add`
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub settlement: bool,
+  /// The shared expense this is the person's part of, from `add --split-with`
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub record: Option<usize>,
+}
+
+/// How much of a shared expense someone takes on, from `add --split-with`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Share {
+  /// `alice:50%`
+  Percent(f64),
+  /// `alice:20`
+  Amount(f64),
+  /// `alice`: an even part of whatever the others don't cover, the same as yours
+  Equal,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Split {
+  pub person: String,
+  pub share: Share,
+}
+
+/// A record typed as one line of text, from `fintrack quick`
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuickEntry {
+  pub category: Category,
+  pub amount: f64,
+  pub date: NaiveDate,
+  /// From `#name` words
+  pub tags: Vec<String>,
+  /// Every word that wasn't anything else
+  pub description: String,
+}
+
+/// What one person owes, from `fintrack owed list`
+#[derive(Debug, Serialize)]
+pub struct OwedBalance {
+  pub person: String,
+  /// Positive when they owe you, negative when you owe them
+  pub balance: f64,
+  /// The earliest due date among their loans, while anything is outstanding
+  pub due: Option<String>,
+  pub overdue: bool,
+}
+
+/// A purchase of `quantity` units of `symbol` at `price` each
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct InvestmentLot {
+  pub id: usize,
+  /// Upper case, e.g. AAPL
+  pub symbol: String,
+  pub quantity: f64,
+  pub price: f64,
+  pub date: String, // Format: DD-MM-YYYY
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+pub struct Investments {
+  #[serde(default)]
+  pub lots: Vec<InvestmentLot>,
+  /// The latest price given or fetched for each symbol
+  #[serde(default)]
+  pub prices: BTreeMap<String, f64>,
+}
+
+impl Investments {
+  pub fn is_empty(&self) -> bool {
+    self.lots.is_empty() && self.prices.is_empty()
+  }
+
+  /// One entry per symbol held, in symbol order
+  pub fn holdings(&self) -> Vec<Holding> {
+    let mut by_symbol: BTreeMap<&str, (f64, f64)> = BTreeMap::new();
+    for lot in &self.lots {
+      let (quantity, cost) = by_symbol.entry(&lot.symbol).or_default();
+      *quantity += lot.quantity;
+      *cost += lot.quantity * lot.price;
+    }
+
+    by_symbol
+      .into_iter()
+      .map(|(symbol, (quantity, cost_basis))| {
+        let price = self.prices.get(symbol).copied();
+        // Without a price, count the holding at what it cost
+        let market_value = price.map_or(cost_basis, |price| price * quantity);
+        Holding {
+          symbol: symbol.to_string(),
+          quantity,
+          cost_basis,
+          price,
+          market_value,
+          unrealized_gain: market_value - cost_basis,
+        }
+      })
+      .collect()
+  }
+
+  pub fn market_value(&self) -> f64 {
+    self.holdings().iter().map(|h| h.market_value).sum()
+  }
+}
+
+/// Everything held of one symbol, from `fintrack invest value`
+#[derive(Debug, Serialize)]
+pub struct Holding {
+  pub symbol: String,
+  pub quantity: f64,
+  /// What the units cost in total
+  pub cost_basis: f64,
+  /// `None` until a price is given or fetched
+  pub price: Option<f64>,
+  pub market_value: f64,
+  pub unrealized_gain: f64,
+}
+
+/// How often a bill or scheduled record comes round
+#[derive(
+  clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, strum::Display,
+  JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Frequency {
+  Day,
+  Week,
+  #[default]
+  Month,
+  Year,
+}
+
+impl Frequency {
+  /// The occurrence after `date`. Monthly and yearly ones fall on `day` of the
+  /// month, or on the month's last day when it's shorter.
+  pub fn after(self, date: NaiveDate, day: u32) -> NaiveDate {
+    match self {
+      Frequency::Day => date + chrono::Days::new(1),
+      Frequency::Week => date + chrono::Days::new(7),
+      Frequency::Month if date.month() == 12 => day_in_month(date.year() + 1, 1, day),
+      Frequency::Month => day_in_month(date.year(), date.month() + 1, day),
+      Frequency::Year => day_in_month(date.year() + 1, date.month(), day),
+    }
+  }
+}
+
+/// A recurring bill, from `fintrack bill add`
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct Bill {
+  /// As first typed; `bill pay` matches it case-insensitively
+  pub name: String,
+  pub amount: f64,
+  /// Subcategory of the expense `bill pay` records
+  pub subcategory: String,
+  #[serde(default)]
+  pub every: Frequency,
+  pub due: String, // Format: DD-MM-YYYY
+  /// The day of the month it falls on, so a bill due on the 31st goes back to
+  /// the 31st after a shorter month. Unused for daily and weekly bills.
+  pub day: u32,
+}
+
+impl Bill {
+  pub fn due_date(&self) -> Option<NaiveDate> {
+    parsers::parse_stored_date(&self.due)
+  }
+
+  /// Move the due date on by one period
+  pub fn advance(&mut self) {
+    if let Some(due) = self.due_date() {
+      let next = self.every.after(due, self.day);
+      self.due = next.format(parsers::STORAGE_DATE_FORMAT).to_string();
+    }
+  }
+}
+
+/// A service paid for on repeat, from `fintrack subscriptions add`
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct Subscription {
+  /// As first typed; expenses whose description contains it are linked
+  pub name: String,
+  pub amount: f64,
+  #[serde(default)]
+  pub every: Frequency,
+  /// The first renewal after it was added, DD-MM-YYYY; later ones follow
+  /// from `every`
+  pub renews: String,
+  /// The day of the month it renews on; see [`Bill::day`]
+  pub day: u32,
+  /// Last day to cancel it, e.g. before a free trial ends, DD-MM-YYYY
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub cancel_by: Option<String>,
+  /// IDs of the expenses that paid for it
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub records: Vec<usize>,
+}
+
+impl Subscription {
+  /// Whether `record` looks like a payment for it: an expense with the
+  /// subscription's name somewhere in its description
+  pub fn matches(&self, record: &Record, expenses_id: usize) -> bool {
+    record.category == expenses_id
+      && record
+        .description
+        .to_lowercase()
+        .contains(&self.name.to_lowercase())
+  }
+
+  /// The first renewal on or after `today`
+  pub fn next_renewal(&self, today: NaiveDate) -> Option<NaiveDate> {
+    let mut renews = parsers::parse_stored_date(&self.renews)?;
+    while renews < today {
+      renews = self.every.after(renews, self.day);
+    }
+    Some(renews)
+  }
+
+  /// What it costs in an average month
+  pub fn monthly_cost(&self) -> f64 {
+    match self.every {
+      Frequency::Day => self.amount * 365.0 / 12.0,
+      Frequency::Week => self.amount * 52.0 / 12.0,
+      Frequency::Month => self.amount,
+      Frequency::Year => self.amount / 12.0,
+    }
+  }
+}
+
+/// A record to add on a schedule, from `fintrack schedule add`
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct Schedule {
+  pub id: usize,
+  /// `income` or `expenses`
+  pub category: String,
+  pub subcategory: String,
+  pub amount: f64,
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub description: String,
+  #[serde(default)]
+  pub every: Frequency,
+  /// When the next record is due, DD-MM-YYYY
+  pub next: String,
+  /// The day of the month it falls on; see [`Bill::day`]
+  pub day: u32,
+}
+
+impl Schedule {
+  pub fn next_date(&self) -> Option<NaiveDate> {
+    parsers::parse_stored_date(&self.next)
+  }
+
+  /// Move on to the following occurrence
+  pub fn advance(&mut self) {
+    if let Some(next) = self.next_date() {
+      let next = self.every.after(next, self.day);
+      self.next = next.format(parsers::STORAGE_DATE_FORMAT).to_string();
+    }
+  }
+}
+
+/// A monthly spending limit on one subcategory, from `fintrack budget set`
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct Budget {
+  /// Subcategory ID, so the budget follows a rename
+  pub subcategory: usize,
+  pub amount: f64,
+  /// Carry each month's unspent budget, or overspend, into the next
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub rollover: bool,
+  /// The first of the month rollover was turned on (DD-MM-YYYY); earlier
+  /// months are never carried over
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub since: Option<String>,
+  /// Percent of the month's budget spent that's worth a warning; `None`
+  /// never warns before `error_at`
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub warn_at: Option<f64>,
+  /// Percent spent that counts as over budget; `None` is 100%
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub error_at: Option<f64>,
+}
+
+impl Budget {
+  fn since_date(&self) -> Option<NaiveDate> {
+    self.since.as_deref().and_then(parsers::parse_stored_date)
+  }
+
+  /// How `spent` stands against `available`, by the budget's thresholds
+  fn level(&self, spent: f64, available: f64) -> BudgetLevel {
+    let reached = |percent: f64| spent >= available * percent / 100.0;
+    if reached(self.error_at.unwrap_or(100.0)) {
+      BudgetLevel::Error
+    } else if self.warn_at.is_some_and(reached) {
+      BudgetLevel::Warn
+    } else {
+      BudgetLevel::Ok
+    }
+  }
+}
+
+/// Which of its thresholds a budget has reached
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetLevel {
+  #[default]
+  Ok,
+  /// Past `warn_at`: shown in yellow
+  Warn,
+  /// Past `error_at`, 100% unless set: shown in red
+  Error,
+}
+
+/// How a budget stands for a month, from `fintrack budget list` and `total`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BudgetStatus {
+  pub subcategory: String,
+  pub budget: f64,
+  /// Left over from earlier months with rollover on; negative when they
+  /// were overspent
+  pub carried: f64,
+  /// Expenses in the subcategory so far this month
+  pub spent: f64,
+  /// Negative once the budget is overspent
+  pub remaining: f64,
+  pub level: BudgetLevel,
+}
+
+/// Where things stand today, from `fintrack status`. Cached in `status.json`
+/// so `status --short` can answer without reading the tracker.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatusSummary {
+  /// DD-MM-YYYY; the summary is out of date on any other day
+  pub date: String,
+  pub currency: String,
+  pub balance: f64,
+  pub spent_today: f64,
+  /// Left of all this month's budgets together, if there are any
+  pub budget_remaining: Option<f64>,
+  /// Budgets already overspent this month
+  pub over_budget: usize,
+  /// Spending against `limits.daily` and `limits.weekly`, when they're set
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub limits: Vec<LimitStatus>,
+  /// The next payday, when `payday.day` is set
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub payday: Option<PaydayStatus>,
+}
+
+/// How far off payday is and what the balance allows per day until then
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PaydayStatus {
+  /// DD-MM-YYYY
+  pub date: String,
+  /// 0 on payday itself
+  pub days: i64,
+  /// The balance spread over the days before payday, today included; `None`
+  /// on payday
+  pub per_day: Option<f64>,
+}
+
+/// A record as it was before a change, kept by `update` and `history --revert`
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct RecordVersion {
+  /// When the change that replaced it was made (RFC 3339)
+  pub replaced_at: String,
+  /// Set when the change was `history --revert` to this earlier version
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub reverted_to: Option<usize>,
+  pub record: Record,
+}
+
+/// One version of a record, from `fintrack history`
+#[derive(Debug, Serialize)]
+pub struct HistoryEntry {
+  /// Counted from 1, the oldest version kept
+  pub version: usize,
+  /// When this version replaced the one before (RFC 3339); `None` for the first
+  pub since: Option<String>,
+  /// The version this one was restored from, if it came from a revert
+  pub reverted_to: Option<usize>,
+  pub current: bool,
+  pub record: Record,
+}
+
+/// How much has gone on expenses against a `[limits]` setting
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LimitStatus {
+  pub period: LimitPeriod,
+  pub limit: f64,
+  pub spent: f64,
+}
+
+impl LimitStatus {
+  pub fn is_over(&self) -> bool {
+    self.spent > self.limit
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LimitPeriod {
+  Daily,
+  Weekly,
+}
+
+/// A month locked by `fintrack close`, with the balance it ended on
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct ClosedPeriod {
+  /// MM-YYYY
+  pub month: String,
+  /// Balance at the end of the month when it was closed. Reports for later
+  /// months start from it.
+  pub closing_balance: f64,
+  /// When it was closed, RFC 3339
+  pub closed_at: String,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+impl ClosedPeriod {
+  /// First day of the month
+  pub fn start(&self) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("01-{}", self.month), "%d-%m-%Y").ok()
+  }
+
+  /// Last day of the month
+  pub fn end(&self) -> Option<NaiveDate> {
+    self.start()?.checked_add_months(Months::new(1))?.pred_opt()
+  }
+}
+
+/// `day` of the given month, or its last day when the month is shorter
+pub fn day_in_month(year: i32, month: u32, day: u32) -> NaiveDate {
+  (1..=day)
+    .rev()
+    .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+    .unwrap_or_default()
+}
+
+/// A subscription and when it next renews, from `fintrack subscriptions list`
+#[derive(Debug, Serialize)]
+pub struct SubscriptionStatus {
+  pub name: String,
+  pub amount: f64,
+  pub every: Frequency,
+  /// `amount` spread over an average month
+  pub monthly_cost: f64,
+  pub renews: String,
+  /// Days until it renews
+  pub days: i64,
+  pub cancel_by: Option<String>,
+  /// Days left to cancel; negative once the date has passed
+  pub cancel_days: Option<i64>,
+  /// How many expenses are linked to it
+  pub payments: usize,
+  /// Date of the latest linked expense
+  pub last_paid: Option<String>,
+}
+
+/// A bill and how far off it is, from `fintrack bills`
+#[derive(Debug, Serialize)]
+pub struct BillStatus {
+  pub name: String,
+  pub amount: f64,
+  pub every: Frequency,
+  pub due: String,
+  /// Days until it's due; negative once it's overdue
+  pub days: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 pub struct TrackerData {
   pub version: u32,
   pub currency: String,
@@ -122,13 +1435,132 @@ pub struct TrackerData {
   pub next_subcategory_id: u32,
   pub records: Vec<Record>,
   pub next_record_id: usize,
+  /// Money lent to or borrowed from people, from `fintrack owed`. Kept apart
+  /// from the records, so it never changes the balance.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub owed: Vec<OwedEntry>,
+  /// Holdings from `fintrack invest`, also kept apart from the records
+  #[serde(default, skip_serializing_if = "Investments::is_empty")]
+  pub investments: Investments,
+  /// Recurring bills from `fintrack bill`. Paying one adds an expense record.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub bills: Vec<Bill>,
+  /// Services paid for on repeat, from `fintrack subscriptions`
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub subscriptions: Vec<Subscription>,
+  /// Records `fintrack schedule run` adds when they come due
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub schedules: Vec<Schedule>,
+  /// Monthly spending limits from `fintrack budget set`
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub budgets: Vec<Budget>,
+  /// Months locked by `fintrack close`, oldest first
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub closed_periods: Vec<ClosedPeriod>,
+  /// Earlier versions of changed records, oldest first, for `fintrack history`
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub history: Vec<RecordVersion>,
+  /// Every tag in use, spelled as first typed; see [`TrackerData::tag_name`]
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub tags: Vec<String>,
+  /// SHA-256 of `records` as of the last save; see [`records_checksum`].
+  /// Missing in files from older versions, which are then not checked.
+  #[serde(default, skip_serializing)]
+  pub checksum: Option<String>,
+  /// The reverse of `categories`, built on the first lookup. Categories never
+  /// change once the tracker exists, so it can't go stale.
+  #[serde(skip)]
+  category_names: OnceLock<HashMap<usize, String>>,
 }
 
 impl TrackerData {
+  /// Insert a record at its place in date order
   pub fn push_record(&mut self, record: Record) -> &Self {
-    self.records.push(record);
+    let key = record.sort_key();
+    let index = self.records.partition_point(|r| r.sort_key() < key);
+    self.records.insert(index, record);
+
+    self
+  }
+
+  /// Keep `previous` as the version of a record a change is replacing
+  pub fn keep_version(&mut self, previous: Record, reverted_to: Option<usize>) {
+    self.history.push(RecordVersion {
+      replaced_at: chrono::Utc::now().to_rfc3339(),
+      reverted_to,
+      record: previous,
+    });
+  }
+
+  /// Every version of record `id`, oldest first and ending with the current
+  /// one; empty if there's no such record
+  pub fn record_versions(&self, id: usize) -> Vec<HistoryEntry> {
+    let Some(current) = self.records.iter().find(|r| r.id == id) else {
+      return Vec::new();
+    };
+    let earlier: Vec<&RecordVersion> = self.history.iter().filter(|v| v.record.id == id).collect();
+
+    let mut since = None;
+    let mut reverted_to = None;
+    let mut versions = Vec::with_capacity(earlier.len() + 1);
+    for (index, version) in earlier.iter().enumerate() {
+      versions.push(HistoryEntry {
+        version: index + 1,
+        since: since.take(),
+        reverted_to: reverted_to.take(),
+        current: false,
+        record: version.record.clone(),
+      });
+      // What replaced this version became current at that moment
+      since = Some(version.replaced_at.clone());
+      reverted_to = version.reverted_to;
+    }
+    versions.push(HistoryEntry {
+      version: earlier.len() + 1,
+      since,
+      reverted_to,
+      current: true,
+      record: current.clone(),
+    });
+    versions
+  }
 
+  /// Restore date order, e.g. after loading a file written by an older
+  /// version or after a record's date changed
+  pub fn sort_records(&mut self) {
+    if !self.records.is_sorted_by_key(Record::sort_key) {
+      self.records.sort_by_key(Record::sort_key);
+    }
+  }
+
+  /// The closed month `date` falls in, if it has been closed
+  pub fn closed_period(&self, date: NaiveDate) -> Option<&ClosedPeriod> {
+    let month = date.format("%m-%Y").to_string();
+    self.closed_periods.iter().find(|closed| closed.month == month)
+  }
+
+  /// The end and closing balance of the latest closed month before `date`
+  pub fn snapshot_before(&self, date: NaiveDate) -> Option<(NaiveDate, f64)> {
     self
+      .closed_periods
+      .iter()
+      .filter_map(|closed| Some((closed.end()?, closed.closing_balance)))
+      .filter(|(end, _)| *end < date)
+      .max_by_key(|(end, _)| *end)
+  }
+
+  /// Records dated within `start..=end` (either bound may be open), found by
+  /// binary search. Records without a valid date are never included.
+  pub fn records_between(&self, start: Option<NaiveDate>, end: Option<NaiveDate>) -> &[Record] {
+    let from = self.records.partition_point(|r| match start {
+      Some(start) => r.naive_date() < Some(start),
+      None => r.naive_date().is_none(),
+    });
+    let to = self
+      .records
+      .partition_point(|r| end.is_none_or(|end| r.naive_date() <= Some(end)));
+
+    &self.records[from..to.max(from)]
   }
 
   pub fn category_id(&self, category: &str) -> usize {
@@ -144,13 +1576,409 @@ impl TrackerData {
   }
 
   pub fn category_name(&self, id: usize) -> Option<&String> {
-    self.categories.iter().find(|(_, v)| **v == id).map(|(k, _)| k)
+    self
+      .category_names
+      .get_or_init(|| {
+        self
+          .categories
+          .iter()
+          .map(|(name, &id)| (id, name.clone()))
+          .collect()
+      })
+      .get(&id)
   }
 
   pub fn subcategory_name(&self, id: usize) -> Option<&String> {
     self.subcategories_by_id.get(&id)
   }
 
+  /// The error for a subcategory name that doesn't exist, suggesting the one
+  /// it was most likely a typo of
+  pub fn subcategory_not_found(&self, name: impl Into<String>) -> CliError {
+    let name = name.into();
+    let nearest = fuzzy::closest(&name, self.subcategories_by_id.values().map(String::as_str));
+    CliError::ValidationError(ValidationErrorKind::SubcategoryNotFound {
+      nearest: nearest.map(str::to_string),
+      name,
+    })
+  }
+
+  /// Totals of `records` per currency they were entered in, the tracker's
+  /// own first. Empty when none was entered in another currency.
+  pub fn currency_totals(&self, records: &[Record]) -> Vec<CurrencyTotal> {
+    if records.iter().all(|r| r.foreign.is_none()) {
+      return Vec::new();
+    }
+
+    let income_id = self.category_id("income");
+    let mut totals: BTreeMap<Option<&str>, CurrencyTotal> = BTreeMap::new();
+    for record in records {
+      let foreign = record.foreign.as_ref();
+      let total = totals
+        .entry(foreign.map(|f| f.currency.as_str()))
+        .or_insert_with(|| CurrencyTotal {
+          currency: foreign.map_or_else(|| self.currency.clone(), |f| f.currency.clone()),
+          records: 0,
+          income: 0.0,
+          expenses: 0.0,
+          converted_income: 0.0,
+          converted_expenses: 0.0,
+          rates: Vec::new(),
+        });
+      let original = foreign.map_or(record.amount, |f| f.amount);
+      total.records += 1;
+      if record.category == income_id {
+        total.income += original;
+        total.converted_income += record.amount;
+      } else {
+        total.expenses += original;
+        total.converted_expenses += record.amount;
+      }
+
+      let Some(foreign) = foreign else { continue };
+      let date = parsers::parse_stored_date(&foreign.rate_date);
+      match total.rates.iter_mut().find(|used| used.rate == foreign.rate) {
+        Some(used) => {
+          used.records += 1;
+          if date < parsers::parse_stored_date(&used.from) {
+            used.from = foreign.rate_date.clone();
+          }
+          if date > parsers::parse_stored_date(&used.to) {
+            used.to = foreign.rate_date.clone();
+          }
+        }
+        None => total.rates.push(RateUsed {
+          rate: foreign.rate,
+          from: foreign.rate_date.clone(),
+          to: foreign.rate_date.clone(),
+          records: 1,
+        }),
+      }
+    }
+
+    // `None`, the tracker's currency, sorts first
+    totals.into_values().collect()
+  }
+
+  /// Create a subcategory named `name` as given, returning its new ID. The
+  /// caller checks it doesn't exist yet.
+  pub fn add_subcategory(&mut self, name: &str) -> usize {
+    let id = self.next_subcategory_id as usize;
+    self.subcategories_by_id.insert(id, name.to_string());
+    self.subcategories_by_name.insert(name.to_lowercase(), id);
+    self.next_subcategory_id += 1;
+    id
+  }
+
+  /// Opening balance plus all income minus all expenses
+  pub fn net_balance(&self) -> f64 {
+    let (income, expenses) = self.totals();
+    self.opening_balance + income - expenses
+  }
+
+  /// Opening balance plus income minus expenses dated up to and including `end`
+  pub fn balance_through(&self, end: NaiveDate) -> f64 {
+    let income_id = self.category_id("income");
+    self.records_between(None, Some(end)).iter().fold(self.opening_balance, |balance, record| {
+      if record.category == income_id {
+        balance + record.amount
+      } else {
+        balance - record.amount
+      }
+    })
+  }
+
+  /// The balance at the end of each day with records from `start` to `end`,
+  /// oldest first. With a `start`, the first point is the balance going into
+  /// that day.
+  pub fn running_balance(&self, start: Option<NaiveDate>, end: Option<NaiveDate>) -> Vec<(NaiveDate, f64)> {
+    let income_id = self.category_id("income");
+    let mut points = Vec::new();
+    let mut balance = self.opening_balance;
+    if let Some(start) = start {
+      balance = start.pred_opt().map_or(balance, |day| self.balance_through(day));
+      points.push((start, balance));
+    }
+
+    for record in self.records_between(start, end) {
+      let Some(date) = record.naive_date() else {
+        continue;
+      };
+      balance += match record.category == income_id {
+        true => record.amount,
+        false => -record.amount,
+      };
+      match points.last_mut() {
+        Some((day, last)) if *day == date => *last = balance,
+        _ => points.push((date, balance)),
+      }
+    }
+    points
+  }
+
+  /// Every budget against the expenses dated from `start` to `end`, by
+  /// subcategory name
+  pub fn budget_statuses(&self, start: NaiveDate, end: NaiveDate) -> Vec<BudgetStatus> {
+    let expenses_id = self.category_id("expenses");
+    let records = self.records_between(Some(start), Some(end));
+    let mut statuses: Vec<BudgetStatus> = self
+      .budgets
+      .iter()
+      .map(|budget| {
+        let spent: f64 = records
+          .iter()
+          .filter(|r| r.category == expenses_id && r.subcategory == budget.subcategory)
+          .map(|r| r.amount)
+          .sum();
+        let carried = if budget.rollover { self.budget_carried(budget, start) } else { 0.0 };
+        BudgetStatus {
+          subcategory: self
+            .subcategory_name(budget.subcategory)
+            .cloned()
+            .unwrap_or_else(|| "Unknown".to_string()),
+          budget: budget.amount,
+          carried,
+          spent,
+          remaining: budget.amount + carried - spent,
+          level: budget.level(spent, budget.amount + carried),
+        }
+      })
+      .collect();
+    statuses.sort_by_key(|status| status.subcategory.to_lowercase());
+    statuses
+  }
+
+  /// What a rollover budget brings into the month starting `start`: the
+  /// budget less the expenses of every month from the one it started in.
+  /// Earlier months count at today's amount.
+  fn budget_carried(&self, budget: &Budget, start: NaiveDate) -> f64 {
+    let Some(since) = budget.since_date().filter(|since| *since < start) else {
+      return 0.0;
+    };
+    let expenses_id = self.category_id("expenses");
+    let spent: f64 = self
+      .records_between(Some(since), start.pred_opt())
+      .iter()
+      .filter(|r| r.category == expenses_id && r.subcategory == budget.subcategory)
+      .map(|r| r.amount)
+      .sum();
+    let months = (start.year() - since.year()) * 12 + start.month() as i32 - since.month() as i32;
+    budget.amount * months as f64 - spent
+  }
+
+  /// Every bill with how many days are left until it's due, soonest first
+  pub fn bill_statuses(&self, today: NaiveDate) -> Vec<BillStatus> {
+    let mut statuses: Vec<(Option<NaiveDate>, BillStatus)> = self
+      .bills
+      .iter()
+      .map(|bill| {
+        let due = bill.due_date();
+        let status = BillStatus {
+          name: bill.name.clone(),
+          amount: bill.amount,
+          every: bill.every,
+          due: bill.due.clone(),
+          days: due.map_or(0, |due| (due - today).num_days()),
+        };
+        (due, status)
+      })
+      .collect();
+    statuses.sort_by_key(|(due, _)| *due);
+    statuses.into_iter().map(|(_, status)| status).collect()
+  }
+
+  /// Link `record` to every subscription it looks like a payment for
+  pub fn link_subscriptions(&mut self, record: &Record) {
+    let expenses_id = self.category_id("expenses");
+    for subscription in &mut self.subscriptions {
+      if subscription.matches(record, expenses_id) && !subscription.records.contains(&record.id) {
+        subscription.records.push(record.id);
+      }
+    }
+  }
+
+  /// Every subscription with its next renewal, soonest first
+  pub fn subscription_statuses(&self, today: NaiveDate) -> Vec<SubscriptionStatus> {
+    let mut statuses: Vec<(Option<NaiveDate>, SubscriptionStatus)> = self
+      .subscriptions
+      .iter()
+      .map(|subscription| {
+        let renews = subscription.next_renewal(today);
+        // Records are in date order, and deleted ones are skipped
+        let payments: Vec<&Record> = self
+          .records
+          .iter()
+          .filter(|r| subscription.records.contains(&r.id))
+          .collect();
+        let cancel_days = subscription
+          .cancel_by
+          .as_deref()
+          .and_then(parsers::parse_stored_date)
+          .map(|date| (date - today).num_days());
+        let status = SubscriptionStatus {
+          name: subscription.name.clone(),
+          amount: subscription.amount,
+          every: subscription.every,
+          monthly_cost: subscription.monthly_cost(),
+          renews: renews.map_or_else(
+            || subscription.renews.clone(),
+            |date| date.format(parsers::STORAGE_DATE_FORMAT).to_string(),
+          ),
+          days: renews.map_or(0, |date| (date - today).num_days()),
+          cancel_by: subscription.cancel_by.clone(),
+          cancel_days,
+          payments: payments.len(),
+          last_paid: payments.last().map(|r| r.date.clone()),
+        };
+        (renews, status)
+      })
+      .collect();
+    statuses.sort_by_key(|(renews, _)| *renews);
+    statuses.into_iter().map(|(_, status)| status).collect()
+  }
+
+  /// `project` as first entered on a record, or as given if it's new
+  pub fn project_name(&self, project: &str) -> String {
+    self
+      .records
+      .iter()
+      .filter_map(|record| record.project.as_ref())
+      .find(|name| name.eq_ignore_ascii_case(project))
+      .map_or_else(|| project.to_string(), |name| name.clone())
+  }
+
+  /// The tracker's spelling of `tag`, adding it to `tags` when it's new.
+  /// Tags are matched case-insensitively, like projects.
+  pub fn tag_name(&mut self, tag: &str) -> String {
+    match self.tags.iter().find(|name| name.eq_ignore_ascii_case(tag)) {
+      Some(name) => name.clone(),
+      None => {
+        self.tags.push(tag.to_string());
+        tag.to_string()
+      }
+    }
+  }
+
+  /// Every tag with the number of records carrying it, most used first.
+  /// Tags only found on records (e.g. after a hand edit) are included too.
+  pub fn tag_counts(&self) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = self.tags.iter().map(|tag| (tag.clone(), 0)).collect();
+    for tag in self.records.iter().flat_map(|record| &record.tags) {
+      match counts.iter_mut().find(|(name, _)| name.eq_ignore_ascii_case(tag)) {
+        Some((_, count)) => *count += 1,
+        None => counts.push((tag.clone(), 1)),
+      }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.to_lowercase().cmp(&b.0.to_lowercase())));
+    counts
+  }
+
+  /// Totals for every project, in the order they started
+  pub fn project_summaries(&self) -> Vec<ProjectSummary> {
+    let income_id = self.category_id("income");
+    let mut summaries: Vec<(ProjectSummary, HashMap<usize, f64>)> = Vec::new();
+
+    // Records are in date order, so the first and last seen bound each project
+    for record in &self.records {
+      let Some(project) = &record.project else {
+        continue;
+      };
+      let index = match summaries.iter().position(|(s, _)| s.name.eq_ignore_ascii_case(project)) {
+        Some(index) => index,
+        None => {
+          let summary = ProjectSummary {
+            name: project.clone(),
+            records: 0,
+            spent: 0.0,
+            income: 0.0,
+            start: Some(record.date.clone()),
+            end: None,
+            by_subcategory: Vec::new(),
+          };
+          summaries.push((summary, HashMap::new()));
+          summaries.len() - 1
+        }
+      };
+      let (summary, by_subcategory) = &mut summaries[index];
+      summary.records += 1;
+      summary.end = Some(record.date.clone());
+      if record.category == income_id {
+        summary.income += record.amount;
+      } else {
+        summary.spent += record.amount;
+        *by_subcategory.entry(record.subcategory).or_default() += record.amount;
+      }
+    }
+
+    summaries
+      .into_iter()
+      .map(|(mut summary, by_subcategory)| {
+        let mut totals: Vec<(String, f64)> = by_subcategory
+          .into_iter()
+          .map(|(id, total)| {
+            let name = self.subcategory_name(id).cloned().unwrap_or_else(|| "Unknown".to_string());
+            (name, total)
+          })
+          .collect();
+        totals.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        summary.by_subcategory = totals;
+        summary
+      })
+      .collect()
+  }
+
+  /// `person` as first entered in the owed ledger, or as given if they're new
+  pub fn owed_name(&self, person: &str) -> String {
+    self
+      .owed
+      .iter()
+      .find(|entry| entry.person.eq_ignore_ascii_case(person))
+      .map_or_else(|| person.to_string(), |entry| entry.person.clone())
+  }
+
+  /// Outstanding balance per person, largest amount first. People whose
+  /// loans are fully settled are left out.
+  pub fn owed_balances(&self, today: NaiveDate) -> Vec<OwedBalance> {
+    let mut balances: Vec<OwedBalance> = Vec::new();
+    for entry in &self.owed {
+      let index = match balances.iter().position(|b| b.person.eq_ignore_ascii_case(&entry.person)) {
+        Some(index) => index,
+        None => {
+          balances.push(OwedBalance {
+            person: entry.person.clone(),
+            balance: 0.0,
+            due: None,
+            overdue: false,
+          });
+          balances.len() - 1
+        }
+      };
+      let balance = &mut balances[index];
+      balance.balance += entry.amount;
+
+      let due = entry.due.as_deref().and_then(parsers::parse_stored_date);
+      let earlier = match balance.due.as_deref().and_then(parsers::parse_stored_date) {
+        Some(current) => due.is_some_and(|due| due < current),
+        None => due.is_some(),
+      };
+      if earlier {
+        balance.due = entry.due.clone();
+      }
+    }
+
+    // Repayments are rounded like any amount, so anything left this small is float noise
+    balances.retain(|b| b.balance.abs() >= 0.005);
+    for balance in &mut balances {
+      balance.overdue = balance
+        .due
+        .as_deref()
+        .and_then(parsers::parse_stored_date)
+        .is_some_and(|due| due < today);
+    }
+    balances.sort_by(|a, b| b.balance.abs().total_cmp(&a.balance.abs()).then(a.person.cmp(&b.person)));
+    balances
+  }
+
   pub fn totals(&self) -> (f64, f64) {
     self.records.iter().fold((0.0, 0.0), |mut acc, r| {
       if r.category == 1 {
@@ -164,6 +1992,13 @@ impl TrackerData {
   }
 }
 
+/// Hex SHA-256 of the records serialized as compact JSON
+pub fn records_checksum(records: &[Record]) -> String {
+  let mut hasher = Sha256::new();
+  serde_json::to_writer(&mut hasher, records).expect("records serialize to JSON");
+  format!("{:x}", hasher.finalize())
+}
+
 pub fn default_tracker_json(currency: &Currency, opening_balance: f64) -> serde_json::Value {
   serde_json::json!({
       "version": 1,
@@ -214,6 +2049,17 @@ mod tests {
             next_subcategory_id: 2,
             records: Vec::new(),
             next_record_id: 1,
+            owed: Vec::new(),
+            investments: Investments::default(),
+            bills: Vec::new(),
+            subscriptions: Vec::new(),
+            schedules: Vec::new(),
+            budgets: Vec::new(),
+            closed_periods: Vec::new(),
+            history: Vec::new(),
+            tags: Vec::new(),
+            checksum: None,
+            category_names: Default::default(),
         }
     }
 
@@ -227,6 +2073,12 @@ mod tests {
             description: "Test".to_string(),
             amount: 100.0,
             date: "01-01-2025".to_string(),
+            entered_by: None,
+            deductible: false,
+            project: None,
+            foreign: None,
+            tags: Vec::new(),
+            parsed_date: Default::default(),
         };
 
         tracker.push_record(record.clone());
@@ -234,6 +2086,84 @@ mod tests {
         assert_eq!(tracker.records[0].id, 1);
     }
 
+    fn dated_record(id: usize, date: &str) -> Record {
+        Record {
+            id,
+            category: 2,
+            subcategory: 1,
+            description: String::new(),
+            amount: 10.0,
+            date: date.to_string(),
+            entered_by: None,
+            deductible: false,
+            project: None,
+            foreign: None,
+            tags: Vec::new(),
+            parsed_date: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_push_record_keeps_date_order() {
+        let mut tracker = create_test_tracker_data();
+        tracker.push_record(dated_record(1, "05-01-2025"));
+        tracker.push_record(dated_record(2, "01-01-2025"));
+        tracker.push_record(dated_record(3, "05-01-2025"));
+        tracker.push_record(dated_record(4, "not a date"));
+        tracker.push_record(dated_record(5, "03-01-2025"));
+
+        let ids: Vec<usize> = tracker.records.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![4, 2, 5, 1, 3]);
+    }
+
+    #[test]
+    fn test_sort_records_after_date_change() {
+        let mut tracker = create_test_tracker_data();
+        tracker.push_record(dated_record(1, "01-01-2025"));
+        tracker.push_record(dated_record(2, "02-01-2025"));
+
+        tracker.records[0].set_date(NaiveDate::from_ymd_opt(2025, 1, 3).unwrap());
+        tracker.sort_records();
+
+        let ids: Vec<usize> = tracker.records.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2, 1]);
+        assert_eq!(tracker.records[1].date, "03-01-2025");
+    }
+
+    #[test]
+    fn test_records_between() {
+        let mut tracker = create_test_tracker_data();
+        for (id, date) in [(1, "01-01-2025"), (2, "bad"), (3, "10-01-2025"), (4, "20-01-2025")] {
+            tracker.push_record(dated_record(id, date));
+        }
+        let ids = |records: &[Record]| records.iter().map(|r| r.id).collect::<Vec<_>>();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d);
+
+        assert_eq!(ids(tracker.records_between(None, None)), vec![1, 3, 4]);
+        assert_eq!(ids(tracker.records_between(day(2), None)), vec![3, 4]);
+        assert_eq!(ids(tracker.records_between(None, day(10))), vec![1, 3]);
+        assert_eq!(ids(tracker.records_between(day(10), day(10))), vec![3]);
+        assert!(tracker.records_between(day(21), day(5)).is_empty());
+    }
+
+    #[test]
+    fn test_running_balance_has_one_point_per_day() {
+        let mut tracker = create_test_tracker_data();
+        for (id, date) in [(1, "01-01-2025"), (2, "01-01-2025"), (3, "10-01-2025"), (4, "20-01-2025")] {
+            tracker.push_record(dated_record(id, date));
+        }
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+
+        assert_eq!(
+            tracker.running_balance(None, None),
+            vec![(day(1), 980.0), (day(10), 970.0), (day(20), 960.0)]
+        );
+        assert_eq!(
+            tracker.running_balance(Some(day(5)), Some(day(15))),
+            vec![(day(5), 980.0), (day(10), 970.0)]
+        );
+    }
+
     #[test]
     fn test_category_id() {
         let tracker = create_test_tracker_data();
@@ -294,6 +2224,12 @@ mod tests {
             description: "Salary".to_string(),
             amount: 500.0,
             date: "01-01-2025".to_string(),
+            entered_by: None,
+            deductible: false,
+            project: None,
+            foreign: None,
+            tags: Vec::new(),
+            parsed_date: Default::default(),
         });
 
         tracker.records.push(Record {
@@ -303,6 +2239,12 @@ mod tests {
             description: "Food".to_string(),
             amount: 100.0,
             date: "02-01-2025".to_string(),
+            entered_by: None,
+            deductible: false,
+            project: None,
+            foreign: None,
+            tags: Vec::new(),
+            parsed_date: Default::default(),
         });
 
         tracker.records.push(Record {
@@ -312,6 +2254,12 @@ mod tests {
             description: "Bonus".to_string(),
             amount: 200.0,
             date: "03-01-2025".to_string(),
+            entered_by: None,
+            deductible: false,
+            project: None,
+            foreign: None,
+            tags: Vec::new(),
+            parsed_date: Default::default(),
         });
 
         let (income, expenses) = tracker.totals();
@@ -334,6 +2282,38 @@ mod tests {
         assert!("INVALID".parse::<Currency>().is_err());
     }
 
+    #[test]
+    fn test_rounding_modes() {
+        assert_eq!(RoundingMode::HalfUp.round(0.125, 2), Some(0.13));
+        assert_eq!(RoundingMode::HalfUp.round(0.285, 2), Some(0.29));
+        assert_eq!(RoundingMode::HalfEven.round(0.125, 2), Some(0.12));
+        assert_eq!(RoundingMode::HalfEven.round(0.135, 2), Some(0.14));
+        assert_eq!(RoundingMode::Down.round(10.129, 2), Some(10.12));
+        assert_eq!(RoundingMode::Up.round(10.121, 2), Some(10.13));
+        assert_eq!(RoundingMode::HalfUp.round(1500.5, 0), Some(1501.0));
+        assert_eq!(RoundingMode::Reject.round(10.5, 2), Some(10.5));
+        assert_eq!(RoundingMode::Reject.round(10.555, 2), None);
+        assert_eq!(RoundingMode::Reject.round(0.1 + 0.2, 2), Some(0.3));
+    }
+
+    #[test]
+    fn test_number_locales() {
+        assert_eq!(NumberLocale::En.parse("1,234.56"), Some(1234.56));
+        assert_eq!(NumberLocale::En.parse("1234.56"), Some(1234.56));
+        assert_eq!(NumberLocale::En.parse("-12"), Some(-12.0));
+        assert_eq!(NumberLocale::De.parse("1.234,56"), Some(1234.56));
+        assert_eq!(NumberLocale::De.parse("4,5"), Some(4.5));
+        assert_eq!(NumberLocale::Fr.parse("1 234,56"), Some(1234.56));
+        assert_eq!(NumberLocale::Fr.parse("12\u{202f}345\u{a0}678"), Some(12345678.0));
+
+        assert_eq!(NumberLocale::En.parse("1,5"), None);
+        assert_eq!(NumberLocale::De.parse("4.50"), None);
+        assert_eq!(NumberLocale::De.parse("1.234.5"), None);
+        assert_eq!(NumberLocale::Fr.parse("1 234.56"), None);
+        assert_eq!(NumberLocale::En.parse("1e5"), None);
+        assert_eq!(NumberLocale::En.parse("."), None);
+    }
+
     #[test]
     fn test_category_display() {
         assert_eq!(Category::Income.to_string(), "income");
@@ -353,14 +2333,35 @@ mod tests {
     fn test_total_calculation() {
         let total = Total {
             currency: Currency::USD,
+            start: None,
+            end: None,
             opening_balance: 1000.0,
             income_total: 500.0,
             expenses_total: 200.0,
+            investments: None,
+            round_up_savings: None,
+            budgets: Vec::new(),
+            by: None,
+            groups: Vec::new(),
+            by_currency: Vec::new(),
         };
 
         assert_eq!(total.total(), 1300.0); // 1000 + 500 - 200
     }
 
+    #[test]
+    fn test_is_confirmation() {
+        assert!(CliResponse::success().is_confirmation());
+        assert!(CliResponse::new(ResponseContent::Message("done".to_string())).is_confirmation());
+        assert!(!CliResponse::new(ResponseContent::Categories(vec![])).is_confirmation());
+        let dump = ResponseContent::TrackerData {
+            tracker_data: create_test_tracker_data(),
+            part: DumpPart::All,
+            compact: false,
+        };
+        assert!(!CliResponse::new(dump).is_confirmation());
+    }
+
     #[test]
     fn test_default_tracker_json() {
         let json = default_tracker_json(&Currency::USD, 1000.0);
@@ -374,4 +2375,40 @@ mod tests {
         assert_eq!(json["next_record_id"], 1);
         assert_eq!(json["next_subcategory_id"], 2);
     }
+
+    #[test]
+    fn test_date_format() {
+        assert_eq!(DateFormat::DdMmYyyy.format("15-01-2025"), "15-01-2025");
+        assert_eq!(DateFormat::Iso.format("15-01-2025"), "2025-01-15");
+        assert_eq!(DateFormat::Short.format("15-01-2025"), "Jan 15 2025");
+        // Anything that isn't a valid stored date is left alone
+        assert_eq!(DateFormat::Iso.format("not a date"), "not a date");
+        assert_eq!("ISO".parse::<DateFormat>().unwrap(), DateFormat::Iso);
+        assert_eq!("dd-mm-yyyy".parse::<DateFormat>().unwrap(), DateFormat::DdMmYyyy);
+    }
+
+    #[test]
+    fn test_bill_advance_keeps_day_of_month() {
+        let mut bill = Bill {
+            name: "Rent".to_string(),
+            amount: 500.0,
+            subcategory: "miscellaneous".to_string(),
+            every: Frequency::Month,
+            due: "31-01-2025".to_string(),
+            day: 31,
+        };
+        bill.advance();
+        assert_eq!(bill.due, "28-02-2025");
+        bill.advance();
+        assert_eq!(bill.due, "31-03-2025");
+
+        bill.every = Frequency::Week;
+        bill.advance();
+        assert_eq!(bill.due, "07-04-2025");
+
+        bill.every = Frequency::Year;
+        bill.due = "31-12-2025".to_string();
+        bill.advance();
+        assert_eq!(bill.due, "31-12-2026");
+    }
 }
@@ -1,265 +1,398 @@
-use std::io;
+use std::{ffi::OsStr, io::{self, Write as _}};
 
 use colored::Colorize;
-use tabled::{Table, Tabled, settings::Style};
+use serde_json::{Value, json};
+use tabled::{
+  Table,
+  builder::Builder,
+  grid::records::vec_records::Cell,
+  settings::{Alignment, Style, Width, object::Columns},
+};
 
-use crate::{CliError, Currency, Record, ResponseContent, TrackerData, ValidationErrorKind};
+use crate::{
+  CliError, Currency, DateFormat, DayOfWeek, DumpPart, LimitPeriod, OutputFormat, Record, ResponseContent, TableStyle, TrackerData,
+  ValidationErrorKind, t, utils::fuzzy::fuzzy_match,
+};
+
+/// Presentation settings that apply to the text output of every command
+#[derive(Clone, Debug, Default)]
+pub struct DisplayOptions {
+  pub table_style: TableStyle,
+  /// Width record tables must fit in, usually the terminal's. Descriptions are
+  /// truncated to fit; `None` (e.g. with `--full` or when piped) never truncates.
+  pub max_width: Option<usize>,
+  /// How record dates are shown; stored dates are always DD-MM-YYYY
+  pub date_format: DateFormat,
+  /// Leave the currency code out of table amounts
+  pub hide_currency: bool,
+  /// Shorten large table amounts to 12.3k, 4.5M and so on
+  pub compact_amounts: bool,
+}
+
+impl DisplayOptions {
+  /// An amount as it appears in a table cell
+  fn table_amount(&self, amount: f64) -> String {
+    if self.compact_amounts { compact_amount(amount) } else { format_amount(amount) }
+  }
+
+  /// What follows each amount in a table: " USD", or nothing
+  fn currency_suffix(&self, currency: Option<impl std::fmt::Display>) -> String {
+    match currency {
+      Some(currency) if !self.hide_currency => format!(" {}", currency),
+      _ => String::new(),
+    }
+  }
+}
+
+/// Descriptions are never truncated below this many characters, even if the
+/// table then overflows the terminal
+const MIN_DESCRIPTION_WIDTH: usize = 12;
+
+/// Column index of the description in record tables
+const DESCRIPTION_COLUMN: usize = 5;
+
+/// Decide whether output should be colored.
+///
+/// Colors are off when `--no-color` is passed, when the `NO_COLOR` environment
+/// variable is set to a non-empty value (see https://no-color.org), when JSON
+/// output is requested, or when stdout is not a terminal (e.g. piped to a file).
+pub fn colors_enabled(
+  no_color_flag: bool,
+  no_color_env: Option<&OsStr>,
+  format: OutputFormat,
+  is_terminal: bool,
+) -> bool {
+  let env_disabled = no_color_env.is_some_and(|value| !value.is_empty());
+
+  !no_color_flag && !env_disabled && format == OutputFormat::Text && is_terminal
+}
+
+/// The translated text of an error, shared by the text and JSON renderings
+struct ErrorText {
+  /// Data was recovered, so the error is shown as a warning
+  is_warning: bool,
+  message: String,
+  suggestion: Option<String>,
+}
+
+impl ErrorText {
+  fn new(message: String, suggestion: Option<String>) -> Self {
+    Self {
+      is_warning: false,
+      message,
+      suggestion,
+    }
+  }
+}
 
 /// Write a CLI error to the given writer
+///
+/// The first line carries the error's stable code, e.g.
+/// `✗ [E012] ValidationError: Subcategory 'Food' not found`.
 pub fn write_error(err: &CliError, writer: &mut impl io::Write) -> io::Result<()> {
+  let text = error_text(err);
+  let marker = if text.is_warning {
+    "⚠".yellow().bold()
+  } else {
+    "✗".red().bold()
+  };
+  let code = format!("[{}]", err.code()).dimmed();
+
+  if let CliError::ValidationError(_) = err {
+    writeln!(
+      writer,
+      "{} {} {} {}",
+      marker,
+      code,
+      t!("validation-error-label").red().bold(),
+      text.message
+    )?;
+  } else {
+    writeln!(writer, "{} {} {}", marker, code, text.message)?;
+  }
+
+  if let Some(suggestion) = text.suggestion {
+    // Multi-line suggestions (e.g. example commands) line up under the first line
+    let suggestion = suggestion.replace('\n', "\n    ");
+    writeln!(writer, "{} {}", t!("suggestion-label").yellow(), suggestion)?;
+  }
+
+  Ok(())
+}
+
+fn error_text(err: &CliError) -> ErrorText {
   match err {
-    CliError::FileNotFound(path) => {
-      writeln!(
-        writer,
-        "{} File not found: {}",
-        "✗".red().bold(),
-        path.bright_red()
-      )?;
-      writeln!(
-        writer,
-        "{} Run 'fintrack init' to initialize the tracker",
-        "Suggestion:".yellow()
-      )?;
-    }
-    CliError::InvalidJson(msg) => {
-      writeln!(
-        writer,
-        "{} Invalid JSON: {}",
-        "✗".red().bold(),
-        msg.bright_red()
-      )?;
-      writeln!(
-        writer,
-        "{} Your tracker data may be corrupted. Try restoring from backup",
-        "Suggestion:".yellow()
-      )?;
-    }
-    CliError::ValidationError(kind) => {
-      write_validation_error(kind, writer)?;
-    }
-    CliError::PermissionDenied(path) => {
-      writeln!(
-        writer,
-        "{} Permission denied: {}",
-        "✗".red().bold(),
-        path.bright_red()
-      )?;
-      writeln!(
-        writer,
-        "{} Check file permissions or run with appropriate access",
-        "Suggestion:".yellow()
-      )?;
-    }
+    CliError::FileNotFound(path) => ErrorText::new(
+      t!("error-file-not-found", path = path.bright_red().to_string()),
+      Some(t!("suggest-init")),
+    ),
+    CliError::InvalidJson(msg) => ErrorText::new(
+      t!("error-invalid-json", message = msg.bright_red().to_string()),
+      Some(t!("suggest-restore-backup")),
+    ),
+    CliError::ValidationError(kind) => validation_error_text(kind),
+    CliError::PermissionDenied(path) => ErrorText::new(
+      t!("error-permission-denied", path = path.bright_red().to_string()),
+      Some(t!("suggest-check-permissions")),
+    ),
     CliError::CorruptedData {
-      backup_restored,
+      backup_restored: true,
       timestamp,
-    } => {
-      if *backup_restored {
-        writeln!(
-          writer,
-          "{} Data was corrupted but restored from backup ({})",
-          "⚠".yellow().bold(),
-          timestamp.bright_yellow()
-        )?;
-        writeln!(
-          writer,
-          "{} Please verify your recent changes",
-          "Suggestion:".yellow()
-        )?;
-      } else {
-        writeln!(
-          writer,
-          "{} Data corruption detected and backup restoration failed",
-          "✗".red().bold()
-        )?;
-        writeln!(
-          writer,
-          "{} Run 'fintrack dump' to inspect remaining data, or 'fintrack clear' to reset",
-          "Suggestion:".yellow()
-        )?;
-      }
-    }
+    } => ErrorText {
+      is_warning: true,
+      message: t!(
+        "error-corrupted-restored",
+        timestamp = timestamp.bright_yellow().to_string()
+      ),
+      suggestion: Some(t!("suggest-verify-changes")),
+    },
+    CliError::CorruptedData {
+      backup_restored: false,
+      ..
+    } => ErrorText::new(
+      t!("error-corrupted-unrecoverable"),
+      Some(t!("suggest-inspect-or-reset")),
+    ),
+    CliError::NotInitialized => ErrorText::new(
+      t!("error-not-initialized"),
+      Some(t!("suggest-quick-start")),
+    ),
     CliError::FileAlreadyExists => {
-      writeln!(writer, "{} Tracker already initialized", "✗".red().bold())?;
-      writeln!(
-        writer,
-        "{} Use 'fintrack clear' to start over",
-        "Suggestion:".yellow()
-      )?;
-    }
-    CliError::Other(msg) => {
-      writeln!(writer, "{} {}", "✗".red().bold(), msg.bright_red())?;
+      ErrorText::new(t!("error-already-initialized"), Some(t!("suggest-clear")))
     }
+    CliError::ReadOnly => ErrorText::new(t!("error-read-only"), Some(t!("suggest-read-only"))),
+    CliError::ChecksumMismatch => ErrorText::new(
+      t!("error-checksum-mismatch"),
+      Some(t!("suggest-checksum-mismatch")),
+    ),
+    CliError::InvalidField {
+      path,
+      message,
+      expected,
+    } => ErrorText::new(
+      t!(
+        "error-invalid-field",
+        path = path.bright_red().to_string(),
+        message = message.clone()
+      ),
+      Some(match expected {
+        Some(expected) => t!(
+          "suggest-fix-field",
+          path = path.bright_yellow().to_string(),
+          expected = expected.clone()
+        ),
+        None => t!("suggest-check-field", path = path.bright_yellow().to_string()),
+      }),
+    ),
+    CliError::Other(msg) => ErrorText::new(msg.bright_red().to_string(), None),
   }
-
-  Ok(())
 }
 
-fn write_validation_error(
-  kind: &ValidationErrorKind,
-  writer: &mut impl io::Write,
-) -> io::Result<()> {
+fn validation_error_text(kind: &ValidationErrorKind) -> ErrorText {
   match kind {
-    ValidationErrorKind::AmountTooSmall { amount } => {
-      writeln!(
-        writer,
-        "{} Amount must be greater than 0, got: {}",
-        "✗ ValidationError:".red().bold(),
-        amount.to_string().bright_red()
-      )?;
-      writeln!(
-        writer,
-        "{} Re-run the command with a positive amount (e.g., --amount 500)",
-        "Suggestion:".yellow()
-      )?;
-    }
+    ValidationErrorKind::AmountTooSmall { amount } => ErrorText::new(
+      t!(
+        "error-amount-too-small",
+        amount = amount.to_string().bright_red().to_string()
+      ),
+      Some(t!("suggest-positive-amount")),
+    ),
+    ValidationErrorKind::InvalidDate {
+      provided,
+      nearest: Some(nearest),
+      ..
+    } => ErrorText::new(
+      t!(
+        "error-impossible-date",
+        provided = provided.bright_red().to_string()
+      ),
+      Some(t!(
+        "suggest-nearest-date",
+        nearest = nearest.bright_yellow().to_string()
+      )),
+    ),
     ValidationErrorKind::InvalidDate {
       provided,
       expected_format,
-    } => {
-      writeln!(
-        writer,
-        "{} Invalid date format: '{}'",
-        "✗ ValidationError:".red().bold(),
-        provided.bright_red()
-      )?;
-      writeln!(
-        writer,
-        "{} Expected format: {}",
-        "Suggestion:".yellow(),
-        expected_format.bright_yellow()
-      )?;
-    }
-    ValidationErrorKind::SubcategoryNotFound { name } => {
-      writeln!(
-        writer,
-        "{} Subcategory '{}' not found",
-        "✗ ValidationError:".red().bold(),
-        name.bright_red()
-      )?;
-      writeln!(
-        writer,
-        "{} Use 'fintrack subcategory list' to see available subcategories",
-        "Suggestion:".yellow()
-      )?;
-    }
-    ValidationErrorKind::SubcategoryAlreadyExists { name } => {
-      writeln!(
-        writer,
-        "{} Subcategory '{}' already exists",
-        "✗ ValidationError:".red().bold(),
-        name.bright_red()
-      )?;
-      writeln!(
-        writer,
-        "{} Use a different name or check existing subcategories",
-        "Suggestion:".yellow()
-      )?;
-    }
-    ValidationErrorKind::RecordNotFound { id } => {
-      writeln!(
-        writer,
-        "{} Record with ID {} not found",
-        "✗ ValidationError:".red().bold(),
-        id.to_string().bright_red()
-      )?;
-      writeln!(
-        writer,
-        "{} Use 'fintrack list' to see available records",
-        "Suggestion:".yellow()
-      )?;
-    }
-    ValidationErrorKind::SubcategoryHasRecords { name, count } => {
-      writeln!(
-        writer,
-        "{} Cannot delete '{}' — it has {} record(s)",
-        "✗ ValidationError:".red().bold(),
-        name.bright_red(),
-        count.to_string().bright_red()
-      )?;
-      writeln!(
-        writer,
-        "{} Delete those records first using 'fintrack delete --by-subcat {}', or manually delete individual records",
-        "Suggestion:".yellow(),
-        name.bright_yellow()
-      )?;
-    }
+      nearest: None,
+    } => ErrorText::new(
+      t!(
+        "error-invalid-date",
+        provided = provided.bright_red().to_string()
+      ),
+      Some(t!(
+        "suggest-date-format",
+        format = expected_format.bright_yellow().to_string()
+      )),
+    ),
+    ValidationErrorKind::SubcategoryNotFound { name, nearest } => ErrorText::new(
+      t!(
+        "error-subcategory-not-found",
+        name = name.bright_red().to_string()
+      ),
+      Some(match nearest {
+        Some(nearest) => t!(
+          "suggest-subcategory-nearest",
+          nearest = nearest.bright_yellow().to_string()
+        ),
+        None => t!("suggest-subcategory-list"),
+      }),
+    ),
+    ValidationErrorKind::SubcategoryAlreadyExists { name } => ErrorText::new(
+      t!(
+        "error-subcategory-exists",
+        name = name.bright_red().to_string()
+      ),
+      Some(t!("suggest-different-name")),
+    ),
+    ValidationErrorKind::RecordNotFound { id } => ErrorText::new(
+      t!(
+        "error-record-not-found",
+        id = id.to_string().bright_red().to_string()
+      ),
+      Some(t!("suggest-list")),
+    ),
+    ValidationErrorKind::SubcategoryHasRecords { name, count } => ErrorText::new(
+      t!(
+        "error-subcategory-has-records",
+        name = name.bright_red().to_string(),
+        count = *count
+      ),
+      Some(t!(
+        "suggest-delete-by-subcat",
+        name = name.bright_yellow().to_string()
+      )),
+    ),
     ValidationErrorKind::CannotDeleteMiscellaneous => {
-      writeln!(
-        writer,
-        "{} Cannot delete 'Miscellaneous' — it is a system subcategory",
-        "✗ ValidationError:".red().bold()
-      )?;
-    }
-    ValidationErrorKind::CategoryImmutable { category } => {
-      writeln!(
-        writer,
-        "{} Category {} is immutable and cannot be modified",
-        "✗ ValidationError:".red().bold(),
-        category.to_string().bright_red()
-      )?;
-    }
-    ValidationErrorKind::InvalidCategoryName { name, reason } => {
-      writeln!(
-        writer,
-        "{} Invalid category name '{}': {}",
-        "✗ ValidationError:".red().bold(),
-        name.bright_red(),
-        reason.bright_red()
-      )?;
-    }
-    ValidationErrorKind::InvalidName { name, reason } => {
-      writeln!(
-        writer,
-        "{} Invalid name '{}': {}",
-        "✗ ValidationError:".red().bold(),
-        name.bright_red(),
-        reason.bright_red()
-      )?;
-    }
-    ValidationErrorKind::InvalidAmount { reason } => {
-      writeln!(
-        writer,
-        "{} Invalid amount: {}",
-        "✗ ValidationError:".red().bold(),
-        reason.bright_red()
-      )?;
+      ErrorText::new(t!("error-cannot-delete-misc"), None)
     }
+    ValidationErrorKind::CategoryImmutable { category } => ErrorText::new(
+      t!(
+        "error-category-immutable",
+        category = category.bright_red().to_string()
+      ),
+      Some(t!("suggest-category-immutable")),
+    ),
+    ValidationErrorKind::InvalidCategoryName { name, reason } => ErrorText::new(
+      t!(
+        "error-invalid-category-name",
+        name = name.bright_red().to_string(),
+        reason = reason.bright_red().to_string()
+      ),
+      None,
+    ),
+    ValidationErrorKind::InvalidName { name, reason } => ErrorText::new(
+      t!(
+        "error-invalid-name",
+        name = name.bright_red().to_string(),
+        reason = reason.bright_red().to_string()
+      ),
+      None,
+    ),
+    ValidationErrorKind::InvalidAmount { reason } => ErrorText::new(
+      t!(
+        "error-invalid-amount",
+        reason = reason.bright_red().to_string()
+      ),
+      None,
+    ),
     ValidationErrorKind::TrackerAlreadyInitialized => {
-      writeln!(
-        writer,
-        "{} Tracker already initialized",
-        "✗ ValidationError:".red().bold()
-      )?;
-      writeln!(
-        writer,
-        "{} Use 'fintrack clear' to start over",
-        "Suggestion:".yellow()
-      )?;
-    }
-    ValidationErrorKind::InvalidSubcommand { subcommand } => {
-      writeln!(
-        writer,
-        "{} Unknown subcommand: '{}'",
-        "✗ ValidationError:".red().bold(),
-        subcommand.bright_red()
-      )?;
-      writeln!(
-        writer,
-        "{} Use 'fintrack --help' to see available commands",
-        "Suggestion:".yellow()
-      )?;
+      ErrorText::new(t!("error-already-initialized"), Some(t!("suggest-clear")))
     }
+    ValidationErrorKind::InvalidSubcommand { subcommand } => ErrorText::new(
+      t!(
+        "error-unknown-subcommand",
+        subcommand = subcommand.bright_red().to_string()
+      ),
+      Some(t!("suggest-help")),
+    ),
+    ValidationErrorKind::UnknownConfigKey { key } => ErrorText::new(
+      t!("error-unknown-config-key", key = key.bright_red().to_string()),
+      Some(t!("suggest-config-list")),
+    ),
+    ValidationErrorKind::InvalidConfigValue { key, value, reason } => ErrorText::new(
+      t!(
+        "error-invalid-config-value",
+        key = key.bright_white().to_string(),
+        value = value.bright_red().to_string(),
+        reason = reason.clone()
+      ),
+      None,
+    ),
+    ValidationErrorKind::StrictCheckFailed { reason } => ErrorText::new(
+      t!(
+        "error-strict-check-failed",
+        reason = reason.bright_red().to_string()
+      ),
+      Some(t!("suggest-strict")),
+    ),
+    ValidationErrorKind::DuplicateRecord { id } => ErrorText::new(
+      t!(
+        "error-duplicate-record",
+        id = id.to_string().bright_red().to_string()
+      ),
+      Some(t!("suggest-duplicate-record")),
+    ),
+    ValidationErrorKind::NothingOwed { person } => ErrorText::new(
+      t!("error-nothing-owed", person = person.bright_red().to_string()),
+      Some(t!("suggest-owed-list")),
+    ),
+    ValidationErrorKind::BillNotFound { name } => ErrorText::new(
+      t!("error-bill-not-found", name = name.bright_red().to_string()),
+      Some(t!("suggest-bills")),
+    ),
+    ValidationErrorKind::ProjectNotFound { name } => ErrorText::new(
+      t!("error-project-not-found", name = name.bright_red().to_string()),
+      Some(t!("suggest-project-list")),
+    ),
+    ValidationErrorKind::ScheduleNotFound { id } => ErrorText::new(
+      t!("error-schedule-not-found", id = id.to_string().bright_red().to_string()),
+      Some(t!("suggest-schedule-list")),
+    ),
+    ValidationErrorKind::BudgetNotFound { subcategory } => ErrorText::new(
+      t!("error-budget-not-found", subcategory = subcategory.bright_red().to_string()),
+      Some(t!("suggest-budget-list")),
+    ),
+    ValidationErrorKind::VersionNotFound { id, version } => ErrorText::new(
+      t!(
+        "error-version-not-found",
+        id = id.to_string(),
+        version = version.to_string().bright_red().to_string()
+      ),
+      Some(t!("suggest-history", id = id.to_string())),
+    ),
+    ValidationErrorKind::SubscriptionNotFound { name } => ErrorText::new(
+      t!("error-subscription-not-found", name = name.bright_red().to_string()),
+      Some(t!("suggest-subscriptions")),
+    ),
+    ValidationErrorKind::TokenNotFound { name } => ErrorText::new(
+      t!("error-token-not-found", name = name.bright_red().to_string()),
+      Some(t!("suggest-token-list")),
+    ),
+    ValidationErrorKind::TagNotFound { name } => ErrorText::new(
+      t!("error-tag-not-found", name = name.bright_red().to_string()),
+      Some(t!("suggest-tag-list")),
+    ),
+    ValidationErrorKind::TagInUse { name, count } => ErrorText::new(
+      t!("error-tag-in-use", name = name.bright_red().to_string(), count = *count),
+      Some(t!("suggest-tag-from-records")),
+    ),
+    ValidationErrorKind::PeriodClosed { month } => ErrorText::new(
+      t!("error-period-closed", month = month.bright_red().to_string()),
+      Some(t!("suggest-reopen", month = month.clone())),
+    ),
   }
-
-  Ok(())
 }
 
 /// Write a CLI response to the given writer
-pub fn write_response(res: &crate::CliResponse, writer: &mut impl io::Write) -> io::Result<()> {
+pub fn write_response(
+  res: &crate::CliResponse,
+  options: &DisplayOptions,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
   let Some(content) = res.content() else {
-    writeln!(writer, "{}", "✓ Success".green().bold())?;
-    return Ok(());
+    writeln!(writer, "{}", format!("✓ {}", t!("success")).green().bold())?;
+    return write_warnings(res.warnings(), writer);
   };
 
   match content {
@@ -272,30 +405,83 @@ pub fn write_response(res: &crate::CliResponse, writer: &mut impl io::Write) ->
       is_update,
     } => {
       let message = if *is_update {
-        "Record updated:"
+        t!("record-updated")
       } else {
-        "Record created:"
+        t!("record-created")
       };
       writeln!(writer, "{} {}", "✓".green().bold(), message.bright_green())?;
       let currency_enum = tracker_data.currency.parse::<Currency>().ok();
-      write_record_single(&record, Some(tracker_data), currency_enum.as_ref(), writer)?;
+      write_record_single(
+        record,
+        Some(tracker_data),
+        currency_enum.as_ref(),
+        options,
+        writer,
+      )?;
+      write_balance(tracker_data, writer)?;
+    }
+    ResponseContent::Deleted {
+      count,
+      tracker_data,
+    } => {
+      writeln!(
+        writer,
+        "{} {}",
+        "✓".green().bold(),
+        t!("records-deleted", count = *count).bright_green()
+      )?;
+      write_balance(tracker_data, writer)?;
     }
     ResponseContent::List {
       records,
       tracker_data,
     } => {
       if records.is_empty() {
-        writeln!(writer, "{}", "No records found.".yellow())?;
+        writeln!(writer, "{}", t!("no-records").yellow())?;
+      } else {
+        let currency_enum = tracker_data.currency.parse::<Currency>().ok();
+        write_records_table(
+          records,
+          Some(tracker_data),
+          currency_enum.as_ref(),
+          None,
+          options,
+          writer,
+        )?;
+      }
+    }
+    ResponseContent::Search {
+      query,
+      records,
+      tracker_data,
+    } => {
+      if records.is_empty() {
+        writeln!(writer, "{}", t!("search-no-matches", query = query.clone()).yellow())?;
       } else {
         let currency_enum = tracker_data.currency.parse::<Currency>().ok();
-        write_records_table(&records, Some(tracker_data), currency_enum.as_ref(), writer)?;
+        write_records_table(
+          records,
+          Some(tracker_data),
+          currency_enum.as_ref(),
+          Some(query),
+          options,
+          writer,
+        )?;
       }
     }
-    ResponseContent::TrackerData(tracker_data) => {
-      write_tracker_data(tracker_data, writer)?;
+    ResponseContent::TrackerData {
+      tracker_data,
+      part,
+      compact,
+    } => {
+      write_tracker_data(tracker_data, *part, *compact, writer)?;
+    }
+    ResponseContent::Schema(schema) => {
+      serde_json::to_writer_pretty(&mut *writer, schema)?;
+      writeln!(writer)?;
     }
     ResponseContent::Total(totals) => {
-      write_total_summary(totals, writer)?;
+      write_total_summary(totals, options, writer)?;
     }
     ResponseContent::Categories(categories) => {
       write_categories_list(categories, writer)?;
@@ -303,30 +489,121 @@ pub fn write_response(res: &crate::CliResponse, writer: &mut impl io::Write) ->
     ResponseContent::Subcategories(subcategories) => {
       write_subcategories_list(subcategories, writer)?;
     }
+    ResponseContent::Tags(tags) => {
+      write_tags(tags, writer)?;
+    }
     ResponseContent::Describe(data) => {
-      write_describe(data, writer)?;
+      write_describe(data, options, writer)?;
+    }
+    ResponseContent::Report(report) => {
+      write_report(report, writer)?;
+    }
+    ResponseContent::Deductions(report) => {
+      write_deductions(report, writer)?;
+    }
+    ResponseContent::Reconciliation(reconciliation) => {
+      write_reconciliation(reconciliation, writer)?;
+    }
+    ResponseContent::DryRun(diff) => {
+      write_tracker_diff(diff, t!("dry-run-title"), t!("dry-run-unchanged"), writer)?;
+    }
+    ResponseContent::Diff { source, reverse, diff } => {
+      let title = match reverse {
+        true => t!("diff-restore-title", source = source.clone()),
+        false => t!("diff-title", source = source.clone()),
+      };
+      write_tracker_diff(diff, title, t!("diff-unchanged", source = source.clone()), writer)?;
+    }
+    ResponseContent::Digest(digest) => {
+      let lines = digest_lines(digest);
+      writeln!(writer, "{}", lines[0].bright_white().bold())?;
+      for line in &lines[1..] {
+        writeln!(writer, "  {}", line)?;
+      }
+    }
+    ResponseContent::Schedules { schedules, currency } => {
+      write_schedules(schedules, currency, writer)?;
+    }
+    ResponseContent::Projects { projects, currency } => {
+      write_projects(projects, currency, writer)?;
+    }
+    ResponseContent::Project { project, currency } => {
+      write_project(project, currency, writer)?;
+    }
+    ResponseContent::Holdings { holdings, currency } => {
+      write_holdings(holdings, currency, writer)?;
+    }
+    ResponseContent::Bills { bills, currency } => {
+      write_bills(bills, currency, writer)?;
+    }
+    ResponseContent::Subscriptions {
+      subscriptions,
+      currency,
+    } => {
+      write_subscriptions(subscriptions, currency, writer)?;
+    }
+    ResponseContent::Tokens { tokens } => {
+      write_tokens(tokens, writer)?;
+    }
+    ResponseContent::Merge(summary) => {
+      write_merge(summary, writer)?;
+    }
+    ResponseContent::History {
+      id,
+      versions,
+      tracker_data,
+    } => {
+      write_history(*id, versions, tracker_data, options, writer)?;
+    }
+    ResponseContent::Status { summary, short: true } => {
+      writeln!(writer, "{}", status_line(summary))?;
+    }
+    ResponseContent::Status { summary, short: false } => {
+      write_status(summary, writer)?;
+    }
+    ResponseContent::Budgets { month, budgets, currency } => {
+      write_budgets(month, budgets, currency, writer)?;
+    }
+    ResponseContent::Owed { balances, currency } => {
+      write_owed(balances, currency, writer)?;
+    }
+    ResponseContent::ConfigValue { value, .. } => match value {
+      Some(value) => writeln!(writer, "{}", value)?,
+      None => writeln!(writer, "{}", t!("config-not-set").dimmed())?,
+    },
+    ResponseContent::Settings(settings) => {
+      write_settings(settings, writer)?;
     }
   }
 
+  write_warnings(res.warnings(), writer)
+}
+
+pub fn write_warnings(warnings: &[String], writer: &mut impl io::Write) -> io::Result<()> {
+  for warning in warnings {
+    writeln!(writer, "{} {}", "⚠".yellow().bold(), warning.yellow())?;
+  }
   Ok(())
 }
 
 pub fn write_records_table_with_context(
   records: &[Record],
   tracker_data: &TrackerData,
+  options: &DisplayOptions,
   writer: &mut impl io::Write,
 ) -> io::Result<()> {
   let currency = tracker_data.currency.parse::<Currency>().ok();
-  write_records_table(records, Some(tracker_data), currency.as_ref(), writer)
+  write_records_table(records, Some(tracker_data), currency.as_ref(), None, options, writer)
 }
 
 pub fn write_record_single_with_context(
   record: &Record,
   tracker_data: &TrackerData,
+  options: &DisplayOptions,
   writer: &mut impl io::Write,
 ) -> io::Result<()> {
   let currency = tracker_data.currency.parse::<Currency>().ok();
-  write_record_single(record, Some(tracker_data), currency.as_ref(), writer)
+  write_record_single(record, Some(tracker_data), currency.as_ref(), options, writer)
 }
 
 /// Write a single record in a formatted line
@@ -334,136 +611,1352 @@ fn write_record_single(
   record: &Record,
   tracker_data: Option<&TrackerData>,
   currency: Option<&Currency>,
+  options: &DisplayOptions,
   writer: &mut impl io::Write,
 ) -> io::Result<()> {
   let category_name = tracker_data
     .and_then(|td| td.category_name(record.category))
-    .map(|s| s.as_str())
-    .unwrap_or_else(|| "Unknown");
+    .cloned()
+    .unwrap_or_else(|| t!("unknown"));
 
   let subcategory_name = tracker_data
     .and_then(|td| td.subcategory_name(record.subcategory))
-    .map(|s| s.as_str())
-    .unwrap_or_else(|| "Unknown");
+    .cloned()
+    .unwrap_or_else(|| t!("unknown"));
 
-  let currency_str = currency.map(|c| format!(" {}", c)).unwrap_or_default();
+  let mut currency_str = currency.map(|c| format!(" {}", c)).unwrap_or_default();
+  if let Some(foreign) = &record.foreign {
+    currency_str.push_str(&format!(
+      " ({} {} @ {})",
+      format_amount(foreign.amount),
+      foreign.currency,
+      foreign.rate
+    ));
+  }
 
   writeln!(
     writer,
-    "  ID: {} | {} | {} | {}{} | {} | {}",
+    "  {}: {} | {} | {} | {}{} | {} | {}{}",
+    t!("column-id"),
     record.id.to_string().cyan(),
     category_name.bright_white(),
     subcategory_name.bright_white(),
     format_amount(record.amount).bright_white(),
     currency_str.bright_white(),
-    record.date.bright_white(),
+    options.date_format.format(&record.date).bright_white(),
     if record.description.is_empty() {
-      "(no description)".dimmed()
+      t!("no-description").dimmed()
     } else {
       record.description.bright_white()
-    }
+    },
+    record.tags.iter().map(|tag| format!(" #{}", tag).cyan().to_string()).collect::<String>()
   )?;
   Ok(())
 }
 
-/// Write records as a formatted table
-fn write_records_table(
-  records: &[Record],
-  tracker_data: Option<&TrackerData>,
-  currency: Option<&Currency>,
+/// Write records as a formatted table
+/// `highlight` is a `search` query whose matching letters are marked in the
+/// subcategory and description columns
+fn write_records_table(
+  records: &[Record],
+  tracker_data: Option<&TrackerData>,
+  currency: Option<&Currency>,
+  highlight: Option<&str>,
+  options: &DisplayOptions,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
+  let currency_str = options.currency_suffix(currency);
+
+  let income_id = tracker_data.and_then(|td| td.categories.get("income").copied());
+
+  let mut builder = Builder::default();
+  builder.push_record([
+    t!("column-id"),
+    t!("column-category"),
+    t!("column-subcategory"),
+    t!("column-amount"),
+    t!("column-date"),
+    t!("column-description"),
+  ]);
+
+  for r in records {
+    let category_name = tracker_data
+      .and_then(|td| td.category_name(r.category))
+      .cloned()
+      .unwrap_or_else(|| t!("category-fallback", id = r.category));
+
+    let subcategory_name = tracker_data
+      .and_then(|td| td.subcategory_name(r.subcategory))
+      .cloned()
+      .unwrap_or_else(|| t!("subcategory-fallback", id = r.subcategory));
+
+    let marked = |text: String| match highlight {
+      Some(query) => highlight_matches(&text, query),
+      None => text,
+    };
+
+    builder.push_record([
+      r.id.to_string(),
+      category_name,
+      marked(subcategory_name),
+      signed_amount(r, income_id, &currency_str, options),
+      options.date_format.format(&r.date),
+      if r.description.is_empty() {
+        t!("no-description")
+      } else {
+        marked(r.description.clone())
+      },
+    ]);
+  }
+
+  let mut table = builder.build();
+  apply_table_style(&mut table, options.table_style);
+
+  if let Some(max_width) = options.max_width {
+    fit_description(&mut table, max_width);
+  }
+
+  writeln!(writer, "{}", table)?;
+  Ok(())
+}
+
+/// `text` with the letters that fuzzily match `query` in bold yellow
+fn highlight_matches(text: &str, query: &str) -> String {
+  let Some(found) = fuzzy_match(query, text) else {
+    return text.to_string();
+  };
+  text
+    .chars()
+    .enumerate()
+    .map(|(i, c)| {
+      if found.positions.contains(&i) {
+        c.to_string().yellow().bold().to_string()
+      } else {
+        c.to_string()
+      }
+    })
+    .collect()
+}
+
+fn apply_table_style(table: &mut Table, style: TableStyle) {
+  match style {
+    TableStyle::Modern => table.with(Style::modern()),
+    TableStyle::Ascii => table.with(Style::ascii()),
+    TableStyle::Markdown => table.with(Style::markdown()),
+    TableStyle::Compact => table.with(Style::psql()),
+  };
+}
+
+/// Truncate the description column just enough for the table to fit in `max_width`
+fn fit_description(table: &mut Table, max_width: usize) {
+  let total_width = table.total_width();
+  if total_width <= max_width {
+    return;
+  }
+
+  let description_width = table
+    .get_records()
+    .iter()
+    .map(|row| row[DESCRIPTION_COLUMN].width())
+    .max()
+    .unwrap_or(0);
+  let target = description_width
+    .saturating_sub(total_width - max_width)
+    .max(MIN_DESCRIPTION_WIDTH);
+
+  if target < description_width {
+    table.modify(
+      Columns::one(DESCRIPTION_COLUMN),
+      Width::truncate(target).suffix("…"),
+    );
+  }
+}
+
+/// The net balance after a change, shown beneath add/update/delete confirmations
+fn write_balance(tracker_data: &TrackerData, writer: &mut impl io::Write) -> io::Result<()> {
+  let balance = tracker_data.net_balance();
+  let amount = format!("{} {}", format_amount(balance), tracker_data.currency);
+  let amount = if balance < 0.0 {
+    amount.bright_red()
+  } else {
+    amount.bright_cyan()
+  };
+
+  writeln!(writer, "  {} {}", t!("new-balance").bright_white(), amount.bold())
+}
+
+/// A record's amount with its direction: a green "+" for income and a red "-"
+/// for expenses. Without tracker data the category is unknown, so the amount is
+/// shown as stored.
+fn signed_amount(
+  record: &Record,
+  income_id: Option<usize>,
+  currency_str: &str,
+  options: &DisplayOptions,
+) -> String {
+  let amount = format!("{}{}", options.table_amount(record.amount), currency_str);
+  match income_id {
+    Some(id) if record.category == id => format!("+{}", amount).green().to_string(),
+    Some(_) => format!("-{}", amount).red().to_string(),
+    None => amount,
+  }
+}
+
+/// Write `part` of the tracker as JSON, for `fintrack dump` and its `--out`
+pub fn write_tracker_data(
+  tracker_data: &TrackerData,
+  part: DumpPart,
+  compact: bool,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
+  let mut writer = io::BufWriter::new(writer);
+  match (part, compact) {
+    (DumpPart::Records, true) => serde_json::to_writer(&mut writer, &tracker_data.records)?,
+    (DumpPart::Records, false) => serde_json::to_writer_pretty(&mut writer, &tracker_data.records)?,
+    (DumpPart::Meta, true) => serde_json::to_writer(&mut writer, &tracker_meta(tracker_data))?,
+    (DumpPart::Meta, false) => serde_json::to_writer_pretty(&mut writer, &tracker_meta(tracker_data))?,
+    (DumpPart::All, true) => serde_json::to_writer(&mut writer, tracker_data)?,
+    (DumpPart::All, false) => serde_json::to_writer_pretty(&mut writer, tracker_data)?,
+  }
+  writeln!(writer)?;
+  writer.flush()
+}
+
+/// The tracker without `records` and `history`, which make up nearly all of a
+/// big one
+fn tracker_meta(tracker_data: &TrackerData) -> Value {
+  let mut meta = serde_json::to_value(tracker_data).expect("tracker serializes");
+  if let Some(fields) = meta.as_object_mut() {
+    fields.remove("records");
+    fields.remove("history");
+  }
+  meta
+}
+
+/// Write total summary with formatting
+fn write_total_summary(
+  totals: &crate::Total,
+  options: &DisplayOptions,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
+  let currency_str = options.currency_suffix(Some(&totals.currency));
+  let table_amount = |amount: f64| format!("{}{}", options.table_amount(amount), currency_str);
+
+  writeln!(writer, "{}", t!("summary-title").bright_white().bold())?;
+  match (&totals.start, &totals.end) {
+    (None, None) => {}
+    (None, Some(end)) => writeln!(writer, "  {} {}", t!("summary-as-of").bright_white(), end)?,
+    (start, end) => {
+      let open = |date: &Option<String>| date.clone().unwrap_or_else(|| "…".to_string());
+      writeln!(
+        writer,
+        "  {} {} – {}",
+        t!("summary-period").bright_white(),
+        open(start),
+        open(end)
+      )?;
+    }
+  }
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("summary-opening").bright_white(),
+    format!(
+      "{} {}",
+      format_amount(totals.opening_balance),
+      totals.currency
+    )
+    .bright_green()
+  )?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("summary-income").bright_white(),
+    format!("{} {}", format_amount(totals.income_total), totals.currency).bright_green()
+  )?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("summary-expenses").bright_white(),
+    format!(
+      "{} {}",
+      format_amount(totals.expenses_total),
+      totals.currency
+    )
+    .bright_red()
+  )?;
+  if let Some(investments) = totals.investments {
+    writeln!(
+      writer,
+      "  {} {}",
+      t!("summary-investments").bright_white(),
+      format!("{} {}", format_amount(investments), totals.currency).bright_green()
+    )?;
+  }
+  writeln!(writer, "  {}", "──────────────────────────────".dimmed())?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("summary-net").bright_white().bold(),
+    format!("{} {}", format_amount(totals.total()), totals.currency)
+      .bright_cyan()
+      .bold()
+  )?;
+
+  if let Some(saved) = totals.round_up_savings {
+    writeln!(
+      writer,
+      "  {} {}",
+      t!("summary-round-up").bright_white(),
+      format!("{} {}", format_amount(saved), totals.currency).bright_green()
+    )?;
+  }
+
+  if !totals.budgets.is_empty() {
+    let remaining: f64 = totals.budgets.iter().map(|b| b.remaining).sum();
+    let remaining_text = format!("{} {}", format_amount(remaining), totals.currency);
+    writeln!(
+      writer,
+      "  {} {}",
+      t!("summary-budget-remaining").bright_white(),
+      if remaining < 0.0 { remaining_text.bright_red() } else { remaining_text.bright_green() }
+    )?;
+
+    // Only budgets with rollover carry anything
+    let carried = totals.budgets.iter().any(|budget| budget.carried != 0.0);
+    let mut builder = Builder::default();
+    let mut header = vec![t!("column-subcategory"), t!("column-budget")];
+    if carried {
+      header.push(t!("column-carried"));
+    }
+    header.extend([t!("column-spent"), t!("column-remaining")]);
+    builder.push_record(header);
+    for budget in &totals.budgets {
+      let mut row = vec![budget.subcategory.clone(), table_amount(budget.budget)];
+      if carried {
+        row.push(table_amount(budget.carried));
+      }
+      row.extend([table_amount(budget.spent), table_amount(budget.remaining)]);
+      builder.push_record(row);
+    }
+    let mut table = builder.build();
+    apply_table_style(&mut table, options.table_style);
+    table.modify(Columns::new(1..), Alignment::right());
+    writeln!(writer)?;
+    writeln!(writer, "{}", table)?;
+  }
+
+  write_currency_totals(&totals.by_currency, &totals.currency, options, writer)?;
+
+  let Some(by) = totals.by else {
+    return Ok(());
+  };
+  let mut builder = Builder::default();
+  builder.push_record([
+    match by {
+      crate::GroupBy::Category => t!("column-category"),
+      crate::GroupBy::Subcategory => t!("column-subcategory"),
+      crate::GroupBy::Tag => t!("column-tag"),
+    },
+    t!("column-income"),
+    t!("column-expenses"),
+    t!("column-net"),
+  ]);
+  for group in &totals.groups {
+    builder.push_record([
+      group.name.clone(),
+      table_amount(group.income),
+      table_amount(group.expenses),
+      table_amount(group.net),
+    ]);
+  }
+  let mut table = builder.build();
+  apply_table_style(&mut table, options.table_style);
+  table.modify(Columns::new(1..), Alignment::right());
+
+  writeln!(writer)?;
+  writeln!(writer, "{}", table)?;
+  Ok(())
+}
+
+/// "Monthly Report: 03-2025", or the year or week it covers
+pub fn report_title(report: &crate::MonthlyReport) -> String {
+  let key = match report.period {
+    crate::ReportPeriod::Month => "report-title",
+    crate::ReportPeriod::Year => "report-title-year",
+    crate::ReportPeriod::Week => "report-title-week",
+  };
+  t!(key, month = report.month.clone())
+}
+
+fn write_report(report: &crate::MonthlyReport, writer: &mut impl io::Write) -> io::Result<()> {
+  let money = |amount: f64| format!("{} {}", format_amount(amount), report.currency);
+
+  writeln!(
+    writer,
+    "{}",
+    report_title(report).bright_white().bold()
+  )?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("report-records").bright_white(),
+    report.records.to_string().bright_cyan()
+  )?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("report-opening").bright_white(),
+    money(report.opening_balance).bright_green()
+  )?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("report-income").bright_white(),
+    money(report.income).bright_green()
+  )?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("report-expenses").bright_white(),
+    money(report.expenses).bright_red()
+  )?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("report-net").bright_white(),
+    money(report.net()).bright_cyan()
+  )?;
+  writeln!(writer, "  {}", "──────────────────────────────".dimmed())?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("report-closing").bright_white().bold(),
+    money(report.closing_balance).bright_cyan().bold()
+  )?;
+  if let Some(snapshot) = report.snapshot {
+    // Only a hand-edited tracker can drift from the balance saved at close
+    if (snapshot - report.closing_balance).abs() < 0.005 {
+      writeln!(writer, "  {}", t!("report-closed").dimmed())?;
+    } else {
+      writeln!(
+        writer,
+        "  {}",
+        t!("report-snapshot-mismatch", snapshot = money(snapshot)).bright_yellow()
+      )?;
+    }
+  }
+
+  if report.records == 0 {
+    writeln!(writer)?;
+    writeln!(writer, "  {}", t!("report-empty").dimmed())?;
+    return Ok(());
+  }
+
+  let groups = [
+    ("report-income-by-subcategory", &report.income_by_subcategory),
+    ("report-expenses-by-subcategory", &report.expenses_by_subcategory),
+  ];
+  for (title, totals) in groups {
+    if totals.is_empty() {
+      continue;
+    }
+    writeln!(writer)?;
+    writeln!(writer, "  {}", t!(title).bright_white().bold())?;
+    for (name, total) in totals {
+      writeln!(writer, "    {}: {}", name.bright_white(), money(*total).bright_green())?;
+    }
+  }
+  Ok(())
+}
+
+fn write_deductions(report: &crate::DeductionReport, writer: &mut impl io::Write) -> io::Result<()> {
+  let money = |amount: f64| format!("{} {}", format_amount(amount), report.currency);
+
+  writeln!(
+    writer,
+    "{}",
+    t!("deductions-title", year = report.year.clone()).bright_white().bold()
+  )?;
+  if report.records == 0 {
+    writeln!(writer, "  {}", t!("deductions-empty").dimmed())?;
+    return Ok(());
+  }
+
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("report-records").bright_white(),
+    report.records.to_string().bright_cyan()
+  )?;
+  for (name, total) in &report.by_subcategory {
+    writeln!(writer, "    {}: {}", name.bright_white(), money(*total).bright_green())?;
+  }
+  writeln!(writer, "  {}", "──────────────────────────────".dimmed())?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("deductions-total").bright_white().bold(),
+    money(report.total).bright_cyan().bold()
+  )?;
+  Ok(())
+}
+
+fn write_reconciliation(reconciliation: &crate::Reconciliation, writer: &mut impl io::Write) -> io::Result<()> {
+  let money = |amount: f64| format!("{} {}", format_amount(amount), reconciliation.currency);
+
+  writeln!(
+    writer,
+    "{}",
+    t!("reconcile-title", date = reconciliation.date.clone()).bright_white().bold()
+  )?;
+  writeln!(writer, "  {} {}", t!("reconcile-recorded").bright_white(), money(reconciliation.recorded))?;
+  writeln!(writer, "  {} {}", t!("reconcile-statement").bright_white(), money(reconciliation.statement))?;
+  if reconciliation.drift == 0.0 {
+    writeln!(writer, "  {}", t!("reconcile-balanced").bright_green())?;
+    return Ok(());
+  }
+
+  let drift = format!("{}{}", if reconciliation.drift > 0.0 { "+" } else { "" }, money(reconciliation.drift));
+  writeln!(writer, "  {} {}", t!("reconcile-drift").bright_white(), drift.bright_red().bold())?;
+  match reconciliation.adjustment {
+    Some(id) => writeln!(writer, "  {}", t!("reconcile-adjusted", id = id).bright_green())?,
+    None => writeln!(writer, "  {}", t!("reconcile-hint").dimmed())?,
+  }
+  Ok(())
+}
+
+/// Records and subcategories added (+), changed (~) and removed (-), then the
+/// totals before and after. `unchanged` is shown instead when nothing differs.
+fn write_tracker_diff(
+  diff: &crate::TrackerDiff,
+  title: String,
+  unchanged: String,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
+  if diff.is_empty() {
+    return writeln!(writer, "{}", unchanged.yellow());
+  }
+  writeln!(writer, "{}", title.bright_white().bold())?;
+
+  let currency = &diff.after.currency;
+  let line = |record: &Record, tracker_data: &TrackerData| {
+    let name = |name: Option<&String>| name.cloned().unwrap_or_else(|| "Unknown".to_string());
+    let line = format!(
+      "#{} {} · {} · {} {} · {} · {}",
+      record.id,
+      name(tracker_data.category_name(record.category)),
+      name(tracker_data.subcategory_name(record.subcategory)),
+      format_amount(record.amount),
+      currency,
+      record.date,
+      record.description
+    );
+    line.trim_end_matches(" · ").to_string()
+  };
+  for record in &diff.added {
+    writeln!(writer, "  {} {}", "+".green().bold(), line(record, &diff.after))?;
+  }
+  for (before, after) in &diff.changed {
+    let changes: Vec<String> = record_changes(before, &diff.before, after, &diff.after)
+      .into_iter()
+      .map(|(field, was, now)| format!("{}: {} → {}", field, was, now))
+      .collect();
+    writeln!(writer, "  {} #{} {}", "~".yellow().bold(), after.id, changes.join("; "))?;
+  }
+  for record in &diff.removed {
+    writeln!(writer, "  {} {}", "-".red().bold(), line(record, &diff.before))?;
+  }
+  for name in &diff.subcategories_added {
+    writeln!(writer, "  {} {}", "+".green().bold(), t!("diff-subcategory", name = name.clone()))?;
+  }
+  for (old, new) in &diff.subcategories_renamed {
+    writeln!(
+      writer,
+      "  {} {}",
+      "~".yellow().bold(),
+      t!("diff-subcategory-renamed", old = old.clone(), new = new.clone())
+    )?;
+  }
+  for name in &diff.subcategories_removed {
+    writeln!(writer, "  {} {}", "-".red().bold(), t!("diff-subcategory", name = name.clone()))?;
+  }
+  if diff.other_changes {
+    writeln!(writer, "  {}", t!("diff-other-changes").dimmed())?;
+  }
+
+  let (income_before, expenses_before) = diff.before.totals();
+  let (income_after, expenses_after) = diff.after.totals();
+  for (label, before, after) in [
+    ("diff-income", income_before, income_after),
+    ("diff-expenses", expenses_before, expenses_after),
+    ("diff-balance", diff.before.net_balance(), diff.after.net_balance()),
+  ] {
+    writeln!(
+      writer,
+      "  {} {} → {} {}",
+      t!(label).bright_white(),
+      format_amount(before),
+      format_amount(after).bold(),
+      currency
+    )?;
+  }
+  Ok(())
+}
+
+/// The fields that differ between two versions of a record, as
+/// `(field, before, after)`
+fn record_changes(
+  before: &Record,
+  before_data: &TrackerData,
+  after: &Record,
+  after_data: &TrackerData,
+) -> Vec<(&'static str, String, String)> {
+  let optional = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+  let name = |name: Option<&String>| name.cloned().unwrap_or_else(|| "Unknown".to_string());
+  let fields = [
+    (
+      "category",
+      name(before_data.category_name(before.category)),
+      name(after_data.category_name(after.category)),
+    ),
+    (
+      "subcategory",
+      name(before_data.subcategory_name(before.subcategory)),
+      name(after_data.subcategory_name(after.subcategory)),
+    ),
+    ("amount", format_amount(before.amount), format_amount(after.amount)),
+    ("date", before.date.clone(), after.date.clone()),
+    (
+      "description",
+      format!("\"{}\"", before.description),
+      format!("\"{}\"", after.description),
+    ),
+    ("entered by", optional(&before.entered_by), optional(&after.entered_by)),
+    ("deductible", before.deductible.to_string(), after.deductible.to_string()),
+    ("project", optional(&before.project), optional(&after.project)),
+    ("tags", before.tags.join(", "), after.tags.join(", ")),
+  ];
+  fields.into_iter().filter(|(_, was, now)| was != now).collect()
+}
+
+/// The digest as plain lines, title first, for the terminal or a notification
+pub fn digest_lines(digest: &crate::WeeklyDigest) -> Vec<String> {
+  let money = |amount: f64| format!("{} {}", format_amount(amount), digest.currency);
+
+  let change = match digest.change() {
+    None => t!("digest-first"),
+    Some(change) if change.round() == 0.0 => t!("digest-same"),
+    Some(change) => t!(
+      if change > 0.0 { "digest-up" } else { "digest-down" },
+      percent = format!("{:.0}", change.abs()),
+      amount = money(digest.last_week)
+    ),
+  };
+  let mut lines = vec![
+    t!("digest-title", start = digest.start.clone(), end = digest.end.clone()),
+    format!("{}, {}", t!("digest-spent", amount = money(digest.spent)), change),
+  ];
+  if !digest.top.is_empty() {
+    let top: Vec<String> = digest
+      .top
+      .iter()
+      .map(|(name, total)| format!("{} {}", name, format_amount(*total)))
+      .collect();
+    lines.push(t!("digest-top", subcategories = top.join(", ")));
+  }
+  for expense in &digest.unusual {
+    lines.push(t!(
+      "digest-unusual",
+      id = expense.id,
+      description = expense.description.clone(),
+      amount = money(expense.amount),
+      subcategory = expense.subcategory.clone(),
+      typical = format_amount(expense.typical)
+    ));
+  }
+  lines
+}
+
+fn write_schedules(schedules: &[crate::Schedule], currency: &str, writer: &mut impl io::Write) -> io::Result<()> {
+  if schedules.is_empty() {
+    writeln!(writer, "{}", t!("schedules-none").yellow())?;
+    return Ok(());
+  }
+
+  writeln!(writer, "{}", t!("schedules-title").bright_white().bold())?;
+  let amounts: Vec<String> = schedules
+    .iter()
+    .map(|s| {
+      let sign = if s.category == "income" { "" } else { "-" };
+      format!("{}{} {}", sign, format_amount(s.amount), currency)
+    })
+    .collect();
+  let amount_width = amounts.iter().map(|a| a.chars().count()).max().unwrap_or(0);
+  let subcategory_width = schedules.iter().map(|s| s.subcategory.chars().count()).max().unwrap_or(0);
+  let frequencies: Vec<String> = schedules.iter().map(|s| t!(&format!("frequency-{}", s.every))).collect();
+  let frequency_width = frequencies.iter().map(|f| f.chars().count()).max().unwrap_or(0);
+  for ((schedule, amount), frequency) in schedules.iter().zip(amounts).zip(frequencies) {
+    let amount = format!("{:>width$}", amount, width = amount_width);
+    let amount = if schedule.category == "income" { amount.bright_green() } else { amount.bright_red() };
+    write!(
+      writer,
+      "  {:>3}  {}  {:<subcategory_width$}  {:<frequency_width$}  {}",
+      schedule.id.to_string().bright_white(),
+      amount,
+      schedule.subcategory,
+      frequency,
+      t!("schedules-next", date = schedule.next.clone()).dimmed(),
+      subcategory_width = subcategory_width,
+      frequency_width = frequency_width
+    )?;
+    if !schedule.description.is_empty() {
+      write!(writer, "  {}", schedule.description.dimmed())?;
+    }
+    writeln!(writer)?;
+  }
+  Ok(())
+}
+
+/// "01-03-2025 – 05-03-2025", or one date when the project spans a single day
+fn project_dates(project: &crate::ProjectSummary) -> String {
+  match (&project.start, &project.end) {
+    (Some(start), Some(end)) if start != end => format!("{} – {}", start, end),
+    (Some(date), _) | (None, Some(date)) => date.clone(),
+    (None, None) => String::new(),
+  }
+}
+
+fn write_projects(projects: &[crate::ProjectSummary], currency: &str, writer: &mut impl io::Write) -> io::Result<()> {
+  if projects.is_empty() {
+    writeln!(writer, "{}", t!("projects-none").yellow())?;
+    return Ok(());
+  }
+
+  writeln!(writer, "{}", t!("projects-title").bright_white().bold())?;
+  let width = projects.iter().map(|p| p.name.chars().count()).max().unwrap_or(0);
+  let amounts: Vec<String> = projects
+    .iter()
+    .map(|p| format!("{} {}", format_amount(p.spent), currency))
+    .collect();
+  let amount_width = amounts.iter().map(|a| a.chars().count()).max().unwrap_or(0);
+  for (project, amount) in projects.iter().zip(amounts) {
+    writeln!(
+      writer,
+      "  {:<width$}  {:>amount_width$}  {}",
+      project.name.bright_white(),
+      amount.bright_red(),
+      project_dates(project).dimmed(),
+      width = width,
+      amount_width = amount_width
+    )?;
+  }
+  Ok(())
+}
+
+fn write_project(project: &crate::ProjectSummary, currency: &str, writer: &mut impl io::Write) -> io::Result<()> {
+  let money = |amount: f64| format!("{} {}", format_amount(amount), currency);
+
+  writeln!(
+    writer,
+    "{}",
+    t!("project-title", name = project.name.clone()).bright_white().bold()
+  )?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("report-records").bright_white(),
+    project.records.to_string().bright_cyan()
+  )?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("project-dates").bright_white(),
+    project_dates(project).bright_cyan()
+  )?;
+  if project.income > 0.0 {
+    writeln!(
+      writer,
+      "  {} {}",
+      t!("report-income").bright_white(),
+      money(project.income).bright_green()
+    )?;
+  }
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("project-spent").bright_white().bold(),
+    money(project.spent).bright_red().bold()
+  )?;
+
+  if !project.by_subcategory.is_empty() {
+    writeln!(writer)?;
+    writeln!(writer, "  {}", t!("report-expenses-by-subcategory").bright_white().bold())?;
+    for (name, total) in &project.by_subcategory {
+      writeln!(writer, "    {}: {}", name.bright_white(), money(*total).bright_green())?;
+    }
+  }
+  Ok(())
+}
+
+fn write_holdings(holdings: &[crate::Holding], currency: &str, writer: &mut impl io::Write) -> io::Result<()> {
+  if holdings.is_empty() {
+    writeln!(writer, "{}", t!("holdings-none").yellow())?;
+    return Ok(());
+  }
+
+  let header = [
+    t!("holdings-symbol"),
+    t!("holdings-quantity"),
+    t!("holdings-cost"),
+    t!("holdings-price"),
+    t!("holdings-value"),
+    t!("holdings-gain"),
+  ];
+  let rows: Vec<[String; 6]> = holdings
+    .iter()
+    .map(|h| {
+      let percent = match h.cost_basis > 0.0 {
+        true => format!(" ({:+.1}%)", h.unrealized_gain / h.cost_basis * 100.0),
+        false => String::new(),
+      };
+      [
+        h.symbol.clone(),
+        h.quantity.to_string(),
+        format_amount(h.cost_basis),
+        h.price.map_or_else(|| "-".to_string(), format_amount),
+        format_amount(h.market_value),
+        format!("{}{}", format_amount(h.unrealized_gain), percent),
+      ]
+    })
+    .collect();
+
+  let mut widths = header.clone().map(|title| title.chars().count());
+  for row in &rows {
+    for (width, cell) in widths.iter_mut().zip(row) {
+      *width = (*width).max(cell.chars().count());
+    }
+  }
+
+  writeln!(writer, "{}", t!("holdings-title", currency = currency.to_string()).bright_white().bold())?;
+  let line: Vec<String> = header
+    .iter()
+    .zip(widths)
+    .enumerate()
+    .map(|(i, (title, width))| match i {
+      0 => format!("{:<width$}", title),
+      _ => format!("{:>width$}", title),
+    })
+    .collect();
+  writeln!(writer, "  {}", line.join("  ").bright_white())?;
+
+  for (row, holding) in rows.iter().zip(holdings) {
+    let cells: Vec<String> = row
+      .iter()
+      .zip(widths)
+      .enumerate()
+      .map(|(i, (cell, width))| match i {
+        0 => format!("{:<width$}", cell).bright_cyan().to_string(),
+        5 if holding.unrealized_gain < 0.0 => format!("{:>width$}", cell).bright_red().to_string(),
+        5 => format!("{:>width$}", cell).bright_green().to_string(),
+        _ => format!("{:>width$}", cell),
+      })
+      .collect();
+    writeln!(writer, "  {}", cells.join("  "))?;
+  }
+
+  let value: f64 = holdings.iter().map(|h| h.market_value).sum();
+  let gain: f64 = holdings.iter().map(|h| h.unrealized_gain).sum();
+  writeln!(writer, "  {}", "──────────────────────────────".dimmed())?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("holdings-total-value").bright_white().bold(),
+    format!("{} {}", format_amount(value), currency).bright_cyan().bold()
+  )?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("holdings-total-gain").bright_white().bold(),
+    format!("{} {}", format_amount(gain), currency).bright_cyan().bold()
+  )?;
+  Ok(())
+}
+
+fn write_bills(bills: &[crate::BillStatus], currency: &str, writer: &mut impl io::Write) -> io::Result<()> {
+  if bills.is_empty() {
+    writeln!(writer, "{}", t!("bills-none").yellow())?;
+    return Ok(());
+  }
+
+  writeln!(writer, "{}", t!("bills-title").bright_white().bold())?;
+  let width = bills.iter().map(|b| b.name.chars().count()).max().unwrap_or(0);
+  let amounts: Vec<String> = bills
+    .iter()
+    .map(|b| format!("{} {}", format_amount(b.amount), currency))
+    .collect();
+  let amount_width = amounts.iter().map(|a| a.chars().count()).max().unwrap_or(0);
+  for (bill, amount) in bills.iter().zip(amounts) {
+    let when = match bill.days {
+      ..0 => t!("bills-overdue", days = -bill.days).bright_red().bold(),
+      0 => t!("bills-due-today").bright_yellow().bold(),
+      days => t!("bills-due-in", days = days).dimmed(),
+    };
+    writeln!(
+      writer,
+      "  {:<width$}  {:>amount_width$}  {}  {}",
+      bill.name.bright_white(),
+      amount,
+      t!("owed-due", date = bill.due.clone()),
+      when,
+      width = width,
+      amount_width = amount_width
+    )?;
+  }
+
+  let total: f64 = bills.iter().map(|b| b.amount).sum();
+  writeln!(writer, "  {}", "──────────────────────────────".dimmed())?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("bills-total").bright_white().bold(),
+    format!("{} {}", format_amount(total), currency).bright_cyan().bold()
+  )?;
+  Ok(())
+}
+
+fn write_subscriptions(
+  subscriptions: &[crate::SubscriptionStatus],
+  currency: &str,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
+  if subscriptions.is_empty() {
+    writeln!(writer, "{}", t!("subscriptions-none").yellow())?;
+    return Ok(());
+  }
+
+  writeln!(writer, "{}", t!("subscriptions-title").bright_white().bold())?;
+  let width = subscriptions.iter().map(|s| s.name.chars().count()).max().unwrap_or(0);
+  let amounts: Vec<String> = subscriptions
+    .iter()
+    .map(|s| {
+      let every = s.every.to_string();
+      format!("{} {} {}", format_amount(s.amount), currency, t!("subscriptions-every", every = every))
+    })
+    .collect();
+  let amount_width = amounts.iter().map(|a| a.chars().count()).max().unwrap_or(0);
+  for (subscription, amount) in subscriptions.iter().zip(amounts) {
+    let when = match subscription.days {
+      0 => t!("subscriptions-renews-today").bright_yellow().bold(),
+      days @ 1..=7 => t!("bills-due-in", days = days).bright_yellow(),
+      days => t!("bills-due-in", days = days).dimmed(),
+    };
+    write!(
+      writer,
+      "  {:<width$}  {:>amount_width$}  {} {}  {}",
+      subscription.name.bright_white(),
+      amount,
+      t!("subscriptions-renews", date = subscription.renews.clone()),
+      when,
+      t!("subscriptions-payments", count = subscription.payments).dimmed(),
+      width = width,
+      amount_width = amount_width
+    )?;
+    if let (Some(date), Some(days)) = (&subscription.cancel_by, subscription.cancel_days) {
+      let cancel = t!("subscriptions-cancel-by", date = date.clone());
+      let cancel = match days {
+        ..0 => t!("subscriptions-cancel-passed", date = date.clone()).dimmed(),
+        0..=7 => cancel.bright_red().bold(),
+        _ => cancel.normal(),
+      };
+      write!(writer, "  {}", cancel)?;
+    }
+    writeln!(writer)?;
+  }
+
+  let total: f64 = subscriptions.iter().map(|s| s.monthly_cost).sum();
+  writeln!(writer, "  {}", "──────────────────────────────".dimmed())?;
+  writeln!(
+    writer,
+    "  {} {}",
+    t!("subscriptions-total").bright_white().bold(),
+    format!("{} {}", format_amount(total), currency).bright_cyan().bold()
+  )?;
+  Ok(())
+}
+
+fn write_merge(summary: &crate::MergeSummary, writer: &mut impl io::Write) -> io::Result<()> {
+  let title = match summary.dry_run {
+    true => t!("merge-title-dry-run", source = summary.source.clone()),
+    false => t!("merge-title", source = summary.source.clone()),
+  };
+  writeln!(writer, "{}", title.bright_white().bold())?;
+  writeln!(
+    writer,
+    "  {}",
+    t!("merge-records", count = summary.records_added).bright_green()
+  )?;
+  if summary.duplicates_skipped > 0 {
+    writeln!(
+      writer,
+      "  {}",
+      t!("merge-duplicates", count = summary.duplicates_skipped).yellow()
+    )?;
+  }
+  for (key, names) in [
+    ("merge-subcategories", &summary.subcategories_added),
+    ("merge-budgets-added", &summary.budgets_added),
+    ("merge-budgets-replaced", &summary.budgets_replaced),
+  ] {
+    if !names.is_empty() {
+      writeln!(writer, "  {} {}", t!(key), names.join(", ").bright_cyan())?;
+    }
+  }
+  if summary.dry_run {
+    writeln!(writer, "{}", t!("merge-dry-run").dimmed())?;
+  }
+  Ok(())
+}
+
+fn write_tokens(tokens: &[crate::utils::tokens::ApiToken], writer: &mut impl io::Write) -> io::Result<()> {
+  if tokens.is_empty() {
+    writeln!(writer, "{}", t!("tokens-none").yellow())?;
+    return Ok(());
+  }
+
+  writeln!(writer, "{}", t!("tokens-title").bright_white().bold())?;
+  let width = tokens.iter().map(|t| t.name.chars().count()).max().unwrap_or(0);
+  for token in tokens {
+    let created = chrono::DateTime::parse_from_rfc3339(&token.created_at)
+      .map_or_else(|_| token.created_at.clone(), |d| d.format("%d-%m-%Y").to_string());
+    writeln!(
+      writer,
+      "  {:<width$}  {}",
+      token.name.bright_white(),
+      t!("tokens-created", date = created).dimmed(),
+      width = width
+    )?;
+  }
+  Ok(())
+}
+
+fn write_history(
+  id: usize,
+  versions: &[crate::HistoryEntry],
+  tracker_data: &TrackerData,
+  options: &DisplayOptions,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
+  writeln!(writer, "{}", t!("history-title", id = id.to_string()).bright_white().bold())?;
+
+  let currency_str = options.currency_suffix(Some(&tracker_data.currency));
+  let income_id = tracker_data.categories.get("income").copied();
+  let mut builder = Builder::default();
+  builder.push_record([
+    t!("column-version"),
+    t!("column-since"),
+    t!("column-category"),
+    t!("column-subcategory"),
+    t!("column-amount"),
+    t!("column-date"),
+    t!("column-description"),
+  ]);
+
+  for entry in versions {
+    let r = &entry.record;
+    let mut version = entry.version.to_string();
+    if let Some(reverted_to) = entry.reverted_to {
+      version = format!("{} {}", version, t!("history-reverted", version = reverted_to.to_string()));
+    }
+    if entry.current {
+      version = format!("{} {}", version, t!("history-current")).bright_white().bold().to_string();
+    }
+    let since = entry
+      .since
+      .as_deref()
+      .and_then(|since| chrono::DateTime::parse_from_rfc3339(since).ok())
+      .map(|since| since.format("%d-%m-%Y %H:%M").to_string())
+      .unwrap_or_else(|| "—".to_string());
+
+    builder.push_record([
+      version,
+      since,
+      tracker_data
+        .category_name(r.category)
+        .cloned()
+        .unwrap_or_else(|| t!("category-fallback", id = r.category)),
+      tracker_data
+        .subcategory_name(r.subcategory)
+        .cloned()
+        .unwrap_or_else(|| t!("subcategory-fallback", id = r.subcategory)),
+      signed_amount(r, income_id, &currency_str, options),
+      options.date_format.format(&r.date),
+      if r.description.is_empty() {
+        t!("no-description")
+      } else {
+        r.description.clone()
+      },
+    ]);
+  }
+
+  let mut table = builder.build();
+  apply_table_style(&mut table, options.table_style);
+  writeln!(writer, "{}", table)?;
+  Ok(())
+}
+
+/// `status --short`: uncoloured, since prompts count escape codes as width
+fn status_line(summary: &crate::StatusSummary) -> String {
+  let mut parts = vec![
+    format!("{} {}", format_amount(summary.balance), summary.currency),
+    t!("status-short-today", amount = format_amount(summary.spent_today)),
+  ];
+  match summary.budget_remaining {
+    Some(_) if summary.over_budget > 0 => {
+      parts.push(t!("status-short-over", count = summary.over_budget))
+    }
+    Some(remaining) => parts.push(t!("status-short-budget", amount = format_amount(remaining))),
+    None => {}
+  }
+  for limit in summary.limits.iter().filter(|limit| limit.is_over()) {
+    parts.push(match limit.period {
+      LimitPeriod::Daily => t!("status-short-daily-over"),
+      LimitPeriod::Weekly => t!("status-short-weekly-over"),
+    });
+  }
+  if let Some(payday) = &summary.payday {
+    parts.push(t!("status-short-payday", days = payday.days));
+  }
+  parts.join(" | ")
+}
+
+fn write_status(summary: &crate::StatusSummary, writer: &mut impl io::Write) -> io::Result<()> {
+  let money = |amount: f64| format!("{} {}", format_amount(amount), summary.currency);
+  writeln!(writer, "{}", t!("status-title", date = summary.date.clone()).bright_white().bold())?;
+  let balance = if summary.balance < 0.0 {
+    money(summary.balance).bright_red().bold()
+  } else {
+    money(summary.balance).bright_cyan().bold()
+  };
+  writeln!(writer, "  {} {}", t!("status-balance").bright_white(), balance)?;
+  writeln!(writer, "  {} {}", t!("status-spent-today").bright_white(), money(summary.spent_today))?;
+  if let Some(remaining) = summary.budget_remaining {
+    let over = if summary.over_budget > 0 {
+      format!(" {}", t!("status-over-budget", count = summary.over_budget).bright_red())
+    } else {
+      String::new()
+    };
+    writeln!(
+      writer,
+      "  {} {}{}",
+      t!("summary-budget-remaining").bright_white(),
+      money(remaining),
+      over
+    )?;
+  }
+  for limit in &summary.limits {
+    let label = match limit.period {
+      LimitPeriod::Daily => t!("status-limit-daily"),
+      LimitPeriod::Weekly => t!("status-limit-weekly"),
+    };
+    let bar = limit_bar(limit.spent, limit.limit);
+    let amounts = t!(
+      "budgets-spent",
+      spent = format_amount(limit.spent),
+      budget = money(limit.limit)
+    );
+    if limit.is_over() {
+      writeln!(writer, "  {} {} {}", label.bright_white(), bar.bright_red(), amounts.bright_red())?;
+    } else {
+      writeln!(writer, "  {} {} {}", label.bright_white(), bar.bright_green(), amounts)?;
+    }
+  }
+  if let Some(payday) = &summary.payday {
+    let countdown = t!("status-payday-in", days = payday.days, date = payday.date.clone());
+    writeln!(writer, "  {} {}", t!("status-payday").bright_white(), countdown)?;
+    if let Some(per_day) = payday.per_day {
+      let amount = if per_day < 0.0 {
+        money(per_day).bright_red()
+      } else {
+        money(per_day).bright_green()
+      };
+      writeln!(writer, "  {} {}", t!("status-per-day").bright_white(), amount)?;
+    }
+  }
+  Ok(())
+}
+
+/// `[██████░░░░]`, full once `spent` reaches `limit`
+fn limit_bar(spent: f64, limit: f64) -> String {
+  const WIDTH: usize = 10;
+  let filled = if limit > 0.0 {
+    ((spent / limit * WIDTH as f64).round() as usize).min(WIDTH)
+  } else {
+    WIDTH
+  };
+  format!("[{}{}]", "█".repeat(filled), "░".repeat(WIDTH - filled))
+}
+
+fn write_budgets(
+  month: &str,
+  budgets: &[crate::BudgetStatus],
+  currency: &str,
   writer: &mut impl io::Write,
 ) -> io::Result<()> {
-  let currency_str = currency
-    .map(|c| format!(" {}", c))
-    .unwrap_or_else(|| "".to_string());
+  if budgets.is_empty() {
+    writeln!(writer, "{}", t!("budgets-none").yellow())?;
+    return Ok(());
+  }
 
-  let table_data: Vec<RecordRow> = records
+  writeln!(writer, "{}", t!("budgets-title", month = month.to_string()).bright_white().bold())?;
+  let width = budgets.iter().map(|b| b.subcategory.chars().count()).max().unwrap_or(0);
+  let spent: Vec<String> = budgets
     .iter()
-    .map(|r| {
-      let category_name = tracker_data
-        .and_then(|td| td.category_name(r.category))
-        .cloned()
-        .unwrap_or_else(|| format!("Category {}", r.category));
-
-      let subcategory_name = tracker_data
-        .and_then(|td| td.subcategory_name(r.subcategory))
-        .cloned()
-        .unwrap_or_else(|| format!("Subcategory {}", r.subcategory));
-
-      RecordRow {
-        id: r.id.to_string(),
-        category: category_name,
-        subcategory: subcategory_name,
-        amount: format!("{}{}", format_amount(r.amount), currency_str),
-        date: r.date.clone(),
-        description: if r.description.is_empty() {
-          "(no description)".to_string()
-        } else {
-          r.description.clone()
-        },
+    .map(|b| {
+      let spent = t!(
+        "budgets-spent",
+        spent = format_amount(b.spent),
+        budget = format!("{} {}", format_amount(b.budget), currency)
+      );
+      if b.carried == 0.0 {
+        return spent;
       }
+      let sign = if b.carried > 0.0 { "+" } else { "" };
+      format!("{} {}", spent, t!("budgets-carried", amount = format!("{}{}", sign, format_amount(b.carried))))
     })
     .collect();
+  let spent_width = spent.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+  for (budget, spent) in budgets.iter().zip(spent) {
+    let left = if budget.remaining < 0.0 {
+      t!("budgets-over", amount = format_amount(-budget.remaining))
+    } else {
+      t!("budgets-left", amount = format_amount(budget.remaining))
+    };
+    let left = match budget.level {
+      crate::BudgetLevel::Ok => left.bright_green(),
+      crate::BudgetLevel::Warn => left.bright_yellow(),
+      crate::BudgetLevel::Error => left.bright_red().bold(),
+    };
+    writeln!(
+      writer,
+      "  {:<width$}  {:<spent_width$}  {}",
+      budget.subcategory.bright_white(),
+      spent,
+      left,
+      width = width,
+      spent_width = spent_width
+    )?;
+  }
 
-  let table = Table::new(&table_data).with(Style::modern()).to_string();
-
-  writeln!(writer, "{}", table)?;
-  Ok(())
-}
-
-/// Write tracker data (for dump command)
-fn write_tracker_data(tracker_data: &TrackerData, writer: &mut impl io::Write) -> io::Result<()> {
-  let json_string = serde_json::to_string_pretty(tracker_data)?;
-  writeln!(writer, "{}", json_string)?;
-  Ok(())
-}
-
-/// Write total summary with formatting
-fn write_total_summary(totals: &crate::Total, writer: &mut impl io::Write) -> io::Result<()> {
-  writeln!(writer, "{}", "Financial Summary:".bright_white().bold())?;
-  writeln!(
-    writer,
-    "  {} {}",
-    "Opening Balance:".bright_white(),
-    format!(
-      "{} {}",
-      format_amount(totals.opening_balance),
-      totals.currency
-    )
-    .bright_green()
-  )?;
-  writeln!(
-    writer,
-    "  {} {}",
-    "Total Income:".bright_white(),
-    format!("{} {}", format_amount(totals.income_total), totals.currency).bright_green()
-  )?;
+  let remaining: f64 = budgets.iter().map(|b| b.remaining).sum();
+  writeln!(writer, "  {}", "──────────────────────────────".dimmed())?;
   writeln!(
     writer,
     "  {} {}",
-    "Total Expenses:".bright_white(),
-    format!(
-      "{} {}",
-      format_amount(totals.expenses_total),
-      totals.currency
-    )
-    .bright_red()
+    t!("summary-budget-remaining").bright_white().bold(),
+    format!("{} {}", format_amount(remaining), currency).bright_cyan().bold()
   )?;
+  Ok(())
+}
+
+fn write_owed(balances: &[crate::OwedBalance], currency: &str, writer: &mut impl io::Write) -> io::Result<()> {
+  if balances.is_empty() {
+    writeln!(writer, "{}", t!("owed-none").yellow())?;
+    return Ok(());
+  }
+
+  writeln!(writer, "{}", t!("owed-title").bright_white().bold())?;
+  let width = balances.iter().map(|b| b.person.chars().count()).max().unwrap_or(0);
+  let (owes_you, you_owe) = (t!("owed-owes-you"), t!("owed-you-owe"));
+  let label_width = owes_you.chars().count().max(you_owe.chars().count());
+  for balance in balances {
+    let amount = format!("{} {}", format_amount(balance.balance.abs()), currency);
+    let (label, amount) = if balance.balance > 0.0 {
+      (&owes_you, amount.bright_green())
+    } else {
+      (&you_owe, amount.bright_red())
+    };
+    write!(
+      writer,
+      "  {:<width$}  {:<label_width$} {}",
+      balance.person.bright_white(),
+      label,
+      amount,
+      width = width,
+      label_width = label_width
+    )?;
+    if let Some(due) = &balance.due {
+      write!(writer, "  {}", t!("owed-due", date = due.clone()).dimmed())?;
+    }
+    if balance.overdue {
+      write!(writer, "  {}", t!("owed-overdue").bright_red().bold())?;
+    }
+    writeln!(writer)?;
+  }
+
+  let net: f64 = balances.iter().map(|b| b.balance).sum();
   writeln!(writer, "  {}", "──────────────────────────────".dimmed())?;
   writeln!(
     writer,
     "  {} {}",
-    "Net Balance:".bright_white().bold(),
-    format!("{} {}", format_amount(totals.total()), totals.currency)
-      .bright_cyan()
-      .bold()
+    t!("owed-net").bright_white().bold(),
+    format!("{} {}", format_amount(net), currency).bright_cyan().bold()
   )?;
   Ok(())
 }
 
+/// A standalone HTML page of a monthly report, for email
+pub fn report_html(report: &crate::MonthlyReport) -> String {
+  let money = |amount: f64| format!("{} {}", format_amount(amount), report.currency);
+  let row = |label: &str, value: String| {
+    format!("<tr><td>{}</td><td style=\"text-align:right\">{}</td></tr>", label, value)
+  };
+  let title = report_title(report);
+
+  let mut html = format!(
+    "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{0}</title></head>\n<body style=\"font-family:sans-serif\">\n<h2>{0}</h2>\n<table>\n",
+    title
+  );
+  for line in [
+    row(&t!("report-records"), report.records.to_string()),
+    row(&t!("report-opening"), money(report.opening_balance)),
+    row(&t!("report-income"), money(report.income)),
+    row(&t!("report-expenses"), money(report.expenses)),
+    row(&t!("report-net"), money(report.net())),
+    row(&format!("<b>{}</b>", t!("report-closing")), format!("<b>{}</b>", money(report.closing_balance))),
+  ] {
+    html.push_str(&line);
+    html.push('\n');
+  }
+  html.push_str("</table>\n");
+
+  let groups = [
+    ("report-income-by-subcategory", &report.income_by_subcategory),
+    ("report-expenses-by-subcategory", &report.expenses_by_subcategory),
+  ];
+  for (title, totals) in groups {
+    if totals.is_empty() {
+      continue;
+    }
+    html.push_str(&format!("<h3>{}</h3>\n<table>\n", t!(title)));
+    for (name, total) in totals {
+      html.push_str(&row(name, money(*total)));
+      html.push('\n');
+    }
+    html.push_str("</table>\n");
+  }
+
+  html.push_str("</body></html>\n");
+  html
+}
+
 /// Format amount with thousand separators and 2 decimal places
-fn format_amount(amount: f64) -> String {
-  let formatted = format!("{:.2}", amount);
+pub fn format_amount(amount: f64) -> String {
+  if amount < 0.0 && format!("{:.2}", amount) != "-0.00" {
+    return format!("-{}", format_amount(-amount));
+  }
+
+  let formatted = format!("{:.2}", amount.abs());
   let parts: Vec<&str> = formatted.split('.').collect();
   let integer_part = parts[0];
   let decimal_part = parts.get(1).unwrap_or(&"00");
@@ -481,26 +1974,28 @@ fn format_amount(amount: f64) -> String {
   format!("{}.{}", result, decimal_part)
 }
 
-/// Table row structure for records
-#[derive(Tabled)]
-struct RecordRow {
-  #[tabled(rename = "ID")]
-  id: String,
-  #[tabled(rename = "Category")]
-  category: String,
-  #[tabled(rename = "Subcategory")]
-  subcategory: String,
-  #[tabled(rename = "Amount")]
-  amount: String,
-  #[tabled(rename = "Date")]
-  date: String,
-  #[tabled(rename = "Description")]
-  description: String,
+/// Amounts of a thousand or more shortened to one decimal, e.g. 12.3k, 4.5M;
+/// smaller ones are left as [`format_amount`] shows them
+pub fn compact_amount(amount: f64) -> String {
+  const UNITS: [&str; 4] = ["k", "M", "B", "T"];
+
+  if amount.abs() < 1000.0 {
+    return format_amount(amount);
+  }
+  let mut short = amount / 1000.0;
+  let mut unit = 0;
+  // 999,960 rounds to 1000.0k, which is 1M
+  while (short.abs() * 10.0).round() >= 10_000.0 && unit + 1 < UNITS.len() {
+    short /= 1000.0;
+    unit += 1;
+  }
+  let short = format!("{:.1}", short);
+  format!("{}{}", short.strip_suffix(".0").unwrap_or(&short), UNITS[unit])
 }
 
 /// Write categories list
 fn write_categories_list(categories: &[(usize, String)], writer: &mut impl io::Write) -> io::Result<()> {
-  writeln!(writer, "{}", "Categories:".bright_white().bold())?;
+  writeln!(writer, "{}", t!("categories-title").bright_white().bold())?;
   for (id, name) in categories {
     writeln!(
       writer,
@@ -512,9 +2007,18 @@ fn write_categories_list(categories: &[(usize, String)], writer: &mut impl io::W
   Ok(())
 }
 
+/// Write the settings from `fintrack config list`
+fn write_settings(settings: &[(String, String)], writer: &mut impl io::Write) -> io::Result<()> {
+  writeln!(writer, "{}", t!("settings-title").bright_white().bold())?;
+  for (key, value) in settings {
+    writeln!(writer, "  {} = {}", key.cyan(), value.bright_white())?;
+  }
+  Ok(())
+}
+
 /// Write subcategories list
 fn write_subcategories_list(subcategories: &[(usize, String)], writer: &mut impl io::Write) -> io::Result<()> {
-  writeln!(writer, "{}", "Subcategories:".bright_white().bold())?;
+  writeln!(writer, "{}", t!("subcategories-title").bright_white().bold())?;
   for (id, name) in subcategories {
     writeln!(
       writer,
@@ -526,42 +2030,139 @@ fn write_subcategories_list(subcategories: &[(usize, String)], writer: &mut impl
   Ok(())
 }
 
+fn write_tags(tags: &[(String, usize)], writer: &mut impl io::Write) -> io::Result<()> {
+  if tags.is_empty() {
+    return writeln!(writer, "{}", t!("tags-none"));
+  }
+  writeln!(writer, "{}", t!("tags-title").bright_white().bold())?;
+  for (name, count) in tags {
+    writeln!(
+      writer,
+      "  {} - {}",
+      name.bright_white(),
+      t!("tag-records", count = *count).cyan()
+    )?;
+  }
+  Ok(())
+}
+
+/// One "name: N records | total" line of the describe breakdowns
+fn describe_group(name: &str, count: usize, total: f64, currency: &Currency) -> String {
+  t!(
+    "describe-group",
+    name = name.bright_white().to_string(),
+    count = count,
+    count_text = count.to_string().bright_cyan().to_string(),
+    total = format_amount(total).bright_green().to_string(),
+    currency = currency.to_string()
+  )
+}
+
+/// The "By Currency" section of `total` and `describe`: what was entered in
+/// each currency, what it came to in the tracker's and the rates used
+fn write_currency_totals(
+  by_currency: &[crate::CurrencyTotal],
+  currency: &Currency,
+  options: &DisplayOptions,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
+  if by_currency.is_empty() {
+    return Ok(());
+  }
+  let base = currency.to_string();
+
+  writeln!(writer)?;
+  writeln!(writer, "  {}", t!("currency-title").bright_white().bold())?;
+  for total in by_currency {
+    writeln!(
+      writer,
+      "    {}",
+      t!(
+        "currency-row",
+        currency = total.currency.bright_white().to_string(),
+        count = total.records,
+        count_text = total.records.to_string().bright_cyan().to_string(),
+        income = format_amount(total.income).bright_green().to_string(),
+        expenses = format_amount(total.expenses).bright_red().to_string()
+      )
+    )?;
+    if total.currency == base {
+      continue;
+    }
+    writeln!(
+      writer,
+      "      {}",
+      t!(
+        "currency-converted",
+        base = base.clone(),
+        income = format_amount(total.converted_income),
+        expenses = format_amount(total.converted_expenses)
+      )
+      .dimmed()
+    )?;
+    for used in &total.rates {
+      let line = if used.from == used.to {
+        t!(
+          "currency-rate",
+          currency = total.currency.clone(),
+          base = base.clone(),
+          rate = used.rate.to_string(),
+          date = options.date_format.format(&used.from),
+          count = used.records
+        )
+      } else {
+        t!(
+          "currency-rate-range",
+          currency = total.currency.clone(),
+          base = base.clone(),
+          rate = used.rate.to_string(),
+          from = options.date_format.format(&used.from),
+          to = options.date_format.format(&used.to),
+          count = used.records
+        )
+      };
+      writeln!(writer, "      {}", line.dimmed())?;
+    }
+  }
+  Ok(())
+}
+
 /// Write describe/EDA output
-fn write_describe(data: &crate::DescribeData, writer: &mut impl io::Write) -> io::Result<()> {
-  writeln!(writer, "{}", "Financial Overview:".bright_white().bold())?;
+fn write_describe(
+  data: &crate::DescribeData,
+  options: &DisplayOptions,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
+  writeln!(writer, "{}", t!("describe-title").bright_white().bold())?;
   writeln!(
     writer,
     "  {} {}",
-    "Total Records:".bright_white(),
+    t!("describe-total-records").bright_white(),
     data.total_records.to_string().bright_cyan()
   )?;
 
   if let Some((start, end)) = &data.date_range {
     writeln!(
       writer,
-      "  {} {} to {}",
-      "Date Range:".bright_white(),
-      start.bright_cyan(),
-      end.bright_cyan()
+      "  {} {}",
+      t!("describe-date-range").bright_white(),
+      t!(
+        "describe-range",
+        start = options.date_format.format(start).bright_cyan().to_string(),
+        end = options.date_format.format(end).bright_cyan().to_string()
+      )
     )?;
   }
 
   writeln!(writer)?;
-  writeln!(writer, "  {}", "By Category:".bright_white().bold())?;
+  writeln!(writer, "  {}", t!("describe-by-category").bright_white().bold())?;
   for (name, count, total) in &data.by_category {
-    writeln!(
-      writer,
-      "    {}: {} records | {} {}",
-      name.bright_white(),
-      count.to_string().bright_cyan(),
-      format_amount(*total).bright_green(),
-      data.currency
-    )?;
+    writeln!(writer, "    {}", describe_group(name, *count, *total, &data.currency))?;
   }
 
   if !data.by_category.is_empty() {
     writeln!(writer)?;
-    writeln!(writer, "  {}", "Category Spending Chart:".bright_white().bold())?;
+    writeln!(writer, "  {}", t!("describe-category-chart").bright_white().bold())?;
     let max_total = data
       .by_category
       .iter()
@@ -584,21 +2185,14 @@ fn write_describe(data: &crate::DescribeData, writer: &mut impl io::Write) -> io
   }
 
   writeln!(writer)?;
-  writeln!(writer, "  {}", "By Subcategory (Top 5):".bright_white().bold())?;
+  writeln!(writer, "  {}", t!("describe-by-subcategory").bright_white().bold())?;
   for (name, count, total) in data.by_subcategory.iter().take(5) {
-    writeln!(
-      writer,
-      "    {}: {} records | {} {}",
-      name.bright_white(),
-      count.to_string().bright_cyan(),
-      format_amount(*total).bright_green(),
-      data.currency
-    )?;
+    writeln!(writer, "    {}", describe_group(name, *count, *total, &data.currency))?;
   }
 
   if !data.by_subcategory.is_empty() {
     writeln!(writer)?;
-    writeln!(writer, "  {}", "Top Subcategories Chart:".bright_white().bold())?;
+    writeln!(writer, "  {}", t!("describe-subcategory-chart").bright_white().bold())?;
     let top_5: Vec<_> = data.by_subcategory.iter().take(5).collect();
     let max_total = top_5
       .iter()
@@ -620,14 +2214,740 @@ fn write_describe(data: &crate::DescribeData, writer: &mut impl io::Write) -> io
     }
   }
 
+  if !data.by_user.is_empty() {
+    writeln!(writer)?;
+    writeln!(writer, "  {}", t!("describe-by-user").bright_white().bold())?;
+    for (name, count, total) in &data.by_user {
+      writeln!(writer, "    {}", describe_group(name, *count, *total, &data.currency))?;
+    }
+  }
+
+  if !data.by_tag.is_empty() {
+    writeln!(writer)?;
+    writeln!(writer, "  {}", t!("describe-by-tag").bright_white().bold())?;
+    for (name, count, total) in &data.by_tag {
+      writeln!(writer, "    {}", describe_group(name, *count, *total, &data.currency))?;
+    }
+  }
+
+  write_weekdays(&data.by_weekday, &data.currency, writer)?;
+  write_currency_totals(&data.by_currency, &data.currency, options, writer)?;
+
   writeln!(writer)?;
   writeln!(
     writer,
     "  {} {} {}",
-    "Average Transaction:".bright_white(),
+    t!("describe-average").bright_white(),
     format_amount(data.average_transaction).bright_cyan(),
     data.currency
   )?;
 
   Ok(())
 }
+
+/// Expenses per day of the week as a chart, the biggest day marked, then
+/// weekdays against the weekend
+fn write_weekdays(
+  by_weekday: &[(DayOfWeek, usize, f64)],
+  currency: &Currency,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
+  let max_total = by_weekday.iter().map(|(_, _, total)| *total).fold(0.0, f64::max);
+  if max_total <= 0.0 {
+    return Ok(());
+  }
+
+  writeln!(writer)?;
+  writeln!(writer, "  {}", t!("describe-by-weekday").bright_white().bold())?;
+  let names: Vec<String> = by_weekday.iter().map(|(day, _, _)| weekday_name(*day)).collect();
+  let width = names.iter().map(|name| name.chars().count()).max().unwrap_or(0);
+  for ((_, _, total), name) in by_weekday.iter().zip(&names) {
+    let bar = "█".repeat(((total / max_total) * 30.0) as usize);
+    let line = format!("    {:<width$} │{} {}", name, bar, format_amount(*total), width = width);
+    if *total == max_total {
+      writeln!(writer, "{} {}", line.bright_yellow().bold(), t!("describe-busiest").yellow())?;
+    } else {
+      writeln!(writer, "{}", line.bright_cyan())?;
+    }
+  }
+
+  let (weekend, weekdays): (Vec<_>, Vec<_>) = by_weekday.iter().partition(|(day, _, _)| day.is_weekend());
+  let sum = |days: &[&(DayOfWeek, usize, f64)]| days.iter().map(|(_, _, total)| total).sum::<f64>();
+  let (weekdays, weekend) = (sum(&weekdays), sum(&weekend));
+  let share = |amount: f64| (amount / (weekdays + weekend) * 100.0).round();
+  for (label, amount) in [("describe-weekdays", weekdays), ("describe-weekend", weekend)] {
+    writeln!(
+      writer,
+      "    {} {}",
+      t!(label).bright_white(),
+      t!(
+        "describe-share",
+        amount = format!("{} {}", format_amount(amount), currency),
+        percent = share(amount).to_string()
+      )
+    )?;
+  }
+  Ok(())
+}
+
+fn weekday_name(day: DayOfWeek) -> String {
+  match day {
+    DayOfWeek::Monday => t!("weekday-monday"),
+    DayOfWeek::Tuesday => t!("weekday-tuesday"),
+    DayOfWeek::Wednesday => t!("weekday-wednesday"),
+    DayOfWeek::Thursday => t!("weekday-thursday"),
+    DayOfWeek::Friday => t!("weekday-friday"),
+    DayOfWeek::Saturday => t!("weekday-saturday"),
+    DayOfWeek::Sunday => t!("weekday-sunday"),
+  }
+}
+
+/// Write a CLI response as a JSON document (for `--output json`)
+pub fn write_response_json(
+  res: &crate::CliResponse,
+  writer: &mut impl io::Write,
+) -> io::Result<()> {
+  let data = res.content().map(response_json).unwrap_or(Value::Null);
+  let mut document = json!({
+    "status": "ok",
+    "data": data,
+  });
+  if !res.warnings().is_empty() {
+    document["warnings"] = json!(res.warnings());
+  }
+
+  writeln!(writer, "{}", serde_json::to_string_pretty(&document)?)?;
+  Ok(())
+}
+
+/// Write a CLI error as a JSON document (for `--output json`)
+pub fn write_error_json(err: &CliError, writer: &mut impl io::Write) -> io::Result<()> {
+  let text = error_text(err);
+
+  let details = match err {
+    CliError::ValidationError(kind) => serde_json::to_value(kind)?,
+    _ => Value::Null,
+  };
+
+  let document = json!({
+    "status": "error",
+    "error": {
+      "code": err.code(),
+      "kind": err.kind(),
+      "exit_code": err.exit_code(),
+      "message": text.message,
+      "suggestion": text.suggestion,
+      "details": details,
+    },
+  });
+
+  writeln!(writer, "{}", serde_json::to_string_pretty(&document)?)?;
+  Ok(())
+}
+
+/// Convert response content into its JSON representation
+fn response_json(content: &ResponseContent) -> Value {
+  match content {
+    ResponseContent::Message(msg) => json!({
+      "type": "message",
+      "message": msg,
+    }),
+    ResponseContent::Record {
+      record,
+      tracker_data,
+      is_update,
+    } => json!({
+      "type": "record",
+      "is_update": is_update,
+      "record": record_json(record, tracker_data),
+      "balance": tracker_data.net_balance(),
+    }),
+    ResponseContent::Deleted {
+      count,
+      tracker_data,
+    } => json!({
+      "type": "deleted",
+      "count": count,
+      "balance": tracker_data.net_balance(),
+    }),
+    ResponseContent::List {
+      records,
+      tracker_data,
+    } => json!({
+      "type": "list",
+      "records": records
+        .iter()
+        .map(|r| record_json(r, tracker_data))
+        .collect::<Vec<_>>(),
+    }),
+    ResponseContent::Search {
+      query,
+      records,
+      tracker_data,
+    } => json!({
+      "type": "search",
+      "query": query,
+      "records": records
+        .iter()
+        .map(|r| record_json(r, tracker_data))
+        .collect::<Vec<_>>(),
+    }),
+    ResponseContent::TrackerData {
+      tracker_data,
+      part: DumpPart::Records,
+      ..
+    } => json!({
+      "type": "records",
+      "records": tracker_data.records,
+    }),
+    ResponseContent::TrackerData {
+      tracker_data,
+      part: DumpPart::Meta,
+      ..
+    } => json!({
+      "type": "tracker_meta",
+      "tracker": tracker_meta(tracker_data),
+    }),
+    ResponseContent::TrackerData { tracker_data, .. } => json!({
+      "type": "tracker_data",
+      "tracker": tracker_data,
+    }),
+    ResponseContent::Schema(schema) => json!({
+      "type": "schema",
+      "schema": schema,
+    }),
+    ResponseContent::Total(totals) => {
+      let mut total = serde_json::to_value(totals).expect("totals serialize");
+      total["net_balance"] = json!(totals.total());
+      json!({
+        "type": "total",
+        "total": total,
+      })
+    }
+    ResponseContent::Categories(categories) => json!({
+      "type": "categories",
+      "categories": labels_json(categories),
+    }),
+    ResponseContent::Subcategories(subcategories) => json!({
+      "type": "subcategories",
+      "subcategories": labels_json(subcategories),
+    }),
+    ResponseContent::Tags(tags) => json!({
+      "type": "tags",
+      "tags": tags
+        .iter()
+        .map(|(name, count)| json!({ "name": name, "records": count }))
+        .collect::<Vec<_>>(),
+    }),
+    ResponseContent::Describe(data) => json!({
+      "type": "describe",
+      "describe": describe_json(data),
+    }),
+    ResponseContent::Report(report) => json!({
+      "type": "report",
+      "report": report,
+    }),
+    ResponseContent::Deductions(report) => json!({
+      "type": "deductions",
+      "deductions": report,
+    }),
+    ResponseContent::Reconciliation(reconciliation) => json!({
+      "type": "reconciliation",
+      "reconciliation": reconciliation,
+    }),
+    ResponseContent::DryRun(diff) => {
+      let mut value = tracker_diff_json(diff);
+      value["type"] = json!("dry_run");
+      value
+    }
+    ResponseContent::Diff { source, reverse, diff } => {
+      let mut value = tracker_diff_json(diff);
+      value["type"] = json!("diff");
+      value["source"] = json!(source);
+      value["reverse"] = json!(reverse);
+      value
+    }
+    ResponseContent::Digest(digest) => json!({
+      "type": "digest",
+      "digest": digest,
+    }),
+    ResponseContent::Schedules { schedules, currency } => json!({
+      "type": "schedules",
+      "currency": currency,
+      "schedules": schedules,
+    }),
+    ResponseContent::Projects { projects, currency } => json!({
+      "type": "projects",
+      "currency": currency,
+      "projects": projects,
+    }),
+    ResponseContent::Project { project, currency } => json!({
+      "type": "project",
+      "currency": currency,
+      "project": project,
+    }),
+    ResponseContent::Holdings { holdings, currency } => json!({
+      "type": "holdings",
+      "currency": currency,
+      "holdings": holdings,
+    }),
+    ResponseContent::Bills { bills, currency } => json!({
+      "type": "bills",
+      "currency": currency,
+      "bills": bills,
+    }),
+    ResponseContent::Subscriptions {
+      subscriptions,
+      currency,
+    } => json!({
+      "type": "subscriptions",
+      "currency": currency,
+      "subscriptions": subscriptions,
+      "monthly_total": subscriptions.iter().map(|s| s.monthly_cost).sum::<f64>(),
+    }),
+    ResponseContent::Merge(summary) => json!({
+      "type": "merge",
+      "merge": summary,
+    }),
+    // Names and dates only; the hashes stay in tokens.json
+    ResponseContent::Tokens { tokens } => json!({
+      "type": "tokens",
+      "tokens": tokens
+        .iter()
+        .map(|t| json!({ "name": t.name, "created_at": t.created_at }))
+        .collect::<Vec<_>>(),
+    }),
+    ResponseContent::History {
+      id,
+      versions,
+      tracker_data,
+    } => json!({
+      "type": "history",
+      "id": id,
+      "versions": versions
+        .iter()
+        .map(|entry| json!({
+          "version": entry.version,
+          "since": entry.since,
+          "reverted_to": entry.reverted_to,
+          "current": entry.current,
+          "record": record_json(&entry.record, tracker_data),
+        }))
+        .collect::<Vec<_>>(),
+    }),
+    ResponseContent::Status { summary, .. } => json!({
+      "type": "status",
+      "status": summary,
+    }),
+    ResponseContent::Budgets { month, budgets, currency } => json!({
+      "type": "budgets",
+      "month": month,
+      "currency": currency,
+      "budgets": budgets,
+    }),
+    ResponseContent::Owed { balances, currency } => json!({
+      "type": "owed",
+      "currency": currency,
+      "balances": balances,
+    }),
+    ResponseContent::ConfigValue { key, value } => json!({
+      "type": "config_value",
+      "key": key,
+      "value": value,
+    }),
+    ResponseContent::Settings(settings) => json!({
+      "type": "settings",
+      "settings": settings
+        .iter()
+        .map(|(key, value)| json!({ "key": key, "value": value }))
+        .collect::<Vec<_>>(),
+    }),
+  }
+}
+
+/// A record with its category and subcategory names resolved
+pub fn record_json(record: &Record, tracker_data: &TrackerData) -> Value {
+  json!({
+    "id": record.id,
+    "category_id": record.category,
+    "category": tracker_data.category_name(record.category),
+    "subcategory_id": record.subcategory,
+    "subcategory": tracker_data.subcategory_name(record.subcategory),
+    "amount": record.amount,
+    "currency": tracker_data.currency,
+    "date": record.date,
+    "description": record.description,
+    "entered_by": record.entered_by,
+    "deductible": record.deductible,
+    "project": record.project,
+    "foreign": record.foreign,
+  })
+}
+
+/// A [`crate::TrackerDiff`] with records as in `list`, and the totals either side
+fn tracker_diff_json(diff: &crate::TrackerDiff) -> Value {
+  let totals = |tracker_data: &TrackerData| {
+    let (income, expenses) = tracker_data.totals();
+    json!({ "income": income, "expenses": expenses, "balance": tracker_data.net_balance() })
+  };
+  json!({
+    "added": diff.added.iter().map(|r| record_json(r, &diff.after)).collect::<Vec<_>>(),
+    "changed": diff
+      .changed
+      .iter()
+      .map(|(before, after)| json!({
+        "id": after.id,
+        "before": record_json(before, &diff.before),
+        "after": record_json(after, &diff.after),
+      }))
+      .collect::<Vec<_>>(),
+    "removed": diff.removed.iter().map(|r| record_json(r, &diff.before)).collect::<Vec<_>>(),
+    "subcategories_added": diff.subcategories_added,
+    "subcategories_removed": diff.subcategories_removed,
+    "subcategories_renamed": diff
+      .subcategories_renamed
+      .iter()
+      .map(|(old, new)| json!({ "old": old, "new": new }))
+      .collect::<Vec<_>>(),
+    "other_changes": diff.other_changes,
+    "before": totals(&diff.before),
+    "after": totals(&diff.after),
+  })
+}
+
+/// `describe` statistics with every group as a named object rather than the
+/// `(name, count, total)` tuples used internally
+fn describe_json(data: &crate::DescribeData) -> Value {
+  let groups = |groups: &[(String, usize, f64)]| -> Vec<Value> {
+    groups
+      .iter()
+      .map(|(name, count, total)| json!({ "name": name, "count": count, "total": total }))
+      .collect()
+  };
+
+  json!({
+    "currency": data.currency,
+    "total_records": data.total_records,
+    "date_range": data
+      .date_range
+      .as_ref()
+      .map(|(start, end)| json!({ "start": start, "end": end })),
+    "average_transaction": data.average_transaction,
+    "by_category": groups(&data.by_category),
+    "by_subcategory": groups(&data.by_subcategory),
+    "by_user": groups(&data.by_user),
+    "by_tag": groups(&data.by_tag),
+    "by_weekday": data
+      .by_weekday
+      .iter()
+      .map(|(day, count, total)| json!({ "day": day, "count": count, "total": total }))
+      .collect::<Vec<_>>(),
+    "by_currency": data.by_currency,
+  })
+}
+
+fn labels_json(labels: &[(usize, String)]) -> Vec<Value> {
+  labels
+    .iter()
+    .map(|(id, name)| json!({ "id": id, "name": name }))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colors_enabled_on_terminal() {
+        assert!(colors_enabled(false, None, OutputFormat::Text, true));
+    }
+
+    #[test]
+    fn test_colors_disabled_by_flag() {
+        assert!(!colors_enabled(true, None, OutputFormat::Text, true));
+    }
+
+    #[test]
+    fn test_colors_disabled_by_no_color_env() {
+        assert!(!colors_enabled(false, Some(OsStr::new("1")), OutputFormat::Text, true));
+        // An empty NO_COLOR is treated as unset
+        assert!(colors_enabled(false, Some(OsStr::new("")), OutputFormat::Text, true));
+    }
+
+    #[test]
+    fn test_format_amount_negative() {
+        assert_eq!(format_amount(1234567.5), "1,234,567.50");
+        assert_eq!(format_amount(-150.0), "-150.00");
+        assert_eq!(format_amount(-396816.29), "-396,816.29");
+        assert_eq!(format_amount(-0.001), "0.00");
+    }
+
+    #[test]
+    fn test_compact_amount() {
+        assert_eq!(compact_amount(950.5), "950.50");
+        assert_eq!(compact_amount(12_345.0), "12.3k");
+        assert_eq!(compact_amount(12_000.0), "12k");
+        assert_eq!(compact_amount(-4_560_000.0), "-4.6M");
+        assert_eq!(compact_amount(999_960.0), "1M");
+        assert_eq!(compact_amount(2_000_000_000.0), "2B");
+    }
+
+    #[test]
+    fn test_describe_json_names_group_fields() {
+        let data = crate::DescribeData {
+            total_records: 2,
+            date_range: Some(("01-01-2025".to_string(), "05-01-2025".to_string())),
+            by_category: vec![("expenses".to_string(), 2, 30.0)],
+            by_subcategory: vec![("Food".to_string(), 2, 30.0)],
+            by_user: vec![],
+            by_tag: vec![],
+            by_weekday: vec![(crate::DayOfWeek::Monday, 2, 30.0)],
+            by_currency: vec![],
+            average_transaction: 15.0,
+            currency: Currency::USD,
+        };
+
+        let json = describe_json(&data);
+        assert_eq!(json["date_range"]["start"], "01-01-2025");
+        assert_eq!(json["by_subcategory"][0], json!({ "name": "Food", "count": 2, "total": 30.0 }));
+        assert_eq!(json["by_user"], json!([]));
+        assert_eq!(json["by_weekday"][0]["day"], "monday");
+    }
+
+    #[test]
+    fn test_records_table_respects_table_style() {
+        let record = Record {
+            id: 1,
+            category: 2,
+            subcategory: 1,
+            description: "Coffee".to_string(),
+            amount: 4.5,
+            date: "01-01-2025".to_string(),
+            entered_by: None,
+            deductible: false,
+            project: None,
+            foreign: None,
+            tags: Vec::new(),
+            parsed_date: Default::default(),
+        };
+        let options = DisplayOptions {
+            table_style: TableStyle::Markdown,
+            ..DisplayOptions::default()
+        };
+
+        let mut buffer = Vec::new();
+        write_records_table(&[record], None, None, None, &options, &mut buffer).unwrap();
+        let table = String::from_utf8(buffer).unwrap();
+
+        assert!(table.contains("|----"));
+        assert!(!table.contains('┌'));
+    }
+
+    #[test]
+    fn test_total_tables_respect_display_options() {
+        let totals = crate::Total {
+            currency: Currency::USD,
+            start: None,
+            end: None,
+            opening_balance: 0.0,
+            income_total: 100.0,
+            expenses_total: 40.0,
+            investments: None,
+            round_up_savings: None,
+            budgets: vec![crate::BudgetStatus {
+                subcategory: "Food".to_string(),
+                budget: 200.0,
+                carried: 0.0,
+                spent: 40.0,
+                remaining: 160.0,
+                level: crate::BudgetLevel::Ok,
+            }],
+            by: Some(crate::GroupBy::Subcategory),
+            groups: vec![crate::GroupTotal {
+                name: "Food".to_string(),
+                income: 0.0,
+                expenses: 40.0,
+                net: -40.0,
+            }],
+            by_currency: Vec::new(),
+        };
+        let options = DisplayOptions {
+            table_style: TableStyle::Markdown,
+            ..DisplayOptions::default()
+        };
+
+        let mut buffer = Vec::new();
+        write_total_summary(&totals, &options, &mut buffer).unwrap();
+        let summary = String::from_utf8(buffer).unwrap();
+
+        // The budget table and the --by table, both in markdown
+        assert_eq!(summary.lines().filter(|line| line.starts_with("|-")).count(), 2);
+        assert!(!summary.contains("-+-"));
+        let rows: Vec<&str> = summary.lines().filter(|line| line.starts_with("| Food")).collect();
+        assert!(rows[0].contains("160.00 USD") && rows[1].contains("-40.00 USD"));
+
+        // --hide-currency drops it from both tables
+        let options = DisplayOptions {
+            hide_currency: true,
+            ..options
+        };
+        let mut buffer = Vec::new();
+        write_total_summary(&totals, &options, &mut buffer).unwrap();
+        let summary = String::from_utf8(buffer).unwrap();
+        let rows: Vec<&str> = summary.lines().filter(|line| line.starts_with("| Food")).collect();
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| !row.contains("USD")));
+    }
+
+    #[test]
+    fn test_records_table_can_hide_currency_and_shorten_amounts() {
+        let record = Record {
+            id: 1,
+            category: 2,
+            subcategory: 1,
+            description: "Rent".to_string(),
+            amount: 12_345.0,
+            date: "01-01-2025".to_string(),
+            entered_by: None,
+            deductible: false,
+            project: None,
+            foreign: None,
+            tags: Vec::new(),
+            parsed_date: Default::default(),
+        };
+        let table = |options: &DisplayOptions| {
+            let mut buffer = Vec::new();
+            write_records_table(std::slice::from_ref(&record), None, Some(&Currency::USD), None, options, &mut buffer).unwrap();
+            String::from_utf8(buffer).unwrap()
+        };
+
+        assert!(table(&DisplayOptions::default()).contains("12,345.00 USD"));
+        let narrow = table(&DisplayOptions {
+            hide_currency: true,
+            compact_amounts: true,
+            ..DisplayOptions::default()
+        });
+        assert!(narrow.contains("12.3k"));
+        assert!(!narrow.contains("USD"));
+    }
+
+    #[test]
+    fn test_records_table_signs_amounts_by_category() {
+        let tracker: TrackerData =
+            serde_json::from_value(crate::default_tracker_json(&Currency::USD, 0.0)).unwrap();
+        let income = tracker.category_id("income");
+        let expenses = tracker.category_id("expenses");
+        let records: Vec<Record> = [(1, income, 500.0), (2, expenses, 20.0)]
+            .into_iter()
+            .map(|(id, category, amount)| Record {
+                id,
+                category,
+                subcategory: 1,
+                description: String::new(),
+                amount,
+                date: "01-01-2025".to_string(),
+                entered_by: None,
+                deductible: false,
+                project: None,
+                foreign: None,
+                tags: Vec::new(),
+                parsed_date: Default::default(),
+            })
+            .collect();
+
+        let mut buffer = Vec::new();
+        write_records_table(&records, Some(&tracker), Some(&Currency::USD), None, &DisplayOptions::default(), &mut buffer)
+            .unwrap();
+        let table = String::from_utf8(buffer).unwrap();
+
+        assert!(table.contains("+500.00 USD"));
+        assert!(table.contains("-20.00 USD"));
+    }
+
+    #[test]
+    fn test_records_table_truncates_description_to_fit() {
+        let record = Record {
+            id: 1,
+            category: 2,
+            subcategory: 1,
+            description: "A very long description that would wrap the table on a narrow terminal".to_string(),
+            amount: 4.5,
+            date: "01-01-2025".to_string(),
+            entered_by: None,
+            deductible: false,
+            project: None,
+            foreign: None,
+            tags: Vec::new(),
+            parsed_date: Default::default(),
+        };
+        let render = |max_width| {
+            let options = DisplayOptions { max_width, ..DisplayOptions::default() };
+            let mut buffer = Vec::new();
+            write_records_table(std::slice::from_ref(&record), None, None, None, &options, &mut buffer).unwrap();
+            String::from_utf8(buffer).unwrap()
+        };
+
+        let narrow = render(Some(80));
+        assert!(narrow.contains('…'));
+        assert!(narrow.lines().all(|line| line.chars().count() <= 80));
+
+        let full = render(None);
+        assert!(full.contains("narrow terminal"));
+        assert!(!full.contains('…'));
+    }
+
+    #[test]
+    fn test_error_text_includes_code() {
+        let err = CliError::ValidationError(ValidationErrorKind::SubcategoryNotFound {
+            name: "Food".to_string(),
+            nearest: None,
+        });
+
+        let mut buffer = Vec::new();
+        write_error(&err, &mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+
+        assert!(text.lines().next().unwrap().contains("[E012]"));
+        assert!(text.contains("Subcategory 'Food' not found"));
+    }
+
+    #[test]
+    fn test_first_run_error_shows_quick_start() {
+        let mut buffer = Vec::new();
+        write_error(&CliError::NotInitialized, &mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+
+        assert!(text.contains("[E006]"));
+        assert!(text.contains("\n    fintrack init --currency"));
+    }
+
+    #[test]
+    fn test_colors_disabled_when_not_terminal_or_json() {
+        assert!(!colors_enabled(false, None, OutputFormat::Text, false));
+        assert!(!colors_enabled(false, None, OutputFormat::Json, true));
+    }
+
+    #[test]
+    fn test_dump_records_only_compact() {
+        let mut tracker: TrackerData =
+            serde_json::from_value(crate::default_tracker_json(&Currency::USD, 0.0)).unwrap();
+        tracker.records.push(
+            serde_json::from_str(
+                r#"{"id":1,"category":2,"subcategory":1,"description":"","amount":4.5,"date":"01-01-2025"}"#,
+            )
+            .unwrap(),
+        );
+
+        let mut buffer = Vec::new();
+        write_tracker_data(&tracker, DumpPart::Records, true, &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "[{\"id\":1,\"category\":2,\"subcategory\":1,\"description\":\"\",\"amount\":4.5,\"date\":\"01-01-2025\"}]\n"
+        );
+
+        let mut buffer = Vec::new();
+        write_tracker_data(&tracker, DumpPart::All, false, &mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.starts_with("{\n  \"version\": 1,"));
+    }
+}
@@ -1,5 +1,31 @@
+pub mod alerts;
+pub mod backup;
+pub mod chart;
+pub mod classify;
 pub mod cli;
 pub mod command_prelude;
+pub mod config;
 pub mod context;
+pub mod email;
+pub mod expand;
 pub mod file;
+pub mod filters;
+pub mod fuzzy;
+pub mod gsheet;
+pub mod hooks;
+pub mod limits;
+pub mod logging;
+pub mod notifier;
 pub mod parsers;
+pub mod payday;
+pub mod period;
+pub mod plan;
+pub mod prices;
+pub mod progress;
+pub mod rates;
+pub mod storage;
+pub mod summary;
+pub mod synthetic;
+pub mod tokens;
+pub mod validation;
+pub mod webhook;
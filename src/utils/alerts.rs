@@ -0,0 +1,70 @@
+//! Things worth interrupting you for: budgets gone over this month, bills
+//! coming due and spending limits gone over. Sent by `fintrack notify
+//! --alerts`, and shown on the desktop by `fintrack watch`.
+
+use chrono::{Datelike, Days, Months};
+
+use crate::output::format_amount;
+use crate::utils::limits;
+use crate::{GlobalContext, LimitPeriod, LimitStatus, TrackerData, t};
+
+/// How many days ahead a bill is mentioned
+const BILL_NOTICE_DAYS: i64 = 3;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Alert {
+  pub title: String,
+  pub body: String,
+}
+
+/// Every alert that holds today
+pub fn current(gctx: &GlobalContext, tracker_data: &TrackerData) -> Vec<Alert> {
+  let today = gctx.today();
+  let currency = &tracker_data.currency;
+  let money = |amount: f64| format!("{} {}", format_amount(amount), currency);
+  let mut alerts = Vec::new();
+
+  let month_start = today.with_day(1).expect("day 1 exists");
+  let month_end = month_start + Months::new(1) - Days::new(1);
+  for budget in tracker_data.budget_statuses(month_start, month_end) {
+    if budget.remaining < 0.0 {
+      alerts.push(Alert {
+        title: t!("alert-budget-title", name = budget.subcategory.as_str()),
+        body: t!(
+          "alert-budget-body",
+          spent = money(budget.spent),
+          budget = money(budget.budget + budget.carried),
+          over = money(-budget.remaining)
+        ),
+      });
+    }
+  }
+
+  for bill in tracker_data.bill_statuses(today) {
+    let (title, body, days) = match bill.days {
+      ..0 => ("alert-bill-overdue-title", "alert-bill-overdue", -bill.days),
+      days @ ..=BILL_NOTICE_DAYS => ("alert-bill-due-title", "alert-bill-due", days),
+      _ => continue,
+    };
+    alerts.push(Alert {
+      title: t!(title, name = bill.name.as_str()),
+      body: t!(body, amount = money(bill.amount), due = bill.due.as_str(), days = days),
+    });
+  }
+
+  for status in limits::statuses(gctx, tracker_data, today).iter().filter(|s| s.is_over()) {
+    alerts.push(limit_alert(status, &money));
+  }
+  alerts
+}
+
+fn limit_alert(status: &LimitStatus, money: &dyn Fn(f64) -> String) -> Alert {
+  let (title, body) = match status.period {
+    LimitPeriod::Daily => ("alert-daily-limit-title", "alert-daily-limit-body"),
+    LimitPeriod::Weekly => ("alert-weekly-limit-title", "alert-weekly-limit-body"),
+  };
+  Alert {
+    title: t!(title),
+    body: t!(body, spent = money(status.spent), limit = money(status.limit)),
+  }
+}
@@ -0,0 +1,155 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  time::{Duration, SystemTime},
+};
+
+use chrono::Utc;
+use tracing::debug;
+
+use crate::{CliError, GlobalContext, TrackerData};
+
+const PREFIX: &str = "tracker_";
+/// Kept apart from `PREFIX` so archives are never pruned or restored
+const ARCHIVE_PREFIX: &str = "archive_";
+const EXTENSION: &str = "json";
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Copy the tracker file into the backups directory before it is overwritten,
+/// then prune old copies according to the `[backup]` settings. Does nothing if
+/// backups are turned off, there is no tracker file yet or it was given with
+/// `--tracker-file`.
+pub fn backup_tracker(gctx: &GlobalContext) -> Result<(), CliError> {
+  if !gctx.config().backup.enabled || !gctx.tracker_path().exists() || gctx.has_custom_tracker() {
+    return Ok(());
+  }
+
+  fs::create_dir_all(gctx.backups_path())?;
+  let timestamp = Utc::now().format("%Y-%m-%dT%H-%M-%S%.3fZ");
+  let path = gctx
+    .backups_path()
+    .join(format!("{}{}.{}", PREFIX, timestamp, EXTENSION));
+
+  gctx.verbose(format!("Backing up to {}", path.display()));
+  fs::copy(gctx.tracker_path(), &path)?;
+
+  prune_backups(gctx)
+}
+
+/// Copy the tracker file to `to`, or into the backups directory as
+/// `archive_<time>.json`, before `fintrack clear` wipes it. `to` may be a
+/// file or an existing directory. Returns where the copy went, or `None` if
+/// there is no tracker file to archive.
+pub fn archive_tracker(gctx: &GlobalContext, to: Option<&Path>) -> Result<Option<PathBuf>, CliError> {
+  if !gctx.tracker_path().exists() {
+    return Ok(None);
+  }
+
+  let name = format!(
+    "{}{}.{}",
+    ARCHIVE_PREFIX,
+    Utc::now().format("%Y-%m-%dT%H-%M-%SZ"),
+    EXTENSION
+  );
+  let path = match to {
+    Some(dir) if dir.is_dir() => dir.join(name),
+    Some(file) => file.to_path_buf(),
+    None => gctx.backups_path().join(name),
+  };
+  if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+    fs::create_dir_all(parent)?;
+  }
+
+  gctx.verbose(format!("Archiving the tracker to {}", path.display()));
+  fs::copy(gctx.tracker_path(), &path)?;
+  Ok(Some(path))
+}
+
+/// Remove backups beyond `backup.max_count` (oldest first) and any older than
+/// `backup.max_age_days`
+pub fn prune_backups(gctx: &GlobalContext) -> Result<(), CliError> {
+  let settings = &gctx.config().backup;
+  let mut backups = list_backups(gctx)?;
+
+  let excess = match settings.max_count {
+    0 => 0,
+    max => backups.len().saturating_sub(max),
+  };
+  let mut expired: Vec<PathBuf> = backups.drain(..excess).collect();
+
+  if let Some(days) = settings.max_age_days {
+    let max_age = Duration::from_secs(days * SECONDS_PER_DAY);
+    let now = SystemTime::now();
+    for path in backups {
+      let modified = fs::metadata(&path)?.modified()?;
+      if now.duration_since(modified).unwrap_or_default() > max_age {
+        expired.push(path);
+      }
+    }
+  }
+
+  for path in expired {
+    gctx.verbose(format!("Removing old backup {}", path.display()));
+    fs::remove_file(&path)?;
+  }
+  Ok(())
+}
+
+/// Replace a tracker file that no longer parses with the newest backup that
+/// does. The broken file is kept next to it as `tracker.corrupt-<time>.json`.
+/// Returns the timestamp of the backup that was restored, or `None` if no
+/// backup could be read.
+pub fn restore_latest(gctx: &GlobalContext) -> Result<Option<String>, CliError> {
+  let backups = list_backups(gctx)?;
+  let Some(backup) = backups.iter().rev().find(|path| is_valid_tracker(path)) else {
+    tracing::warn!(count = backups.len(), "no usable backup to restore");
+    return Ok(None);
+  };
+
+  let corrupt = gctx.base_path().join(format!(
+    "tracker.corrupt-{}.{}",
+    Utc::now().format("%Y-%m-%dT%H-%M-%SZ"),
+    EXTENSION
+  ));
+  gctx.verbose(format!("Moving the damaged tracker to {}", corrupt.display()));
+  fs::rename(gctx.tracker_path(), &corrupt)?;
+  gctx.verbose(format!("Restoring {}", backup.display()));
+  fs::copy(backup, gctx.tracker_path())?;
+  tracing::warn!(backup = %backup.display(), "tracker restored from backup");
+
+  let timestamp = backup
+    .file_stem()
+    .and_then(|stem| stem.to_str())
+    .and_then(|stem| stem.strip_prefix(PREFIX))
+    .unwrap_or_default();
+  Ok(Some(timestamp.to_string()))
+}
+
+fn is_valid_tracker(path: &Path) -> bool {
+  fs::read(path)
+    .ok()
+    .is_some_and(|bytes| serde_json::from_slice::<TrackerData>(&bytes).is_ok())
+}
+
+/// Backup files, oldest first
+pub fn list_backups(gctx: &GlobalContext) -> Result<Vec<PathBuf>, CliError> {
+  if !gctx.backups_path().exists() {
+    return Ok(Vec::new());
+  }
+
+  let mut backups: Vec<PathBuf> = fs::read_dir(gctx.backups_path())?
+    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+    .filter(|path| {
+      path.extension().is_some_and(|ext| ext == EXTENSION)
+        && path
+          .file_name()
+          .and_then(|name| name.to_str())
+          .is_some_and(|name| name.starts_with(PREFIX))
+    })
+    .collect();
+  // Timestamps in the names sort chronologically
+  backups.sort();
+  debug!(count = backups.len(), "backups found");
+
+  Ok(backups)
+}
@@ -0,0 +1,132 @@
+//! Charts drawn as SVG by hand, and turned into PNG with resvg when asked
+
+use std::fmt::Write;
+
+use chrono::NaiveDate;
+
+use crate::{CliError, t};
+use crate::output::format_amount;
+use crate::parsers::STORAGE_DATE_FORMAT;
+
+pub const WIDTH: u32 = 800;
+pub const HEIGHT: u32 = 400;
+
+/// Room around the plot for the title and axis labels
+const LEFT: f64 = 90.0;
+const RIGHT: f64 = 30.0;
+const TOP: f64 = 50.0;
+const BOTTOM: f64 = 50.0;
+/// Horizontal grid lines, each labelled with its balance
+const Y_TICKS: usize = 5;
+
+/// A step chart of `points`, a balance at the end of each day, oldest first
+pub fn balance_svg(points: &[(NaiveDate, f64)], currency: &str) -> String {
+  let (width, height) = (f64::from(WIDTH), f64::from(HEIGHT));
+  let (plot_width, plot_height) = (width - LEFT - RIGHT, height - TOP - BOTTOM);
+
+  let first = points.first().map_or_else(NaiveDate::default, |p| p.0);
+  let last = points.last().map_or(first, |p| p.0);
+  let days = (last - first).num_days().max(1) as f64;
+  let (mut low, mut high) = points
+    .iter()
+    .fold((f64::MAX, f64::MIN), |(low, high), (_, b)| (low.min(*b), high.max(*b)));
+  // A flat line still needs some height to sit in
+  let padding = ((high - low) * 0.05).max(1.0);
+  low -= padding;
+  high += padding;
+
+  let x = |date: NaiveDate| LEFT + (date - first).num_days() as f64 / days * plot_width;
+  let y = |balance: f64| TOP + (high - balance) / (high - low) * plot_height;
+
+  let mut svg = String::new();
+  let _ = writeln!(
+    svg,
+    r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="Helvetica, Arial, 'DejaVu Sans', sans-serif" font-size="12">"#,
+    w = WIDTH,
+    h = HEIGHT
+  );
+  let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+  let _ = writeln!(
+    svg,
+    r##"<text x="{}" y="28" font-size="16" font-weight="bold" fill="#333">Balance ({})</text>"##,
+    LEFT,
+    escape(currency)
+  );
+
+  for tick in 0..=Y_TICKS {
+    let balance = low + (high - low) * tick as f64 / Y_TICKS as f64;
+    let y = y(balance);
+    let _ = writeln!(
+      svg,
+      r##"<line x1="{}" y1="{y:.1}" x2="{}" y2="{y:.1}" stroke="#e5e5e5"/><text x="{}" y="{:.1}" text-anchor="end" fill="#666">{}</text>"##,
+      LEFT,
+      width - RIGHT,
+      LEFT - 8.0,
+      y + 4.0,
+      format_amount(balance)
+    );
+  }
+  if low < 0.0 && high > 0.0 {
+    let zero = y(0.0);
+    let _ = writeln!(
+      svg,
+      r##"<line x1="{}" y1="{zero:.1}" x2="{}" y2="{zero:.1}" stroke="#d9534f" stroke-dasharray="4 4"/>"##,
+      LEFT,
+      width - RIGHT
+    );
+  }
+
+  let mut labels = vec![first, last];
+  if days >= 2.0 {
+    labels.insert(1, first + chrono::Days::new((days / 2.0) as u64));
+  }
+  labels.dedup();
+  for (i, date) in labels.iter().enumerate() {
+    let anchor = match i {
+      0 => "start",
+      i if i == labels.len() - 1 => "end",
+      _ => "middle",
+    };
+    let _ = writeln!(
+      svg,
+      r##"<text x="{:.1}" y="{:.1}" text-anchor="{}" fill="#666">{}</text>"##,
+      x(*date),
+      height - BOTTOM + 20.0,
+      anchor,
+      date.format(STORAGE_DATE_FORMAT)
+    );
+  }
+
+  // Steps rather than slopes: the balance holds until the next day with records
+  let mut line = Vec::new();
+  for (i, (date, balance)) in points.iter().enumerate() {
+    if i > 0 {
+      line.push(format!("{:.1},{:.1}", x(*date), y(points[i - 1].1)));
+    }
+    line.push(format!("{:.1},{:.1}", x(*date), y(*balance)));
+  }
+  let _ = writeln!(
+    svg,
+    r##"<polyline points="{}" fill="none" stroke="#2a7ae2" stroke-width="2" stroke-linejoin="round"/>"##,
+    line.join(" ")
+  );
+  svg.push_str("</svg>\n");
+  svg
+}
+
+/// Render an SVG made by this module as a PNG of the same size
+pub fn svg_to_png(svg: &str) -> Result<Vec<u8>, CliError> {
+  let failed = |reason: String| CliError::Other(t!("error-chart-png", reason = reason));
+
+  let mut options = resvg::usvg::Options::default();
+  options.fontdb_mut().load_system_fonts();
+  let tree = resvg::usvg::Tree::from_str(svg, &options).map_err(|err| failed(err.to_string()))?;
+  let mut pixmap =
+    resvg::tiny_skia::Pixmap::new(WIDTH, HEIGHT).ok_or_else(|| failed("the image is empty".to_string()))?;
+  resvg::render(&tree, resvg::tiny_skia::Transform::default(), &mut pixmap.as_mut());
+  pixmap.encode_png().map_err(|err| failed(err.to_string()))
+}
+
+fn escape(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,69 @@
+//! Subcategory suggestions for `fintrack add`, from the descriptions of
+//! records already filed
+
+use std::collections::{HashMap, HashSet};
+
+use crate::TrackerData;
+
+/// Words too common to say anything about a purchase
+const STOP_WORDS: [&str; 12] =
+  ["a", "an", "and", "at", "for", "from", "in", "of", "on", "the", "to", "with"];
+
+/// The subcategory records described like `description` were usually filed
+/// under, among records in `category`. Records left in Miscellaneous don't
+/// count, and `None` means no past description shares a word with this one.
+pub fn suggest(tracker_data: &TrackerData, category: usize, description: &str) -> Option<usize> {
+  let words = tokens(description);
+  if words.is_empty() {
+    return None;
+  }
+
+  let miscellaneous = tracker_data.miscellaneous_subcategory_id();
+  // Per subcategory: how well its records match, and the latest one's index
+  // to break ties in favour of recent habits
+  let mut scores: HashMap<usize, (f64, usize)> = HashMap::new();
+  for (index, record) in tracker_data.records.iter().enumerate() {
+    if record.category != category || Some(record.subcategory) == miscellaneous {
+      continue;
+    }
+    let theirs = tokens(&record.description);
+    let shared = words.intersection(&theirs).count();
+    if shared == 0 {
+      continue;
+    }
+    // Jaccard similarity, so a long description doesn't match everything
+    let similarity = shared as f64 / words.union(&theirs).count() as f64;
+    let score = scores.entry(record.subcategory).or_insert((0.0, 0));
+    score.0 += similarity;
+    score.1 = score.1.max(index);
+  }
+
+  scores
+    .into_iter()
+    .max_by(|(_, a), (_, b)| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)))
+    .map(|(subcategory, _)| subcategory)
+}
+
+/// Lowercase words of two or more letters. Numbers such as amounts and
+/// dates say little about what was bought, and neither do stop words.
+fn tokens(text: &str) -> HashSet<String> {
+  text
+    .split(|c: char| !c.is_alphanumeric())
+    .filter(|word| word.chars().count() >= 2 && !word.chars().all(|c| c.is_ascii_digit()))
+    .map(str::to_lowercase)
+    .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_skip_numbers_and_single_letters() {
+        let words = tokens("Coffee & a croissant, 2x at Café 21");
+        let mut words: Vec<&str> = words.iter().map(String::as_str).collect();
+        words.sort();
+        assert_eq!(words, ["2x", "café", "coffee", "croissant"]);
+    }
+}
@@ -1,7 +1,8 @@
 use chrono::NaiveDate;
 use clap::ArgMatches;
 
-use crate::{Category, CliError, Currency};
+use crate::utils::parsers::parse_date;
+use crate::{Category, CliError, Currency, t};
 
 // Constants for default values
 const DEFAULT_F64: f64 = 0.0;
@@ -14,6 +15,8 @@ pub trait ArgMatchesExt {
   // Required value extractors (return Result for error handling)
   fn get_category(&self, id: &str) -> Result<&Category, CliError>;
   fn get_usize(&self, id: &str) -> Result<usize, CliError>;
+  /// Parse an optional date argument, which clap keeps as the typed string
+  fn get_date_opt(&self, id: &str) -> Result<Option<NaiveDate>, CliError>;
 
   // Optional value extractors (return Option)
   fn get_category_opt(&self, id: &str) -> Option<&Category>;
@@ -21,7 +24,6 @@ pub trait ArgMatchesExt {
   fn get_usize_opt(&self, id: &str) -> Option<usize>;
   fn get_string_opt(&self, id: &str) -> Option<String>;
   fn get_subcategory_opt(&self, id: &str) -> Option<String>;
-  fn get_date_opt(&self, id: &str) -> Option<NaiveDate>;
   fn get_currency_opt(&self, id: &str) -> Option<&Currency>;
 
   // Value extractors with defaults
@@ -54,7 +56,7 @@ impl ArgMatchesExt for ArgMatches {
     self
       .get_one::<usize>(id)
       .copied()
-      .ok_or_else(|| CliError::Other(format!("Required argument '{}' not provided", id)))
+      .ok_or_else(|| CliError::Other(t!("error-argument-missing", name = id)))
   }
 
   fn get_category_opt(&self, id: &str) -> Option<&Category> {
@@ -77,8 +79,12 @@ impl ArgMatchesExt for ArgMatches {
     self.get_one::<String>(id).cloned()
   }
 
-  fn get_date_opt(&self, id: &str) -> Option<NaiveDate> {
-    self.get_one::<NaiveDate>(id).copied()
+  fn get_date_opt(&self, id: &str) -> Result<Option<NaiveDate>, CliError> {
+    self
+      .get_one::<String>(id)
+      .map(|s| parse_date(s))
+      .transpose()
+      .map_err(CliError::ValidationError)
   }
 
   fn get_currency_opt(&self, id: &str) -> Option<&Currency> {
@@ -134,7 +140,7 @@ mod tests {
             .arg(clap::Arg::new("amount").short('a').long("amount").value_parser(clap::value_parser!(f64)))
             .arg(clap::Arg::new("id").short('i').long("id").value_parser(clap::value_parser!(usize)))
             .arg(clap::Arg::new("text").short('t').long("text").value_parser(clap::value_parser!(String)))
-            .arg(clap::Arg::new("date").short('D').long("date").value_parser(crate::utils::parsers::parse_date))
+            .arg(clap::Arg::new("date").short('D').long("date").value_parser(clap::value_parser!(String)))
             .arg(clap::Arg::new("currency").short('c').long("currency").value_parser(clap::value_parser!(Currency)))
             .arg(clap::Arg::new("ids").long("ids").value_parser(clap::value_parser!(usize)).action(clap::ArgAction::Append))
     }
@@ -142,7 +148,7 @@ mod tests {
     #[test]
     fn test_get_category() {
         let cmd = create_test_command();
-        let matches = cmd.get_matches_from(&["test", "income"]);
+        let matches = cmd.get_matches_from(["test", "income"]);
 
         let category = matches.get_category("category").unwrap();
         assert!(matches!(category, &Category::Income));
@@ -151,7 +157,7 @@ mod tests {
     #[test]
     fn test_get_category_missing() {
         let cmd = create_test_command();
-        let matches = cmd.get_matches_from(&["test"]);
+        let matches = cmd.get_matches_from(["test"]);
 
         let result = matches.get_category("category");
         assert!(result.is_err());
@@ -160,7 +166,7 @@ mod tests {
     #[test]
     fn test_get_category_opt() {
         let cmd = create_test_command();
-        let matches = cmd.get_matches_from(&["test", "expenses"]);
+        let matches = cmd.get_matches_from(["test", "expenses"]);
 
         let category = matches.get_category_opt("category").unwrap();
         assert!(matches!(category, &Category::Expenses));
@@ -169,7 +175,7 @@ mod tests {
     #[test]
     fn test_get_category_opt_missing() {
         let cmd = create_test_command();
-        let matches = cmd.get_matches_from(&["test"]);
+        let matches = cmd.get_matches_from(["test"]);
 
         assert!(matches.get_category_opt("category").is_none());
     }
@@ -177,7 +183,7 @@ mod tests {
     #[test]
     fn test_get_f64_opt() {
         let cmd = create_test_command();
-        let matches = cmd.get_matches_from(&["test", "--amount", "100.5"]);
+        let matches = cmd.get_matches_from(["test", "--amount", "100.5"]);
 
         assert_eq!(matches.get_f64_opt("amount"), Some(100.5));
     }
@@ -186,8 +192,8 @@ mod tests {
     fn test_get_f64_or_default() {
         let cmd1 = create_test_command();
         let cmd2 = create_test_command();
-        let matches1 = cmd1.get_matches_from(&["test", "--amount", "100.5"]);
-        let matches2 = cmd2.get_matches_from(&["test"]);
+        let matches1 = cmd1.get_matches_from(["test", "--amount", "100.5"]);
+        let matches2 = cmd2.get_matches_from(["test"]);
 
         assert_eq!(matches1.get_f64_or_default("amount"), 100.5);
         assert_eq!(matches2.get_f64_or_default("amount"), 0.0);
@@ -196,7 +202,7 @@ mod tests {
     #[test]
     fn test_get_usize() {
         let cmd = create_test_command();
-        let matches = cmd.get_matches_from(&["test", "--id", "42"]);
+        let matches = cmd.get_matches_from(["test", "--id", "42"]);
 
         assert_eq!(matches.get_usize("id").unwrap(), 42);
     }
@@ -205,8 +211,8 @@ mod tests {
     fn test_get_usize_or_default() {
         let cmd1 = create_test_command();
         let cmd2 = create_test_command();
-        let matches1 = cmd1.get_matches_from(&["test", "--id", "42"]);
-        let matches2 = cmd2.get_matches_from(&["test"]);
+        let matches1 = cmd1.get_matches_from(["test", "--id", "42"]);
+        let matches2 = cmd2.get_matches_from(["test"]);
 
         assert_eq!(matches1.get_usize_or_default("id"), 42);
         assert_eq!(matches2.get_usize_or_default("id"), 0);
@@ -216,8 +222,8 @@ mod tests {
     fn test_get_string_or_default() {
         let cmd1 = create_test_command();
         let cmd2 = create_test_command();
-        let matches1 = cmd1.get_matches_from(&["test", "--text", "hello"]);
-        let matches2 = cmd2.get_matches_from(&["test"]);
+        let matches1 = cmd1.get_matches_from(["test", "--text", "hello"]);
+        let matches2 = cmd2.get_matches_from(["test"]);
 
         assert_eq!(matches1.get_string_or_default("text"), "hello");
         assert_eq!(matches2.get_string_or_default("text"), "");
@@ -227,8 +233,8 @@ mod tests {
     fn test_get_subcategory_or_default() {
         let cmd1 = create_test_command();
         let cmd2 = create_test_command();
-        let matches1 = cmd1.get_matches_from(&["test", "--text", "groceries"]);
-        let matches2 = cmd2.get_matches_from(&["test"]);
+        let matches1 = cmd1.get_matches_from(["test", "--text", "groceries"]);
+        let matches2 = cmd2.get_matches_from(["test"]);
 
         assert_eq!(matches1.get_subcategory_or_default("text"), "groceries");
         assert_eq!(matches2.get_subcategory_or_default("text"), "miscellaneous");
@@ -238,9 +244,9 @@ mod tests {
     fn test_get_date_opt() {
         use chrono::Datelike;
         let cmd = create_test_command();
-        let matches = cmd.get_matches_from(&["test", "--date", "15-01-2025"]);
+        let matches = cmd.get_matches_from(["test", "--date", "15-01-2025"]);
 
-        let date = matches.get_date_opt("date").unwrap();
+        let date = matches.get_date_opt("date").unwrap().unwrap();
         assert_eq!(date.day(), 15);
         assert_eq!(date.month(), 1);
         assert_eq!(date.year(), 2025);
@@ -250,8 +256,8 @@ mod tests {
     fn test_get_currency_or_default() {
         let cmd1 = create_test_command();
         let cmd2 = create_test_command();
-        let matches1 = cmd1.get_matches_from(&["test", "--currency", "usd"]);
-        let matches2 = cmd2.get_matches_from(&["test"]);
+        let matches1 = cmd1.get_matches_from(["test", "--currency", "usd"]);
+        let matches2 = cmd2.get_matches_from(["test"]);
 
         assert!(matches!(matches1.get_currency_or_default("currency"), &Currency::USD));
         assert!(matches!(matches2.get_currency_or_default("currency"), &Currency::NGN));
@@ -260,7 +266,7 @@ mod tests {
     #[test]
     fn test_get_vec() {
         let cmd = create_test_command();
-        let matches = cmd.get_matches_from(&["test", "--ids", "1", "--ids", "2", "--ids", "3"]);
+        let matches = cmd.get_matches_from(["test", "--ids", "1", "--ids", "2", "--ids", "3"]);
 
         let ids: Vec<usize> = matches.get_vec("ids");
         assert_eq!(ids, vec![1, 2, 3]);
@@ -269,7 +275,7 @@ mod tests {
     #[test]
     fn test_get_vec_empty() {
         let cmd = create_test_command();
-        let matches = cmd.get_matches_from(&["test"]);
+        let matches = cmd.get_matches_from(["test"]);
 
         let ids: Vec<usize> = matches.get_vec("ids");
         assert_eq!(ids, Vec::<usize>::new());
@@ -0,0 +1,796 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+use crate::{
+  CliError, DateFormat, DayOfWeek, MonthOfYear, NumberLocale, RoundingMode, TableStyle,
+  ValidationErrorKind,
+  i18n::Language,
+  parsers::InputDateFormat, t,
+};
+
+/// Settings `fintrack config` can read and change, with what each one does.
+/// Aliases (`aliases.<name>`) and default flags (`<command>.default`) are
+/// accepted in addition to these.
+pub const SETTINGS: [(&str, &str); 55] = [
+  ("general.confirm", "Ask before destructive commands such as 'clear' (true/false)"),
+  ("general.read_only", "Refuse every command that would change the tracker (true/false)"),
+  ("general.user", "Name stored on records you add; defaults to $USER"),
+  ("general.timezone", "Time zone that decides what 'today' is, e.g. Africa/Lagos; defaults to the system's"),
+  ("display.date_format", "How dates are shown: dd-mm-yyyy, iso, short or locale"),
+  ("display.table_style", "Default table style: modern, ascii, markdown or compact"),
+  ("display.language", "Message language (en, fr or es); defaults to your locale"),
+  ("display.hide_currency", "Leave the currency code out of amounts in tables (true/false)"),
+  ("display.compact_amounts", "Show large amounts in tables as 12.3k, 4.5M and so on (true/false)"),
+  ("export.dir", "Where 'fintrack export' writes when no path is given"),
+  ("list.default_limit", "How many records 'fintrack list' shows when neither --first nor --last is given; unset shows them all"),
+  ("list.default_order", "Which records list.default_limit keeps: newest or oldest"),
+  ("input.date_formats", "Date formats you can type, e.g. [\"DD-MM-YYYY\", \"YYYY-MM-DD\"]"),
+  ("input.decimals", "Decimal places amounts are kept to; defaults to the currency's (0 for JPY, 2 otherwise)"),
+  ("input.rounding", "What to do with extra decimals: half-up, half-even, down, up or reject"),
+  ("input.locale", "How you type amounts: en (1,234.56), de (1.234,56) or fr (1 234,56)"),
+  ("validation.strict", "Reject future dates, empty descriptions, unknown currencies and amounts above validation.max_amount when adding (true/false)"),
+  ("validation.max_amount", "Largest amount strict mode accepts; unset means no limit"),
+  ("validation.reject_exact_duplicates", "Refuse to add a record with the same date, amount, subcategory and description as an existing one (true/false)"),
+  ("limits.daily", "Warn when a day's expenses go over this amount; unset means no limit"),
+  ("limits.weekly", "Warn when a week's expenses go over this amount; weeks start on period.week_start"),
+  ("period.fiscal_year_start", "Month your financial year starts in, e.g. april; used by --year in report and describe"),
+  ("period.week_start", "Day weeks start on, e.g. sunday; used by 'report --week'"),
+  ("payday.day", "Day of the month you're paid, e.g. 25; 'fintrack status' then counts down to it"),
+  ("payday.weekend", "When payday falls on a weekend or holiday, you're paid the working day before or after, or on the day itself with none"),
+  ("payday.holidays", "Public holidays that move payday, e.g. [\"25-12-2026\", \"26-12-2026\"]"),
+  ("savings.round_up", "Count what each expense falls short of the next whole unit as round-up savings, shown by 'fintrack total' (true/false)"),
+  ("savings.round_up_to", "The unit expenses are rounded up to, e.g. 1 or 5"),
+  ("storage.compact", "Write tracker.json without indentation; smaller and faster for large trackers (true/false)"),
+  ("backup.enabled", "Copy the tracker to the backups directory before each change (true/false)"),
+  ("backup.max_count", "How many backups to keep; 0 keeps them all"),
+  ("backup.max_age_days", "Delete backups older than this many days"),
+  ("webhook.url", "URL that receives a JSON POST after each add, update or delete"),
+  ("notify.ntfy_topic", "ntfy.sh topic, or a full topic URL, for 'fintrack notify'"),
+  ("notify.telegram_token", "Telegram bot token for 'fintrack notify'"),
+  ("notify.telegram_chat_id", "Telegram chat that 'fintrack notify' sends to"),
+  ("notify.desktop", "Also show 'fintrack notify' messages as desktop notifications, and alerts from 'fintrack watch' (true/false)"),
+  ("email.smtp_host", "SMTP server for 'fintrack report --email'"),
+  ("email.smtp_port", "SMTP server port"),
+  ("email.smtp_security", "How to connect to the SMTP server: starttls, tls or none"),
+  ("email.smtp_user", "SMTP user name"),
+  ("email.smtp_password", "SMTP password; FINTRACK_SMTP_PASSWORD takes precedence"),
+  ("email.from", "Sender address, e.g. \"Fintrack <me@example.com>\""),
+  ("email.to", "Recipients, separated by commas"),
+  ("gsheet.spreadsheet_id", "Google Sheet that 'fintrack export -t gsheet' writes to"),
+  ("gsheet.access_token", "Google OAuth access token; FINTRACK_GSHEET_TOKEN takes precedence"),
+  ("gsheet.records_tab", "Tab that receives the records"),
+  ("gsheet.summary_tab", "Tab that receives the totals"),
+  ("invest.price_url", "URL 'fintrack invest value --fetch' gets prices from; {symbol} is replaced by each symbol"),
+  ("invest.price_field", "Where the price is in the JSON response, e.g. \"price\" or \"quote.last\""),
+  ("rates.provider", "Where 'add --currency' gets exchange rates when --rate isn't given: manual, exchangerate-host or ecb"),
+  ("rates.manual", "Rates for the manual provider, in the tracker's currency per unit, e.g. { EUR = 1.08, GBP = 1.27 }"),
+  ("rates.access_key", "API access key for exchangerate.host"),
+  ("schedule.run_on_start", "Add scheduled records that have come due before every command (true/false)"),
+  ("watch.view", "What 'fintrack watch' shows, e.g. \"list --last 10\" or \"total\""),
+];
+
+/// Settings `fintrack config list` masks; `config get` still shows them
+const SECRETS: [&str; 4] = [
+  "email.smtp_password",
+  "gsheet.access_token",
+  "notify.telegram_token",
+  "rates.access_key",
+];
+const MASK: &str = "********";
+
+const ALIASES_PREFIX: &str = "aliases.";
+const DEFAULT_ARGS_KEY: &str = "default";
+
+/// User settings stored in `~/.fintrack/config.toml`. Every key is optional;
+/// anything missing falls back to the built-in default.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+  pub general: GeneralConfig,
+  pub display: DisplayConfig,
+  pub export: ExportConfig,
+  pub list: ListConfig,
+  pub input: InputConfig,
+  pub validation: ValidationConfig,
+  pub limits: LimitsConfig,
+  pub period: PeriodConfig,
+  pub payday: PaydayConfig,
+  pub savings: SavingsConfig,
+  pub storage: StorageConfig,
+  pub backup: BackupConfig,
+  pub webhook: WebhookConfig,
+  pub notify: NotifyConfig,
+  pub email: EmailConfig,
+  pub gsheet: GsheetConfig,
+  pub watch: WatchConfig,
+  pub invest: InvestConfig,
+  pub rates: RatesConfig,
+  pub schedule: ScheduleConfig,
+  /// The `[aliases]` section: extra command names, e.g. `spent = "add expenses"`
+  pub aliases: BTreeMap<String, String>,
+  /// Sections named after a command, e.g. `[list]`
+  #[serde(flatten)]
+  pub commands: BTreeMap<String, CommandConfig>,
+}
+
+/// The `[general]` section: how commands behave
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GeneralConfig {
+  pub confirm: bool,
+  pub read_only: bool,
+  /// Stored as `entered_by` on new records; `None` falls back to `$USER`
+  pub user: Option<String>,
+  /// IANA time zone used to decide which day it is; `None` uses the
+  /// system's local time
+  pub timezone: Option<Tz>,
+}
+
+impl Default for GeneralConfig {
+  fn default() -> Self {
+    Self {
+      confirm: true,
+      read_only: false,
+      user: None,
+      timezone: None,
+    }
+  }
+}
+
+/// The `[display]` section: how command output is rendered
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+  pub date_format: DateFormat,
+  pub table_style: TableStyle,
+  /// `None` follows the locale (`LC_ALL`, `LC_MESSAGES`, `LANG`)
+  pub language: Option<Language>,
+  /// Leave the currency code out of amounts in tables
+  pub hide_currency: bool,
+  /// Show large amounts in tables as 12.3k, 4.5M and so on
+  pub compact_amounts: bool,
+}
+
+/// The `[export]` section
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ExportConfig {
+  /// Default directory for exports; a leading `~` is the home directory
+  pub dir: Option<String>,
+  /// Flags put before the ones typed for `fintrack export`
+  pub default: Option<String>,
+}
+
+/// The `[list]` section
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ListConfig {
+  /// Records shown without --first or --last; `None` shows them all
+  pub default_limit: Option<usize>,
+  pub default_order: ListOrder,
+  /// Flags put before the ones typed for `fintrack list`
+  pub default: Option<String>,
+}
+
+/// Which end of the records `list.default_limit` keeps
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListOrder {
+  /// The most recent, like --last
+  #[default]
+  Newest,
+  /// The earliest, like --first
+  Oldest,
+}
+
+/// The `[input]` section: what the user may type
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct InputConfig {
+  /// Tried in order, so put the format you use most first
+  pub date_formats: Vec<InputDateFormat>,
+  /// `None` uses the tracker currency's precision
+  pub decimals: Option<u32>,
+  pub rounding: RoundingMode,
+  /// Which characters group thousands and mark decimals in typed amounts
+  pub locale: NumberLocale,
+}
+
+impl Default for InputConfig {
+  fn default() -> Self {
+    Self {
+      date_formats: vec![InputDateFormat::default()],
+      decimals: None,
+      rounding: RoundingMode::default(),
+      locale: NumberLocale::default(),
+    }
+  }
+}
+
+/// The `[validation]` section: extra checks on new records
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ValidationConfig {
+  /// Same as passing `--strict` to every command
+  pub strict: bool,
+  /// Only checked in strict mode; `None` means no ceiling
+  pub max_amount: Option<f64>,
+  /// Checked whether or not strict mode is on
+  pub reject_exact_duplicates: bool,
+}
+
+/// The `[savings]` section: round-up savings, kept apart from the balance
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SavingsConfig {
+  pub round_up: bool,
+  /// Expenses are rounded up to a multiple of this; anything not above 0
+  /// counts as 1
+  pub round_up_to: f64,
+}
+
+impl Default for SavingsConfig {
+  fn default() -> Self {
+    Self {
+      round_up: false,
+      round_up_to: 1.0,
+    }
+  }
+}
+
+/// The `[limits]` section: soft spending limits. Going over one only warns.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LimitsConfig {
+  pub daily: Option<f64>,
+  pub weekly: Option<f64>,
+}
+
+/// The `[storage]` section: how the tracker file is written
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct StorageConfig {
+  /// Write JSON on a single line instead of pretty-printing it
+  pub compact: bool,
+}
+
+/// The `[backup]` section: automatic copies of the tracker file
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BackupConfig {
+  pub enabled: bool,
+  /// 0 means no limit
+  pub max_count: usize,
+  /// `None` keeps backups regardless of age
+  pub max_age_days: Option<u64>,
+}
+
+impl Default for BackupConfig {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      max_count: 10,
+      max_age_days: None,
+    }
+  }
+}
+
+/// The `[webhook]` section
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+  pub url: Option<String>,
+}
+
+/// The `[notify]` section: where `fintrack notify` sends summaries
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NotifyConfig {
+  pub ntfy_topic: Option<String>,
+  pub telegram_token: Option<String>,
+  pub telegram_chat_id: Option<String>,
+  /// Native notifications on this machine
+  pub desktop: bool,
+}
+
+/// The `[email]` section: how `fintrack report --email` sends mail
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct EmailConfig {
+  pub smtp_host: Option<String>,
+  pub smtp_port: u16,
+  pub smtp_security: SmtpSecurity,
+  pub smtp_user: Option<String>,
+  pub smtp_password: Option<String>,
+  pub from: Option<String>,
+  /// Comma-separated addresses
+  pub to: Option<String>,
+}
+
+impl Default for EmailConfig {
+  fn default() -> Self {
+    Self {
+      smtp_host: None,
+      smtp_port: 587,
+      smtp_security: SmtpSecurity::default(),
+      smtp_user: None,
+      smtp_password: None,
+      from: None,
+      to: None,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+  /// Upgrade a plain connection, usually on port 587
+  #[default]
+  Starttls,
+  /// TLS from the start, usually on port 465
+  Tls,
+  /// No encryption; only for local relays
+  None,
+}
+
+/// The `[gsheet]` section: where `fintrack export -t gsheet` writes
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GsheetConfig {
+  /// The long ID in the sheet's URL, between `/d/` and `/edit`
+  pub spreadsheet_id: Option<String>,
+  pub access_token: Option<String>,
+  pub records_tab: String,
+  pub summary_tab: String,
+}
+
+impl Default for GsheetConfig {
+  fn default() -> Self {
+    Self {
+      spreadsheet_id: None,
+      access_token: None,
+      records_tab: "Records".to_string(),
+      summary_tab: "Summary".to_string(),
+    }
+  }
+}
+
+/// The `[invest]` section: where `fintrack invest value --fetch` gets prices
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct InvestConfig {
+  /// Contains `{symbol}`, e.g. `https://quotes.example.com/v1/{symbol}`
+  pub price_url: Option<String>,
+  /// Dot-separated path to the price in the response
+  pub price_field: String,
+}
+
+impl Default for InvestConfig {
+  fn default() -> Self {
+    Self {
+      price_url: None,
+      price_field: "price".to_string(),
+    }
+  }
+}
+
+/// The `[rates]` section: where exchange rates come from when `add
+/// --currency` is given without `--rate`
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RatesConfig {
+  pub provider: RateSource,
+  /// Currency code to how much one unit is worth in the tracker's currency
+  #[serde(deserialize_with = "positive_rates")]
+  pub manual: BTreeMap<String, f64>,
+  /// Needed by exchangerate.host
+  pub access_key: Option<String>,
+}
+
+/// A `rates.provider`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RateSource {
+  /// Only the rates in `rates.manual`
+  #[default]
+  Manual,
+  ExchangerateHost,
+  /// The European Central Bank's daily reference rates
+  Ecb,
+}
+
+fn positive_rates<'de, D: serde::Deserializer<'de>>(
+  deserializer: D,
+) -> Result<BTreeMap<String, f64>, D::Error> {
+  let rates = BTreeMap::<String, f64>::deserialize(deserializer)?;
+  match rates.iter().find(|(_, rate)| !(rate.is_finite() && **rate > 0.0)) {
+    Some((currency, rate)) => Err(serde::de::Error::custom(format!(
+      "the rate for {} must be more than 0, not {}",
+      currency, rate
+    ))),
+    None => Ok(rates),
+  }
+}
+
+/// The `[period]` section: where years and weeks start
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PeriodConfig {
+  pub fiscal_year_start: MonthOfYear,
+  pub week_start: DayOfWeek,
+}
+
+/// The `[payday]` section: when salary arrives
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PaydayConfig {
+  /// 1 to 31; in shorter months the last day. `None` turns payday off.
+  #[serde(deserialize_with = "day_of_month")]
+  pub day: Option<u32>,
+  pub weekend: PaydayShift,
+  /// DD-MM-YYYY dates that count like a weekend
+  #[serde(deserialize_with = "stored_dates")]
+  pub holidays: Vec<String>,
+}
+
+/// Which way payday moves when it isn't a working day
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaydayShift {
+  /// The last working day before, as most employers pay
+  #[default]
+  Before,
+  After,
+  /// Paid on the day regardless
+  None,
+}
+
+fn day_of_month<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
+  let day = Option::<u32>::deserialize(deserializer)?;
+  match day {
+    Some(day) if !(1..=31).contains(&day) => Err(serde::de::Error::custom(format!(
+      "{} is not a day of the month",
+      day
+    ))),
+    day => Ok(day),
+  }
+}
+
+fn stored_dates<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+  let dates = Vec::<String>::deserialize(deserializer)?;
+  match dates.iter().find(|date| crate::parsers::parse_stored_date(date).is_none()) {
+    Some(date) => Err(serde::de::Error::custom(format!("'{}' is not a DD-MM-YYYY date", date))),
+    None => Ok(dates),
+  }
+}
+
+/// The `[schedule]` section
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ScheduleConfig {
+  /// Run `fintrack schedule run` before each command, instead of from cron
+  pub run_on_start: bool,
+}
+
+/// The `[watch]` section
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WatchConfig {
+  /// A command line run by `fintrack watch`
+  pub view: String,
+}
+
+impl Default for WatchConfig {
+  fn default() -> Self {
+    Self {
+      view: "list".to_string(),
+    }
+  }
+}
+
+/// A `[<command>]` section
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CommandConfig {
+  /// Flags put before the ones typed for this command, e.g. `"--last 20"`
+  pub default: Option<String>,
+}
+
+impl Config {
+  /// Read the config file, or the defaults if it doesn't exist yet
+  pub fn load(path: &Path) -> Result<Self, CliError> {
+    Self::from_table(read_table(path)?).map_err(|err| {
+      CliError::Other(t!("error-config-invalid", path = path.display().to_string(), reason = err.to_string()))
+    })
+  }
+
+  pub fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+    toml::from_str(contents)
+  }
+
+  fn from_table(table: Table) -> Result<Self, toml::de::Error> {
+    Value::Table(table).try_into()
+  }
+
+  /// The effective value of a setting, defaults included. `None` for settings
+  /// with no value, such as an undefined alias or a language left to the locale.
+  pub fn get(&self, key: &str) -> Result<Option<String>, ValidationErrorKind> {
+    let (section, name) = check_key(key)?;
+    let table = Table::try_from(self).expect("config serializes to a table");
+
+    Ok(
+      table
+        .get(section)
+        .and_then(|section| section.get(name))
+        .map(display_value),
+    )
+  }
+
+  /// Every setting that has a value, followed by the aliases. Secrets such
+  /// as passwords are masked.
+  pub fn entries(&self) -> Vec<(String, String)> {
+    let settings = SETTINGS.iter().filter_map(|(key, _)| {
+      let value = self.get(key).ok().flatten()?;
+      let value = if SECRETS.contains(key) { MASK.to_string() } else { value };
+      Some((key.to_string(), value))
+    });
+    let defaults = crate::commands::cli().into_iter().filter_map(|cmd| {
+      let args = self.default_args(cmd.get_name())?;
+      Some((format!("{}.{}", cmd.get_name(), DEFAULT_ARGS_KEY), args.to_string()))
+    });
+    let aliases = self
+      .aliases
+      .iter()
+      .map(|(name, expansion)| (format!("{}{}", ALIASES_PREFIX, name), expansion.clone()));
+
+    settings.chain(defaults).chain(aliases).collect()
+  }
+
+  /// The configured default flags for a top-level command
+  pub fn default_args(&self, command: &str) -> Option<&str> {
+    match command {
+      "export" => self.export.default.as_deref(),
+      "list" => self.list.default.as_deref(),
+      _ => self.commands.get(command)?.default.as_deref(),
+    }
+  }
+}
+
+/// Read the raw contents of the config file; a missing file is an empty table
+pub fn read_table(path: &Path) -> Result<Table, CliError> {
+  let contents = match fs::read_to_string(path) {
+    Ok(contents) => contents,
+    Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Table::new()),
+    Err(err) => return Err(err.into()),
+  };
+
+  contents.parse::<Table>().map_err(|err| {
+    CliError::Other(t!("error-config-invalid", path = path.display().to_string(), reason = err.to_string()))
+  })
+}
+
+pub fn write_table(path: &Path, table: &Table) -> Result<(), CliError> {
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  let contents = toml::to_string_pretty(table)
+    .map_err(|err| CliError::Other(t!("error-config-write", reason = err.to_string())))?;
+  fs::write(path, contents)?;
+  Ok(())
+}
+
+/// Set `key` in the raw config, checking the result is still a valid config.
+/// Values are read as TOML (`true`, `25`) where that makes sense for the key,
+/// and as plain strings otherwise, so `iso` and `"iso"` both work.
+pub fn set_value(table: &mut Table, key: &str, value: &str) -> Result<(), ValidationErrorKind> {
+  let (section, name) = check_key(key)?;
+
+  let mut candidates: Vec<Value> = format!("value = {}", value)
+    .parse::<Table>()
+    .ok()
+    .and_then(|mut parsed| parsed.remove("value"))
+    .into_iter()
+    .collect();
+  candidates.push(Value::String(value.to_string()));
+
+  let mut last_error = String::new();
+  for candidate in candidates {
+    let mut updated = table.clone();
+    section_mut(&mut updated, section).insert(name.to_string(), candidate);
+
+    match Config::from_table(updated.clone()) {
+      Ok(_) => {
+        *table = updated;
+        return Ok(());
+      }
+      Err(err) => last_error = err.message().to_string(),
+    }
+  }
+
+  Err(ValidationErrorKind::InvalidConfigValue {
+    key: key.to_string(),
+    value: value.to_string(),
+    reason: last_error,
+  })
+}
+
+/// Remove `key` from the raw config so its default applies again. Returns
+/// whether the key was set.
+pub fn unset_value(table: &mut Table, key: &str) -> Result<bool, ValidationErrorKind> {
+  let (section, name) = check_key(key)?;
+
+  let Some(Value::Table(entries)) = table.get_mut(section) else {
+    return Ok(false);
+  };
+  let removed = entries.remove(name).is_some();
+  if entries.is_empty() {
+    table.remove(section);
+  }
+
+  Ok(removed)
+}
+
+/// Split a known `section.name` key
+fn check_key(key: &str) -> Result<(&str, &str), ValidationErrorKind> {
+  let known = SETTINGS.iter().any(|(k, _)| *k == key)
+    || key.strip_prefix(ALIASES_PREFIX).is_some_and(|name| !name.is_empty())
+    || key.split_once('.').is_some_and(|(command, name)| {
+      name == DEFAULT_ARGS_KEY && crate::commands::cli().iter().any(|c| c.get_name() == command)
+    });
+
+  match key.split_once('.') {
+    Some(parts) if known => Ok(parts),
+    _ => Err(ValidationErrorKind::UnknownConfigKey {
+      key: key.to_string(),
+    }),
+  }
+}
+
+fn section_mut<'a>(table: &'a mut Table, section: &str) -> &'a mut Table {
+  let entry = table
+    .entry(section)
+    .or_insert_with(|| Value::Table(Table::new()));
+  if !entry.is_table() {
+    *entry = Value::Table(Table::new());
+  }
+  entry.as_table_mut().expect("section is a table")
+}
+
+fn display_value(value: &Value) -> String {
+  match value {
+    Value::String(s) => s.clone(),
+    other => other.to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_parse_display_section() {
+        let config = Config::parse("[display]\ndate_format = \"iso\"\n").unwrap();
+        assert_eq!(config.display.date_format, DateFormat::Iso);
+    }
+
+    #[test]
+    fn test_parse_aliases() {
+        let config = Config::parse("[aliases]\nspent = \"add expenses\"\nls = \"list --last 10\"\n").unwrap();
+        assert_eq!(config.aliases.len(), 2);
+        assert_eq!(config.aliases["spent"], "add expenses");
+    }
+
+    #[test]
+    fn test_rejects_unknown_date_format() {
+        assert!(Config::parse("[display]\ndate_format = \"yyyy\"\n").is_err());
+    }
+
+    #[test]
+    fn test_get_includes_defaults() {
+        let config = Config::parse("[display]\ntable_style = \"ascii\"\n").unwrap();
+        assert_eq!(config.get("display.table_style").unwrap().as_deref(), Some("ascii"));
+        assert_eq!(config.get("general.confirm").unwrap().as_deref(), Some("true"));
+        assert_eq!(config.get("display.language").unwrap(), None);
+        assert!(config.get("display.nope").is_err());
+    }
+
+    #[test]
+    fn test_set_value_validates_and_types_values() {
+        let mut table = Table::new();
+        set_value(&mut table, "general.confirm", "false").unwrap();
+        set_value(&mut table, "display.date_format", "iso").unwrap();
+        set_value(&mut table, "aliases.ls", "list --last 10").unwrap();
+
+        let config = Config::from_table(table.clone()).unwrap();
+        assert!(!config.general.confirm);
+        assert_eq!(config.display.date_format, DateFormat::Iso);
+        assert_eq!(config.aliases["ls"], "list --last 10");
+
+        assert!(matches!(
+            set_value(&mut table, "display.date_format", "yyyy"),
+            Err(ValidationErrorKind::InvalidConfigValue { .. })
+        ));
+        assert!(matches!(
+            set_value(&mut table, "display.colour", "red"),
+            Err(ValidationErrorKind::UnknownConfigKey { .. })
+        ));
+        // A rejected value leaves the config untouched
+        assert_eq!(Config::from_table(table).unwrap(), config);
+    }
+
+    #[test]
+    fn test_timezone_setting_takes_iana_names() {
+        let mut table = Table::new();
+        set_value(&mut table, "general.timezone", "Africa/Lagos").unwrap();
+        let config = Config::from_table(table.clone()).unwrap();
+        assert_eq!(config.general.timezone, Some(chrono_tz::Africa::Lagos));
+
+        assert!(matches!(
+            set_value(&mut table, "general.timezone", "Mars/Olympus_Mons"),
+            Err(ValidationErrorKind::InvalidConfigValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_input_date_formats() {
+        let config = Config::parse("[input]\ndate_formats = [\"YYYY-MM-DD\", \"dd/mm/yy\"]\n").unwrap();
+        let names: Vec<&str> = config.input.date_formats.iter().map(|f| f.name()).collect();
+        assert_eq!(names, ["YYYY-MM-DD", "DD/MM/YY"]);
+
+        let mut table = Table::new();
+        set_value(&mut table, "input.date_formats", "[\"DD-MM-YYYY\", \"YYYY-MM-DD\"]").unwrap();
+        assert!(set_value(&mut table, "input.date_formats", "[\"YYYY\"]").is_err());
+    }
+
+    #[test]
+    fn test_command_default_args() {
+        let config = Config::parse(
+            "[list]\ndefault = \"--last 20\"\n\n[export]\ndir = \"~\"\ndefault = \"-t csv\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.default_args("list"), Some("--last 20"));
+        assert_eq!(config.default_args("export"), Some("-t csv"));
+        assert_eq!(config.default_args("total"), None);
+
+        let mut table = Table::new();
+        set_value(&mut table, "total.default", "--category income").unwrap();
+        assert!(set_value(&mut table, "nope.default", "-x").is_err());
+        assert!(set_value(&mut table, "total.other", "-x").is_err());
+    }
+
+    #[test]
+    fn test_entries_mask_secrets() {
+        let config = Config::parse("[email]\nsmtp_host = \"smtp.example.com\"\nsmtp_password = \"hunter2\"\n").unwrap();
+        let entries = config.entries();
+        let value = |key: &str| entries.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+
+        assert_eq!(value("email.smtp_host"), Some("smtp.example.com"));
+        assert_eq!(value("email.smtp_password"), Some(MASK));
+        assert_eq!(config.get("email.smtp_password").unwrap().as_deref(), Some("hunter2"));
+    }
+
+    #[test]
+    fn test_unset_value_removes_empty_sections() {
+        let mut table = Table::new();
+        set_value(&mut table, "display.table_style", "markdown").unwrap();
+
+        assert!(unset_value(&mut table, "display.table_style").unwrap());
+        assert!(!unset_value(&mut table, "display.table_style").unwrap());
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_missing_file_uses_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::load(&dir.path().join("config.toml")).unwrap();
+        assert_eq!(config, Config::default());
+    }
+}
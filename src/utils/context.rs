@@ -1,20 +1,61 @@
-use std::path::PathBuf;
+use std::{cell::RefCell, fmt::Display, path::PathBuf};
+
+use chrono::{Local, NaiveDate, Utc};
+use colored::Colorize;
+
+use crate::{CliError, i18n, parsers, t};
+use crate::output::DisplayOptions;
+use crate::utils::config::Config;
+use crate::utils::storage::{DryRun, Session, TrackerCache};
+
+/// How much the CLI should print besides the command's own result
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Verbosity {
+  /// Only errors and requested data; confirmations are suppressed
+  Quiet,
+  #[default]
+  Normal,
+  /// Also report files touched, timings and parsed filters (on stderr)
+  Verbose,
+}
 
 #[derive(Debug)]
 pub struct GlobalContext {
   home_path: PathBuf, // The location of the user's home directory
   base_path: PathBuf,
   tracker_path: PathBuf, // The location of the tracker.json containing the data
-  config_path: PathBuf,  // The location of configuration
+  config_path: PathBuf,  // The location of config.toml
   backups_path: PathBuf, // The location of backups.
+  hooks_path: PathBuf,   // The location of user hook scripts
+  log_path: PathBuf,     // The location of the optional diagnostic log
+  status_path: PathBuf,  // The location of the summary cached for `status --short`
+  tokens_path: PathBuf,  // The location of the access tokens for `serve`
+  rates_path: PathBuf,   // The location of exchange rates fetched earlier
+  verbosity: Verbosity,
+  display: DisplayOptions,
+  config: Config,
+  session: Option<Session>, // Tracker data kept in memory by `fintrack shell`
+  custom_tracker: bool,     // Set by `--tracker-file`
+  read_only: bool,          // Set by `--read-only`
+  offline: bool,            // Set by `--offline`
+  dry_run: bool,            // Set by `--dry-run`
+  strict: bool,             // Set by `--strict`
+  interactive: bool,        // Someone is at the terminal to answer questions
+  cache: RefCell<Option<TrackerCache>>, // The last tracker read or written
+  staged: RefCell<Option<DryRun>>,      // What `--dry-run` kept from being written
 }
 
 impl GlobalContext {
   pub fn new(home_dir: PathBuf) -> Self {
     let base_path = home_dir.join(".fintrack");
     let tracker_path = base_path.join("tracker.json");
-    let config_path = base_path.join("/config");
-    let backups_path = base_path.join("/backups");
+    let config_path = base_path.join("config.toml");
+    let backups_path = base_path.join("backups");
+    let hooks_path = base_path.join("hooks");
+    let log_path = base_path.join("fintrack.log");
+    let status_path = base_path.join("status.json");
+    let tokens_path = base_path.join("tokens.json");
+    let rates_path = base_path.join("rates.json");
 
     GlobalContext {
       home_path: home_dir,
@@ -22,6 +63,23 @@ impl GlobalContext {
       tracker_path,
       config_path,
       backups_path,
+      hooks_path,
+      log_path,
+      status_path,
+      tokens_path,
+      rates_path,
+      verbosity: Verbosity::default(),
+      display: DisplayOptions::default(),
+      config: Config::default(),
+      session: None,
+      custom_tracker: false,
+      read_only: false,
+      offline: false,
+      dry_run: false,
+      strict: false,
+      interactive: false,
+      cache: RefCell::new(None),
+      staged: RefCell::new(None),
     }
   }
 
@@ -29,6 +87,19 @@ impl GlobalContext {
     &self.tracker_path
   }
 
+  /// Use the tracker at `path` instead of `~/.fintrack/tracker.json`. The
+  /// config still comes from `~/.fintrack`, but nothing else there is touched:
+  /// no backups, no cached status and no restoring from backups.
+  pub fn set_tracker_file(&mut self, path: PathBuf) {
+    self.tracker_path = path;
+    self.custom_tracker = true;
+  }
+
+  /// Whether the tracker was given with `--tracker-file`
+  pub fn has_custom_tracker(&self) -> bool {
+    self.custom_tracker
+  }
+
   pub fn home_path(&self) -> &PathBuf {
     &self.home_path
   }
@@ -44,4 +115,166 @@ impl GlobalContext {
   pub fn backups_path(&self) -> &PathBuf {
     &self.backups_path
   }
+
+  pub fn hooks_path(&self) -> &PathBuf {
+    &self.hooks_path
+  }
+
+  pub fn log_path(&self) -> &PathBuf {
+    &self.log_path
+  }
+
+  pub fn status_path(&self) -> &PathBuf {
+    &self.status_path
+  }
+
+  pub fn tokens_path(&self) -> &PathBuf {
+    &self.tokens_path
+  }
+
+  pub fn rates_path(&self) -> &PathBuf {
+    &self.rates_path
+  }
+
+  pub fn verbosity(&self) -> Verbosity {
+    self.verbosity
+  }
+
+  pub fn set_verbosity(&mut self, verbosity: Verbosity) {
+    self.verbosity = verbosity;
+  }
+
+  pub fn display(&self) -> &DisplayOptions {
+    &self.display
+  }
+
+  pub fn display_mut(&mut self) -> &mut DisplayOptions {
+    &mut self.display
+  }
+
+  pub fn config(&self) -> &Config {
+    &self.config
+  }
+
+  /// Read the user's config file and apply its display settings. Command-line
+  /// flags applied afterwards take precedence.
+  pub fn load_config(&mut self) -> Result<(), CliError> {
+    self.config = Config::load(&self.config_path)?;
+    self.display.date_format = self.config.display.date_format;
+    self.display.table_style = self.config.display.table_style;
+    self.display.hide_currency = self.config.display.hide_currency;
+    self.display.compact_amounts = self.config.display.compact_amounts;
+    parsers::set_input_date_formats(self.config.input.date_formats.clone());
+    parsers::set_input_locale(self.config.input.locale);
+    i18n::set_language(
+      self
+        .config
+        .display
+        .language
+        .unwrap_or_else(i18n::language_from_env),
+    );
+    Ok(())
+  }
+
+  pub fn session(&self) -> Option<&Session> {
+    self.session.as_ref()
+  }
+
+  pub(crate) fn session_mut(&mut self) -> Option<&mut Session> {
+    self.session.as_mut()
+  }
+
+  pub(crate) fn set_session(&mut self, session: Option<Session>) {
+    self.session = session;
+  }
+
+  pub(crate) fn cache(&self) -> &RefCell<Option<TrackerCache>> {
+    &self.cache
+  }
+
+  pub(crate) fn staged(&self) -> &RefCell<Option<DryRun>> {
+    &self.staged
+  }
+
+  /// Today's date in the `general.timezone` setting, or in the system's
+  /// time zone when that isn't set. Timestamps such as `last_modified` stay
+  /// in UTC; this is what dates new records and decides what's due.
+  pub fn today(&self) -> NaiveDate {
+    match self.config.general.timezone {
+      Some(tz) => Utc::now().with_timezone(&tz).date_naive(),
+      None => Local::now().date_naive(),
+    }
+  }
+
+  /// Whether commands must leave the tracker alone, from `--read-only` or the
+  /// `general.read_only` setting
+  pub fn is_read_only(&self) -> bool {
+    self.read_only || self.config.general.read_only
+  }
+
+  pub fn set_read_only(&mut self, read_only: bool) {
+    self.read_only = read_only;
+  }
+
+  /// Whether the network is off limits, from `--offline`
+  pub fn is_offline(&self) -> bool {
+    self.offline
+  }
+
+  pub fn set_offline(&mut self, offline: bool) {
+    self.offline = offline;
+  }
+
+  /// Whether changes to the tracker are shown instead of written, from
+  /// `--dry-run`
+  pub fn is_dry_run(&self) -> bool {
+    self.dry_run
+  }
+
+  pub fn set_dry_run(&mut self, dry_run: bool) {
+    self.dry_run = dry_run;
+  }
+
+  /// Fail with a message naming `what` when running `--offline`
+  pub fn ensure_online(&self, what: &str) -> Result<(), CliError> {
+    match self.offline {
+      true => Err(CliError::Other(t!("error-offline", what = what))),
+      false => Ok(()),
+    }
+  }
+
+  /// Whether new records get the extra checks of strict mode, from `--strict`
+  /// or the `validation.strict` setting
+  pub fn is_strict(&self) -> bool {
+    self.strict || self.config.validation.strict
+  }
+
+  pub fn set_strict(&mut self, strict: bool) {
+    self.strict = strict;
+  }
+
+  /// Whether a command may stop to ask something: text output to a terminal,
+  /// not quiet, and not inside `batch` or `shell`
+  pub fn is_interactive(&self) -> bool {
+    self.interactive && !self.is_quiet() && self.session.is_none()
+  }
+
+  pub fn set_interactive(&mut self, interactive: bool) {
+    self.interactive = interactive;
+  }
+
+  pub fn is_quiet(&self) -> bool {
+    self.verbosity == Verbosity::Quiet
+  }
+
+  pub fn is_verbose(&self) -> bool {
+    self.verbosity == Verbosity::Verbose
+  }
+
+  /// Print a diagnostic line to stderr when running with `--verbose`
+  pub fn verbose(&self, message: impl Display) {
+    if self.is_verbose() {
+      eprintln!("{} {}", "[verbose]".dimmed(), message);
+    }
+  }
 }
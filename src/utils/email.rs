@@ -0,0 +1,67 @@
+use lettre::{
+  Message, SmtpTransport, Transport,
+  message::{Mailbox, header::ContentType},
+  transport::smtp::authentication::Credentials,
+};
+
+use crate::{
+  CliError, t,
+  utils::config::{EmailConfig, SmtpSecurity},
+};
+
+/// Overrides `email.smtp_password`, so the password needn't be kept in config.toml
+pub const PASSWORD_ENV: &str = "FINTRACK_SMTP_PASSWORD";
+
+/// Send an HTML email to the addresses in `email.to`, returning them
+pub fn send_html(config: &EmailConfig, subject: &str, html: String) -> Result<Vec<String>, CliError> {
+  let (Some(host), Some(from), Some(to)) = (&config.smtp_host, &config.from, &config.to) else {
+    return Err(CliError::Other(t!("error-email-not-set-up")));
+  };
+
+  let recipients: Vec<String> = to
+    .split(',')
+    .map(|address| address.trim().to_string())
+    .filter(|address| !address.is_empty())
+    .collect();
+
+  let mut message = Message::builder()
+    .from(parse_mailbox(from)?)
+    .subject(subject)
+    .header(ContentType::TEXT_HTML);
+  for recipient in &recipients {
+    message = message.to(parse_mailbox(recipient)?);
+  }
+  let message = message
+    .body(html)
+    .map_err(|err| CliError::Other(t!("error-email-build", reason = err.to_string())))?;
+
+  let mut transport = match config.smtp_security {
+    SmtpSecurity::Starttls => SmtpTransport::starttls_relay(host),
+    SmtpSecurity::Tls => SmtpTransport::relay(host),
+    SmtpSecurity::None => Ok(SmtpTransport::builder_dangerous(host)),
+  }
+  .map_err(|err| CliError::Other(t!("error-smtp-host", host = host.as_str(), reason = err.to_string())))?
+  .port(config.smtp_port);
+
+  if let Some(user) = &config.smtp_user {
+    let password = std::env::var(PASSWORD_ENV)
+      .ok()
+      .or_else(|| config.smtp_password.clone())
+      .unwrap_or_default();
+    transport = transport.credentials(Credentials::new(user.clone(), password));
+  }
+
+  let _span = tracing::info_span!("email", %host, port = config.smtp_port).entered();
+  transport
+    .build()
+    .send(&message)
+    .map_err(|err| CliError::Other(t!("error-email-send", reason = err.to_string())))?;
+
+  Ok(recipients)
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, CliError> {
+  address
+    .parse::<Mailbox>()
+    .map_err(|err| CliError::Other(t!("error-email-address", address = address, reason = err.to_string())))
+}
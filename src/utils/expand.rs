@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+
+use clap::Command;
+
+use crate::{parsers::split_command_line, utils::config::Config};
+
+/// Expand a user-defined alias in the command position of `args` (which don't
+/// include the binary name), the way git expands its aliases. Anything after
+/// the alias is kept, so `ls = "list --last 10"` makes `ls --summary` run
+/// `list --last 10 --summary`.
+///
+/// Built-in commands always win over an alias of the same name. An alias may
+/// expand to another alias, but not back to itself.
+pub fn expand_aliases(
+  mut args: Vec<String>,
+  aliases: &BTreeMap<String, String>,
+  root: &Command,
+) -> Result<Vec<String>, String> {
+  let mut expanded: Vec<String> = Vec::new();
+
+  while let Some(index) = command_index(&args, root) {
+    let name = &args[index];
+    if root.find_subcommand(name).is_some() {
+      break;
+    }
+    let Some(expansion) = aliases.get(name) else {
+      break;
+    };
+
+    if expanded.contains(name) {
+      return Err(format!("Alias '{}' expands back to itself", name));
+    }
+    let replacement = split_command_line(expansion)
+      .map_err(|err| format!("Alias '{}' is invalid: {}", name, err))?;
+    if replacement.is_empty() {
+      return Err(format!("Alias '{}' is empty", name));
+    }
+
+    expanded.push(name.clone());
+    args.splice(index..=index, replacement);
+  }
+
+  Ok(args)
+}
+
+/// Put a command's configured default flags (e.g. `list.default = "--last 20"`)
+/// right after the command name in `args`. Flags typed on the command line
+/// come later, and the command is set up so that a repeated option overrides
+/// the earlier one, so what the user typed always wins over the defaults.
+pub fn insert_default_args(
+  root: Command,
+  mut args: Vec<String>,
+  config: &Config,
+) -> Result<(Command, Vec<String>), String> {
+  let Some(index) = command_index(&args, &root) else {
+    return Ok((root, args));
+  };
+  let name = args[index].clone();
+  let Some(defaults) = config.default_args(&name) else {
+    return Ok((root, args));
+  };
+  if root.find_subcommand(&name).is_none() {
+    return Ok((root, args));
+  }
+
+  let defaults = split_command_line(defaults)
+    .map_err(|err| format!("Default flags for '{}' are invalid: {}", name, err))?;
+  args.splice(index + 1..index + 1, defaults);
+
+  let root = root.mut_subcommand(&name, |cmd| cmd.args_override_self(true));
+  Ok((root, args))
+}
+
+/// Position of the first argument that isn't a global option or its value
+fn command_index(args: &[String], root: &Command) -> Option<usize> {
+  let mut iter = args.iter().enumerate();
+
+  while let Some((index, arg)) = iter.next() {
+    if arg == "--" {
+      return None;
+    }
+
+    let takes_value = if let Some(long) = arg.strip_prefix("--") {
+      !long.contains('=') && root.get_arguments().any(|a| a.get_long() == Some(long) && a.get_action().takes_values())
+    } else if let Some(short) = arg.strip_prefix('-').filter(|s| !s.is_empty()) {
+      let mut chars = short.chars();
+      let flag = chars.next();
+      chars.next().is_none()
+        && root
+          .get_arguments()
+          .any(|a| a.get_short() == flag && a.get_action().takes_values())
+    } else {
+      return Some(index);
+    };
+
+    if takes_value {
+      iter.next();
+    }
+  }
+
+  None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+
+    fn root() -> Command {
+        Command::new("fintrack")
+            .no_binary_name(true)
+            .arg(Arg::new("output").long("output").global(true))
+            .arg(Arg::new("quiet").short('q').long("quiet").action(clap::ArgAction::SetTrue))
+            .subcommand(Command::new("add"))
+            .subcommand(Command::new("list"))
+    }
+
+    fn aliases(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn args(line: &str) -> Vec<String> {
+        split_command_line(line).unwrap()
+    }
+
+    #[test]
+    fn test_expands_alias_and_keeps_trailing_args() {
+        let aliases = aliases(&[("spent", "add expenses"), ("ls", "list --last 10")]);
+
+        assert_eq!(
+            expand_aliases(args("spent 20 -s Food"), &aliases, &root()).unwrap(),
+            args("add expenses 20 -s Food")
+        );
+        assert_eq!(
+            expand_aliases(args("ls --summary"), &aliases, &root()).unwrap(),
+            args("list --last 10 --summary")
+        );
+    }
+
+    #[test]
+    fn test_skips_global_options_before_the_command() {
+        let aliases = aliases(&[("ls", "list --last 10")]);
+
+        assert_eq!(
+            expand_aliases(args("--output json -q ls"), &aliases, &root()).unwrap(),
+            args("--output json -q list --last 10")
+        );
+        assert_eq!(
+            expand_aliases(args("--output=json ls"), &aliases, &root()).unwrap(),
+            args("--output=json list --last 10")
+        );
+    }
+
+    #[test]
+    fn test_builtin_commands_win_over_aliases() {
+        let aliases = aliases(&[("list", "add income")]);
+        assert_eq!(expand_aliases(args("list"), &aliases, &root()).unwrap(), args("list"));
+    }
+
+    #[test]
+    fn test_quoted_alias_arguments() {
+        let aliases = aliases(&[("coffee", "add expenses 3 --description 'Morning coffee'")]);
+        let expanded = expand_aliases(args("coffee"), &aliases, &root()).unwrap();
+        assert_eq!(expanded.last().unwrap(), "Morning coffee");
+    }
+
+    #[test]
+    fn test_inserts_default_args_before_typed_ones() {
+        let root = root().mut_subcommand("list", |cmd| {
+            cmd.arg(Arg::new("last").long("last").value_parser(clap::value_parser!(usize)))
+        });
+        let config = Config::parse("[list]\ndefault = \"--last 20\"\n").unwrap();
+
+        let (root, expanded) = insert_default_args(root, args("-q list --last 5"), &config).unwrap();
+        assert_eq!(expanded, args("-q list --last 20 --last 5"));
+
+        // The typed value wins instead of clap rejecting the repeat
+        let matches = root.try_get_matches_from(expanded).unwrap();
+        let (_, list) = matches.subcommand().unwrap();
+        assert_eq!(list.get_one::<usize>("last"), Some(&5));
+    }
+
+    #[test]
+    fn test_commands_without_defaults_are_unchanged() {
+        let config = Config::parse("[list]\ndefault = \"--last 20\"\n").unwrap();
+        let (_, expanded) = insert_default_args(root(), args("add income 5"), &config).unwrap();
+        assert_eq!(expanded, args("add income 5"));
+    }
+
+    #[test]
+    fn test_nested_and_recursive_aliases() {
+        let nested = aliases(&[("l", "ls"), ("ls", "list --last 10")]);
+        assert_eq!(
+            expand_aliases(args("l"), &nested, &root()).unwrap(),
+            args("list --last 10")
+        );
+
+        let looping = aliases(&[("a", "b"), ("b", "a --x")]);
+        assert!(expand_aliases(args("a"), &looping, &root()).is_err());
+    }
+}
@@ -2,20 +2,32 @@ use std::io::prelude::*;
 
 use std::{
   fs::{self, File},
-  io,
+  io::{self, BufWriter},
   path::Path,
 };
 
-use serde_json::Value;
+use serde::Serialize;
 
 use crate::CliError;
 
-pub fn write_json_to_file(json: &Value, file: &mut File) -> Result<(), CliError> {
-  let json_string = serde_json::to_string_pretty(&json)?;
-
+/// Replace the file's contents with `value` as JSON, streamed through a
+/// buffer rather than built up as one string first. `compact` leaves out the
+/// indentation and newlines.
+pub fn write_json_to_file<T: Serialize + ?Sized>(
+  value: &T,
+  file: &mut File,
+  compact: bool,
+) -> Result<(), CliError> {
   file.seek(io::SeekFrom::Start(0))?;
   file.set_len(0)?;
-  file.write_all(json_string.as_bytes())?;
+
+  let mut writer = BufWriter::new(&mut *file);
+  if compact {
+    serde_json::to_writer(&mut writer, value).map_err(io::Error::from)?;
+  } else {
+    serde_json::to_writer_pretty(&mut writer, value).map_err(io::Error::from)?;
+  }
+  writer.flush()?;
 
   Ok(())
 }
@@ -60,6 +72,7 @@ impl<P: AsRef<Path>> FilePath for P {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::Value;
     use std::io::Read;
     use tempfile::TempDir;
 
@@ -179,7 +192,7 @@ mod tests {
         });
 
         let mut file = file_path.create_file_if_not_exists().unwrap();
-        write_json_to_file(&json, &mut file).unwrap();
+        write_json_to_file(&json, &mut file, false).unwrap();
 
         let content = fs::read_to_string(&file_path).unwrap();
         let parsed: Value = serde_json::from_str(&content).unwrap();
@@ -197,10 +210,10 @@ mod tests {
         let json2 = serde_json::json!({"new": "data"});
 
         let mut file = file_path.create_file_if_not_exists().unwrap();
-        write_json_to_file(&json1, &mut file).unwrap();
+        write_json_to_file(&json1, &mut file, false).unwrap();
 
         let mut file = file_path.open_read_write().unwrap();
-        write_json_to_file(&json2, &mut file).unwrap();
+        write_json_to_file(&json2, &mut file, false).unwrap();
 
         let content = fs::read_to_string(&file_path).unwrap();
         let parsed: Value = serde_json::from_str(&content).unwrap();
@@ -208,4 +221,17 @@ mod tests {
         assert_eq!(parsed["new"], "data");
         assert!(parsed.get("old").is_none());
     }
+
+    #[test]
+    fn test_write_json_to_file_compact() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.json");
+
+        let json = serde_json::json!({"key": "value", "list": [1, 2]});
+        let mut file = file_path.create_file_if_not_exists().unwrap();
+        write_json_to_file(&json, &mut file, true).unwrap();
+
+        let content = fs::read_to_string(&file_path).unwrap();
+        assert_eq!(content, r#"{"key":"value","list":[1,2]}"#);
+    }
 }
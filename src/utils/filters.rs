@@ -0,0 +1,550 @@
+//! Record filters shared by commands that look at a slice of the records,
+//! such as `list` and `total`
+
+use std::borrow::Cow;
+
+use chrono::{Days, Months, NaiveDate};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::command_prelude::{ArgMatchesExt, GlobalContext};
+use crate::parsers::{parse_amount, parse_category, parse_date, parse_month, parse_tag};
+use crate::utils::validation::decimals;
+use crate::{CliError, Record, TrackerData, t};
+
+/// An inclusive date range; `None` leaves that end open
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DateRange {
+  pub start: Option<NaiveDate>,
+  pub end: Option<NaiveDate>,
+}
+
+impl DateRange {
+  pub fn is_all_time(&self) -> bool {
+    self.start.is_none() && self.end.is_none()
+  }
+
+  /// The days this range and `other` have in common
+  pub fn within(self, other: DateRange) -> DateRange {
+    DateRange {
+      start: self.start.max(other.start),
+      end: match (self.end, other.end) {
+        (Some(end), Some(other)) => Some(end.min(other)),
+        (end, other) => end.or(other),
+      },
+    }
+  }
+}
+
+/// Add `--start`, `--end`, `--month` and `--days` to `cmd`
+pub fn date_range_args(cmd: Command) -> Command {
+  cmd
+    .arg(
+      Arg::new("start")
+        .short('S')
+        .long("start")
+        .value_parser(clap::value_parser!(String))
+        .help("Only include records from this date onwards (DD-MM-YYYY)")
+        .long_help("Only records on or after this date. Format: DD-MM-YYYY (e.g., 01-12-2025), or any format listed in the 'input.date_formats' setting. Use with --end to specify a date range."),
+    )
+    .arg(
+      Arg::new("end")
+        .short('E')
+        .long("end")
+        .value_parser(clap::value_parser!(String))
+        .help("Only include records up to this date (DD-MM-YYYY)")
+        .long_help("Only records on or before this date. Format: DD-MM-YYYY (e.g., 31-12-2025), or any format listed in the 'input.date_formats' setting. Use with --start to specify a date range."),
+    )
+    .arg(
+      Arg::new("month")
+        .long("month")
+        .value_parser(parse_month)
+        .conflicts_with_all(["start", "end"])
+        .help("Only include records in this month (MM-YYYY)"),
+    )
+    .arg(
+      Arg::new("days")
+        .long("days")
+        .value_parser(clap::value_parser!(u32).range(1..))
+        .conflicts_with_all(["start", "end", "month"])
+        .help("Only include records from the last N days, today included"),
+    )
+}
+
+/// The range picked with the arguments from [`date_range_args`]
+pub fn date_range(gctx: &GlobalContext, args: &ArgMatches) -> Result<DateRange, CliError> {
+  if let Some(month) = args.get_one::<NaiveDate>("month") {
+    return Ok(DateRange {
+      start: Some(*month),
+      end: Some(*month + Months::new(1) - Days::new(1)),
+    });
+  }
+  if let Some(days) = args.get_one::<u32>("days") {
+    let today = gctx.today();
+    return Ok(DateRange {
+      start: Some(today - Days::new(u64::from(*days) - 1)),
+      end: Some(today),
+    });
+  }
+
+  Ok(DateRange {
+    start: args.get_date_opt("start")?,
+    end: args.get_date_opt("end")?,
+  })
+}
+
+/// Which records a command works on. Every part that's set has to match;
+/// an empty filter matches every record.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RecordFilter {
+  pub dates: DateRange,
+  pub category: Option<usize>,
+  pub subcategory: Option<usize>,
+  pub user: Option<String>,
+  pub deductible: bool,
+  pub project: Option<String>,
+  pub tag: Option<String>,
+  pub min_amount: Option<f64>,
+  pub max_amount: Option<f64>,
+  /// Lowercased, as descriptions are compared ignoring case
+  pub text: Option<String>,
+}
+
+impl RecordFilter {
+  /// The filter picked with the arguments from [`filter_args`]
+  pub fn from_args(
+    gctx: &GlobalContext,
+    tracker_data: &TrackerData,
+    args: &ArgMatches,
+  ) -> Result<Self, CliError> {
+    let mut filter = RecordFilter::default().with_dates(date_range(gctx, args)?);
+    if let Some(category) = args.get_category_opt("category") {
+      filter = filter.with_category(tracker_data.category_id(&category.to_string()));
+    }
+    if let Some(name) = args.get_subcategory_opt("subcategory") {
+      let id = tracker_data
+        .subcategory_id(&name)
+        .ok_or_else(|| tracker_data.subcategory_not_found(name))?;
+      filter = filter.with_subcategory(id);
+    }
+    if let Some(user) = args.get_one::<String>("by-user") {
+      filter = filter.with_user(user);
+    }
+    if args.get_flag("deductible") {
+      filter = filter.with_deductible();
+    }
+    if let Some(project) = args.get_one::<String>("project") {
+      filter = filter.with_project(project);
+    }
+    if let Some(tag) = args.get_one::<String>("tag") {
+      filter = filter.with_tag(tag);
+    }
+    if let Some(text) = args.get_one::<String>("text") {
+      filter = filter.with_text(text);
+    }
+
+    let min_amount = args.get_f64_opt("min-amount");
+    let max_amount = args.get_f64_opt("max-amount");
+    if let (Some(min), Some(max)) = (min_amount, max_amount)
+      && min > max
+    {
+      return Err(CliError::Other(t!("error-amount-range", min = min, max = max)));
+    }
+    filter = filter.with_amounts(min_amount, max_amount);
+
+    match args.get_one::<String>("where") {
+      Some(expression) => filter.with_where(gctx, tracker_data, expression),
+      None => Ok(filter),
+    }
+  }
+
+  /// Narrow the filter down with a `--where` expression: conditions such as
+  /// `subcategory = groceries`, `amount < 10` or `date >= 01-09-2026`, joined
+  /// with `and`. A condition on something the filter already picks has to agree
+  /// with it, so `-s rent --where "subcategory = food"` is an error rather than
+  /// a surprise.
+  pub fn with_where(
+    mut self,
+    gctx: &GlobalContext,
+    tracker_data: &TrackerData,
+    expression: &str,
+  ) -> Result<Self, CliError> {
+    let invalid = |reason: String| CliError::Other(t!("error-where", expression = expression, reason = reason));
+    // Amounts are kept to the currency's decimals, so `< 10` is `<= 9.99`
+    let step = 10f64.powi(-(decimals(gctx, &tracker_data.currency) as i32));
+
+    let conditions = conditions(expression).map_err(invalid)?;
+    for (field, op, value) in conditions {
+      let unsupported = || invalid(t!("where-unsupported-op", op = op, field = field.as_str()));
+      let agree = |current: &mut Option<String>, value: &str| match current {
+        Some(current) if !current.eq_ignore_ascii_case(value) => {
+          Err(invalid(t!("where-already-set", field = field.as_str(), value = current.as_str())))
+        }
+        _ => {
+          *current = Some(value.to_string());
+          Ok(())
+        }
+      };
+
+      match field.to_lowercase().as_str() {
+        "category" | "subcategory" if op != "=" => return Err(unsupported()),
+        "category" => {
+          let id = tracker_data.category_id(&parse_category(&value).map_err(invalid)?.to_string());
+          if self.category.is_some_and(|current| current != id) {
+            return Err(invalid(t!("where-category-set")));
+          }
+          self.category = Some(id);
+        }
+        "subcategory" => {
+          let id = tracker_data
+            .subcategory_id(&value)
+            .ok_or_else(|| tracker_data.subcategory_not_found(&value))?;
+          if self.subcategory.is_some_and(|current| current != id) {
+            return Err(invalid(t!("where-subcategory-set")));
+          }
+          self.subcategory = Some(id);
+        }
+        "user" | "by" | "project" | "tag" | "deductible" if op != "=" => return Err(unsupported()),
+        "user" | "by" => agree(&mut self.user, &value)?,
+        "project" => agree(&mut self.project, &value)?,
+        "tag" => agree(&mut self.tag, value.strip_prefix('#').unwrap_or(&value))?,
+        "deductible" => match value.to_lowercase().as_str() {
+          "true" | "yes" => self.deductible = true,
+          _ => return Err(invalid(t!("where-deductible"))),
+        },
+        "description" | "text" if op != "~" => {
+          return Err(invalid(t!("where-use-contains", field = field.as_str())));
+        }
+        "description" | "text" => agree(&mut self.text, &value.to_lowercase())?,
+        "amount" => {
+          let amount = parse_amount(&value).map_err(invalid)?;
+          let (min, max) = match op {
+            "=" => (Some(amount), Some(amount)),
+            ">=" => (Some(amount), None),
+            ">" => (Some(amount + step), None),
+            "<=" => (None, Some(amount)),
+            "<" => (None, Some(amount - step)),
+            _ => return Err(unsupported()),
+          };
+          self.min_amount = match (self.min_amount, min) {
+            (Some(current), Some(min)) => Some(current.max(min)),
+            (current, min) => current.or(min),
+          };
+          self.max_amount = match (self.max_amount, max) {
+            (Some(current), Some(max)) => Some(current.min(max)),
+            (current, max) => current.or(max),
+          };
+        }
+        "date" => {
+          let date = parse_date(&value).map_err(CliError::ValidationError)?;
+          let (start, end) = match op {
+            "=" => (Some(date), Some(date)),
+            ">=" => (Some(date), None),
+            ">" => (date.succ_opt(), None),
+            "<=" => (None, Some(date)),
+            "<" => (None, date.pred_opt()),
+            _ => return Err(unsupported()),
+          };
+          self.dates = self.dates.within(DateRange { start, end });
+        }
+        _ => {
+          return Err(invalid(t!("where-unknown-field", field = field.as_str())));
+        }
+      }
+    }
+    Ok(self)
+  }
+
+  pub fn with_dates(self, dates: DateRange) -> Self {
+    RecordFilter { dates, ..self }
+  }
+
+  pub fn with_category(self, category: usize) -> Self {
+    RecordFilter {
+      category: Some(category),
+      ..self
+    }
+  }
+
+  pub fn with_subcategory(self, subcategory: usize) -> Self {
+    RecordFilter {
+      subcategory: Some(subcategory),
+      ..self
+    }
+  }
+
+  /// Only records entered by `user`, ignoring case
+  pub fn with_user(self, user: &str) -> Self {
+    RecordFilter {
+      user: Some(user.to_string()),
+      ..self
+    }
+  }
+
+  pub fn with_deductible(self) -> Self {
+    RecordFilter {
+      deductible: true,
+      ..self
+    }
+  }
+
+  /// Only records in `project`, ignoring case
+  pub fn with_project(self, project: &str) -> Self {
+    RecordFilter {
+      project: Some(project.to_string()),
+      ..self
+    }
+  }
+
+  /// Only records carrying `tag`, ignoring case
+  pub fn with_tag(self, tag: &str) -> Self {
+    RecordFilter {
+      tag: Some(tag.to_string()),
+      ..self
+    }
+  }
+
+  /// Only amounts from `min` to `max`, both included
+  pub fn with_amounts(self, min: Option<f64>, max: Option<f64>) -> Self {
+    RecordFilter {
+      min_amount: min,
+      max_amount: max,
+      ..self
+    }
+  }
+
+  /// Only records whose description contains `text`, ignoring case
+  pub fn with_text(self, text: &str) -> Self {
+    RecordFilter {
+      text: Some(text.to_lowercase()),
+      ..self
+    }
+  }
+
+  /// Whether the filter matches every record
+  pub fn is_empty(&self) -> bool {
+    *self == RecordFilter::default()
+  }
+
+  pub fn matches(&self, record: &Record) -> bool {
+    let in_range = |date: Option<NaiveDate>| {
+      self.dates.is_all_time()
+        || date.is_some_and(|date| {
+          self.dates.start.is_none_or(|start| date >= start) && self.dates.end.is_none_or(|end| date <= end)
+        })
+    };
+    // Names set on records before they could be filtered on never match
+    let same_name = |name: &Option<String>, expected: &Option<String>| {
+      expected
+        .as_ref()
+        .is_none_or(|expected| name.as_ref().is_some_and(|name| name.eq_ignore_ascii_case(expected)))
+    };
+
+    self.category.is_none_or(|id| record.category == id)
+      && self.subcategory.is_none_or(|id| record.subcategory == id)
+      && same_name(&record.entered_by, &self.user)
+      && (!self.deductible || record.deductible)
+      && same_name(&record.project, &self.project)
+      && self
+        .tag
+        .as_ref()
+        .is_none_or(|expected| record.tags.iter().any(|tag| tag.eq_ignore_ascii_case(expected)))
+      && self.min_amount.is_none_or(|min| record.amount >= min)
+      && self.max_amount.is_none_or(|max| record.amount <= max)
+      && self
+        .text
+        .as_ref()
+        .is_none_or(|text| record.description.to_lowercase().contains(text.as_str()))
+      && in_range(record.naive_date())
+  }
+
+  /// The matching records, in date order. Records are kept in date order, so
+  /// a filter on dates alone is a slice of them and nothing is copied.
+  pub fn select<'a>(&self, tracker_data: &'a TrackerData) -> Cow<'a, [Record]> {
+    let records = if self.dates.is_all_time() {
+      &tracker_data.records[..]
+    } else {
+      tracker_data.records_between(self.dates.start, self.dates.end)
+    };
+
+    let dates_only = RecordFilter {
+      dates: self.dates,
+      ..RecordFilter::default()
+    };
+    if *self == dates_only {
+      Cow::Borrowed(records)
+    } else {
+      Cow::Owned(records.iter().filter(|r| self.matches(r)).cloned().collect())
+    }
+  }
+}
+
+/// The ids of the arguments [`filter_args`] adds
+pub const FILTER_ARGS: [&str; 14] = [
+  "start",
+  "end",
+  "month",
+  "days",
+  "category",
+  "subcategory",
+  "by-user",
+  "deductible",
+  "project",
+  "tag",
+  "min-amount",
+  "max-amount",
+  "text",
+  "where",
+];
+
+/// Add the record filters to `cmd`: the dates from [`date_range_args`],
+/// `--category`, `--subcategory`, `--by-user`, `--deductible`, `--project`,
+/// `--tag`, `--min-amount`, `--max-amount`, `--text` and `--where`
+pub fn filter_args(cmd: Command) -> Command {
+  date_range_args(cmd)
+    .arg(
+      Arg::new("category")
+        .short('c')
+        .long("category")
+        .value_parser(parse_category)
+        .help("Only include records in this category: 'income' or 'expenses'"),
+    )
+    .arg(
+      Arg::new("subcategory")
+        .short('s')
+        .long("subcategory")
+        .value_parser(clap::value_parser!(String))
+        .help("Only include records in this subcategory")
+        .long_help("Only records in the given subcategory. The name is case-insensitive. Use 'fintrack subcategory list' to see available subcategories."),
+    )
+    .arg(
+      Arg::new("by-user")
+        .short('u')
+        .long("by-user")
+        .value_parser(clap::value_parser!(String))
+        .help("Only include records entered by this person")
+        .long_help("Only records whose 'entered by' name matches, ignoring case. Records added before attribution existed have no name and never match."),
+    )
+    .arg(
+      Arg::new("deductible")
+        .long("deductible")
+        .action(ArgAction::SetTrue)
+        .help("Only include tax-deductible records"),
+    )
+    .arg(
+      Arg::new("project")
+        .long("project")
+        .value_parser(clap::value_parser!(String))
+        .help("Only include records in this project (case-insensitive)"),
+    )
+    .arg(
+      Arg::new("tag")
+        .long("tag")
+        .value_parser(parse_tag)
+        .help("Only include records with this tag (case-insensitive)"),
+    )
+    .arg(
+      Arg::new("min-amount")
+        .long("min-amount")
+        .value_name("AMOUNT")
+        .value_parser(parse_amount)
+        .help("Only include records of at least this amount"),
+    )
+    .arg(
+      Arg::new("max-amount")
+        .long("max-amount")
+        .value_name("AMOUNT")
+        .value_parser(parse_amount)
+        .help("Only include records of at most this amount"),
+    )
+    .arg(
+      Arg::new("text")
+        .long("text")
+        .value_parser(clap::value_parser!(String))
+        .help("Only include records whose description contains this text (case-insensitive)"),
+    )
+    .arg(
+      Arg::new("where")
+        .long("where")
+        .value_name("CONDITIONS")
+        .value_parser(clap::value_parser!(String))
+        .help("Only include records matching these conditions, e.g. \"subcategory = food and amount < 10\"")
+        .long_help("Conditions joined with 'and', each a field, an operator and a value: 'category = income', 'subcategory = food', 'user = ada', 'project = \"lagos trip\"', 'tag = vacation', 'deductible = true', 'description ~ coffee' (contains, ignoring case), and 'amount' or 'date' with =, <, <=, > or >=. Dates are written as for --start. Every condition has to match, along with any other filters given."),
+    )
+}
+
+/// The `field op value` conditions of a `--where` expression. Values can be
+/// quoted to hold spaces or the word 'and'.
+fn conditions(expression: &str) -> Result<Vec<(String, &'static str, String)>, String> {
+  let is_and = |rest: &str| {
+    rest.get(..3).is_some_and(|word| word.eq_ignore_ascii_case("and"))
+      && rest[3..].chars().next().is_none_or(char::is_whitespace)
+  };
+
+  let mut parts = Vec::new();
+  let mut start = 0;
+  let mut quote = None;
+  let mut previous = ' ';
+  for (i, c) in expression.char_indices() {
+    match quote {
+      Some(q) if c == q => quote = None,
+      Some(_) => {}
+      None if c == '"' || c == '\'' => quote = Some(c),
+      None if previous.is_whitespace() && is_and(&expression[i..]) => {
+        parts.push(&expression[start..i]);
+        start = i + 3;
+      }
+      None => {}
+    }
+    previous = c;
+  }
+  if quote.is_some() {
+    return Err("a quote isn't closed".to_string());
+  }
+  parts.push(&expression[start..]);
+
+  parts.into_iter().map(condition).collect()
+}
+
+/// The field, operator and value of one condition, however it's spaced
+fn condition(text: &str) -> Result<(String, &'static str, String), String> {
+  const OPERATORS: [&str; 6] = ["<=", ">=", "=", "<", ">", "~"];
+  let text = text.trim();
+  let missing = || format!("'{}' isn't a condition such as 'amount < 10'", text);
+
+  let at = text.find(['<', '>', '=', '~']).ok_or_else(missing)?;
+  let op = OPERATORS
+    .into_iter()
+    .find(|op| text[at..].starts_with(op))
+    .ok_or_else(missing)?;
+  let field = text[..at].trim();
+  let value = text[at + op.len()..].trim();
+  let value = ['"', '\'']
+    .into_iter()
+    .find_map(|q| value.strip_prefix(q)?.strip_suffix(q))
+    .unwrap_or(value);
+  if field.is_empty() || field.contains(char::is_whitespace) || value.is_empty() {
+    return Err(missing());
+  }
+  Ok((field.to_string(), op, value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conditions_split_on_and_outside_quotes() {
+        let parsed = conditions("subcategory=food AND description ~ \"fish and chips\" and amount >= 10").unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                ("subcategory".to_string(), "=", "food".to_string()),
+                ("description".to_string(), "~", "fish and chips".to_string()),
+                ("amount".to_string(), ">=", "10".to_string()),
+            ]
+        );
+        assert!(conditions("amount < 10 and").is_err());
+        assert!(conditions("description ~ 'unclosed").is_err());
+        assert!(conditions("the amount < 10").is_err());
+    }
+}
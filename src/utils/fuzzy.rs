@@ -0,0 +1,137 @@
+//! Fuzzy text matching for `fintrack search`, and "did you mean" suggestions
+//! for mistyped names
+
+/// Where and how well a query matched some text
+#[derive(Debug, PartialEq)]
+pub struct FuzzyMatch {
+  /// Higher is better
+  pub score: i64,
+  /// Char indices of `text` that matched the query, in order
+  pub positions: Vec<usize>,
+}
+
+/// Match `query` against `text`, ignoring case and whitespace in the query.
+///
+/// A plain substring match always wins; otherwise every query character must
+/// appear in `text` in order. Consecutive characters and characters at the
+/// start of a word score extra, so "cof" ranks "Coffee" above "Cash for fuel".
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+  let needle: Vec<char> = query
+    .chars()
+    .filter(|c| !c.is_whitespace())
+    .flat_map(char::to_lowercase)
+    .collect();
+  if needle.is_empty() {
+    return None;
+  }
+  let haystack: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+
+  if let Some(start) = find_substring(&haystack, &needle) {
+    let at_word_start = start == 0 || !haystack[start - 1].is_alphanumeric();
+    return Some(FuzzyMatch {
+      score: 1000 + 10 * needle.len() as i64 + if at_word_start { 50 } else { 0 }
+        - start as i64,
+      positions: (start..start + needle.len()).collect(),
+    });
+  }
+
+  let mut positions = Vec::with_capacity(needle.len());
+  let mut score = 0;
+  let mut next = 0;
+  for &wanted in &needle {
+    let found = (next..haystack.len()).find(|&i| haystack[i] == wanted)?;
+    score += 1;
+    if positions.last().is_some_and(|&last| last + 1 == found) {
+      score += 5;
+    }
+    if found == 0 || !haystack[found - 1].is_alphanumeric() {
+      score += 3;
+    }
+    // Gaps make the match weaker
+    score -= (found - next) as i64;
+    positions.push(found);
+    next = found + 1;
+  }
+
+  Some(FuzzyMatch { score, positions })
+}
+
+/// How many single-character insertions, deletions or substitutions turn `a`
+/// into `b`, ignoring case
+pub fn levenshtein(a: &str, b: &str) -> usize {
+  let a: Vec<char> = a.chars().flat_map(char::to_lowercase).collect();
+  let b: Vec<char> = b.chars().flat_map(char::to_lowercase).collect();
+
+  // One row of the edit-distance table at a time
+  let mut previous: Vec<usize> = (0..=b.len()).collect();
+  for (i, &ca) in a.iter().enumerate() {
+    let mut current = vec![i + 1; b.len() + 1];
+    for (j, &cb) in b.iter().enumerate() {
+      let substitution = previous[j] + usize::from(ca != cb);
+      current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+    }
+    previous = current;
+  }
+  previous[b.len()]
+}
+
+/// The candidate `name` was most likely a typo of: the one with the fewest
+/// edits, as long as no more than half of the longer name had to change. A
+/// candidate that starts with `name`, such as "Groceries" for "groc", counts
+/// too.
+pub fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+  let typed = name.trim().to_lowercase();
+  if typed.is_empty() {
+    return None;
+  }
+
+  candidates
+    .into_iter()
+    .filter_map(|candidate| {
+      let distance = levenshtein(&typed, candidate);
+      let longest = typed.chars().count().max(candidate.chars().count());
+      let prefix = typed.chars().count() >= 3 && candidate.to_lowercase().starts_with(&typed);
+      (distance * 2 <= longest || prefix).then_some((distance, candidate))
+    })
+    .min_by_key(|&(distance, candidate)| (distance, candidate))
+    .map(|(_, candidate)| candidate)
+}
+
+fn find_substring(haystack: &[char], needle: &[char]) -> Option<usize> {
+  haystack
+    .windows(needle.len())
+    .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substring_matches_outrank_scattered_ones() {
+        let coffee = fuzzy_match("cof", "Coffee").unwrap();
+        let scattered = fuzzy_match("cof", "Cash for fuel").unwrap();
+        assert_eq!(coffee.positions, vec![0, 1, 2]);
+        assert!(coffee.score > scattered.score);
+        assert_eq!(scattered.positions, vec![0, 6, 9]);
+    }
+
+    #[test]
+    fn test_every_query_character_must_appear_in_order() {
+        assert!(fuzzy_match("fco", "Coffee").is_none());
+        assert!(fuzzy_match("   ", "Coffee").is_none());
+        assert!(fuzzy_match("gro store", "Grocery store").is_some());
+    }
+
+    #[test]
+    fn test_closest_suggests_likely_typos_only() {
+        let names = ["Groceries", "Gas", "Rent", "Transport"];
+        assert_eq!(levenshtein("Grocery", "groceries"), 3);
+        assert_eq!(closest("grocery", names), Some("Groceries"));
+        assert_eq!(closest("Trnasport", names), Some("Transport"));
+        assert_eq!(closest("groc", names), Some("Groceries"));
+        assert_eq!(closest("rnet", names), Some("Rent"));
+        assert_eq!(closest("holiday", names), None);
+        assert_eq!(closest("", names), None);
+    }
+}
@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use crate::{CliError, TrackerData, t, utils::config::GsheetConfig};
+
+const API: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+/// Overrides `gsheet.access_token`, which expires after an hour anyway
+pub const TOKEN_ENV: &str = "FINTRACK_GSHEET_TOKEN";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Replace the records and summary tabs of the configured spreadsheet with
+/// the tracker's contents, creating the tabs if needed. Returns the
+/// spreadsheet's URL.
+pub fn push(config: &GsheetConfig, tracker_data: &TrackerData) -> Result<String, CliError> {
+  let Some(id) = &config.spreadsheet_id else {
+    return Err(CliError::Other(t!("error-gsheet-no-spreadsheet")));
+  };
+  let token = std::env::var(TOKEN_ENV)
+    .ok()
+    .or_else(|| config.access_token.clone())
+    .ok_or_else(|| {
+      CliError::Other(t!("error-gsheet-no-token", variable = TOKEN_ENV))
+    })?;
+  let client = Client { token, base: format!("{}/{}", API, id) };
+  let tabs = [config.records_tab.as_str(), config.summary_tab.as_str()];
+
+  let metadata = client.get("?fields=sheets.properties.title")?;
+  let existing: Vec<&str> = metadata["sheets"]
+    .as_array()
+    .into_iter()
+    .flatten()
+    .filter_map(|sheet| sheet["properties"]["title"].as_str())
+    .collect();
+  let missing: Vec<Value> = tabs
+    .iter()
+    .filter(|tab| !existing.contains(tab))
+    .map(|tab| json!({ "addSheet": { "properties": { "title": tab } } }))
+    .collect();
+  if !missing.is_empty() {
+    client.post(":batchUpdate", &json!({ "requests": missing }))?;
+  }
+
+  let ranges: Vec<String> = tabs.iter().map(|tab| range(tab)).collect();
+  client.post("/values:batchClear", &json!({ "ranges": ranges }))?;
+  client.post(
+    "/values:batchUpdate",
+    &json!({
+      "valueInputOption": "RAW",
+      "data": [
+        { "range": ranges[0], "values": record_rows(tracker_data) },
+        { "range": ranges[1], "values": summary_rows(tracker_data) },
+      ],
+    }),
+  )?;
+
+  Ok(format!("https://docs.google.com/spreadsheets/d/{}", id))
+}
+
+/// A header row followed by one row per record, in the same columns as CSV exports
+pub fn record_rows(tracker_data: &TrackerData) -> Vec<Vec<Value>> {
+  let mut rows = vec![
+    ["ID", "Category", "Subcategory", "Amount", "Currency", "Date", "Description"]
+      .iter()
+      .map(|h| json!(h))
+      .collect(),
+  ];
+  for record in &tracker_data.records {
+    rows.push(vec![
+      json!(record.id),
+      json!(tracker_data.category_name(record.category)),
+      json!(tracker_data.subcategory_name(record.subcategory)),
+      json!(record.amount),
+      json!(tracker_data.currency),
+      json!(record.date),
+      json!(record.description),
+    ]);
+  }
+  rows
+}
+
+/// Totals for the summary tab, followed by spending per subcategory
+pub fn summary_rows(tracker_data: &TrackerData) -> Vec<Vec<Value>> {
+  let (income, expenses) = tracker_data.totals();
+  let mut rows = vec![
+    vec![json!("Currency"), json!(tracker_data.currency)],
+    vec![json!("Opening balance"), json!(tracker_data.opening_balance)],
+    vec![json!("Total income"), json!(income)],
+    vec![json!("Total expenses"), json!(expenses)],
+    vec![json!("Net balance"), json!(tracker_data.net_balance())],
+    vec![json!("Records"), json!(tracker_data.records.len())],
+    vec![json!("Last modified"), json!(tracker_data.last_modified)],
+    vec![],
+    vec![json!("Subcategory"), json!("Income"), json!("Expenses")],
+  ];
+
+  let mut names: Vec<(&usize, &String)> = tracker_data.subcategories_by_id.iter().collect();
+  names.sort_by(|a, b| a.1.cmp(b.1));
+  let income_id = tracker_data.category_id("income");
+  for (id, name) in names {
+    let (sub_income, sub_expenses) = tracker_data
+      .records
+      .iter()
+      .filter(|r| r.subcategory == *id)
+      .fold((0.0, 0.0), |(i, e), r| {
+        if r.category == income_id { (i + r.amount, e) } else { (i, e + r.amount) }
+      });
+    rows.push(vec![json!(name), json!(sub_income), json!(sub_expenses)]);
+  }
+  rows
+}
+
+/// A range covering a whole tab, quoted in case the name has spaces
+fn range(tab: &str) -> String {
+  format!("'{}'", tab.replace('\'', "''"))
+}
+
+struct Client {
+  token: String,
+  base: String,
+}
+
+impl Client {
+  fn get(&self, path: &str) -> Result<Value, CliError> {
+    self.send(ureq::get(&format!("{}{}", self.base, path)), None)
+  }
+
+  fn post(&self, path: &str, body: &Value) -> Result<Value, CliError> {
+    self.send(ureq::post(&format!("{}{}", self.base, path)), Some(body))
+  }
+
+  fn send(&self, request: ureq::Request, body: Option<&Value>) -> Result<Value, CliError> {
+    let request = request
+      .timeout(TIMEOUT)
+      .set("Authorization", &format!("Bearer {}", self.token));
+    let result = match body {
+      Some(body) => request
+        .set("Content-Type", "application/json")
+        .send_string(&body.to_string()),
+      None => request.call(),
+    };
+
+    match result {
+      Ok(response) => {
+        let text = response.into_string()?;
+        Ok(serde_json::from_str(&text).unwrap_or(Value::Null))
+      }
+      Err(ureq::Error::Status(code, response)) => {
+        // Google explains what went wrong in the body, e.g. an expired token
+        let detail = response
+          .into_string()
+          .ok()
+          .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+          .and_then(|body| body["error"]["message"].as_str().map(str::to_string))
+          .unwrap_or_default();
+        Err(CliError::Other(t!("error-gsheet-status", status = code, detail = detail)))
+      }
+      Err(ureq::Error::Transport(err)) => {
+        Err(CliError::Other(t!("error-gsheet-unreachable", reason = err.to_string())))
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Currency, default_tracker_json};
+
+    #[test]
+    fn test_rows() {
+        let mut data: TrackerData =
+            serde_json::from_value(default_tracker_json(&Currency::USD, 10.0)).unwrap();
+        data.records.push(crate::Record {
+            id: 1,
+            category: 2,
+            subcategory: 1,
+            description: "Lunch".to_string(),
+            amount: 4.5,
+            date: "01-02-2025".to_string(),
+            entered_by: None,
+            deductible: false,
+            project: None,
+            foreign: None,
+            tags: Vec::new(),
+            parsed_date: Default::default(),
+        });
+
+        let rows = record_rows(&data);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1][1], json!("expenses"));
+        assert_eq!(rows[1][2], json!("miscellaneous"));
+        assert_eq!(rows[1][6], json!("Lunch"));
+
+        let summary = summary_rows(&data);
+        assert_eq!(summary[4], vec![json!("Net balance"), json!(5.5)]);
+        assert_eq!(summary.last().unwrap(), &vec![json!("miscellaneous"), json!(0.0), json!(4.5)]);
+    }
+
+    #[test]
+    fn test_range_quotes_tab_names() {
+        assert_eq!(range("Monthly Summary"), "'Monthly Summary'");
+        assert_eq!(range("Tom's"), "'Tom''s'");
+    }
+}
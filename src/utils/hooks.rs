@@ -0,0 +1,80 @@
+use std::{
+  io::{self, Write},
+  process::{Command, Stdio},
+};
+
+use serde_json::Value;
+
+use crate::{CliError, GlobalContext, t};
+
+/// Scripts in `~/.fintrack/hooks/` that are run around commands, named after
+/// the hook (e.g. `hooks/post-add`). Each receives JSON on stdin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Hook {
+  /// Before a record is saved; a non-zero exit cancels the add
+  PreAdd,
+  PostAdd,
+  PostDelete,
+  PostExport,
+}
+
+impl Hook {
+  fn is_pre(self) -> bool {
+    matches!(self, Hook::PreAdd)
+  }
+
+  /// Whether the hook follows a change to the tracker, which a dry run doesn't make
+  fn follows_change(self) -> bool {
+    matches!(self, Hook::PostAdd | Hook::PostDelete)
+  }
+}
+
+/// Run `hook` if its script exists, passing `payload` on stdin.
+///
+/// A failing pre-hook is an error so the command can stop before changing
+/// anything. Post-hooks run after the change is saved, so their failures are
+/// only logged.
+pub fn run(gctx: &GlobalContext, hook: Hook, payload: &Value) -> Result<(), CliError> {
+  let path = gctx.hooks_path().join(hook.to_string());
+  if !path.is_file() {
+    return Ok(());
+  }
+  if gctx.is_dry_run() && hook.follows_change() {
+    gctx.verbose(format!("Dry run: not running the {} hook", hook));
+    return Ok(());
+  }
+
+  let _span = tracing::info_span!("hook", name = %hook).entered();
+  gctx.verbose(format!("Running {} hook {}", hook, path.display()));
+
+  let result = Command::new(&path)
+    .stdin(Stdio::piped())
+    // Keep stdout for fintrack's own output, which may be JSON
+    .stdout(io::stderr())
+    .spawn()
+    .and_then(|mut child| {
+      if let Some(mut stdin) = child.stdin.take() {
+        // A hook that doesn't read its input closes the pipe early
+        if let Err(err) = stdin.write_all(payload.to_string().as_bytes())
+          && err.kind() != io::ErrorKind::BrokenPipe
+        {
+          return Err(err);
+        }
+      }
+      child.wait()
+    });
+
+  let error = match result {
+    Ok(status) if status.success() => return Ok(()),
+    Ok(status) => t!("error-hook-failed", hook = hook.to_string(), status = status.to_string()),
+    Err(err) => t!("error-hook-not-run", hook = hook.to_string(), reason = err.to_string()),
+  };
+
+  if hook.is_pre() {
+    return Err(CliError::Other(error));
+  }
+  tracing::warn!(name = %hook, "{}", error);
+  gctx.verbose(error);
+  Ok(())
+}
@@ -0,0 +1,114 @@
+//! Soft spending limits from the `[limits]` settings, and the thresholds
+//! budgets warn at
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::output::format_amount;
+use crate::utils::period::Period;
+use crate::{BudgetLevel, GlobalContext, LimitPeriod, LimitStatus, Record, TrackerData, t};
+
+/// Expenses on `date` and in its week, against whichever limits are set
+pub fn statuses(gctx: &GlobalContext, tracker_data: &TrackerData, date: NaiveDate) -> Vec<LimitStatus> {
+  let config = gctx.config();
+  let expenses_id = tracker_data.category_id("expenses");
+  let spent = |period: Period| -> f64 {
+    tracker_data
+      .records_between(Some(period.start), Some(period.end))
+      .iter()
+      .filter(|r| r.category == expenses_id)
+      .map(|r| r.amount)
+      .sum()
+  };
+
+  let mut statuses = Vec::new();
+  if let Some(limit) = config.limits.daily {
+    statuses.push(LimitStatus {
+      period: LimitPeriod::Daily,
+      limit,
+      spent: spent(Period { start: date, end: date }),
+    });
+  }
+  if let Some(limit) = config.limits.weekly {
+    statuses.push(LimitStatus {
+      period: LimitPeriod::Weekly,
+      limit,
+      spent: spent(Period::week(&config.period, date)),
+    });
+  }
+  statuses
+}
+
+/// What to warn about after `record` was added: every limit its day or week
+/// is now over. Income never warns.
+pub fn warnings(gctx: &GlobalContext, tracker_data: &TrackerData, record: &Record) -> Vec<String> {
+  let Some(date) = record.naive_date() else {
+    return Vec::new();
+  };
+  if record.category != tracker_data.category_id("expenses") {
+    return Vec::new();
+  }
+
+  let currency = tracker_data.currency.as_str();
+  statuses(gctx, tracker_data, date)
+    .into_iter()
+    .filter(LimitStatus::is_over)
+    .map(|status| {
+      let spent = format_amount(status.spent);
+      let limit = format_amount(status.limit);
+      match status.period {
+        LimitPeriod::Daily => t!(
+          "limit-daily-over",
+          spent = spent,
+          date = record.date.as_str(),
+          limit = limit,
+          currency = currency
+        ),
+        LimitPeriod::Weekly => t!(
+          "limit-weekly-over",
+          spent = spent,
+          week = Period::week(&gctx.config().period, date).start.format("%d-%m-%Y").to_string(),
+          limit = limit,
+          currency = currency
+        ),
+      }
+    })
+    .collect()
+}
+
+/// What to warn about after `record` was added: its subcategory's budget for
+/// the record's month, once that has reached `warn_at` or `error_at`
+pub fn budget_warnings(tracker_data: &TrackerData, record: &Record) -> Vec<String> {
+  let Some(date) = record.naive_date() else {
+    return Vec::new();
+  };
+  if record.category != tracker_data.category_id("expenses")
+    || !tracker_data.budgets.iter().any(|b| b.subcategory == record.subcategory)
+  {
+    return Vec::new();
+  }
+
+  let month_start = date.with_day(1).expect("day 1 exists");
+  let name = tracker_data.subcategory_name(record.subcategory).cloned().unwrap_or_default();
+  let currency = tracker_data.currency.as_str();
+  tracker_data
+    .budget_statuses(month_start, month_start + Months::new(1) - Days::new(1))
+    .into_iter()
+    .filter(|status| status.subcategory == name && status.level != BudgetLevel::Ok)
+    .map(|status| {
+      let available = status.budget + status.carried;
+      let id = match status.remaining < 0.0 || available <= 0.0 {
+        true => "budget-over",
+        false => "budget-threshold-reached",
+      };
+      t!(
+        id,
+        name = name.as_str(),
+        month = month_start.format("%m-%Y").to_string(),
+        percent = format!("{:.0}", status.spent / available * 100.0),
+        spent = format_amount(status.spent),
+        available = format_amount(available),
+        currency = currency
+      )
+    })
+    .collect()
+}
@@ -0,0 +1,84 @@
+use std::{
+  fs::{self, OpenOptions},
+  io,
+  path::Path,
+  sync::Mutex,
+};
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Verbosity of the diagnostic log enabled with `--log-level`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum LogLevel {
+  Off,
+  Error,
+  Warn,
+  Info,
+  Debug,
+  Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+  fn from(level: LogLevel) -> Self {
+    match level {
+      LogLevel::Off => LevelFilter::OFF,
+      LogLevel::Error => LevelFilter::ERROR,
+      LogLevel::Warn => LevelFilter::WARN,
+      LogLevel::Info => LevelFilter::INFO,
+      LogLevel::Debug => LevelFilter::DEBUG,
+      LogLevel::Trace => LevelFilter::TRACE,
+    }
+  }
+}
+
+/// Install the global tracing subscriber.
+///
+/// Logs go to stderr, or are appended to `log_file` when one is given. Span
+/// close events are included so each load/parse/filter/write phase reports how
+/// long it took. `ansi` only applies to stderr; log files are always plain
+/// text. Does nothing when the level is `Off`.
+pub fn init_logging(level: LogLevel, log_file: Option<&Path>, ansi: bool) -> io::Result<()> {
+  if level == LogLevel::Off {
+    return Ok(());
+  }
+
+  let builder = tracing_subscriber::fmt()
+    .with_max_level(LevelFilter::from(level))
+    .with_target(false)
+    .with_span_events(FmtSpan::CLOSE);
+
+  match log_file {
+    Some(path) => {
+      if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+      }
+      let file = OpenOptions::new().create(true).append(true).open(path)?;
+      builder.with_ansi(false).with_writer(Mutex::new(file)).init();
+    }
+    None => builder.with_ansi(ansi).with_writer(io::stderr).init(),
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_level_to_filter() {
+        assert_eq!(LevelFilter::from(LogLevel::Off), LevelFilter::OFF);
+        assert_eq!(LevelFilter::from(LogLevel::Info), LevelFilter::INFO);
+        assert_eq!(LevelFilter::from(LogLevel::Trace), LevelFilter::TRACE);
+    }
+
+    #[test]
+    fn test_init_logging_off_is_noop() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let log_file = temp_dir.path().join("fintrack.log");
+
+        init_logging(LogLevel::Off, Some(&log_file), false).unwrap();
+        assert!(!log_file.exists());
+    }
+}
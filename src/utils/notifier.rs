@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::{CliError, t, utils::config::NotifyConfig};
+
+const NTFY_SERVER: &str = "https://ntfy.sh";
+const TELEGRAM_API: &str = "https://api.telegram.org";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Somewhere `fintrack notify` can send messages, set up in `[notify]`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Channel {
+  /// An ntfy.sh topic, or a full topic URL on a self-hosted server
+  Ntfy { url: String },
+  Telegram { token: String, chat_id: String },
+  /// A native notification on this machine
+  Desktop,
+}
+
+impl Channel {
+  /// Every channel with enough settings to send to
+  pub fn configured(config: &NotifyConfig) -> Vec<Channel> {
+    let mut channels = Vec::new();
+    if let Some(topic) = &config.ntfy_topic {
+      let url = if topic.contains("://") {
+        topic.clone()
+      } else {
+        format!("{}/{}", NTFY_SERVER, topic)
+      };
+      channels.push(Channel::Ntfy { url });
+    }
+    if let (Some(token), Some(chat_id)) = (&config.telegram_token, &config.telegram_chat_id) {
+      channels.push(Channel::Telegram {
+        token: token.clone(),
+        chat_id: chat_id.clone(),
+      });
+    }
+    if config.desktop {
+      channels.push(Channel::Desktop);
+    }
+    channels
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      Channel::Ntfy { .. } => "ntfy",
+      Channel::Telegram { .. } => "telegram",
+      Channel::Desktop => "desktop",
+    }
+  }
+
+  /// Everything but desktop notifications goes over the network
+  pub fn needs_network(&self) -> bool {
+    *self != Channel::Desktop
+  }
+
+  pub fn send(&self, title: &str, body: &str) -> Result<(), CliError> {
+    let _span = tracing::info_span!("notify", channel = self.name()).entered();
+
+    let result = match self {
+      Channel::Desktop => {
+        return notify_rust::Notification::new()
+          .appname("fintrack")
+          .summary(title)
+          .body(body)
+          .show()
+          .map(|_| ())
+          .map_err(|err| CliError::Other(t!("error-desktop-notification", reason = err.to_string())));
+      }
+      Channel::Ntfy { url } => ureq::post(url)
+        .timeout(TIMEOUT)
+        .set("Title", title)
+        .send_string(body),
+      Channel::Telegram { token, chat_id } => {
+        let url = format!("{}/bot{}/sendMessage", TELEGRAM_API, token);
+        let payload = json!({
+          "chat_id": chat_id,
+          "text": format!("{}\n\n{}", title, body),
+        });
+        ureq::post(&url)
+          .timeout(TIMEOUT)
+          .set("Content-Type", "application/json")
+          .send_string(&payload.to_string())
+      }
+    };
+
+    // Describe errors without their URL, which holds the Telegram bot token
+    let reason = match result {
+      Ok(_) => return Ok(()),
+      Err(ureq::Error::Status(code, _)) => t!("error-server-status", status = code),
+      Err(ureq::Error::Transport(err)) => err.kind().to_string(),
+    };
+    Err(CliError::Other(t!("error-notify-send", channel = self.name(), reason = reason)))
+  }
+}
@@ -1,14 +1,239 @@
-use chrono::NaiveDate;
+use std::{fmt, str::FromStr, sync::RwLock};
 
-use crate::Category;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
 
-/// Parse a date string in DD-MM-YYYY format
+use crate::{
+  Category, Frequency, NumberLocale, QuickEntry, Share, Split, ValidationErrorKind,
+  models::day_in_month,
+};
+
+/// The chrono pattern dates are stored with in the tracker (DD-MM-YYYY)
+pub const STORAGE_DATE_FORMAT: &str = "%d-%m-%Y";
+
+/// A date format users can type dates in, written with `DD`, `MM`, `YY` and
+/// `YYYY` plus separators, e.g. `YYYY-MM-DD` or `DD/MM/YY`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct InputDateFormat {
+  name: String,
+  pattern: String,
+}
+
+impl InputDateFormat {
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn parse(&self, s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, &self.pattern).ok()
+  }
+
+  /// For a string shaped like this format but naming a day that doesn't
+  /// exist (31-02-2025, 15-13-2025), the closest real date, written in this
+  /// format. The month is brought into 1-12 and the day into that month.
+  pub fn nearest(&self, s: &str) -> Option<String> {
+    let (year, month, day) = self.fields(s)?;
+    let month = month.clamp(1, 12);
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let last_day = first.checked_add_months(chrono::Months::new(1))?.pred_opt()?.day();
+    let date = first.with_day(day.clamp(1, last_day))?;
+    Some(date.format(&self.pattern).to_string())
+  }
+
+  /// The year, month and day of `s`, read by position without checking
+  /// that they make a date
+  fn fields(&self, s: &str) -> Option<(i32, u32, u32)> {
+    let (mut year, mut month, mut day) = (None, None, None);
+    let mut rest = s.trim();
+    let mut pattern = self.pattern.chars();
+
+    while let Some(c) = pattern.next() {
+      if c != '%' {
+        rest = rest.strip_prefix(c)?;
+        continue;
+      }
+      let token = pattern.next()?;
+      let width = if token == 'Y' { 4 } else { 2 };
+      let len = rest.chars().take(width).take_while(char::is_ascii_digit).count();
+      if len == 0 {
+        return None;
+      }
+      let value: u32 = rest[..len].parse().ok()?;
+      rest = &rest[len..];
+      match token {
+        'Y' => year = Some(value as i32),
+        // Same pivot as chrono's %y
+        'y' if value < 70 => year = Some(2000 + value as i32),
+        'y' => year = Some(1900 + value as i32),
+        'm' => month = Some(value),
+        _ => day = Some(value),
+      }
+    }
+
+    match rest.is_empty() {
+      true => Some((year?, month?, day?)),
+      false => None,
+    }
+  }
+}
+
+impl Default for InputDateFormat {
+  fn default() -> Self {
+    Self {
+      name: "DD-MM-YYYY".to_string(),
+      pattern: STORAGE_DATE_FORMAT.to_string(),
+    }
+  }
+}
+
+impl FromStr for InputDateFormat {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let name = s.trim().to_uppercase();
+    let mut pattern = String::new();
+    let (mut days, mut months, mut years) = (0, 0, 0);
+
+    let mut rest = name.as_str();
+    while let Some(c) = rest.chars().next() {
+      let (token, len) = if rest.starts_with("YYYY") {
+        years += 1;
+        ("%Y", 4)
+      } else if rest.starts_with("YY") {
+        years += 1;
+        ("%y", 2)
+      } else if rest.starts_with("MM") {
+        months += 1;
+        ("%m", 2)
+      } else if rest.starts_with("DD") {
+        days += 1;
+        ("%d", 2)
+      } else if c.is_alphanumeric() || c == '%' {
+        return Err(format!(
+          "'{}' is not a date format; use DD, MM and YYYY (or YY), e.g. YYYY-MM-DD",
+          s
+        ));
+      } else {
+        pattern.push(c);
+        rest = &rest[c.len_utf8()..];
+        continue;
+      };
+      pattern.push_str(token);
+      rest = &rest[len..];
+    }
+
+    if (days, months, years) != (1, 1, 1) {
+      return Err(format!(
+        "'{}' must contain the day (DD), month (MM) and year (YYYY or YY) once each",
+        s
+      ));
+    }
+
+    Ok(Self { name, pattern })
+  }
+}
+
+impl TryFrom<String> for InputDateFormat {
+  type Error = String;
+
+  fn try_from(s: String) -> Result<Self, Self::Error> {
+    s.parse()
+  }
+}
+
+impl From<InputDateFormat> for String {
+  fn from(format: InputDateFormat) -> Self {
+    format.name
+  }
+}
+
+impl fmt::Display for InputDateFormat {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.name)
+  }
+}
+
+/// Formats [`parse_date`] accepts, from the `input.date_formats` setting.
+/// Empty means the default, DD-MM-YYYY.
+static INPUT_DATE_FORMATS: RwLock<Vec<InputDateFormat>> = RwLock::new(Vec::new());
+
+pub fn set_input_date_formats(formats: Vec<InputDateFormat>) {
+  *INPUT_DATE_FORMATS.write().unwrap_or_else(|e| e.into_inner()) = formats;
+}
+
+/// Parse a date typed by the user, in DD-MM-YYYY or any of the formats
+/// configured in `input.date_formats`
 ///
-/// This is the standard date format used throughout the application.
-/// Used as a clap value parser for date arguments.
-pub fn parse_date(s: &str) -> Result<NaiveDate, String> {
-  NaiveDate::parse_from_str(s, "%d-%m-%Y")
-    .map_err(|_| format!("'{}' is not in the format DD-MM-YYYY", s))
+/// Date arguments are read as strings and parsed with this when the command
+/// runs, so a bad date is reported as [`ValidationErrorKind::InvalidDate`].
+pub fn parse_date(s: &str) -> Result<NaiveDate, ValidationErrorKind> {
+  let formats = INPUT_DATE_FORMATS.read().unwrap_or_else(|e| e.into_inner());
+  if formats.is_empty() {
+    parse_date_with(s, &[InputDateFormat::default()])
+  } else {
+    parse_date_with(s, &formats)
+  }
+}
+
+/// Parse a date in the first of `formats` it matches. When none does but the
+/// input has the shape of one, the error carries the nearest real date.
+pub fn parse_date_with(s: &str, formats: &[InputDateFormat]) -> Result<NaiveDate, ValidationErrorKind> {
+  if let Some(date) = formats.iter().find_map(|format| format.parse(s)) {
+    return Ok(date);
+  }
+
+  let names: Vec<&str> = formats.iter().map(|f| f.name()).collect();
+  Err(ValidationErrorKind::InvalidDate {
+    provided: s.to_string(),
+    expected_format: names.join(", "),
+    nearest: formats.iter().find_map(|format| format.nearest(s)),
+  })
+}
+
+/// How [`parse_amount`] reads amounts, from the `input.locale` setting
+static INPUT_LOCALE: RwLock<NumberLocale> = RwLock::new(NumberLocale::En);
+
+pub fn set_input_locale(locale: NumberLocale) {
+  *INPUT_LOCALE.write().unwrap_or_else(|e| e.into_inner()) = locale;
+}
+
+/// Parse an amount typed by the user, written the way `input.locale` says
+/// (1,234.56 by default). Used as a clap value parser for amount arguments.
+pub fn parse_amount(s: &str) -> Result<f64, String> {
+  let locale = *INPUT_LOCALE.read().unwrap_or_else(|e| e.into_inner());
+  locale
+    .parse(s)
+    .filter(|amount| amount.is_finite())
+    .ok_or_else(|| format!("'{}' is not an amount; write it like {} (input.locale is '{}')", s, locale.example(), locale))
+}
+
+/// Parse a date as stored in the tracker
+pub fn parse_stored_date(s: &str) -> Option<NaiveDate> {
+  NaiveDate::parse_from_str(s, STORAGE_DATE_FORMAT).ok()
+}
+
+/// Parse when something recurring is first due, giving the date and the day of
+/// the month it repeats on. A bare day such as `10` means the next 10th,
+/// counting `today`; anything else is a date.
+pub fn parse_due(s: &str, today: NaiveDate) -> Result<(NaiveDate, u32), ValidationErrorKind> {
+  match s.trim().parse::<u32>() {
+    Ok(day @ 1..=31) => {
+      let this_month = day_in_month(today.year(), today.month(), day);
+      if this_month >= today {
+        Ok((this_month, day))
+      } else {
+        Ok((Frequency::Month.after(this_month, day), day))
+      }
+    }
+    _ => parse_date(s).map(|date| (date, date.day())),
+  }
+}
+
+/// Parse a month as MM-YYYY, giving its first day
+pub fn parse_month(s: &str) -> Result<NaiveDate, String> {
+  NaiveDate::parse_from_str(&format!("01-{}", s.trim()), STORAGE_DATE_FORMAT)
+    .map_err(|_| format!("'{}' is not a month in the format MM-YYYY", s))
 }
 
 /// Parse a category string case-insensitively
@@ -24,6 +249,121 @@ pub fn parse_category(s: &str) -> Result<Category, String> {
   })
 }
 
+/// Parse a `--split-with` value: `alice:50%`, `alice:20` or just `alice`
+pub fn parse_split(s: &str) -> Result<Split, String> {
+  let (person, share) = match s.rsplit_once(':') {
+    Some((person, share)) => (person.trim(), Some(share.trim())),
+    None => (s.trim(), None),
+  };
+  if person.is_empty() {
+    return Err(format!("'{}' should start with a name, e.g. alice:50%", s));
+  }
+  if person.contains([',', ':']) {
+    return Err(format!(
+      "'{}' names more than one person; repeat --split-with for each, e.g. --split-with alice:50% --split-with bob",
+      s
+    ));
+  }
+
+  let share = match share {
+    None => Share::Equal,
+    Some(share) => {
+      let (value, percent) = match share.strip_suffix('%') {
+        Some(value) => (value, true),
+        None => (share, false),
+      };
+      // Written the way `input.locale` says, like the amount itself
+      let value = parse_amount(value)
+        .ok()
+        .filter(|v| *v > 0.0 && (!percent || *v <= 100.0))
+        .ok_or_else(|| format!("'{}' is not a share; use a percentage up to 100% or an amount", share))?;
+      if percent { Share::Percent(value) } else { Share::Amount(value) }
+    }
+  };
+
+  Ok(Split {
+    person: person.to_string(),
+    share,
+  })
+}
+
+/// Parse a `fintrack quick` line such as `coffee 4.50 yesterday #food`.
+///
+/// The last number is the amount, optionally after a currency symbol; a
+/// leading `+` makes it income rather than an expense. `today`, `yesterday`,
+/// a weekday (the latest one, counting today) or a date set the date, and a
+/// `#name` word the subcategory. Whatever is left is the description.
+pub fn parse_quick(s: &str, today: NaiveDate) -> Result<QuickEntry, ValidationErrorKind> {
+  let words: Vec<&str> = s.split_whitespace().collect();
+
+  let amount_at = words
+    .iter()
+    .rposition(|word| quick_amount(word).is_some())
+    .ok_or_else(|| ValidationErrorKind::InvalidAmount {
+      reason: format!("'{}' has no amount in it", s.trim()),
+    })?;
+  let (amount, income) = quick_amount(words[amount_at]).unwrap_or_default();
+
+  let mut date = None;
+  let mut tags: Vec<String> = Vec::new();
+  let mut description = Vec::new();
+  for (i, word) in words.iter().enumerate() {
+    if i == amount_at {
+      continue;
+    }
+    if word.starts_with('#') {
+      if let Ok(tag) = parse_tag(word) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+          tags.push(tag);
+        }
+        continue;
+      }
+    } else if date.is_none()
+      && let Some(day) = quick_date(word, today)
+    {
+      date = Some(day);
+      continue;
+    }
+    description.push(*word);
+  }
+
+  Ok(QuickEntry {
+    category: if income { Category::Income } else { Category::Expenses },
+    amount,
+    date: date.unwrap_or(today),
+    tags,
+    description: description.join(" "),
+  })
+}
+
+/// `4.50`, `$4.50` or `+4.50`, giving the amount and whether it's income
+fn quick_amount(word: &str) -> Option<(f64, bool)> {
+  let (word, income) = match word.strip_prefix('+') {
+    Some(rest) => (rest, true),
+    None => (word, false),
+  };
+  let number = word.trim_start_matches(|c: char| !c.is_ascii_digit() && c != '.' && c != ',');
+  // Only symbols may come before the number, so "v2" stays a word
+  if word[..word.len() - number.len()].chars().any(char::is_alphanumeric) {
+    return None;
+  }
+  parse_amount(number).ok().map(|amount| (amount, income))
+}
+
+fn quick_date(word: &str, today: NaiveDate) -> Option<NaiveDate> {
+  match word.to_lowercase().as_str() {
+    "today" => Some(today),
+    "yesterday" => today.pred_opt(),
+    lower => match lower.parse::<chrono::Weekday>() {
+      Ok(weekday) => {
+        let back = (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+        today.checked_sub_days(chrono::Days::new(back.into()))
+      }
+      Err(_) => parse_date(word).ok(),
+    },
+  }
+}
+
 /// Parse a label string. Used for categories and subcategories
 pub fn parse_label(s: &str) -> Result<String, String> {
   if s.is_empty() {
@@ -46,10 +386,149 @@ pub fn parse_label(s: &str) -> Result<String, String> {
   Ok(s.to_string())
 }
 
+/// Parse a tag such as `vacation` or `#kitchen-2026`. The `#` is optional;
+/// letters, numbers, `-` and `_` are allowed.
+pub fn parse_tag(s: &str) -> Result<String, String> {
+  let tag = s.trim();
+  let tag = tag.strip_prefix('#').unwrap_or(tag);
+  if tag.is_empty() {
+    return Err("A tag can't be empty".to_string());
+  }
+  if !tag.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+    return Err(format!(
+      "'{}' can only contain letters, numbers, '-' and '_'",
+      s
+    ));
+  }
+  Ok(tag.to_string())
+}
+
+/// Parse a percentage such as `80` or `80%`, which must be greater than 0
+pub fn parse_percent(s: &str) -> Result<f64, String> {
+  let trimmed = s.trim();
+  let number = trimmed.strip_suffix('%').unwrap_or(trimmed).trim_end();
+  match number.parse::<f64>() {
+    Ok(percent) if percent.is_finite() && percent > 0.0 => Ok(percent),
+    Ok(_) => Err(format!("'{}' must be greater than 0%", s)),
+    Err(_) => Err(format!("'{}' is not a percentage, e.g. 80 or 80%", s)),
+  }
+}
+
+/// Split a command line into arguments the way a POSIX shell would for simple input.
+///
+/// Supports single quotes, double quotes and backslash escapes so descriptions
+/// with spaces can be typed into the interactive front-ends.
+pub fn split_command_line(line: &str) -> Result<Vec<String>, String> {
+  let mut args = Vec::new();
+  let mut current = String::new();
+  let mut in_arg = false;
+  let mut quote: Option<char> = None;
+  let mut chars = line.chars();
+
+  while let Some(c) = chars.next() {
+    match (quote, c) {
+      (Some(q), c) if c == q => quote = None,
+      (Some('"'), '\\') | (None, '\\') => {
+        let escaped = chars
+          .next()
+          .ok_or_else(|| "Command line ends with a dangling '\\'".to_string())?;
+        current.push(escaped);
+        in_arg = true;
+      }
+      (Some(_), c) => current.push(c),
+      (None, '\'' | '"') => {
+        quote = Some(c);
+        in_arg = true;
+      }
+      (None, c) if c.is_whitespace() => {
+        if in_arg {
+          args.push(std::mem::take(&mut current));
+          in_arg = false;
+        }
+      }
+      (None, c) => {
+        current.push(c);
+        in_arg = true;
+      }
+    }
+  }
+
+  if let Some(q) = quote {
+    return Err(format!("Unterminated {} quote", q));
+  }
+  if in_arg {
+    args.push(current);
+  }
+
+  Ok(args)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_due() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let date = |d, m, y| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(parse_due("20", today).unwrap(), (date(20, 1, 2025), 20));
+        assert_eq!(parse_due("15", today).unwrap(), (date(15, 1, 2025), 15));
+        assert_eq!(parse_due("10", today).unwrap(), (date(10, 2, 2025), 10));
+        assert_eq!(parse_due("31", date(1, 2, 2025)).unwrap(), (date(28, 2, 2025), 31));
+        assert_eq!(parse_due("03-04-2025", today).unwrap(), (date(3, 4, 2025), 3));
+        assert!(parse_due("45", today).is_err());
+    }
+
+    #[test]
+    fn test_parse_quick() {
+        // 16-10-2026 is a Friday
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let entry = parse_quick("coffee 4.50 yesterday #food", today).unwrap();
+        assert_eq!(
+            entry,
+            QuickEntry {
+                category: Category::Expenses,
+                amount: 4.5,
+                date: NaiveDate::from_ymd_opt(2026, 10, 15).unwrap(),
+                tags: vec!["food".to_string()],
+                description: "coffee".to_string(),
+            }
+        );
+
+        // Every #word is a tag, once; a lone '#' is just a word
+        let entry = parse_quick("#work lunch # 12 #Work #team", today).unwrap();
+        assert_eq!(entry.tags, ["work", "team"]);
+        assert_eq!(entry.description, "lunch #");
+
+        let entry = parse_quick("2 coffees with Ada $9 Monday", today).unwrap();
+        assert_eq!(entry.amount, 9.0);
+        assert_eq!(entry.date, NaiveDate::from_ymd_opt(2026, 10, 12).unwrap());
+        assert_eq!(entry.description, "2 coffees with Ada");
+
+        let entry = parse_quick("+250 refund", today).unwrap();
+        assert_eq!(entry.category, Category::Income);
+        assert_eq!(entry.date, today);
+        assert_eq!(parse_quick("lunch fri 12", today).unwrap().date, today);
+
+        assert!(parse_quick("coffee #food", today).is_err());
+    }
+
+    #[test]
+    fn test_parse_split() {
+        let split = parse_split("alice:50%").unwrap();
+        assert_eq!(split.person, "alice");
+        assert_eq!(split.share, Share::Percent(50.0));
+        assert_eq!(parse_split("bob:12.5").unwrap().share, Share::Amount(12.5));
+        assert_eq!(parse_split("bob:1,250").unwrap().share, Share::Amount(1250.0));
+        assert_eq!(parse_split("carol").unwrap().share, Share::Equal);
+        assert!(parse_split("alice:150%").is_err());
+        assert!(parse_split("alice:-5").is_err());
+        assert!(parse_split(":50%").is_err());
+        // One person per --split-with
+        assert!(parse_split("ana,ben").unwrap_err().contains("repeat --split-with"));
+        assert!(parse_split("ana:7,ben:5").unwrap_err().contains("repeat --split-with"));
+    }
+
     #[test]
     fn test_parse_date_valid() {
         use chrono::Datelike;
@@ -74,6 +553,66 @@ mod tests {
         assert!(parse_date("01-13-2025").is_err());
     }
 
+    #[test]
+    fn test_input_date_format_from_str() {
+        let iso: InputDateFormat = "yyyy-mm-dd".parse().unwrap();
+        assert_eq!(iso.name(), "YYYY-MM-DD");
+        assert_eq!(iso.parse("2025-01-15"), NaiveDate::from_ymd_opt(2025, 1, 15));
+
+        let short: InputDateFormat = "DD/MM/YY".parse().unwrap();
+        assert_eq!(short.parse("15/01/25"), NaiveDate::from_ymd_opt(2025, 1, 15));
+
+        assert!("DD-MM".parse::<InputDateFormat>().is_err());
+        assert!("DD-MM-YYYY-YY".parse::<InputDateFormat>().is_err());
+        assert!("%d-%m-%Y".parse::<InputDateFormat>().is_err());
+        assert!("DD-MON-YYYY".parse::<InputDateFormat>().is_err());
+    }
+
+    #[test]
+    fn test_parse_date_with_several_formats() {
+        let formats: Vec<InputDateFormat> = ["DD-MM-YYYY", "YYYY-MM-DD", "DD/MM/YY"]
+            .iter()
+            .map(|f| f.parse().unwrap())
+            .collect();
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 15);
+
+        assert_eq!(parse_date_with("15-01-2025", &formats).ok(), expected);
+        assert_eq!(parse_date_with("2025-01-15", &formats).ok(), expected);
+        assert_eq!(parse_date_with("15/01/25", &formats).ok(), expected);
+
+        let err = parse_date_with("Jan 15", &formats).unwrap_err();
+        assert!(matches!(
+            err,
+            ValidationErrorKind::InvalidDate { expected_format, nearest: None, .. }
+                if expected_format == "DD-MM-YYYY, YYYY-MM-DD, DD/MM/YY"
+        ));
+    }
+
+    #[test]
+    fn test_parse_date_suggests_nearest_real_date() {
+        let nearest = |s: &str, format: &str| {
+            match parse_date_with(s, &[format.parse().unwrap()]).unwrap_err() {
+                ValidationErrorKind::InvalidDate { nearest, .. } => nearest,
+                other => panic!("unexpected error: {:?}", other),
+            }
+        };
+
+        assert_eq!(nearest("31-02-2025", "DD-MM-YYYY").as_deref(), Some("28-02-2025"));
+        assert_eq!(nearest("31-04-2025", "DD-MM-YYYY").as_deref(), Some("30-04-2025"));
+        assert_eq!(nearest("15-13-2025", "DD-MM-YYYY").as_deref(), Some("15-12-2025"));
+        assert_eq!(nearest("2025-02-30", "YYYY-MM-DD").as_deref(), Some("2025-02-28"));
+        assert_eq!(nearest("31/02/24", "DD/MM/YY").as_deref(), Some("29/02/24"));
+        assert_eq!(nearest("02-2025", "DD-MM-YYYY"), None);
+        assert_eq!(nearest("tomorrow", "DD-MM-YYYY"), None);
+    }
+
+    #[test]
+    fn test_parse_month() {
+        assert_eq!(parse_month("03-2025").ok(), NaiveDate::from_ymd_opt(2025, 3, 1));
+        assert!(parse_month("13-2025").is_err());
+        assert!(parse_month("2025-03").is_err());
+    }
+
     #[test]
     fn test_parse_category_valid() {
         assert!(matches!(parse_category("income").unwrap(), Category::Income));
@@ -124,9 +663,52 @@ mod tests {
         assert!(parse_label("test.item").is_err());
     }
 
+    #[test]
+    fn test_split_command_line_plain() {
+        assert_eq!(
+            split_command_line("add expenses 20  -s food").unwrap(),
+            vec!["add", "expenses", "20", "-s", "food"]
+        );
+        assert!(split_command_line("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_split_command_line_quotes_and_escapes() {
+        assert_eq!(
+            split_command_line(r#"add expenses 5 -d "Coffee with Ada" -s 'misc'"#).unwrap(),
+            vec!["add", "expenses", "5", "-d", "Coffee with Ada", "-s", "misc"]
+        );
+        assert_eq!(split_command_line(r"a\ b").unwrap(), vec!["a b"]);
+        assert_eq!(split_command_line(r#""""#).unwrap(), vec![""]);
+    }
+
+    #[test]
+    fn test_split_command_line_unterminated_quote() {
+        assert!(split_command_line("add -d \"oops").is_err());
+    }
+
     #[test]
     fn test_parse_label_allows_underscore_in_middle() {
         assert_eq!(parse_label("test_item").unwrap(), "test_item");
         assert_eq!(parse_label("test_item_123").unwrap(), "test_item_123");
     }
+
+    #[test]
+    fn test_parse_tag() {
+        assert_eq!(parse_tag("vacation").unwrap(), "vacation");
+        assert_eq!(parse_tag(" #kitchen-2026 ").unwrap(), "kitchen-2026");
+        assert!(parse_tag("#").is_err());
+        assert!(parse_tag("new kitchen").is_err());
+        assert!(parse_tag("a,b").is_err());
+    }
+
+    #[test]
+    fn test_parse_percent() {
+        assert_eq!(parse_percent("80").unwrap(), 80.0);
+        assert_eq!(parse_percent(" 92.5% ").unwrap(), 92.5);
+        assert_eq!(parse_percent("120%").unwrap(), 120.0);
+        assert!(parse_percent("0").is_err());
+        assert!(parse_percent("-5%").is_err());
+        assert!(parse_percent("most").is_err());
+    }
 }
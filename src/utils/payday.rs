@@ -0,0 +1,90 @@
+//! The next payday, following the `[payday]` settings
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+use crate::parsers::parse_stored_date;
+use crate::utils::config::{PaydayConfig, PaydayShift};
+
+/// The first payday on or after `today`, or `None` when `payday.day` isn't set
+pub fn next(config: &PaydayConfig, today: NaiveDate) -> Option<NaiveDate> {
+  let day = config.day?;
+  let month_start = today.with_day(1).expect("day 1 exists");
+  // Moving forward can put last month's payday in this one, and moving back
+  // can put next month's in this one and leave the month after as the next
+  (-1..=2)
+    .filter_map(|offset: i32| {
+      let start = if offset < 0 {
+        month_start.checked_sub_months(Months::new(1))?
+      } else {
+        month_start.checked_add_months(Months::new(offset as u32))?
+      };
+      Some(payday_in(config, start, day))
+    })
+    .find(|&payday| payday >= today)
+}
+
+/// Payday in the month starting on `month_start`
+fn payday_in(config: &PaydayConfig, month_start: NaiveDate, day: u32) -> NaiveDate {
+  let last = month_start + Months::new(1) - Days::new(1);
+  let mut date = month_start.with_day(day.min(last.day())).expect("day is in the month");
+  let holidays: Vec<NaiveDate> = config.holidays.iter().filter_map(|d| parse_stored_date(d)).collect();
+  let is_working = |date: NaiveDate| {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !holidays.contains(&date)
+  };
+  while !is_working(date) {
+    date = match config.weekend {
+      PaydayShift::Before => date - Days::new(1),
+      PaydayShift::After => date + Days::new(1),
+      PaydayShift::None => break,
+    };
+  }
+  date
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        parse_stored_date(s).unwrap()
+    }
+
+    fn config(day: u32, weekend: PaydayShift, holidays: &[&str]) -> PaydayConfig {
+        PaydayConfig {
+            day: Some(day),
+            weekend,
+            holidays: holidays.iter().map(|h| h.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_next_payday_moves_off_weekends_and_holidays() {
+        // 25-10-2026 is a Sunday
+        let before = config(25, PaydayShift::Before, &[]);
+        assert_eq!(next(&before, date("16-10-2026")), Some(date("23-10-2026")));
+        let after = config(25, PaydayShift::After, &[]);
+        assert_eq!(next(&after, date("16-10-2026")), Some(date("26-10-2026")));
+        let fixed = config(25, PaydayShift::None, &[]);
+        assert_eq!(next(&fixed, date("16-10-2026")), Some(date("25-10-2026")));
+
+        // Friday 25-12-2026 is a holiday, so payday is the Thursday
+        let christmas = config(25, PaydayShift::Before, &["25-12-2026"]);
+        assert_eq!(next(&christmas, date("01-12-2026")), Some(date("24-12-2026")));
+        assert_eq!(next(&christmas, date("25-12-2026")), Some(date("25-01-2027")));
+    }
+
+    #[test]
+    fn test_next_payday_in_short_months_and_across_months() {
+        let end_of_month = config(31, PaydayShift::None, &[]);
+        assert_eq!(next(&end_of_month, date("10-02-2026")), Some(date("28-02-2026")));
+
+        // Sunday 01-11-2026 paid after: Monday the 2nd, seen from 31 October
+        let first = config(1, PaydayShift::After, &[]);
+        assert_eq!(next(&first, date("31-10-2026")), Some(date("02-11-2026")));
+        // Paid before: Friday 30 October, which is already past
+        let first = config(1, PaydayShift::Before, &[]);
+        assert_eq!(next(&first, date("31-10-2026")), Some(date("01-12-2026")));
+
+        assert_eq!(next(&PaydayConfig::default(), date("31-10-2026")), None);
+    }
+}
@@ -0,0 +1,103 @@
+//! Fiscal years and weeks, following the `[period]` settings
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::utils::config::PeriodConfig;
+
+/// A run of whole days, both ends included
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Period {
+  pub start: NaiveDate,
+  pub end: NaiveDate,
+}
+
+impl Period {
+  pub fn month(first_day: NaiveDate) -> Self {
+    Self {
+      start: first_day,
+      end: first_day + Months::new(1) - Days::new(1),
+    }
+  }
+
+  /// The fiscal year that starts in calendar year `year`
+  pub fn fiscal_year(config: &PeriodConfig, year: i32) -> Self {
+    let start = NaiveDate::from_ymd_opt(year, config.fiscal_year_start.number(), 1)
+      .expect("the first of a month exists");
+    Self {
+      start,
+      end: start + Months::new(12) - Days::new(1),
+    }
+  }
+
+  /// The week `date` falls in
+  pub fn week(config: &PeriodConfig, date: NaiveDate) -> Self {
+    let first = config.week_start.weekday();
+    let back = (7 + date.weekday().num_days_from_monday() - first.num_days_from_monday()) % 7;
+    let start = date - Days::new(back.into());
+    Self {
+      start,
+      end: start + Days::new(6),
+    }
+  }
+
+  pub fn contains(&self, date: NaiveDate) -> bool {
+    self.start <= date && date <= self.end
+  }
+
+  /// "01-04-2024 – 31-03-2025"
+  pub fn label(&self) -> String {
+    format!("{} – {}", self.start.format("%d-%m-%Y"), self.end.format("%d-%m-%Y"))
+  }
+}
+
+/// The fiscal year `date` falls in, named after the calendar year it starts in
+pub fn fiscal_year_of(config: &PeriodConfig, date: NaiveDate) -> i32 {
+  if date.month() >= config.fiscal_year_start.number() {
+    date.year()
+  } else {
+    date.year() - 1
+  }
+}
+
+/// "2024" for a calendar year, "2024-25" for one starting later in 2024
+pub fn fiscal_year_label(config: &PeriodConfig, year: i32) -> String {
+  if config.fiscal_year_start.number() == 1 {
+    year.to_string()
+  } else {
+    format!("{}-{:02}", year, (year + 1).rem_euclid(100))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DayOfWeek, MonthOfYear};
+
+    fn date(day: u32, month: u32, year: i32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_fiscal_year() {
+        let calendar = PeriodConfig::default();
+        assert_eq!(Period::fiscal_year(&calendar, 2024), Period { start: date(1, 1, 2024), end: date(31, 12, 2024) });
+        assert_eq!(fiscal_year_label(&calendar, 2024), "2024");
+
+        let april = PeriodConfig { fiscal_year_start: MonthOfYear::April, ..Default::default() };
+        assert_eq!(Period::fiscal_year(&april, 2024), Period { start: date(1, 4, 2024), end: date(31, 3, 2025) });
+        assert_eq!(fiscal_year_of(&april, date(15, 3, 2025)), 2024);
+        assert_eq!(fiscal_year_of(&april, date(1, 4, 2025)), 2025);
+        assert_eq!(fiscal_year_label(&april, 2099), "2099-00");
+    }
+
+    #[test]
+    fn test_week() {
+        // 16-10-2026 is a Friday
+        let monday = PeriodConfig::default();
+        assert_eq!(Period::week(&monday, date(16, 10, 2026)), Period { start: date(12, 10, 2026), end: date(18, 10, 2026) });
+
+        let sunday = PeriodConfig { week_start: DayOfWeek::Sunday, ..Default::default() };
+        assert_eq!(Period::week(&sunday, date(16, 10, 2026)).start, date(11, 10, 2026));
+        assert_eq!(Period::week(&sunday, date(11, 10, 2026)).start, date(11, 10, 2026));
+    }
+}
@@ -0,0 +1,138 @@
+//! Plan files for `fintrack plan`: the shape of a tracker without its money.
+//! Subcategories, budgets, schedules and spending limits, but no records, so a
+//! starter setup can be shared and adopted by someone else.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::config::LimitsConfig;
+use crate::{CliError, Frequency, TrackerData, t};
+
+/// The plan format this version writes and reads
+pub const VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Plan {
+  pub version: u32,
+  /// What the amounts are in
+  pub currency: String,
+  /// Names, without Miscellaneous, which every tracker has
+  #[serde(default)]
+  pub subcategories: Vec<String>,
+  /// The `[limits]` settings; left out when none are set
+  #[serde(default, skip_serializing_if = "is_unset")]
+  pub limits: LimitsConfig,
+  #[serde(default)]
+  pub budgets: Vec<PlanBudget>,
+  #[serde(default)]
+  pub schedules: Vec<PlanSchedule>,
+}
+
+/// A monthly budget, by subcategory name
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PlanBudget {
+  pub subcategory: String,
+  pub amount: f64,
+  /// Carry what's left of each month into the next
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub rollover: bool,
+  /// Percent spent that's worth a warning
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub warn_at: Option<f64>,
+  /// Percent spent that counts as over budget
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub error_at: Option<f64>,
+}
+
+/// A schedule without its next date: importing it starts it afresh
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PlanSchedule {
+  /// `income` or `expenses`
+  pub category: String,
+  pub subcategory: String,
+  pub amount: f64,
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub description: String,
+  #[serde(default)]
+  pub every: Frequency,
+  /// The day of the month it falls on
+  pub day: u32,
+}
+
+fn is_unset(limits: &LimitsConfig) -> bool {
+  *limits == LimitsConfig::default()
+}
+
+impl Plan {
+  pub fn from_tracker(tracker_data: &TrackerData, limits: &LimitsConfig) -> Self {
+    let name = |id: usize| tracker_data.subcategory_name(id).cloned().unwrap_or_default();
+    let mut subcategories: Vec<(&usize, &String)> = tracker_data
+      .subcategories_by_id
+      .iter()
+      .filter(|&(&id, _)| Some(id) != tracker_data.miscellaneous_subcategory_id())
+      .collect();
+    subcategories.sort();
+
+    Plan {
+      version: VERSION,
+      currency: tracker_data.currency.clone(),
+      subcategories: subcategories.into_iter().map(|(_, name)| name.clone()).collect(),
+      limits: limits.clone(),
+      budgets: tracker_data
+        .budgets
+        .iter()
+        .map(|budget| PlanBudget {
+          subcategory: name(budget.subcategory),
+          amount: budget.amount,
+          rollover: budget.rollover,
+          warn_at: budget.warn_at,
+          error_at: budget.error_at,
+        })
+        .collect(),
+      schedules: tracker_data
+        .schedules
+        .iter()
+        .map(|schedule| PlanSchedule {
+          category: schedule.category.clone(),
+          subcategory: schedule.subcategory.clone(),
+          amount: schedule.amount,
+          description: schedule.description.clone(),
+          every: schedule.every,
+          day: schedule.day,
+        })
+        .collect(),
+    }
+  }
+
+  pub fn read(path: &Path) -> Result<Self, CliError> {
+    let contents = match fs::read_to_string(path) {
+      Ok(contents) => contents,
+      Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+        return Err(CliError::FileNotFound(path.display().to_string()));
+      }
+      Err(err) => return Err(err.into()),
+    };
+    let plan: Plan = toml::from_str(&contents)
+      .map_err(|err| {
+        CliError::Other(t!("error-plan-invalid", path = path.display().to_string(), reason = err.to_string()))
+      })?;
+    if plan.version > VERSION {
+      return Err(CliError::Other(t!(
+        "error-plan-version",
+        path = path.display().to_string(),
+        version = plan.version,
+        supported = VERSION
+      )));
+    }
+    Ok(plan)
+  }
+
+  pub fn write(&self, path: &Path) -> Result<(), CliError> {
+    let contents = toml::to_string_pretty(self)
+      .map_err(|err| CliError::Other(t!("error-plan-write", reason = err.to_string())))?;
+    fs::write(path, contents)?;
+    Ok(())
+  }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::{CliError, t, utils::config::InvestConfig};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Get the current price of `symbol` from the `invest.price_url` service
+pub fn fetch(config: &InvestConfig, symbol: &str) -> Result<f64, CliError> {
+  let Some(template) = &config.price_url else {
+    return Err(CliError::Other(t!("error-no-price-service")));
+  };
+  let url = template.replace("{symbol}", symbol);
+
+  let body = match ureq::get(&url).timeout(TIMEOUT).call() {
+    Ok(response) => response.into_string()?,
+    Err(ureq::Error::Status(code, _)) => {
+      return Err(CliError::Other(t!("error-price-status", status = code, symbol = symbol)));
+    }
+    Err(ureq::Error::Transport(err)) => {
+      return Err(CliError::Other(t!("error-price-unreachable", reason = err.to_string())));
+    }
+  };
+
+  let json: Value = serde_json::from_str(&body)?;
+  price_at(&json, &config.price_field).ok_or_else(|| {
+    CliError::Other(t!("error-price-missing", field = config.price_field.as_str(), symbol = symbol))
+  })
+}
+
+/// The number at a dot-separated `path`. Services often send prices as
+/// strings, so those are accepted too.
+fn price_at(json: &Value, path: &str) -> Option<f64> {
+  let value = path
+    .split('.')
+    .filter(|key| !key.is_empty())
+    .try_fold(json, |value, key| match key.parse::<usize>() {
+      Ok(index) => value.get(index),
+      Err(_) => value.get(key),
+    })?;
+
+  match value {
+    Value::Number(number) => number.as_f64(),
+    Value::String(text) => text.trim().parse().ok(),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_price_at() {
+        assert_eq!(price_at(&json!({ "price": 195.5 }), "price"), Some(195.5));
+        assert_eq!(price_at(&json!({ "quote": { "last": "12.30" } }), "quote.last"), Some(12.3));
+        assert_eq!(price_at(&json!({ "data": [{ "close": 7 }] }), "data.0.close"), Some(7.0));
+        assert_eq!(price_at(&json!({ "price": null }), "price"), None);
+        assert_eq!(price_at(&json!({}), "price"), None);
+    }
+}
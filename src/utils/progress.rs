@@ -0,0 +1,73 @@
+use std::io::{self, IsTerminal};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::GlobalContext;
+
+/// Below these sizes an operation finishes before a bar would be readable
+const MIN_RECORDS: u64 = 5_000;
+const MIN_BYTES: u64 = 4 * 1024 * 1024;
+
+/// What a progress bar counts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Unit {
+  Records,
+  Bytes,
+}
+
+impl Unit {
+  fn template(self) -> &'static str {
+    match self {
+      Unit::Records => "{msg} [{bar:30}] {human_pos}/{human_len} records ({eta})",
+      Unit::Bytes => "{msg} [{bar:30}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})",
+    }
+  }
+
+  fn threshold(self) -> u64 {
+    match self {
+      Unit::Records => MIN_RECORDS,
+      Unit::Bytes => MIN_BYTES,
+    }
+  }
+}
+
+/// Start a progress bar on stderr for an operation over `total` records or bytes.
+///
+/// The bar is hidden when stderr isn't a terminal, in quiet mode, and for
+/// datasets small enough to finish instantly, so callers can always drive it
+/// unconditionally.
+pub fn start(gctx: &GlobalContext, unit: Unit, total: u64, message: &'static str) -> ProgressBar {
+  if !should_show(total, unit, gctx.is_quiet(), io::stderr().is_terminal()) {
+    return ProgressBar::hidden();
+  }
+
+  let style = ProgressStyle::with_template(unit.template())
+    .expect("progress template is valid")
+    .progress_chars("=> ");
+  ProgressBar::new(total)
+    .with_style(style)
+    .with_message(message)
+}
+
+fn should_show(total: u64, unit: Unit, quiet: bool, is_terminal: bool) -> bool {
+  is_terminal && !quiet && total >= unit.threshold()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_hidden_for_small_datasets() {
+        assert!(!should_show(10, Unit::Records, false, true));
+        assert!(should_show(50_000, Unit::Records, false, true));
+        assert!(!should_show(1024, Unit::Bytes, false, true));
+        assert!(should_show(64 * 1024 * 1024, Unit::Bytes, false, true));
+    }
+
+    #[test]
+    fn test_progress_hidden_when_not_a_terminal_or_quiet() {
+        assert!(!should_show(50_000, Unit::Records, false, false));
+        assert!(!should_show(50_000, Unit::Records, true, true));
+    }
+}
@@ -0,0 +1,306 @@
+//! Exchange rates for records in another currency, from the provider chosen
+//! with `rates.provider`. Rates fetched over the network are kept in
+//! `rates.json` next to the tracker, so each one is only asked for once.
+
+use std::{collections::BTreeMap, fs, io::Read, time::Duration};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+  CliError, GlobalContext, t,
+  utils::config::{RateSource, RatesConfig},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a rate for today is trusted; rates for earlier days don't change
+const FRESH_HOURS: i64 = 12;
+const EXCHANGERATE_HOST_URL: &str = "https://api.exchangerate.host/convert";
+/// The last 90 days of ECB reference rates, and every day since 1999
+const ECB_RECENT_URL: &str = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-hist-90d.xml";
+const ECB_HISTORY_URL: &str = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-hist.xml";
+
+/// Somewhere exchange rates come from
+pub trait RateProvider {
+  fn name(&self) -> &'static str;
+
+  /// How much one unit of `from` was worth in `to` on `date`
+  fn rate(&self, from: &str, to: &str, date: NaiveDate) -> Result<f64, CliError>;
+
+  /// Whether `rate` goes online, so its answers are worth caching
+  fn needs_network(&self) -> bool {
+    true
+  }
+}
+
+/// The provider `rates.provider` names. `base` is the tracker's currency,
+/// which manual rates are given in.
+pub fn provider(config: &RatesConfig, base: &str) -> Box<dyn RateProvider> {
+  match config.provider {
+    RateSource::Manual => Box::new(ManualRates {
+      rates: config.manual.clone(),
+      base: base.to_string(),
+    }),
+    RateSource::ExchangerateHost => Box::new(ExchangerateHost {
+      access_key: config.access_key.clone(),
+    }),
+    RateSource::Ecb => Box::new(Ecb),
+  }
+}
+
+/// How much one unit of `from` was worth in `to` on `date`, from the cache
+/// when it has the rate and from the configured provider otherwise
+pub fn lookup(
+  gctx: &GlobalContext,
+  base: &str,
+  from: &str,
+  to: &str,
+  date: NaiveDate,
+) -> Result<f64, CliError> {
+  let provider = provider(&gctx.config().rates, base);
+  if !provider.needs_network() {
+    return provider.rate(from, to, date);
+  }
+
+  let key = format!("{}:{}:{}:{}", provider.name(), from, to, date.format("%Y-%m-%d"));
+  let mut cache = load_cache(gctx)?;
+  if let Some(cached) = cache.get(&key) {
+    let fresh = date < gctx.today()
+      || DateTime::parse_from_rfc3339(&cached.fetched_at)
+        .is_ok_and(|fetched_at| Utc::now() - fetched_at.to_utc() < chrono::Duration::hours(FRESH_HOURS));
+    if fresh || gctx.is_offline() {
+      gctx.verbose(format!("Using the cached {} rate for {} to {} on {}", provider.name(), from, to, date));
+      return Ok(cached.rate);
+    }
+  }
+
+  gctx.ensure_online(&format!("Getting the {} to {} rate from {}", from, to, provider.name()))?;
+  gctx.verbose(format!("Fetching the {} to {} rate for {} from {}", from, to, date, provider.name()));
+  let rate = provider.rate(from, to, date)?;
+  cache.insert(
+    key,
+    CachedRate {
+      rate,
+      fetched_at: Utc::now().to_rfc3339(),
+    },
+  );
+  fs::write(gctx.rates_path(), serde_json::to_vec_pretty(&cache)?)?;
+  Ok(rate)
+}
+
+/// A rate fetched earlier, in `rates.json`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CachedRate {
+  rate: f64,
+  /// RFC 3339
+  fetched_at: String,
+}
+
+/// The cached rates by `provider:FROM:TO:YYYY-MM-DD`, or none when there's no
+/// `rates.json`. A damaged file is started over rather than failing the record.
+fn load_cache(gctx: &GlobalContext) -> Result<BTreeMap<String, CachedRate>, CliError> {
+  match fs::read(gctx.rates_path()) {
+    Ok(bytes) => Ok(serde_json::from_slice(&bytes).unwrap_or_default()),
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+    Err(err) => Err(err.into()),
+  }
+}
+
+/// `rates.manual`: each currency's worth in the tracker's currency
+pub struct ManualRates {
+  pub rates: BTreeMap<String, f64>,
+  pub base: String,
+}
+
+impl ManualRates {
+  fn value(&self, currency: &str) -> Option<f64> {
+    if currency.eq_ignore_ascii_case(&self.base) {
+      return Some(1.0);
+    }
+    self
+      .rates
+      .iter()
+      .find(|(code, _)| code.eq_ignore_ascii_case(currency))
+      .map(|(_, rate)| *rate)
+  }
+}
+
+impl RateProvider for ManualRates {
+  fn name(&self) -> &'static str {
+    "manual"
+  }
+
+  fn rate(&self, from: &str, to: &str, _date: NaiveDate) -> Result<f64, CliError> {
+    let value = |currency: &str| {
+      self.value(currency).ok_or_else(|| {
+        CliError::Other(t!("error-no-rate", currency = currency))
+      })
+    };
+    Ok(value(from)? / value(to)?)
+  }
+
+  fn needs_network(&self) -> bool {
+    false
+  }
+}
+
+/// The exchangerate.host API, which needs an access key
+pub struct ExchangerateHost {
+  pub access_key: Option<String>,
+}
+
+impl RateProvider for ExchangerateHost {
+  fn name(&self) -> &'static str {
+    "exchangerate.host"
+  }
+
+  fn rate(&self, from: &str, to: &str, date: NaiveDate) -> Result<f64, CliError> {
+    let Some(access_key) = &self.access_key else {
+      return Err(CliError::Other(t!("error-rates-access-key")));
+    };
+    let request = ureq::get(EXCHANGERATE_HOST_URL)
+      .timeout(TIMEOUT)
+      .query("from", from)
+      .query("to", to)
+      .query("amount", "1")
+      .query("date", &date.format("%Y-%m-%d").to_string())
+      .query("access_key", access_key);
+    let json: Value = serde_json::from_str(&fetch(request, self.name())?)?;
+
+    if json.get("success").and_then(Value::as_bool) == Some(false) {
+      let reason = json
+        .pointer("/error/info")
+        .and_then(Value::as_str)
+        .map_or_else(|| t!("error-rates-refused"), str::to_string);
+      return Err(CliError::Other(t!("error-rates-provider", provider = self.name(), reason = reason)));
+    }
+    json
+      .get("result")
+      .and_then(Value::as_f64)
+      .filter(|rate| *rate > 0.0)
+      .ok_or_else(|| {
+        CliError::Other(t!("error-rates-no-result", provider = self.name(), from = from, to = to))
+      })
+  }
+}
+
+/// The European Central Bank's reference rates, published each working day
+/// against the euro
+pub struct Ecb;
+
+impl RateProvider for Ecb {
+  fn name(&self) -> &'static str {
+    "ECB"
+  }
+
+  fn rate(&self, from: &str, to: &str, date: NaiveDate) -> Result<f64, CliError> {
+    let recent = fetch(ureq::get(ECB_RECENT_URL).timeout(TIMEOUT), self.name())?;
+    let rates = match ecb_rates_on(&recent, date) {
+      Some(rates) => rates,
+      None => {
+        let history = fetch(ureq::get(ECB_HISTORY_URL).timeout(TIMEOUT), self.name())?;
+        ecb_rates_on(&history, date).ok_or_else(|| {
+          CliError::Other(t!("error-ecb-no-rates", date = date.format("%d-%m-%Y").to_string()))
+        })?
+      }
+    };
+
+    let per_euro = |currency: &str| match currency.to_uppercase().as_str() {
+      "EUR" => Ok(1.0),
+      code => rates
+        .get(code)
+        .copied()
+        .ok_or_else(|| CliError::Other(t!("error-ecb-no-currency", currency = code))),
+    };
+    Ok(per_euro(to)? / per_euro(from)?)
+  }
+}
+
+/// The rates per euro on the last day on or before `date` the feed covers.
+/// `None` when `date` is older than the feed, so a longer one is needed.
+fn ecb_rates_on(xml: &str, date: NaiveDate) -> Option<BTreeMap<String, f64>> {
+  let mut days = Vec::new();
+  for tag in xml.split('<').filter(|tag| tag.starts_with("Cube ")) {
+    if let Some(time) = attribute(tag, "time") {
+      days.push((NaiveDate::parse_from_str(time, "%Y-%m-%d").ok()?, BTreeMap::new()));
+    } else if let (Some(currency), Some(rate), Some((_, rates))) =
+      (attribute(tag, "currency"), attribute(tag, "rate"), days.last_mut())
+    {
+      rates.insert(currency.to_string(), rate.parse().ok()?);
+    }
+  }
+
+  // Feeds list the newest day first
+  let oldest = days.iter().map(|(day, _)| *day).min()?;
+  if date < oldest {
+    return None;
+  }
+  days
+    .into_iter()
+    .filter(|(day, _)| *day <= date)
+    .max_by_key(|(day, _)| *day)
+    .map(|(_, rates)| rates)
+}
+
+/// The value of `name='…'` or `name="…"` in an XML tag
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+  let start = tag.find(&format!(" {}=", name))? + name.len() + 2;
+  let quote = tag[start..].chars().next()?;
+  let value = &tag[start + 1..];
+  Some(&value[..value.find(quote)?])
+}
+
+fn fetch(request: ureq::Request, name: &str) -> Result<String, CliError> {
+  match request.call() {
+    Ok(response) => {
+      // The full ECB history is larger than `into_string` allows
+      let mut body = String::new();
+      response.into_reader().read_to_string(&mut body)?;
+      Ok(body)
+    }
+    Err(ureq::Error::Status(code, _)) => {
+      Err(CliError::Other(t!("error-service-status", service = name, status = code)))
+    }
+    Err(ureq::Error::Transport(err)) => Err(CliError::Other(t!(
+      "error-service-unreachable",
+      service = name,
+      reason = err.to_string()
+    ))),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32, month: u32, year: i32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_manual_rates_go_through_the_base_currency() {
+        let manual = ManualRates {
+            rates: BTreeMap::from([("EUR".to_string(), 1.5), ("GBP".to_string(), 2.0)]),
+            base: "USD".to_string(),
+        };
+        let today = date(16, 10, 2026);
+        assert_eq!(manual.rate("eur", "USD", today).unwrap(), 1.5);
+        assert_eq!(manual.rate("USD", "GBP", today).unwrap(), 0.5);
+        assert_eq!(manual.rate("EUR", "GBP", today).unwrap(), 0.75);
+        assert!(manual.rate("JPY", "USD", today).is_err());
+    }
+
+    #[test]
+    fn test_ecb_rates_on_uses_the_last_published_day() {
+        let xml = "<gesmes:Envelope><Cube>\
+            <Cube time='2026-10-16'><Cube currency='USD' rate='1.10'/><Cube currency='GBP' rate='0.85'/></Cube>\
+            <Cube time=\"2026-10-15\"><Cube currency=\"USD\" rate=\"1.08\"/></Cube>\
+            </Cube></gesmes:Envelope>";
+        assert_eq!(ecb_rates_on(xml, date(16, 10, 2026)).unwrap()["GBP"], 0.85);
+        // A Saturday gets Friday's rates
+        assert_eq!(ecb_rates_on(xml, date(17, 10, 2026)).unwrap()["USD"], 1.10);
+        assert_eq!(ecb_rates_on(xml, date(15, 10, 2026)).unwrap()["USD"], 1.08);
+        assert_eq!(ecb_rates_on(xml, date(14, 10, 2026)), None);
+    }
+}
@@ -0,0 +1,470 @@
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::SystemTime;
+
+use jsonschema::error::{TypeKind, ValidationErrorKind};
+use jsonschema::{JsonType, ValidationError};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{debug, info_span};
+
+use crate::utils::backup;
+use crate::utils::file::{FilePath, write_json_to_file};
+use crate::utils::hooks::{self, Hook};
+use crate::utils::progress::{self, Unit};
+use crate::utils::summary;
+use crate::utils::webhook::{self, Event};
+use crate::{
+  CliError, CliResponse, CliResult, GlobalContext, ResponseContent, TrackerData, TrackerDiff,
+  records_checksum, t,
+};
+
+/// Tracker data held in memory for the length of an interactive shell session.
+///
+/// While a session is active, [`load_tracker`] and [`save_tracker`] work on this
+/// copy instead of the file; changes are written out by [`flush_session`].
+#[derive(Debug)]
+pub struct Session {
+  data: TrackerData,
+  dirty: bool,
+  /// Calls held back until the changes they report are written
+  after_save: Vec<AfterSave>,
+}
+
+impl Session {
+  pub fn data(&self) -> &TrackerData {
+    &self.data
+  }
+
+  /// Whether the in-memory data has changes that haven't been written yet
+  pub fn is_dirty(&self) -> bool {
+    self.dirty
+  }
+}
+
+/// A post-change hook or webhook call, made once the change it reports is on
+/// disk
+#[derive(Debug)]
+pub enum AfterSave {
+  Hook(Hook, Value),
+  Webhook(Event, Value),
+}
+
+impl AfterSave {
+  fn run(&self, gctx: &GlobalContext) {
+    match self {
+      AfterSave::Hook(hook, payload) => {
+        // Only pre-hooks can fail; a failing post-hook is just logged
+        let _ = hooks::run(gctx, *hook, payload);
+      }
+      AfterSave::Webhook(event, payload) => webhook::send(gctx, *event, payload),
+    }
+  }
+}
+
+/// Run `action` now, or when a session is active, once the session is
+/// written. Changes a session discards are never reported.
+pub fn after_save(gctx: &mut GlobalContext, action: AfterSave) {
+  match gctx.session_mut() {
+    Some(session) => session.after_save.push(action),
+    None => action.run(gctx),
+  }
+}
+
+/// The last tracker read from or written to disk, so long-running modes
+/// (`serve`, `watch`, the TUI) don't parse an unchanged file over and over.
+///
+/// The file counts as unchanged while its modification time and length match.
+#[derive(Debug)]
+pub struct TrackerCache {
+  modified: SystemTime,
+  len: u64,
+  data: TrackerData,
+}
+
+/// The tracker as it was and as a command left it, when `--dry-run` kept the
+/// changes from being written
+#[derive(Debug)]
+pub struct DryRun {
+  before: TrackerData,
+  after: TrackerData,
+}
+
+/// Read and parse the tracker file, reusing the cached copy if the file
+/// hasn't changed since it was last read or written
+pub fn load_tracker(gctx: &GlobalContext) -> Result<TrackerData, CliError> {
+  if let Some(session) = gctx.session() {
+    return Ok(session.data.clone());
+  }
+  // Later steps of a dry run see the changes earlier ones would have made
+  if let Some(staged) = gctx.staged().borrow().as_ref() {
+    return Ok(staged.after.clone());
+  }
+  read_tracker(gctx)
+}
+
+/// [`load_tracker`] straight from the file, whatever a session or dry run holds
+fn read_tracker(gctx: &GlobalContext) -> Result<TrackerData, CliError> {
+  let _span = info_span!("load", path = %gctx.tracker_path().display()).entered();
+  if !gctx.tracker_path().exists() {
+    return Err(match gctx.has_custom_tracker() {
+      true => CliError::FileNotFound(gctx.tracker_path().display().to_string()),
+      false => CliError::NotInitialized,
+    });
+  }
+
+  let file = gctx.tracker_path().open_read()?;
+  let metadata = file.metadata()?;
+  let modified = metadata.modified()?;
+  if let Some(cache) = gctx.cache().borrow().as_ref()
+    && cache.modified == modified
+    && cache.len == metadata.len()
+  {
+    debug!(records = cache.data.records.len(), "tracker unchanged, using cache");
+    return Ok(cache.data.clone());
+  }
+
+  gctx.verbose(format!("Reading {}", gctx.tracker_path().display()));
+  let bar = progress::start(gctx, Unit::Bytes, metadata.len(), "Loading");
+
+  let _span = info_span!("parse").entered();
+  let mut de = serde_json::Deserializer::from_reader(BufReader::new(bar.wrap_read(file)));
+  let parsed = match serde_path_to_error::deserialize::<_, TrackerData>(&mut de) {
+    Ok(tracker_data) => de.end().map(|()| tracker_data),
+    // Well-formed JSON with a bad value was most likely edited by hand, so
+    // point at it rather than swapping in a backup
+    Err(err) if err.inner().is_data() => {
+      bar.finish_and_clear();
+      return Err(invalid_field(err));
+    }
+    Err(err) => Err(err.into_inner()),
+  };
+  bar.finish_and_clear();
+  let mut tracker_data = match parsed {
+    Ok(tracker_data) => tracker_data,
+    Err(err) if !err.is_io() && !gctx.is_read_only() && !gctx.is_dry_run() && !gctx.has_custom_tracker() => {
+      return Err(recover(gctx, err)?);
+    }
+    Err(err) => return Err(err.into()),
+  };
+  verify_checksum(&tracker_data)?;
+  tracker_data.sort_records();
+  debug!(records = tracker_data.records.len(), "tracker parsed");
+
+  gctx.verbose(format!("Loaded {} record(s)", tracker_data.records.len()));
+  *gctx.cache().borrow_mut() = Some(TrackerCache {
+    modified,
+    len: metadata.len(),
+    data: tracker_data.clone(),
+  });
+  Ok(tracker_data)
+}
+
+/// Read a tracker file other than this one, e.g. for `fintrack merge`. It's
+/// checked like the tracker is, but nothing is cached or recovered.
+pub fn read_tracker_file(path: &Path) -> Result<TrackerData, CliError> {
+  let _span = info_span!("load", path = %path.display()).entered();
+  if !path.is_file() {
+    return Err(CliError::FileNotFound(path.display().to_string()));
+  }
+
+  let file = path.open_read()?;
+  let json: Value = serde_json::from_reader(BufReader::new(file))?;
+  tracker_from_json(json)
+}
+
+/// Parse tracker JSON that didn't come from the tracker file, such as a copy
+/// edited by `fintrack edit-file`. A bad field is reported like the tracker's.
+pub fn parse_tracker(bytes: &[u8]) -> Result<TrackerData, CliError> {
+  tracker_from_json(serde_json::from_slice(bytes)?)
+}
+
+/// The JSON Schema tracker files follow, as `fintrack schema` prints it
+pub fn tracker_schema() -> Value {
+  schemars::schema_for!(TrackerData).to_value()
+}
+
+/// Check JSON from outside the tracker against [`tracker_schema`] before
+/// reading it, so a file another tool wrote fails on the first value that
+/// doesn't fit rather than somewhere inside serde
+fn tracker_from_json(json: Value) -> Result<TrackerData, CliError> {
+  let schema = tracker_schema();
+  let validator = jsonschema::validator_for(&schema)
+    .map_err(|err| CliError::Other(t!("error-schema-compile", reason = err.to_string())))?;
+  validator.validate(&json).map_err(schema_error)?;
+
+  let mut tracker_data = serde_path_to_error::deserialize::<_, TrackerData>(json).map_err(invalid_field)?;
+  verify_checksum(&tracker_data)?;
+  tracker_data.sort_records();
+  Ok(tracker_data)
+}
+
+fn schema_error(err: ValidationError) -> CliError {
+  // `/records/2/amount` reads as `records[2].amount`, like serde's paths
+  let mut path = String::new();
+  for segment in err.instance_path().to_string().split('/').skip(1) {
+    let segment = segment.replace("~1", "/").replace("~0", "~");
+    match segment.parse::<usize>() {
+      Ok(index) => path.push_str(&format!("[{}]", index)),
+      Err(_) if path.is_empty() => path.push_str(&segment),
+      Err(_) => path.push_str(&format!(".{}", segment)),
+    }
+  }
+  // A missing field is pointed at by name rather than by the object it's missing from
+  if let ValidationErrorKind::Required { property: Value::String(property) } = err.kind() {
+    match path.is_empty() {
+      true => path.push_str(property),
+      false => path.push_str(&format!(".{}", property)),
+    }
+  }
+  tracing::warn!(path = %path, error = %err, "tracker doesn't fit the schema");
+
+  let expected = match err.kind() {
+    ValidationErrorKind::Type { kind: TypeKind::Single(kind) } => Some(describe_type(*kind).to_string()),
+    ValidationErrorKind::Type { kind: TypeKind::Multiple(kinds) } => {
+      let names: Vec<&str> = kinds.iter().map(describe_type).collect();
+      Some(names.join(" or "))
+    }
+    _ => None,
+  };
+  CliError::InvalidField {
+    path: if path.is_empty() { "(top level)".to_string() } else { path },
+    message: err.to_string(),
+    expected,
+  }
+}
+
+fn describe_type(kind: JsonType) -> &'static str {
+  match kind {
+    JsonType::Array => "a list",
+    JsonType::Boolean => "true or false",
+    JsonType::Integer => "a whole number",
+    JsonType::Null => "null",
+    JsonType::Number => "a number",
+    JsonType::Object => "an object",
+    JsonType::String => "a string",
+  }
+}
+
+fn invalid_field(err: serde_path_to_error::Error<serde_json::Error>) -> CliError {
+  tracing::warn!(path = %err.path(), error = %err.inner(), "tracker field doesn't fit");
+  let message = err.inner().to_string();
+  // serde phrases these as "invalid type: string \"ten\", expected f64 at line 4 column 3"
+  let expected = message
+    .split_once(", expected ")
+    .map(|(_, rest)| rest.split(" at line ").next().unwrap_or(rest))
+    .map(|expected| match expected {
+      "f32" | "f64" => "a number".to_string(),
+      "u8" | "u16" | "u32" | "u64" | "usize" | "i32" | "i64" => "a whole number".to_string(),
+      other => other.to_string(),
+    });
+
+  CliError::InvalidField {
+    path: err.path().to_string(),
+    message,
+    expected,
+  }
+}
+
+/// What's written to `tracker.json`: the tracker plus a checksum of its records
+#[derive(Serialize)]
+struct StoredTracker<'a> {
+  // First, so the whole line can be deleted after a deliberate hand edit
+  checksum: String,
+  #[serde(flatten)]
+  data: &'a TrackerData,
+}
+
+/// Fail if the records don't match the checksum saved with them. Files
+/// without a checksum (written by older versions) are accepted as they are.
+fn verify_checksum(tracker_data: &TrackerData) -> Result<(), CliError> {
+  let Some(expected) = &tracker_data.checksum else {
+    return Ok(());
+  };
+
+  let _span = info_span!("verify").entered();
+  if records_checksum(&tracker_data.records) != *expected {
+    tracing::warn!("tracker checksum mismatch");
+    return Err(CliError::ChecksumMismatch);
+  }
+  Ok(())
+}
+
+/// The tracker file doesn't parse: put the newest good backup in its place
+/// and report what happened, so the user can check it before carrying on
+fn recover(gctx: &GlobalContext, err: serde_json::Error) -> Result<CliError, CliError> {
+  tracing::warn!(error = %err, "tracker file is corrupted");
+  gctx.verbose(format!("Tracker file is corrupted: {}", err));
+
+  Ok(match backup::restore_latest(gctx)? {
+    Some(timestamp) => CliError::CorruptedData {
+      backup_restored: true,
+      timestamp,
+    },
+    None => CliError::CorruptedData {
+      backup_restored: false,
+      timestamp: chrono::Utc::now().to_rfc3339(),
+    },
+  })
+}
+
+/// Fail with [`CliError::ReadOnly`] if the tracker must not be changed
+pub fn ensure_writable(gctx: &GlobalContext) -> Result<(), CliError> {
+  match gctx.is_read_only() {
+    true => Err(CliError::ReadOnly),
+    false => Ok(()),
+  }
+}
+
+/// Persist modified tracker data, or stage it in the session if one is active
+pub fn save_tracker(gctx: &mut GlobalContext, tracker_data: &TrackerData) -> Result<(), CliError> {
+  ensure_writable(gctx)?;
+  if let Some(session) = gctx.session_mut() {
+    session.data = tracker_data.clone();
+    session.dirty = true;
+    return Ok(());
+  }
+
+  write_tracker(gctx, tracker_data)
+}
+
+/// Load the tracker once and keep it in memory until [`end_session`]
+pub fn start_session(gctx: &mut GlobalContext) -> Result<(), CliError> {
+  let data = load_tracker(gctx)?;
+  gctx.set_session(Some(Session {
+    data,
+    dirty: false,
+    after_save: Vec::new(),
+  }));
+  Ok(())
+}
+
+/// Write the session's data to the tracker file if it has unsaved changes.
+/// Returns whether anything was written.
+pub fn flush_session(gctx: &mut GlobalContext) -> Result<bool, CliError> {
+  let Some(session) = gctx.session() else {
+    return Ok(false);
+  };
+  if !session.dirty {
+    return Ok(false);
+  }
+
+  let data = session.data.clone();
+  write_tracker(gctx, &data)?;
+  let pending = match gctx.session_mut() {
+    Some(session) => {
+      session.dirty = false;
+      std::mem::take(&mut session.after_save)
+    }
+    None => Vec::new(),
+  };
+  for action in &pending {
+    action.run(gctx);
+  }
+  Ok(true)
+}
+
+/// Flush any pending changes and go back to reading and writing the file directly
+pub fn end_session(gctx: &mut GlobalContext) -> Result<(), CliError> {
+  flush_session(gctx)?;
+  gctx.set_session(None);
+  Ok(())
+}
+
+/// Drop the session and any changes it holds without writing them, along
+/// with the hooks and webhook calls that would have reported them
+pub fn discard_session(gctx: &mut GlobalContext) {
+  gctx.set_session(None);
+}
+
+fn write_tracker(gctx: &GlobalContext, tracker_data: &TrackerData) -> Result<(), CliError> {
+  if gctx.is_dry_run() {
+    return stage(gctx, tracker_data);
+  }
+  let _span = info_span!("write", path = %gctx.tracker_path().display()).entered();
+  backup::backup_tracker(gctx)?;
+  let mut file = gctx.tracker_path().open_read_write()?;
+  let stored = StoredTracker {
+    checksum: records_checksum(&tracker_data.records),
+    data: tracker_data,
+  };
+  write_json_to_file(&stored, &mut file, gctx.config().storage.compact)?;
+  debug!(records = tracker_data.records.len(), "tracker written");
+  written(gctx, &file, tracker_data)
+}
+
+/// Swap the tracker file for `tracker_data` in one step: it's written beside
+/// the tracker and renamed over it, so a crash leaves either the old file or
+/// the new one. Used where the whole tracker is being replaced.
+pub fn replace_tracker(gctx: &mut GlobalContext, tracker_data: &TrackerData) -> Result<(), CliError> {
+  ensure_writable(gctx)?;
+  if gctx.is_dry_run() {
+    return stage(gctx, tracker_data);
+  }
+  let _span = info_span!("replace", path = %gctx.tracker_path().display()).entered();
+  backup::backup_tracker(gctx)?;
+  let staged = gctx.tracker_path().with_extension("json.new");
+  let mut file = fs::File::create(&staged)?;
+  let stored = StoredTracker {
+    checksum: records_checksum(&tracker_data.records),
+    data: tracker_data,
+  };
+  write_json_to_file(&stored, &mut file, gctx.config().storage.compact)?;
+  file.sync_all()?;
+  fs::rename(&staged, gctx.tracker_path())?;
+  debug!(records = tracker_data.records.len(), "tracker replaced");
+  written(gctx, &file, tracker_data)
+}
+
+/// Keep `tracker_data` instead of writing it, for [`finish_dry_run`] to show
+fn stage(gctx: &GlobalContext, tracker_data: &TrackerData) -> Result<(), CliError> {
+  let staged = gctx.staged().borrow_mut().take();
+  let before = match staged {
+    Some(staged) => staged.before,
+    None => read_tracker(gctx)?,
+  };
+  gctx.verbose(format!(
+    "Dry run: not writing {} record(s) to {}",
+    tracker_data.records.len(),
+    gctx.tracker_path().display()
+  ));
+  *gctx.staged().borrow_mut() = Some(DryRun {
+    before,
+    after: tracker_data.clone(),
+  });
+  Ok(())
+}
+
+/// With `--dry-run`, swap the response of a command that would have changed
+/// the tracker for what it would have changed. Failures are passed on as they
+/// are.
+pub fn finish_dry_run(gctx: &GlobalContext, result: CliResult) -> CliResult {
+  let staged = gctx.staged().borrow_mut().take();
+  match (staged, result) {
+    (Some(DryRun { before, after }), Ok(response)) => Ok(
+      CliResponse::new(ResponseContent::DryRun(Box::new(TrackerDiff::between(before, after))))
+        .with_warnings(response.warnings().to_vec()),
+    ),
+    (_, result) => result,
+  }
+}
+
+/// Remember what was just written to the tracker file and refresh the status
+/// summary
+fn written(gctx: &GlobalContext, file: &fs::File, tracker_data: &TrackerData) -> Result<(), CliError> {
+  let metadata = file.metadata()?;
+  *gctx.cache().borrow_mut() = Some(TrackerCache {
+    modified: metadata.modified()?,
+    len: metadata.len(),
+    data: tracker_data.clone(),
+  });
+  summary::update(gctx, tracker_data);
+
+  gctx.verbose(format!(
+    "Wrote {} record(s) to {}",
+    tracker_data.records.len(),
+    gctx.tracker_path().display()
+  ));
+  Ok(())
+}
@@ -0,0 +1,137 @@
+//! Today's summary for `fintrack status`, cached in `status.json` next to the
+//! tracker so `status --short` can run in a shell prompt without parsing it
+
+use std::fs;
+use std::time::UNIX_EPOCH;
+
+use chrono::{Datelike, Days, Months};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{limits, payday};
+use crate::utils::storage::load_tracker;
+use crate::{
+  CliError, GlobalContext, LimitStatus, PaydayStatus, RoundingMode, StatusSummary, TrackerData,
+};
+
+/// `status.json`: the summary and the tracker and config files it was
+/// worked out from
+#[derive(Serialize, Deserialize)]
+struct CachedStatus {
+  tracker_modified: u64,
+  tracker_len: u64,
+  /// Limits and week start come from the config; 0 without a config file
+  #[serde(default)]
+  config_modified: u64,
+  #[serde(flatten)]
+  summary: StatusSummary,
+}
+
+/// Balance, today's spending, this month's budgets, the spending limits and
+/// payday as of today
+pub fn summarize(gctx: &GlobalContext, tracker_data: &TrackerData) -> StatusSummary {
+  let today = gctx.today();
+  let round = |amount: f64| RoundingMode::HalfUp.round(amount, 2).unwrap_or(amount);
+  let expenses_id = tracker_data.category_id("expenses");
+  let spent_today = tracker_data
+    .records_between(Some(today), Some(today))
+    .iter()
+    .filter(|r| r.category == expenses_id)
+    .map(|r| r.amount)
+    .sum();
+
+  let month_start = today.with_day(1).expect("day 1 exists");
+  let budgets =
+    tracker_data.budget_statuses(month_start, month_start + Months::new(1) - Days::new(1));
+
+  StatusSummary {
+    date: today.format("%d-%m-%Y").to_string(),
+    currency: tracker_data.currency.clone(),
+    balance: round(tracker_data.net_balance()),
+    spent_today: round(spent_today),
+    budget_remaining: (!budgets.is_empty())
+      .then(|| round(budgets.iter().map(|b| b.remaining).sum())),
+    over_budget: budgets.iter().filter(|b| b.remaining < 0.0).count(),
+    limits: limits::statuses(gctx, tracker_data, today)
+      .into_iter()
+      .map(|status| LimitStatus {
+        spent: round(status.spent),
+        ..status
+      })
+      .collect(),
+    payday: payday::next(&gctx.config().payday, today).map(|date| {
+      let days = (date - today).num_days();
+      PaydayStatus {
+        date: date.format("%d-%m-%Y").to_string(),
+        days,
+        per_day: (days > 0).then(|| round(tracker_data.net_balance() / days as f64)),
+      }
+    }),
+  }
+}
+
+/// The cached summary if it's from today and the tracker hasn't changed since,
+/// otherwise a fresh one from the tracker, which is cached for next time
+pub fn current(gctx: &GlobalContext) -> Result<StatusSummary, CliError> {
+  let today = gctx.today();
+  if gctx.session().is_none()
+    && let Some(cached) = read_cache(gctx)
+    && cached.summary.date == today.format("%d-%m-%Y").to_string()
+    && stamp(gctx) == Some((cached.tracker_modified, cached.tracker_len, cached.config_modified))
+  {
+    return Ok(cached.summary);
+  }
+
+  let tracker_data = load_tracker(gctx)?;
+  let summary = summarize(gctx, &tracker_data);
+  if gctx.session().is_none() && !gctx.is_read_only() {
+    write_cache(gctx, &summary);
+  }
+  Ok(summary)
+}
+
+/// Refresh the cache after the tracker was written. A cache that can't be
+/// written is only logged: `status` will work it out again next time.
+pub fn update(gctx: &GlobalContext, tracker_data: &TrackerData) {
+  write_cache(gctx, &summarize(gctx, tracker_data));
+}
+
+fn read_cache(gctx: &GlobalContext) -> Option<CachedStatus> {
+  if gctx.has_custom_tracker() {
+    return None;
+  }
+  let bytes = fs::read(gctx.status_path()).ok()?;
+  serde_json::from_slice(&bytes).ok()
+}
+
+fn write_cache(gctx: &GlobalContext, summary: &StatusSummary) {
+  if gctx.has_custom_tracker() {
+    return;
+  }
+  let Some((tracker_modified, tracker_len, config_modified)) = stamp(gctx) else {
+    return;
+  };
+  let cached = CachedStatus {
+    tracker_modified,
+    tracker_len,
+    config_modified,
+    summary: summary.clone(),
+  };
+  let written = serde_json::to_vec(&cached)
+    .map_err(std::io::Error::from)
+    .and_then(|bytes| fs::write(gctx.status_path(), bytes));
+  if let Err(err) = written {
+    tracing::warn!(error = %err, "couldn't cache the status summary");
+  }
+}
+
+/// The tracker file's modification time (in nanoseconds) and length, and
+/// the config file's modification time
+fn stamp(gctx: &GlobalContext) -> Option<(u64, u64, u64)> {
+  let modified = |metadata: &fs::Metadata| -> Option<u64> {
+    let since_epoch = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some(since_epoch.as_nanos() as u64)
+  };
+  let tracker = fs::metadata(gctx.tracker_path()).ok()?;
+  let config = fs::metadata(gctx.config_path()).ok().and_then(|m| modified(&m)).unwrap_or(0);
+  Some((modified(&tracker)?, tracker.len(), config))
+}
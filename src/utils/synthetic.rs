@@ -0,0 +1,88 @@
+use chrono::{Duration, NaiveDate};
+
+use crate::{Currency, Record, TrackerData, default_tracker_json};
+
+/// Subcategories every synthetic tracker has, besides "miscellaneous"
+const SUBCATEGORIES: [&str; 7] = [
+  "groceries", "rent", "transport", "salary", "utilities", "dining", "health",
+];
+const DESCRIPTIONS: [&str; 5] = ["", "Weekly shop", "Monthly payment", "Card", "Paid in cash"];
+/// Records are spread evenly over this many days, starting on [`first_day`]
+const SPAN_DAYS: usize = 5 * 365;
+
+/// A tracker with `count` made-up records, for benchmarks and for trying
+/// FinTrack on a large data set. The same `count` always gives the same data.
+pub fn tracker(count: usize) -> TrackerData {
+  let mut data: TrackerData = serde_json::from_value(default_tracker_json(&Currency::USD, 1000.0))
+    .expect("default tracker is valid");
+
+  for (index, name) in SUBCATEGORIES.iter().enumerate() {
+    let id = index + 2;
+    data.subcategories_by_id.insert(id, name.to_string());
+    data.subcategories_by_name.insert(name.to_string(), id);
+  }
+  data.next_subcategory_id = SUBCATEGORIES.len() as u32 + 2;
+
+  let income_id = data.category_id("income");
+  let expenses_id = data.category_id("expenses");
+  let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+  data.records = (1..=count)
+    .map(|id| {
+      let roll = next(&mut seed);
+      let date = first_day() + Duration::days((id * SPAN_DAYS / count.max(1)) as i64);
+      // Roughly one record in ten is income
+      let category = if roll.is_multiple_of(10) { income_id } else { expenses_id };
+      let subcategory = (roll >> 8) as usize % (SUBCATEGORIES.len() + 1) + 1;
+
+      let mut record = Record {
+        id,
+        category,
+        subcategory,
+        description: DESCRIPTIONS[(roll >> 16) as usize % DESCRIPTIONS.len()].to_string(),
+        amount: ((roll >> 24) % 50_000) as f64 / 100.0 + 1.0,
+        date: String::new(),
+        entered_by: None,
+        deductible: false,
+        project: None,
+        foreign: None,
+        tags: Vec::new(),
+        parsed_date: Default::default(),
+      };
+      record.set_date(date);
+      record
+    })
+    .collect();
+  data.next_record_id = count + 1;
+
+  data
+}
+
+fn first_day() -> NaiveDate {
+  NaiveDate::from_ymd_opt(2021, 1, 1).expect("valid date")
+}
+
+/// xorshift64: fast, and good enough to vary made-up amounts
+fn next(seed: &mut u64) -> u64 {
+  *seed ^= *seed << 13;
+  *seed ^= *seed >> 7;
+  *seed ^= *seed << 17;
+  *seed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker_is_sorted_and_repeatable() {
+        let data = tracker(1000);
+        assert_eq!(data.records.len(), 1000);
+        assert_eq!(data.next_record_id, 1001);
+        assert!(data.records.is_sorted_by_key(Record::sort_key));
+        assert!(data.records.iter().all(|r| data.subcategory_name(r.subcategory).is_some()));
+        assert!(data.records.iter().all(|r| r.amount > 0.0));
+
+        let again = tracker(1000);
+        assert_eq!(data.records[500].amount, again.records[500].amount);
+    }
+}
@@ -0,0 +1,92 @@
+//! Access tokens for `fintrack serve`, kept in `tokens.json` next to the
+//! tracker. Only a hash of each token is stored, so the file can't be used to
+//! get in.
+
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{CliError, GlobalContext, t};
+
+/// Tokens start with this, so they're easy to spot in a config or a log
+const PREFIX: &str = "ft_";
+
+/// A token from `fintrack serve token create`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApiToken {
+  /// What it's for, e.g. `grafana`; `serve token revoke` finds it by this
+  pub name: String,
+  /// Hex SHA-256 of the token
+  pub hash: String,
+  /// RFC 3339
+  pub created_at: String,
+}
+
+/// The tokens in `tokens.json`, or none when there's no such file
+pub fn load(gctx: &GlobalContext) -> Result<Vec<ApiToken>, CliError> {
+  match fs::read(gctx.tokens_path()) {
+    Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+    Err(err) => Err(err.into()),
+  }
+}
+
+pub fn save(gctx: &GlobalContext, tokens: &[ApiToken]) -> Result<(), CliError> {
+  fs::write(gctx.tokens_path(), serde_json::to_vec_pretty(tokens)?)?;
+  Ok(())
+}
+
+/// A new random token: the prefix and 32 hex characters
+pub fn generate() -> Result<String, CliError> {
+  let mut bytes = [0u8; 16];
+  getrandom::fill(&mut bytes)
+    .map_err(|err| CliError::Other(t!("error-token-generate", reason = err.to_string())))?;
+  let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+  Ok(format!("{}{}", PREFIX, hex))
+}
+
+pub fn hash(token: &str) -> String {
+  format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// Whether an `Authorization` header value carries one of `tokens`
+pub fn authorized(tokens: &[ApiToken], authorization: Option<&str>) -> bool {
+  let Some(token) = authorization.and_then(|value| value.trim().strip_prefix("Bearer ")) else {
+    return false;
+  };
+  let hash = hash(token.trim());
+  tokens.iter().any(|t| t.hash == hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(name: &str, secret: &str) -> ApiToken {
+        ApiToken {
+            name: name.to_string(),
+            hash: hash(secret),
+            created_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_generate_gives_distinct_prefixed_tokens() {
+        let first = generate().unwrap();
+        let second = generate().unwrap();
+        assert!(first.starts_with("ft_"));
+        assert_eq!(first.len(), 35);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_authorized_needs_a_known_bearer_token() {
+        let tokens = vec![token("grafana", "ft_abc")];
+        assert!(authorized(&tokens, Some("Bearer ft_abc")));
+        assert!(!authorized(&tokens, Some("Bearer ft_abd")));
+        assert!(!authorized(&tokens, Some("ft_abc")));
+        assert!(!authorized(&tokens, None));
+        assert!(!authorized(&[], Some("Bearer ft_abc")));
+    }
+}
@@ -0,0 +1,240 @@
+use chrono::NaiveDate;
+
+use crate::{CliError, Currency, GlobalContext, Record, TrackerData, ValidationErrorKind};
+
+/// Decimal places amounts in the tracker's currency are kept to: the
+/// `input.decimals` setting, or the currency's own precision
+pub fn decimals(gctx: &GlobalContext, currency: &str) -> u32 {
+  gctx
+    .config()
+    .input
+    .decimals
+    .unwrap_or_else(|| currency.parse::<Currency>().map_or(2, |c| c.decimals()))
+}
+
+/// Bring a typed amount to the currency's precision using the
+/// `input.rounding` mode, or refuse it when that mode is `reject`
+pub fn round_amount(gctx: &GlobalContext, currency: &str, amount: f64) -> Result<f64, CliError> {
+  let decimals = decimals(gctx, currency);
+  gctx
+    .config()
+    .input
+    .rounding
+    .round(amount, decimals)
+    .ok_or_else(|| {
+      CliError::ValidationError(ValidationErrorKind::InvalidAmount {
+        reason: format!(
+          "{} has more than {} decimal place(s), the precision of {}",
+          amount, decimals, currency
+        ),
+      })
+    })
+}
+
+/// Refuse `record` if strict mode is on and it fails one of its checks:
+/// a date in the future, an empty description, an amount above
+/// `validation.max_amount`, or a tracker currency fintrack doesn't know.
+pub fn check_strict(
+  gctx: &GlobalContext,
+  record: &Record,
+  tracker_data: &TrackerData,
+) -> Result<(), CliError> {
+  if !gctx.is_strict() {
+    return Ok(());
+  }
+
+  strict_violation(record, tracker_data, gctx.config().validation.max_amount, gctx.today())
+    .map_or(Ok(()), |reason| {
+      Err(CliError::ValidationError(ValidationErrorKind::StrictCheckFailed { reason }))
+    })
+}
+
+/// Refuse a change to a record dated `date` if its month has been closed
+pub fn check_open(tracker_data: &TrackerData, date: Option<NaiveDate>) -> Result<(), CliError> {
+  match date.and_then(|date| tracker_data.closed_period(date)) {
+    Some(closed) => Err(CliError::ValidationError(ValidationErrorKind::PeriodClosed {
+      month: closed.month.clone(),
+    })),
+    None => Ok(()),
+  }
+}
+
+/// Refuse `record` if `validation.reject_exact_duplicates` is on and a record
+/// with the same date, amount, subcategory and description already exists
+pub fn check_duplicate(
+  gctx: &GlobalContext,
+  record: &Record,
+  tracker_data: &TrackerData,
+) -> Result<(), CliError> {
+  if !gctx.config().validation.reject_exact_duplicates {
+    return Ok(());
+  }
+
+  match find_duplicate(record, tracker_data) {
+    Some(existing) => Err(CliError::ValidationError(ValidationErrorKind::DuplicateRecord {
+      id: existing.id,
+    })),
+    None => Ok(()),
+  }
+}
+
+/// An existing record on the same day with the same amount, subcategory and
+/// description as `record`
+pub fn find_duplicate<'a>(record: &Record, tracker_data: &'a TrackerData) -> Option<&'a Record> {
+  let same_day = match record.naive_date() {
+    Some(date) => tracker_data.records_between(Some(date), Some(date)),
+    None => &tracker_data.records,
+  };
+
+  same_day.iter().find(|existing| {
+    existing.id != record.id
+      && existing.amount == record.amount
+      && existing.subcategory == record.subcategory
+      && existing.description == record.description
+      && (record.naive_date().is_some() || existing.date == record.date)
+  })
+}
+
+/// What's wrong with a tracker beyond its JSON shape, e.g. after it was
+/// edited by hand: records pointing at categories or subcategories that
+/// don't exist, clashing ids, bad dates. Empty when it's sound.
+pub fn integrity_problems(tracker_data: &TrackerData) -> Vec<String> {
+  let mut problems = Vec::new();
+
+  for category in ["income", "expenses"] {
+    if !tracker_data.categories.contains_key(category) {
+      problems.push(format!("the '{}' category is missing", category));
+    }
+  }
+  if tracker_data.miscellaneous_subcategory_id().is_none() {
+    problems.push("the miscellaneous subcategory is missing".to_string());
+  }
+  for (name, id) in &tracker_data.subcategories_by_name {
+    let matches = tracker_data
+      .subcategories_by_id
+      .get(id)
+      .is_some_and(|by_id| by_id.to_lowercase() == *name);
+    if !matches {
+      problems.push(format!(
+        "subcategories_by_name has '{}' as {}, which subcategories_by_id doesn't agree with",
+        name, id
+      ));
+    }
+  }
+  if tracker_data.subcategories_by_name.len() != tracker_data.subcategories_by_id.len() {
+    problems.push("subcategories_by_id and subcategories_by_name list different subcategories".to_string());
+  }
+
+  let mut ids = std::collections::HashSet::new();
+  for record in &tracker_data.records {
+    let id = record.id;
+    if !ids.insert(id) {
+      problems.push(format!("record id {} is used more than once", id));
+    }
+    if id >= tracker_data.next_record_id {
+      problems.push(format!("record {} isn't below next_record_id ({})", id, tracker_data.next_record_id));
+    }
+    if tracker_data.category_name(record.category).is_none() {
+      problems.push(format!("record {} has category {}, which doesn't exist", id, record.category));
+    }
+    if tracker_data.subcategory_name(record.subcategory).is_none() {
+      problems.push(format!("record {} has subcategory {}, which doesn't exist", id, record.subcategory));
+    }
+    if record.naive_date().is_none() {
+      problems.push(format!("record {} has '{}' as its date, not DD-MM-YYYY", id, record.date));
+    }
+    if !record.amount.is_finite() || record.amount <= 0.0 {
+      problems.push(format!("record {} has an amount of {}; amounts must be more than 0", id, record.amount));
+    }
+  }
+
+  for budget in &tracker_data.budgets {
+    if tracker_data.subcategory_name(budget.subcategory).is_none() {
+      problems.push(format!("a budget is for subcategory {}, which doesn't exist", budget.subcategory));
+    }
+  }
+  for schedule in &tracker_data.schedules {
+    if tracker_data.subcategory_id(&schedule.subcategory).is_none() {
+      problems.push(format!(
+        "schedule {} is for subcategory '{}', which doesn't exist",
+        schedule.id, schedule.subcategory
+      ));
+    }
+  }
+
+  problems
+}
+
+fn strict_violation(
+  record: &Record,
+  tracker_data: &TrackerData,
+  max_amount: Option<f64>,
+  today: NaiveDate,
+) -> Option<String> {
+  if let Some(date) = record.naive_date().filter(|date| *date > today) {
+    return Some(format!("{} is in the future", date.format("%d-%m-%Y")));
+  }
+  if record.description.trim().is_empty() {
+    return Some("the description is empty".to_string());
+  }
+  if let Some(max) = max_amount.filter(|max| record.amount > *max) {
+    return Some(format!("{} is above the limit of {}", record.amount, max));
+  }
+  if tracker_data.currency.parse::<Currency>().is_err() {
+    return Some(format!("'{}' is not a known currency", tracker_data.currency));
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default_tracker_json;
+
+    fn record(amount: f64, description: &str, date: &str) -> Record {
+        Record {
+            id: 1,
+            category: 1,
+            amount,
+            subcategory: 1,
+            description: description.to_string(),
+            date: date.to_string(),
+            entered_by: None,
+            deductible: false,
+            project: None,
+            foreign: None,
+            tags: Vec::new(),
+            parsed_date: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_strict_violation() {
+        let mut data: TrackerData =
+            serde_json::from_value(default_tracker_json(&Currency::USD, 0.0)).unwrap();
+        let today = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+
+        assert_eq!(strict_violation(&record(50.0, "Lunch", "01-01-2025"), &data, Some(100.0), today), None);
+        assert!(strict_violation(&record(50.0, "Lunch", "01-01-2999"), &data, None, today).is_some());
+        assert!(strict_violation(&record(50.0, "  ", "01-01-2025"), &data, None, today).is_some());
+        assert!(strict_violation(&record(500.0, "Rent", "01-01-2025"), &data, Some(100.0), today).is_some());
+
+        data.currency = "XYZ".to_string();
+        assert!(strict_violation(&record(50.0, "Lunch", "01-01-2025"), &data, None, today).is_some());
+    }
+
+    #[test]
+    fn test_find_duplicate() {
+        let mut data: TrackerData =
+            serde_json::from_value(default_tracker_json(&Currency::USD, 0.0)).unwrap();
+        data.push_record(record(50.0, "Lunch", "01-01-2025"));
+
+        let mut again = record(50.0, "Lunch", "01-01-2025");
+        again.id = 2;
+        assert_eq!(find_duplicate(&again, &data).map(|r| r.id), Some(1));
+
+        again.description = "Dinner".to_string();
+        assert!(find_duplicate(&again, &data).is_none());
+        assert!(find_duplicate(&record(50.0, "Lunch", "02-01-2025"), &data).is_none());
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde_json::json;
+
+use crate::utils::storage::{AfterSave, after_save};
+use crate::{GlobalContext, Record, TrackerData, output::record_json};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A change to the tracker that is reported to the webhook
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Event {
+  Add,
+  Update,
+  Delete,
+}
+
+/// POST a JSON description of a change to `webhook.url`, if one is configured,
+/// once the change is saved: straight away, or at the end of a `batch` or
+/// `shell` session.
+pub fn notify(gctx: &mut GlobalContext, event: Event, records: &[Record], tracker_data: &TrackerData) {
+  if gctx.config().webhook.url.is_none() {
+    return;
+  }
+
+  let payload = json!({
+    "event": event.to_string(),
+    "records": records
+      .iter()
+      .map(|record| record_json(record, tracker_data))
+      .collect::<Vec<_>>(),
+    "balance": tracker_data.net_balance(),
+    "budgets": budgets_json(records, tracker_data),
+    "currency": tracker_data.currency,
+    "timestamp": tracker_data.last_modified,
+  });
+  after_save(gctx, AfterSave::Webhook(event, payload));
+}
+
+/// How the budgets of `records`' subcategories stand in the months the
+/// records are dated in
+fn budgets_json(records: &[Record], tracker_data: &TrackerData) -> Vec<serde_json::Value> {
+  let mut months: BTreeMap<NaiveDate, BTreeSet<&String>> = BTreeMap::new();
+  for record in records {
+    let name = tracker_data.subcategory_name(record.subcategory);
+    let (Some(date), Some(name)) = (record.naive_date(), name) else {
+      continue;
+    };
+    months.entry(date.with_day(1).expect("day 1 exists")).or_default().insert(name);
+  }
+
+  months
+    .into_iter()
+    .flat_map(|(month_start, names)| {
+      tracker_data
+        .budget_statuses(month_start, month_start + Months::new(1) - Days::new(1))
+        .into_iter()
+        .filter(move |status| names.contains(&status.subcategory))
+        .map(move |status| {
+          let mut value = json!(status);
+          value["month"] = json!(month_start.format("%m-%Y").to_string());
+          value
+        })
+    })
+    .collect()
+}
+
+/// Send a change made by [`notify`]. The change has already been saved by the
+/// time this runs, so a webhook that can't be reached is logged rather than
+/// failing the command.
+pub(crate) fn send(gctx: &GlobalContext, event: Event, payload: &serde_json::Value) {
+  let Some(url) = gctx.config().webhook.url.as_deref() else {
+    return;
+  };
+  if gctx.is_offline() {
+    gctx.verbose(format!("Not sending '{}' to the webhook while offline", event));
+    return;
+  }
+  if gctx.is_dry_run() {
+    gctx.verbose(format!("Dry run: not sending '{}' to the webhook", event));
+    return;
+  }
+
+  let _span = tracing::info_span!("webhook", %url, %event).entered();
+  gctx.verbose(format!("Sending '{}' to webhook {}", event, url));
+
+  let result = ureq::post(url)
+    .timeout(TIMEOUT)
+    .set("Content-Type", "application/json")
+    .send_string(&payload.to_string());
+
+  match result {
+    Ok(response) => tracing::debug!(status = response.status(), "webhook delivered"),
+    Err(err) => {
+      tracing::warn!(error = %err, "webhook failed");
+      gctx.verbose(format!("Webhook failed: {}", err));
+    }
+  }
+}
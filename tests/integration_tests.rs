@@ -1,6 +1,6 @@
 mod common;
 
-use common::TestContext;
+use common::{TestContext, capture_http};
 use fintrack::*;
 use fintrack::commands;
 use std::fs;
@@ -8,7 +8,7 @@ use std::fs;
 #[test]
 fn test_init_creates_tracker_file() {
     let mut ctx = TestContext::new();
-    let args = commands::init::cli().get_matches_from(&["init", "--currency", "usd", "--opening", "1000.0"]);
+    let args = commands::init::cli().get_matches_from(["init", "--currency", "usd", "--opening", "1000.0"]);
 
     let result = commands::init::exec(ctx.gctx_mut(), &args);
     assert!(result.is_ok());
@@ -27,7 +27,7 @@ fn test_init_creates_tracker_file() {
 #[test]
 fn test_init_with_defaults() {
     let mut ctx = TestContext::new();
-    let args = commands::init::cli().get_matches_from(&["init"]);
+    let args = commands::init::cli().get_matches_from(["init"]);
 
     let result = commands::init::exec(ctx.gctx_mut(), &args);
     assert!(result.is_ok());
@@ -42,8 +42,8 @@ fn test_init_with_defaults() {
 #[test]
 fn test_init_fails_when_file_exists() {
     let mut ctx = TestContext::new();
-    let args1 = commands::init::cli().get_matches_from(&["init"]);
-    let args2 = commands::init::cli().get_matches_from(&["init"]);
+    let args1 = commands::init::cli().get_matches_from(["init"]);
+    let args2 = commands::init::cli().get_matches_from(["init"]);
 
     let result1 = commands::init::exec(ctx.gctx_mut(), &args1);
     assert!(result1.is_ok());
@@ -57,10 +57,10 @@ fn test_init_fails_when_file_exists() {
 fn test_add_record() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args = commands::add::cli().get_matches_from(&["add", "income", "500.0", "--subcategory", "miscellaneous"]);
+    let add_args = commands::add::cli().get_matches_from(["add", "income", "500.0", "--subcategory", "miscellaneous"]);
     let result = commands::add::exec(ctx.gctx_mut(), &add_args);
 
     assert!(result.is_ok());
@@ -78,10 +78,10 @@ fn test_add_record() {
 fn test_add_record_with_all_fields() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args = commands::add::cli().get_matches_from(&[
+    let add_args = commands::add::cli().get_matches_from([
         "add",
         "expenses",
         "100.50",
@@ -110,10 +110,10 @@ fn test_add_record_with_all_fields() {
 fn test_add_record_rejects_zero_amount() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args = commands::add::cli().get_matches_from(&["add", "income", "0.0"]);
+    let add_args = commands::add::cli().get_matches_from(["add", "income", "0.0"]);
     let result = commands::add::exec(ctx.gctx_mut(), &add_args);
 
     assert!(result.is_err());
@@ -127,10 +127,10 @@ fn test_add_record_rejects_zero_amount() {
 fn test_add_record_rejects_negative_amount() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args = commands::add::cli().get_matches_from(&["add", "income", "--", "-100.0"]);
+    let add_args = commands::add::cli().get_matches_from(["add", "income", "--", "-100.0"]);
     let result = commands::add::exec(ctx.gctx_mut(), &add_args);
 
     assert!(result.is_err());
@@ -144,10 +144,10 @@ fn test_add_record_rejects_negative_amount() {
 fn test_add_record_rejects_invalid_subcategory() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args = commands::add::cli().get_matches_from(&["add", "income", "100.0", "--subcategory", "nonexistent"]);
+    let add_args = commands::add::cli().get_matches_from(["add", "income", "100.0", "--subcategory", "nonexistent"]);
     let result = commands::add::exec(ctx.gctx_mut(), &add_args);
 
     assert!(result.is_err());
@@ -161,18 +161,18 @@ fn test_add_record_rejects_invalid_subcategory() {
 fn test_list_all_records() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add1 = commands::add::cli().get_matches_from(&["add", "income", "100.0", "--date", "01-01-2025"]);
-    let add2 = commands::add::cli().get_matches_from(&["add", "expenses", "50.0", "--date", "02-01-2025"]);
-    let add3 = commands::add::cli().get_matches_from(&["add", "income", "200.0", "--date", "03-01-2025"]);
+    let add1 = commands::add::cli().get_matches_from(["add", "income", "100.0", "--date", "01-01-2025"]);
+    let add2 = commands::add::cli().get_matches_from(["add", "expenses", "50.0", "--date", "02-01-2025"]);
+    let add3 = commands::add::cli().get_matches_from(["add", "income", "200.0", "--date", "03-01-2025"]);
 
     commands::add::exec(ctx.gctx_mut(), &add1).unwrap();
     commands::add::exec(ctx.gctx_mut(), &add2).unwrap();
     commands::add::exec(ctx.gctx_mut(), &add3).unwrap();
 
-    let list_args = commands::list::cli().get_matches_from(&["list"]);
+    let list_args = commands::list::cli().get_matches_from(["list"]);
     let result = commands::list::exec(ctx.gctx_mut(), &list_args);
 
     assert!(result.is_ok());
@@ -196,18 +196,18 @@ fn test_list_all_records() {
 fn test_list_filter_by_category() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add1 = commands::add::cli().get_matches_from(&["add", "income", "100.0"]);
-    let add2 = commands::add::cli().get_matches_from(&["add", "expenses", "50.0"]);
-    let add3 = commands::add::cli().get_matches_from(&["add", "income", "200.0"]);
+    let add1 = commands::add::cli().get_matches_from(["add", "income", "100.0"]);
+    let add2 = commands::add::cli().get_matches_from(["add", "expenses", "50.0"]);
+    let add3 = commands::add::cli().get_matches_from(["add", "income", "200.0"]);
 
     commands::add::exec(ctx.gctx_mut(), &add1).unwrap();
     commands::add::exec(ctx.gctx_mut(), &add2).unwrap();
     commands::add::exec(ctx.gctx_mut(), &add3).unwrap();
 
-    let list_args = commands::list::cli().get_matches_from(&["list", "--category", "income"]);
+    let list_args = commands::list::cli().get_matches_from(["list", "--category", "income"]);
     let result = commands::list::exec(ctx.gctx_mut(), &list_args);
 
     assert!(result.is_ok());
@@ -225,18 +225,52 @@ fn test_list_filter_by_category() {
     }
 }
 
+#[test]
+fn test_list_filter_by_user() {
+    let mut ctx = TestContext::new();
+
+    let init_args = commands::init::cli().get_matches_from(["init"]);
+    commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
+
+    let add1 = commands::add::cli().get_matches_from(["add", "income", "100.0", "--by", "Ada"]);
+    let add2 = commands::add::cli().get_matches_from(["add", "expenses", "50.0", "--by", "Sam"]);
+    let add3 = commands::add::cli().get_matches_from(["add", "expenses", "20.0", "--by", "Ada"]);
+
+    commands::add::exec(ctx.gctx_mut(), &add1).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &add2).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &add3).unwrap();
+
+    let list_args = commands::list::cli().get_matches_from(["list", "--by-user", "ada"]);
+    let response = commands::list::exec(ctx.gctx_mut(), &list_args).unwrap();
+    let Some(ResponseContent::List { records, .. }) = response.content() else {
+        panic!("Expected List response");
+    };
+    assert_eq!(records.len(), 2);
+    assert!(records.iter().all(|r| r.entered_by.as_deref() == Some("Ada")));
+
+    let describe_args = commands::describe::cli().get_matches_from(["describe"]);
+    let response = commands::describe::exec(ctx.gctx_mut(), &describe_args).unwrap();
+    let Some(ResponseContent::Describe(data)) = response.content() else {
+        panic!("Expected Describe response");
+    };
+    assert_eq!(
+        data.by_user,
+        vec![("Ada".to_string(), 2, 120.0), ("Sam".to_string(), 1, 50.0)]
+    );
+}
+
 #[test]
 fn test_list_filter_by_date_range() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "100.0", "--date", "01-01-2025"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "expenses", "50.0", "--date", "05-01-2025"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "200.0", "--date", "10-01-2025"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "100.0", "--date", "01-01-2025"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "expenses", "50.0", "--date", "05-01-2025"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "200.0", "--date", "10-01-2025"])).unwrap();
 
-    let list_args = commands::list::cli().get_matches_from(&["list", "--start", "03-01-2025", "--end", "07-01-2025"]);
+    let list_args = commands::list::cli().get_matches_from(["list", "--start", "03-01-2025", "--end", "07-01-2025"]);
     let result = commands::list::exec(ctx.gctx_mut(), &list_args);
 
     assert!(result.is_ok());
@@ -257,16 +291,16 @@ fn test_list_filter_by_date_range() {
 fn test_list_first_n_records() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     for i in 1..=5 {
         let date = format!("{:02}-01-2025", i);
-        let add_args = commands::add::cli().get_matches_from(&["add", "income", "100.0", "--date", &date]);
+        let add_args = commands::add::cli().get_matches_from(["add", "income", "100.0", "--date", &date]);
         commands::add::exec(ctx.gctx_mut(), &add_args).unwrap();
     }
 
-    let list_args = commands::list::cli().get_matches_from(&["list", "--first", "3"]);
+    let list_args = commands::list::cli().get_matches_from(["list", "--first", "3"]);
     let result = commands::list::exec(ctx.gctx_mut(), &list_args);
 
     assert!(result.is_ok());
@@ -286,16 +320,16 @@ fn test_list_first_n_records() {
 fn test_list_last_n_records() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     for i in 1..=5 {
         let date = format!("{:02}-01-2025", i);
-        let add_args = commands::add::cli().get_matches_from(&["add", "income", "100.0", "--date", &date]);
+        let add_args = commands::add::cli().get_matches_from(["add", "income", "100.0", "--date", &date]);
         commands::add::exec(ctx.gctx_mut(), &add_args).unwrap();
     }
 
-    let list_args = commands::list::cli().get_matches_from(&["list", "--last", "2"]);
+    let list_args = commands::list::cli().get_matches_from(["list", "--last", "2"]);
     let result = commands::list::exec(ctx.gctx_mut(), &list_args);
 
     assert!(result.is_ok());
@@ -311,16 +345,41 @@ fn test_list_last_n_records() {
     }
 }
 
+#[test]
+fn test_list_default_limit_applies_without_first_or_last() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    for i in 1..=5 {
+        commands::exec_line(ctx.gctx_mut(), &format!("add income {} --date {:02}-01-2025", i, i)).unwrap();
+    }
+    commands::exec_line(ctx.gctx_mut(), "config set list.default_limit 2").unwrap();
+
+    let amounts = |ctx: &mut TestContext, line: &str| -> Vec<f64> {
+        match commands::exec_line(ctx.gctx_mut(), line).unwrap().content() {
+            Some(ResponseContent::List { records, .. }) => records.iter().map(|r| r.amount).collect(),
+            other => panic!("Expected List response, got {:?}", other),
+        }
+    };
+
+    assert_eq!(amounts(&mut ctx, "list"), vec![4.0, 5.0]);
+    assert_eq!(amounts(&mut ctx, "list --first 3"), vec![1.0, 2.0, 3.0]);
+    assert_eq!(amounts(&mut ctx, "list --last 0").len(), 5);
+
+    commands::exec_line(ctx.gctx_mut(), "config set list.default_order oldest").unwrap();
+    assert_eq!(amounts(&mut ctx, "list"), vec![1.0, 2.0]);
+    assert!(commands::exec_line(ctx.gctx_mut(), "config set list.default_order sideways").is_err());
+}
+
 #[test]
 fn test_update_record() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "100.0"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "100.0"])).unwrap();
 
-    let update_args = commands::update::cli().get_matches_from(&["update", "1", "--amount", "150.0"]);
+    let update_args = commands::update::cli().get_matches_from(["update", "1", "--amount", "150.0"]);
     let result = commands::update::exec(ctx.gctx_mut(), &update_args);
 
     assert!(result.is_ok());
@@ -335,10 +394,10 @@ fn test_update_record() {
 fn test_update_record_not_found() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let update_args = commands::update::cli().get_matches_from(&["update", "999", "--amount", "100.0"]);
+    let update_args = commands::update::cli().get_matches_from(["update", "999", "--amount", "100.0"]);
     let result = commands::update::exec(ctx.gctx_mut(), &update_args);
 
     assert!(result.is_err());
@@ -352,12 +411,12 @@ fn test_update_record_not_found() {
 fn test_update_record_rejects_zero_amount() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "100.0"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "100.0"])).unwrap();
 
-    let update_args = commands::update::cli().get_matches_from(&["update", "1", "--amount", "0.0"]);
+    let update_args = commands::update::cli().get_matches_from(["update", "1", "--amount", "0.0"]);
     let result = commands::update::exec(ctx.gctx_mut(), &update_args);
 
     assert!(result.is_err());
@@ -371,15 +430,15 @@ fn test_update_record_rejects_zero_amount() {
 fn test_delete_by_ids() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     for _i in 1..=5 {
-        let add_args = commands::add::cli().get_matches_from(&["add", "income", "100.0"]);
+        let add_args = commands::add::cli().get_matches_from(["add", "income", "100.0"]);
         commands::add::exec(ctx.gctx_mut(), &add_args).unwrap();
     }
 
-    let delete_args = commands::delete::cli().get_matches_from(&["delete", "--ids", "1,3,5"]);
+    let delete_args = commands::delete::cli().get_matches_from(["delete", "--ids", "1,3,5"]);
     let result = commands::delete::exec(ctx.gctx_mut(), &delete_args);
 
     assert!(result.is_ok());
@@ -396,14 +455,14 @@ fn test_delete_by_ids() {
 fn test_delete_by_category() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "100.0"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "expenses", "50.0"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "200.0"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "100.0"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "expenses", "50.0"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "200.0"])).unwrap();
 
-    let delete_args = commands::delete::cli().get_matches_from(&["delete", "--by-cat", "income"]);
+    let delete_args = commands::delete::cli().get_matches_from(["delete", "--by-cat", "income"]);
     let result = commands::delete::exec(ctx.gctx_mut(), &delete_args);
 
     assert!(result.is_ok());
@@ -419,14 +478,14 @@ fn test_delete_by_category() {
 fn test_total_calculation() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init", "--opening", "1000.0"]);
+    let init_args = commands::init::cli().get_matches_from(["init", "--opening", "1000.0"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "500.0"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "expenses", "200.0"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "300.0"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "500.0"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "expenses", "200.0"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "300.0"])).unwrap();
 
-    let total_args = commands::total::cli().get_matches_from(&["total"]);
+    let total_args = commands::total::cli().get_matches_from(["total"]);
     let result = commands::total::exec(ctx.gctx_mut(), &total_args);
 
     assert!(result.is_ok());
@@ -449,10 +508,10 @@ fn test_total_calculation() {
 fn test_subcategory_add() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args = commands::subcategory::add::cli().get_matches_from(&["add", "Groceries"]);
+    let add_args = commands::subcategory::add::cli().get_matches_from(["add", "Groceries"]);
     let result = commands::subcategory::add::exec(ctx.gctx_mut(), &add_args);
 
     assert!(result.is_ok());
@@ -468,11 +527,11 @@ fn test_subcategory_add() {
 fn test_subcategory_add_rejects_duplicate() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args1 = commands::subcategory::add::cli().get_matches_from(&["add", "Groceries"]);
-    let add_args2 = commands::subcategory::add::cli().get_matches_from(&["add", "groceries"]); // Case-insensitive duplicate
+    let add_args1 = commands::subcategory::add::cli().get_matches_from(["add", "Groceries"]);
+    let add_args2 = commands::subcategory::add::cli().get_matches_from(["add", "groceries"]); // Case-insensitive duplicate
 
     commands::subcategory::add::exec(ctx.gctx_mut(), &add_args1).unwrap();
     let result = commands::subcategory::add::exec(ctx.gctx_mut(), &add_args2);
@@ -488,10 +547,10 @@ fn test_subcategory_add_rejects_duplicate() {
 fn test_subcategory_add_rejects_miscellaneous() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args = commands::subcategory::add::cli().get_matches_from(&["add", "Miscellaneous"]);
+    let add_args = commands::subcategory::add::cli().get_matches_from(["add", "Miscellaneous"]);
     let result = commands::subcategory::add::exec(ctx.gctx_mut(), &add_args);
 
     assert!(result.is_err());
@@ -505,16 +564,16 @@ fn test_subcategory_add_rejects_miscellaneous() {
 fn test_subcategory_list() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args1 = commands::subcategory::add::cli().get_matches_from(&["add", "Groceries"]);
-    let add_args2 = commands::subcategory::add::cli().get_matches_from(&["add", "Salary"]);
+    let add_args1 = commands::subcategory::add::cli().get_matches_from(["add", "Groceries"]);
+    let add_args2 = commands::subcategory::add::cli().get_matches_from(["add", "Salary"]);
 
     commands::subcategory::add::exec(ctx.gctx_mut(), &add_args1).unwrap();
     commands::subcategory::add::exec(ctx.gctx_mut(), &add_args2).unwrap();
 
-    let list_args = commands::subcategory::list::cli().get_matches_from(&["list"]);
+    let list_args = commands::subcategory::list::cli().get_matches_from(["list"]);
     let result = commands::subcategory::list::exec(ctx.gctx_mut(), &list_args);
 
     assert!(result.is_ok());
@@ -537,13 +596,13 @@ fn test_subcategory_list() {
 fn test_subcategory_delete() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args = commands::subcategory::add::cli().get_matches_from(&["add", "Groceries"]);
+    let add_args = commands::subcategory::add::cli().get_matches_from(["add", "Groceries"]);
     commands::subcategory::add::exec(ctx.gctx_mut(), &add_args).unwrap();
 
-    let delete_args = commands::subcategory::delete::cli().get_matches_from(&["delete", "Groceries"]);
+    let delete_args = commands::subcategory::delete::cli().get_matches_from(["delete", "Groceries"]);
     let result = commands::subcategory::delete::exec(ctx.gctx_mut(), &delete_args);
 
     assert!(result.is_ok());
@@ -558,10 +617,10 @@ fn test_subcategory_delete() {
 fn test_subcategory_delete_rejects_miscellaneous() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let delete_args = commands::subcategory::delete::cli().get_matches_from(&["delete", "Miscellaneous"]);
+    let delete_args = commands::subcategory::delete::cli().get_matches_from(["delete", "Miscellaneous"]);
     let result = commands::subcategory::delete::exec(ctx.gctx_mut(), &delete_args);
 
     assert!(result.is_err());
@@ -575,16 +634,16 @@ fn test_subcategory_delete_rejects_miscellaneous() {
 fn test_subcategory_delete_rejects_when_has_records() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_sub = commands::subcategory::add::cli().get_matches_from(&["add", "Groceries"]);
+    let add_sub = commands::subcategory::add::cli().get_matches_from(["add", "Groceries"]);
     commands::subcategory::add::exec(ctx.gctx_mut(), &add_sub).unwrap();
 
-    let add_rec = commands::add::cli().get_matches_from(&["add", "expenses", "100.0", "--subcategory", "groceries"]);
+    let add_rec = commands::add::cli().get_matches_from(["add", "expenses", "100.0", "--subcategory", "groceries"]);
     commands::add::exec(ctx.gctx_mut(), &add_rec).unwrap();
 
-    let delete_args = commands::subcategory::delete::cli().get_matches_from(&["delete", "Groceries"]);
+    let delete_args = commands::subcategory::delete::cli().get_matches_from(["delete", "Groceries"]);
     let result = commands::subcategory::delete::exec(ctx.gctx_mut(), &delete_args);
 
     assert!(result.is_err());
@@ -598,13 +657,13 @@ fn test_subcategory_delete_rejects_when_has_records() {
 fn test_subcategory_rename() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add_args = commands::subcategory::add::cli().get_matches_from(&["add", "Groceries"]);
+    let add_args = commands::subcategory::add::cli().get_matches_from(["add", "Groceries"]);
     commands::subcategory::add::exec(ctx.gctx_mut(), &add_args).unwrap();
 
-    let rename_args = commands::subcategory::rename::cli().get_matches_from(&["rename", "Groceries", "Food"]);
+    let rename_args = commands::subcategory::rename::cli().get_matches_from(["rename", "Groceries", "Food"]);
     let result = commands::subcategory::rename::exec(ctx.gctx_mut(), &rename_args);
 
     assert!(result.is_ok());
@@ -621,16 +680,16 @@ fn test_subcategory_rename() {
 fn test_subcategory_rename_rejects_duplicate() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let add1 = commands::subcategory::add::cli().get_matches_from(&["add", "Groceries"]);
-    let add2 = commands::subcategory::add::cli().get_matches_from(&["add", "Food"]);
+    let add1 = commands::subcategory::add::cli().get_matches_from(["add", "Groceries"]);
+    let add2 = commands::subcategory::add::cli().get_matches_from(["add", "Food"]);
 
     commands::subcategory::add::exec(ctx.gctx_mut(), &add1).unwrap();
     commands::subcategory::add::exec(ctx.gctx_mut(), &add2).unwrap();
 
-    let rename_args = commands::subcategory::rename::cli().get_matches_from(&["rename", "Groceries", "Food"]);
+    let rename_args = commands::subcategory::rename::cli().get_matches_from(["rename", "Groceries", "Food"]);
     let result = commands::subcategory::rename::exec(ctx.gctx_mut(), &rename_args);
 
     assert!(result.is_err());
@@ -648,16 +707,16 @@ fn test_subcategory_rename_rejects_duplicate() {
 fn test_export_to_json() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init", "--currency", "usd", "--opening", "1000.0"]);
+    let init_args = commands::init::cli().get_matches_from(["init", "--currency", "usd", "--opening", "1000.0"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Add some test data
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "500.0", "--description", "Salary"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "expenses", "100.0", "--description", "Food"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "500.0", "--description", "Salary"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "expenses", "100.0", "--description", "Food"])).unwrap();
 
     // Export to JSON
     let export_path = ctx.temp_dir.path().to_path_buf();
-    let export_args = commands::export::cli().get_matches_from(&["export", export_path.to_str().unwrap(), "--type", "json"]);
+    let export_args = commands::export::cli().get_matches_from(["export", export_path.to_str().unwrap(), "--type", "json"]);
     let result = commands::export::exec(ctx.gctx_mut(), &export_args);
 
     assert!(result.is_ok());
@@ -684,16 +743,16 @@ fn test_export_to_json() {
 fn test_export_to_csv() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Add test data with special characters in description
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "250.50", "--description", "Test, with \"quotes\" and commas"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "expenses", "75.25", "--description", "Normal description"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "250.50", "--description", "Test, with \"quotes\" and commas"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "expenses", "75.25", "--description", "Normal description"])).unwrap();
 
     // Export to CSV
     let export_path = ctx.temp_dir.path().to_path_buf();
-    let export_args = commands::export::cli().get_matches_from(&["export", export_path.to_str().unwrap(), "--type", "csv"]);
+    let export_args = commands::export::cli().get_matches_from(["export", export_path.to_str().unwrap(), "--type", "csv"]);
     let result = commands::export::exec(ctx.gctx_mut(), &export_args);
 
     assert!(result.is_ok());
@@ -722,11 +781,11 @@ fn test_export_to_csv() {
 fn test_export_invalid_path() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Try to export to non-existent directory
-    let export_args = commands::export::cli().get_matches_from(&["export", "/nonexistent/path/that/does/not/exist", "--type", "json"]);
+    let export_args = commands::export::cli().get_matches_from(["export", "/nonexistent/path/that/does/not/exist", "--type", "json"]);
     let result = commands::export::exec(ctx.gctx_mut(), &export_args);
 
     assert!(result.is_err());
@@ -736,7 +795,7 @@ fn test_export_invalid_path() {
 fn test_export_path_is_file() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Create a file in temp directory
@@ -744,7 +803,7 @@ fn test_export_path_is_file() {
     fs::write(&file_path, "test").unwrap();
 
     // Try to export to a file instead of directory
-    let export_args = commands::export::cli().get_matches_from(&["export", file_path.to_str().unwrap(), "--type", "json"]);
+    let export_args = commands::export::cli().get_matches_from(["export", file_path.to_str().unwrap(), "--type", "json"]);
     let result = commands::export::exec(ctx.gctx_mut(), &export_args);
 
     assert!(result.is_err());
@@ -758,10 +817,10 @@ fn test_export_path_is_file() {
 fn test_describe_empty_tracker() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let describe_args = commands::describe::cli().get_matches_from(&["describe"]);
+    let describe_args = commands::describe::cli().get_matches_from(["describe"]);
     let result = commands::describe::exec(ctx.gctx_mut(), &describe_args);
 
     assert!(result.is_ok());
@@ -781,15 +840,15 @@ fn test_describe_empty_tracker() {
 fn test_describe_with_data() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init", "--currency", "usd"]);
+    let init_args = commands::init::cli().get_matches_from(["init", "--currency", "usd"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Add various records
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "1000.0", "--date", "01-01-2025"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "expenses", "200.0", "--date", "15-01-2025"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "500.0", "--date", "20-01-2025"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "1000.0", "--date", "01-01-2025"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "expenses", "200.0", "--date", "15-01-2025"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "500.0", "--date", "20-01-2025"])).unwrap();
 
-    let describe_args = commands::describe::cli().get_matches_from(&["describe"]);
+    let describe_args = commands::describe::cli().get_matches_from(["describe"]);
     let result = commands::describe::exec(ctx.gctx_mut(), &describe_args);
 
     assert!(result.is_ok());
@@ -807,26 +866,51 @@ fn test_describe_with_data() {
             assert!((data.average_transaction - 566.67).abs() < 0.1);
 
             // Verify category breakdown
-            assert!(data.by_category.len() > 0);
+            assert!(!data.by_category.is_empty());
         } else {
             panic!("Expected Describe response");
         }
     }
 }
 
+#[test]
+fn test_describe_breaks_expenses_down_by_weekday() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+
+    // Wednesday, Saturday and Sunday; income doesn't count
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 --date 15-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 50 --date 18-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 10 --date 19-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 900 --date 15-01-2025").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "describe").unwrap();
+    let Some(ResponseContent::Describe(data)) = response.content() else {
+        panic!("Expected Describe response");
+    };
+
+    assert_eq!(data.by_weekday.len(), 7);
+    assert_eq!(data.by_weekday[0].0, DayOfWeek::Monday);
+    assert_eq!(data.by_weekday[2], (DayOfWeek::Wednesday, 1, 20.0));
+    assert_eq!(data.by_weekday[5], (DayOfWeek::Saturday, 1, 50.0));
+    assert_eq!(data.by_weekday[6], (DayOfWeek::Sunday, 1, 10.0));
+    let weekend: f64 = data.by_weekday.iter().filter(|(day, _, _)| day.is_weekend()).map(|(_, _, total)| total).sum();
+    assert_eq!(weekend, 60.0);
+}
+
 #[test]
 fn test_describe_date_range() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Add records with different dates
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "100.0", "--date", "10-03-2024"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "expenses", "50.0", "--date", "05-01-2025"])).unwrap();
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "75.0", "--date", "20-12-2023"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "100.0", "--date", "10-03-2024"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "expenses", "50.0", "--date", "05-01-2025"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "75.0", "--date", "20-12-2023"])).unwrap();
 
-    let describe_args = commands::describe::cli().get_matches_from(&["describe"]);
+    let describe_args = commands::describe::cli().get_matches_from(["describe"]);
     let result = commands::describe::exec(ctx.gctx_mut(), &describe_args);
 
     assert!(result.is_ok());
@@ -852,18 +936,18 @@ fn test_describe_date_range() {
 fn test_dump_tracker_data() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init", "--currency", "gbp", "--opening", "500.0"]);
+    let init_args = commands::init::cli().get_matches_from(["init", "--currency", "gbp", "--opening", "500.0"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "100.0"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "100.0"])).unwrap();
 
-    let dump_args = commands::dump::cli().get_matches_from(&["dump"]);
+    let dump_args = commands::dump::cli().get_matches_from(["dump"]);
     let result = commands::dump::exec(ctx.gctx_mut(), &dump_args);
 
     assert!(result.is_ok());
 
     if let Ok(response) = result {
-        if let Some(ResponseContent::TrackerData(data)) = response.content() {
+        if let Some(ResponseContent::TrackerData { tracker_data: data, .. }) = response.content() {
             assert_eq!(data.currency, "GBP");
             assert_eq!(data.opening_balance, 500.0);
             assert_eq!(data.records.len(), 1);
@@ -873,6 +957,32 @@ fn test_dump_tracker_data() {
     }
 }
 
+#[test]
+fn test_dump_since_meta_only_and_out() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 5 --date 01-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 7 --date 01-03-2025").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "dump --records-only --since 01-02-2025").unwrap();
+    match response.content() {
+        Some(ResponseContent::TrackerData { tracker_data, part, .. }) => {
+            assert_eq!(*part, DumpPart::Records);
+            assert_eq!(tracker_data.records.len(), 1);
+            assert_eq!(tracker_data.records[0].amount, 7.0);
+        }
+        other => panic!("Expected TrackerData response, got {:?}", other),
+    }
+
+    let out = ctx.gctx.base_path().join("meta.json");
+    commands::exec_line(ctx.gctx_mut(), &format!("dump --meta-only --out {}", out.display())).unwrap();
+    let meta: serde_json::Value = serde_json::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+    assert_eq!(meta["currency"], "USD");
+    assert!(meta.get("records").is_none());
+
+    assert!(commands::exec_line(ctx.gctx_mut(), "dump --meta-only --records-only").is_err());
+}
+
 // ============================================================================
 // CATEGORY COMMAND TESTS
 // ============================================================================
@@ -881,10 +991,10 @@ fn test_dump_tracker_data() {
 fn test_category_list() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
-    let category_args = commands::category::list::cli().get_matches_from(&["list"]);
+    let category_args = commands::category::list::cli().get_matches_from(["list"]);
     let result = commands::category::list::exec(ctx.gctx_mut(), &category_args);
 
     assert!(result.is_ok());
@@ -914,11 +1024,11 @@ fn test_add_command_on_uninitialized_tracker() {
     let mut ctx = TestContext::new();
     // Do NOT initialize tracker
 
-    let add_args = commands::add::cli().get_matches_from(&["add", "income", "100.0"]);
+    let add_args = commands::add::cli().get_matches_from(["add", "income", "100.0"]);
     let result = commands::add::exec(ctx.gctx_mut(), &add_args);
 
     assert!(result.is_err());
-    assert!(matches!(result.unwrap_err(), CliError::FileNotFound(_)));
+    assert!(matches!(result.unwrap_err(), CliError::NotInitialized));
 }
 
 #[test]
@@ -926,11 +1036,11 @@ fn test_list_command_on_uninitialized_tracker() {
     let mut ctx = TestContext::new();
     // Do NOT initialize tracker
 
-    let list_args = commands::list::cli().get_matches_from(&["list"]);
+    let list_args = commands::list::cli().get_matches_from(["list"]);
     let result = commands::list::exec(ctx.gctx_mut(), &list_args);
 
     assert!(result.is_err());
-    assert!(matches!(result.unwrap_err(), CliError::FileNotFound(_)));
+    assert!(matches!(result.unwrap_err(), CliError::NotInitialized));
 }
 
 #[test]
@@ -938,22 +1048,22 @@ fn test_total_command_on_uninitialized_tracker() {
     let mut ctx = TestContext::new();
     // Do NOT initialize tracker
 
-    let total_args = commands::total::cli().get_matches_from(&["total"]);
+    let total_args = commands::total::cli().get_matches_from(["total"]);
     let result = commands::total::exec(ctx.gctx_mut(), &total_args);
 
     assert!(result.is_err());
-    assert!(matches!(result.unwrap_err(), CliError::FileNotFound(_)));
+    assert!(matches!(result.unwrap_err(), CliError::NotInitialized));
 }
 
 #[test]
 fn test_add_with_future_date() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Add record with future date
-    let add_args = commands::add::cli().get_matches_from(&["add", "income", "100.0", "--date", "31-12-2099"]);
+    let add_args = commands::add::cli().get_matches_from(["add", "income", "100.0", "--date", "31-12-2099"]);
     let result = commands::add::exec(ctx.gctx_mut(), &add_args);
 
     assert!(result.is_ok(), "Should accept future dates");
@@ -968,11 +1078,11 @@ fn test_add_with_future_date() {
 fn test_add_with_very_large_amount() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Add record with very large amount (> 1 million)
-    let add_args = commands::add::cli().get_matches_from(&["add", "income", "9999999.99"]);
+    let add_args = commands::add::cli().get_matches_from(["add", "income", "9999999.99"]);
     let result = commands::add::exec(ctx.gctx_mut(), &add_args);
 
     assert!(result.is_ok(), "Should accept large amounts");
@@ -987,14 +1097,14 @@ fn test_add_with_very_large_amount() {
 fn test_list_with_no_results() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Add records in January 2025
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "100.0", "--date", "15-01-2025"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "100.0", "--date", "15-01-2025"])).unwrap();
 
     // List with date range in different month (should return empty)
-    let list_args = commands::list::cli().get_matches_from(&["list", "--start", "01-02-2025", "--end", "28-02-2025"]);
+    let list_args = commands::list::cli().get_matches_from(["list", "--start", "01-02-2025", "--end", "28-02-2025"]);
     let result = commands::list::exec(ctx.gctx_mut(), &list_args);
 
     assert!(result.is_ok(), "Empty results should not be an error");
@@ -1012,17 +1122,17 @@ fn test_list_with_no_results() {
 fn test_update_multiple_fields() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Add a subcategory first
-    commands::subcategory::add::exec(ctx.gctx_mut(), &commands::subcategory::add::cli().get_matches_from(&["add", "Salary"])).unwrap();
+    commands::subcategory::add::exec(ctx.gctx_mut(), &commands::subcategory::add::cli().get_matches_from(["add", "Salary"])).unwrap();
 
     // Add initial record
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "100.0", "--date", "01-01-2025"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "100.0", "--date", "01-01-2025"])).unwrap();
 
     // Update multiple fields at once
-    let update_args = commands::update::cli().get_matches_from(&[
+    let update_args = commands::update::cli().get_matches_from([
         "update",
         "1",
         "--amount", "500.0",
@@ -1047,14 +1157,14 @@ fn test_update_multiple_fields() {
 fn test_delete_nonexistent_ids() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init"]);
+    let init_args = commands::init::cli().get_matches_from(["init"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Add one record (ID will be 1)
-    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(&["add", "income", "100.0"])).unwrap();
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "income", "100.0"])).unwrap();
 
     // Try to delete non-existent IDs
-    let delete_args = commands::delete::cli().get_matches_from(&["delete", "--ids", "999,1000"]);
+    let delete_args = commands::delete::cli().get_matches_from(["delete", "--ids", "999,1000"]);
     let result = commands::delete::exec(ctx.gctx_mut(), &delete_args);
 
     // Should succeed but delete nothing (or handle gracefully)
@@ -1066,11 +1176,11 @@ fn test_delete_nonexistent_ids() {
 fn test_total_with_empty_tracker() {
     let mut ctx = TestContext::new();
 
-    let init_args = commands::init::cli().get_matches_from(&["init", "--opening", "2500.0"]);
+    let init_args = commands::init::cli().get_matches_from(["init", "--opening", "2500.0"]);
     commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
 
     // Get total without adding any records
-    let total_args = commands::total::cli().get_matches_from(&["total"]);
+    let total_args = commands::total::cli().get_matches_from(["total"]);
     let result = commands::total::exec(ctx.gctx_mut(), &total_args);
 
     assert!(result.is_ok());
@@ -1086,3 +1196,2356 @@ fn test_total_with_empty_tracker() {
         }
     }
 }
+
+#[test]
+fn test_list_json_output() {
+    let mut ctx = TestContext::new();
+
+    let init_args = commands::init::cli().get_matches_from(["init", "--currency", "usd"]);
+    commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
+
+    commands::add::exec(ctx.gctx_mut(), &commands::add::cli().get_matches_from(["add", "expenses", "42.5", "-d", "Lunch"])).unwrap();
+
+    let list_args = commands::list::cli().get_matches_from(["list"]);
+    let response = commands::list::exec(ctx.gctx_mut(), &list_args).unwrap();
+
+    let mut buffer = Vec::new();
+    response.write_json_to(&mut buffer).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+
+    assert_eq!(json["status"], "ok");
+    assert_eq!(json["data"]["type"], "list");
+    assert_eq!(json["data"]["records"][0]["category"], "expenses");
+    assert_eq!(json["data"]["records"][0]["amount"], 42.5);
+    assert_eq!(json["data"]["records"][0]["currency"], "USD");
+    assert_eq!(json["data"]["records"][0]["description"], "Lunch");
+}
+
+#[test]
+fn test_error_json_output() {
+    let mut ctx = TestContext::new();
+
+    let init_args = commands::init::cli().get_matches_from(["init"]);
+    commands::init::exec(ctx.gctx_mut(), &init_args).unwrap();
+
+    let update_args = commands::update::cli().get_matches_from(["update", "99", "--amount", "10"]);
+    let err = commands::update::exec(ctx.gctx_mut(), &update_args).unwrap_err();
+
+    let mut buffer = Vec::new();
+    err.write_json_to(&mut buffer).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+
+    assert_eq!(json["status"], "error");
+    assert_eq!(json["error"]["code"], "E014");
+    assert_eq!(json["error"]["kind"], "record_not_found");
+    assert_eq!(json["error"]["exit_code"], exit_code::NOT_FOUND);
+    assert_eq!(json["error"]["details"]["id"], 99);
+    assert!(json["error"]["suggestion"].as_str().unwrap().contains("fintrack list"));
+}
+
+#[test]
+fn test_exec_line_runs_commands() {
+    let mut ctx = TestContext::new();
+
+    commands::exec_line(ctx.gctx_mut(), "init --currency usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), r#"add expenses 12.5 -d "Lunch with Ada""#).unwrap();
+
+    let content = fs::read_to_string(ctx.gctx.tracker_path()).unwrap();
+    let data: TrackerData = serde_json::from_str(&content).unwrap();
+
+    assert_eq!(data.records.len(), 1);
+    assert_eq!(data.records[0].description, "Lunch with Ada");
+}
+
+#[test]
+fn test_exec_line_rejects_interactive_and_invalid_commands() {
+    let mut ctx = TestContext::new();
+
+    assert!(matches!(commands::exec_line(ctx.gctx_mut(), "clear"), Err(CliError::Other(_))));
+    assert!(matches!(commands::exec_line(ctx.gctx_mut(), "frobnicate"), Err(CliError::Other(_))));
+    assert!(matches!(commands::exec_line(ctx.gctx_mut(), "add -d \"unterminated"), Err(CliError::Other(_))));
+}
+
+#[test]
+fn test_session_defers_writes_until_flushed() {
+    use fintrack::utils::storage::{end_session, flush_session, start_session};
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init --currency usd").unwrap();
+    let before = fs::read_to_string(ctx.gctx.tracker_path()).unwrap();
+
+    start_session(ctx.gctx_mut()).unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 4 -d Coffee").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100 -d Refund").unwrap();
+
+    // Commands see the in-memory changes, the file does not
+    let res = commands::exec_line(ctx.gctx_mut(), "list").unwrap();
+    match res.content() {
+        Some(ResponseContent::List { records, .. }) => assert_eq!(records.len(), 2),
+        _ => panic!("Expected list response"),
+    }
+    assert!(ctx.gctx.session().unwrap().is_dirty());
+    assert_eq!(fs::read_to_string(ctx.gctx.tracker_path()).unwrap(), before);
+
+    assert!(flush_session(ctx.gctx_mut()).unwrap());
+    assert!(!flush_session(ctx.gctx_mut()).unwrap());
+    let data: TrackerData =
+        serde_json::from_str(&fs::read_to_string(ctx.gctx.tracker_path()).unwrap()).unwrap();
+    assert_eq!(data.records.len(), 2);
+
+    commands::exec_line(ctx.gctx_mut(), "delete -i 1").unwrap();
+    end_session(ctx.gctx_mut()).unwrap();
+    assert!(ctx.gctx.session().is_none());
+
+    let data: TrackerData =
+        serde_json::from_str(&fs::read_to_string(ctx.gctx.tracker_path()).unwrap()).unwrap();
+    assert_eq!(data.records.len(), 1);
+}
+
+#[test]
+fn test_changes_report_new_balance() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init --currency usd --opening 100").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 50").unwrap();
+
+    let res = commands::exec_line(ctx.gctx_mut(), "add expenses 30").unwrap();
+    match res.content() {
+        Some(ResponseContent::Record { tracker_data, .. }) => {
+            assert_eq!(tracker_data.net_balance(), 120.0)
+        }
+        _ => panic!("Expected record response"),
+    }
+
+    let res = commands::exec_line(ctx.gctx_mut(), "delete -i 1,2").unwrap();
+    match res.content() {
+        Some(ResponseContent::Deleted { count, tracker_data }) => {
+            assert_eq!(*count, 2);
+            assert_eq!(tracker_data.net_balance(), 100.0);
+        }
+        _ => panic!("Expected deleted response"),
+    }
+
+    let mut buffer = Vec::new();
+    res.write_json_to(&mut buffer).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+    assert_eq!(json["data"]["type"], "deleted");
+    assert_eq!(json["data"]["balance"], 100.0);
+}
+
+#[test]
+fn test_config_date_format_applies_to_tables() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 50 -D 15-01-2025").unwrap();
+
+    fs::write(ctx.gctx.config_path(), "[display]\ndate_format = \"iso\"\n").unwrap();
+    ctx.gctx_mut().load_config().unwrap();
+    assert_eq!(ctx.gctx.display().date_format, DateFormat::Iso);
+
+    let res = commands::exec_line(ctx.gctx_mut(), "list").unwrap();
+    let mut buffer = Vec::new();
+    res.write_to(ctx.gctx.display(), &mut buffer).unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    assert!(text.contains("2025-01-15"));
+    assert!(!text.contains("15-01-2025"));
+
+    // Storage is unaffected
+    let content = fs::read_to_string(ctx.gctx.tracker_path()).unwrap();
+    assert!(content.contains("15-01-2025"));
+}
+
+#[test]
+fn test_invalid_config_is_reported() {
+    let mut ctx = TestContext::new();
+    fs::write(ctx.gctx.config_path(), "[display]\ndate_format = 3\n").unwrap();
+    assert!(matches!(ctx.gctx_mut().load_config(), Err(CliError::Other(_))));
+}
+
+#[test]
+fn test_config_aliases_expand_in_exec_line() {
+    let mut ctx = TestContext::new();
+    fs::create_dir_all(ctx.gctx.base_path()).unwrap();
+    fs::write(
+        ctx.gctx.config_path(),
+        "[aliases]\nspent = \"add expenses\"\nrecent = \"list --last 1\"\n",
+    )
+    .unwrap();
+    ctx.gctx_mut().load_config().unwrap();
+
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "spent 12 --description Lunch").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "spent 8").unwrap();
+
+    let res = commands::exec_line(ctx.gctx_mut(), "recent").unwrap();
+    match res.content() {
+        Some(ResponseContent::List { records, tracker_data }) => {
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].amount, 8.0);
+            assert_eq!(records[0].category, tracker_data.categories["expenses"]);
+        }
+        _ => panic!("Expected list response"),
+    }
+}
+
+#[test]
+fn test_config_set_get_unset() {
+    let mut ctx = TestContext::new();
+
+    commands::exec_line(ctx.gctx_mut(), "config set display.table_style markdown").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "config set general.confirm false").unwrap();
+    assert_eq!(ctx.gctx.display().table_style, TableStyle::Markdown);
+    assert!(!ctx.gctx.config().general.confirm);
+
+    let content = fs::read_to_string(ctx.gctx.config_path()).unwrap();
+    assert!(content.contains("table_style = \"markdown\""));
+    assert!(content.contains("confirm = false"));
+
+    let res = commands::exec_line(ctx.gctx_mut(), "config get display.table_style").unwrap();
+    match res.content() {
+        Some(ResponseContent::ConfigValue { value, .. }) => {
+            assert_eq!(value.as_deref(), Some("markdown"))
+        }
+        _ => panic!("Expected config value response"),
+    }
+
+    commands::exec_line(ctx.gctx_mut(), "config unset display.table_style").unwrap();
+    assert_eq!(ctx.gctx.display().table_style, TableStyle::Modern);
+
+    let res = commands::exec_line(ctx.gctx_mut(), "config list").unwrap();
+    match res.content() {
+        Some(ResponseContent::Settings(settings)) => {
+            assert!(settings.contains(&("general.confirm".to_string(), "false".to_string())));
+            assert!(settings.contains(&("display.table_style".to_string(), "modern".to_string())));
+        }
+        _ => panic!("Expected settings response"),
+    }
+}
+
+#[test]
+fn test_config_set_rejects_bad_keys_and_values() {
+    let mut ctx = TestContext::new();
+
+    let err = commands::exec_line(ctx.gctx_mut(), "config set display.colour red").unwrap_err();
+    assert_eq!(err.code(), "E023");
+
+    let err = commands::exec_line(ctx.gctx_mut(), "config set display.date_format yyyy").unwrap_err();
+    assert_eq!(err.code(), "E024");
+
+    let err = commands::exec_line(ctx.gctx_mut(), "config set aliases.list 'add income'").unwrap_err();
+    assert_eq!(err.code(), "E024");
+
+    // Nothing was written
+    assert!(!ctx.gctx.config_path().exists());
+}
+
+#[test]
+fn test_export_uses_configured_default_dir() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 10").unwrap();
+
+    let err = commands::exec_line(ctx.gctx_mut(), "export -t csv").unwrap_err();
+    assert!(matches!(err, CliError::Other(_)));
+
+    commands::exec_line(ctx.gctx_mut(), "config set export.dir ~/exports/fintrack").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "export -t csv").unwrap();
+
+    let export_dir = ctx.gctx.home_path().join("exports").join("fintrack");
+    let files: Vec<_> = fs::read_dir(&export_dir).unwrap().collect();
+    assert_eq!(files.len(), 1);
+}
+
+#[test]
+fn test_config_default_args_are_overridden_by_typed_flags() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    for amount in 1..=5 {
+        commands::exec_line(ctx.gctx_mut(), &format!("add income {}", amount)).unwrap();
+    }
+    commands::exec_line(ctx.gctx_mut(), "config set list.default '--last 2'").unwrap();
+
+    let count = |res: CliResult| match res.unwrap().content() {
+        Some(ResponseContent::List { records, .. }) => records.len(),
+        _ => panic!("Expected list response"),
+    };
+
+    assert_eq!(count(commands::exec_line(ctx.gctx_mut(), "list")), 2);
+    assert_eq!(count(commands::exec_line(ctx.gctx_mut(), "list --last 4")), 4);
+    assert_eq!(count(commands::exec_line(ctx.gctx_mut(), "list --first 3")), 3);
+}
+
+#[test]
+fn test_config_export_and_import() {
+    let mut source = TestContext::new();
+    commands::exec_line(source.gctx_mut(), "config set display.date_format iso").unwrap();
+    commands::exec_line(source.gctx_mut(), "config set aliases.spent 'add expenses'").unwrap();
+
+    let settings = source.temp_dir.path().join("settings.toml");
+    let line = format!("config export {}", settings.display());
+    commands::exec_line(source.gctx_mut(), &line).unwrap();
+    // Refuses to overwrite without --force
+    assert!(commands::exec_line(source.gctx_mut(), &line).is_err());
+
+    let mut target = TestContext::new();
+    commands::exec_line(target.gctx_mut(), "config set display.table_style ascii").unwrap();
+    commands::exec_line(target.gctx_mut(), &format!("config import --merge {}", settings.display())).unwrap();
+
+    let config = target.gctx.config();
+    assert_eq!(config.display.date_format, DateFormat::Iso);
+    assert_eq!(config.display.table_style, TableStyle::Ascii);
+    assert_eq!(config.aliases["spent"], "add expenses");
+
+    // Without --merge the file replaces the current settings
+    commands::exec_line(target.gctx_mut(), &format!("config import {}", settings.display())).unwrap();
+    assert_eq!(target.gctx.config().display.table_style, TableStyle::Modern);
+}
+
+#[test]
+fn test_config_import_rejects_invalid_file() {
+    let mut ctx = TestContext::new();
+    let settings = ctx.temp_dir.path().join("settings.toml");
+    fs::write(&settings, "[display]\ndate_format = \"nope\"\n").unwrap();
+
+    let result = commands::exec_line(ctx.gctx_mut(), &format!("config import {}", settings.display()));
+    assert!(result.is_err());
+    assert!(!ctx.gctx.config_path().exists());
+}
+
+#[test]
+fn test_backups_are_written_and_pruned() {
+    use fintrack::utils::backup::list_backups;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    // Creating the tracker has nothing to back up yet
+    assert!(list_backups(&ctx.gctx).unwrap().is_empty());
+
+    commands::exec_line(ctx.gctx_mut(), "config set backup.max_count 2").unwrap();
+    for amount in 1..=4 {
+        commands::exec_line(ctx.gctx_mut(), &format!("add income {}", amount)).unwrap();
+    }
+    let backups = list_backups(&ctx.gctx).unwrap();
+    assert_eq!(backups.len(), 2);
+    // The newest backup is the tracker as it was before the last change
+    let newest: TrackerData = serde_json::from_str(&fs::read_to_string(&backups[1]).unwrap()).unwrap();
+    assert_eq!(newest.records.len(), 3);
+
+    commands::exec_line(ctx.gctx_mut(), "config set backup.enabled false").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 5").unwrap();
+    assert_eq!(list_backups(&ctx.gctx).unwrap().len(), 2);
+}
+
+#[test]
+fn test_webhook_receives_mutations() {
+    let (url, server) = capture_http(2);
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), &format!("config set webhook.url {}/hook", url)).unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100 -d Salary").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "delete -i 1").unwrap();
+
+    let bodies: Vec<_> = server.join().unwrap().iter().map(|r| r.json()).collect();
+    assert_eq!(bodies[0]["event"], "add");
+    assert_eq!(bodies[0]["records"][0]["description"], "Salary");
+    assert_eq!(bodies[0]["balance"], 100.0);
+    assert_eq!(bodies[1]["event"], "delete");
+    assert_eq!(bodies[1]["records"][0]["id"], 1);
+    assert_eq!(bodies[1]["balance"], 0.0);
+}
+
+#[test]
+fn test_webhook_reports_budget_status() {
+    let (url, server) = capture_http(1);
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Rent").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set food 200").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set rent 900").unwrap();
+    commands::exec_line(ctx.gctx_mut(), &format!("config set webhook.url {}/hook", url)).unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 50 -s Food -D 10-03-2025").unwrap();
+
+    let body = server.join().unwrap()[0].json();
+    // Only the budget the record counts against, for the record's month
+    let budgets = body["budgets"].as_array().unwrap();
+    assert_eq!(budgets.len(), 1);
+    assert_eq!(budgets[0]["subcategory"], "Food");
+    assert_eq!(budgets[0]["month"], "03-2025");
+    assert_eq!(budgets[0]["spent"], 50.0);
+    assert_eq!(budgets[0]["remaining"], 150.0);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_hooks_receive_records_and_pre_add_can_cancel() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+
+    let hooks = ctx.gctx.hooks_path().clone();
+    fs::create_dir_all(&hooks).unwrap();
+    let write_hook = |name: &str, script: &str| {
+        let path = hooks.join(name);
+        fs::write(&path, script).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    };
+    write_hook("pre-add", "#!/bin/sh\ngrep -q '\"description\":\"blocked\"' && exit 1\nexit 0\n");
+    write_hook("post-add", "#!/bin/sh\ncat > \"$(dirname \"$0\")/added.json\"\n");
+
+    commands::exec_line(ctx.gctx_mut(), "add income 100 -d Salary").unwrap();
+    let added: serde_json::Value = serde_json::from_str(&fs::read_to_string(hooks.join("added.json")).unwrap()).unwrap();
+    assert_eq!(added["description"], "Salary");
+    assert_eq!(added["amount"], 100.0);
+
+    let err = commands::exec_line(ctx.gctx_mut(), "add income 5 -d blocked").unwrap_err();
+    assert!(matches!(err, CliError::Other(_)));
+    let data: TrackerData = serde_json::from_str(&fs::read_to_string(ctx.gctx.tracker_path()).unwrap()).unwrap();
+    assert_eq!(data.records.len(), 1);
+}
+
+#[test]
+fn test_notify_sends_summary_to_ntfy() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 500").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 120").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30 --date 01-01-2000").unwrap();
+
+    let err = commands::exec_line(ctx.gctx_mut(), "notify").unwrap_err();
+    assert!(matches!(err, CliError::Other(_)));
+
+    let (url, server) = capture_http(1);
+    commands::exec_line(ctx.gctx_mut(), &format!("config set notify.ntfy_topic {}/money", url)).unwrap();
+    commands::exec_line(ctx.gctx_mut(), "notify").unwrap();
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests[0].path, "/money");
+    assert!(requests[0].header("title").unwrap().starts_with("fintrack:"));
+    // Only records from the last 7 days count
+    assert!(requests[0].body.contains("Expenses: 120.00"));
+    assert!(requests[0].body.contains("Balance: 350.00"));
+}
+
+#[test]
+fn test_monthly_report() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init --opening 100").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 1000 --date 15-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 2000 --date 01-02-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 300 -s Food --date 10-02-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 50 --date 28-02-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 75 --date 01-03-2025").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "report --month 02-2025").unwrap();
+    let Some(ResponseContent::Report(report)) = response.content() else {
+        panic!("expected a report");
+    };
+    assert_eq!(report.month, "02-2025");
+    assert_eq!(report.records, 3);
+    assert_eq!(report.opening_balance, 1100.0);
+    assert_eq!(report.income, 2000.0);
+    assert_eq!(report.expenses, 350.0);
+    assert_eq!(report.closing_balance, 2750.0);
+    assert_eq!(report.expenses_by_subcategory[0], ("Food".to_string(), 300.0));
+
+    let html = output::report_html(report);
+    assert!(html.contains("02-2025"));
+    assert!(html.contains("2,750.00"));
+
+    // Email needs SMTP settings first
+    let err = commands::exec_line(ctx.gctx_mut(), "report --month 02-2025 --email").unwrap_err();
+    assert!(matches!(err, CliError::Other(_)));
+}
+
+#[test]
+fn test_watch_only_accepts_read_only_views() {
+    assert!(commands::watch::check_view("list --last 10").is_ok());
+    assert!(commands::watch::check_view("total").is_ok());
+    assert!(commands::watch::check_view("delete -i 1").is_err());
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "config set watch.view dump").unwrap();
+    let args = commands::watch::cli().get_matches_from(["watch"]);
+    assert!(matches!(commands::watch::exec(ctx.gctx_mut(), &args), Err(CliError::Other(_))));
+}
+
+#[test]
+fn test_gsheet_export_needs_spreadsheet_and_token() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+
+    // No path is needed, but the spreadsheet must be configured
+    let err = commands::exec_line(ctx.gctx_mut(), "export -t gsheet").unwrap_err();
+    assert!(matches!(err, CliError::Other(ref msg) if msg.contains("gsheet.spreadsheet_id")));
+
+    commands::exec_line(ctx.gctx_mut(), "config set gsheet.spreadsheet_id abc123").unwrap();
+    if std::env::var_os("FINTRACK_GSHEET_TOKEN").is_none() {
+        let err = commands::exec_line(ctx.gctx_mut(), "export -t gsheet").unwrap_err();
+        assert!(matches!(err, CliError::Other(ref msg) if msg.contains("FINTRACK_GSHEET_TOKEN")));
+    }
+}
+
+#[test]
+fn test_serve_tokens_are_stored_hashed_and_can_be_revoked() {
+    use fintrack::utils::tokens;
+
+    // `serve` isn't allowed through exec_line, which is for interactive use
+    fn token(ctx: &mut TestContext, args: &[&str]) -> CliResult {
+        let matches = commands::serve::cli().get_matches_from([&["serve", "token"], args].concat());
+        commands::serve::exec(ctx.gctx_mut(), &matches)
+    }
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+
+    let response = token(&mut ctx, &["create", "grafana"]).unwrap();
+    let Some(ResponseContent::Message(message)) = response.content() else {
+        panic!("Expected Message response");
+    };
+    let secret = message.lines().map(str::trim).find(|line| line.starts_with("ft_")).unwrap();
+
+    let stored = std::fs::read_to_string(ctx.gctx.tokens_path()).unwrap();
+    assert!(!stored.contains(secret));
+    let saved = tokens::load(&ctx.gctx).unwrap();
+    assert!(tokens::authorized(&saved, Some(&format!("Bearer {}", secret))));
+
+    assert_eq!(token(&mut ctx, &["create", "Grafana"]).unwrap_err().code(), "E019");
+    token(&mut ctx, &["revoke", "GRAFANA"]).unwrap();
+    assert!(tokens::load(&ctx.gctx).unwrap().is_empty());
+    assert_eq!(token(&mut ctx, &["revoke", "grafana"]).unwrap_err().code(), "E035");
+}
+
+#[test]
+fn test_serve_metrics() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init --opening 50").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 200 --date 02-03-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30 -s Food --date 05-03-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 10 -s Food --date 28-02-2025").unwrap();
+
+    let data: TrackerData = serde_json::from_str(&fs::read_to_string(ctx.gctx.tracker_path()).unwrap()).unwrap();
+    let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 20).unwrap();
+    let metrics = commands::serve::metrics(&data, today);
+
+    assert!(metrics.contains("# TYPE fintrack_balance gauge"));
+    assert!(metrics.contains("fintrack_balance{currency=\"NGN\"} 210"));
+    // Only March counts towards this month's spend
+    assert!(metrics.contains("fintrack_month_spend{currency=\"NGN\",subcategory=\"Food\"} 30"));
+    assert!(metrics.contains("fintrack_month_spend{currency=\"NGN\",subcategory=\"miscellaneous\"} 0"));
+    assert!(metrics.contains("fintrack_records 3"));
+}
+
+#[test]
+fn test_serve_metrics_include_budget_utilization() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Rent").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set Food 200").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set Rent 500").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 50 -s Food --date 05-03-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 600 -s Rent --date 01-03-2025").unwrap();
+
+    let data = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 20).unwrap();
+    let metrics = commands::serve::metrics(&data, today);
+
+    assert!(metrics.contains("# TYPE fintrack_budget_utilization gauge"));
+    assert!(metrics.contains("fintrack_budget_utilization{subcategory=\"Food\"} 0.25"));
+    assert!(metrics.contains("fintrack_budget_utilization{subcategory=\"Rent\"} 1.2"));
+    // Subcategories without a budget have nothing to be measured against
+    assert!(!metrics.contains("fintrack_budget_utilization{subcategory=\"miscellaneous\"}"));
+}
+
+#[test]
+fn test_read_only_mode_blocks_changes() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100").unwrap();
+
+    ctx.gctx.set_read_only(true);
+    for line in ["add income 5", "update 1 -a 5", "delete -i 1", "subcategory add Food"] {
+        let err = commands::exec_line(ctx.gctx_mut(), line).unwrap_err();
+        assert!(matches!(err, CliError::ReadOnly), "{}", line);
+        assert_eq!(err.exit_code(), exit_code::PERMISSION_DENIED);
+    }
+    let clear_args = commands::clear::cli().get_matches_from(["clear"]);
+    assert!(matches!(commands::clear::exec(ctx.gctx_mut(), &clear_args), Err(CliError::ReadOnly)));
+    assert!(ctx.gctx.tracker_path().exists());
+    // Reading still works
+    commands::exec_line(ctx.gctx_mut(), "list").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "total").unwrap();
+
+    // The setting does the same as the flag, and config stays editable to turn it off
+    ctx.gctx.set_read_only(false);
+    commands::exec_line(ctx.gctx_mut(), "config set general.read_only true").unwrap();
+    assert!(matches!(commands::exec_line(ctx.gctx_mut(), "add income 5"), Err(CliError::ReadOnly)));
+    commands::exec_line(ctx.gctx_mut(), "config unset general.read_only").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 5").unwrap();
+}
+
+#[test]
+fn test_batch_applies_all_or_nothing() {
+    use fintrack::utils::storage::load_tracker;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    let script = ctx.temp_dir.path().join("script.txt");
+
+    fs::write(&script, "# groceries\nsubcategory add Food\n\nadd expenses 12 -s Food\nadd income 100\n").unwrap();
+    let args = commands::batch::cli().get_matches_from(["batch", script.to_str().unwrap()]);
+    commands::batch::exec(ctx.gctx_mut(), &args).unwrap();
+
+    let data = load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(data.records.len(), 2);
+    assert!(data.subcategory_id("Food").is_some());
+    assert!(ctx.gctx.session().is_none());
+
+    // The third line fails, so the first two are not saved either
+    fs::write(&script, "add income 1\nadd income 2\nadd expenses 3 -s Nope\nadd income 4\n").unwrap();
+    let err = commands::batch::exec(ctx.gctx_mut(), &args).unwrap_err();
+    assert!(matches!(err, CliError::ValidationError(ValidationErrorKind::SubcategoryNotFound { .. })));
+    assert_eq!(load_tracker(&ctx.gctx).unwrap().records.len(), 2);
+    assert!(ctx.gctx.session().is_none());
+}
+
+#[test]
+fn test_load_tracker_reuses_cache_until_file_changes() {
+    use fintrack::utils::storage::load_tracker;
+    use std::time::Duration;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100").unwrap();
+    assert_eq!(load_tracker(&ctx.gctx).unwrap().records.len(), 1);
+
+    // Same length and modification time: the cached copy is used, not the file
+    let path = ctx.gctx.tracker_path().clone();
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+    let len = fs::metadata(&path).unwrap().len() as usize;
+    fs::write(&path, "x".repeat(len)).unwrap();
+    fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+    assert_eq!(load_tracker(&ctx.gctx).unwrap().records.len(), 1);
+
+    // Any change to the modification time makes it read the file again
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(modified + Duration::from_secs(1))
+        .unwrap();
+    assert!(matches!(load_tracker(&ctx.gctx), Err(CliError::CorruptedData { .. })));
+}
+
+#[test]
+fn test_export_large_csv_keeps_record_order() {
+    use fintrack::utils::storage::{load_tracker, save_tracker};
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+
+    // Enough records to be split across several parallel chunks
+    let mut data = load_tracker(&ctx.gctx).unwrap();
+    for id in 1..=10_000 {
+        let record: Record = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "category": 2,
+            "subcategory": 1,
+            "description": format!("item {}", id),
+            "amount": 1.5,
+            "date": "01-01-2025",
+        }))
+        .unwrap();
+        data.push_record(record);
+    }
+    data.next_record_id = 10_001;
+    save_tracker(ctx.gctx_mut(), &data).unwrap();
+
+    let export_path = ctx.temp_dir.path().join("exports");
+    fs::create_dir(&export_path).unwrap();
+    let line = format!("export {} -t csv", export_path.display());
+    commands::exec_line(ctx.gctx_mut(), &line).unwrap();
+
+    let file = fs::read_dir(&export_path).unwrap().next().unwrap().unwrap().path();
+    let content = fs::read_to_string(file).unwrap();
+    let ids: Vec<usize> = content
+        .lines()
+        .skip(1)
+        .map(|line| line.split(',').next().unwrap().parse().unwrap())
+        .collect();
+    assert_eq!(ids, (1..=10_000).collect::<Vec<_>>());
+
+    let response = commands::exec_line(ctx.gctx_mut(), "describe").unwrap();
+    let Some(ResponseContent::Describe(describe)) = response.content() else {
+        panic!("Expected Describe response");
+    };
+    assert_eq!(describe.by_category, vec![("expenses".to_string(), 10_000, 15_000.0)]);
+}
+
+#[test]
+fn test_corrupted_tracker_is_restored_from_backup() {
+    use fintrack::utils::storage::load_tracker;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 50").unwrap();
+
+    // Truncated mid-write
+    fs::write(ctx.gctx.tracker_path(), "{\"version\": 1, \"curr").unwrap();
+    let err = commands::exec_line(ctx.gctx_mut(), "add income 5").unwrap_err();
+    assert!(matches!(err, CliError::CorruptedData { backup_restored: true, .. }));
+    assert_eq!(err.code(), "E004");
+
+    // The newest backup was taken before the second add
+    assert_eq!(load_tracker(&ctx.gctx).unwrap().records.len(), 1);
+    let kept_aside = fs::read_dir(ctx.gctx.base_path())
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("tracker.corrupt-"))
+        .count();
+    assert_eq!(kept_aside, 1);
+}
+
+#[test]
+fn test_corrupted_tracker_without_backups() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    fs::write(ctx.gctx.tracker_path(), "not json").unwrap();
+
+    let err = commands::exec_line(ctx.gctx_mut(), "list").unwrap_err();
+    assert!(matches!(err, CliError::CorruptedData { backup_restored: false, .. }));
+    assert_eq!(fs::read_to_string(ctx.gctx.tracker_path()).unwrap(), "not json");
+
+    // Read-only mode never replaces the file
+    ctx.gctx.set_read_only(true);
+    let err = commands::exec_line(ctx.gctx_mut(), "list").unwrap_err();
+    assert!(matches!(err, CliError::InvalidJson(_)));
+}
+
+#[test]
+fn test_checksum_detects_external_edits() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100").unwrap();
+
+    let path = ctx.gctx.tracker_path().to_path_buf();
+    let saved = fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("\"checksum\""));
+
+    fs::write(&path, saved.replace("100.0", "900.0")).unwrap();
+    let err = commands::exec_line(ctx.gctx_mut(), "list").unwrap_err();
+    assert!(matches!(err, CliError::ChecksumMismatch));
+    assert_eq!(err.code(), "E008");
+
+    // Dropping the checksum accepts the edit
+    let edited: String = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("\"checksum\""))
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(&path, edited).unwrap();
+    commands::exec_line(ctx.gctx_mut(), "list").unwrap();
+}
+
+#[test]
+fn test_strict_mode_rejects_suspicious_records() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+
+    // Off by default
+    commands::exec_line(ctx.gctx_mut(), "add expenses 5000").unwrap();
+
+    ctx.gctx.set_strict(true);
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 50").unwrap_err();
+    assert_eq!(err.code(), "E025");
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 50 -d Lunch -D 01-01-2999").unwrap_err();
+    assert_eq!(err.code(), "E025");
+    commands::exec_line(ctx.gctx_mut(), "add expenses 50 -d Lunch -D 01-01-2025").unwrap();
+
+    commands::exec_line(ctx.gctx_mut(), "config set validation.max_amount 1000").unwrap();
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 5000 -d Rent").unwrap_err();
+    assert_eq!(err.code(), "E025");
+
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.records.len(), 2);
+}
+
+#[test]
+fn test_reject_exact_duplicates() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 -d Coffee -D 02-03-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 -d Coffee -D 02-03-2025").unwrap();
+
+    commands::exec_line(ctx.gctx_mut(), "config set validation.reject_exact_duplicates true").unwrap();
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 20 -d Coffee -D 02-03-2025").unwrap_err();
+    assert!(matches!(err, CliError::ValidationError(ValidationErrorKind::DuplicateRecord { id: 1 })));
+    assert_eq!(err.code(), "E026");
+
+    // Any difference is enough
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 -d Coffee -D 03-03-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 21 -d Coffee -D 02-03-2025").unwrap();
+}
+
+#[test]
+fn test_invalid_field_reports_its_path() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 250").unwrap();
+
+    let path = ctx.gctx.tracker_path().to_path_buf();
+    let saved = fs::read_to_string(&path).unwrap();
+    fs::write(&path, saved.replace("250.0", "\"250\"")).unwrap();
+
+    let err = commands::exec_line(ctx.gctx_mut(), "list").unwrap_err();
+    match &err {
+        CliError::InvalidField { path, expected, .. } => {
+            assert_eq!(path, "records[1].amount");
+            assert_eq!(expected.as_deref(), Some("a number"));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(err.code(), "E009");
+
+    // Left in place for the user to fix
+    assert!(fs::read_to_string(&path).unwrap().contains("\"250\""));
+}
+
+#[test]
+fn test_impossible_date_suggests_nearest() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 10 -D 31-02-2025").unwrap_err();
+    assert_eq!(err.code(), "E011");
+    assert!(matches!(
+        err,
+        CliError::ValidationError(ValidationErrorKind::InvalidDate { nearest: Some(ref date), .. })
+            if date == "28-02-2025"
+    ));
+
+    let err = commands::exec_line(ctx.gctx_mut(), "list --start 2025/02/01").unwrap_err();
+    assert!(matches!(
+        err,
+        CliError::ValidationError(ValidationErrorKind::InvalidDate { nearest: None, .. })
+    ));
+}
+
+#[test]
+fn test_amounts_follow_currency_precision() {
+    use fintrack::utils::storage::load_tracker;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c jpy -o 1000.4").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 1500.5").unwrap();
+    let tracker = load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.opening_balance, 1000.0);
+    assert_eq!(tracker.records[0].amount, 1501.0);
+
+    commands::exec_line(ctx.gctx_mut(), "config set input.rounding down").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "update 1 -a 99.9").unwrap();
+    assert_eq!(load_tracker(&ctx.gctx).unwrap().records[0].amount, 99.0);
+
+    commands::exec_line(ctx.gctx_mut(), "config set input.rounding reject").unwrap();
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 10.5").unwrap_err();
+    assert_eq!(err.code(), "E020");
+
+    commands::exec_line(ctx.gctx_mut(), "config set input.decimals 2").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 10.5").unwrap();
+}
+
+#[test]
+fn test_owed_tracks_loans_per_person() {
+    use fintrack::utils::storage::load_tracker;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "owed add Ada 200 --due 15-02-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "owed add bob 50 --borrowed").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "owed add ada 25").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "owed settle ADA 100").unwrap();
+
+    let tracker = load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.net_balance(), tracker.opening_balance);
+    assert!(tracker.owed.iter().all(|entry| entry.person == "Ada" || entry.person == "bob"));
+
+    let response = commands::exec_line(ctx.gctx_mut(), "owed list").unwrap();
+    let Some(ResponseContent::Owed { balances, .. }) = response.content() else {
+        panic!("expected balances");
+    };
+    assert_eq!(balances.len(), 2);
+    assert_eq!((balances[0].person.as_str(), balances[0].balance), ("Ada", 125.0));
+    assert_eq!(balances[0].due.as_deref(), Some("15-02-2025"));
+    assert_eq!((balances[1].person.as_str(), balances[1].balance), ("bob", -50.0));
+
+    let err = commands::exec_line(ctx.gctx_mut(), "owed settle Ada 500").unwrap_err();
+    assert_eq!(err.code(), "E020");
+    commands::exec_line(ctx.gctx_mut(), "owed settle bob").unwrap();
+    let err = commands::exec_line(ctx.gctx_mut(), "owed settle bob").unwrap_err();
+    assert_eq!(err.code(), "E027");
+}
+
+#[test]
+fn test_invest_tracks_cost_basis_and_gains() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -o 100").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "invest add aapl 10 --price 180").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "invest add AAPL 5 --price 120").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "invest value --price AAPL=200").unwrap();
+    let Some(ResponseContent::Holdings { holdings, .. }) = response.content() else {
+        panic!("expected holdings");
+    };
+    assert_eq!(holdings.len(), 1);
+    assert_eq!(holdings[0].quantity, 15.0);
+    assert_eq!(holdings[0].cost_basis, 2400.0);
+    assert_eq!(holdings[0].market_value, 3000.0);
+    assert_eq!(holdings[0].unrealized_gain, 600.0);
+
+    // The price is remembered, and only counted in the total when asked
+    let response = commands::exec_line(ctx.gctx_mut(), "total").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("expected totals");
+    };
+    assert_eq!(total.total(), 100.0);
+    let response = commands::exec_line(ctx.gctx_mut(), "total --with-investments").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("expected totals");
+    };
+    assert_eq!(total.total(), 3100.0);
+
+    let err = commands::exec_line(ctx.gctx_mut(), "invest value --price MSFT=300").unwrap_err();
+    assert_eq!(err.code(), "E019");
+}
+
+#[test]
+fn test_bill_pay_records_expense_and_advances_due_date() {
+    use fintrack::utils::storage::load_tracker;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -o 1000").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "bill add Rent 500 --due 31-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "bill add Gym 20 --due 01-01-2025 --every week").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "bills").unwrap();
+    let Some(ResponseContent::Bills { bills, .. }) = response.content() else {
+        panic!("expected bills");
+    };
+    assert_eq!(bills.len(), 2);
+    assert_eq!(bills[0].name, "Gym");
+    assert!(bills[0].days < 0);
+
+    commands::exec_line(ctx.gctx_mut(), "bill pay rent -D 30-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "bill pay GYM -a 25").unwrap();
+
+    let tracker = load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.bills[0].due, "28-02-2025");
+    assert_eq!(tracker.bills[1].due, "08-01-2025");
+    assert_eq!(tracker.records.len(), 2);
+    assert_eq!(tracker.records[0].description, "Rent");
+    assert_eq!(tracker.net_balance(), 1000.0 - 525.0);
+
+    let err = commands::exec_line(ctx.gctx_mut(), "bill pay water").unwrap_err();
+    assert_eq!(err.code(), "E028");
+}
+
+#[test]
+fn test_split_with_adds_shares_to_balances() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -o 500").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 90 --split-with alice:50% --split-with bob").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30 --split-with Alice:10").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "balances").unwrap();
+    let Some(ResponseContent::Owed { balances, .. }) = response.content() else {
+        panic!("expected balances");
+    };
+    assert_eq!((balances[0].person.as_str(), balances[0].balance), ("alice", 55.0));
+    assert_eq!((balances[1].person.as_str(), balances[1].balance), ("bob", 22.5));
+
+    commands::exec_line(ctx.gctx_mut(), "balances settle bob").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "delete -i 2").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "balances").unwrap();
+    let Some(ResponseContent::Owed { balances, .. }) = response.content() else {
+        panic!("expected balances");
+    };
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].balance, 45.0);
+
+    let err = commands::exec_line(ctx.gctx_mut(), "add income 10 --split-with alice").unwrap_err();
+    assert_eq!(err.code(), "E020");
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 10 --split-with a:60% --split-with b:5").unwrap_err();
+    assert_eq!(err.code(), "E020");
+}
+
+#[test]
+fn test_deduction_report_totals_deductible_expenses_for_year() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -o 1000").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Office").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 120 -s Office -D 10-03-2024 --deductible").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 50 -D 11-04-2024 --deductible").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 80 -s Office -D 12-05-2024").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30 -s Office -D 01-01-2025 --deductible").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "update 3 --deductible").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "update 2 --deductible false").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "report --deductions --year 2024").unwrap();
+    let Some(ResponseContent::Deductions(report)) = response.content() else {
+        panic!("expected a deduction report");
+    };
+    assert_eq!(report.records, 2);
+    assert_eq!(report.total, 200.0);
+    assert_eq!(report.by_subcategory, vec![("Office".to_string(), 200.0)]);
+
+    let response = commands::exec_line(ctx.gctx_mut(), "list --deductible").unwrap();
+    let Some(ResponseContent::List { records, .. }) = response.content() else {
+        panic!("expected records");
+    };
+    assert_eq!(records.iter().map(|r| r.id).collect::<Vec<_>>(), vec![1, 3, 4]);
+}
+
+#[test]
+fn test_report_year_follows_fiscal_year_start() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -o 1000").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "config set period.fiscal_year_start april").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 100 -D 31-03-2024").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 500 -D 01-04-2024").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 40 -D 31-03-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 60 -D 01-04-2025").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "report --year 2024").unwrap();
+    let Some(ResponseContent::Report(report)) = response.content() else {
+        panic!("expected a report");
+    };
+    assert_eq!(report.month, "2024-25");
+    assert_eq!(report.records, 2);
+    assert_eq!(report.opening_balance, 900.0);
+    assert_eq!(report.closing_balance, 1360.0);
+
+    let err = commands::exec_line(ctx.gctx_mut(), "config set period.week_start someday").unwrap_err();
+    assert_eq!(err.code(), "E024");
+}
+
+#[test]
+fn test_close_locks_month_and_reports_start_from_snapshot() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -o 1000").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 200 -D 15-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 50 -D 03-02-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "close --month 01-2025").unwrap();
+
+    for line in ["add expenses 5 -D 20-01-2025", "update 1 -a 300", "update 2 --date 31-01-2025", "delete -i 1"] {
+        let err = commands::exec_line(ctx.gctx_mut(), line).unwrap_err();
+        assert_eq!(err.code(), "E031", "{}", line);
+    }
+    commands::exec_line(ctx.gctx_mut(), "update 2 -a 60").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "report --month 01-2025").unwrap();
+    let Some(ResponseContent::Report(report)) = response.content() else {
+        panic!("expected a report");
+    };
+    assert_eq!(report.snapshot, Some(800.0));
+    assert_eq!(report.closing_balance, 800.0);
+
+    let response = commands::exec_line(ctx.gctx_mut(), "report --month 02-2025").unwrap();
+    let Some(ResponseContent::Report(report)) = response.content() else {
+        panic!("expected a report");
+    };
+    assert_eq!(report.opening_balance, 800.0);
+    assert_eq!(report.closing_balance, 860.0);
+    assert_eq!(report.snapshot, None);
+
+    commands::exec_line(ctx.gctx_mut(), "close --month 01-2025 --reopen").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "delete -i 1").unwrap();
+    assert!(commands::exec_line(ctx.gctx_mut(), "close --month 12-2099").is_err());
+}
+
+#[test]
+fn test_clear_archives_tracker_and_can_keep_subcategories() {
+    use fintrack::utils::storage::load_tracker;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -o 500").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "config set general.confirm false").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 -s Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30 -s Food").unwrap();
+
+    let archive = ctx.gctx.home_path().join("final.json");
+    let args = commands::clear::cli().get_matches_from([
+        "clear",
+        "--keep-subcategories",
+        "--archive-to",
+        archive.to_str().unwrap(),
+    ]);
+    commands::clear::exec(ctx.gctx_mut(), &args).unwrap();
+
+    let archived: TrackerData = serde_json::from_slice(&fs::read(&archive).unwrap()).unwrap();
+    assert_eq!(archived.records.len(), 2);
+    let tracker = load_tracker(&ctx.gctx).unwrap();
+    assert!(tracker.records.is_empty());
+    assert_eq!(tracker.opening_balance, 500.0);
+    assert!(tracker.subcategory_id("food").is_some());
+    commands::exec_line(ctx.gctx_mut(), "add expenses 5 -s Food").unwrap();
+    assert_eq!(load_tracker(&ctx.gctx).unwrap().records[0].id, 1);
+
+    let args = commands::clear::cli().get_matches_from(["clear"]);
+    commands::clear::exec(ctx.gctx_mut(), &args).unwrap();
+    assert!(!ctx.gctx.tracker_path().exists());
+    let archives: Vec<_> = fs::read_dir(ctx.gctx.backups_path())
+        .unwrap()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with("archive_"))
+        .collect();
+    assert_eq!(archives.len(), 1);
+}
+
+#[test]
+fn test_total_for_a_month_starts_from_the_balance_before_it() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -o 1000").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 100 -D 20-12-2024").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 300 -D 05-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 50 -D 31-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 70 -D 01-02-2025").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "total --month 01-2025").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("expected totals");
+    };
+    assert_eq!(total.start.as_deref(), Some("01-01-2025"));
+    assert_eq!(total.end.as_deref(), Some("31-01-2025"));
+    assert_eq!(total.opening_balance, 900.0);
+    assert_eq!((total.income_total, total.expenses_total), (300.0, 50.0));
+    assert_eq!(total.total(), 1150.0);
+
+    let response = commands::exec_line(ctx.gctx_mut(), "total --as-of 31-12-2024").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("expected totals");
+    };
+    assert_eq!((total.start.as_deref(), total.end.as_deref()), (None, Some("31-12-2024")));
+    assert_eq!(total.total(), 900.0);
+
+    let response = commands::exec_line(ctx.gctx_mut(), "list --month 01-2025").unwrap();
+    let Some(ResponseContent::List { records, .. }) = response.content() else {
+        panic!("expected records");
+    };
+    assert_eq!(records.len(), 2);
+    assert!(commands::exec_line(ctx.gctx_mut(), "total --month 01-2025 --days 7").is_err());
+}
+
+#[test]
+fn test_total_by_subcategory_nets_each_group() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Salary").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30 -s Food -D 02-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 12.5 -s Food -D 03-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 1000 -s Salary -D 04-01-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 5 -s Food -D 05-02-2025").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "total --by subcategory --month 01-2025").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("expected totals");
+    };
+    assert_eq!(total.by, Some(GroupBy::Subcategory));
+    assert_eq!(
+        total.groups,
+        vec![
+            GroupTotal { name: "Salary".to_string(), income: 1000.0, expenses: 0.0, net: 1000.0 },
+            GroupTotal { name: "Food".to_string(), income: 0.0, expenses: 42.5, net: -42.5 },
+        ]
+    );
+}
+
+#[test]
+fn test_budgets_compare_this_months_expenses() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Fun").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set food 200").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set fun 40").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set fun 50").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30 -s Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 70 -s Fun").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 500 -s Fun").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 99 -s Food -D 01-01-2020").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory rename fun Leisure").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "total").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("expected totals");
+    };
+    assert_eq!(
+        total.budgets,
+        vec![
+            BudgetStatus { subcategory: "Food".to_string(), budget: 200.0, carried: 0.0, spent: 30.0, remaining: 170.0, level: BudgetLevel::Ok },
+            BudgetStatus { subcategory: "Leisure".to_string(), budget: 50.0, carried: 0.0, spent: 70.0, remaining: -20.0, level: BudgetLevel::Error },
+        ]
+    );
+
+    commands::exec_line(ctx.gctx_mut(), "budget delete leisure").unwrap();
+    let err = commands::exec_line(ctx.gctx_mut(), "budget delete leisure").unwrap_err();
+    assert_eq!(err.code(), "E032");
+}
+
+#[test]
+fn test_project_totals_records_across_subcategories() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Travel").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 300 -s Travel -D 01-03-2025 --project 'Lagos trip'").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 45 -D 05-03-2025 --project 'lagos TRIP'").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 80 -D 02-03-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "update 3 --project 'Lagos Trip'").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "update 2 --project ''").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "project status 'LAGOS TRIP'").unwrap();
+    let Some(ResponseContent::Project { project, .. }) = response.content() else {
+        panic!("expected a project");
+    };
+    assert_eq!(project.name, "Lagos trip");
+    assert_eq!(project.records, 2);
+    assert_eq!(project.spent, 380.0);
+    assert_eq!(project.start.as_deref(), Some("01-03-2025"));
+    assert_eq!(project.end.as_deref(), Some("02-03-2025"));
+    assert_eq!(project.by_subcategory[0], ("Travel".to_string(), 300.0));
+
+    let err = commands::exec_line(ctx.gctx_mut(), "project status wedding").unwrap_err();
+    assert_eq!(err.code(), "E029");
+}
+
+#[test]
+fn test_schedule_run_catches_up_exactly_once() {
+    use chrono::{Days, Local};
+    use fintrack::commands::schedule::run::apply_due;
+    use fintrack::utils::storage::load_tracker;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -o 100").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "schedule add income 1000 --start 31-01-2025 -d Salary").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "schedule add expenses 10 --every week --start 01-04-2025").unwrap();
+
+    let today = chrono::NaiveDate::from_ymd_opt(2025, 4, 15).unwrap();
+    assert_eq!(apply_due(ctx.gctx_mut(), today).unwrap().0, 3 + 3);
+    assert_eq!(apply_due(ctx.gctx_mut(), today).unwrap().0, 0);
+
+    let tracker = load_tracker(&ctx.gctx).unwrap();
+    let dates: Vec<&str> = tracker.records.iter().map(|r| r.date.as_str()).collect();
+    assert_eq!(
+        dates,
+        vec!["31-01-2025", "28-02-2025", "31-03-2025", "01-04-2025", "08-04-2025", "15-04-2025"]
+    );
+    assert_eq!(tracker.schedules[0].next, "30-04-2025");
+    assert_eq!(tracker.schedules[1].next, "22-04-2025");
+
+    // Due in the future, so `schedule run` leaves it alone
+    let tomorrow = Local::now().date_naive() + Days::new(1);
+    commands::exec_line(ctx.gctx_mut(), &format!("schedule add expenses 5 --start {}", tomorrow.format("%d-%m-%Y"))).unwrap();
+    commands::exec_line(ctx.gctx_mut(), "schedule delete 1").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "schedule delete 2").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "schedule run").unwrap();
+    assert_eq!(load_tracker(&ctx.gctx).unwrap().records.len(), 6);
+
+    let err = commands::exec_line(ctx.gctx_mut(), "schedule delete 1").unwrap_err();
+    assert_eq!(err.code(), "E030");
+}
+
+#[test]
+fn test_search_ranks_close_matches_first() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Groceries").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 4 -d 'Cash for fuel'").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 3 -d 'Morning coffee'").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 60 -s Groceries -d 'Weekly shop'").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 9 -d 'Cinema'").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "search COF").unwrap();
+    let Some(ResponseContent::Search { records, .. }) = response.content() else {
+        panic!("expected search results");
+    };
+    let ids: Vec<usize> = records.iter().map(|r| r.id).collect();
+    assert_eq!(ids, vec![2, 1]);
+
+    // Subcategory names are searched too
+    let response = commands::exec_line(ctx.gctx_mut(), "search grcrs").unwrap();
+    let Some(ResponseContent::Search { records, .. }) = response.content() else {
+        panic!("expected search results");
+    };
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].description, "Weekly shop");
+
+    // And so are tags
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 -d Ferry --tag vacation").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "search vactn").unwrap();
+    let Some(ResponseContent::Search { records, .. }) = response.content() else {
+        panic!("expected search results");
+    };
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].description, "Ferry");
+}
+
+#[test]
+fn test_quick_adds_a_record_from_text() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "quick 'coffee 4.50 yesterday' -s food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "quick +20 from Ada").unwrap();
+
+    use fintrack::utils::storage::load_tracker;
+    let tracker = load_tracker(&ctx.gctx).unwrap();
+    let yesterday = ctx.gctx.today().pred_opt().unwrap().format("%d-%m-%Y").to_string();
+    let coffee = &tracker.records[0];
+    assert_eq!(coffee.description, "coffee");
+    assert_eq!(coffee.amount, 4.5);
+    assert_eq!(coffee.date, yesterday);
+    assert_eq!(tracker.subcategory_name(coffee.subcategory).unwrap(), "Food");
+    assert_eq!(tracker.records[1].category, tracker.category_id("income"));
+    assert_eq!(tracker.net_balance(), 15.5);
+
+    let err = commands::exec_line(ctx.gctx_mut(), "quick tea 2 -s drinks").unwrap_err();
+    assert_eq!(err.code(), "E012");
+}
+
+#[test]
+fn test_quick_tags_hashed_words() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 3 --tag Work").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "quick 'coffee 4.50 #work #treats'").unwrap();
+
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    let coffee = &tracker.records[1];
+    assert_eq!(coffee.description, "coffee");
+    // Tags keep the spelling they were first used with
+    assert_eq!(coffee.tags, ["Work", "treats"]);
+    assert_eq!(coffee.subcategory, tracker.miscellaneous_subcategory_id().unwrap());
+    assert_eq!(tracker.tag_counts()[0], ("Work".to_string(), 2));
+}
+
+#[test]
+fn test_status_check_exit_codes() {
+    let mut ctx = TestContext::new();
+    let err = commands::exec_line(ctx.gctx_mut(), "status --check").unwrap_err();
+    assert_eq!(err.exit_code(), exit_code::FILE_NOT_FOUND);
+
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "status --check").unwrap();
+    assert!(response.content().is_none());
+
+    fs::write(ctx.gctx.tracker_path(), "{ not json").unwrap();
+    let err = commands::exec_line(ctx.gctx_mut(), "status --check").unwrap_err();
+    assert_eq!(err.exit_code(), exit_code::DATA_ERROR);
+    assert_eq!(fs::read_to_string(ctx.gctx.tracker_path()).unwrap(), "{ not json");
+}
+
+#[test]
+fn test_status_summary_is_cached_on_writes() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -o 100").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set food 10").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 12.5 -s food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 3 -D 01-01-2020").unwrap();
+    assert!(ctx.gctx.status_path().exists());
+
+    let response = commands::exec_line(ctx.gctx_mut(), "status --short").unwrap();
+    let Some(ResponseContent::Status { summary, short: true }) = response.content() else {
+        panic!("expected a status");
+    };
+    assert_eq!(summary.balance, 84.5);
+    assert_eq!(summary.spent_today, 12.5);
+    assert_eq!(summary.budget_remaining, Some(-2.5));
+    assert_eq!(summary.over_budget, 1);
+
+    // A cache that can't be used is worked out again from the tracker
+    commands::exec_line(ctx.gctx_mut(), "delete -i 1").unwrap();
+    std::fs::write(ctx.gctx.status_path(), "{}").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "status").unwrap();
+    let Some(ResponseContent::Status { summary, .. }) = response.content() else {
+        panic!("expected a status");
+    };
+    assert_eq!(summary.balance, 97.0);
+    assert_eq!(summary.budget_remaining, Some(10.0));
+}
+
+#[test]
+fn test_history_reverts_a_record_and_logs_the_revert() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 10 -d Lunch -D 01-03-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "update 1 -a 12").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "update 1 -a 15 -d 'Team lunch' -D 02-03-2025").unwrap();
+    // Changing nothing keeps no version
+    commands::exec_line(ctx.gctx_mut(), "update 1 -a 15").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "history 1").unwrap();
+    let Some(ResponseContent::History { versions, .. }) = response.content() else {
+        panic!("expected a history");
+    };
+    let amounts: Vec<f64> = versions.iter().map(|v| v.record.amount).collect();
+    assert_eq!(amounts, vec![10.0, 12.0, 15.0]);
+    assert!(versions[0].since.is_none() && versions[1].since.is_some());
+    assert!(versions[2].current);
+
+    commands::exec_line(ctx.gctx_mut(), "history 1 --revert 1").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "history 1").unwrap();
+    let Some(ResponseContent::History { versions, tracker_data, .. }) = response.content() else {
+        panic!("expected a history");
+    };
+    assert_eq!(versions.len(), 4);
+    assert_eq!(versions[3].reverted_to, Some(1));
+    assert_eq!(tracker_data.records[0].description, "Lunch");
+    assert_eq!(tracker_data.records[0].date, "01-03-2025");
+    assert_eq!(tracker_data.records[0].amount, 10.0);
+
+    let err = commands::exec_line(ctx.gctx_mut(), "history 1 --revert 9").unwrap_err();
+    assert_eq!(err.code(), "E033");
+
+    commands::exec_line(ctx.gctx_mut(), "delete -i 1").unwrap();
+    use fintrack::utils::storage::load_tracker;
+    assert!(load_tracker(&ctx.gctx).unwrap().history.is_empty());
+}
+
+#[test]
+fn test_round_up_savings_are_counted_apart_from_the_balance() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 4.30").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 12.10").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 7.25").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "total").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("expected totals");
+    };
+    assert_eq!(total.round_up_savings, None);
+
+    commands::exec_line(ctx.gctx_mut(), "config set savings.round_up true").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "total").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("expected totals");
+    };
+    assert_eq!(total.round_up_savings, Some(1.6));
+    assert_eq!(total.total(), -29.15);
+
+    commands::exec_line(ctx.gctx_mut(), "config set savings.round_up_to 5").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "total").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("expected totals");
+    };
+    assert_eq!(total.round_up_savings, Some(3.6));
+}
+
+#[test]
+fn test_spending_limits_warn_on_add_and_show_in_status() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "config set limits.daily 50").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "config set limits.weekly 60").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 40").unwrap();
+    assert!(response.warnings().is_empty());
+    let response = commands::exec_line(ctx.gctx_mut(), "add income 500").unwrap();
+    assert!(response.warnings().is_empty());
+
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 15").unwrap();
+    assert_eq!(response.warnings().len(), 1);
+    assert!(response.warnings()[0].contains("daily limit of 50.00"));
+
+    let response = commands::exec_line(ctx.gctx_mut(), "status").unwrap();
+    let Some(ResponseContent::Status { summary, .. }) = response.content() else {
+        panic!("expected a status");
+    };
+    assert_eq!(summary.limits.len(), 2);
+    assert!(summary.limits.iter().all(|limit| limit.spent == 55.0));
+    assert_eq!(summary.limits.iter().filter(|limit| limit.is_over()).count(), 1);
+}
+
+#[test]
+fn test_alerts_cover_budgets_bills_and_limits() {
+    use fintrack::utils::{alerts, storage::load_tracker};
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    let in_two_days = (ctx.gctx.today() + chrono::Days::new(2)).format("%d-%m-%Y").to_string();
+    let in_a_month = (ctx.gctx.today() + chrono::Days::new(30)).format("%d-%m-%Y").to_string();
+    commands::exec_line(ctx.gctx_mut(), &format!("bill add Rent 500 --due {}", in_two_days)).unwrap();
+    commands::exec_line(ctx.gctx_mut(), &format!("bill add Water 20 --due {}", in_a_month)).unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set Food 50").unwrap();
+
+    let tracker_data = load_tracker(&ctx.gctx).unwrap();
+    let titles: Vec<String> = alerts::current(&ctx.gctx, &tracker_data).into_iter().map(|a| a.title).collect();
+    assert_eq!(titles, vec!["Bill due: Rent"]);
+
+    commands::exec_line(ctx.gctx_mut(), "config set limits.daily 40").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 60 -s Food").unwrap();
+
+    let tracker_data = load_tracker(&ctx.gctx).unwrap();
+    let alerts = alerts::current(&ctx.gctx, &tracker_data);
+    let titles: Vec<&str> = alerts.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!(titles, vec!["Over budget: Food", "Bill due: Rent", "Over your daily limit"]);
+    assert_eq!(alerts[0].body, "Spent 60.00 USD of 50.00 USD this month, 10.00 USD over");
+}
+
+#[test]
+fn test_subscriptions_link_payments_and_total_monthly_cost() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+
+    commands::exec_line(ctx.gctx_mut(), "add expenses 15.49 -d 'Netflix September' --date 20-09-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subscriptions add Netflix 15.49 --renews 20-09-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subscriptions add Cloud 120 --every year --renews 01-03-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 15.49 -d 'netflix october' --date 20-10-2025").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30 -d 'Dinner'").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "subscriptions list").unwrap();
+    let Some(ResponseContent::Subscriptions { subscriptions, .. }) = response.content() else {
+        panic!("Expected Subscriptions response");
+    };
+
+    assert_eq!(subscriptions.len(), 2);
+    let netflix = subscriptions.iter().find(|s| s.name == "Netflix").unwrap();
+    assert_eq!(netflix.payments, 2);
+    assert_eq!(netflix.last_paid.as_deref(), Some("20-10-2025"));
+    let cloud = subscriptions.iter().find(|s| s.name == "Cloud").unwrap();
+    assert_eq!(cloud.payments, 0);
+    assert_eq!(cloud.monthly_cost, 10.0);
+    assert!(subscriptions.iter().all(|s| s.days >= 0));
+
+    let err = commands::exec_line(ctx.gctx_mut(), "subscriptions delete spotify").unwrap_err();
+    assert_eq!(err.code(), "E034");
+}
+
+#[test]
+fn test_chart_balance_writes_svg() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    let out = ctx.gctx.base_path().join("balance.svg");
+    let chart = format!("chart balance --from 01-01-2024 --out {}", out.display());
+
+    let err = commands::exec_line(ctx.gctx_mut(), "chart balance --out balance.gif").unwrap_err();
+    assert!(matches!(err, CliError::Other(message) if message.contains(".svg or .png")));
+
+    commands::exec_line(ctx.gctx_mut(), "add income 1000 --date 01-01-2024").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 300 --date 15-01-2024").unwrap();
+    commands::exec_line(ctx.gctx_mut(), &chart).unwrap();
+
+    let svg = std::fs::read_to_string(&out).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("Balance (USD)"));
+    assert!(svg.contains("15-01-2024"));
+    assert!(svg.contains("<polyline"));
+}
+
+#[test]
+fn test_merge_adds_records_and_subcategories_skipping_duplicates() {
+    let mut other = TestContext::new();
+    commands::exec_line(other.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(other.gctx_mut(), "subcategory add travel").unwrap();
+    commands::exec_line(other.gctx_mut(), "add expenses 300 -s travel -d Flight --date 02-10-2026").unwrap();
+    commands::exec_line(other.gctx_mut(), "add expenses 12 -d Lunch --date 03-10-2026").unwrap();
+    commands::exec_line(other.gctx_mut(), "budget set travel 500").unwrap();
+    commands::exec_line(other.gctx_mut(), "budget set miscellaneous 80").unwrap();
+    let other_path = other.gctx.tracker_path();
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 12 -d Lunch --date 03-10-2026").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set miscellaneous 100").unwrap();
+
+    let merge = |ctx: &mut TestContext, flags: &str| {
+        let line = format!("merge {} {}", other_path.display(), flags);
+        match commands::exec_line(ctx.gctx_mut(), &line).unwrap().content() {
+            Some(ResponseContent::Merge(summary)) => summary.clone(),
+            other => panic!("Expected a merge summary, got {:?}", other),
+        }
+    };
+
+    let preview = merge(&mut ctx, "--dry-run --budgets theirs");
+    assert!(preview.dry_run);
+    assert_eq!(preview.records_added, 1);
+    assert_eq!(preview.duplicates_skipped, 1);
+    assert_eq!(preview.subcategories_added, vec!["Travel".to_string()]);
+    assert_eq!(preview.budgets_replaced, vec!["miscellaneous".to_string()]);
+    assert_eq!(fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap().records.len(), 1);
+
+    let summary = merge(&mut ctx, "--budgets");
+    assert!(!summary.dry_run);
+    assert_eq!(summary.budgets_added, vec!["Travel".to_string()]);
+    assert!(summary.budgets_replaced.is_empty());
+
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.records.len(), 2);
+    let travel = tracker.subcategory_id("travel").unwrap();
+    let flight = tracker.records.iter().find(|r| r.description == "Flight").unwrap();
+    assert_eq!((flight.id, flight.subcategory), (2, travel));
+    let misc = tracker.subcategory_id("miscellaneous").unwrap();
+    let budget = tracker.budgets.iter().find(|b| b.subcategory == misc).unwrap();
+    assert_eq!(budget.amount, 100.0);
+
+    assert_eq!(merge(&mut ctx, "--duplicates keep").records_added, 2);
+
+    let mut euros = TestContext::new();
+    commands::exec_line(euros.gctx_mut(), "init -c eur").unwrap();
+    let err = commands::exec_line(euros.gctx_mut(), &format!("merge {}", other_path.display())).unwrap_err();
+    assert!(matches!(err, CliError::Other(message) if message.contains("USD")));
+}
+
+#[test]
+fn test_plan_export_and_import_carry_setup_without_records() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add rent").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 40 -s food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set food 300").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "schedule add expenses 900 -s rent --start 1 -d Flat").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "config set limits.weekly 150").unwrap();
+    let plan = ctx.gctx.base_path().join("plan.toml");
+    commands::exec_line(ctx.gctx_mut(), &format!("plan export {}", plan.display())).unwrap();
+    assert!(!fs::read_to_string(&plan).unwrap().contains("records"));
+
+    let mut other = TestContext::new();
+    commands::exec_line(other.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(other.gctx_mut(), "subcategory add food").unwrap();
+    commands::exec_line(other.gctx_mut(), "budget set food 100").unwrap();
+    let import = format!("plan import {}", plan.display());
+    commands::exec_line(other.gctx_mut(), &import).unwrap();
+    commands::exec_line(other.gctx_mut(), &import).unwrap();
+
+    let tracker = fintrack::utils::storage::load_tracker(&other.gctx).unwrap();
+    assert!(tracker.records.is_empty());
+    assert!(tracker.subcategory_id("rent").is_some());
+    assert_eq!(tracker.budgets.len(), 1);
+    assert_eq!(tracker.budgets[0].amount, 300.0);
+    assert_eq!(tracker.schedules.len(), 1);
+    assert_eq!(tracker.schedules[0].subcategory, "Rent");
+    assert_eq!(tracker.schedules[0].day, 1);
+    assert_eq!(other.gctx.config().limits.weekly, Some(150.0));
+
+    fs::write(&plan, "version = 1\ncurrency = \"USD\"\n[[budgets]]\nsubcategory = \"Fun\"\namount = -5\n").unwrap();
+    let err = commands::exec_line(other.gctx_mut(), &import).unwrap_err();
+    assert!(matches!(err, CliError::Other(message) if message.contains("Fun")));
+    let tracker = fintrack::utils::storage::load_tracker(&other.gctx).unwrap();
+    assert!(tracker.subcategory_id("fun").is_none());
+}
+
+#[test]
+fn test_unknown_subcategory_suggests_nearest_and_create_adds_it() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add groceries").unwrap();
+
+    let nearest = |err: CliError| match err {
+        CliError::ValidationError(ValidationErrorKind::SubcategoryNotFound { nearest, .. }) => nearest,
+        other => panic!("Expected SubcategoryNotFound, got {:?}", other),
+    };
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 5 -s grocery").unwrap_err();
+    assert_eq!(nearest(err).as_deref(), Some("Groceries"));
+    let err = commands::exec_line(ctx.gctx_mut(), "list -s grocerys").unwrap_err();
+    assert_eq!(nearest(err).as_deref(), Some("Groceries"));
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 5 -s holiday").unwrap_err();
+    assert_eq!(nearest(err), None);
+
+    commands::exec_line(ctx.gctx_mut(), "add expenses 5 -s holiday --create").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "update 1 -s travel --create").unwrap();
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert!(tracker.subcategory_id("holiday").is_some());
+    let travel = tracker.subcategory_id("travel").unwrap();
+    assert_eq!(tracker.subcategory_name(travel).unwrap(), "Travel");
+    assert_eq!(tracker.records[0].subcategory, travel);
+
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 5 -s 9lives --create").unwrap_err();
+    assert_eq!(err.code(), "E019");
+}
+
+#[test]
+fn test_foreign_currency_records_get_a_breakdown_in_total() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100 -D 01-10-2026").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 50 --currency EUR --rate 1.08 -D 03-10-2026").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 10 --currency eur --rate 1.08 -D 05-10-2026").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 --currency usd --rate 2").unwrap();
+
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.records[1].amount, 54.0);
+    let foreign = tracker.records[1].foreign.as_ref().unwrap();
+    assert_eq!((foreign.currency.as_str(), foreign.amount), ("EUR", 50.0));
+    assert_eq!(tracker.records[3].amount, 20.0);
+    assert!(tracker.records[3].foreign.is_none());
+
+    let response = commands::exec_line(ctx.gctx_mut(), "total").unwrap();
+    match response.content() {
+        Some(ResponseContent::Total(total)) => {
+            assert_eq!(total.expenses_total, 84.8);
+            let currencies: Vec<&str> = total.by_currency.iter().map(|c| c.currency.as_str()).collect();
+            assert_eq!(currencies, ["USD", "EUR"]);
+            let euros = &total.by_currency[1];
+            assert_eq!((euros.records, euros.expenses, euros.converted_expenses), (2, 60.0, 64.8));
+            assert_eq!(euros.rates.len(), 1);
+            assert_eq!((euros.rates[0].from.as_str(), euros.rates[0].to.as_str()), ("03-10-2026", "05-10-2026"));
+        }
+        other => panic!("Expected Total, got {:?}", other),
+    }
+
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 5 --currency eur --rate 0").unwrap_err();
+    assert_eq!(err.exit_code(), exit_code::INVALID_INPUT);
+}
+
+#[test]
+fn test_add_in_another_currency_uses_the_configured_rates() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    assert!(commands::exec_line(ctx.gctx_mut(), "add expenses 50 --currency eur").is_err());
+
+    commands::exec_line(ctx.gctx_mut(), "config set rates.manual '{ EUR = 1.08, GBP = 1.25 }'").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 50 --currency eur").unwrap();
+    match response.content() {
+        Some(ResponseContent::Record { record, .. }) => {
+            assert_eq!(record.amount, 54.0);
+            assert_eq!(record.foreign.as_ref().unwrap().rate, 1.08);
+        }
+        other => panic!("Expected Record, got {:?}", other),
+    }
+    assert!(commands::exec_line(ctx.gctx_mut(), "config set rates.manual '{ EUR = 0 }'").is_err());
+
+    // Fetched rates are cached, and only the cache is used offline
+    commands::exec_line(ctx.gctx_mut(), "config set rates.provider ecb").unwrap();
+    ctx.gctx.set_offline(true);
+    let err = commands::exec_line(ctx.gctx_mut(), "add expenses 10 --currency gbp -D 01-10-2026").unwrap_err();
+    assert!(matches!(err, CliError::Other(message) if message.contains("--offline")));
+    let cache = r#"{ "ECB:GBP:USD:2026-10-01": { "rate": 1.3, "fetched_at": "2026-10-01T12:00:00+00:00" } }"#;
+    std::fs::write(ctx.gctx.rates_path(), cache).unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 10 --currency gbp -D 01-10-2026").unwrap();
+    match response.content() {
+        Some(ResponseContent::Record { record, .. }) => assert_eq!(record.amount, 13.0),
+        other => panic!("Expected Record, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_status_counts_down_to_payday() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 700").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "status").unwrap();
+    match response.content() {
+        Some(ResponseContent::Status { summary, .. }) => assert_eq!(summary.payday, None),
+        other => panic!("Expected Status, got {:?}", other),
+    }
+
+    let day = chrono::Datelike::day(&(ctx.gctx.today() + chrono::Days::new(7)));
+    commands::exec_line(ctx.gctx_mut(), &format!("config set payday.day {}", day)).unwrap();
+    commands::exec_line(ctx.gctx_mut(), "config set payday.weekend none").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "status").unwrap();
+    match response.content() {
+        Some(ResponseContent::Status { summary, .. }) => {
+            let payday = summary.payday.as_ref().unwrap();
+            assert_eq!(payday.days, 7);
+            assert_eq!(payday.per_day, Some(100.0));
+        }
+        other => panic!("Expected Status, got {:?}", other),
+    }
+
+    assert!(commands::exec_line(ctx.gctx_mut(), "config set payday.day 32").is_err());
+    assert!(commands::exec_line(ctx.gctx_mut(), "config set payday.holidays '[\"2026-12-25\"]'").is_err());
+}
+
+#[test]
+fn test_add_without_subcategory_hints_at_a_similar_records_subcategory() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add groceries").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add transport").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 -s groceries -d \"Tesco weekly shop\"").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 5 -s transport -d \"Uber to work\"").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 30 -d \"weekly shop at Tesco\"").unwrap();
+    assert!(response.warnings().iter().any(|w| w.contains("Groceries") && w.contains("update 3")));
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 3 -d \"bus ticket\"").unwrap();
+    assert!(response.warnings().is_empty());
+    // An explicit subcategory is never second-guessed, nor is income
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 4 -s miscellaneous -d \"uber\"").unwrap();
+    assert!(response.warnings().is_empty());
+    let response = commands::exec_line(ctx.gctx_mut(), "add income 4 -d \"uber refund\"").unwrap();
+    assert!(response.warnings().is_empty());
+
+    // Without anyone to ask, the record stays in Miscellaneous
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(Some(tracker.records[2].subcategory), tracker.miscellaneous_subcategory_id());
+}
+
+#[test]
+#[cfg(unix)]
+fn test_edit_file_replaces_the_tracker_only_when_the_edit_is_sound() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 5 -d coffee").unwrap();
+    let edit = |ctx: &mut TestContext, editor: &str| {
+        let args = commands::edit_file::cli().get_matches_from(["edit-file", "--editor", editor]);
+        commands::edit_file::exec(ctx.gctx_mut(), &args)
+    };
+    let edit_path = ctx.gctx.base_path().join("tracker.edit.json");
+
+    edit(&mut ctx, "sed -i s/coffee/tea/").unwrap();
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.records[0].description, "tea");
+    assert!(!edit_path.exists());
+
+    let err = edit(&mut ctx, "sed -i s/miscellaneous/other/").unwrap_err();
+    assert!(matches!(err, CliError::Other(message) if message.contains("miscellaneous subcategory is missing")));
+    assert!(edit_path.exists());
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert!(tracker.miscellaneous_subcategory_id().is_some());
+
+    // The kept edit is picked up again until it's fixed or thrown away
+    assert!(edit(&mut ctx, "true").is_err());
+    assert!(edit(&mut ctx, "false").is_err());
+    assert!(edit_path.exists());
+    let args = commands::edit_file::cli().get_matches_from(["edit-file", "--editor", "true", "--fresh"]);
+    commands::edit_file::exec(ctx.gctx_mut(), &args).unwrap();
+    assert!(!edit_path.exists());
+
+    assert!(edit(&mut ctx, "false").is_err());
+    assert!(!edit_path.exists());
+}
+
+#[test]
+fn test_import_csv_reads_statements_and_stops_at_bad_rows() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 1 -D 01-09-2026").unwrap();
+    let statement = ctx.gctx.base_path().join("statement.csv");
+    fs::write(
+        &statement,
+        "Date,Description,Amount,Subcategory\n01-10-2026,Salary,2500,\n02-10-2026,\"Coffee, large\",-4.5,Food\n03-10-2026,Oops,abc,\n",
+    )
+    .unwrap();
+    let import = format!("import {}", statement.display());
+
+    let err = commands::exec_line(ctx.gctx_mut(), &import).unwrap_err();
+    assert!(matches!(err, CliError::Other(message) if message.contains("line 3") && message.contains("'Food'")));
+    let err = commands::exec_line(ctx.gctx_mut(), &format!("{} --create", import)).unwrap_err();
+    assert!(matches!(err, CliError::Other(message) if message.contains("line 4")));
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.records.len(), 1);
+    assert!(tracker.subcategory_id("food").is_none());
+
+    let response = commands::exec_line(ctx.gctx_mut(), &format!("{} --create --skip-invalid", import)).unwrap();
+    assert_eq!(response.warnings().len(), 1);
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.records.len(), 3);
+    assert_eq!(tracker.next_record_id, 4);
+    let coffee = tracker.records.iter().find(|r| r.description == "Coffee, large").unwrap();
+    assert_eq!(coffee.amount, 4.5);
+    assert_eq!(coffee.category, tracker.category_id("expenses"));
+    assert_eq!(Some(coffee.subcategory), tracker.subcategory_id("food"));
+    let salary = tracker.records.iter().find(|r| r.description == "Salary").unwrap();
+    assert_eq!(salary.category, tracker.category_id("income"));
+}
+
+#[test]
+fn test_digest_compares_the_week_and_flags_unusual_expenses() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    let today = ctx.gctx.today();
+    let days_ago = |days: u64| (today - chrono::Days::new(days)).format("%d-%m-%Y").to_string();
+
+    for days in [20, 30, 40] {
+        let line = format!("add expenses 10 -s food -D {}", days_ago(days));
+        commands::exec_line(ctx.gctx_mut(), &line).unwrap();
+    }
+    let line = format!("add expenses 40 -s food -D {}", days_ago(7));
+    commands::exec_line(ctx.gctx_mut(), &line).unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 12 -s food -d Lunch").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 48 -s food -d 'Birthday dinner'").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 500").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "digest --week").unwrap();
+    match response.content() {
+        Some(ResponseContent::Digest(digest)) => {
+            assert_eq!(digest.spent, 60.0);
+            assert_eq!(digest.last_week, 40.0);
+            assert_eq!(digest.change(), Some(50.0));
+            assert_eq!(digest.top, vec![("Food".to_string(), 60.0)]);
+            assert_eq!(digest.unusual.len(), 1);
+            assert_eq!(digest.unusual[0].description, "Birthday dinner");
+            assert_eq!(digest.unusual[0].typical, 10.0);
+        }
+        other => panic!("Expected Digest, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_tracker_file_points_a_command_at_another_tracker() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100").unwrap();
+    let real = fs::read(ctx.gctx.tracker_path()).unwrap();
+    let backups = fs::read_dir(ctx.gctx.backups_path()).unwrap().count();
+
+    let demo = ctx.temp_dir.path().join("demo.json");
+    fs::copy(ctx.gctx.tracker_path(), &demo).unwrap();
+    ctx.gctx.set_tracker_file(demo.clone());
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30").unwrap();
+
+    let demo_tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(demo_tracker.records.len(), 2);
+    assert_eq!(fs::read(ctx.gctx.base_path().join("tracker.json")).unwrap(), real);
+    assert_eq!(fs::read_dir(ctx.gctx.backups_path()).unwrap().count(), backups);
+
+    ctx.gctx.set_tracker_file(ctx.temp_dir.path().join("missing.json"));
+    let err = commands::exec_line(ctx.gctx_mut(), "list").unwrap_err();
+    assert!(matches!(err, CliError::FileNotFound(_)));
+}
+
+#[test]
+fn test_reconcile_reports_drift_and_adds_an_adjustment() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "reconcile --statement-balance 65.5").unwrap();
+    match response.content() {
+        Some(ResponseContent::Reconciliation(reconciliation)) => {
+            assert_eq!(reconciliation.recorded, 70.0);
+            assert_eq!(reconciliation.drift, -4.5);
+            assert_eq!(reconciliation.adjustment, None);
+        }
+        other => panic!("Expected Reconciliation, got {:?}", other),
+    }
+
+    commands::exec_line(ctx.gctx_mut(), "reconcile --statement-balance 65.5 --adjust").unwrap();
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.net_balance(), 65.5);
+
+    let response = commands::exec_line(ctx.gctx_mut(), "reconcile --statement-balance 65.5").unwrap();
+    match response.content() {
+        Some(ResponseContent::Reconciliation(reconciliation)) => assert_eq!(reconciliation.drift, 0.0),
+        other => panic!("Expected Reconciliation, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_category_changes_are_refused_as_immutable() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+
+    for line in ["category add Savings", "category delete income", "category rename expenses Spending"] {
+        let err = commands::exec_line(ctx.gctx_mut(), line).unwrap_err();
+        assert_eq!(err.code(), "E017");
+        assert_eq!(err.exit_code(), exit_code::CONFLICT);
+    }
+    let err = commands::exec_line(ctx.gctx_mut(), "category add Savings").unwrap_err();
+    assert!(matches!(
+        err,
+        CliError::ValidationError(ValidationErrorKind::CategoryImmutable { category }) if category == "Savings"
+    ));
+}
+
+#[test]
+fn test_list_total_and_delete_share_the_record_filters() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 5 -d \"Milk and bread\"").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 50 -d \"Weekly shop\"").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100 -d \"Bread sale\"").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "list --text BREAD --max-amount 10").unwrap();
+    let Some(ResponseContent::List { records, .. }) = response.content() else {
+        panic!("Expected List, got {:?}", response.content());
+    };
+    assert_eq!(records.iter().map(|r| r.id).collect::<Vec<_>>(), vec![1]);
+
+    let response = commands::exec_line(ctx.gctx_mut(), "total -c expenses --min-amount 10").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("Expected Total, got {:?}", response.content());
+    };
+    assert_eq!((total.income_total, total.expenses_total), (0.0, 50.0));
+
+    let err = commands::exec_line(ctx.gctx_mut(), "delete").unwrap_err();
+    assert!(matches!(err, CliError::Other(message) if message.contains("--ids")));
+
+    commands::exec_line(ctx.gctx_mut(), "delete --text bread").unwrap();
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.records.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2]);
+}
+
+#[test]
+fn test_delete_by_date_range_and_where() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 5 -D 31-08-2026").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 10 -D 01-09-2026").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 -D 30-09-2026 -d \"Fish and chips\"").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100 -D 01-10-2026").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "delete --start 01-09-2026 --end 30-09-2026 --max-amount 15").unwrap();
+    assert!(matches!(response.content(), Some(ResponseContent::Deleted { count: 1, .. })));
+
+    let err = commands::exec_line(ctx.gctx_mut(), "delete --where \"amount >\"").unwrap_err();
+    assert!(matches!(err, CliError::Other(message) if message.contains("isn't a condition")));
+
+    commands::exec_line(
+        ctx.gctx_mut(),
+        "delete --where \"description ~ 'fish and chips' and date >= 01-09-2026 and amount < 100\"",
+    )
+    .unwrap();
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert_eq!(tracker.records.iter().map(|r| r.id).collect::<Vec<_>>(), vec![1, 4]);
+}
+
+#[test]
+fn test_dry_run_shows_the_changes_without_saving_them() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30 -d Lunch").unwrap();
+    let before = fs::read_to_string(ctx.gctx.tracker_path()).unwrap();
+
+    ctx.gctx_mut().set_dry_run(true);
+    let response = commands::exec_line(ctx.gctx_mut(), "update 2 -a 40").unwrap();
+    let Some(ResponseContent::DryRun(diff)) = response.content() else {
+        panic!("Expected DryRun, got {:?}", response.content());
+    };
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!((diff.changed[0].0.amount, diff.changed[0].1.amount), (30.0, 40.0));
+    assert_eq!((diff.before.net_balance(), diff.after.net_balance()), (70.0, 60.0));
+
+    let response = commands::exec_line(ctx.gctx_mut(), "delete -i 1").unwrap();
+    let Some(ResponseContent::DryRun(diff)) = response.content() else {
+        panic!("Expected DryRun, got {:?}", response.content());
+    };
+    assert_eq!(diff.removed.iter().map(|r| r.id).collect::<Vec<_>>(), vec![1]);
+    assert!(diff.added.is_empty() && diff.changed.is_empty() && !diff.other_changes);
+
+    let response = commands::exec_line(ctx.gctx_mut(), "subcategory add Rent").unwrap();
+    assert!(matches!(
+        response.content(),
+        Some(ResponseContent::DryRun(diff)) if diff.subcategories_added == ["Rent"] && !diff.other_changes
+    ));
+
+    assert_eq!(fs::read_to_string(ctx.gctx.tracker_path()).unwrap(), before);
+}
+
+#[test]
+fn test_dry_run_leaves_settings_and_tokens_alone() {
+    use fintrack::utils::tokens;
+
+    // `serve` isn't allowed through exec_line, which is for interactive use
+    fn token(ctx: &mut TestContext, args: &[&str]) -> CliResult {
+        let matches = commands::serve::cli().get_matches_from([&["serve", "token"], args].concat());
+        commands::serve::exec(ctx.gctx_mut(), &matches)
+    }
+    fn is_dry_run(response: CliResponse) -> bool {
+        matches!(response.content(), Some(ResponseContent::Message(message)) if message.starts_with("Dry run"))
+    }
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "config set general.user ada").unwrap();
+    token(&mut ctx, &["create", "grafana"]).unwrap();
+    let settings = ctx.temp_dir.path().join("settings.toml");
+    commands::exec_line(ctx.gctx_mut(), &format!("config export {}", settings.display())).unwrap();
+    let config = fs::read_to_string(ctx.gctx.config_path()).unwrap();
+
+    ctx.gctx_mut().set_dry_run(true);
+    for line in [
+        "config set general.timezone UTC".to_string(),
+        "config unset general.user".to_string(),
+        format!("config import {}", settings.display()),
+    ] {
+        assert!(is_dry_run(commands::exec_line(ctx.gctx_mut(), &line).unwrap()), "{}", line);
+    }
+    assert!(is_dry_run(token(&mut ctx, &["create", "prometheus"]).unwrap()));
+    assert!(is_dry_run(token(&mut ctx, &["revoke", "grafana"]).unwrap()));
+    // Values are still checked
+    assert!(commands::exec_line(ctx.gctx_mut(), "config set general.timezone Nowhere/Else").is_err());
+
+    assert_eq!(fs::read_to_string(ctx.gctx.config_path()).unwrap(), config);
+    let names: Vec<String> = tokens::load(&ctx.gctx).unwrap().into_iter().map(|token| token.name).collect();
+    assert_eq!(names, ["grafana"]);
+}
+
+#[test]
+fn test_diff_compares_the_tracker_with_another_file() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 100").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    let snapshot = ctx.temp_dir.path().join("snapshot.json");
+    fs::copy(ctx.gctx.tracker_path(), &snapshot).unwrap();
+
+    commands::exec_line(ctx.gctx_mut(), "update 2 -a 40").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "delete -i 1").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 5").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory rename Food Groceries").unwrap();
+
+    let line = format!("diff {}", snapshot.display());
+    let response = commands::exec_line(ctx.gctx_mut(), &line).unwrap();
+    let Some(ResponseContent::Diff { reverse: false, diff, .. }) = response.content() else {
+        panic!("Expected Diff, got {:?}", response.content());
+    };
+    assert_eq!(diff.added.iter().map(|r| r.id).collect::<Vec<_>>(), vec![3]);
+    assert_eq!(diff.removed.iter().map(|r| r.id).collect::<Vec<_>>(), vec![1]);
+    assert_eq!((diff.changed[0].0.amount, diff.changed[0].1.amount), (30.0, 40.0));
+    assert_eq!(diff.subcategories_renamed, vec![("Food".to_string(), "Groceries".to_string())]);
+
+    let response = commands::exec_line(ctx.gctx_mut(), &format!("{} --reverse", line)).unwrap();
+    let Some(ResponseContent::Diff { reverse: true, diff, .. }) = response.content() else {
+        panic!("Expected Diff, got {:?}", response.content());
+    };
+    assert_eq!(diff.added.iter().map(|r| r.id).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(diff.after.net_balance(), 70.0);
+}
+
+#[test]
+fn test_schema_is_published_and_checks_other_tracker_files() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 30").unwrap();
+
+    let response = commands::exec_line(ctx.gctx_mut(), "schema").unwrap();
+    let Some(ResponseContent::Schema(schema)) = response.content() else {
+        panic!("Expected Schema, got {:?}", response.content());
+    };
+    assert_eq!(schema["title"], "TrackerData");
+    assert!(schema["required"].as_array().unwrap().contains(&"currency".into()));
+
+    let mut tracker: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(ctx.gctx.tracker_path()).unwrap()).unwrap();
+    tracker.as_object_mut().unwrap().remove("checksum");
+    tracker["records"][0]["amount"] = "thirty".into();
+    let other = ctx.temp_dir.path().join("other.json");
+    fs::write(&other, tracker.to_string()).unwrap();
+
+    let err = commands::exec_line(ctx.gctx_mut(), &format!("diff {}", other.display())).unwrap_err();
+    let CliError::InvalidField { path, expected, .. } = err else {
+        panic!("Expected InvalidField, got {:?}", err);
+    };
+    assert_eq!(path, "records[0].amount");
+    assert_eq!(expected.as_deref(), Some("a number"));
+}
+
+#[test]
+fn test_tag_list_rename_and_delete() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init -c usd").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 100 --tag Vacation,beach").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 --tag vacation").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 5 --tag holiday").unwrap();
+
+    let tags = |ctx: &mut TestContext| {
+        let response = commands::exec_line(ctx.gctx_mut(), "tag list").unwrap();
+        let Some(ResponseContent::Tags(tags)) = response.content() else {
+            panic!("Expected Tags, got {:?}", response.content());
+        };
+        tags.clone()
+    };
+    assert_eq!(
+        tags(&mut ctx),
+        vec![("Vacation".to_string(), 2), ("beach".to_string(), 1), ("holiday".to_string(), 1)]
+    );
+
+    commands::exec_line(ctx.gctx_mut(), "tag rename holiday VACATION").unwrap();
+    assert_eq!(tags(&mut ctx), vec![("Vacation".to_string(), 3), ("beach".to_string(), 1)]);
+    let response = commands::exec_line(ctx.gctx_mut(), "list --tag vacation").unwrap();
+    let Some(ResponseContent::List { records, .. }) = response.content() else {
+        panic!("Expected List, got {:?}", response.content());
+    };
+    assert_eq!(records.len(), 3);
+
+    let err = commands::exec_line(ctx.gctx_mut(), "tag delete beach").unwrap_err();
+    assert_eq!(err.code(), "E037");
+    commands::exec_line(ctx.gctx_mut(), "tag delete beach --from-records").unwrap();
+    assert_eq!(tags(&mut ctx), vec![("Vacation".to_string(), 3)]);
+    let tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    assert!(tracker.records.iter().all(|r| r.tags == ["Vacation"]));
+    assert_eq!(commands::exec_line(ctx.gctx_mut(), "tag delete nope").unwrap_err().code(), "E036");
+}
+
+#[test]
+fn test_total_and_describe_group_by_tag() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 100 --tag Kitchen").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 20 -s Food --tag kitchen,vacation").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add income 50 --tag vacation").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "add expenses 7").unwrap();
+
+    // A record counts under each of its tags; untagged ones are left out
+    let response = commands::exec_line(ctx.gctx_mut(), "total --by tag").unwrap();
+    let Some(ResponseContent::Total(total)) = response.content() else {
+        panic!("expected totals");
+    };
+    assert_eq!(total.by, Some(GroupBy::Tag));
+    assert_eq!(
+        total.groups,
+        vec![
+            GroupTotal { name: "vacation".to_string(), income: 50.0, expenses: 20.0, net: 30.0 },
+            GroupTotal { name: "Kitchen".to_string(), income: 0.0, expenses: 120.0, net: -120.0 },
+        ]
+    );
+
+    let response = commands::exec_line(ctx.gctx_mut(), "describe").unwrap();
+    let Some(ResponseContent::Describe(data)) = response.content() else {
+        panic!("Expected Describe response");
+    };
+    assert!(data.by_tag.is_empty());
+
+    let response = commands::exec_line(ctx.gctx_mut(), "describe --by tag").unwrap();
+    let Some(ResponseContent::Describe(data)) = response.content() else {
+        panic!("Expected Describe response");
+    };
+    assert_eq!(
+        data.by_tag,
+        vec![("Kitchen".to_string(), 2, 120.0), ("vacation".to_string(), 2, 70.0)]
+    );
+}
+
+#[test]
+fn test_budget_rollover_carries_leftovers_and_overspend() {
+    use chrono::{Datelike, Months};
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set food 100 --rollover").unwrap();
+
+    // Pretend rollover was turned on two months ago
+    let month_start = ctx.gctx.today().with_day(1).unwrap();
+    let months_ago = |n: u32| (month_start - Months::new(n)).format("%d-%m-%Y").to_string();
+    let mut tracker = fintrack::utils::storage::load_tracker(&ctx.gctx).unwrap();
+    tracker.budgets[0].since = Some(months_ago(2));
+    fintrack::utils::storage::save_tracker(ctx.gctx_mut(), &tracker).unwrap();
+
+    let add = |ctx: &mut TestContext, amount: u32, date: String| {
+        commands::exec_line(ctx.gctx_mut(), &format!("add expenses {} -s Food -D {}", amount, date)).unwrap();
+    };
+    add(&mut ctx, 500, months_ago(3));
+    add(&mut ctx, 30, months_ago(2));
+    add(&mut ctx, 150, months_ago(1));
+    add(&mut ctx, 10, months_ago(0));
+
+    let budgets = |ctx: &mut TestContext| {
+        let response = commands::exec_line(ctx.gctx_mut(), "budget list").unwrap();
+        let Some(ResponseContent::Budgets { budgets, .. }) = response.content() else {
+            panic!("expected budgets");
+        };
+        budgets.clone()
+    };
+    // 70 left two months ago, 50 over last month
+    assert_eq!(
+        budgets(&mut ctx),
+        vec![BudgetStatus { subcategory: "Food".to_string(), budget: 100.0, carried: 20.0, spent: 10.0, remaining: 110.0, level: BudgetLevel::Ok }]
+    );
+
+    // Changing the amount keeps rollover on
+    commands::exec_line(ctx.gctx_mut(), "budget set food 120").unwrap();
+    assert_eq!(budgets(&mut ctx)[0].carried, 60.0);
+
+    commands::exec_line(ctx.gctx_mut(), "budget set food 120 --no-rollover").unwrap();
+    assert_eq!(budgets(&mut ctx)[0].carried, 0.0);
+    assert_eq!(budgets(&mut ctx)[0].remaining, 110.0);
+}
+
+#[test]
+fn test_budget_thresholds_set_warnings_and_levels() {
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "subcategory add Food").unwrap();
+    commands::exec_line(ctx.gctx_mut(), "budget set food 200 --warn-at 80%").unwrap();
+
+    let level = |ctx: &mut TestContext| {
+        let response = commands::exec_line(ctx.gctx_mut(), "budget list").unwrap();
+        let Some(ResponseContent::Budgets { budgets, .. }) = response.content() else {
+            panic!("expected budgets");
+        };
+        budgets[0].level
+    };
+
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 150 -s Food").unwrap();
+    assert!(response.warnings().is_empty());
+    assert_eq!(level(&mut ctx), BudgetLevel::Ok);
+
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 20 -s Food").unwrap();
+    assert_eq!(response.warnings().len(), 1);
+    assert!(response.warnings()[0].contains("Food budget") && response.warnings()[0].contains("85% spent"));
+    assert_eq!(level(&mut ctx), BudgetLevel::Warn);
+
+    // Lowering the error threshold keeps the warning one
+    commands::exec_line(ctx.gctx_mut(), "budget set food 200 --error-at 90").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 10 -s Food").unwrap();
+    assert_eq!(level(&mut ctx), BudgetLevel::Error);
+    assert!(response.warnings()[0].contains("90% spent"));
+
+    let response = commands::exec_line(ctx.gctx_mut(), "add expenses 50 -s Food").unwrap();
+    assert!(response.warnings()[0].starts_with("Over the Food budget"));
+    // Records added for the user are checked too
+    let response = commands::exec_line(ctx.gctx_mut(), "quick snack 5 -s food").unwrap();
+    assert!(response.warnings()[0].starts_with("Over the Food budget"));
+    commands::exec_line(ctx.gctx_mut(), "bill add Groceries 30 --due 01-01-2025 -s food").unwrap();
+    let response = commands::exec_line(ctx.gctx_mut(), "bill pay groceries").unwrap();
+    assert!(response.warnings()[0].starts_with("Over the Food budget"));
+    // Income never warns
+    let response = commands::exec_line(ctx.gctx_mut(), "add income 50 -s Food").unwrap();
+    assert!(response.warnings().is_empty());
+
+    assert!(commands::exec_line(ctx.gctx_mut(), "budget set food 200 --warn-at 95").is_err());
+}
+
+#[test]
+fn test_batch_runs_post_hooks_only_once_saved() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut ctx = TestContext::new();
+    commands::exec_line(ctx.gctx_mut(), "init").unwrap();
+    let hooks = ctx.gctx.hooks_path().clone();
+    fs::create_dir_all(&hooks).unwrap();
+    let hook = hooks.join("post-add");
+    // Counts the records in the tracker file when the hook runs
+    fs::write(
+        &hook,
+        format!(
+            "#!/bin/sh\ngrep -o '\"amount\"' '{}' | wc -l >> \"$(dirname \"$0\")/added.log\"\n",
+            ctx.gctx.tracker_path().display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+    let log = hooks.join("added.log");
+    let script = ctx.temp_dir.path().join("script.txt");
+    let args = commands::batch::cli().get_matches_from(["batch", script.to_str().unwrap()]);
+
+    fs::write(&script, "add income 1\nadd expenses 3 -s Nope\n").unwrap();
+    commands::batch::exec(ctx.gctx_mut(), &args).unwrap_err();
+    assert!(!log.exists());
+
+    fs::write(&script, "add income 1\nadd income 2\n").unwrap();
+    commands::batch::exec(ctx.gctx_mut(), &args).unwrap();
+    let counts: Vec<String> = fs::read_to_string(&log).unwrap().split_whitespace().map(String::from).collect();
+    assert_eq!(counts, ["2", "2"]);
+}